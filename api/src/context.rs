@@ -20,7 +20,9 @@ use creditchain_config::config::{GasEstimationConfig, NodeConfig, RoleType};
 use creditchain_crypto::HashValue;
 use creditchain_gas_schedule::{CreditChainGasParameters, FromOnChainGasSchedule};
 use creditchain_logger::{error, info, Schema};
-use creditchain_mempool::{MempoolClientRequest, MempoolClientSender, SubmissionStatus};
+use creditchain_mempool::{
+    MempoolClientRequest, MempoolClientSender, SubmissionStatus, TransactionLifecycleEvent,
+};
 use creditchain_storage_interface::{
     state_store::state_view::db_state_view::{
         DbStateView, DbStateViewAtVersion, LatestDbStateCheckpointView,
@@ -990,6 +992,21 @@ impl Context {
         callback.await.map_err(anyhow::Error::from)
     }
 
+    pub async fn get_transaction_lifecycle_by_hash(
+        &self,
+        hash: HashValue,
+    ) -> Result<Option<Vec<TransactionLifecycleEvent>>> {
+        let (req_sender, callback) = oneshot::channel();
+
+        self.mp_sender
+            .clone()
+            .send(MempoolClientRequest::GetTransactionLifecycle(hash, req_sender))
+            .await
+            .map_err(anyhow::Error::from)?;
+
+        callback.await.map_err(anyhow::Error::from)
+    }
+
     pub fn get_transaction_by_version(
         &self,
         version: u64,
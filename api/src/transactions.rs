@@ -25,12 +25,17 @@ use creditchain_api_types::{
     transaction::TransactionSummary, verify_function_identifier, verify_module_identifier, Address,
     CreditChainError, CreditChainErrorCode, AsConverter, EncodeSubmissionRequest, GasEstimation,
     GasEstimationBcs, HashValue, HexEncodedBytes, LedgerInfo, MoveType, PendingTransaction,
-    SubmitTransactionRequest, Transaction, TransactionData, TransactionOnChainData,
+    SubmitTransactionRequest, Transaction, TransactionData, TransactionLifecycle,
+    TransactionLifecycleEvent, TransactionLifecycleState, TransactionOnChainData,
     TransactionsBatchSingleSubmissionFailure, TransactionsBatchSubmissionResult, UserTransaction,
     VerifyInput, VerifyInputWithRecursion, U64,
 };
 use creditchain_crypto::{hash::CryptoHash, signing_message};
 use creditchain_logger::error;
+use creditchain_mempool::{
+    TransactionLifecycleEvent as MempoolLifecycleEvent,
+    TransactionLifecycleState as MempoolLifecycleState,
+};
 use creditchain_types::{
     account_address::AccountAddress,
     mempool_status::MempoolStatusCode,
@@ -49,7 +54,11 @@ use poem_openapi::{
     payload::Json,
     ApiRequest, OpenApi,
 };
-use std::{cmp::min, sync::Arc, time::Duration};
+use std::{
+    cmp::min,
+    sync::Arc,
+    time::{Duration, UNIX_EPOCH},
+};
 
 generate_success_response!(SubmitTransactionResponse, (202, Accepted));
 
@@ -281,6 +290,66 @@ impl TransactionsApi {
         result
     }
 
+    /// Get transaction lifecycle by hash
+    ///
+    /// Retrieves the lifecycle of a transaction recently accepted by this node's mempool,
+    /// e.g., whether it is parked waiting for a missing sequence number, how many peers it
+    /// was broadcast to, and whether it was pulled by consensus, proposed, rejected by the
+    /// VM, evicted or expired. Each state is returned with the time it was entered.
+    ///
+    /// Note: only the states observed by this node are returned (e.g., fullnodes do not
+    /// observe consensus states), and lifecycles are only retained for a bounded number
+    /// of recent transactions.
+    #[oai(
+        path = "/transactions/by_hash/:txn_hash/lifecycle",
+        method = "get",
+        operation_id = "get_transaction_lifecycle_by_hash",
+        tag = "ApiTags::Transactions"
+    )]
+    async fn get_transaction_lifecycle_by_hash(
+        &self,
+        accept_type: AcceptType,
+        /// Hash of transaction to retrieve the lifecycle of
+        txn_hash: Path<HashValue>,
+    ) -> BasicResultWith404<TransactionLifecycle> {
+        fail_point_poem("endpoint_transaction_lifecycle_by_hash")?;
+        self.context
+            .check_api_output_enabled("Get transaction lifecycle by hash", &accept_type)?;
+
+        let hash = txn_hash.0;
+        let latest_ledger_info = self.context.get_latest_ledger_info()?;
+        let events = self
+            .context
+            .get_transaction_lifecycle_by_hash(hash.into())
+            .await
+            .context(format!("Failed to get transaction lifecycle by hash {}", hash))
+            .map_err(|err| {
+                BasicErrorWith404::internal_with_code(
+                    err,
+                    CreditChainErrorCode::InternalError,
+                    &latest_ledger_info,
+                )
+            })?
+            .ok_or_else(|| transaction_not_found_by_hash(hash, &latest_ledger_info))?;
+
+        let lifecycle = TransactionLifecycle {
+            hash,
+            events: events.into_iter().map(convert_lifecycle_event).collect(),
+        };
+        match accept_type {
+            AcceptType::Json => BasicResponse::try_from_json((
+                lifecycle,
+                &latest_ledger_info,
+                BasicResponseStatus::Ok,
+            )),
+            AcceptType::Bcs => BasicResponse::try_from_bcs((
+                lifecycle,
+                &latest_ledger_info,
+                BasicResponseStatus::Ok,
+            )),
+        }
+    }
+
     /// Get transaction by version
     ///
     /// Retrieves a transaction by a given version. If the version has been
@@ -1769,3 +1838,42 @@ enum GetByVersionResponse {
     VersionTooOld,
     Found(TransactionData),
 }
+
+/// Converts a mempool lifecycle event into its API representation
+fn convert_lifecycle_event(event: MempoolLifecycleEvent) -> TransactionLifecycleEvent {
+    let mut lifecycle_event = TransactionLifecycleEvent {
+        state: TransactionLifecycleState::Accepted,
+        timestamp: U64::from(
+            event
+                .timestamp
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_micros() as u64,
+        ),
+        expected_sequence_number: None,
+        num_peers: None,
+        vm_status: None,
+    };
+    lifecycle_event.state = match event.state {
+        MempoolLifecycleState::Accepted => TransactionLifecycleState::Accepted,
+        MempoolLifecycleState::Parked {
+            expected_sequence_number,
+        } => {
+            lifecycle_event.expected_sequence_number = Some(expected_sequence_number.into());
+            TransactionLifecycleState::Parked
+        },
+        MempoolLifecycleState::Broadcast { num_peers } => {
+            lifecycle_event.num_peers = Some((num_peers as u64).into());
+            TransactionLifecycleState::Broadcast
+        },
+        MempoolLifecycleState::PulledIntoBatch => TransactionLifecycleState::PulledIntoBatch,
+        MempoolLifecycleState::Proposed => TransactionLifecycleState::Proposed,
+        MempoolLifecycleState::Rejected { reason } => {
+            lifecycle_event.vm_status = Some(format!("{:?}", reason));
+            TransactionLifecycleState::Rejected
+        },
+        MempoolLifecycleState::Evicted => TransactionLifecycleState::Evicted,
+        MempoolLifecycleState::Expired => TransactionLifecycleState::Expired,
+    };
+    lifecycle_event
+}
@@ -52,6 +52,7 @@ pub use transaction::{
     MultiKeySignature, MultisigPayload, MultisigTransactionPayload, NoAccountSignature,
     PendingTransaction, PublicKey, ScriptPayload, ScriptWriteSet, Signature, SingleKeySignature,
     SubmitTransactionRequest, Transaction, TransactionData, TransactionId, TransactionInfo,
+    TransactionLifecycle, TransactionLifecycleEvent, TransactionLifecycleState,
     TransactionOnChainData, TransactionPayload, TransactionSignature, TransactionSigningMessage,
     TransactionSummary, TransactionsBatchSingleSubmissionFailure,
     TransactionsBatchSubmissionResult, UserCreateSigningMessageRequest, UserTransaction,
@@ -36,7 +36,7 @@ use creditchain_types::{
 };
 use bcs::to_bytes;
 use once_cell::sync::Lazy;
-use poem_openapi::{Object, Union};
+use poem_openapi::{Enum, Object, Union};
 use serde::{Deserialize, Serialize};
 use std::{
     boxed::Box,
@@ -2399,3 +2399,48 @@ pub struct GasEstimation {
     /// The prioritized estimate for the gas unit price
     pub prioritized_gas_estimate: Option<u64>,
 }
+
+/// A lifecycle state of a pending transaction, as observed by the node's mempool
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize, Enum)]
+#[serde(rename_all = "snake_case")]
+#[oai(rename_all = "snake_case")]
+pub enum TransactionLifecycleState {
+    /// The transaction was accepted into mempool
+    Accepted,
+    /// The transaction is waiting for a transaction with a lower sequence number
+    Parked,
+    /// The transaction was broadcast to other nodes
+    Broadcast,
+    /// The transaction was pulled by consensus (e.g., into a quorum store batch)
+    PulledIntoBatch,
+    /// The transaction was included in a block proposal
+    Proposed,
+    /// The transaction was rejected by the VM during execution
+    Rejected,
+    /// The transaction was evicted from mempool (e.g., by an operator, or to make room for
+    /// other transactions)
+    Evicted,
+    /// The transaction expired before it was committed
+    Expired,
+}
+
+/// A lifecycle event of a pending transaction
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, Object)]
+pub struct TransactionLifecycleEvent {
+    pub state: TransactionLifecycleState,
+    /// The time the state was entered, in microseconds since the unix epoch
+    pub timestamp: U64,
+    /// For parked transactions, the (missing) sequence number the transaction is waiting for
+    pub expected_sequence_number: Option<U64>,
+    /// For broadcast transactions, the number of peers the transaction was broadcast to
+    pub num_peers: Option<U64>,
+    /// For rejected transactions, the status with which the VM discarded the transaction
+    pub vm_status: Option<String>,
+}
+
+/// The lifecycle of a transaction recently accepted by the node's mempool
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, Object)]
+pub struct TransactionLifecycle {
+    pub hash: HashValue,
+    pub events: Vec<TransactionLifecycleEvent>,
+}
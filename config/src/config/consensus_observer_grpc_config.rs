@@ -0,0 +1,166 @@
+// Copyright © CreditChain Research Team
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    config::{
+        config_sanitizer::ConfigSanitizer, node_config_loader::NodeType, AuthenticationConfig,
+        Error, NodeConfig,
+    },
+    utils,
+};
+use creditchain_types::chain_id::ChainId;
+use serde::{Deserialize, Serialize};
+use std::net::{Ipv4Addr, SocketAddr, SocketAddrV4};
+
+pub const DEFAULT_CONSENSUS_OBSERVER_GRPC_PORT: u16 = 50053;
+
+/// The config for the gRPC service that re-publishes consensus observer
+/// messages (e.g., ordered blocks and commit decisions) to external subscribers.
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ConsensusObserverGrpcConfig {
+    /// Whether the gRPC service is enabled (requires the consensus publisher)
+    pub enabled: bool,
+    /// The address that the gRPC server will listen on
+    pub address: SocketAddr,
+    /// If empty, will allow all subscriptions without authentication. (Not allowed on mainnet.)
+    /// The passcode is expected in the `authorization` metadata, i.e., `Bearer <passcode>`.
+    pub authentication_configs: Vec<AuthenticationConfig>,
+    /// The maximum number of concurrent external subscribers
+    pub max_concurrent_subscribers: u64,
+    /// Maximum number of pending messages for each subscriber. Subscribers that
+    /// fall behind by more than this are disconnected (to avoid blocking the publisher).
+    pub max_subscriber_buffer_size: u64,
+}
+
+impl Default for ConsensusObserverGrpcConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            address: SocketAddr::V4(SocketAddrV4::new(
+                Ipv4Addr::new(0, 0, 0, 0),
+                DEFAULT_CONSENSUS_OBSERVER_GRPC_PORT,
+            )),
+            authentication_configs: vec![],
+            max_concurrent_subscribers: 10,
+            max_subscriber_buffer_size: 1000,
+        }
+    }
+}
+
+impl ConsensusObserverGrpcConfig {
+    pub fn randomize_ports(&mut self) {
+        self.address.set_port(utils::get_available_port());
+    }
+}
+
+impl ConfigSanitizer for ConsensusObserverGrpcConfig {
+    fn sanitize(
+        node_config: &NodeConfig,
+        _node_type: NodeType,
+        chain_id: Option<ChainId>,
+    ) -> Result<(), Error> {
+        let sanitizer_name = Self::get_sanitizer_name();
+        let grpc_config = &node_config.consensus_observer_grpc;
+        if !grpc_config.enabled {
+            return Ok(());
+        }
+
+        // The gRPC service re-publishes the messages of the consensus publisher
+        if !node_config.consensus_observer.publisher_enabled {
+            return Err(Error::ConfigSanitizerFailed(
+                sanitizer_name,
+                "consensus_observer.publisher_enabled must be true if consensus_observer_grpc.enabled is true".into(),
+            ));
+        }
+
+        // Verify that authentication is enabled on mainnet
+        if let Some(chain_id) = chain_id {
+            if chain_id.is_mainnet() && grpc_config.authentication_configs.is_empty() {
+                return Err(Error::ConfigSanitizerFailed(
+                    sanitizer_name,
+                    "Must enable authentication for the consensus observer gRPC service on mainnet.".into(),
+                ));
+            }
+        }
+
+        // Verify that the subscriber limits are non-zero
+        if grpc_config.max_concurrent_subscribers == 0 || grpc_config.max_subscriber_buffer_size == 0
+        {
+            return Err(Error::ConfigSanitizerFailed(
+                sanitizer_name,
+                "The max concurrent subscribers and subscriber buffer size must be non-zero!".into(),
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::ConsensusObserverConfig;
+
+    #[test]
+    fn test_sanitize_publisher_disabled() {
+        // Create a node config with the gRPC service enabled but the publisher disabled
+        let node_config = NodeConfig {
+            consensus_observer_grpc: ConsensusObserverGrpcConfig {
+                enabled: true,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        // Verify that the config fails sanitization
+        let error = ConsensusObserverGrpcConfig::sanitize(
+            &node_config,
+            NodeType::PublicFullnode,
+            Some(ChainId::testnet()),
+        )
+        .unwrap_err();
+        assert!(matches!(error, Error::ConfigSanitizerFailed(_, _)));
+    }
+
+    #[test]
+    fn test_sanitize_mainnet_authentication() {
+        // Create a node config with the gRPC service enabled and no authentication
+        let mut node_config = NodeConfig {
+            consensus_observer: ConsensusObserverConfig {
+                publisher_enabled: true,
+                ..Default::default()
+            },
+            consensus_observer_grpc: ConsensusObserverGrpcConfig {
+                enabled: true,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        // Verify that the config fails sanitization on mainnet, but passes on testnet
+        let error = ConsensusObserverGrpcConfig::sanitize(
+            &node_config,
+            NodeType::PublicFullnode,
+            Some(ChainId::mainnet()),
+        )
+        .unwrap_err();
+        assert!(matches!(error, Error::ConfigSanitizerFailed(_, _)));
+        ConsensusObserverGrpcConfig::sanitize(
+            &node_config,
+            NodeType::PublicFullnode,
+            Some(ChainId::testnet()),
+        )
+        .unwrap();
+
+        // Enable authentication and verify that the config passes sanitization on mainnet
+        node_config.consensus_observer_grpc.authentication_configs =
+            vec![AuthenticationConfig::PasscodeSha256("passcode_sha256".into())];
+        ConsensusObserverGrpcConfig::sanitize(
+            &node_config,
+            NodeType::PublicFullnode,
+            Some(ChainId::mainnet()),
+        )
+        .unwrap();
+    }
+}
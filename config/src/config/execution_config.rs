@@ -45,6 +45,9 @@ pub struct ExecutionConfig {
     pub paranoid_type_verification: bool,
     /// Enabled discarding blocks that fail execution due to BlockSTM/VM issue.
     pub discard_failed_blocks: bool,
+    /// Records per-block BlockSTM conflict reports (hot keys, dependency chains and
+    /// re-executions), served by the admin service. Adds overhead to parallel execution.
+    pub blockstm_conflict_diagnostics: bool,
     /// Enables paranoid mode for hot potatoes, which adds extra runtime VM checks
     pub paranoid_hot_potato_verification: bool,
     /// Enables enhanced metrics around processed transactions
@@ -80,6 +83,7 @@ impl Default for ExecutionConfig {
             paranoid_type_verification: true,
             paranoid_hot_potato_verification: true,
             discard_failed_blocks: false,
+            blockstm_conflict_diagnostics: false,
             processed_transactions_detailed_counters: false,
             genesis_waypoint: None,
         }
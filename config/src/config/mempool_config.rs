@@ -10,6 +10,7 @@ use creditchain_global_constants::DEFAULT_BUCKETS;
 use creditchain_types::chain_id::ChainId;
 use serde::{Deserialize, Serialize};
 use serde_yaml::Value;
+use std::path::PathBuf;
 
 #[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize)]
 #[serde(default, deny_unknown_fields)]
@@ -102,6 +103,16 @@ pub struct MempoolConfig {
     pub enable_max_load_balancing_at_any_load: bool,
     /// Maximum number of orderless transactions allowed in the Mempool per user
     pub orderless_txn_capacity_per_user: usize,
+    /// Whether or not to journal accepted transactions to disk, so that pending
+    /// transactions are restored (and re-validated) after a node restart.
+    pub enable_persistence: bool,
+    /// The directory of the mempool journal. Relative paths are resolved against the data dir.
+    pub persistence_dir: PathBuf,
+    /// Interval to compact the mempool journal into a snapshot of the transactions in mempool.
+    pub persistence_snapshot_interval_secs: u64,
+    /// The maximum number of recently accepted transactions for which lifecycle
+    /// states are tracked (and exposed via the API). 0 disables tracking.
+    pub lifecycle_tracker_capacity: usize,
 }
 
 impl Default for MempoolConfig {
@@ -167,17 +178,34 @@ impl Default for MempoolConfig {
             ],
             enable_max_load_balancing_at_any_load: false,
             orderless_txn_capacity_per_user: 1000,
+            enable_persistence: false,
+            persistence_dir: PathBuf::from("mempool"),
+            persistence_snapshot_interval_secs: 60,
+            lifecycle_tracker_capacity: 50_000,
         }
     }
 }
 
 impl ConfigSanitizer for MempoolConfig {
     fn sanitize(
-        _node_config: &NodeConfig,
+        node_config: &NodeConfig,
         _node_type: NodeType,
         _chain_id: Option<ChainId>,
     ) -> Result<(), Error> {
-        Ok(()) // TODO: add reasonable verifications
+        let sanitizer_name = Self::get_sanitizer_name();
+        let mempool_config = &node_config.mempool;
+
+        // Verify that the journal is compacted periodically (otherwise it grows without bound)
+        if mempool_config.enable_persistence
+            && mempool_config.persistence_snapshot_interval_secs == 0
+        {
+            return Err(Error::ConfigSanitizerFailed(
+                sanitizer_name,
+                "The mempool persistence snapshot interval must be non-zero!".into(),
+            ));
+        }
+
+        Ok(()) // TODO: add more reasonable verifications
     }
 }
 
@@ -10,6 +10,7 @@ mod config_optimizer;
 mod config_sanitizer;
 mod consensus_config;
 mod consensus_observer_config;
+mod consensus_observer_grpc_config;
 mod dag_consensus_config;
 mod dkg_config;
 mod error;
@@ -46,6 +47,7 @@ pub use api_config::*;
 pub use base_config::*;
 pub use consensus_config::*;
 pub use consensus_observer_config::*;
+pub use consensus_observer_grpc_config::*;
 pub use dag_consensus_config::*;
 pub use error::*;
 pub use execution_config::*;
@@ -4,15 +4,17 @@
 use super::{DagConsensusConfig, IndexerTableInfoConfig};
 use crate::{
     config::{
-        consensus_observer_config::ConsensusObserverConfig, dkg_config::DKGConfig,
+        consensus_observer_config::ConsensusObserverConfig,
+        consensus_observer_grpc_config::ConsensusObserverGrpcConfig, dkg_config::DKGConfig,
         internal_indexer_db_config::InternalIndexerDBConfig,
         jwk_consensus_config::JWKConsensusConfig, netbench_config::NetbenchConfig,
         node_config_loader::NodeConfigLoader, node_startup_config::NodeStartupConfig,
         persistable_config::PersistableConfig,
         transaction_filters_config::TransactionFiltersConfig, utils::RootPath, AdminServiceConfig,
-        ApiConfig, BaseConfig, ConsensusConfig, Error, ExecutionConfig, IndexerConfig,
-        IndexerGrpcConfig, InspectionServiceConfig, LoggerConfig, MempoolConfig, NetworkConfig,
-        PeerMonitoringServiceConfig, SafetyRulesTestConfig, StateSyncConfig, StorageConfig,
+        ApiConfig, BackupBootstrappingConfig, BaseConfig, ConsensusConfig, Error, ExecutionConfig,
+        IndexerConfig, IndexerGrpcConfig, InspectionServiceConfig, LoggerConfig, MempoolConfig,
+        NetworkConfig, PeerMonitoringServiceConfig, SafetyRulesTestConfig, StateSyncConfig,
+        StorageConfig,
     },
     network_id::NetworkId,
 };
@@ -40,12 +42,16 @@ pub struct NodeConfig {
     #[serde(default)]
     pub api: ApiConfig,
     #[serde(default)]
+    pub backup_bootstrapping: BackupBootstrappingConfig,
+    #[serde(default)]
     pub base: BaseConfig,
     #[serde(default)]
     pub consensus: ConsensusConfig,
     #[serde(default)]
     pub consensus_observer: ConsensusObserverConfig,
     #[serde(default)]
+    pub consensus_observer_grpc: ConsensusObserverGrpcConfig,
+    #[serde(default)]
     pub dag_consensus: DagConsensusConfig,
     #[serde(default)]
     pub dkg: DKGConfig,
@@ -185,6 +191,7 @@ impl NodeConfig {
         // Randomize the ports for the services
         self.admin_service.randomize_ports();
         self.api.randomize_ports();
+        self.consensus_observer_grpc.randomize_ports();
         self.inspection_service.randomize_ports();
         self.storage.randomize_ports();
         self.logger.disable_tokio_console();
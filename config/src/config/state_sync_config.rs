@@ -9,6 +9,7 @@ use crate::config::{
 use creditchain_types::chain_id::ChainId;
 use serde::{Deserialize, Serialize};
 use serde_yaml::Value;
+use std::path::PathBuf;
 
 // The maximum message size per state sync message
 const MAX_MESSAGE_SIZE: usize = 10 * 1024 * 1024; /* 10 MiB */
@@ -91,6 +92,19 @@ impl ContinuousSyncingMode {
     }
 }
 
+/// A historical target for fast syncing the node (i.e., instead of the latest
+/// state). This allows nodes to reproduce the chain state at a specific point.
+/// Note: state snapshots can only be verified at epoch boundaries (using the epoch
+/// ending ledger infos), so version targets are rounded down to the closest epoch
+/// ending version.
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Eq, Serialize)]
+pub enum SnapshotSyncTarget {
+    /// Downloads the state at the end of the given epoch
+    Epoch(u64),
+    /// Downloads the state at the highest epoch ending version <= the given version
+    Version(u64),
+}
+
 #[derive(Clone, Copy, Debug, Deserialize, PartialEq, Eq, Serialize)]
 #[serde(default, deny_unknown_fields)]
 pub struct StateSyncDriverConfig {
@@ -104,6 +118,8 @@ pub struct StateSyncDriverConfig {
     pub enable_auto_bootstrapping: bool,
     /// The interval (ms) to refresh the storage summary
     pub fallback_to_output_syncing_secs: u64,
+    /// Whether to stop syncing once the snapshot sync target is reached (e.g., for audit nodes)
+    pub halt_at_snapshot_sync_target: bool,
     /// The interval (ms) at which to check state sync progress
     pub progress_check_interval_ms: u64,
     /// The maximum time (secs) to wait for connections from peers before auto-bootstrapping
@@ -120,6 +136,8 @@ pub struct StateSyncDriverConfig {
     pub max_stream_wait_time_ms: u64,
     /// The version lag we'll tolerate before snapshot syncing
     pub num_versions_to_skip_snapshot_sync: u64,
+    /// The historical epoch or version to fast sync to (instead of the latest state)
+    pub snapshot_sync_target: Option<SnapshotSyncTarget>,
 }
 
 /// The default state sync driver config will be the one that gets (and keeps)
@@ -132,6 +150,7 @@ impl Default for StateSyncDriverConfig {
             continuous_syncing_mode: ContinuousSyncingMode::ExecuteTransactionsOrApplyOutputs,
             enable_auto_bootstrapping: false,
             fallback_to_output_syncing_secs: 180, // 3 minutes
+            halt_at_snapshot_sync_target: false,
             progress_check_interval_ms: 100,
             max_connection_deadline_secs: 10,
             max_consecutive_stream_notifications: 10,
@@ -140,10 +159,47 @@ impl Default for StateSyncDriverConfig {
             max_pending_mempool_notifications: 100,
             max_stream_wait_time_ms: 5000,
             num_versions_to_skip_snapshot_sync: 400_000_000, // At 5k TPS, this allows a node to fail for about 24 hours.
+            snapshot_sync_target: None,
+        }
+    }
+}
+
+/// The config for bootstrapping from a local backup archive (i.e., one created
+/// by the backup-cli), instead of fetching the data from peers in the network.
+/// Once the node has bootstrapped, it will continue to sync from peers. Note:
+/// this config lives in the node config (and not the state sync config) so
+/// that the state sync config can remain `Copy`.
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct BackupBootstrappingConfig {
+    /// The command adapter config file for the backup storage (e.g., for cloud storage)
+    pub command_adapter_config: Option<PathBuf>,
+    /// The maximum number of concurrent backup metadata file downloads
+    pub concurrent_downloads: usize,
+    /// The local directory holding the backup storage (e.g., a mounted volume)
+    pub local_fs_dir: Option<PathBuf>,
+    /// The directory used to cache backup metadata files (defaults to a temporary directory)
+    pub metadata_cache_dir: Option<PathBuf>,
+}
+
+impl Default for BackupBootstrappingConfig {
+    fn default() -> Self {
+        Self {
+            command_adapter_config: None,
+            concurrent_downloads: 8,
+            local_fs_dir: None,
+            metadata_cache_dir: None,
         }
     }
 }
 
+impl BackupBootstrappingConfig {
+    /// Returns true iff the node should bootstrap from a backup archive
+    pub fn is_enabled(&self) -> bool {
+        self.command_adapter_config.is_some() || self.local_fs_dir.is_some()
+    }
+}
+
 #[derive(Clone, Copy, Debug, Deserialize, PartialEq, Eq, Serialize)]
 #[serde(default, deny_unknown_fields)]
 pub struct StorageServiceConfig {
@@ -173,6 +229,8 @@ pub struct StorageServiceConfig {
     pub max_transaction_output_chunk_size: u64,
     /// Minimum time (secs) to ignore peers after too many invalid requests
     pub min_time_to_ignore_peers_secs: u64,
+    /// The per-peer serving budgets (bandwidth and concurrency) of the server
+    pub peer_serving_budgets: PeerServingBudgetConfig,
     /// The interval (ms) to refresh the request moderator state
     pub request_moderator_refresh_interval_ms: u64,
     /// The interval (ms) to refresh the storage summary
@@ -195,12 +253,53 @@ impl Default for StorageServiceConfig {
             max_transaction_chunk_size: MAX_TRANSACTION_CHUNK_SIZE,
             max_transaction_output_chunk_size: MAX_TRANSACTION_OUTPUT_CHUNK_SIZE,
             min_time_to_ignore_peers_secs: 300, // 5 minutes
+            peer_serving_budgets: PeerServingBudgetConfig::default(),
             request_moderator_refresh_interval_ms: 1000, // 1 second
             storage_summary_refresh_interval_ms: 100, // Optimal for <= 10 blocks per second
         }
     }
 }
 
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Eq, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct PeerServingBudgetConfig {
+    /// Whether or not to enforce per-peer serving budgets
+    pub enable_peer_serving_budgets: bool,
+    /// Maximum number of bytes (per peer) that can be served in a single burst,
+    /// expressed as a multiple (in secs) of the peer's bytes per second budget.
+    pub max_burst_secs: u64,
+    /// Maximum number of concurrent requests (per peer) being served
+    pub max_concurrent_requests_per_peer: u64,
+    /// Maximum number of responses (for peers over budget) to store in the LRU cache
+    pub max_over_budget_lru_cache_size: u64,
+    /// Maximum number of requests (per peer) waiting to be served
+    pub max_pending_requests_per_peer: u64,
+    /// Maximum number of bytes (per second) to serve to each public network peer
+    pub max_public_peer_bytes_per_sec: u64,
+    /// Maximum number of bytes (per second) to serve to each validator network peer
+    pub max_validator_peer_bytes_per_sec: u64,
+    /// Maximum number of bytes (per second) to serve to each VFN network peer
+    pub max_vfn_peer_bytes_per_sec: u64,
+    /// Maximum number of bytes to send per network message to peers over budget
+    pub over_budget_max_network_chunk_bytes: u64,
+}
+
+impl Default for PeerServingBudgetConfig {
+    fn default() -> Self {
+        Self {
+            enable_peer_serving_budgets: false,
+            max_burst_secs: 5,
+            max_concurrent_requests_per_peer: 4,
+            max_over_budget_lru_cache_size: 100,
+            max_pending_requests_per_peer: 100,
+            max_public_peer_bytes_per_sec: 20 * 1024 * 1024, // 20 MiB/s
+            max_validator_peer_bytes_per_sec: 200 * 1024 * 1024, // 200 MiB/s
+            max_vfn_peer_bytes_per_sec: 100 * 1024 * 1024, // 100 MiB/s
+            over_budget_max_network_chunk_bytes: 1024 * 1024, // 1 MiB
+        }
+    }
+}
+
 #[derive(Clone, Copy, Debug, Deserialize, PartialEq, Eq, Serialize)]
 #[serde(default, deny_unknown_fields)]
 pub struct DataStreamingServiceConfig {
@@ -392,6 +491,33 @@ impl Default for CreditChainLatencyFilteringConfig {
     }
 }
 
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Eq, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct CreditChainPeerScorePersistenceConfig {
+    /// The half-life (in seconds) used to decay persisted peer states when they are reloaded
+    pub decay_half_life_secs: u64,
+    /// Whether or not to persist peer states (e.g., scores and latencies) across restarts
+    pub enable_persistence: bool,
+    /// Maximum number of peer states to persist
+    pub max_persisted_peers: u64,
+    /// Interval (in ms) between peer state persistence rounds
+    pub persistence_interval_ms: u64,
+    /// Maximum time (in seconds) to keep the state of a disconnected peer (if it doesn't reconnect)
+    pub untracked_peer_state_ttl_secs: u64,
+}
+
+impl Default for CreditChainPeerScorePersistenceConfig {
+    fn default() -> Self {
+        Self {
+            decay_half_life_secs: 3600, // 1 hour
+            enable_persistence: false,
+            max_persisted_peers: 1000,
+            persistence_interval_ms: 30_000, // 30 seconds
+            untracked_peer_state_ttl_secs: 6 * 3600, // 6 hours (i.e., 6 half-lives)
+        }
+    }
+}
+
 #[derive(Clone, Copy, Debug, Deserialize, PartialEq, Eq, Serialize)]
 #[serde(default, deny_unknown_fields)]
 pub struct CreditChainDataClientConfig {
@@ -431,6 +557,8 @@ pub struct CreditChainDataClientConfig {
     pub max_transaction_output_chunk_size: u64,
     /// Timeout (in ms) when waiting for an optimistic fetch response
     pub optimistic_fetch_timeout_ms: u64,
+    /// The peer score persistence config for the data client
+    pub peer_score_persistence_config: CreditChainPeerScorePersistenceConfig,
     /// First timeout (in ms) when waiting for a response
     pub response_timeout_ms: u64,
     /// Timeout (in ms) when waiting for a subscription response
@@ -458,6 +586,7 @@ impl Default for CreditChainDataClientConfig {
             max_transaction_chunk_size: MAX_TRANSACTION_CHUNK_SIZE,
             max_transaction_output_chunk_size: MAX_TRANSACTION_OUTPUT_CHUNK_SIZE,
             optimistic_fetch_timeout_ms: 5000,        // 5 seconds
+            peer_score_persistence_config: CreditChainPeerScorePersistenceConfig::default(),
             response_timeout_ms: 10_000,              // 10 seconds
             subscription_response_timeout_ms: 15_000, // 15 seconds (longer than a regular timeout because of prefetching)
             use_compression: true,
@@ -472,15 +601,107 @@ impl ConfigSanitizer for StateSyncConfig {
         chain_id: Option<ChainId>,
     ) -> Result<(), Error> {
         // Sanitize the state sync driver config
-        StateSyncDriverConfig::sanitize(node_config, node_type, chain_id)
+        StateSyncDriverConfig::sanitize(node_config, node_type, chain_id)?;
+
+        // Sanitize the peer serving budget config
+        PeerServingBudgetConfig::sanitize(node_config, node_type, chain_id)?;
+
+        // Sanitize the backup bootstrapping config
+        BackupBootstrappingConfig::sanitize(node_config, node_type, chain_id)
     }
 }
 
-impl ConfigSanitizer for StateSyncDriverConfig {
+impl ConfigSanitizer for BackupBootstrappingConfig {
+    fn sanitize(
+        node_config: &NodeConfig,
+        _node_type: NodeType,
+        _chain_id: Option<ChainId>,
+    ) -> Result<(), Error> {
+        let sanitizer_name = Self::get_sanitizer_name();
+        let backup_bootstrapping_config = &node_config.backup_bootstrapping;
+        if !backup_bootstrapping_config.is_enabled() {
+            return Ok(());
+        }
+
+        // Verify that only a single backup storage is specified
+        if backup_bootstrapping_config.command_adapter_config.is_some()
+            && backup_bootstrapping_config.local_fs_dir.is_some()
+        {
+            return Err(Error::ConfigSanitizerFailed(
+                sanitizer_name,
+                "Only one of the command adapter config and the local fs dir can be specified \
+                for backup bootstrapping!"
+                    .to_string(),
+            ));
+        }
+
+        // Verify that the node is fast syncing. Backup proofs are relative to
+        // the ledger infos of each backup, so only the state snapshots (and
+        // not transaction ranges) can be verified against the bootstrapping target.
+        let bootstrapping_mode = node_config.state_sync.state_sync_driver.bootstrapping_mode;
+        if !bootstrapping_mode.is_fast_sync() {
+            return Err(Error::ConfigSanitizerFailed(
+                sanitizer_name,
+                format!(
+                    "Backup bootstrapping requires fast sync (i.e., {:?}), but the bootstrapping mode is: {:?}",
+                    BootstrappingMode::DownloadLatestStates,
+                    bootstrapping_mode
+                ),
+            ));
+        }
+
+        // Verify that the number of concurrent downloads is not zero
+        if backup_bootstrapping_config.concurrent_downloads == 0 {
+            return Err(Error::ConfigSanitizerFailed(
+                sanitizer_name,
+                "The number of concurrent downloads for backup bootstrapping must be non-zero!"
+                    .to_string(),
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+impl ConfigSanitizer for PeerServingBudgetConfig {
     fn sanitize(
         node_config: &NodeConfig,
         _node_type: NodeType,
         _chain_id: Option<ChainId>,
+    ) -> Result<(), Error> {
+        let sanitizer_name = Self::get_sanitizer_name();
+        let peer_serving_budgets = &node_config.state_sync.storage_service.peer_serving_budgets;
+        if !peer_serving_budgets.enable_peer_serving_budgets {
+            return Ok(());
+        }
+
+        // Verify that each peer can have at least one request in flight
+        if peer_serving_budgets.max_concurrent_requests_per_peer == 0 {
+            return Err(Error::ConfigSanitizerFailed(
+                sanitizer_name,
+                "The max concurrent requests per peer must be non-zero when peer serving budgets are enabled!"
+                    .to_string(),
+            ));
+        }
+
+        // Verify that peers over budget can still be served
+        if peer_serving_budgets.over_budget_max_network_chunk_bytes == 0 {
+            return Err(Error::ConfigSanitizerFailed(
+                sanitizer_name,
+                "The over budget max network chunk bytes must be non-zero when peer serving budgets are enabled!"
+                    .to_string(),
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+impl ConfigSanitizer for StateSyncDriverConfig {
+    fn sanitize(
+        node_config: &NodeConfig,
+        node_type: NodeType,
+        _chain_id: Option<ChainId>,
     ) -> Result<(), Error> {
         let sanitizer_name = Self::get_sanitizer_name();
         let state_sync_driver_config = &node_config.state_sync.state_sync_driver;
@@ -496,6 +717,34 @@ impl ConfigSanitizer for StateSyncDriverConfig {
             ));
         }
 
+        // Verify that snapshot sync targets are only used by fullnodes that are fast syncing
+        if let Some(snapshot_sync_target) = state_sync_driver_config.snapshot_sync_target {
+            if node_type.is_validator() {
+                return Err(Error::ConfigSanitizerFailed(
+                    sanitizer_name,
+                    format!(
+                        "Validators cannot bootstrap to a historical snapshot! Snapshot sync target: {:?}",
+                        snapshot_sync_target
+                    ),
+                ));
+            }
+            if !fast_sync_enabled {
+                return Err(Error::ConfigSanitizerFailed(
+                    sanitizer_name,
+                    format!(
+                        "A snapshot sync target requires fast sync (i.e., {:?}), but the bootstrapping mode is: {:?}",
+                        BootstrappingMode::DownloadLatestStates,
+                        state_sync_driver_config.bootstrapping_mode
+                    ),
+                ));
+            }
+        } else if state_sync_driver_config.halt_at_snapshot_sync_target {
+            return Err(Error::ConfigSanitizerFailed(
+                sanitizer_name,
+                "Halting at the snapshot sync target requires a snapshot sync target!".to_string(),
+            ));
+        }
+
         Ok(())
     }
 }
@@ -801,6 +1050,125 @@ mod tests {
         assert!(matches!(error, Error::ConfigSanitizerFailed(_, _)));
     }
 
+    #[test]
+    fn test_sanitize_backup_bootstrapping() {
+        // Create a node config that bootstraps from a backup (without fast sync)
+        let mut node_config = NodeConfig {
+            backup_bootstrapping: BackupBootstrappingConfig {
+                local_fs_dir: Some(PathBuf::from("/opt/backup")),
+                ..Default::default()
+            },
+            state_sync: StateSyncConfig {
+                state_sync_driver: StateSyncDriverConfig {
+                    bootstrapping_mode: BootstrappingMode::ExecuteTransactionsFromGenesis,
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        // Verify that sanitization fails
+        let error =
+            StateSyncConfig::sanitize(&node_config, NodeType::Validator, Some(ChainId::testnet()))
+                .unwrap_err();
+        assert!(matches!(error, Error::ConfigSanitizerFailed(_, _)));
+
+        // Enable fast sync and verify that sanitization passes
+        node_config.state_sync.state_sync_driver.bootstrapping_mode =
+            BootstrappingMode::DownloadLatestStates;
+        StateSyncConfig::sanitize(&node_config, NodeType::Validator, Some(ChainId::testnet()))
+            .unwrap();
+
+        // Specify multiple backup storages and verify that sanitization fails
+        node_config.backup_bootstrapping.command_adapter_config =
+            Some(PathBuf::from("/opt/backup/config.yaml"));
+        let error =
+            StateSyncConfig::sanitize(&node_config, NodeType::Validator, Some(ChainId::testnet()))
+                .unwrap_err();
+        assert!(matches!(error, Error::ConfigSanitizerFailed(_, _)));
+    }
+
+    #[test]
+    fn test_sanitize_snapshot_sync_target() {
+        // Create a node config with a snapshot sync target (without fast sync)
+        let mut node_config = NodeConfig {
+            state_sync: StateSyncConfig {
+                state_sync_driver: StateSyncDriverConfig {
+                    bootstrapping_mode: BootstrappingMode::ExecuteTransactionsFromGenesis,
+                    snapshot_sync_target: Some(SnapshotSyncTarget::Epoch(10)),
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        // Verify that sanitization fails
+        let error =
+            StateSyncConfig::sanitize(&node_config, NodeType::PublicFullnode, None).unwrap_err();
+        assert!(matches!(error, Error::ConfigSanitizerFailed(_, _)));
+
+        // Enable fast sync and verify that sanitization passes
+        node_config.state_sync.state_sync_driver.bootstrapping_mode =
+            BootstrappingMode::DownloadLatestStates;
+        StateSyncConfig::sanitize(&node_config, NodeType::PublicFullnode, None).unwrap();
+
+        // Verify that sanitization fails for validators
+        let error =
+            StateSyncConfig::sanitize(&node_config, NodeType::Validator, None).unwrap_err();
+        assert!(matches!(error, Error::ConfigSanitizerFailed(_, _)));
+
+        // Remove the snapshot sync target but halt at the target, and verify that sanitization fails
+        let state_sync_driver_config = &mut node_config.state_sync.state_sync_driver;
+        state_sync_driver_config.snapshot_sync_target = None;
+        state_sync_driver_config.halt_at_snapshot_sync_target = true;
+        let error =
+            StateSyncConfig::sanitize(&node_config, NodeType::PublicFullnode, None).unwrap_err();
+        assert!(matches!(error, Error::ConfigSanitizerFailed(_, _)));
+    }
+
+    #[test]
+    fn test_sanitize_peer_serving_budgets() {
+        // Create a node config that doesn't serve peers over budget
+        let mut node_config = NodeConfig {
+            state_sync: StateSyncConfig {
+                storage_service: StorageServiceConfig {
+                    peer_serving_budgets: PeerServingBudgetConfig {
+                        enable_peer_serving_budgets: true,
+                        over_budget_max_network_chunk_bytes: 0,
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        // Verify that sanitization fails
+        let error =
+            StateSyncConfig::sanitize(&node_config, NodeType::PublicFullnode, None).unwrap_err();
+        assert!(matches!(error, Error::ConfigSanitizerFailed(_, _)));
+
+        // Update the over budget chunk size and verify that sanitization passes
+        let peer_serving_budgets = &mut node_config.state_sync.storage_service.peer_serving_budgets;
+        peer_serving_budgets.over_budget_max_network_chunk_bytes = 512;
+        StateSyncConfig::sanitize(&node_config, NodeType::PublicFullnode, None).unwrap();
+
+        // Disallow concurrent requests and verify that sanitization fails
+        let peer_serving_budgets = &mut node_config.state_sync.storage_service.peer_serving_budgets;
+        peer_serving_budgets.max_concurrent_requests_per_peer = 0;
+        let error =
+            StateSyncConfig::sanitize(&node_config, NodeType::PublicFullnode, None).unwrap_err();
+        assert!(matches!(error, Error::ConfigSanitizerFailed(_, _)));
+
+        // Disable the peer serving budgets and verify that sanitization passes
+        let peer_serving_budgets = &mut node_config.state_sync.storage_service.peer_serving_budgets;
+        peer_serving_budgets.enable_peer_serving_budgets = false;
+        StateSyncConfig::sanitize(&node_config, NodeType::PublicFullnode, None).unwrap();
+    }
+
     /// Creates and returns a node config with the syncing modes set to execution
     fn create_execution_mode_config() -> NodeConfig {
         NodeConfig {
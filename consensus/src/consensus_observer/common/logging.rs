@@ -40,6 +40,7 @@ impl LogSchema<'_> {
 pub enum LogEntry {
     ConsensusObserver,
     ConsensusPublisher,
+    ConsensusPublisherGrpc,
     GetDownstreamPeers,
     SendDirectSendMessage,
     SendRpcRequest,
@@ -6,7 +6,8 @@
 use creditchain_config::network_id::{NetworkId, PeerNetworkId};
 use creditchain_metrics_core::{
     exponential_buckets, register_histogram_vec, register_int_counter, register_int_counter_vec,
-    register_int_gauge_vec, HistogramVec, IntCounter, IntCounterVec, IntGaugeVec,
+    register_int_gauge, register_int_gauge_vec, HistogramVec, IntCounter, IntCounterVec, IntGauge,
+    IntGaugeVec,
 };
 use once_cell::sync::Lazy;
 
@@ -216,6 +217,45 @@ pub static PENDING_CONSENSUS_OBSERVER_NETWORK_EVENTS: Lazy<IntCounterVec> = Lazy
     .unwrap()
 });
 
+/// Counter for tracking disconnected gRPC subscribers of the consensus publisher
+pub static PUBLISHER_GRPC_DISCONNECTED_SUBSCRIBERS: Lazy<IntCounterVec> = Lazy::new(|| {
+    register_int_counter_vec!(
+        "consensus_publisher_grpc_disconnected_subscribers",
+        "Counters related to disconnected gRPC subscribers of the consensus publisher",
+        &["reason"]
+    )
+    .unwrap()
+});
+
+/// Counter for tracking messages dropped before reaching the gRPC subscribers
+pub static PUBLISHER_GRPC_DROPPED_MESSAGES: Lazy<IntCounterVec> = Lazy::new(|| {
+    register_int_counter_vec!(
+        "consensus_publisher_grpc_dropped_messages",
+        "Counters related to messages dropped before reaching the gRPC subscribers",
+        &["message_type"]
+    )
+    .unwrap()
+});
+
+/// Gauge for tracking the number of active gRPC subscribers of the consensus publisher
+pub static PUBLISHER_GRPC_NUM_ACTIVE_SUBSCRIBERS: Lazy<IntGauge> = Lazy::new(|| {
+    register_int_gauge!(
+        "consensus_publisher_grpc_num_active_subscribers",
+        "Gauge related to active gRPC subscribers of the consensus publisher"
+    )
+    .unwrap()
+});
+
+/// Counter for tracking messages sent to gRPC subscribers by the consensus publisher
+pub static PUBLISHER_GRPC_SENT_MESSAGES: Lazy<IntCounterVec> = Lazy::new(|| {
+    register_int_counter_vec!(
+        "consensus_publisher_grpc_sent_messages",
+        "Counters related to messages sent to gRPC subscribers by the consensus publisher",
+        &["message_type"]
+    )
+    .unwrap()
+});
+
 /// Gauge for tracking the number of active subscribers for the consensus publisher
 pub static PUBLISHER_NUM_ACTIVE_SUBSCRIBERS: Lazy<IntGaugeVec> = Lazy::new(|| {
     register_int_gauge_vec!(
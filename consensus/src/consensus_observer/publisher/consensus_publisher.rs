@@ -14,6 +14,7 @@ use crate::consensus_observer::{
             ConsensusObserverResponse,
         },
     },
+    publisher::grpc_service::ConsensusObserverGrpcPublisher,
 };
 use creditchain_channels::creditchain_channel::Receiver;
 use creditchain_config::{config::ConsensusObserverConfig, network_id::PeerNetworkId};
@@ -41,6 +42,9 @@ pub struct ConsensusPublisher {
 
     // The sender for outbound network messages
     outbound_message_sender: mpsc::Sender<(PeerNetworkId, ConsensusObserverDirectSend)>,
+
+    // The gRPC publisher for external subscribers (if enabled)
+    grpc_publisher: Option<ConsensusObserverGrpcPublisher>,
 }
 
 impl ConsensusPublisher {
@@ -64,12 +68,19 @@ impl ConsensusPublisher {
             consensus_observer_config,
             active_subscribers: Arc::new(RwLock::new(HashSet::new())),
             outbound_message_sender,
+            grpc_publisher: None,
         };
 
         // Return the publisher and the outbound message receiver
         (consensus_publisher, outbound_message_receiver)
     }
 
+    /// Sets the gRPC publisher used to re-publish messages to external subscribers
+    pub fn with_grpc_publisher(mut self, grpc_publisher: ConsensusObserverGrpcPublisher) -> Self {
+        self.grpc_publisher = Some(grpc_publisher);
+        self
+    }
+
     #[cfg(test)]
     /// Creates a new consensus publisher with the given active subscribers
     pub fn new_with_active_subscribers(
@@ -210,6 +221,11 @@ impl ConsensusPublisher {
     /// Publishes a direct send message to all active subscribers. Note: this method
     /// is non-blocking (to avoid blocking callers during publishing, e.g., consensus).
     pub fn publish_message(&self, message: ConsensusObserverDirectSend) {
+        // Forward the message to the gRPC subscribers (if enabled)
+        if let Some(grpc_publisher) = &self.grpc_publisher {
+            grpc_publisher.publish_message(&message);
+        }
+
         // Get the active subscribers
         let active_subscribers = self.get_active_subscribers();
 
@@ -0,0 +1,454 @@
+// Copyright © CreditChain Research Team
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::consensus_observer::{
+    common::{
+        logging::{LogEntry, LogEvent, LogSchema},
+        metrics,
+    },
+    network::observer_message::ConsensusObserverDirectSend,
+};
+use creditchain_config::config::{AuthenticationConfig, ConsensusObserverGrpcConfig};
+use creditchain_infallible::RwLock;
+use creditchain_logger::{error, info, warn};
+use creditchain_protos::consensus_observer::v1::{
+    consensus_observer_service_server::{ConsensusObserverService, ConsensusObserverServiceServer},
+    ObserverMessageType, SubscribeRequest, SubscribeResponse,
+};
+use std::{
+    collections::HashMap,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+};
+use tokio::sync::{mpsc, mpsc::error::TrySendError};
+use tokio_stream::wrappers::ReceiverStream;
+use tonic::{metadata::MetadataMap, transport::Server, Request, Response, Status};
+
+// Useful constants for the authorization metadata
+const AUTHORIZATION_METADATA_KEY: &str = "authorization";
+const BEARER_PREFIX: &str = "Bearer ";
+
+// Labels for disconnected subscribers
+const SUBSCRIBER_CLOSED_LABEL: &str = "closed";
+const SUBSCRIBER_DROPPED_MESSAGE_LABEL: &str = "dropped_message";
+const SUBSCRIBER_LAGGING_LABEL: &str = "lagging";
+
+/// The sender half of a single gRPC subscription stream
+type SubscriberSender = mpsc::Sender<Result<SubscribeResponse, Status>>;
+
+/// The gRPC publisher re-publishes consensus observer messages to external
+/// subscribers (e.g., indexers and bridges) over a server-streaming gRPC API.
+#[derive(Clone)]
+pub struct ConsensusObserverGrpcPublisher {
+    // The configuration for the gRPC service
+    grpc_config: ConsensusObserverGrpcConfig,
+
+    // The identifier to assign to the next subscriber
+    next_subscriber_id: Arc<AtomicU64>,
+
+    // The set of active gRPC subscribers (indexed by subscriber ID)
+    active_subscribers: Arc<RwLock<HashMap<u64, SubscriberSender>>>,
+
+    // The sender for messages to forward to the gRPC subscribers
+    message_sender: mpsc::Sender<ConsensusObserverDirectSend>,
+}
+
+impl ConsensusObserverGrpcPublisher {
+    pub fn new(
+        grpc_config: ConsensusObserverGrpcConfig,
+        max_channel_size: usize,
+    ) -> (Self, mpsc::Receiver<ConsensusObserverDirectSend>) {
+        // Create the message sender and receiver
+        let (message_sender, message_receiver) = mpsc::channel(max_channel_size);
+
+        // Create the gRPC publisher
+        let grpc_publisher = Self {
+            grpc_config,
+            next_subscriber_id: Arc::new(AtomicU64::new(0)),
+            active_subscribers: Arc::new(RwLock::new(HashMap::new())),
+            message_sender,
+        };
+
+        // Return the publisher and the message receiver
+        (grpc_publisher, message_receiver)
+    }
+
+    /// Adds a new subscriber and returns the receiver for its messages.
+    /// If the maximum number of subscribers has been reached, an error is returned.
+    fn add_subscriber(&self) -> Result<mpsc::Receiver<Result<SubscribeResponse, Status>>, Status> {
+        let mut active_subscribers = self.active_subscribers.write();
+
+        // Verify that we have capacity for the new subscriber
+        let max_concurrent_subscribers = self.grpc_config.max_concurrent_subscribers as usize;
+        if active_subscribers.len() >= max_concurrent_subscribers {
+            return Err(Status::resource_exhausted(format!(
+                "The maximum number of subscribers has been reached: {}",
+                max_concurrent_subscribers
+            )));
+        }
+
+        // Create the subscriber channel and register the subscriber
+        let (subscriber_sender, subscriber_receiver) =
+            mpsc::channel(self.grpc_config.max_subscriber_buffer_size as usize);
+        let subscriber_id = self.next_subscriber_id.fetch_add(1, Ordering::Relaxed);
+        active_subscribers.insert(subscriber_id, subscriber_sender);
+        metrics::PUBLISHER_GRPC_NUM_ACTIVE_SUBSCRIBERS.set(active_subscribers.len() as i64);
+
+        info!(LogSchema::new(LogEntry::ConsensusPublisherGrpc)
+            .event(LogEvent::Subscription)
+            .message(&format!("New gRPC subscriber added! ID: {}", subscriber_id)));
+
+        Ok(subscriber_receiver)
+    }
+
+    /// Verifies the authorization metadata of a subscription request
+    fn authenticate(&self, metadata: &MetadataMap) -> Result<(), Status> {
+        // If no authentication is configured, all requests are allowed
+        let authentication_configs = &self.grpc_config.authentication_configs;
+        if authentication_configs.is_empty() {
+            return Ok(());
+        }
+
+        // Extract the passcode from the authorization metadata
+        let passcode = metadata
+            .get(AUTHORIZATION_METADATA_KEY)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.strip_prefix(BEARER_PREFIX))
+            .ok_or_else(|| Status::unauthenticated("Missing authorization bearer passcode!"))?;
+
+        // Verify the passcode against the authentication configs
+        let passcode_sha256 = sha256::digest(passcode);
+        for authentication_config in authentication_configs {
+            match authentication_config {
+                AuthenticationConfig::PasscodeSha256(expected_sha256) => {
+                    if passcode_sha256 == *expected_sha256 {
+                        return Ok(());
+                    }
+                },
+            }
+        }
+
+        Err(Status::unauthenticated("Invalid authorization passcode!"))
+    }
+
+    /// Returns true iff there are active gRPC subscribers
+    pub fn has_active_subscribers(&self) -> bool {
+        !self.active_subscribers.read().is_empty()
+    }
+
+    /// Enqueues the given message for the gRPC subscribers. Note: this function
+    /// is non-blocking. If the channel is full, the message is dropped and all
+    /// subscribers are disconnected (so that they can resubscribe and resync,
+    /// instead of silently missing the message).
+    pub fn publish_message(&self, message: &ConsensusObserverDirectSend) {
+        // Avoid the overhead of cloning and forwarding messages if nobody is listening
+        if !self.has_active_subscribers() {
+            return;
+        }
+
+        // Send the message to the forwarder
+        let message_label = message.get_label();
+        if let Err(error) = self.message_sender.try_send(message.clone()) {
+            warn!(LogSchema::new(LogEntry::ConsensusPublisherGrpc)
+                .event(LogEvent::SendDirectSendMessage)
+                .message(&format!(
+                    "Failed to forward message to the gRPC subscribers! Disconnecting all subscribers. Error: {:?}",
+                    error
+                )));
+            metrics::PUBLISHER_GRPC_DROPPED_MESSAGES
+                .with_label_values(&[message_label])
+                .inc();
+
+            // Disconnect all subscribers, as they would otherwise miss the message
+            self.disconnect_all_subscribers(SUBSCRIBER_DROPPED_MESSAGE_LABEL);
+        }
+    }
+
+    /// Disconnects all active subscribers (for the given reason). This closes
+    /// their streams, so that they can resubscribe.
+    fn disconnect_all_subscribers(&self, reason_label: &str) {
+        let mut active_subscribers = self.active_subscribers.write();
+        metrics::PUBLISHER_GRPC_DISCONNECTED_SUBSCRIBERS
+            .with_label_values(&[reason_label])
+            .inc_by(active_subscribers.len() as u64);
+        active_subscribers.clear();
+        metrics::PUBLISHER_GRPC_NUM_ACTIVE_SUBSCRIBERS.set(0);
+    }
+
+    /// Sends the given message to all active subscribers. Subscribers that
+    /// have disconnected or fallen too far behind are removed.
+    fn send_to_subscribers(&self, message: &ConsensusObserverDirectSend) {
+        // Create the subscription response
+        let response = match create_subscribe_response(message) {
+            Ok(response) => response,
+            Err(error) => {
+                error!(LogSchema::new(LogEntry::ConsensusPublisherGrpc)
+                    .event(LogEvent::UnexpectedError)
+                    .message(&format!(
+                        "Failed to serialize message for the gRPC subscribers! Error: {:?}",
+                        error
+                    )));
+                return;
+            },
+        };
+
+        // Send the response to each subscriber (without blocking)
+        let mut active_subscribers = self.active_subscribers.write();
+        active_subscribers.retain(|subscriber_id, subscriber_sender| {
+            match subscriber_sender.try_send(Ok(response.clone())) {
+                Ok(()) => true,
+                Err(TrySendError::Full(_)) => {
+                    // The subscriber is lagging. Disconnect it so that it can resubscribe.
+                    warn!(LogSchema::new(LogEntry::ConsensusPublisherGrpc)
+                        .event(LogEvent::Subscription)
+                        .message(&format!(
+                            "Disconnecting lagging gRPC subscriber! ID: {}",
+                            subscriber_id
+                        )));
+                    metrics::PUBLISHER_GRPC_DISCONNECTED_SUBSCRIBERS
+                        .with_label_values(&[SUBSCRIBER_LAGGING_LABEL])
+                        .inc();
+                    false
+                },
+                Err(TrySendError::Closed(_)) => {
+                    info!(LogSchema::new(LogEntry::ConsensusPublisherGrpc)
+                        .event(LogEvent::Subscription)
+                        .message(&format!(
+                            "Removed closed gRPC subscription! ID: {}",
+                            subscriber_id
+                        )));
+                    metrics::PUBLISHER_GRPC_DISCONNECTED_SUBSCRIBERS
+                        .with_label_values(&[SUBSCRIBER_CLOSED_LABEL])
+                        .inc();
+                    false
+                },
+            }
+        });
+        metrics::PUBLISHER_GRPC_NUM_ACTIVE_SUBSCRIBERS.set(active_subscribers.len() as i64);
+
+        // Update the sent message metrics
+        if !active_subscribers.is_empty() {
+            metrics::PUBLISHER_GRPC_SENT_MESSAGES
+                .with_label_values(&[message.get_label()])
+                .inc_by(active_subscribers.len() as u64);
+        }
+    }
+
+    /// Starts the gRPC server and the message forwarder
+    pub async fn start(self, mut message_receiver: mpsc::Receiver<ConsensusObserverDirectSend>) {
+        // Spawn the gRPC server
+        let address = self.grpc_config.address;
+        let service = ConsensusObserverServiceServer::new(self.clone());
+        tokio::spawn(async move {
+            info!(LogSchema::new(LogEntry::ConsensusPublisherGrpc)
+                .message(&format!("Starting the gRPC server on {}!", address)));
+            if let Err(error) = Server::builder().add_service(service).serve(address).await {
+                error!(LogSchema::new(LogEntry::ConsensusPublisherGrpc)
+                    .event(LogEvent::UnexpectedError)
+                    .message(&format!("The gRPC server failed! Error: {:?}", error)));
+            }
+        });
+
+        // Forward the published messages to the gRPC subscribers
+        while let Some(message) = message_receiver.recv().await {
+            self.send_to_subscribers(&message);
+        }
+
+        // Log the exit of the forwarder
+        error!(LogSchema::new(LogEntry::ConsensusPublisherGrpc)
+            .message("The gRPC message forwarder has stopped!"));
+    }
+}
+
+#[tonic::async_trait]
+impl ConsensusObserverService for ConsensusObserverGrpcPublisher {
+    type SubscribeStream = ReceiverStream<Result<SubscribeResponse, Status>>;
+
+    async fn subscribe(
+        &self,
+        request: Request<SubscribeRequest>,
+    ) -> Result<Response<Self::SubscribeStream>, Status> {
+        // Authenticate the request and register the subscriber
+        self.authenticate(request.metadata())?;
+        let subscriber_receiver = self.add_subscriber()?;
+
+        Ok(Response::new(ReceiverStream::new(subscriber_receiver)))
+    }
+}
+
+/// Creates the gRPC subscription response for the given message
+fn create_subscribe_response(
+    message: &ConsensusObserverDirectSend,
+) -> Result<SubscribeResponse, bcs::Error> {
+    // Identify the message type, epoch and round
+    let (message_type, epoch, round) = match message {
+        ConsensusObserverDirectSend::OrderedBlock(ordered_block) => {
+            let block_info = ordered_block.proof_block_info();
+            (
+                ObserverMessageType::OrderedBlock,
+                block_info.epoch(),
+                block_info.round(),
+            )
+        },
+        ConsensusObserverDirectSend::CommitDecision(commit_decision) => (
+            ObserverMessageType::CommitDecision,
+            commit_decision.epoch(),
+            commit_decision.round(),
+        ),
+        ConsensusObserverDirectSend::BlockPayload(block_payload) => (
+            ObserverMessageType::BlockPayload,
+            block_payload.epoch(),
+            block_payload.round(),
+        ),
+        ConsensusObserverDirectSend::OrderedBlockWithWindow(ordered_block_with_window) => {
+            let block_info = ordered_block_with_window.ordered_block().proof_block_info();
+            (
+                ObserverMessageType::OrderedBlockWithWindow,
+                block_info.epoch(),
+                block_info.round(),
+            )
+        },
+    };
+
+    // Serialize the message
+    let bcs_message = bcs::to_bytes(message)?;
+
+    Ok(SubscribeResponse {
+        message_type: message_type.into(),
+        epoch,
+        round,
+        bcs_message,
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::consensus_observer::network::observer_message::ConsensusObserverMessage;
+    use creditchain_crypto::HashValue;
+    use creditchain_types::{
+        aggregate_signature::AggregateSignature,
+        block_info::BlockInfo,
+        ledger_info::{LedgerInfo, LedgerInfoWithSignatures},
+    };
+
+    #[tokio::test]
+    async fn test_subscriber_limits_and_lagging() {
+        // Create a gRPC publisher with a small subscriber limit and buffer
+        let grpc_config = ConsensusObserverGrpcConfig {
+            enabled: true,
+            max_concurrent_subscribers: 2,
+            max_subscriber_buffer_size: 1,
+            ..Default::default()
+        };
+        let (grpc_publisher, _) = ConsensusObserverGrpcPublisher::new(grpc_config, 10);
+        assert!(!grpc_publisher.has_active_subscribers());
+
+        // Add the maximum number of subscribers and verify the next one is rejected
+        let mut subscriber_1 = grpc_publisher.add_subscriber().unwrap();
+        let _subscriber_2 = grpc_publisher.add_subscriber().unwrap();
+        let error = grpc_publisher.add_subscriber().unwrap_err();
+        assert_eq!(error.code(), tonic::Code::ResourceExhausted);
+
+        // Send a commit decision to the subscribers and verify the response
+        let commit_decision = create_commit_decision(10, 20);
+        grpc_publisher.send_to_subscribers(&commit_decision);
+        let response = subscriber_1.recv().await.unwrap().unwrap();
+        assert_eq!(
+            response.message_type,
+            i32::from(ObserverMessageType::CommitDecision)
+        );
+        assert_eq!(response.epoch, 10);
+        assert_eq!(response.round, 20);
+        let message: ConsensusObserverDirectSend = bcs::from_bytes(&response.bcs_message).unwrap();
+        assert_eq!(message, commit_decision);
+
+        // Send another message and verify that the lagging subscriber is disconnected
+        grpc_publisher.send_to_subscribers(&commit_decision);
+        assert_eq!(grpc_publisher.active_subscribers.read().len(), 1);
+
+        // Drop the remaining subscriber and verify it is removed on the next send
+        drop(subscriber_1);
+        grpc_publisher.send_to_subscribers(&commit_decision);
+        assert!(!grpc_publisher.has_active_subscribers());
+    }
+
+    #[tokio::test]
+    async fn test_dropped_messages() {
+        // Create a gRPC publisher with a forwarder channel of size 1
+        let grpc_config = ConsensusObserverGrpcConfig {
+            enabled: true,
+            ..Default::default()
+        };
+        let (grpc_publisher, mut message_receiver) =
+            ConsensusObserverGrpcPublisher::new(grpc_config, 1);
+
+        // Publish a message without subscribers and verify it is not forwarded
+        let commit_decision = create_commit_decision(10, 20);
+        grpc_publisher.publish_message(&commit_decision);
+        assert!(message_receiver.try_recv().is_err());
+
+        // Add a subscriber and publish messages until the channel is full
+        let mut subscriber = grpc_publisher.add_subscriber().unwrap();
+        grpc_publisher.publish_message(&commit_decision);
+        assert!(grpc_publisher.has_active_subscribers());
+
+        // Publish another message and verify the subscriber is disconnected
+        grpc_publisher.publish_message(&commit_decision);
+        assert!(!grpc_publisher.has_active_subscribers());
+        assert!(subscriber.recv().await.is_none());
+
+        // Verify that the first message is still forwarded
+        let message = message_receiver.try_recv().unwrap();
+        assert_eq!(message, commit_decision);
+    }
+
+    #[test]
+    fn test_authentication() {
+        // Create a gRPC publisher with passcode authentication
+        let grpc_config = ConsensusObserverGrpcConfig {
+            enabled: true,
+            authentication_configs: vec![AuthenticationConfig::PasscodeSha256(sha256::digest(
+                "passcode",
+            ))],
+            ..Default::default()
+        };
+        let (grpc_publisher, _) = ConsensusObserverGrpcPublisher::new(grpc_config, 10);
+
+        // Verify that missing and invalid passcodes are rejected
+        let mut metadata = MetadataMap::new();
+        assert!(grpc_publisher.authenticate(&metadata).is_err());
+        metadata.insert(
+            AUTHORIZATION_METADATA_KEY,
+            "Bearer invalid".parse().unwrap(),
+        );
+        assert!(grpc_publisher.authenticate(&metadata).is_err());
+
+        // Verify that the valid passcode is accepted
+        metadata.insert(
+            AUTHORIZATION_METADATA_KEY,
+            "Bearer passcode".parse().unwrap(),
+        );
+        assert!(grpc_publisher.authenticate(&metadata).is_ok());
+    }
+
+    /// Creates a commit decision message for the given epoch and round
+    fn create_commit_decision(epoch: u64, round: u64) -> ConsensusObserverDirectSend {
+        let block_info = BlockInfo::new(
+            epoch,
+            round,
+            HashValue::random(),
+            HashValue::random(),
+            0,
+            0,
+            None,
+        );
+        let ledger_info = LedgerInfoWithSignatures::new(
+            LedgerInfo::new(block_info, HashValue::random()),
+            AggregateSignature::empty(),
+        );
+        ConsensusObserverMessage::new_commit_decision_message(ledger_info)
+    }
+}
@@ -2,3 +2,4 @@
 // SPDX-License-Identifier: Apache-2.0
 
 pub mod consensus_publisher;
+pub mod grpc_service;
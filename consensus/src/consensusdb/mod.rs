@@ -11,7 +11,10 @@ use anyhow::Result;
 use creditchain_consensus_types::{block::Block, quorum_cert::QuorumCert};
 use creditchain_crypto::HashValue;
 use creditchain_logger::prelude::*;
-use creditchain_schemadb::{batch::SchemaBatch, schema::Schema, Options, DB, DEFAULT_COLUMN_FAMILY_NAME};
+use creditchain_schemadb::{
+    batch::SchemaBatch, schema::Schema, ColumnFamilyDescriptor, Options, DB,
+    DEFAULT_COLUMN_FAMILY_NAME,
+};
 use creditchain_storage_interface::CreditChainDbError;
 pub use schema::{
     block::BlockSchema,
@@ -48,18 +51,20 @@ pub struct ConsensusDB {
     db: DB,
 }
 
+const COLUMN_FAMILIES: [&str; 8] = [
+    /* UNUSED CF = */ DEFAULT_COLUMN_FAMILY_NAME,
+    BLOCK_CF_NAME,
+    QC_CF_NAME,
+    SINGLE_ENTRY_CF_NAME,
+    NODE_CF_NAME,
+    CERTIFIED_NODE_CF_NAME,
+    DAG_VOTE_CF_NAME,
+    "ordered_anchor_id", // deprecated CF
+];
+
 impl ConsensusDB {
     pub fn new<P: AsRef<Path> + Clone>(db_root_path: P) -> Self {
-        let column_families = vec![
-            /* UNUSED CF = */ DEFAULT_COLUMN_FAMILY_NAME,
-            BLOCK_CF_NAME,
-            QC_CF_NAME,
-            SINGLE_ENTRY_CF_NAME,
-            NODE_CF_NAME,
-            CERTIFIED_NODE_CF_NAME,
-            DAG_VOTE_CF_NAME,
-            "ordered_anchor_id", // deprecated CF
-        ];
+        let column_families = COLUMN_FAMILIES.to_vec();
 
         let path = db_root_path.as_ref().join(CONSENSUS_DB_NAME);
         let instant = Instant::now();
@@ -78,6 +83,20 @@ impl ConsensusDB {
         Self { db }
     }
 
+    /// Opens an existing ConsensusDB in read-only mode (e.g., for offline
+    /// analysis while the node is stopped). Unlike `new`, this never creates
+    /// the db, and returns an error if the db can't be opened.
+    pub fn open_readonly<P: AsRef<Path>>(db_root_path: P) -> Result<Self> {
+        let path = db_root_path.as_ref().join(CONSENSUS_DB_NAME);
+        let column_families = COLUMN_FAMILIES
+            .iter()
+            .map(|cf_name| ColumnFamilyDescriptor::new(*cf_name, Options::default()))
+            .collect();
+        let db = DB::open_cf_readonly(&Options::default(), path, "consensus", column_families)?;
+
+        Ok(Self { db })
+    }
+
     pub fn get_data(
         &self,
     ) -> Result<(
@@ -100,6 +100,16 @@ pub static COMMITTED_FAILED_ROUNDS_COUNT: Lazy<IntCounter> = Lazy::new(|| {
     .unwrap()
 });
 
+/// Count of the proposed txn notifications to mempool that were dropped (because
+/// the channel to mempool was full).
+pub static PROPOSED_TXNS_NOTIFICATIONS_DROPPED: Lazy<IntCounter> = Lazy::new(|| {
+    register_int_counter!(
+        "creditchain_consensus_proposed_txns_notifications_dropped",
+        "Count of the proposed txn notifications to mempool that were dropped."
+    )
+    .unwrap()
+});
+
 /// Count of the committed blocks since last restart.
 pub static COMMITTED_BLOCKS_COUNT: Lazy<IntCounter> = Lazy::new(|| {
     register_int_counter!(
@@ -23,6 +23,7 @@ use creditchain_storage_interface::DbReader;
 use creditchain_types::{
     account_config::NewBlockEvent, epoch_change::EpochChangeProof, epoch_state::EpochState,
 };
+use serde::Serialize;
 use std::{
     cmp::max,
     collections::{HashMap, HashSet},
@@ -517,6 +518,66 @@ impl ProposerAndVoterHeuristic {
             ),
         }
     }
+
+    /// Explains, for every candidate of the given epoch, which statistics were observed in the
+    /// history windows and which rule of the heuristic produced its weight.
+    /// Unlike `get_weights`, this doesn't update any metrics, so it can be used by offline tools.
+    pub fn explain_weights(
+        &self,
+        epoch: u64,
+        epoch_to_candidates: &HashMap<u64, Vec<Author>>,
+        history: &[NewBlockEvent],
+    ) -> Vec<ReputationWeightExplanation> {
+        assert!(epoch_to_candidates.contains_key(&epoch));
+
+        let votes = self.aggregation.count_votes(epoch_to_candidates, history);
+        let proposals = self
+            .aggregation
+            .count_proposals(epoch_to_candidates, history);
+        let failed_proposals = self
+            .aggregation
+            .count_failed_proposals(epoch_to_candidates, history);
+
+        epoch_to_candidates[&epoch]
+            .iter()
+            .map(|author| self.explain_weight(*author, &votes, &proposals, &failed_proposals))
+            .collect()
+    }
+
+    fn explain_weight(
+        &self,
+        author: Author,
+        votes: &HashMap<Author, u32>,
+        proposals: &HashMap<Author, u32>,
+        failed_proposals: &HashMap<Author, u32>,
+    ) -> ReputationWeightExplanation {
+        let cur_votes = *votes.get(&author).unwrap_or(&0);
+        let cur_proposals = *proposals.get(&author).unwrap_or(&0);
+        let cur_failed_proposals = *failed_proposals.get(&author).unwrap_or(&0);
+
+        let (reason, weight) = if cur_failed_proposals * 100
+            > (cur_proposals + cur_failed_proposals) * self.failure_threshold_percent
+        {
+            (
+                ReputationWeightReason::FailureThresholdExceeded,
+                self.failed_weight,
+            )
+        } else if cur_proposals > 0 || cur_votes > 0 {
+            (ReputationWeightReason::Active, self.active_weight)
+        } else {
+            (ReputationWeightReason::Inactive, self.inactive_weight)
+        };
+
+        ReputationWeightExplanation {
+            author,
+            votes: cur_votes,
+            proposals: cur_proposals,
+            failed_proposals: cur_failed_proposals,
+            failure_threshold_percent: self.failure_threshold_percent,
+            reason,
+            weight,
+        }
+    }
 }
 
 impl ReputationHeuristic for ProposerAndVoterHeuristic {
@@ -535,24 +596,40 @@ impl ReputationHeuristic for ProposerAndVoterHeuristic {
         epoch_to_candidates[&epoch]
             .iter()
             .map(|author| {
-                let cur_votes = *votes.get(author).unwrap_or(&0);
-                let cur_proposals = *proposals.get(author).unwrap_or(&0);
-                let cur_failed_proposals = *failed_proposals.get(author).unwrap_or(&0);
-
-                if cur_failed_proposals * 100
-                    > (cur_proposals + cur_failed_proposals) * self.failure_threshold_percent
-                {
-                    self.failed_weight
-                } else if cur_proposals > 0 || cur_votes > 0 {
-                    self.active_weight
-                } else {
-                    self.inactive_weight
-                }
+                self.explain_weight(*author, &votes, &proposals, &failed_proposals)
+                    .weight
             })
             .collect()
     }
 }
 
+/// Rule of the `ProposerAndVoterHeuristic` that determined a candidate's weight.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReputationWeightReason {
+    /// Failed proposal rounds are strictly above the failure threshold.
+    FailureThresholdExceeded,
+    /// The candidate proposed or voted at least once within the windows.
+    Active,
+    /// The candidate neither proposed nor voted within the windows.
+    Inactive,
+}
+
+/// Per-candidate statistics and the resulting reputation weight, before voting power is applied.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+pub struct ReputationWeightExplanation {
+    pub author: Author,
+    /// Number of committed blocks in the voter window that the candidate voted for.
+    pub votes: u32,
+    /// Number of committed blocks in the proposer window proposed by the candidate.
+    pub proposals: u32,
+    /// Number of rounds in the proposer window in which the candidate failed to propose.
+    pub failed_proposals: u32,
+    pub failure_threshold_percent: u32,
+    pub reason: ReputationWeightReason,
+    pub weight: u64,
+}
+
 /// Committed history based proposer election implementation that could help bias towards
 /// successful leaders to help improve performance.
 pub struct LeaderReputation {
@@ -715,20 +792,7 @@ impl ProposerElection for LeaderReputation {
             .map(|(i, w)| *w as u128 * self.voting_powers[i] as u128)
             .collect();
 
-        let state = if self.use_root_hash {
-            [
-                root_hash.to_vec(),
-                self.epoch.to_le_bytes().to_vec(),
-                round.to_le_bytes().to_vec(),
-            ]
-            .concat()
-        } else {
-            [
-                self.epoch.to_le_bytes().to_vec(),
-                round.to_le_bytes().to_vec(),
-            ]
-            .concat()
-        };
+        let state = election_state(self.use_root_hash, root_hash, self.epoch, round);
 
         let chosen_index = choose_index(stake_weights, state);
         (proposers[chosen_index], voting_power_participation_ratio)
@@ -745,6 +809,25 @@ impl ProposerElection for LeaderReputation {
     }
 }
 
+/// Seed used to pick the proposer of a round out of the stake weighted candidates.
+pub(crate) fn election_state(
+    use_root_hash: bool,
+    root_hash: HashValue,
+    epoch: u64,
+    round: Round,
+) -> Vec<u8> {
+    if use_root_hash {
+        [
+            root_hash.to_vec(),
+            epoch.to_le_bytes().to_vec(),
+            round.to_le_bytes().to_vec(),
+        ]
+        .concat()
+    } else {
+        [epoch.to_le_bytes().to_vec(), round.to_le_bytes().to_vec()].concat()
+    }
+}
+
 pub(crate) fn extract_epoch_to_proposers_impl(
     next_epoch_states_and_cur_epoch_rounds: &[(&EpochState, u64)],
     epoch: u64,
@@ -4,6 +4,7 @@
 
 use super::leader_reputation::{
     extract_epoch_to_proposers_impl, CreditChainDBBackend, ProposerAndVoterHeuristic,
+    ReputationWeightReason,
 };
 use crate::liveness::{
     leader_reputation::{
@@ -275,6 +276,46 @@ fn test_proposer_and_voter_heuristic() {
     );
 }
 
+#[test]
+fn test_proposer_and_voter_heuristic_explanations() {
+    let mut example1 = Example1::new(5);
+    let validators0 = example1.validators0.clone();
+    let epoch_to_validators0 = HashMap::from([(0u64, validators0.clone())]);
+    let heuristic =
+        ProposerAndVoterHeuristic::new(example1.validators0[0], 100, 10, 1, 49, 2, 5, false);
+
+    example1.step1();
+    let history = example1.history();
+    let explanations = heuristic.explain_weights(0, &epoch_to_validators0, &history);
+    assert_eq!(
+        explanations.iter().map(|e| e.weight).collect::<Vec<_>>(),
+        heuristic.get_weights(0, &epoch_to_validators0, &history)
+    );
+    assert_eq!(
+        explanations.iter().map(|e| e.author).collect::<Vec<_>>(),
+        validators0
+    );
+    for explanation in explanations {
+        match explanation.reason {
+            ReputationWeightReason::FailureThresholdExceeded => {
+                assert_eq!(explanation.weight, 1);
+                assert!(
+                    explanation.failed_proposals * 100
+                        > (explanation.proposals + explanation.failed_proposals) * 49
+                );
+            },
+            ReputationWeightReason::Active => {
+                assert_eq!(explanation.weight, 100);
+                assert!(explanation.proposals > 0 || explanation.votes > 0);
+            },
+            ReputationWeightReason::Inactive => {
+                assert_eq!(explanation.weight, 10);
+                assert_eq!(explanation.proposals + explanation.votes, 0);
+            },
+        }
+    }
+}
+
 /// #### LeaderReputation test ####
 
 #[test]
@@ -380,7 +380,12 @@ impl PipelineBuilder {
         } = rx;
 
         let prepare_fut = spawn_shared_fut(
-            Self::prepare(self.block_preparer.clone(), block.clone(), qc_rx),
+            Self::prepare(
+                self.block_preparer.clone(),
+                self.txn_notifier.clone(),
+                block.clone(),
+                qc_rx,
+            ),
             Some(&mut abort_handles),
         );
         let execute_fut = spawn_shared_fut(
@@ -493,9 +498,11 @@ impl PipelineBuilder {
     }
 
     /// Precondition: Block is inserted into block tree (all ancestors are available)
-    /// What it does: Wait for all data becomes available and verify transaction signatures
+    /// What it does: Wait for all data becomes available, notify mempool about the proposed
+    /// transactions and verify transaction signatures
     async fn prepare(
         preparer: Arc<BlockPreparer>,
+        mempool_notifier: Arc<dyn TxnNotifier>,
         block: Arc<Block>,
         qc_rx: oneshot::Receiver<Arc<QuorumCert>>,
     ) -> TaskResult<PrepareResult> {
@@ -526,6 +533,14 @@ impl PipelineBuilder {
                 },
             }
         };
+
+        // notify mempool about proposed transactions
+        if let Err(e) = mempool_notifier.notify_proposed_txns(&input_txns) {
+            warn!(
+                error = ?e, "Failed to notify mempool of proposed txns",
+            );
+        }
+
         let sig_verification_start = Instant::now();
         let sig_verified_txns: Vec<SignatureVerifiedTransaction> = SIG_VERIFY_POOL.install(|| {
             let num_txns = input_txns.len();
@@ -1,9 +1,10 @@
 // Copyright © CreditChain Research Team
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::{error::MempoolError, monitor};
+use crate::{counters, error::MempoolError, monitor};
 use anyhow::{format_err, Result};
 use creditchain_consensus_types::common::RejectedTransactionSummary;
+use creditchain_infallible::Mutex;
 use creditchain_mempool::QuorumStoreRequest;
 use creditchain_types::transaction::{SignedTransaction, TransactionStatus};
 use futures::channel::{mpsc, oneshot};
@@ -21,11 +22,20 @@ pub trait TxnNotifier: Send + Sync {
         txns: &[SignedTransaction],
         statuses: &[TransactionStatus],
     ) -> Result<(), MempoolError>;
+
+    /// Notification of txns which were pulled into a block proposal (before the block is
+    /// executed). This is only used to track the txn lifecycle in mempool, so no ack is
+    /// expected. The notification is best-effort: it is dropped if mempool is not keeping up.
+    fn notify_proposed_txns(&self, txns: &[SignedTransaction]) -> Result<(), MempoolError>;
 }
 
 /// Execution -> Mempool notification of failed transactions.
 pub struct MempoolNotifier {
     consensus_to_mempool_sender: mpsc::Sender<QuorumStoreRequest>,
+    /// A dedicated sender for proposed txn notifications. Unlike a fresh clone of the
+    /// sender (which always has a free slot), this sender fails once the channel is
+    /// full, so the notifications can't crowd out other requests to mempool.
+    proposed_txns_sender: Mutex<mpsc::Sender<QuorumStoreRequest>>,
     /// Timeout for consensus to get an ack from mempool for executed transactions (in milliseconds)
     mempool_executed_txn_timeout_ms: u64,
 }
@@ -37,6 +47,7 @@ impl MempoolNotifier {
         mempool_executed_txn_timeout_ms: u64,
     ) -> Self {
         Self {
+            proposed_txns_sender: Mutex::new(consensus_to_mempool_sender.clone()),
             consensus_to_mempool_sender,
             mempool_executed_txn_timeout_ms,
         }
@@ -89,4 +100,53 @@ impl TxnNotifier for MempoolNotifier {
             Ok(())
         }
     }
+
+    fn notify_proposed_txns(&self, txns: &[SignedTransaction]) -> Result<(), MempoolError> {
+        if txns.is_empty() {
+            return Ok(());
+        }
+
+        let proposed_txns = txns.iter().map(|txn| txn.committed_hash()).collect();
+        let req = QuorumStoreRequest::ProposedNotification(proposed_txns);
+
+        // send to shared mempool (the notification is dropped if the channel is full)
+        match self.proposed_txns_sender.lock().try_send(req) {
+            Ok(()) => Ok(()),
+            Err(error) if error.is_full() => {
+                counters::PROPOSED_TXNS_NOTIFICATIONS_DROPPED.inc();
+                Ok(())
+            },
+            Err(error) => Err(anyhow::Error::from(error).into()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::create_signed_transaction;
+
+    #[test]
+    fn test_proposed_txns_notifications_dropped() {
+        // Create a mempool notifier with a small channel
+        let (consensus_to_mempool_sender, mut consensus_to_mempool_receiver) = mpsc::channel(1);
+        let mempool_notifier = MempoolNotifier::new(consensus_to_mempool_sender, 100);
+
+        // Send many notifications (without mempool processing them)
+        let txns = vec![create_signed_transaction(1)];
+        for _ in 0..10 {
+            mempool_notifier.notify_proposed_txns(&txns).unwrap();
+        }
+
+        // Verify that the notifications beyond the channel capacity were dropped
+        let mut num_notifications = 0;
+        while let Ok(Some(request)) = consensus_to_mempool_receiver.try_next() {
+            assert!(matches!(
+                request,
+                QuorumStoreRequest::ProposedNotification(_)
+            ));
+            num_notifications += 1;
+        }
+        assert_eq!(num_notifications, 2); // The buffer size, plus the sender's slot
+    }
 }
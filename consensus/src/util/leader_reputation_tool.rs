@@ -0,0 +1,431 @@
+// Copyright © CreditChain Research Team
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    consensusdb::{ConsensusDB, CONSENSUS_DB_NAME},
+    liveness::{
+        leader_reputation::{
+            election_state, extract_epoch_to_proposers, ProposerAndVoterHeuristic,
+            ReputationWeightExplanation,
+        },
+        proposer_election::choose_index,
+    },
+};
+use anyhow::{bail, ensure, format_err, Result};
+use creditchain_consensus_types::common::{Author, Round};
+use creditchain_crypto::HashValue;
+use creditchain_storage_interface::{
+    state_store::state_view::db_state_view::DbStateViewAtVersion, DbReader,
+};
+use creditchain_types::{
+    account_address::AccountAddress,
+    account_config::NewBlockEvent,
+    epoch_state::EpochState,
+    on_chain_config::{
+        LeaderReputationType, OnChainConfig, OnChainConsensusConfig, ProposerElectionType,
+    },
+    transaction::Version,
+};
+use clap::Parser;
+use serde::Serialize;
+use std::{collections::HashMap, path::PathBuf, sync::Arc};
+
+#[derive(Parser)]
+#[clap(
+    about = "Replay leader reputation from committed NewBlockEvents and explain validator weights."
+)]
+pub struct Command {
+    /// Storage directory of the node, containing both the ledger db and the consensus db.
+    /// Both dbs are opened read-only, but the node must not be running.
+    #[clap(long, value_parser)]
+    pub db_dir: PathBuf,
+
+    /// Epoch to analyze. If None, the latest epoch is used.
+    #[clap(long)]
+    pub epoch: Option<u64>,
+
+    /// Round to elect the proposer for. If None, the round after the last committed block of the
+    /// epoch is used.
+    #[clap(long)]
+    pub round: Option<Round>,
+
+    /// Overrides the proposer window size derived from the on-chain consensus config.
+    #[clap(long)]
+    pub proposer_window_size: Option<usize>,
+
+    /// Overrides the voter window size derived from the on-chain consensus config.
+    #[clap(long)]
+    pub voter_window_size: Option<usize>,
+
+    /// Print the report as json instead of a table.
+    #[clap(long)]
+    pub json: bool,
+}
+
+/// Result of replaying the leader election of a single round.
+#[derive(Debug, Serialize)]
+pub struct LeaderReputationReport {
+    pub epoch: u64,
+    pub round: Round,
+    /// Latest round that was taken into account, i.e. `round - exclude_round`.
+    pub target_round: Round,
+    pub proposer_window_size: usize,
+    pub voter_window_size: usize,
+    pub reputation_window_from_stale_end: bool,
+    /// (epoch, round) of the oldest and newest committed block in the history window.
+    pub history_range: Option<((u64, Round), (u64, Round))>,
+    pub history_len: usize,
+    pub use_root_hash: bool,
+    pub root_hash: HashValue,
+    pub elected_proposer: Author,
+    pub validators: Vec<ValidatorReputationReport>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ValidatorReputationReport {
+    #[serde(flatten)]
+    pub explanation: ReputationWeightExplanation,
+    pub voting_power: u64,
+    /// Reputation weight multiplied by voting power, if the on-chain config asks for it.
+    pub stake_weight: u128,
+    pub selection_probability: f64,
+    /// Blocks in the consensus db proposed by the validator that are not committed yet.
+    pub uncommitted_proposals: u32,
+    /// Quorum certificates in the consensus db for uncommitted blocks signed by the validator.
+    pub uncommitted_votes: u32,
+}
+
+impl Command {
+    /// Runs the analysis over the given ledger db (opened by the caller from `db_dir`, so
+    /// that consensus doesn't depend on the db implementation).
+    pub async fn run(self, creditchain_db: Arc<dyn DbReader>) -> Result<()> {
+        let report = self.analyze(creditchain_db)?;
+        if self.json {
+            println!("{}", serde_json::to_string_pretty(&report)?);
+        } else {
+            print_report(&report);
+        }
+
+        Ok(())
+    }
+
+    pub fn analyze(&self, creditchain_db: Arc<dyn DbReader>) -> Result<LeaderReputationReport> {
+        let epoch = match self.epoch {
+            Some(epoch) => epoch,
+            None => creditchain_db.get_latest_epoch_state()?.epoch,
+        };
+        ensure!(epoch > 0, "Genesis epoch has no leader election.");
+
+        let epoch_start = creditchain_db
+            .get_epoch_ending_ledger_infos(epoch - 1, epoch)?
+            .ledger_info_with_sigs
+            .pop()
+            .ok_or_else(|| {
+                format_err!("Epoch ending ledger info of epoch {} not found.", epoch - 1)
+            })?;
+        let epoch_state = epoch_start
+            .ledger_info()
+            .next_epoch_state()
+            .cloned()
+            .ok_or_else(|| {
+                format_err!(
+                    "Ledger info of epoch {} has no next epoch state.",
+                    epoch - 1
+                )
+            })?;
+        let last_version = last_version_of_epoch(&creditchain_db, epoch)?;
+        let (_, _, last_block) = creditchain_db.get_block_info_by_version(last_version)?;
+
+        // Consensus config that was in effect at the beginning of the epoch.
+        let onchain_config = OnChainConsensusConfig::fetch_config(
+            &creditchain_db.state_view_at_version(Some(epoch_start.ledger_info().version()))?,
+        )
+        .ok_or_else(|| {
+            format_err!("Consensus config not found for epoch {epoch}, is the state pruned?")
+        })?;
+        let leader_reputation_type = match onchain_config.proposer_election_type() {
+            ProposerElectionType::LeaderReputation(leader_reputation_type) => {
+                leader_reputation_type
+            },
+            other => bail!("Epoch {epoch} doesn't use leader reputation: {other:?}"),
+        };
+        let (LeaderReputationType::ProposerAndVoter(proposer_and_voter_config)
+        | LeaderReputationType::ProposerAndVoterV2(proposer_and_voter_config)) =
+            leader_reputation_type;
+
+        let proposers = epoch_state
+            .verifier
+            .get_ordered_account_addresses_iter()
+            .collect::<Vec<_>>();
+        let proposer_window_size = self.proposer_window_size.unwrap_or(
+            proposers.len() * proposer_and_voter_config.proposer_window_num_validators_multiplier,
+        );
+        let voter_window_size = self.voter_window_size.unwrap_or(
+            proposers.len() * proposer_and_voter_config.voter_window_num_validators_multiplier,
+        );
+        let window_size = std::cmp::max(proposer_window_size, voter_window_size);
+        let reputation_window_from_stale_end =
+            leader_reputation_type.use_reputation_window_from_stale_end();
+
+        let round = self.round.unwrap_or(last_block.round() + 1);
+        let target_round = round.saturating_sub(onchain_config.leader_reputation_exclude_round());
+        let history = fetch_history(
+            &creditchain_db,
+            &last_block,
+            epoch,
+            target_round,
+            window_size,
+        )?;
+
+        let epoch_to_proposers = extract_epoch_proposers(
+            &creditchain_db,
+            &epoch_state,
+            proposer_and_voter_config.use_history_from_previous_epoch_max_count,
+            &proposers,
+            window_size as u64,
+        )?;
+
+        let heuristic = ProposerAndVoterHeuristic::new(
+            AccountAddress::ZERO,
+            proposer_and_voter_config.active_weight,
+            proposer_and_voter_config.inactive_weight,
+            proposer_and_voter_config.failed_weight,
+            proposer_and_voter_config.failure_threshold_percent,
+            voter_window_size,
+            proposer_window_size,
+            reputation_window_from_stale_end,
+        );
+        let events: Vec<_> = history.iter().map(|(event, _)| event.clone()).collect();
+        let explanations = heuristic.explain_weights(epoch, &epoch_to_proposers, &events);
+
+        let voting_powers: Vec<u64> = if proposer_and_voter_config.weight_by_voting_power {
+            proposers
+                .iter()
+                .map(|p| epoch_state.verifier.get_voting_power(p).unwrap_or(0))
+                .collect()
+        } else {
+            vec![1; proposers.len()]
+        };
+        let stake_weights: Vec<u128> = explanations
+            .iter()
+            .zip(voting_powers.iter())
+            .map(|(explanation, voting_power)| explanation.weight as u128 * *voting_power as u128)
+            .collect();
+        let total_stake_weight: u128 = stake_weights.iter().sum();
+
+        let use_root_hash = leader_reputation_type.use_root_hash_for_seed();
+        let root_hash = match history.iter().map(|(_, version)| *version).max() {
+            Some(version) if use_root_hash => creditchain_db.get_accumulator_root_hash(version)?,
+            _ => HashValue::zero(),
+        };
+        let chosen_index = choose_index(
+            stake_weights.clone(),
+            election_state(use_root_hash, root_hash, epoch, round),
+        );
+
+        let (uncommitted_proposals, uncommitted_votes) =
+            self.count_uncommitted(epoch, last_block.round(), &proposers)?;
+
+        let validators = explanations
+            .into_iter()
+            .zip(voting_powers)
+            .zip(stake_weights)
+            .map(|((explanation, voting_power), stake_weight)| {
+                let author = explanation.author;
+                ValidatorReputationReport {
+                    explanation,
+                    voting_power,
+                    stake_weight,
+                    selection_probability: if total_stake_weight > 0 {
+                        stake_weight as f64 / total_stake_weight as f64
+                    } else {
+                        0.0
+                    },
+                    uncommitted_proposals: *uncommitted_proposals.get(&author).unwrap_or(&0),
+                    uncommitted_votes: *uncommitted_votes.get(&author).unwrap_or(&0),
+                }
+            })
+            .collect();
+
+        Ok(LeaderReputationReport {
+            epoch,
+            round,
+            target_round,
+            proposer_window_size,
+            voter_window_size,
+            reputation_window_from_stale_end,
+            history_range: history.first().zip(history.last()).map(|(newest, oldest)| {
+                (
+                    (oldest.0.epoch(), oldest.0.round()),
+                    (newest.0.epoch(), newest.0.round()),
+                )
+            }),
+            history_len: history.len(),
+            use_root_hash,
+            root_hash,
+            elected_proposer: proposers[chosen_index],
+            validators,
+        })
+    }
+
+    /// Counts proposals and votes per validator for blocks of the epoch that are still in the
+    /// consensus db and haven't been committed yet. Nothing is counted if there is no
+    /// consensus db (e.g., for a db restored from a backup).
+    pub(crate) fn count_uncommitted(
+        &self,
+        epoch: u64,
+        last_committed_round: Round,
+        proposers: &[Author],
+    ) -> Result<(HashMap<Author, u32>, HashMap<Author, u32>)> {
+        let mut proposals = HashMap::new();
+        let mut votes = HashMap::new();
+        if !self.db_dir.join(CONSENSUS_DB_NAME).exists() {
+            return Ok((proposals, votes));
+        }
+
+        let consensus_db = ConsensusDB::open_readonly(&self.db_dir)?;
+        let (_, _, blocks, qcs) = consensus_db.get_data()?;
+        for block in blocks {
+            if block.epoch() != epoch || block.round() <= last_committed_round {
+                continue;
+            }
+            if let Some(author) = block.author() {
+                *proposals.entry(author).or_insert(0) += 1;
+            }
+        }
+        for qc in qcs {
+            let certified_block = qc.certified_block();
+            if certified_block.epoch() != epoch || certified_block.round() <= last_committed_round {
+                continue;
+            }
+            for voter in qc.ledger_info().get_voters(proposers) {
+                *votes.entry(voter).or_insert(0) += 1;
+            }
+        }
+
+        Ok((proposals, votes))
+    }
+}
+
+fn last_version_of_epoch(creditchain_db: &Arc<dyn DbReader>, epoch: u64) -> Result<Version> {
+    let latest_epoch = creditchain_db.get_latest_epoch_state()?.epoch;
+    ensure!(
+        epoch <= latest_epoch,
+        "Epoch {} is newer than the latest epoch {} in the db.",
+        epoch,
+        latest_epoch
+    );
+    if epoch == latest_epoch {
+        Ok(creditchain_db.get_latest_ledger_info_version()?)
+    } else {
+        creditchain_db
+            .get_epoch_ending_ledger_infos(epoch, epoch + 1)?
+            .ledger_info_with_sigs
+            .first()
+            .map(|li| li.ledger_info().version())
+            .ok_or_else(|| format_err!("Epoch ending ledger info of epoch {epoch} not found."))
+    }
+}
+
+/// Returns up to `window_size` committed NewBlockEvents at or before (epoch, target_round),
+/// newest first, the same way `CreditChainDBBackend` feeds them to the heuristic.
+fn fetch_history(
+    creditchain_db: &Arc<dyn DbReader>,
+    last_block: &NewBlockEvent,
+    epoch: u64,
+    target_round: Round,
+    window_size: usize,
+) -> Result<Vec<(NewBlockEvent, Version)>> {
+    let mut history = Vec::with_capacity(window_size);
+    let mut height = Some(last_block.height());
+    while let Some(cur_height) = height {
+        if history.len() >= window_size {
+            break;
+        }
+        let (version, _, event) = creditchain_db.get_block_info_by_height(cur_height)?;
+        if (event.epoch(), event.round()) <= (epoch, target_round) {
+            history.push((event, version));
+        }
+        height = cur_height.checked_sub(1);
+    }
+    Ok(history)
+}
+
+/// Mirrors `EpochManager::extract_epoch_proposers`, but fails instead of falling back to the
+/// current epoch only, so that the analysis never silently diverges from what nodes computed.
+fn extract_epoch_proposers(
+    creditchain_db: &Arc<dyn DbReader>,
+    epoch_state: &EpochState,
+    use_history_from_previous_epoch_max_count: u32,
+    proposers: &[Author],
+    needed_rounds: u64,
+) -> Result<HashMap<u64, Vec<Author>>> {
+    let first_epoch_to_consider = std::cmp::max(
+        if epoch_state.epoch == 1 { 1 } else { 2 },
+        epoch_state
+            .epoch
+            .saturating_sub(use_history_from_previous_epoch_max_count as u64),
+    );
+    if epoch_state.epoch > first_epoch_to_consider {
+        let proof = creditchain_db
+            .get_epoch_ending_ledger_infos(first_epoch_to_consider - 1, epoch_state.epoch)?;
+        extract_epoch_to_proposers(proof, epoch_state.epoch, proposers, needed_rounds)
+    } else {
+        Ok(HashMap::from([(epoch_state.epoch, proposers.to_vec())]))
+    }
+}
+
+fn print_report(report: &LeaderReputationReport) {
+    println!(
+        "Epoch {}, round {} (history up to round {}), elected proposer: {}",
+        report.epoch, report.round, report.target_round, report.elected_proposer
+    );
+    println!(
+        "Proposer window: {}, voter window: {}, from stale end: {}, history: {} blocks {}",
+        report.proposer_window_size,
+        report.voter_window_size,
+        report.reputation_window_from_stale_end,
+        report.history_len,
+        report
+            .history_range
+            .map(|(from, to)| format!("from {:?} to {:?}", from, to))
+            .unwrap_or_default(),
+    );
+    println!(
+        "{:<66} {:>6} {:>9} {:>6} {:>6} {:>26} {:>8} {:>12} {:>8} {:>8} {:>8}",
+        "validator",
+        "props",
+        "failures",
+        "fail%",
+        "votes",
+        "reason",
+        "weight",
+        "voting_power",
+        "chance%",
+        "pending",
+        "p_votes",
+    );
+    for validator in &report.validators {
+        let explanation = &validator.explanation;
+        let total_proposals = explanation.proposals + explanation.failed_proposals;
+        let failure_percent = if total_proposals > 0 {
+            explanation.failed_proposals as f64 * 100.0 / total_proposals as f64
+        } else {
+            0.0
+        };
+        println!(
+            "{:<66} {:>6} {:>9} {:>6.1} {:>6} {:>26} {:>8} {:>12} {:>8.3} {:>8} {:>8}",
+            explanation.author.to_hex_literal(),
+            explanation.proposals,
+            explanation.failed_proposals,
+            failure_percent,
+            explanation.votes,
+            format!("{:?}", explanation.reason),
+            explanation.weight,
+            validator.voting_power,
+            validator.selection_probability * 100.0,
+            validator.uncommitted_proposals,
+            validator.uncommitted_votes,
+        );
+    }
+}
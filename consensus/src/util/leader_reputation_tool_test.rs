@@ -0,0 +1,118 @@
+// Copyright © CreditChain Research Team
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    consensusdb::{ConsensusDB, CONSENSUS_DB_NAME},
+    util::leader_reputation_tool::Command,
+};
+use creditchain_bitvec::BitVec;
+use creditchain_consensus_types::{
+    block::{block_test_utils::certificate_for_genesis, Block},
+    common::{Author, Payload, Round},
+    quorum_cert::QuorumCert,
+    vote_data::VoteData,
+};
+use creditchain_crypto::{hash::CryptoHash, HashValue};
+use creditchain_temppath::TempPath;
+use creditchain_types::{
+    aggregate_signature::AggregateSignature,
+    block_info::BlockInfo,
+    ledger_info::{LedgerInfo, LedgerInfoWithSignatures},
+    validator_signer::ValidatorSigner,
+};
+use std::{collections::HashMap, path::Path};
+
+#[test]
+fn test_count_uncommitted() {
+    let signers: Vec<_> = (0..3).map(|_| ValidatorSigner::random(None)).collect();
+    let proposers: Vec<_> = signers.iter().map(|signer| signer.author()).collect();
+
+    // Create blocks for rounds 1 to 4 of epoch 1 (the genesis qc certifies epoch 1)
+    let blocks: Vec<_> = (1..=4)
+        .map(|round| {
+            Block::new_proposal(
+                Payload::empty(false, true),
+                round,
+                round,
+                certificate_for_genesis(),
+                &signers[(round as usize - 1) % signers.len()],
+                Vec::new(),
+            )
+            .unwrap()
+        })
+        .collect();
+
+    // Create qcs for rounds 2 to 4 of epoch 1, and one qc for epoch 2
+    let qcs = vec![
+        create_quorum_cert(1, 2, vec![true, true, true]),
+        create_quorum_cert(1, 3, vec![true, true, false]),
+        create_quorum_cert(1, 4, vec![false, true, true]),
+        create_quorum_cert(2, 5, vec![true, true, true]),
+    ];
+
+    // Save the blocks and qcs, and close the db
+    let db_dir = TempPath::new();
+    ConsensusDB::new(db_dir.path())
+        .save_blocks_and_quorum_certificates(blocks, qcs)
+        .unwrap();
+
+    // Verify that only the uncommitted blocks and qcs of epoch 1 are counted (round 2 is committed)
+    let (proposals, votes) = create_command(db_dir.path())
+        .count_uncommitted(1, 2, &proposers)
+        .unwrap();
+    assert_eq!(
+        proposals,
+        HashMap::from([(proposers[0], 1), (proposers[2], 1)])
+    );
+    assert_eq!(
+        votes,
+        HashMap::from([(proposers[0], 1), (proposers[1], 2), (proposers[2], 1)])
+    );
+}
+
+#[test]
+fn test_count_uncommitted_without_consensus_db() {
+    // Verify that nothing is counted, and that no consensus db is created
+    let db_dir = TempPath::new();
+    db_dir.create_as_dir().unwrap();
+    let (proposals, votes) = create_command(db_dir.path())
+        .count_uncommitted(1, 0, &[Author::random()])
+        .unwrap();
+    assert!(proposals.is_empty());
+    assert!(votes.is_empty());
+    assert!(!db_dir.path().join(CONSENSUS_DB_NAME).exists());
+}
+
+/// Creates a command that analyzes the dbs in the given directory
+fn create_command(db_dir: &Path) -> Command {
+    Command {
+        db_dir: db_dir.to_path_buf(),
+        epoch: None,
+        round: None,
+        proposer_window_size: None,
+        voter_window_size: None,
+        json: false,
+    }
+}
+
+/// Creates a qc for a (random) block at the given epoch and round, signed by the given voters
+fn create_quorum_cert(epoch: u64, round: Round, voters: Vec<bool>) -> QuorumCert {
+    let block_info = |round| {
+        BlockInfo::new(
+            epoch,
+            round,
+            HashValue::random(),
+            HashValue::zero(),
+            0,
+            0,
+            None,
+        )
+    };
+    let vote_data = VoteData::new(block_info(round), block_info(round - 1));
+    let ledger_info = LedgerInfo::new(BlockInfo::empty(), vote_data.hash());
+    let signatures = AggregateSignature::new(BitVec::from(voters), None);
+    QuorumCert::new(
+        vote_data,
+        LedgerInfoWithSignatures::new(ledger_info, signatures),
+    )
+}
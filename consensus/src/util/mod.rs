@@ -9,6 +9,9 @@ use creditchain_types::{
 };
 
 pub mod db_tool;
+pub mod leader_reputation_tool;
+#[cfg(test)]
+mod leader_reputation_tool_test;
 #[cfg(any(test, feature = "fuzzing"))]
 pub mod mock_time_service;
 pub mod time_service;
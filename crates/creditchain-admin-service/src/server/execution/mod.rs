@@ -0,0 +1,47 @@
+// Copyright © CreditChain Research Team
+// SPDX-License-Identifier: Apache-2.0
+
+use creditchain_block_executor::conflict_diagnostics::recent_conflict_reports;
+use creditchain_logger::info;
+use creditchain_system_utils::utils::{reply_with, reply_with_status};
+use http::header::{HeaderValue, CONTENT_TYPE};
+use hyper::{Body, Request, Response, StatusCode};
+use std::collections::HashMap;
+
+/// Returns the most recent BlockSTM conflict reports (as JSON), newest first. The optional
+/// `limit` query parameter restricts the number of returned reports. Reports are only
+/// recorded if conflict diagnostics are enabled in the execution config.
+pub async fn handle_conflict_reports_request(req: Request<Body>) -> hyper::Result<Response<Body>> {
+    let query = req.uri().query().unwrap_or("");
+    let query_pairs: HashMap<_, _> = url::form_urlencoded::parse(query.as_bytes()).collect();
+
+    let limit: Option<usize> = match query_pairs.get("limit") {
+        Some(val) => match val.parse() {
+            Ok(val) => Some(val),
+            Err(err) => return Ok(reply_with_status(StatusCode::BAD_REQUEST, err.to_string())),
+        },
+        None => None,
+    };
+
+    info!("Dumping BlockSTM conflict reports.");
+
+    let mut reports = recent_conflict_reports();
+    reports.reverse();
+    if let Some(limit) = limit {
+        reports.truncate(limit);
+    }
+
+    match serde_json::to_vec(&reports) {
+        Ok(body) => Ok(reply_with(
+            vec![(CONTENT_TYPE, HeaderValue::from_static("application/json"))],
+            body,
+        )),
+        Err(e) => {
+            info!("Failed to serialize BlockSTM conflict reports: {e:?}");
+            Ok(reply_with_status(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                e.to_string(),
+            ))
+        },
+    }
+}
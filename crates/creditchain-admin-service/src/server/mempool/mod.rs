@@ -1,13 +1,16 @@
 // Copyright © CreditChain Research Team
 // SPDX-License-Identifier: Apache-2.0
 
-use creditchain_logger::info;
+use creditchain_crypto::HashValue;
+use creditchain_logger::{info, warn};
 use creditchain_mempool::{MempoolClientRequest, MempoolClientSender};
 use creditchain_system_utils::utils::{reply_with, reply_with_status};
 use creditchain_types::account_address::AccountAddress;
-use futures_channel::oneshot::Canceled;
-use http::{Request, Response, StatusCode};
+use futures_channel::oneshot::{self, Canceled};
+use http::{header::CONTENT_TYPE, HeaderValue, Request, Response, StatusCode};
 use hyper::Body;
+use serde::Serialize;
+use std::{collections::HashMap, time::Duration};
 
 pub async fn mempool_handle_parking_lot_address_request(
     _req: Request<Body>,
@@ -37,19 +40,231 @@ pub async fn mempool_handle_parking_lot_address_request(
     }
 }
 
+/// Returns the detailed state of all mempool transactions of the
+/// account given by the `account` query parameter (as JSON).
+pub async fn mempool_handle_account_transactions_request(
+    req: Request<Body>,
+    mempool_client_sender: MempoolClientSender,
+) -> hyper::Result<Response<Body>> {
+    let query_pairs = parse_query_pairs(&req);
+    let account: AccountAddress = match parse_required_query_param(&query_pairs, "account") {
+        Ok(account) => account,
+        Err(response) => return Ok(response),
+    };
+
+    let result = send_mempool_request(mempool_client_sender, |sender| {
+        MempoolClientRequest::GetAccountTransactionInfos(account, sender)
+    })
+    .await;
+    match result {
+        Ok(txn_infos) => Ok(reply_with_json(&txn_infos)),
+        Err(e) => {
+            info!("Failed to get account transactions from mempool: {e:?}");
+            Ok(reply_with_status(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                e.to_string(),
+            ))
+        },
+    }
+}
+
+/// Returns the detailed state (and per-peer broadcast status) of the mempool
+/// transaction given by the `hash` query parameter (as JSON).
+pub async fn mempool_handle_transaction_request(
+    req: Request<Body>,
+    mempool_client_sender: MempoolClientSender,
+) -> hyper::Result<Response<Body>> {
+    let query_pairs = parse_query_pairs(&req);
+    let hash: HashValue = match parse_required_query_param(&query_pairs, "hash") {
+        Ok(hash) => hash,
+        Err(response) => return Ok(response),
+    };
+
+    let result = send_mempool_request(mempool_client_sender, |sender| {
+        MempoolClientRequest::GetTransactionInfoByHash(hash, sender)
+    })
+    .await;
+    match result {
+        Ok(Some((txn_info, broadcast_statuses))) => {
+            #[derive(Serialize)]
+            struct TransactionResponse<T, B> {
+                transaction: T,
+                peer_broadcast_statuses: B,
+            }
+            Ok(reply_with_json(&TransactionResponse {
+                transaction: txn_info,
+                peer_broadcast_statuses: broadcast_statuses,
+            }))
+        },
+        Ok(None) => Ok(reply_with_status(
+            StatusCode::NOT_FOUND,
+            format!("Transaction {} is not in mempool.", hash),
+        )),
+        Err(e) => {
+            info!("Failed to get transaction from mempool: {e:?}");
+            Ok(reply_with_status(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                e.to_string(),
+            ))
+        },
+    }
+}
+
+/// Evicts the mempool transaction given by the `hash` query parameter, or all
+/// mempool transactions of the sender given by the `account` query parameter.
+pub async fn mempool_handle_evict_request(
+    req: Request<Body>,
+    mempool_client_sender: MempoolClientSender,
+) -> hyper::Result<Response<Body>> {
+    let query_pairs = parse_query_pairs(&req);
+    let result = if query_pairs.contains_key("hash") {
+        let hash: HashValue = match parse_required_query_param(&query_pairs, "hash") {
+            Ok(hash) => hash,
+            Err(response) => return Ok(response),
+        };
+        warn!("Admin request to evict transaction {} from mempool.", hash);
+        send_mempool_request(mempool_client_sender, |sender| {
+            MempoolClientRequest::EvictTransaction(hash, sender)
+        })
+        .await
+        .map(|evicted| if evicted { vec![hash] } else { vec![] })
+    } else {
+        let account: AccountAddress = match parse_required_query_param(&query_pairs, "account") {
+            Ok(account) => account,
+            Err(response) => return Ok(response),
+        };
+        warn!(
+            "Admin request to evict all transactions of sender {} from mempool.",
+            account
+        );
+        send_mempool_request(mempool_client_sender, |sender| {
+            MempoolClientRequest::EvictSender(account, sender)
+        })
+        .await
+    };
+
+    match result {
+        Ok(evicted_hashes) => {
+            info!(
+                "Evicted {} transaction(s) from mempool.",
+                evicted_hashes.len()
+            );
+            Ok(reply_with_json(
+                &serde_json::json!({ "evicted": evicted_hashes }),
+            ))
+        },
+        Err(e) => {
+            info!("Failed to evict transactions from mempool: {e:?}");
+            Ok(reply_with_status(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                e.to_string(),
+            ))
+        },
+    }
+}
+
+/// Temporarily rejects all new mempool transactions from the sender given by the
+/// `account` query parameter, for the duration given by `duration_secs`.
+pub async fn mempool_handle_block_sender_request(
+    req: Request<Body>,
+    mempool_client_sender: MempoolClientSender,
+) -> hyper::Result<Response<Body>> {
+    let query_pairs = parse_query_pairs(&req);
+    let account: AccountAddress = match parse_required_query_param(&query_pairs, "account") {
+        Ok(account) => account,
+        Err(response) => return Ok(response),
+    };
+    let duration_secs: u64 = match parse_required_query_param(&query_pairs, "duration_secs") {
+        Ok(duration_secs) => duration_secs,
+        Err(response) => return Ok(response),
+    };
+
+    warn!(
+        "Admin request to block sender {} from mempool for {} seconds.",
+        account, duration_secs
+    );
+    let result = send_mempool_request(mempool_client_sender, |sender| {
+        MempoolClientRequest::BlockSender(account, Duration::from_secs(duration_secs), sender)
+    })
+    .await;
+    match result {
+        Ok(Ok(())) => Ok(reply_with_status(
+            StatusCode::OK,
+            format!("Blocked sender {} for {} seconds.", account, duration_secs),
+        )),
+        Ok(Err(e)) => Ok(reply_with_status(StatusCode::BAD_REQUEST, e.to_string())),
+        Err(e) => {
+            info!("Failed to block sender in mempool: {e:?}");
+            Ok(reply_with_status(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                e.to_string(),
+            ))
+        },
+    }
+}
+
 async fn get_parking_lot_addresses(
     mempool_client_sender: MempoolClientSender,
 ) -> Result<Vec<(AccountAddress, u64)>, Canceled> {
-    let (sender, receiver) = futures_channel::oneshot::channel();
+    send_mempool_request(
+        mempool_client_sender,
+        MempoolClientRequest::GetAddressesFromParkingLot,
+    )
+    .await
+}
+
+/// Sends the given request to mempool and waits for the response
+async fn send_mempool_request<T>(
+    mempool_client_sender: MempoolClientSender,
+    create_request: impl FnOnce(oneshot::Sender<T>) -> MempoolClientRequest,
+) -> Result<T, Canceled> {
+    let (sender, receiver) = oneshot::channel();
 
     match mempool_client_sender
         .clone()
-        .try_send(MempoolClientRequest::GetAddressesFromParkingLot(sender))
+        .try_send(create_request(sender))
     {
         Ok(_) => receiver.await,
         Err(e) => {
-            info!("Failed to send request for GetAddressesFromParkingLot: {e:?}");
+            info!("Failed to send request to mempool: {e:?}");
             Err(Canceled)
         },
     }
 }
+
+fn parse_query_pairs(req: &Request<Body>) -> HashMap<String, String> {
+    let query = req.uri().query().unwrap_or("");
+    url::form_urlencoded::parse(query.as_bytes())
+        .into_owned()
+        .collect()
+}
+
+/// Parses the given (required) query parameter, or returns a bad request response
+fn parse_required_query_param<T>(
+    query_pairs: &HashMap<String, String>,
+    name: &str,
+) -> Result<T, Response<Body>>
+where
+    T: std::str::FromStr,
+    T::Err: std::fmt::Display,
+{
+    match query_pairs.get(name) {
+        Some(value) => value.parse().map_err(|e: T::Err| {
+            reply_with_status(StatusCode::BAD_REQUEST, format!("Invalid {}: {}", name, e))
+        }),
+        None => Err(reply_with_status(
+            StatusCode::BAD_REQUEST,
+            format!("Missing query parameter: {}", name),
+        )),
+    }
+}
+
+fn reply_with_json<T: Serialize>(value: &T) -> Response<Body> {
+    match serde_json::to_vec(value) {
+        Ok(body) => reply_with(
+            vec![(CONTENT_TYPE, HeaderValue::from_static("application/json"))],
+            body,
+        ),
+        Err(e) => reply_with_status(StatusCode::INTERNAL_SERVER_ERROR, e.to_string()),
+    }
+}
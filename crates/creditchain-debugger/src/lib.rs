@@ -2,10 +2,18 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use anyhow::Result;
+use creditchain_config::config::{
+    RocksdbConfigs, StorageDirPaths, BUFFERED_STATE_TARGET_ITEMS,
+    DEFAULT_MAX_NUM_NODES_PER_LRU_CACHE_SHARD, NO_OP_STORAGE_PRUNER_CONFIG,
+};
+use creditchain_db::CreditChainDB;
 use clap::Parser;
+use std::sync::Arc;
 
 #[derive(Parser)]
 pub enum Cmd {
+    AnalyzeLeaderReputation(creditchain_consensus::util::leader_reputation_tool::Command),
+
     #[clap(subcommand)]
     CreditChainDb(creditchain_db_tool::DBTool),
 
@@ -20,6 +28,19 @@ pub enum Cmd {
 impl Cmd {
     pub async fn run(self) -> Result<()> {
         match self {
+            Cmd::AnalyzeLeaderReputation(cmd) => {
+                let creditchain_db = CreditChainDB::open(
+                    StorageDirPaths::from_path(&cmd.db_dir),
+                    true, /* readonly */
+                    NO_OP_STORAGE_PRUNER_CONFIG,
+                    RocksdbConfigs::default(),
+                    false, /* indexer */
+                    BUFFERED_STATE_TARGET_ITEMS,
+                    DEFAULT_MAX_NUM_NODES_PER_LRU_CACHE_SHARD,
+                    None,
+                )?;
+                cmd.run(Arc::new(creditchain_db)).await
+            },
             Cmd::CreditChainDb(cmd) => cmd.run().await,
             Cmd::Decode(cmd) => cmd.run().await,
             Cmd::DumpPendingTxns(cmd) => cmd.run().await,
@@ -266,6 +266,28 @@ fn display_state_sync_metadata(
                 "\t\t- Received responses by type: {:?}",
                 received_responses_by_type
             ));
+
+            // Get the peer's bad responses and response latencies
+            let bad_responses_by_type = peer_state_entry.get_bad_responses_by_type();
+            let response_latencies = peer_state_entry
+                .get_response_latency_histogram()
+                .get_bucket_counts();
+
+            // Display the peer's bad responses and response latencies
+            peer_information_output.push(format!(
+                "\t\t- Bad responses by type: {:?}",
+                bad_responses_by_type
+            ));
+            peer_information_output.push(format!(
+                "\t\t- Response latencies (by bucket): {:?}",
+                response_latencies
+            ));
+
+            // Display the peer selection rationale
+            peer_information_output.push(format!(
+                "\t\t- Peer selection: {}",
+                peer_state_entry.get_peer_selection_rationale()
+            ));
         }
     }
 }
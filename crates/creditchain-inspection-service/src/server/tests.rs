@@ -264,6 +264,7 @@ async fn send_get_request_to_path(config: &NodeConfig, endpoint: &str) -> Respon
         Arc::new(MockDatabaseReader {}),
         StorageServiceClient::new(network_client),
         None,
+        None,
     );
 
     // Serve the request
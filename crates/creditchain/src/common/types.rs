@@ -1221,6 +1221,16 @@ pub struct MovePackageOptions {
     #[clap(long)]
     pub(crate) skip_fetch_latest_git_deps: bool,
 
+    /// Fail instead of accessing the network to fetch dependencies
+    ///
+    /// Remote dependencies must already be in the Move home directory, e.g. from an earlier
+    /// build or vendored with `move-package-cache vendor` and `MOVE_HOME` set to the vendor
+    /// directory, and Git dependencies are not updated. Dependencies pinned in the package's
+    /// `Move.lock` are used at their pinned commit or version, and checked against their
+    /// recorded content hash, in either case.
+    #[clap(long)]
+    pub(crate) offline: bool,
+
     /// Do not complain about unknown attributes in Move code.
     #[clap(long)]
     pub skip_attribute_checks: bool,
@@ -1293,6 +1303,7 @@ impl MovePackageOptions {
             named_addresses: Default::default(),
             override_std: None,
             skip_fetch_latest_git_deps: true,
+            offline: false,
             bytecode_version: None,
             compiler_version: Some(CompilerVersion::latest_stable()),
             language_version: Some(LanguageVersion::latest_stable()),
@@ -0,0 +1,365 @@
+// Copyright © CreditChain Research Team
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    common::types::{
+        load_account_arg, CliCommand, CliError, CliTypedResult, MovePackageOptions, ProfileOptions,
+        RestOptions,
+    },
+    move_tool::{fix_bytecode_version, CachedPackageRegistry, IncludedArtifacts},
+};
+use creditchain_framework::{
+    natives::code::{PackageMetadata, UpgradePolicy},
+    BuildOptions, BuiltPackage,
+};
+use creditchain_api_types::CreditChainErrorCode;
+use creditchain_gas_schedule::{gas_feature_versions, LATEST_GAS_FEATURE_VERSION};
+use creditchain_rest_client::{error::RestError, Client};
+use creditchain_types::{
+    account_address::AccountAddress,
+    chain_id::ChainId,
+    on_chain_config::{
+        FeatureFlag, Features, GasScheduleV2, TimedFeatureFlag, TimedFeatures, TimedFeaturesBuilder,
+    },
+};
+use async_trait::async_trait;
+use clap::Parser;
+use move_binary_format::{access::ModuleAccess, compatibility::Compatibility, CompiledModule};
+use move_core_types::language_storage::CORE_CODE_ADDRESS;
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    path::PathBuf,
+};
+
+/// Checks whether a local package can upgrade the version deployed on chain
+///
+/// Builds the package and compares it against the package of the same name in the
+/// `0x1::code::PackageRegistry` of the account. The same rules as the ones enforced on publish
+/// are checked: the upgrade policy, the upgrade policies of dependencies, removed modules, module
+/// names clashing with other packages, and the compatibility of every module with its deployed
+/// version. All breaking changes are reported, rather than only the first one.
+///
+/// The compatibility rules are derived from the on-chain features of the network. With
+/// `--registry-file`, the rules of the latest release are used instead, and only dependencies
+/// published at the same address as the package are checked.
+#[derive(Parser)]
+pub struct CheckUpgrade {
+    /// Address of the account the package is deployed at
+    #[clap(long, value_parser = load_account_arg, required_unless_present = "registry_file")]
+    pub(crate) account: Option<AccountAddress>,
+
+    /// File containing the BCS-encoded `0x1::code::PackageRegistry` resource of the account
+    ///
+    /// When given, the deployed packages are read from this file instead of being fetched
+    /// from the network.
+    #[clap(long, value_parser)]
+    pub(crate) registry_file: Option<PathBuf>,
+
+    /// Directory containing the `.mv` bytecode files of the deployed modules
+    ///
+    /// Required to check module compatibility with `--registry-file`, e.g. the
+    /// `bytecode_modules` directory written by `creditchain move download --bytecode`.
+    #[clap(long, value_parser, requires = "registry_file")]
+    pub(crate) bytecode_dir: Option<PathBuf>,
+
+    #[clap(flatten)]
+    pub(crate) move_options: MovePackageOptions,
+    #[clap(flatten)]
+    pub(crate) rest_options: RestOptions,
+    #[clap(flatten)]
+    pub(crate) profile_options: ProfileOptions,
+}
+
+impl CheckUpgrade {
+    /// Loads the registry of the account, the registries of the addresses the dependencies of
+    /// `package` are published at, and the compatibility checks applied on publish.
+    async fn load_registries(
+        &self,
+        package: &PackageMetadata,
+    ) -> CliTypedResult<(
+        CachedPackageRegistry,
+        BTreeMap<AccountAddress, Option<CachedPackageRegistry>>,
+        Compatibility,
+    )> {
+        if let Some(registry_file) = &self.registry_file {
+            let registry =
+                CachedPackageRegistry::load(registry_file, self.bytecode_dir.as_deref())?;
+            let compatibility = publish_compatibility(
+                &Features::default(),
+                &TimedFeaturesBuilder::enable_all().build(),
+                LATEST_GAS_FEATURE_VERSION,
+            );
+            return Ok((registry, BTreeMap::new(), compatibility));
+        }
+
+        let account = self.account.ok_or_else(|| {
+            CliError::CommandArgumentError(
+                "Either --account or --registry-file must be provided".to_string(),
+            )
+        })?;
+        let url = self.rest_options.url(&self.profile_options)?;
+        let registry = CachedPackageRegistry::create(url.clone(), account, true).await?;
+        let mut dep_registries = BTreeMap::new();
+        for dep in &package.deps {
+            if dep.account == account || dep_registries.contains_key(&dep.account) {
+                continue;
+            }
+            let dep_registry =
+                match CachedPackageRegistry::create(url.clone(), dep.account, false).await {
+                    Ok(dep_registry) => Some(dep_registry),
+                    Err(err) if is_resource_not_found(&err) => None,
+                    Err(err) => return Err(err.into()),
+                };
+            dep_registries.insert(dep.account, dep_registry);
+        }
+        let compatibility = fetch_publish_compatibility(&Client::new(url)).await?;
+        Ok((registry, dep_registries, compatibility))
+    }
+}
+
+#[async_trait]
+impl CliCommand<String> for CheckUpgrade {
+    fn command_name(&self) -> &'static str {
+        "CheckUpgrade"
+    }
+
+    async fn execute(self) -> CliTypedResult<String> {
+        let build_options = BuildOptions {
+            install_dir: self.move_options.output_dir.clone(),
+            bytecode_version: fix_bytecode_version(
+                self.move_options.bytecode_version,
+                self.move_options.language_version,
+            ),
+            ..IncludedArtifacts::Sparse.build_options(&self.move_options)?
+        };
+        let pack = BuiltPackage::build(self.move_options.get_package_path()?, build_options)
+            .map_err(|e| CliError::MoveCompilationError(format!("{:#}", e)))?;
+        let metadata = pack.extract_metadata()?;
+        let modules = pack.modules().collect::<Vec<_>>();
+        let Some(publish_address) = modules.first().map(|module| *module.self_id().address())
+        else {
+            return Err(CliError::CommandArgumentError(format!(
+                "Package `{}` has no modules",
+                pack.name()
+            )));
+        };
+
+        let (registry, dep_registries, compatibility) = self.load_registries(&metadata).await?;
+
+        let breaking_changes = breaking_changes(
+            &registry,
+            &dep_registries,
+            publish_address,
+            &metadata,
+            &modules,
+            &compatibility,
+        )
+        .await?;
+        if breaking_changes.is_empty() {
+            return Ok(format!(
+                "Package `{}` is compatible with the deployed version",
+                pack.name()
+            ));
+        }
+
+        let mut message = format!(
+            "Package `{}` cannot be upgraded, found {} breaking change(s):",
+            pack.name(),
+            breaking_changes.len()
+        );
+        for change in &breaking_changes {
+            message.push_str("\n  - ");
+            message.push_str(change);
+        }
+        Err(CliError::UnexpectedError(message))
+    }
+}
+
+/// Returns the compatibility checks the VM applies when publishing modules with the given
+/// on-chain features.
+fn publish_compatibility(
+    features: &Features,
+    timed_features: &TimedFeatures,
+    gas_feature_version: u64,
+) -> Compatibility {
+    Compatibility::new(
+        true,
+        !features.is_enabled(FeatureFlag::TREAT_FRIEND_AS_PRIVATE),
+        timed_features.is_enabled(TimedFeatureFlag::EntryCompatibility),
+        gas_feature_version < gas_feature_versions::RELEASE_V1_34,
+    )
+}
+
+/// Fetches the on-chain features of the network and returns the compatibility checks the VM
+/// currently applies when publishing modules.
+async fn fetch_publish_compatibility(client: &Client) -> CliTypedResult<Compatibility> {
+    let features = client
+        .get_account_resource_bcs::<Features>(CORE_CODE_ADDRESS, "0x1::features::Features")
+        .await?
+        .into_inner();
+    let gas_schedule = client
+        .get_account_resource_bcs::<GasScheduleV2>(
+            CORE_CODE_ADDRESS,
+            "0x1::gas_schedule::GasScheduleV2",
+        )
+        .await?
+        .into_inner();
+    let state = client
+        .get_ledger_information()
+        .await
+        .map_err(|err| CliError::ApiError(err.to_string()))?
+        .into_inner();
+    let timed_features =
+        TimedFeaturesBuilder::new(ChainId::new(state.chain_id), state.timestamp_usecs).build();
+    Ok(publish_compatibility(
+        &features,
+        &timed_features,
+        gas_schedule.feature_version,
+    ))
+}
+
+fn is_resource_not_found(err: &anyhow::Error) -> bool {
+    matches!(
+        err.downcast_ref::<RestError>(),
+        Some(RestError::Api(response))
+            if response.error.error_code == CreditChainErrorCode::ResourceNotFound
+    )
+}
+
+/// Core addresses whose packages can be depended on regardless of their upgrade policy, see
+/// `0x1::code::is_policy_exempted_address`.
+fn is_policy_exempted_address(address: AccountAddress) -> bool {
+    (AccountAddress::ONE..=AccountAddress::TEN).contains(&address)
+}
+
+/// Returns every reason for which publishing `package` with `modules` at `publish_address` on top
+/// of the packages in `registry` would be rejected, mirroring the checks of `0x1::code` and of
+/// the VM. `dep_registries` holds the registries of the dependency addresses other than
+/// `publish_address` (`None` if there is none), dependencies at addresses missing from it are
+/// not checked.
+async fn breaking_changes(
+    registry: &CachedPackageRegistry,
+    dep_registries: &BTreeMap<AccountAddress, Option<CachedPackageRegistry>>,
+    publish_address: AccountAddress,
+    package: &PackageMetadata,
+    modules: &[&CompiledModule],
+    compatibility: &Compatibility,
+) -> CliTypedResult<Vec<String>> {
+    let mut changes = vec![];
+    let module_names = package
+        .modules
+        .iter()
+        .map(|module| module.name.as_str())
+        .collect::<BTreeSet<_>>();
+
+    // Dependencies must be published with a policy at least as strong as the package's one, and
+    // `arbitrary` dependencies must be published at the same address
+    for dep in &package.deps {
+        if is_policy_exempted_address(dep.account) {
+            continue;
+        }
+        let dep_registry = if dep.account == publish_address {
+            Some(registry)
+        } else {
+            match dep_registries.get(&dep.account) {
+                Some(dep_registry) => dep_registry.as_ref(),
+                None => continue,
+            }
+        };
+        let dep_package = match dep_registry {
+            Some(dep_registry) => dep_registry.get_package(&dep.package_name).await.ok(),
+            None => None,
+        };
+        let Some(dep_package) = dep_package else {
+            changes.push(format!(
+                "dependency `{}` is not published at {}",
+                dep.package_name,
+                dep.account.to_hex_literal()
+            ));
+            continue;
+        };
+        let dep_policy = dep_package.upgrade_policy();
+        if dep_policy.policy < package.upgrade_policy.policy {
+            changes.push(format!(
+                "dependency `{}` has the weaker upgrade policy `{}`",
+                dep.package_name, dep_policy
+            ));
+        }
+        if dep_policy == UpgradePolicy::arbitrary() && dep.account != publish_address {
+            changes.push(format!(
+                "dependency `{}` has the `arbitrary` upgrade policy and is not published at the \
+                 same address",
+                dep.package_name
+            ));
+        }
+    }
+
+    // Modules cannot be published by two different packages at the same address
+    for other_name in registry.package_names() {
+        if other_name == package.name {
+            continue;
+        }
+        let other = registry.get_package(other_name).await?;
+        for module_name in other.module_names() {
+            if module_names.contains(module_name) {
+                changes.push(format!(
+                    "module `{}` is already published by package `{}`",
+                    module_name, other_name
+                ));
+            }
+        }
+    }
+
+    // A package which is not deployed yet is a fresh publish, not an upgrade
+    let Ok(deployed) = registry.get_package(&package.name).await else {
+        return Ok(changes);
+    };
+    let old_policy = deployed.upgrade_policy();
+    if old_policy == UpgradePolicy::immutable() {
+        changes.push("the deployed package is immutable and cannot be upgraded".to_string());
+        return Ok(changes);
+    }
+    if package.upgrade_policy.policy < old_policy.policy {
+        changes.push(format!(
+            "the upgrade policy cannot be weakened from `{}` to `{}`",
+            old_policy, package.upgrade_policy
+        ));
+    }
+    for module_name in deployed.module_names() {
+        if !module_names.contains(module_name) {
+            changes.push(format!("module `{}` was removed", module_name));
+        }
+    }
+
+    // Modules of packages with the `arbitrary` policy can change in any way
+    if old_policy == UpgradePolicy::arbitrary() {
+        return Ok(changes);
+    }
+    let deployed_module_names = deployed.module_names();
+    for module in modules {
+        let module_name = module.name().as_str();
+        if !deployed_module_names.contains(&module_name) {
+            continue;
+        }
+        let bytecode = registry.get_bytecode(module_name).await?.ok_or_else(|| {
+            CliError::CommandArgumentError(format!(
+                "Bytecode of deployed module `{}` not found, provide it with --bytecode-dir",
+                module_name
+            ))
+        })?;
+        let deployed_module = CompiledModule::deserialize(bytecode).map_err(|err| {
+            CliError::UnexpectedError(format!(
+                "Failed to deserialize deployed module `{}`: {}",
+                module_name, err
+            ))
+        })?;
+        for incompatibility in compatibility.incompatibilities(&deployed_module, module) {
+            changes.push(format!(
+                "module `{}`: {}",
+                module.self_id().short_str_lossless(),
+                incompatibility
+            ));
+        }
+    }
+    Ok(changes)
+}
@@ -11,6 +11,7 @@ use clap::{Parser, Subcommand};
 use legacy_move_compiler::compiled_unit::{CompiledUnit, NamedCompiledModule};
 use move_coverage::{
     coverage_map::CoverageMap,
+    export::{write_cobertura, write_lcov, ExportFormat, ModuleLineCoverage},
     format_csv_summary, format_human_summary,
     source_coverage::{ColorChoice, SourceCoverageBuilder, TextIndicator},
     summary::summarize_inst_cov,
@@ -18,6 +19,7 @@ use move_coverage::{
 use move_disassembler::disassembler::Disassembler;
 use move_model::metadata::{CompilerVersion, LanguageVersion};
 use move_package::{compilation::compiled_package::CompiledPackage, BuildConfig, CompilerConfig};
+use std::{io::Write, path::PathBuf};
 
 /// Display a coverage summary for all modules in a package
 ///
@@ -166,6 +168,121 @@ impl CliCommand<()> for BytecodeCoverage {
     }
 }
 
+/// Export line and branch coverage of a package for coverage dashboards
+///
+/// Bytecode coverage is mapped back to source lines and branches, and written as an LCOV
+/// tracefile or a Cobertura XML report. The outcomes of branches whose destinations can both be
+/// reached from elsewhere (e.g., loop headers) are approximated.
+#[derive(Debug, Parser)]
+pub struct ExportCoverage {
+    /// Format of the exported coverage report
+    #[clap(long, default_value_t = ExportFormat::Lcov)]
+    pub format: ExportFormat,
+
+    /// File to write the report to, printed to stdout if not provided
+    #[clap(long)]
+    pub output_file: Option<PathBuf>,
+
+    /// Include modules of dependencies in the report
+    ///
+    /// Dependencies whose sources are out of sync with their compiled modules are skipped.
+    #[clap(long)]
+    pub include_dependencies: bool,
+
+    /// A filter string to determine which modules to export coverage for
+    #[clap(long, short)]
+    pub filter: Option<String>,
+
+    #[clap(flatten)]
+    pub move_options: MovePackageOptions,
+}
+
+#[async_trait]
+impl CliCommand<()> for ExportCoverage {
+    fn command_name(&self) -> &'static str {
+        "ExportCoverage"
+    }
+
+    async fn execute(self) -> CliTypedResult<()> {
+        let package_path = self.move_options.get_package_path()?;
+        let (coverage_map, package) = compile_coverage(self.move_options)?;
+        let coverage_map = coverage_map.to_unified_exec_map();
+
+        let root_package_name = package.compiled_package_info.package_name;
+        let root_units = package
+            .root_compiled_units
+            .iter()
+            .map(|unit| (root_package_name, unit));
+        let dep_units = package
+            .deps_compiled_units
+            .iter()
+            .filter(|_| self.include_dependencies)
+            .map(|(package_name, unit)| (*package_name, unit));
+
+        let mut modules = vec![];
+        for (package_name, unit) in root_units.chain(dep_units) {
+            if let Some(filter_str) = &self.filter {
+                if !unit.unit.name().as_str().contains(filter_str.as_str()) {
+                    continue;
+                }
+            }
+            if let CompiledUnit::Module(NamedCompiledModule {
+                module, source_map, ..
+            }) = &unit.unit
+            {
+                match ModuleLineCoverage::new(
+                    package_name.as_str(),
+                    module,
+                    source_map,
+                    &unit.source_path,
+                    &coverage_map,
+                ) {
+                    Ok(module_coverage) => modules.push(module_coverage),
+                    Err(err) if package_name != root_package_name => eprintln!(
+                        "Warning: skipping coverage of module `{}` of dependency `{}`: {}",
+                        unit.unit.name(),
+                        package_name,
+                        err
+                    ),
+                    Err(err) => {
+                        return Err(CliError::UnexpectedError(format!(
+                            "Failed to compute coverage {}",
+                            err
+                        )))
+                    },
+                }
+            }
+        }
+        let num_approximate_branches = modules
+            .iter()
+            .flat_map(|module| &module.branches)
+            .filter(|branch| branch.approximate)
+            .count();
+        if num_approximate_branches > 0 {
+            eprintln!(
+                "Note: the outcomes of {} branch(es) are approximate, as both of their \
+                 destinations can also be reached from elsewhere",
+                num_approximate_branches
+            );
+        }
+
+        let mut output = vec![];
+        match self.format {
+            ExportFormat::Lcov => write_lcov(&modules, &mut output),
+            ExportFormat::Cobertura => write_cobertura(&modules, &package_path, &mut output),
+        }
+        .map_err(|err| CliError::UnexpectedError(format!("Failed to export coverage {}", err)))?;
+
+        match &self.output_file {
+            Some(output_file) => std::fs::write(output_file, output)
+                .map_err(|err| CliError::IO(output_file.display().to_string(), err)),
+            None => std::io::stdout()
+                .write_all(&output)
+                .map_err(|err| CliError::IO("stdout".to_string(), err)),
+        }
+    }
+}
+
 fn compile_coverage(
     move_options: MovePackageOptions,
 ) -> CliTypedResult<(CoverageMap, CompiledPackage)> {
@@ -176,6 +293,7 @@ fn compile_coverage(
         full_model_generation: !move_options.skip_checks_on_test_code,
         install_dir: move_options.output_dir.clone(),
         skip_fetch_latest_git_deps: move_options.skip_fetch_latest_git_deps,
+        offline: move_options.offline,
         compiler_config: CompilerConfig {
             known_attributes: extended_checks::get_all_attribute_names().clone(),
             skip_attribute_checks: move_options.skip_attribute_checks,
@@ -215,6 +333,7 @@ pub enum CoveragePackage {
     Summary(SummaryCoverage),
     Source(SourceCoverage),
     Bytecode(BytecodeCoverage),
+    Export(ExportCoverage),
 }
 
 impl CoveragePackage {
@@ -223,6 +342,7 @@ impl CoveragePackage {
             Self::Summary(tool) => tool.execute_serialized_success().await,
             Self::Source(tool) => tool.execute_serialized_success().await,
             Self::Bytecode(tool) => tool.execute_serialized_success().await,
+            Self::Export(tool) => tool.execute_serialized_success().await,
         }
     }
 }
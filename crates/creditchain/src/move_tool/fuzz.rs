@@ -0,0 +1,1275 @@
+// Copyright © CreditChain Research Team
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    common::types::{CliCommand, CliError, CliTypedResult, MovePackageOptions},
+    move_tool::{fix_bytecode_version, IncludedArtifacts},
+};
+use creditchain_block_executor::txn_provider::default::DefaultTxnProvider;
+use creditchain_cached_packages::creditchain_stdlib;
+use creditchain_framework::{build_model, extended_checks, BuildOptions, BuiltPackage};
+use creditchain_keygen::KeyGen;
+use creditchain_transaction_simulation::{Account, InMemoryStateStore, SimulationStateStore};
+use creditchain_types::{
+    account_config::AccountResource,
+    state_store::state_key::inner::StateKeyInner,
+    transaction::{
+        signature_verified_transaction::into_signature_verified_block, EntryABI, EntryFunction,
+        ExecutionStatus, SignedTransaction, Transaction, TransactionOutput, TransactionStatus,
+    },
+};
+use creditchain_vm::{creditchain_vm::CreditChainVMBlockExecutor, CreditChainVM, VMBlockExecutor};
+use async_trait::async_trait;
+use clap::Parser;
+use move_binary_format::{
+    access::ModuleAccess,
+    file_format::{SignatureToken, Visibility},
+    CompiledModule,
+};
+use move_core_types::{
+    account_address::AccountAddress,
+    identifier::Identifier,
+    language_storage::{ModuleId, TypeTag, CORE_CODE_ADDRESS},
+    u256::U256,
+    value::MoveValue,
+    vm_status::AbortLocation,
+};
+use move_coverage::coverage_map::{output_map_to_file, CoverageMap};
+use rand::{rngs::StdRng, seq::SliceRandom, Rng, SeedableRng};
+use serde::Serialize;
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fs,
+    path::{Path, PathBuf},
+};
+use tokio::task;
+
+/// Balance given to every account used by the fuzzer
+const ACCOUNT_BALANCE: u64 = 1_000_000_000_000_000;
+const MAX_GAS_AMOUNT: u64 = 2_000_000;
+const GAS_UNIT_PRICE: u64 = 100;
+/// Seed of the account keys, fixed so that reproductions refer to the same addresses across runs
+const ACCOUNT_KEY_SEED: [u8; 32] = [0; 32];
+/// Environment variable naming the file the VM traces executed instructions to
+const TRACE_ENV_VAR: &str = "MOVE_VM_TRACE";
+const COVERAGE_MAP_FILE: &str = ".coverage_map.mvcov";
+
+/// Fuzzes the entry functions of a Move package
+///
+/// The package is published on a local in-memory chain, and random sequences of calls to its
+/// entry functions are executed by several signers. Sequences reaching new bytecode of the
+/// package are kept and mutated further. A call fails when it aborts with a code which is not
+/// declared as an error constant, hits a runtime error such as an arithmetic overflow, or when
+/// a `#[invariant]` view function of the package returns `false` after it.
+///
+/// Every failure is minimized and written as a Move unit test reproducing it, which fails until
+/// the issue is fixed. The coverage reached is saved like `creditchain move test --coverage`
+/// does, so it can be inspected with `creditchain move coverage`.
+#[derive(Parser)]
+pub struct FuzzPackage {
+    /// Number of call sequences to execute
+    #[clap(long, default_value_t = 1000)]
+    pub iterations: u64,
+
+    /// Maximum number of calls in a sequence
+    #[clap(long, default_value_t = 8)]
+    pub max_calls: usize,
+
+    /// Number of accounts calling the entry functions, in addition to the package publisher
+    #[clap(long, default_value_t = 3)]
+    pub signers: usize,
+
+    /// Seed of the random generator, to replay a previous run
+    #[clap(long)]
+    pub seed: Option<u64>,
+
+    /// Only fuzz the entry functions whose `<module>::<function>` name contains this string
+    #[clap(long)]
+    pub filter: Option<String>,
+
+    /// Abort codes of the package which are expected, even if not declared as error constants
+    #[clap(long, value_delimiter = ',')]
+    pub allowed_abort_codes: Vec<u64>,
+
+    /// Directory to write the reproductions to, defaults to the `tests` directory of the package
+    #[clap(long, value_parser)]
+    pub output_dir: Option<PathBuf>,
+
+    #[clap(flatten)]
+    pub move_options: MovePackageOptions,
+}
+
+/// Summary of a fuzzing run without failures
+#[derive(Serialize)]
+pub struct FuzzSummary {
+    /// Seed of the random generator
+    pub seed: u64,
+    /// Number of call sequences executed
+    pub sequences: u64,
+    /// Number of entry function calls executed
+    pub calls: u64,
+    /// Number of distinct bytecode instructions of the package reached
+    pub covered_instructions: usize,
+    /// Number of call sequences kept because they reached new instructions
+    pub corpus_size: usize,
+    /// Entry functions which could not be fuzzed, and why
+    pub skipped_functions: Vec<String>,
+}
+
+#[async_trait]
+impl CliCommand<FuzzSummary> for FuzzPackage {
+    fn command_name(&self) -> &'static str {
+        "FuzzPackage"
+    }
+
+    async fn execute(self) -> CliTypedResult<FuzzSummary> {
+        task::spawn_blocking(move || self.fuzz())
+            .await
+            .map_err(|err| CliError::UnexpectedError(err.to_string()))?
+    }
+}
+
+impl FuzzPackage {
+    fn fuzz(self) -> CliTypedResult<FuzzSummary> {
+        let package_path = self.move_options.get_package_path()?;
+        // The VM reads this variable and opens the trace file once, when it first executes code,
+        // and then keeps writing to that file for the rest of the process. So it is set before
+        // anything is executed, and an existing value is kept for later runs in the same process.
+        let trace_path = std::env::var_os(TRACE_ENV_VAR)
+            .map(PathBuf::from)
+            .unwrap_or_else(|| {
+                std::env::temp_dir().join(format!(
+                    "creditchain_move_fuzz_{}.trace",
+                    std::process::id()
+                ))
+            });
+        std::env::set_var(TRACE_ENV_VAR, &trace_path);
+
+        let build_options = BuildOptions {
+            with_abis: true,
+            install_dir: self.move_options.output_dir.clone(),
+            bytecode_version: fix_bytecode_version(
+                self.move_options.bytecode_version,
+                self.move_options.language_version,
+            ),
+            ..IncludedArtifacts::Sparse.build_options(&self.move_options)?
+        };
+        let pack = BuiltPackage::build(package_path.clone(), build_options)
+            .map_err(|e| CliError::MoveCompilationError(format!("{:#}", e)))?;
+        let env = build_model(
+            self.move_options.dev,
+            package_path.as_path(),
+            self.move_options.named_addresses(),
+            None,
+            fix_bytecode_version(
+                self.move_options.bytecode_version,
+                self.move_options.language_version,
+            ),
+            self.move_options.compiler_version,
+            self.move_options.language_version,
+            self.move_options.skip_attribute_checks,
+            extended_checks::get_all_attribute_names().clone(),
+            self.move_options.compute_experiments(),
+        )
+        .map_err(|e| CliError::MoveCompilationError(format!("{:#}", e)))?;
+        let invariants = extended_checks::get_invariant_functions(&env);
+
+        let modules = pack.modules().collect::<Vec<_>>();
+        let package_address = package_address(&modules)?;
+        let mut skipped_functions = vec![];
+        let targets = find_targets(
+            &pack.extract_abis().unwrap_or_default(),
+            &modules,
+            self.filter.as_deref(),
+            self.signers + 1,
+            &mut skipped_functions,
+        );
+        if targets.is_empty() {
+            return Err(CliError::CommandArgumentError(
+                "No entry functions of the package can be fuzzed".to_string(),
+            ));
+        }
+
+        let seed = self.seed.unwrap_or_else(|| rand::thread_rng().gen());
+        let executor = Executor::new(&pack, package_address, self.signers, targets, invariants)?;
+        let generator = Generator::new(
+            StdRng::seed_from_u64(seed),
+            &modules,
+            &executor.accounts,
+            self.max_calls,
+        );
+        let coverage = Coverage {
+            trace_path,
+            map: CoverageMap {
+                exec_maps: BTreeMap::new(),
+            },
+            package_modules: modules
+                .iter()
+                .map(|module| (*module.address(), module.name().to_owned()))
+                .collect(),
+            covered: 0,
+        };
+        // Drop what publishing the package traced
+        coverage.clear_trace()?;
+        let mut fuzzer = Fuzzer {
+            executor,
+            generator,
+            coverage,
+            allowed_abort_codes: self.allowed_abort_codes.iter().copied().collect(),
+            calls: 0,
+        };
+
+        let mut corpus: Vec<Vec<Call>> = vec![];
+        let mut failures: BTreeMap<String, (Vec<Call>, Option<usize>)> = BTreeMap::new();
+        for _ in 0..self.iterations {
+            let calls = if corpus.is_empty() || fuzzer.generator.rng.gen_bool(0.2) {
+                fuzzer.generator.gen_sequence(&fuzzer.executor.targets)
+            } else {
+                let parent = corpus.choose(&mut fuzzer.generator.rng).unwrap().clone();
+                fuzzer.generator.mutate(parent, &fuzzer.executor.targets)
+            };
+            let (failure, new_coverage) = fuzzer.run(&calls)?;
+            match failure {
+                Some(failure) => {
+                    failures.entry(failure.reason).or_insert_with(|| {
+                        (calls[..failure.call_count].to_vec(), failure.invariant)
+                    });
+                },
+                None if new_coverage => corpus.push(calls),
+                None => (),
+            }
+        }
+
+        let output_dir = self
+            .output_dir
+            .clone()
+            .unwrap_or_else(|| package_path.join("tests"));
+        let mut reproductions = vec![];
+        for (reason, (calls, invariant)) in failures {
+            let calls = fuzzer.minimize(&reason, calls)?;
+            let path = fuzzer.write_reproduction(&output_dir, &reason, &calls, invariant)?;
+            reproductions.push(format!("{}\n    reproduction: {}", reason, path.display()));
+        }
+
+        output_map_to_file(&package_path.join(COVERAGE_MAP_FILE), &fuzzer.coverage.map)
+            .map_err(|err| CliError::UnexpectedError(format!("{:#}", err)))?;
+        fuzzer.coverage.clear_trace()?;
+
+        if !reproductions.is_empty() {
+            return Err(CliError::UnexpectedError(format!(
+                "Fuzzing with seed {} found {} failure(s):\n  - {}",
+                seed,
+                reproductions.len(),
+                reproductions.join("\n  - ")
+            )));
+        }
+        Ok(FuzzSummary {
+            seed,
+            sequences: self.iterations,
+            calls: fuzzer.calls,
+            covered_instructions: fuzzer.coverage.covered,
+            corpus_size: corpus.len(),
+            skipped_functions,
+        })
+    }
+}
+
+/// Returns the address all modules of the package are published at.
+fn package_address(modules: &[&CompiledModule]) -> CliTypedResult<AccountAddress> {
+    let addresses = modules
+        .iter()
+        .map(|module| *module.address())
+        .collect::<BTreeSet<_>>();
+    let address = match addresses.into_iter().collect::<Vec<_>>().as_slice() {
+        [address] => *address,
+        _ => {
+            return Err(CliError::CommandArgumentError(
+                "All modules of the package must be published at the same address".to_string(),
+            ))
+        },
+    };
+    if address.is_special() {
+        return Err(CliError::CommandArgumentError(format!(
+            "Packages at the reserved address {} cannot be fuzzed",
+            address.to_hex_literal()
+        )));
+    }
+    Ok(address)
+}
+
+/// An entry function which can be fuzzed.
+struct Target {
+    module: ModuleId,
+    function: Identifier,
+    /// Number of leading `signer` parameters, which are not part of the ABI arguments
+    signer_count: usize,
+    arg_types: Vec<ArgType>,
+    is_public: bool,
+}
+
+impl Target {
+    fn name(&self) -> String {
+        format!("{}::{}", self.module.short_str_lossless(), self.function)
+    }
+}
+
+fn find_targets(
+    abis: &[EntryABI],
+    modules: &[&CompiledModule],
+    filter: Option<&str>,
+    account_count: usize,
+    skipped: &mut Vec<String>,
+) -> Vec<Target> {
+    let mut targets = vec![];
+    for abi in abis {
+        let EntryABI::EntryFunction(abi) = abi else {
+            continue;
+        };
+        let name = format!("{}::{}", abi.module_name().name(), abi.name());
+        if filter.is_some_and(|filter| !name.contains(filter)) {
+            continue;
+        }
+        let Some(module) = modules
+            .iter()
+            .find(|module| &module.self_id() == abi.module_name())
+        else {
+            continue;
+        };
+        let Some((signer_count, is_public)) = signer_parameters(module, abi.name()) else {
+            continue;
+        };
+        if !abi.ty_args().is_empty() {
+            skipped.push(format!("{}: generic functions are not supported", name));
+            continue;
+        }
+        if signer_count > account_count {
+            skipped.push(format!(
+                "{}: takes {} signers, increase --signers",
+                name, signer_count
+            ));
+            continue;
+        }
+        let Some(arg_types) = abi
+            .args()
+            .iter()
+            .map(|arg| ArgType::from_type_tag(arg.type_tag()))
+            .collect::<Option<Vec<_>>>()
+        else {
+            skipped.push(format!("{}: unsupported argument type", name));
+            continue;
+        };
+        targets.push(Target {
+            module: abi.module_name().clone(),
+            function: Identifier::new(abi.name()).unwrap(),
+            signer_count,
+            arg_types,
+            is_public,
+        });
+    }
+    targets
+}
+
+/// Returns the number of leading signer parameters of the function `name`, and whether it is
+/// public.
+fn signer_parameters(module: &CompiledModule, name: &str) -> Option<(usize, bool)> {
+    module.function_defs().iter().find_map(|def| {
+        let handle = module.function_handle_at(def.function);
+        if module.identifier_at(handle.name).as_str() != name {
+            return None;
+        }
+        let signer_count = module
+            .signature_at(handle.parameters)
+            .0
+            .iter()
+            .take_while(|token| match token {
+                SignatureToken::Signer => true,
+                SignatureToken::Reference(inner) => **inner == SignatureToken::Signer,
+                _ => false,
+            })
+            .count();
+        Some((signer_count, def.visibility == Visibility::Public))
+    })
+}
+
+/// Type of an entry function argument the fuzzer can generate values for.
+#[derive(Clone)]
+enum ArgType {
+    Bool,
+    U8,
+    U16,
+    U32,
+    U64,
+    U128,
+    U256,
+    Address,
+    String,
+    Object(TypeTag),
+    Option(Box<ArgType>),
+    Vector(Box<ArgType>),
+}
+
+impl ArgType {
+    fn from_type_tag(tag: &TypeTag) -> Option<Self> {
+        Some(match tag {
+            TypeTag::Bool => ArgType::Bool,
+            TypeTag::U8 => ArgType::U8,
+            TypeTag::U16 => ArgType::U16,
+            TypeTag::U32 => ArgType::U32,
+            TypeTag::U64 => ArgType::U64,
+            TypeTag::U128 => ArgType::U128,
+            TypeTag::U256 => ArgType::U256,
+            TypeTag::Address => ArgType::Address,
+            TypeTag::Vector(inner) => ArgType::Vector(Box::new(Self::from_type_tag(inner)?)),
+            TypeTag::Struct(tag) if tag.is_std_string(&CORE_CODE_ADDRESS) => ArgType::String,
+            TypeTag::Struct(tag) if tag.is_std_option(&CORE_CODE_ADDRESS) => {
+                ArgType::Option(Box::new(Self::from_type_tag(tag.type_args.first()?)?))
+            },
+            TypeTag::Struct(tag)
+                if tag.address == CORE_CODE_ADDRESS
+                    && tag.module.as_str() == "object"
+                    && tag.name.as_str() == "Object" =>
+            {
+                ArgType::Object(tag.type_args.first()?.clone())
+            },
+            _ => return None,
+        })
+    }
+
+    /// Returns the type in Move source syntax.
+    fn move_type(&self) -> String {
+        match self {
+            ArgType::Bool => "bool".to_string(),
+            ArgType::U8 => "u8".to_string(),
+            ArgType::U16 => "u16".to_string(),
+            ArgType::U32 => "u32".to_string(),
+            ArgType::U64 => "u64".to_string(),
+            ArgType::U128 => "u128".to_string(),
+            ArgType::U256 => "u256".to_string(),
+            ArgType::Address => "address".to_string(),
+            ArgType::String => "std::string::String".to_string(),
+            ArgType::Object(tag) => format!("creditchain_framework::object::Object<{}>", tag),
+            ArgType::Option(inner) => format!("std::option::Option<{}>", inner.move_type()),
+            ArgType::Vector(inner) => format!("vector<{}>", inner.move_type()),
+        }
+    }
+
+    /// Returns the simplest value of the type, used to minimize reproductions.
+    fn simplest_value(&self) -> MoveValue {
+        match self {
+            ArgType::Bool => MoveValue::Bool(false),
+            ArgType::U8 => MoveValue::U8(0),
+            ArgType::U16 => MoveValue::U16(0),
+            ArgType::U32 => MoveValue::U32(0),
+            ArgType::U64 => MoveValue::U64(0),
+            ArgType::U128 => MoveValue::U128(0),
+            ArgType::U256 => MoveValue::U256(U256::zero()),
+            ArgType::Address | ArgType::Object(_) => MoveValue::Address(AccountAddress::ZERO),
+            ArgType::String | ArgType::Option(_) | ArgType::Vector(_) => MoveValue::Vector(vec![]),
+        }
+    }
+
+    /// Renders `value` of this type as a Move expression.
+    fn render_value(&self, value: &MoveValue) -> String {
+        match (self, value) {
+            (ArgType::Bool, MoveValue::Bool(value)) => value.to_string(),
+            (ArgType::U8, MoveValue::U8(value)) => format!("{}u8", value),
+            (ArgType::U16, MoveValue::U16(value)) => format!("{}u16", value),
+            (ArgType::U32, MoveValue::U32(value)) => format!("{}u32", value),
+            (ArgType::U64, MoveValue::U64(value)) => format!("{}u64", value),
+            (ArgType::U128, MoveValue::U128(value)) => format!("{}u128", value),
+            (ArgType::U256, MoveValue::U256(value)) => format!("{}u256", value),
+            (ArgType::Address, MoveValue::Address(address)) => {
+                format!("@{}", address.to_hex_literal())
+            },
+            (ArgType::Object(tag), MoveValue::Address(address)) => format!(
+                "creditchain_framework::object::address_to_object<{}>(@{})",
+                tag,
+                address.to_hex_literal()
+            ),
+            (ArgType::String, MoveValue::Vector(bytes)) => {
+                format!("std::string::utf8({})", render_bytes(bytes))
+            },
+            (ArgType::Option(inner), MoveValue::Vector(values)) => match values.first() {
+                Some(value) => format!("std::option::some({})", inner.render_value(value)),
+                None => format!("std::option::none<{}>()", inner.move_type()),
+            },
+            (ArgType::Vector(inner), MoveValue::Vector(values)) => match inner.as_ref() {
+                ArgType::U8 => render_bytes(values),
+                _ => format!(
+                    "vector[{}]",
+                    values
+                        .iter()
+                        .map(|value| inner.render_value(value))
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                ),
+            },
+            _ => unreachable!("values are generated from their type"),
+        }
+    }
+}
+
+fn render_bytes(values: &[MoveValue]) -> String {
+    let bytes = values
+        .iter()
+        .map(|value| match value {
+            MoveValue::U8(byte) => *byte,
+            _ => unreachable!("byte vectors only contain u8 values"),
+        })
+        .collect::<Vec<_>>();
+    format!("x\"{}\"", hex::encode(bytes))
+}
+
+/// A call to an entry function in a sequence.
+#[derive(Clone)]
+struct Call {
+    /// Index of the called function in the targets
+    target: usize,
+    /// Indices of the signing accounts, the first one being the sender
+    signers: Vec<usize>,
+    args: Vec<MoveValue>,
+}
+
+/// Generates and mutates call sequences.
+struct Generator {
+    rng: StdRng,
+    /// Integer constants of the package, tried as argument values
+    integers: Vec<u128>,
+    /// Addresses tried as argument values: the accounts, and the addresses found in constants
+    /// or written to by earlier calls
+    addresses: BTreeSet<AccountAddress>,
+    account_count: usize,
+    max_calls: usize,
+}
+
+impl Generator {
+    fn new(
+        rng: StdRng,
+        modules: &[&CompiledModule],
+        accounts: &[Account],
+        max_calls: usize,
+    ) -> Self {
+        let mut integers = BTreeSet::new();
+        let mut addresses = accounts
+            .iter()
+            .map(|account| *account.address())
+            .collect::<BTreeSet<_>>();
+        for constant in modules.iter().flat_map(|module| module.constant_pool()) {
+            match constant.deserialize_constant() {
+                Some(MoveValue::U8(value)) => integers.insert(value as u128),
+                Some(MoveValue::U16(value)) => integers.insert(value as u128),
+                Some(MoveValue::U32(value)) => integers.insert(value as u128),
+                Some(MoveValue::U64(value)) => integers.insert(value as u128),
+                Some(MoveValue::U128(value)) => integers.insert(value),
+                Some(MoveValue::Address(address)) => addresses.insert(address),
+                _ => false,
+            };
+        }
+        Self {
+            rng,
+            integers: integers.into_iter().collect(),
+            addresses,
+            account_count: accounts.len(),
+            max_calls: max_calls.max(1),
+        }
+    }
+
+    fn gen_sequence(&mut self, targets: &[Target]) -> Vec<Call> {
+        let len = self.rng.gen_range(1, self.max_calls + 1);
+        (0..len).map(|_| self.gen_call(targets)).collect()
+    }
+
+    fn gen_call(&mut self, targets: &[Target]) -> Call {
+        let target = self.rng.gen_range(0, targets.len());
+        Call {
+            target,
+            signers: self.gen_signers(&targets[target]),
+            args: targets[target]
+                .arg_types
+                .iter()
+                .map(|ty| self.gen_value(ty))
+                .collect(),
+        }
+    }
+
+    /// Picks distinct accounts for the signers of `target`, and at least one for the sender.
+    fn gen_signers(&mut self, target: &Target) -> Vec<usize> {
+        let accounts = (0..self.account_count).collect::<Vec<_>>();
+        accounts
+            .choose_multiple(&mut self.rng, target.signer_count.max(1))
+            .copied()
+            .collect()
+    }
+
+    fn mutate(&mut self, mut calls: Vec<Call>, targets: &[Target]) -> Vec<Call> {
+        let index = self.rng.gen_range(0, calls.len());
+        match self.rng.gen_range(0, 5) {
+            0 if calls.len() < self.max_calls => {
+                let call = self.gen_call(targets);
+                calls.insert(self.rng.gen_range(0, calls.len() + 1), call);
+            },
+            1 if calls.len() > 1 => {
+                calls.remove(index);
+            },
+            2 if !calls[index].args.is_empty() => {
+                let arg = self.rng.gen_range(0, calls[index].args.len());
+                let ty = targets[calls[index].target].arg_types[arg].clone();
+                calls[index].args[arg] = self.gen_value(&ty);
+            },
+            3 => calls[index].signers = self.gen_signers(&targets[calls[index].target]),
+            _ => calls[index] = self.gen_call(targets),
+        }
+        calls
+    }
+
+    fn gen_value(&mut self, ty: &ArgType) -> MoveValue {
+        match ty {
+            ArgType::Bool => MoveValue::Bool(self.rng.gen()),
+            ArgType::U8 => MoveValue::U8(self.gen_integer(u8::MAX as u128) as u8),
+            ArgType::U16 => MoveValue::U16(self.gen_integer(u16::MAX as u128) as u16),
+            ArgType::U32 => MoveValue::U32(self.gen_integer(u32::MAX as u128) as u32),
+            ArgType::U64 => MoveValue::U64(self.gen_integer(u64::MAX as u128) as u64),
+            ArgType::U128 => MoveValue::U128(self.gen_integer(u128::MAX)),
+            ArgType::U256 => MoveValue::U256(match self.rng.gen_range(0, 8) {
+                0 => U256::max_value(),
+                _ => U256::from(self.gen_integer(u128::MAX)),
+            }),
+            ArgType::Address | ArgType::Object(_) => {
+                let index = self.rng.gen_range(0, self.addresses.len());
+                MoveValue::Address(*self.addresses.iter().nth(index).unwrap())
+            },
+            ArgType::String => {
+                let len = self.rng.gen_range(0, 16);
+                MoveValue::Vector(
+                    (0..len)
+                        .map(|_| MoveValue::U8(self.rng.gen_range(b' ', b'~' + 1)))
+                        .collect(),
+                )
+            },
+            ArgType::Option(inner) => MoveValue::Vector(if self.rng.gen() {
+                vec![self.gen_value(inner)]
+            } else {
+                vec![]
+            }),
+            ArgType::Vector(inner) => {
+                let len = self.rng.gen_range(0, 6);
+                MoveValue::Vector((0..len).map(|_| self.gen_value(inner)).collect())
+            },
+        }
+    }
+
+    /// Generates an integer up to `max`, favoring boundaries and the constants of the package.
+    fn gen_integer(&mut self, max: u128) -> u128 {
+        match self.rng.gen_range(0, 4) {
+            0 => *[0, 1, max - 1, max].choose(&mut self.rng).unwrap(),
+            1 if !self.integers.is_empty() => {
+                // Neighbours of constants cross comparisons against them
+                let value = *self.integers.choose(&mut self.rng).unwrap();
+                match self.rng.gen_range(0, 3) {
+                    0 => value.saturating_sub(1),
+                    1 => value,
+                    _ => value.saturating_add(1),
+                }
+                .min(max)
+            },
+            2 => self.rng.gen_range(0, max.min(1000) + 1),
+            _ if max == u128::MAX => self.rng.gen(),
+            _ => self.rng.gen_range(0, max + 1),
+        }
+    }
+}
+
+/// Executes call sequences on a local chain the package is published on.
+struct Executor {
+    /// State after publishing the package and funding the accounts
+    base: InMemoryStateStore,
+    /// The package publisher, followed by the other signers
+    accounts: Vec<Account>,
+    targets: Vec<Target>,
+    invariants: Vec<(ModuleId, Identifier)>,
+}
+
+/// The first unexpected outcome of a call sequence.
+struct Failure {
+    reason: String,
+    /// Number of calls up to and including the failing one
+    call_count: usize,
+    /// Index of the violated invariant, if any
+    invariant: Option<usize>,
+}
+
+impl Executor {
+    fn new(
+        pack: &BuiltPackage,
+        package_address: AccountAddress,
+        signers: usize,
+        targets: Vec<Target>,
+        invariants: Vec<(ModuleId, Identifier)>,
+    ) -> CliTypedResult<Self> {
+        let base = InMemoryStateStore::from_head_genesis();
+        let mut keygen = KeyGen::from_seed(ACCOUNT_KEY_SEED);
+        let mut accounts = vec![Account::new_from_addr_with_new_keypair_from_seed(
+            package_address,
+            &mut keygen,
+        )];
+        accounts.extend((0..signers).map(|_| Account::new_from_seed(&mut keygen)));
+        for account in &accounts {
+            base.store_and_fund_account(account.clone(), ACCOUNT_BALANCE, 0)?;
+        }
+
+        let payload = creditchain_stdlib::code_publish_package_txn(
+            bcs::to_bytes(&pack.extract_metadata()?)?,
+            pack.extract_code(),
+        );
+        let txn = accounts[0]
+            .transaction()
+            .sequence_number(0)
+            .payload(payload)
+            .max_gas_amount(MAX_GAS_AMOUNT)
+            .gas_unit_price(GAS_UNIT_PRICE)
+            .sign();
+        let output = execute_transaction(&base, txn)?;
+        if output.status() != &TransactionStatus::Keep(ExecutionStatus::Success) {
+            return Err(CliError::UnexpectedError(format!(
+                "Failed to publish the package: {:?}",
+                output.status()
+            )));
+        }
+
+        Ok(Self {
+            base,
+            accounts,
+            targets,
+            invariants,
+        })
+    }
+
+    /// Executes `calls` from the base state, stopping at the first failure. Returns the failure,
+    /// the number of calls executed, and the addresses written to.
+    fn run_sequence(
+        &self,
+        calls: &[Call],
+        allowed_abort_codes: &BTreeSet<u64>,
+    ) -> CliTypedResult<(Option<Failure>, usize, BTreeSet<AccountAddress>)> {
+        let storage = self.base.clone();
+        let mut written = BTreeSet::new();
+        for (index, call) in calls.iter().enumerate() {
+            let target = &self.targets[call.target];
+            let output = self.execute_call(&storage, target, call)?;
+            for (state_key, _) in output.write_set().write_op_iter() {
+                if let StateKeyInner::AccessPath(path) = state_key.inner() {
+                    written.insert(path.address);
+                }
+            }
+
+            let failure = |reason: String, invariant| Failure {
+                reason: format!("`{}`: {}", target.name(), reason),
+                call_count: index + 1,
+                invariant,
+            };
+            let TransactionStatus::Keep(status) = output.status() else {
+                // Discarded transactions are rejected before running any code of the package
+                continue;
+            };
+            if let Some(reason) = unexpected_status(status, &target.module, allowed_abort_codes) {
+                return Ok((Some(failure(reason, None)), index + 1, written));
+            }
+            if status != &ExecutionStatus::Success {
+                continue;
+            }
+            for (invariant, (module, function)) in self.invariants.iter().enumerate() {
+                if let Some(reason) = check_invariant(&storage, module, function) {
+                    return Ok((Some(failure(reason, Some(invariant))), index + 1, written));
+                }
+            }
+        }
+        Ok((None, calls.len(), written))
+    }
+
+    fn execute_call(
+        &self,
+        storage: &InMemoryStateStore,
+        target: &Target,
+        call: &Call,
+    ) -> CliTypedResult<TransactionOutput> {
+        let sender = &self.accounts[call.signers[0]];
+        let sequence_number = storage
+            .get_resource::<AccountResource>(*sender.address())?
+            .map(|resource| resource.sequence_number())
+            .unwrap_or_default();
+        let entry_function = EntryFunction::new(
+            target.module.clone(),
+            target.function.clone(),
+            vec![],
+            call.args
+                .iter()
+                .map(|arg| arg.simple_serialize().unwrap())
+                .collect(),
+        );
+        let builder = sender
+            .transaction()
+            .sequence_number(sequence_number)
+            .entry_function(entry_function)
+            .max_gas_amount(MAX_GAS_AMOUNT)
+            .gas_unit_price(GAS_UNIT_PRICE);
+        let txn = if call.signers.len() > 1 {
+            builder
+                .secondary_signers(
+                    call.signers[1..]
+                        .iter()
+                        .map(|signer| self.accounts[*signer].clone())
+                        .collect(),
+                )
+                .sign_multi_agent()
+        } else {
+            builder.sign()
+        };
+        execute_transaction(storage, txn)
+    }
+}
+
+/// Executes `txn` on top of `storage`, applying its writes if it is kept.
+fn execute_transaction(
+    storage: &InMemoryStateStore,
+    txn: SignedTransaction,
+) -> CliTypedResult<TransactionOutput> {
+    let txn_provider = DefaultTxnProvider::new_without_info(into_signature_verified_block(vec![
+        Transaction::UserTransaction(txn),
+    ]));
+    let output = CreditChainVMBlockExecutor::new()
+        .execute_block_no_limit(&txn_provider, storage)
+        .map_err(|status| {
+            CliError::UnexpectedError(format!("Failed to execute transaction: {:?}", status))
+        })?
+        .pop()
+        .ok_or_else(|| CliError::UnexpectedError("No transaction output".to_string()))?;
+    if let TransactionStatus::Keep(_) = output.status() {
+        storage.apply_write_set(output.write_set())?;
+    }
+    Ok(output)
+}
+
+/// Returns why `status` is unexpected, if it is. Aborts with a code declared as an error
+/// constant are expected, as are aborts outside of `module` which are caused by its arguments.
+fn unexpected_status(
+    status: &ExecutionStatus,
+    module: &ModuleId,
+    allowed_abort_codes: &BTreeSet<u64>,
+) -> Option<String> {
+    match status {
+        ExecutionStatus::Success | ExecutionStatus::OutOfGas => None,
+        ExecutionStatus::MoveAbort { info: Some(_), .. } => None,
+        ExecutionStatus::MoveAbort { code, .. } if allowed_abort_codes.contains(code) => None,
+        ExecutionStatus::MoveAbort {
+            location: AbortLocation::Module(location),
+            code,
+            info: None,
+        } if location.address() == module.address() => Some(format!(
+            "abort with undeclared code {} in `{}`",
+            code,
+            location.short_str_lossless()
+        )),
+        ExecutionStatus::MoveAbort { .. } => None,
+        ExecutionStatus::ExecutionFailure {
+            location,
+            function,
+            code_offset,
+        } => Some(format!(
+            "runtime error in function #{} of {} at offset {}",
+            function,
+            render_location(location),
+            code_offset
+        )),
+        ExecutionStatus::MiscellaneousError(code) => Some(format!("VM error {:?}", code)),
+    }
+}
+
+fn render_location(location: &AbortLocation) -> String {
+    match location {
+        AbortLocation::Module(module) => format!("`{}`", module.short_str_lossless()),
+        AbortLocation::Script => "the script".to_string(),
+    }
+}
+
+/// Returns why the invariant `module::function` does not hold, if it does not.
+fn check_invariant(
+    storage: &InMemoryStateStore,
+    module: &ModuleId,
+    function: &Identifier,
+) -> Option<String> {
+    let name = format!("{}::{}", module.short_str_lossless(), function);
+    let output = CreditChainVM::execute_view_function(
+        storage,
+        module.clone(),
+        function.clone(),
+        vec![],
+        vec![],
+        MAX_GAS_AMOUNT,
+    );
+    match output.values {
+        Ok(values) if values == vec![bcs::to_bytes(&true).unwrap()] => None,
+        Ok(_) => Some(format!("invariant `{}` does not hold", name)),
+        Err(err) => Some(format!("invariant `{}` failed: {}", name, err)),
+    }
+}
+
+/// Bytecode coverage of the package, read from the VM trace.
+struct Coverage {
+    trace_path: PathBuf,
+    map: CoverageMap,
+    package_modules: BTreeSet<(AccountAddress, Identifier)>,
+    /// Number of distinct instructions of the package reached so far
+    covered: usize,
+}
+
+impl Coverage {
+    /// Adds the instructions traced since the last update and clears the trace. Returns whether
+    /// new instructions of the package were reached.
+    fn update(&mut self) -> CliTypedResult<bool> {
+        if !self.trace_path.exists() {
+            return Ok(false);
+        }
+        let map = std::mem::replace(
+            &mut self.map,
+            CoverageMap {
+                exec_maps: BTreeMap::new(),
+            },
+        );
+        self.map = map.update_coverage_from_trace_file(&self.trace_path)?;
+        self.clear_trace()?;
+
+        let covered = self
+            .map
+            .exec_maps
+            .values()
+            .flat_map(|exec_map| exec_map.module_maps.iter())
+            .filter(|(module, _)| self.package_modules.contains(module))
+            .flat_map(|(_, module_map)| module_map.function_maps.values())
+            .map(|function_map| function_map.len())
+            .sum();
+        let new_coverage = covered > self.covered;
+        self.covered = covered;
+        Ok(new_coverage)
+    }
+
+    /// Empties the trace file. It is truncated rather than removed, as the VM keeps it open and
+    /// would otherwise go on writing to an unlinked file.
+    fn clear_trace(&self) -> CliTypedResult<()> {
+        if !self.trace_path.exists() {
+            return Ok(());
+        }
+        fs::OpenOptions::new()
+            .write(true)
+            .open(&self.trace_path)
+            .and_then(|file| file.set_len(0))
+            .map_err(|err| CliError::IO(self.trace_path.display().to_string(), err))
+    }
+}
+
+struct Fuzzer {
+    executor: Executor,
+    generator: Generator,
+    coverage: Coverage,
+    allowed_abort_codes: BTreeSet<u64>,
+    /// Number of calls executed so far
+    calls: u64,
+}
+
+impl Fuzzer {
+    /// Runs `calls`, returning the failure found, if any, and whether new code was reached.
+    fn run(&mut self, calls: &[Call]) -> CliTypedResult<(Option<Failure>, bool)> {
+        let (failure, call_count, written) = self
+            .executor
+            .run_sequence(calls, &self.allowed_abort_codes)?;
+        self.calls += call_count as u64;
+        self.generator.addresses.extend(written);
+        let new_coverage = self.coverage.update()?;
+        Ok((failure, new_coverage))
+    }
+
+    /// Removes the calls and simplifies the arguments of `calls` which are not needed to
+    /// reproduce the failure `reason`.
+    fn minimize(&mut self, reason: &str, mut calls: Vec<Call>) -> CliTypedResult<Vec<Call>> {
+        for index in (0..calls.len()).rev() {
+            if calls.len() == 1 || index >= calls.len() {
+                continue;
+            }
+            let mut candidate = calls.clone();
+            candidate.remove(index);
+            if let Some(reproduction) = self.reproduce(reason, candidate)? {
+                calls = reproduction;
+            }
+        }
+        for index in 0..calls.len() {
+            for arg in 0..calls[index].args.len() {
+                let simplest =
+                    self.executor.targets[calls[index].target].arg_types[arg].simplest_value();
+                if calls[index].args[arg] == simplest {
+                    continue;
+                }
+                let mut candidate = calls.clone();
+                candidate[index].args[arg] = simplest;
+                if let Some(reproduction) = self.reproduce(reason, candidate)? {
+                    calls = reproduction;
+                }
+            }
+        }
+        Ok(calls)
+    }
+
+    /// Returns `calls` up to the failing one if they fail because of `reason`.
+    fn reproduce(
+        &mut self,
+        reason: &str,
+        mut calls: Vec<Call>,
+    ) -> CliTypedResult<Option<Vec<Call>>> {
+        match self.run(&calls)?.0 {
+            Some(failure) if failure.reason == reason && failure.call_count <= calls.len() => {
+                calls.truncate(failure.call_count);
+                Ok(Some(calls))
+            },
+            _ => Ok(None),
+        }
+    }
+
+    /// Writes `calls` as a Move unit test into the first free `fuzz_repro_<n>.move` file of
+    /// `dir`, and returns its path.
+    fn write_reproduction(
+        &self,
+        dir: &Path,
+        reason: &str,
+        calls: &[Call],
+        invariant: Option<usize>,
+    ) -> CliTypedResult<PathBuf> {
+        fs::create_dir_all(dir).map_err(|err| CliError::IO(dir.display().to_string(), err))?;
+        let (index, path) = (1..)
+            .map(|index| (index, dir.join(format!("fuzz_repro_{}.move", index))))
+            .find(|(_, path)| !path.exists())
+            .unwrap();
+
+        let accounts = calls
+            .iter()
+            .flat_map(|call| call.signers.iter().copied())
+            .collect::<BTreeSet<_>>();
+        let account_address =
+            |account: usize| self.executor.accounts[account].address().to_hex_literal();
+        let signer_name = |account: usize| format!("account{}", account);
+
+        let mut source = format!(
+            "// Reproduction found by `creditchain move fuzz`:\n\
+             // {}\n\
+             //\n\
+             // Unit tests do not run `init_module`: if the package relies on it, call it or a\n\
+             // test-only initializer at the start of the test.\n\
+             #[test_only]\n\
+             module {}::fuzz_repro_{} {{\n",
+            reason,
+            account_address(0),
+            index
+        );
+        source.push_str(&format!(
+            "    #[test({})]\n    fun reproduce({}) {{\n",
+            accounts
+                .iter()
+                .map(|account| format!(
+                    "{} = @{}",
+                    signer_name(*account),
+                    account_address(*account)
+                ))
+                .collect::<Vec<_>>()
+                .join(", "),
+            accounts
+                .iter()
+                .map(|account| format!("{}: &signer", signer_name(*account)))
+                .collect::<Vec<_>>()
+                .join(", "),
+        ));
+        for account in &accounts {
+            source.push_str(&format!(
+                "        creditchain_framework::account::create_account_for_test(@{});\n",
+                account_address(*account)
+            ));
+        }
+        for call in calls {
+            let target = &self.executor.targets[call.target];
+            if !target.is_public {
+                source.push_str(&format!(
+                    "        // `{}` is not public: move this test into its module to call it\n",
+                    target.name()
+                ));
+            }
+            let args = call.signers[..target.signer_count]
+                .iter()
+                .map(|account| signer_name(*account))
+                .chain(
+                    target
+                        .arg_types
+                        .iter()
+                        .zip(&call.args)
+                        .map(|(ty, value)| ty.render_value(value)),
+                )
+                .collect::<Vec<_>>();
+            source.push_str(&format!(
+                "        {}({});\n",
+                target.name(),
+                args.join(", ")
+            ));
+        }
+        if let Some(invariant) = invariant {
+            let (module, function) = &self.executor.invariants[invariant];
+            source.push_str(&format!(
+                "        assert!({}::{}(), 0);\n",
+                module.short_str_lossless(),
+                function
+            ));
+        }
+        source.push_str("    }\n}\n");
+
+        fs::write(&path, source).map_err(|err| CliError::IO(path.display().to_string(), err))?;
+        Ok(path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test::CliTestFramework;
+    use creditchain_temppath::TempPath;
+
+    const COUNTER_MODULE: &str = r#"
+module 0xcafe::counter {
+    use std::signer;
+
+    /// The amount added at once is too large
+    const E_TOO_LARGE: u64 = 1;
+    const MAGIC: u64 = 4242;
+
+    struct Counter has key {
+        value: u64,
+    }
+
+    public entry fun add(account: &signer, amount: u64) acquires Counter {
+        assert!(amount <= 1000000, E_TOO_LARGE);
+        let addr = signer::address_of(account);
+        if (!exists<Counter>(addr)) {
+            move_to(account, Counter { value: 0 });
+        };
+        if (amount == MAGIC) {
+            abort 7
+        };
+        let counter = borrow_global_mut<Counter>(addr);
+        counter.value = counter.value + amount;
+    }
+}
+"#;
+
+    const FLAG_MODULE: &str = r#"
+module 0xcafe::flag {
+    use std::signer;
+
+    struct Flag has key {}
+
+    public entry fun raise(account: &signer) {
+        if (!exists<Flag>(signer::address_of(account))) {
+            move_to(account, Flag {});
+        }
+    }
+
+    #[view]
+    #[invariant]
+    public fun flag_is_down(): bool {
+        !exists<Flag>(@0xcafe)
+    }
+}
+"#;
+
+    fn create_package() -> TempPath {
+        let dir = TempPath::new();
+        dir.create_as_dir().unwrap();
+        fs::create_dir_all(dir.path().join("sources")).unwrap();
+        fs::write(
+            dir.path().join("Move.toml"),
+            format!(
+                "[package]\nname = \"FuzzTest\"\nversion = \"0.0.0\"\n\n[dependencies]\n\
+                 CreditChainFramework = {{ local = \"{}\" }}\n",
+                CliTestFramework::creditchain_framework_dir().display()
+            ),
+        )
+        .unwrap();
+        fs::write(dir.path().join("sources/counter.move"), COUNTER_MODULE).unwrap();
+        dir
+    }
+
+    fn fuzz_package(package: &TempPath, output_dir: &TempPath, allowed: Vec<u64>) -> FuzzPackage {
+        FuzzPackage {
+            iterations: 300,
+            max_calls: 1,
+            signers: 1,
+            seed: Some(7),
+            filter: None,
+            allowed_abort_codes: allowed,
+            output_dir: Some(output_dir.path().to_path_buf()),
+            move_options: MovePackageOptions {
+                package_dir: Some(package.path().to_path_buf()),
+                ..MovePackageOptions::new()
+            },
+        }
+    }
+
+    #[tokio::test]
+    async fn test_fuzz_finds_undeclared_abort() {
+        let package = create_package();
+        let output_dir = TempPath::new();
+
+        // With the abort allowed, every sequence passes and the coverage keeps growing: a
+        // single call reaches either the argument check, the addition or the abort
+        let summary = fuzz_package(&package, &output_dir, vec![7])
+            .execute()
+            .await
+            .unwrap();
+        assert!(summary.covered_instructions > 0);
+        assert!(summary.corpus_size > 1);
+        assert!(package.path().join(COVERAGE_MAP_FILE).exists());
+
+        let err = fuzz_package(&package, &output_dir, vec![])
+            .execute()
+            .await
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("abort with undeclared code 7"), "{}", err);
+        let reproduction = fs::read_to_string(output_dir.path().join("fuzz_repro_1.move")).unwrap();
+        assert!(reproduction.contains("module 0xcafe::fuzz_repro_1"));
+        assert!(
+            reproduction.contains("0xcafe::counter::add(account")
+                && reproduction.contains(", 4242u64);"),
+            "{}",
+            reproduction
+        );
+    }
+
+    #[tokio::test]
+    async fn test_fuzz_reports_violated_invariant() {
+        let package = create_package();
+        fs::remove_file(package.path().join("sources/counter.move")).unwrap();
+        fs::write(package.path().join("sources/flag.move"), FLAG_MODULE).unwrap();
+        let output_dir = TempPath::new();
+
+        let err = fuzz_package(&package, &output_dir, vec![])
+            .execute()
+            .await
+            .unwrap_err()
+            .to_string();
+        assert!(
+            err.contains("invariant `0xcafe::flag::flag_is_down` does not hold"),
+            "{}",
+            err
+        );
+        let reproduction = fs::read_to_string(output_dir.path().join("fuzz_repro_1.move")).unwrap();
+        assert!(
+            reproduction.contains("0xcafe::flag::raise(account0);")
+                && reproduction.contains("assert!(0xcafe::flag::flag_is_down(), 0);"),
+            "{}",
+            reproduction
+        );
+    }
+}
@@ -2,18 +2,25 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
-    common::types::{AccountAddressWrapper, CliCommand, CliTypedResult, MovePackageOptions},
+    common::types::{
+        AccountAddressWrapper, CliCommand, CliError, CliTypedResult, MovePackageOptions,
+    },
     move_tool::IncludedArtifacts,
 };
 use anyhow::bail;
 use creditchain_framework::{BuildOptions, BuiltPackage};
 use async_trait::async_trait;
 use clap::Parser;
-use move_compiler_v2::Experiment;
-use move_linter::MoveLintChecks;
+use move_compiler_v2::{external_checks::ExternalChecks, Experiment};
+use move_linter::{MoveLintChecks, CHECKS_KEY, LEVEL_KEY_PREFIX, WARNINGS_AS_ERRORS_KEY};
 use move_model::metadata::{CompilerVersion, LanguageVersion, LATEST_STABLE_LANGUAGE_VERSION};
 use move_package::source_package::std_lib::StdVersion;
-use std::{collections::BTreeMap, path::PathBuf, str::FromStr};
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+    str::FromStr,
+    sync::Arc,
+};
 
 /// Run a Lint tool to show additional warnings about the current package, in addition to ordinary
 /// warnings and/or errors generated by the Move 2 compiler.
@@ -58,6 +65,10 @@ pub struct LintPackage {
     #[clap(long)]
     pub(crate) skip_fetch_latest_git_deps: bool,
 
+    /// Fail instead of accessing the network to fetch dependencies
+    #[clap(long)]
+    pub(crate) offline: bool,
+
     /// Do not complain about unknown attributes in Move code.
     #[clap(long)]
     pub skip_attribute_checks: bool,
@@ -83,6 +94,12 @@ pub struct LintPackage {
     ///   Also runs the default checks.
     /// - "experimental": Run experimental lint checks that are not yet stable.
     ///   Also runs the strict and default checks.
+    /// - "security": Run security-focused lint checks, e.g., unchecked signers, caller
+    ///   controlled `borrow_global_mut`, or objects created without a deletion or transfer
+    ///   policy. Also runs the default checks.
+    ///
+    /// Overrides the `checks` of the `[lint]` section in Move.toml, whose lint levels
+    /// still apply.
     #[clap(long, verbatim_doc_comment, value_parser = clap::value_parser!(LintOptions))]
     pub checks: Option<LintOptions>,
 }
@@ -96,6 +113,7 @@ impl LintPackage {
             named_addresses,
             override_std,
             skip_fetch_latest_git_deps,
+            offline,
             language_version,
             skip_attribute_checks,
             experiments,
@@ -108,6 +126,7 @@ impl LintPackage {
             named_addresses,
             override_std,
             skip_fetch_latest_git_deps,
+            offline,
             language_version,
             skip_attribute_checks,
             experiments,
@@ -127,6 +146,9 @@ pub enum LintOptions {
     /// Run experimental lint checks that are not yet stable.
     /// Includes all strict and default checks.
     Experimental,
+    /// Run security-focused lint checks.
+    /// Also includes the default checks.
+    Security,
 }
 
 impl FromStr for LintOptions {
@@ -137,7 +159,8 @@ impl FromStr for LintOptions {
             "" | "default" => Ok(LintOptions::Default),
             "strict" => Ok(LintOptions::Strict),
             "experimental" => Ok(LintOptions::Experimental),
-            _ => bail!("unrecognized lint option: `{}` (expected one of: `default`, `strict`, `experimental`)", s),
+            "security" => Ok(LintOptions::Security),
+            _ => bail!("unrecognized lint option: `{}` (expected one of: `default`, `strict`, `experimental`, `security`)", s),
         }
     }
 }
@@ -148,6 +171,7 @@ impl LintOptions {
             LintOptions::Default => "default",
             LintOptions::Strict => "strict",
             LintOptions::Experimental => "experimental",
+            LintOptions::Security => "security",
         };
         BTreeMap::from([("checks".to_string(), value.to_string())])
     }
@@ -183,16 +207,68 @@ impl CliCommand<&'static str> for LintPackage {
 
         let build_config = BuiltPackage::create_build_config(&build_options)?;
         let resolved_graph =
-            BuiltPackage::prepare_resolution_graph(package_path, build_config.clone())?;
+            BuiltPackage::prepare_resolution_graph(package_path.clone(), build_config.clone())?;
         BuiltPackage::build_with_external_checks(
             resolved_graph,
             build_options,
             build_config,
-            vec![MoveLintChecks::make(
-                self.checks.unwrap_or_default().to_config(),
-            )],
+            vec![make_lint_checks(&package_path, self.checks)?],
         )?;
 
         Ok("succeeded")
     }
 }
+
+/// Makes the lint checks configured by the `[lint]` section of the package manifest, if any.
+/// If given, `checks` overrides the category of lint checks selected in the manifest.
+fn make_lint_checks(
+    package_path: &Path,
+    checks: Option<LintOptions>,
+) -> CliTypedResult<Arc<dyn ExternalChecks>> {
+    let manifest_path = package_path.join("Move.toml");
+    let manifest = std::fs::read_to_string(&manifest_path).map_err(|err| {
+        CliError::UnableToReadFile(manifest_path.display().to_string(), err.to_string())
+    })?;
+    let manifest_options = move_package_manifest::parse_lint_options(&manifest)
+        .map_err(|err| CliError::UnableToParse("Move.toml", err.to_string()))?
+        .unwrap_or_default();
+    let mut config = match checks {
+        Some(checks) => checks.to_config(),
+        None => BTreeMap::from([(
+            CHECKS_KEY.to_string(),
+            manifest_options
+                .checks
+                .map_or("default".to_string(), |category| category.to_string()),
+        )]),
+    };
+    config.insert(
+        WARNINGS_AS_ERRORS_KEY.to_string(),
+        manifest_options.warnings_as_errors.to_string(),
+    );
+    for (name, level) in manifest_options.levels {
+        config.insert(format!("{}{}", LEVEL_KEY_PREFIX, name), level.to_string());
+    }
+    MoveLintChecks::try_make(config).map_err(|err| {
+        CliError::CommandArgumentError(format!("Invalid `[lint]` section in Move.toml: {:#}", err))
+    })
+}
+
+/// Builds the package at `package_path` with the lint checks configured by the `[lint]` section
+/// of its manifest. Lints denied there (or all lints, if `warnings_as_errors` is set) are
+/// reported as errors, and fail the build.
+pub(crate) fn build_with_manifest_lints(
+    package_path: PathBuf,
+    mut build_options: BuildOptions,
+) -> CliTypedResult<BuiltPackage> {
+    build_options
+        .experiments
+        .push(Experiment::LINT_CHECKS.to_string());
+    let lint_checks = make_lint_checks(&package_path, None)?;
+    let build_config = BuiltPackage::create_build_config(&build_options)?;
+    let resolved_graph =
+        BuiltPackage::prepare_resolution_graph(package_path, build_config.clone())?;
+    BuiltPackage::build_with_external_checks(resolved_graph, build_options, build_config, vec![
+        lint_checks,
+    ])
+    .map_err(|err| CliError::MoveCompilationError(format!("{:#}", err)))
+}
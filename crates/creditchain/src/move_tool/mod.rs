@@ -21,9 +21,11 @@ use crate::{
     governance::CompileScriptFunction,
     move_tool::{
         bytecode::{Decompile, Disassemble},
+        check_upgrade::CheckUpgrade,
         coverage::SummaryCoverage,
         fmt::Fmt,
-        lint::LintPackage,
+        fuzz::FuzzPackage,
+        lint::{build_with_manifest_lints, LintPackage},
         manifest::{Dependency, ManifestNamedAddress, MovePackageManifest, PackageInfo},
     },
     CliCommand, CliResult,
@@ -81,8 +83,10 @@ use url::Url;
 
 pub mod creditchain_debug_natives;
 mod bytecode;
+pub mod check_upgrade;
 pub mod coverage;
 mod fmt;
+mod fuzz;
 mod lint;
 mod manifest;
 pub mod package_hooks;
@@ -101,6 +105,7 @@ const HELLO_BLOCKCHAIN_EXAMPLE: &str = include_str!(
 #[derive(Subcommand)]
 pub enum MoveTool {
     BuildPublishPayload(BuildPublishPayload),
+    CheckUpgrade(CheckUpgrade),
     Clean(CleanPackage),
     ClearStagingArea(ClearStagingArea),
     #[clap(alias = "build")]
@@ -119,6 +124,7 @@ pub enum MoveTool {
     #[clap(alias = "doc")]
     Document(DocumentPackage),
     Download(DownloadPackage),
+    Fuzz(FuzzPackage),
     Init(InitPackage),
     Lint(LintPackage),
     List(ListPackage),
@@ -141,6 +147,7 @@ impl MoveTool {
     pub async fn execute(self) -> CliResult {
         match self {
             MoveTool::BuildPublishPayload(tool) => tool.execute_serialized().await,
+            MoveTool::CheckUpgrade(tool) => tool.execute_serialized().await,
             MoveTool::Clean(tool) => tool.execute_serialized().await,
             MoveTool::ClearStagingArea(tool) => tool.execute_serialized().await,
             MoveTool::Compile(tool) => tool.execute_serialized().await,
@@ -159,6 +166,7 @@ impl MoveTool {
             MoveTool::Decompile(tool) => tool.execute_serialized().await,
             MoveTool::Document(tool) => tool.execute_serialized().await,
             MoveTool::Download(tool) => tool.execute_serialized().await,
+            MoveTool::Fuzz(tool) => tool.execute_serialized().await,
             MoveTool::Init(tool) => tool.execute_serialized_success().await,
             MoveTool::List(tool) => tool.execute_serialized().await,
             MoveTool::Prove(tool) => tool.execute_serialized().await,
@@ -397,6 +405,12 @@ pub struct CompilePackage {
     #[clap(long)]
     pub fetch_deps_only: bool,
 
+    /// Run the lint checks configured in the `[lint]` section of Move.toml
+    ///
+    /// Lints denied there (or all lints, if `warnings_as_errors` is set) fail the build.
+    #[clap(long)]
+    pub lint: bool,
+
     #[clap(flatten)]
     pub included_artifacts_args: IncludedArtifactsArgs,
     #[clap(flatten)]
@@ -423,8 +437,12 @@ impl CliCommand<Vec<String>> for CompilePackage {
             BuiltPackage::prepare_resolution_graph(package_path, config)?;
             return Ok(vec![]);
         }
-        let pack = BuiltPackage::build(self.move_options.get_package_path()?, build_options)
-            .map_err(|e| CliError::MoveCompilationError(format!("{:#}", e)))?;
+        let pack = if self.lint {
+            build_with_manifest_lints(package_path, build_options)?
+        } else {
+            BuiltPackage::build(package_path, build_options)
+                .map_err(|e| CliError::MoveCompilationError(format!("{:#}", e)))?
+        };
         if self.save_metadata {
             pack.extract_metadata_and_save()?;
         }
@@ -538,6 +556,23 @@ pub struct TestPackage {
     /// Dump storage state on failure.
     #[clap(long = "dump")]
     pub dump_state: bool,
+
+    /// Write a JUnit XML report of the test results to this file
+    ///
+    /// The report includes per-test timing, gas used, abort codes and failure locations.
+    #[clap(long, value_parser)]
+    pub junit_report: Option<PathBuf>,
+
+    /// Write the test results as JSON events, one per line, to this file once all tests have run
+    #[clap(long, value_parser)]
+    pub json_report: Option<PathBuf>,
+
+    /// Run the lint checks configured in the `[lint]` section of Move.toml before the tests
+    ///
+    /// Lints denied there (or all lints, if `warnings_as_errors` is set) fail the build, and
+    /// no tests are run.
+    #[clap(long)]
+    pub lint: bool,
 }
 
 pub(crate) fn fix_bytecode_version(
@@ -570,6 +605,7 @@ impl CliCommand<&'static str> for TestPackage {
             full_model_generation: !self.move_options.skip_checks_on_test_code,
             install_dir: self.move_options.output_dir.clone(),
             skip_fetch_latest_git_deps: self.move_options.skip_fetch_latest_git_deps,
+            offline: self.move_options.offline,
             compiler_config: CompilerConfig {
                 known_attributes: known_attributes.clone(),
                 skip_attribute_checks: self.move_options.skip_attribute_checks,
@@ -591,6 +627,16 @@ impl CliCommand<&'static str> for TestPackage {
         };
 
         let path = self.move_options.get_package_path()?;
+        if self.lint {
+            // The unit test compilation does not run external checks, so the package is
+            // additionally built with the lint checks.
+            let build_options = IncludedArtifacts::Sparse.build_options_with_experiments(
+                &self.move_options,
+                vec![],
+                true,
+            )?;
+            build_with_manifest_lints(path.clone(), build_options)?;
+        }
         let result = move_cli::base::test::run_move_unit_tests(
             path.as_path(),
             config.clone(),
@@ -599,6 +645,8 @@ impl CliCommand<&'static str> for TestPackage {
                 report_stacktrace_on_abort: true,
                 report_storage_on_error: self.dump_state,
                 ignore_compile_warnings: self.ignore_compile_warnings,
+                junit_report: self.junit_report.clone(),
+                json_report: self.json_report.clone(),
                 named_address_values: self
                     .move_options
                     .named_addresses
@@ -732,6 +780,7 @@ impl CliCommand<&'static str> for DocumentPackage {
             override_std: move_options.override_std.clone(),
             docgen_options: Some(docgen_options),
             skip_fetch_latest_git_deps: move_options.skip_fetch_latest_git_deps,
+            offline: move_options.offline,
             bytecode_version: fix_bytecode_version(
                 move_options.bytecode_version,
                 move_options.language_version,
@@ -908,6 +957,7 @@ impl IncludedArtifacts {
     ) -> CliTypedResult<BuildOptions> {
         let dev = move_options.dev;
         let skip_fetch_latest_git_deps = move_options.skip_fetch_latest_git_deps;
+        let offline = move_options.offline;
         let named_addresses = move_options.named_addresses();
         let override_std = move_options.override_std.clone();
         let bytecode_version =
@@ -930,6 +980,7 @@ impl IncludedArtifacts {
             named_addresses,
             override_std,
             skip_fetch_latest_git_deps,
+            offline,
             bytecode_version,
             compiler_version,
             language_version,
@@ -41,9 +41,11 @@ async fn maybe_download_package(info: &CustomDepInfo) -> anyhow::Result<()> {
         .join(CompiledPackageLayout::BuildInfo.path())
         .exists()
     {
-        let registry = CachedPackageRegistry::create(
+        // Download the package at the network version pinned in the lock file, if any
+        let registry = CachedPackageRegistry::create_at_version(
             Url::parse(info.node_url.as_str())?,
             load_account_arg(info.package_address.as_str())?,
+            info.network_version,
             false,
         )
         .await?;
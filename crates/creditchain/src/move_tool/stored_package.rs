@@ -44,23 +44,52 @@ impl CachedPackageRegistry {
         url: Url,
         addr: AccountAddress,
         with_bytecode: bool,
+    ) -> anyhow::Result<Self> {
+        Self::create_at_version(url, addr, None, with_bytecode).await
+    }
+
+    /// Creates a new registry as of the given ledger version, or the latest one if `None`.
+    pub async fn create_at_version(
+        url: Url,
+        addr: AccountAddress,
+        version: Option<u64>,
+        with_bytecode: bool,
     ) -> anyhow::Result<Self> {
         let client = Client::new(url);
         // Need to use a different type to deserialize JSON
-        let inner = client
-            .get_account_resource_bcs::<PackageRegistry>(addr, "0x1::code::PackageRegistry")
-            .await?
-            .into_inner();
+        let inner = match version {
+            Some(version) => client
+                .get_account_resource_at_version_bcs::<PackageRegistry>(
+                    addr,
+                    "0x1::code::PackageRegistry",
+                    version,
+                )
+                .await?
+                .into_inner(),
+            None => client
+                .get_account_resource_bcs::<PackageRegistry>(addr, "0x1::code::PackageRegistry")
+                .await?
+                .into_inner(),
+        };
         let mut bytecode = BTreeMap::new();
         if with_bytecode {
             for pack in &inner.packages {
                 for module in &pack.modules {
-                    let bytes = client
-                        .get_account_module(addr, &module.name)
-                        .await?
-                        .into_inner()
-                        .bytecode
-                        .0;
+                    let bytes = match version {
+                        Some(version) => client
+                            .get_account_module_bcs_at_version(addr, &module.name, version)
+                            .await?
+                            .into_inner()
+                            .to_vec(),
+                        None => {
+                            client
+                                .get_account_module(addr, &module.name)
+                                .await?
+                                .into_inner()
+                                .bytecode
+                                .0
+                        },
+                    };
                     bytecode.insert(module.name.clone(), bytes);
                 }
             }
@@ -68,6 +97,24 @@ impl CachedPackageRegistry {
         Ok(Self { inner, bytecode })
     }
 
+    /// Loads a registry from a local file containing the BCS-encoded `0x1::code::PackageRegistry`
+    /// resource, along with the bytecode of its modules from `.mv` files in `bytecode_dir`.
+    pub fn load(registry_file: &Path, bytecode_dir: Option<&Path>) -> anyhow::Result<Self> {
+        let inner: PackageRegistry = bcs::from_bytes(&fs::read(registry_file)?)?;
+        let mut bytecode = BTreeMap::new();
+        if let Some(bytecode_dir) = bytecode_dir {
+            for pack in &inner.packages {
+                for module in &pack.modules {
+                    let path = bytecode_dir.join(format!("{}.mv", module.name));
+                    if path.exists() {
+                        bytecode.insert(module.name.clone(), fs::read(path)?);
+                    }
+                }
+            }
+        }
+        Ok(Self { inner, bytecode })
+    }
+
     /// Returns the list of packages in this registry by name.
     pub fn package_names(&self) -> Vec<&str> {
         self.inner
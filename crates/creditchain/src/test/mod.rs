@@ -29,9 +29,9 @@ use crate::{
         SubmitVote, SubmitVoteArgs, VerifyProposal, VerifyProposalResponse,
     },
     move_tool::{
-        ArgWithType, CompilePackage, DownloadPackage, FrameworkPackageArgs, IncludedArtifacts,
-        IncludedArtifactsArgs, InitPackage, MemberId, PublishPackage, RunFunction, RunScript,
-        TestPackage,
+        check_upgrade::CheckUpgrade, ArgWithType, CompilePackage, DownloadPackage,
+        FrameworkPackageArgs, IncludedArtifacts, IncludedArtifactsArgs, InitPackage, MemberId,
+        PublishPackage, RunFunction, RunScript, TestPackage,
     },
     node::{
         AnalyzeMode, AnalyzeValidatorPerformance, GetStakePool, InitializeValidator,
@@ -838,6 +838,7 @@ impl CliTestFramework {
             move_options: self.move_options(account_strs),
             save_metadata: false,
             fetch_deps_only: false,
+            lint: false,
             included_artifacts_args: IncludedArtifactsArgs {
                 included_artifacts: included_artifacts.unwrap_or(IncludedArtifacts::Sparse),
             },
@@ -858,6 +859,9 @@ impl CliTestFramework {
             ignore_compile_warnings: false,
             compute_coverage: false,
             dump_state: false,
+            junit_report: None,
+            json_report: None,
+            lint: false,
         }
         .execute()
         .await
@@ -891,6 +895,23 @@ impl CliTestFramework {
         .await
     }
 
+    pub async fn check_upgrade(
+        &self,
+        index: usize,
+        account_strs: BTreeMap<&str, &str>,
+    ) -> CliTypedResult<String> {
+        CheckUpgrade {
+            account: Some(self.account_id(index)),
+            registry_file: None,
+            bytecode_dir: None,
+            move_options: self.move_options(account_strs),
+            rest_options: self.rest_options(),
+            profile_options: Default::default(),
+        }
+        .execute()
+        .await
+    }
+
     pub async fn download_package(
         &self,
         index: usize,
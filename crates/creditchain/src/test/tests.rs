@@ -63,6 +63,7 @@ async fn ensure_every_command_args_work() {
     assert_cmd_not_panic(&["creditchain", "key", "extract-peer", "--help"]).await;
 
     assert_cmd_not_panic(&["creditchain", "move"]).await;
+    assert_cmd_not_panic(&["creditchain", "move", "check-upgrade", "--help"]).await;
     assert_cmd_not_panic(&["creditchain", "move", "clean", "--help"]).await;
     assert_cmd_not_panic(&["creditchain", "move", "compile", "--help"]).await;
     assert_cmd_not_panic(&["creditchain", "move", "compile-script", "--help"]).await;
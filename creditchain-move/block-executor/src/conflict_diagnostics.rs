@@ -0,0 +1,406 @@
+// Copyright © A-p-t-o-s Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! Opt-in diagnostics for parallel (BlockSTMv1) execution. When enabled via
+//! `BlockExecutorLocalConfig::enable_conflict_diagnostics`, the executor records, per block,
+//! which state keys caused read dependencies and validation failures, how many incarnations
+//! each transaction required and how much execution time was wasted on aborted incarnations.
+//! The resulting [BlockConflictReport]s are kept in a bounded in-memory buffer, from which
+//! they can be served as JSON (e.g., by the admin service or the debugger). Reports are only
+//! recorded for parallel execution with BlockSTMv1.
+
+use creditchain_infallible::Mutex;
+use creditchain_mvhashmap::types::TxnIndex;
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{BTreeSet, HashMap, VecDeque},
+    fmt::Debug,
+    hash::Hash,
+    time::Duration,
+};
+
+/// The maximum number of block reports retained in memory.
+pub const MAX_RECENT_CONFLICT_REPORTS: usize = 32;
+/// The maximum number of hot keys included in a single block report.
+const MAX_REPORTED_HOT_KEYS: usize = 20;
+/// The maximum number of dependency chains included in a single block report.
+const MAX_REPORTED_DEPENDENCY_CHAINS: usize = 5;
+
+static RECENT_CONFLICT_REPORTS: Lazy<Mutex<ConflictReportBuffer>> =
+    Lazy::new(|| Mutex::new(ConflictReportBuffer::default()));
+
+/// Serializes the tests that record reports into, or take them from, the global buffer.
+#[cfg(test)]
+pub(crate) static TEST_REPORTS_LOCK: Lazy<Mutex<()>> = Lazy::new(|| Mutex::new(()));
+
+/// The bounded buffer of recent block reports. Also counts the reports that were dropped
+/// (to make room for newer ones), so that consumers can tell if reports are missing.
+#[derive(Default)]
+struct ConflictReportBuffer {
+    reports: VecDeque<BlockConflictReport>,
+    num_dropped_reports: u64,
+}
+
+/// Conflict statistics for a single state key within a block.
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize)]
+pub struct HotKeyReport {
+    /// The debug representation of the state key.
+    pub key: String,
+    /// The number of times a transaction had to wait on an estimated write to this key.
+    pub num_dependency_waits: u64,
+    /// The number of failed validations in which a read of this key was invalidated.
+    pub num_validation_failures: u64,
+    /// The number of distinct transactions that waited on, or failed validation for, this key.
+    pub num_conflicting_txns: usize,
+}
+
+/// Execution statistics for a single transaction that required more than one incarnation.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct TxnConflictReport {
+    pub txn_idx: TxnIndex,
+    pub num_incarnations: u32,
+    pub num_validation_failures: u32,
+    /// Time spent executing incarnations that were later discarded.
+    pub wasted_execution_secs: f64,
+    /// The lower transactions that this transaction waited on during execution.
+    pub dependencies: Vec<TxnIndex>,
+}
+
+/// Conflict report for a single block executed in parallel.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct BlockConflictReport {
+    /// Describes the executed transactions (e.g., the block id or the chunk versions).
+    pub block: String,
+    pub num_txns: usize,
+    /// The total number of executed incarnations, across all transactions.
+    pub num_executions: u64,
+    /// The number of executed incarnations beyond the first one for each transaction.
+    pub num_re_executions: u64,
+    pub num_dependency_waits: u64,
+    pub num_validation_failures: u64,
+    pub total_execution_secs: f64,
+    /// Time spent executing incarnations that were later discarded.
+    pub wasted_execution_secs: f64,
+    /// The keys with the most conflicts, ordered by the total number of conflicts.
+    pub hot_keys: Vec<HotKeyReport>,
+    /// The longest chains of read dependencies (in increasing transaction order).
+    pub dependency_chains: Vec<Vec<TxnIndex>>,
+    /// The transactions that were executed more than once, in increasing transaction order.
+    pub re_executed_txns: Vec<TxnConflictReport>,
+}
+
+/// The most recent block conflict reports, along with the number of older reports that were
+/// dropped because the buffer was full.
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+pub struct RecentConflictReports {
+    /// The most recent block reports (oldest first).
+    pub reports: Vec<BlockConflictReport>,
+    /// The number of reports dropped since the reports were last taken.
+    pub num_dropped_reports: u64,
+}
+
+/// Returns the most recent block conflict reports (oldest first).
+pub fn recent_conflict_reports() -> Vec<BlockConflictReport> {
+    RECENT_CONFLICT_REPORTS
+        .lock()
+        .reports
+        .iter()
+        .cloned()
+        .collect()
+}
+
+/// Removes and returns the most recent block conflict reports, and resets the number of
+/// dropped reports.
+pub fn take_recent_conflict_reports() -> RecentConflictReports {
+    let mut buffer = RECENT_CONFLICT_REPORTS.lock();
+    RecentConflictReports {
+        reports: buffer.reports.drain(..).collect(),
+        num_dropped_reports: std::mem::take(&mut buffer.num_dropped_reports),
+    }
+}
+
+pub(crate) fn record_conflict_report(report: BlockConflictReport) {
+    let mut buffer = RECENT_CONFLICT_REPORTS.lock();
+    if buffer.reports.len() == MAX_RECENT_CONFLICT_REPORTS {
+        buffer.reports.pop_front();
+        buffer.num_dropped_reports += 1;
+    }
+    buffer.reports.push_back(report);
+}
+
+#[derive(Default)]
+struct TxnDiagnostics {
+    num_incarnations: u32,
+    num_validation_failures: u32,
+    total_execution_time: Duration,
+    last_execution_time: Duration,
+    dependencies: BTreeSet<TxnIndex>,
+}
+
+impl TxnDiagnostics {
+    fn wasted_execution_time(&self) -> Duration {
+        self.total_execution_time - self.last_execution_time
+    }
+}
+
+#[derive(Default)]
+struct KeyDiagnostics {
+    num_dependency_waits: u64,
+    num_validation_failures: u64,
+    conflicting_txns: BTreeSet<TxnIndex>,
+}
+
+/// Collects conflict information concurrently from the worker threads executing a block.
+pub(crate) struct ConflictDiagnostics<K> {
+    txns: Vec<Mutex<TxnDiagnostics>>,
+    keys: Mutex<HashMap<K, KeyDiagnostics>>,
+}
+
+impl<K: Clone + Debug + Hash + Eq> ConflictDiagnostics<K> {
+    pub(crate) fn new(num_txns: usize) -> Self {
+        Self {
+            txns: (0..num_txns).map(|_| Mutex::new(TxnDiagnostics::default())).collect(),
+            keys: Mutex::new(HashMap::new()),
+        }
+    }
+
+    fn txn(&self, txn_idx: TxnIndex) -> Option<&Mutex<TxnDiagnostics>> {
+        // Block epilogue (and other out-of-range indices) are not tracked.
+        self.txns.get(txn_idx as usize)
+    }
+
+    pub(crate) fn record_execution(&self, txn_idx: TxnIndex, execution_time: Duration) {
+        if let Some(txn) = self.txn(txn_idx) {
+            let mut txn = txn.lock();
+            txn.num_incarnations += 1;
+            txn.total_execution_time += execution_time;
+            txn.last_execution_time = execution_time;
+        }
+    }
+
+    pub(crate) fn record_dependency(&self, txn_idx: TxnIndex, dep_idx: TxnIndex, key: &K) {
+        if let Some(txn) = self.txn(txn_idx) {
+            txn.lock().dependencies.insert(dep_idx);
+        }
+
+        let mut keys = self.keys.lock();
+        let key_diagnostics = keys.entry(key.clone()).or_default();
+        key_diagnostics.num_dependency_waits += 1;
+        key_diagnostics.conflicting_txns.insert(txn_idx);
+    }
+
+    pub(crate) fn record_validation_failure(
+        &self,
+        txn_idx: TxnIndex,
+        invalid_keys: impl IntoIterator<Item = K>,
+    ) {
+        if let Some(txn) = self.txn(txn_idx) {
+            txn.lock().num_validation_failures += 1;
+        }
+
+        let mut keys = self.keys.lock();
+        for key in invalid_keys {
+            let key_diagnostics = keys.entry(key).or_default();
+            key_diagnostics.num_validation_failures += 1;
+            key_diagnostics.conflicting_txns.insert(txn_idx);
+        }
+    }
+
+    pub(crate) fn into_report(self, block: String) -> BlockConflictReport {
+        let txns: Vec<TxnDiagnostics> = self.txns.into_iter().map(Mutex::into_inner).collect();
+        let keys = self.keys.into_inner();
+        let num_dependency_waits = keys.values().map(|key| key.num_dependency_waits).sum();
+
+        let mut hot_keys: Vec<HotKeyReport> = keys
+            .into_iter()
+            .map(|(key, diagnostics)| HotKeyReport {
+                key: format!("{:?}", key),
+                num_dependency_waits: diagnostics.num_dependency_waits,
+                num_validation_failures: diagnostics.num_validation_failures,
+                num_conflicting_txns: diagnostics.conflicting_txns.len(),
+            })
+            .collect();
+        hot_keys.sort_by(|a, b| {
+            let conflicts = |r: &HotKeyReport| r.num_dependency_waits + r.num_validation_failures;
+            conflicts(b).cmp(&conflicts(a)).then_with(|| a.key.cmp(&b.key))
+        });
+        hot_keys.truncate(MAX_REPORTED_HOT_KEYS);
+
+        let re_executed_txns = txns
+            .iter()
+            .enumerate()
+            .filter(|(_, txn)| txn.num_incarnations > 1)
+            .map(|(txn_idx, txn)| TxnConflictReport {
+                txn_idx: txn_idx as TxnIndex,
+                num_incarnations: txn.num_incarnations,
+                num_validation_failures: txn.num_validation_failures,
+                wasted_execution_secs: txn.wasted_execution_time().as_secs_f64(),
+                dependencies: txn.dependencies.iter().copied().collect(),
+            })
+            .collect();
+
+        BlockConflictReport {
+            block,
+            num_txns: txns.len(),
+            num_executions: txns.iter().map(|txn| txn.num_incarnations as u64).sum(),
+            num_re_executions: txns
+                .iter()
+                .map(|txn| txn.num_incarnations.saturating_sub(1) as u64)
+                .sum(),
+            num_dependency_waits,
+            num_validation_failures: txns
+                .iter()
+                .map(|txn| txn.num_validation_failures as u64)
+                .sum(),
+            total_execution_secs: txns
+                .iter()
+                .map(|txn| txn.total_execution_time)
+                .sum::<Duration>()
+                .as_secs_f64(),
+            wasted_execution_secs: txns
+                .iter()
+                .map(TxnDiagnostics::wasted_execution_time)
+                .sum::<Duration>()
+                .as_secs_f64(),
+            hot_keys,
+            dependency_chains: longest_dependency_chains(&txns),
+            re_executed_txns,
+        }
+    }
+}
+
+/// Returns the longest chains of read dependencies. Dependencies always point to lower
+/// transactions, so the chain lengths can be computed in a single pass in transaction order.
+fn longest_dependency_chains(txns: &[TxnDiagnostics]) -> Vec<Vec<TxnIndex>> {
+    // For each transaction, the length of the longest chain ending at it, and the previous
+    // transaction in that chain.
+    let mut chain_ends: Vec<(usize, Option<usize>)> = Vec::with_capacity(txns.len());
+    for txn in txns {
+        let longest = txn
+            .dependencies
+            .iter()
+            .map(|dep_idx| *dep_idx as usize)
+            .filter(|dep_idx| *dep_idx < chain_ends.len())
+            .max_by_key(|dep_idx| (chain_ends[*dep_idx].0, std::cmp::Reverse(*dep_idx)));
+        chain_ends.push(match longest {
+            Some(dep_idx) => (chain_ends[dep_idx].0 + 1, Some(dep_idx)),
+            None => (1, None),
+        });
+    }
+
+    let mut ends: Vec<usize> = (0..chain_ends.len())
+        .filter(|txn_idx| chain_ends[*txn_idx].0 > 1)
+        .collect();
+    ends.sort_by_key(|txn_idx| (std::cmp::Reverse(chain_ends[*txn_idx].0), *txn_idx));
+
+    let mut chains = Vec::new();
+    let mut covered = BTreeSet::new();
+    for end in ends {
+        if chains.len() == MAX_REPORTED_DEPENDENCY_CHAINS {
+            break;
+        }
+        // Skip the chains that are a prefix of an already reported chain.
+        if covered.contains(&end) {
+            continue;
+        }
+
+        let mut chain = vec![end as TxnIndex];
+        let mut current = end;
+        while let Some(prev) = chain_ends[current].1 {
+            chain.push(prev as TxnIndex);
+            current = prev;
+        }
+        chain.reverse();
+        covered.extend(chain.iter().map(|txn_idx| *txn_idx as usize));
+        chains.push(chain);
+    }
+    chains
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ms(millis: u64) -> Duration {
+        Duration::from_millis(millis)
+    }
+
+    #[test]
+    fn test_conflict_report() {
+        let diagnostics = ConflictDiagnostics::<&'static str>::new(6);
+        for txn_idx in 0..6 {
+            diagnostics.record_execution(txn_idx, ms(10));
+        }
+
+        // Txns 1 -> 2 -> 4 form a chain of dependencies on the "counter" key.
+        diagnostics.record_dependency(2, 1, &"counter");
+        diagnostics.record_dependency(4, 2, &"counter");
+        diagnostics.record_dependency(4, 2, &"counter");
+        diagnostics.record_dependency(5, 3, &"balance");
+
+        // Txn 3 and txn 4 fail validation and are re-executed.
+        diagnostics.record_validation_failure(3, vec!["counter", "balance"]);
+        diagnostics.record_validation_failure(4, vec!["counter"]);
+        diagnostics.record_execution(3, ms(20));
+        diagnostics.record_execution(4, ms(5));
+        diagnostics.record_execution(4, ms(5));
+
+        // Out of range indices (e.g. block epilogue) are ignored.
+        diagnostics.record_execution(6, ms(100));
+
+        let report = diagnostics.into_report("test".to_string());
+        assert_eq!(report.num_txns, 6);
+        assert_eq!(report.num_executions, 9);
+        assert_eq!(report.num_re_executions, 3);
+        assert_eq!(report.num_dependency_waits, 4);
+        assert_eq!(report.num_validation_failures, 2);
+        assert_eq!(report.total_execution_secs, ms(90).as_secs_f64());
+        assert_eq!(report.wasted_execution_secs, ms(25).as_secs_f64());
+
+        assert_eq!(report.hot_keys, vec![
+            HotKeyReport {
+                key: "\"counter\"".to_string(),
+                num_dependency_waits: 3,
+                num_validation_failures: 2,
+                num_conflicting_txns: 3,
+            },
+            HotKeyReport {
+                key: "\"balance\"".to_string(),
+                num_dependency_waits: 1,
+                num_validation_failures: 1,
+                num_conflicting_txns: 2,
+            },
+        ]);
+        assert_eq!(report.dependency_chains, vec![vec![1, 2, 4], vec![3, 5]]);
+
+        let re_executed: Vec<_> = report
+            .re_executed_txns
+            .iter()
+            .map(|txn| (txn.txn_idx, txn.num_incarnations, txn.num_validation_failures))
+            .collect();
+        assert_eq!(re_executed, vec![(3, 2, 1), (4, 3, 1)]);
+        assert_eq!(report.re_executed_txns[1].dependencies, vec![2]);
+        assert_eq!(
+            report.re_executed_txns[1].wasted_execution_secs,
+            ms(15).as_secs_f64()
+        );
+    }
+
+    #[test]
+    fn test_recent_conflict_reports_are_bounded() {
+        let _guard = TEST_REPORTS_LOCK.lock();
+        take_recent_conflict_reports();
+        for i in 0..MAX_RECENT_CONFLICT_REPORTS + 3 {
+            let diagnostics = ConflictDiagnostics::<u64>::new(1);
+            record_conflict_report(diagnostics.into_report(i.to_string()));
+        }
+
+        let recent_reports = take_recent_conflict_reports();
+        assert_eq!(recent_reports.reports.len(), MAX_RECENT_CONFLICT_REPORTS);
+        assert_eq!(recent_reports.reports[0].block, "3");
+        assert_eq!(recent_reports.num_dropped_reports, 3);
+        assert!(recent_conflict_reports().is_empty());
+        assert_eq!(take_recent_conflict_reports().num_dropped_reports, 0);
+    }
+}
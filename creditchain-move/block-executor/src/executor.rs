@@ -6,6 +6,7 @@ use crate::{
     captured_reads::CapturedReads,
     code_cache_global::GlobalModuleCache,
     code_cache_global_manager::CreditChainModuleCacheManagerGuard,
+    conflict_diagnostics::{record_conflict_report, ConflictDiagnostics},
     counters::{
         self, BLOCK_EXECUTOR_INNER_EXECUTE_BLOCK, PARALLEL_EXECUTION_SECONDS,
         RAYON_EXECUTION_SECONDS, TASK_EXECUTE_SECONDS, TASK_VALIDATE_SECONDS, VM_INIT_SECONDS,
@@ -31,7 +32,7 @@ use creditchain_aggregator::{
 };
 use creditchain_crypto::HashValue;
 use creditchain_drop_helper::DEFAULT_DROPPER;
-use creditchain_logger::{error, info};
+use creditchain_logger::{error, info, sample, sample::SampleRate, warn};
 use creditchain_mvhashmap::{
     types::{Incarnation, MVDelayedFieldsError, TxnIndex, ValueWithLayout},
     unsync_map::UnsyncMap,
@@ -77,6 +78,7 @@ use std::{
         atomic::{AtomicBool, AtomicU32, Ordering},
         Arc,
     },
+    time::{Duration, Instant},
 };
 
 struct SharedSyncParams<'a, 'b, T, E, S>
@@ -723,6 +725,28 @@ where
                 ))
     }
 
+    // Only called with conflict diagnostics enabled, after a failed validation. The keys are
+    // re-validated individually, so concurrent updates may cause the recorded set to differ
+    // from the keys that caused the failure (best-effort).
+    fn record_validation_failure(
+        txn_idx: TxnIndex,
+        last_input_output: &TxnLastInputOutput<T, E::Output, E::Error>,
+        versioned_cache: &MVHashMap<T::Key, T::Tag, T::Value, DelayedFieldID>,
+        conflict_diagnostics: &ConflictDiagnostics<T::Key>,
+    ) {
+        let invalid_read_keys = last_input_output
+            .read_set(txn_idx)
+            .map(|read_set| {
+                read_set.invalid_read_keys(
+                    versioned_cache.data(),
+                    versioned_cache.group_data(),
+                    txn_idx,
+                )
+            })
+            .unwrap_or_default();
+        conflict_diagnostics.record_validation_failure(txn_idx, invalid_read_keys);
+    }
+
     fn update_transaction_on_abort(
         txn_idx: TxnIndex,
         last_input_output: &TxnLastInputOutput<T, E::Output, E::Error>,
@@ -1286,6 +1310,7 @@ where
         total_txns_to_materialize: &AtomicU32,
         num_running_workers: &AtomicU32,
         num_workers: usize,
+        conflict_diagnostics: Option<&ConflictDiagnostics<T::Key>>,
     ) -> Result<(), PanicOr<ParallelBlockExecutionError>> {
         defer!( num_running_workers.fetch_sub(1, Ordering::SeqCst); );
 
@@ -1472,6 +1497,16 @@ where
                         versioned_cache,
                         skip_module_reads_validation.load(Ordering::Relaxed),
                     );
+                    if !valid {
+                        if let Some(conflict_diagnostics) = conflict_diagnostics {
+                            Self::record_validation_failure(
+                                txn_idx,
+                                last_input_output,
+                                versioned_cache,
+                                conflict_diagnostics,
+                            );
+                        }
+                    }
                     Self::update_on_validation(
                         txn_idx,
                         incarnation,
@@ -1487,6 +1522,7 @@ where
                     incarnation,
                     ExecutionTaskType::Execution,
                 ) => {
+                    let execution_start = Instant::now();
                     let needs_suffix_validation = Self::execute(
                         txn_idx,
                         incarnation,
@@ -1503,8 +1539,12 @@ where
                             start_shared_counter,
                             shared_counter,
                             incarnation,
-                        ),
+                        )
+                        .with_conflict_diagnostics(conflict_diagnostics),
                     )?;
+                    if let Some(conflict_diagnostics) = conflict_diagnostics {
+                        conflict_diagnostics.record_execution(txn_idx, execution_start.elapsed());
+                    }
                     scheduler.finish_execution(txn_idx, incarnation, needs_suffix_validation)?
                 },
                 SchedulerTask::ExecutionTask(_, _, ExecutionTaskType::Wakeup(condvar)) => {
@@ -1868,6 +1908,11 @@ where
         let num_txns_materialized = AtomicU32::new(0);
         let total_txns_to_materialize = AtomicU32::new(num_txns);
         let num_running_workers = AtomicU32::new(num_workers as u32);
+        let conflict_diagnostics = self
+            .config
+            .local
+            .enable_conflict_diagnostics
+            .then(|| ConflictDiagnostics::new(num_txns as usize));
 
         let timer = RAYON_EXECUTION_SECONDS.start_timer();
         self.executor_thread_pool.scope(|s| {
@@ -1892,6 +1937,7 @@ where
                         &total_txns_to_materialize,
                         &num_running_workers,
                         num_workers,
+                        conflict_diagnostics.as_ref(),
                     ) {
                         // If there are multiple errors, they all get logged:
                         // ModulePathReadWriteError and FatalVMError variant is logged at construction,
@@ -1909,6 +1955,12 @@ where
         });
         drop(timer);
 
+        if let Some(conflict_diagnostics) = conflict_diagnostics {
+            record_conflict_report(
+                conflict_diagnostics.into_report(format!("{:?}", transaction_slice_metadata)),
+            );
+        }
+
         self.finalize_parallel_execution(
             &shared_maybe_error,
             scheduler.pop_from_commit_queue().is_ok(),
@@ -2498,6 +2550,17 @@ where
     ) -> BlockExecutionResult<BlockOutput<T::Key, E::Output>, E::Error> {
         let _timer = BLOCK_EXECUTOR_INNER_EXECUTE_BLOCK.start_timer();
 
+        if self.config.local.enable_conflict_diagnostics
+            && (self.config.local.concurrency_level <= 1 || self.config.local.blockstm_v2)
+        {
+            sample!(
+                SampleRate::Duration(Duration::from_secs(60)),
+                warn!(
+                    "Conflict diagnostics are enabled, but only recorded for parallel execution with BlockSTMv1!"
+                )
+            );
+        }
+
         if self.config.local.concurrency_level > 1 {
             let parallel_result = if self.config.local.blockstm_v2 {
                 unimplemented!("BlockSTMv2 is not fully implemented");
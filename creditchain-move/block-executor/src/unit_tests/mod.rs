@@ -14,6 +14,7 @@ use crate::{
             ValueType,
         },
     },
+    conflict_diagnostics::{take_recent_conflict_reports, HotKeyReport, TEST_REPORTS_LOCK},
     errors::SequentialBlockExecutionError,
     executor::BlockExecutor,
     scheduler::{
@@ -34,7 +35,12 @@ use creditchain_types::{
     },
     contract_event::TransactionEvent,
     executable::ModulePath,
-    state_store::{state_value::StateValueMetadata, MockStateView},
+    state_store::{
+        errors::StateViewError,
+        state_storage_usage::StateStorageUsage,
+        state_value::{StateValue, StateValueMetadata},
+        MockStateView, TStateView,
+    },
     transaction::AuxiliaryInfo,
     write_set::WriteOpKind,
 };
@@ -48,6 +54,8 @@ use std::{
     hash::Hash,
     marker::PhantomData,
     sync::Arc,
+    thread,
+    time::Duration,
 };
 
 #[test]
@@ -588,6 +596,139 @@ where
     baseline.assert_parallel_output(&output);
 }
 
+/// A state view that is empty, but takes a while to read some of the keys. Used to control
+/// the order in which the worker threads observe the writes of concurrent transactions.
+struct SlowStateView {
+    slow_keys: HashSet<KeyType<u32>>,
+}
+
+impl TStateView for SlowStateView {
+    type Key = KeyType<u32>;
+
+    fn get_state_value(&self, key: &KeyType<u32>) -> Result<Option<StateValue>, StateViewError> {
+        if self.slow_keys.contains(key) {
+            thread::sleep(Duration::from_millis(200));
+        }
+        Ok(None)
+    }
+
+    fn get_usage(&self) -> Result<StateStorageUsage, StateViewError> {
+        unreachable!("Not used in tests");
+    }
+}
+
+#[test]
+fn conflict_diagnostics() {
+    let (key_0, key_1) = (KeyType(0), KeyType(1));
+    let (slow_key_0, slow_key_1) = (KeyType(10), KeyType(11));
+
+    // Txn 0 is slow and writes key 0. Meanwhile, txn 1 reads key 0 from storage and writes
+    // key 1, which txn 2 then reads. Once txn 0 finishes, txn 1 fails validation. Its writes
+    // become estimates while it is slowly re-executed, so txn 2 fails validation as well and,
+    // when re-executed, waits on txn 1.
+    let transactions = vec![
+        MockTransaction::from_behavior(MockIncarnation::<KeyType<u32>, MockEvent>::new(
+            vec![(slow_key_0, false)],
+            vec![(key_0, random_value(false), false)],
+            vec![],
+            vec![],
+            1,
+        )),
+        MockTransaction::from_behaviors(vec![
+            MockIncarnation::new(
+                vec![(key_0, false)],
+                vec![(key_1, random_value(false), false)],
+                vec![],
+                vec![],
+                1,
+            ),
+            MockIncarnation::new(
+                vec![(key_0, false), (slow_key_1, false)],
+                vec![(key_1, random_value(false), false)],
+                vec![],
+                vec![],
+                1,
+            ),
+        ]),
+        MockTransaction::from_behavior(MockIncarnation::new(
+            vec![(key_1, false)],
+            vec![],
+            vec![],
+            vec![],
+            1,
+        )),
+    ];
+
+    // Two workers: while txn 0 executes, txn 1 and txn 2 are executed one after another.
+    let mut config = BlockExecutorConfig::new_no_block_limit(2);
+    config.local.enable_conflict_diagnostics = true;
+    let executor_thread_pool = Arc::new(
+        rayon::ThreadPoolBuilder::new()
+            .num_threads(2)
+            .build()
+            .unwrap(),
+    );
+    let data_view = SlowStateView {
+        slow_keys: HashSet::from([slow_key_0, slow_key_1]),
+    };
+    let txn_provider = DefaultTxnProvider::new_without_info(transactions);
+
+    let _guard = TEST_REPORTS_LOCK.lock();
+    take_recent_conflict_reports();
+    let output = BlockExecutor::<
+        MockTransaction<KeyType<u32>, MockEvent>,
+        MockTask<KeyType<u32>, MockEvent>,
+        SlowStateView,
+        NoOpTransactionCommitHook<MockOutput<KeyType<u32>, MockEvent>, usize>,
+        _,
+    >::new(config, executor_thread_pool, None)
+    .execute_transactions_parallel(
+        &txn_provider,
+        &data_view,
+        &TransactionSliceMetadata::block_from_u64(0, 1),
+        &mut CreditChainModuleCacheManagerGuard::none(),
+    );
+    BaselineOutput::generate(txn_provider.get_txns(), None).assert_parallel_output(&output);
+
+    let mut reports = take_recent_conflict_reports().reports;
+    assert_eq!(reports.len(), 1);
+    let report = reports.pop().unwrap();
+    assert_eq!(report.num_txns, 3);
+    assert_eq!(report.num_executions, 5);
+    assert_eq!(report.num_re_executions, 2);
+    assert_eq!(report.num_dependency_waits, 1);
+    assert_eq!(report.num_validation_failures, 2);
+    assert_eq!(report.hot_keys, vec![
+        HotKeyReport {
+            key: format!("{:?}", key_1),
+            num_dependency_waits: 1,
+            num_validation_failures: 1,
+            num_conflicting_txns: 1,
+        },
+        HotKeyReport {
+            key: format!("{:?}", key_0),
+            num_dependency_waits: 0,
+            num_validation_failures: 1,
+            num_conflicting_txns: 1,
+        },
+    ]);
+    assert_eq!(report.dependency_chains, vec![vec![1, 2]]);
+
+    let re_executed: Vec<_> = report
+        .re_executed_txns
+        .iter()
+        .map(|txn| {
+            (
+                txn.txn_idx,
+                txn.num_incarnations,
+                txn.num_validation_failures,
+                txn.dependencies.clone(),
+            )
+        })
+        .collect();
+    assert_eq!(re_executed, vec![(1, 2, 1, vec![]), (2, 2, 1, vec![1])]);
+}
+
 fn random_value(delete_value: bool) -> ValueType {
     ValueType::from_value(
         (0..32).map(|_| (random::<u8>())).collect::<Vec<u8>>(),
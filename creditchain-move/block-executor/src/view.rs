@@ -16,6 +16,7 @@ use crate::{
         UnsyncReadSet,
     },
     code_cache_global::GlobalModuleCache,
+    conflict_diagnostics::ConflictDiagnostics,
     counters,
     scheduler::{DependencyResult, DependencyStatus, TWaitForDependency},
     scheduler_wrapper::SchedulerWrapper,
@@ -240,6 +241,7 @@ pub(crate) struct ParallelState<'a, T: Transaction> {
     incarnation: Incarnation,
     pub(crate) captured_reads:
         RefCell<CapturedReads<T, ModuleId, CompiledModule, Module, CreditChainModuleExtension>>,
+    conflict_diagnostics: Option<&'a ConflictDiagnostics<T::Key>>,
 }
 
 fn get_delayed_field_value_impl<T: Transaction>(
@@ -537,6 +539,22 @@ impl<'a, T: Transaction> ParallelState<'a, T> {
             counter: shared_counter,
             incarnation,
             captured_reads: RefCell::new(CapturedReads::new(blockstm_v2.then_some(incarnation))),
+            conflict_diagnostics: None,
+        }
+    }
+
+    /// Records read dependencies encountered during execution in the provided diagnostics.
+    pub(crate) fn with_conflict_diagnostics(
+        mut self,
+        conflict_diagnostics: Option<&'a ConflictDiagnostics<T::Key>>,
+    ) -> Self {
+        self.conflict_diagnostics = conflict_diagnostics;
+        self
+    }
+
+    fn record_dependency(&self, txn_idx: TxnIndex, dep_idx: TxnIndex, key: &T::Key) {
+        if let Some(conflict_diagnostics) = self.conflict_diagnostics {
+            conflict_diagnostics.record_dependency(txn_idx, dep_idx, key);
         }
     }
 
@@ -590,6 +608,7 @@ impl<'a, T: Transaction> ParallelState<'a, T> {
                     unreachable!("Reading group size does not require a specific tag look-up");
                 },
                 Err(Dependency(dep_idx)) => {
+                    self.record_dependency(txn_idx, dep_idx, group_key);
                     if !wait_for_dependency(&self.scheduler, txn_idx, dep_idx)? {
                         return Err(PartialVMError::new(
                             StatusCode::SPECULATIVE_EXECUTION_ABORT_ERROR,
@@ -689,6 +708,7 @@ impl<T: Transaction> ResourceState<T> for ParallelState<'_, T> {
                     return Ok(ReadResult::Uninitialized);
                 },
                 Err(Dependency(dep_idx)) => {
+                    self.record_dependency(txn_idx, dep_idx, key);
                     match wait_for_dependency(&self.scheduler, txn_idx, dep_idx) {
                         Err(e) => {
                             error!("Error {:?} in wait for dependency", e);
@@ -829,6 +849,7 @@ impl<T: Transaction> ResourceGroupState<T> for ParallelState<'_, T> {
                     continue;
                 },
                 Err(Dependency(dep_idx)) => {
+                    self.record_dependency(txn_idx, dep_idx, group_key);
                     if !wait_for_dependency(&self.scheduler, txn_idx, dep_idx)? {
                         // TODO[agg_v2](cleanup): consider changing from PartialVMResult<GroupReadResult> to GroupReadResult
                         // like in ReadResult for resources.
@@ -2,11 +2,12 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use anyhow::{bail, Result};
+use creditchain_gas_profiling::{GasProfile, ProfileFormat};
 use creditchain_move_debugger::creditchain_debugger::CreditChainDebugger;
 use creditchain_rest_client::Client;
 use creditchain_types::transaction::Transaction;
 use creditchain_vm::CreditChainVM;
-use clap::{Parser, Subcommand};
+use clap::{Parser, Subcommand, ValueEnum};
 use std::path::{Path, PathBuf};
 use url::Url;
 
@@ -18,6 +19,16 @@ pub enum Target {
     DB { path: PathBuf },
 }
 
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ReportFormat {
+    /// Human-readable HTML report with flamegraphs.
+    Html,
+    /// Chrome Trace Event JSON, viewable in `chrome://tracing` or Perfetto.
+    ChromeTrace,
+    /// Speedscope JSON, viewable in speedscope.
+    Speedscope,
+}
+
 #[derive(Parser)]
 pub struct Args {
     #[clap(subcommand)]
@@ -25,13 +36,32 @@ pub struct Args {
 
     #[clap(long)]
     version: u64,
+
+    /// Number of consecutive transactions to profile, starting at the version. Non-user
+    /// transactions in the range are skipped.
+    #[clap(long, default_value_t = 1)]
+    limit: u64,
+
+    /// Formats of the generated reports.
+    #[clap(long, value_enum, value_delimiter = ',', default_value = "html")]
+    format: Vec<ReportFormat>,
+
+    /// Merge the profiles of all transactions in the range into a single profile for hot-spot
+    /// analysis. Only supported for the chrome-trace and speedscope formats.
+    #[clap(long)]
+    aggregate: bool,
 }
 
 #[tokio::main]
 async fn main() -> Result<()> {
     // Parse the commandline args
     let args = Args::parse();
-    let version = args.version;
+    if args.limit == 0 {
+        bail!("at least one transaction must be profiled");
+    }
+    if args.aggregate && args.format.contains(&ReportFormat::Html) {
+        bail!("HTML reports cannot be aggregated, use --format chrome-trace or speedscope");
+    }
 
     // Initialize the debugger
     creditchain_logger::Logger::new().init();
@@ -44,32 +74,80 @@ async fn main() -> Result<()> {
         Target::DB { path } => CreditChainDebugger::db(path)?,
     };
 
-    // Execute the transaction w/ the gas profiler
-    let (txn, _txn_info) = debugger
-        .get_committed_transaction_at_version(version)
+    // Execute the transactions w/ the gas profiler
+    let (txns, _txn_infos) = debugger
+        .get_committed_transactions(args.version, args.limit)
         .await?;
 
-    let txn = match txn {
-        Transaction::UserTransaction(txn) => txn,
-        _ => bail!("not a user transaction"),
-    };
+    let mut gas_logs = vec![];
+    for (version, txn) in (args.version..).zip(txns) {
+        let txn = match txn {
+            Transaction::UserTransaction(txn) => txn,
+            _ if args.limit > 1 => continue,
+            _ => bail!("not a user transaction"),
+        };
 
-    let (_status, output, gas_log) =
-        debugger.execute_transaction_at_version_with_gas_profiler(version, txn)?;
+        let (_status, output, gas_log) =
+            debugger.execute_transaction_at_version_with_gas_profiler(version, txn)?;
 
-    let txn_output =
-        output.try_materialize_into_transaction_output(&debugger.state_view_at_version(version))?;
+        let txn_output = output
+            .try_materialize_into_transaction_output(&debugger.state_view_at_version(version))?;
 
-    // Show results to the user
-    println!("{:#?}", txn_output);
+        // Show results to the user
+        if args.limit == 1 {
+            println!("{:#?}", txn_output);
+        } else {
+            println!("Transaction {}: {:?}", version, txn_output.status());
+        }
 
-    let report_path = Path::new("gas-profiling").join(format!("txn-{}", version));
-    gas_log.generate_html_report(
-        &report_path,
-        format!("Gas Report - Transaction {}", version),
-    )?;
+        if !args.aggregate {
+            let report_path = Path::new("gas-profiling").join(format!("txn-{}", version));
+            for format in &args.format {
+                match format {
+                    ReportFormat::Html => gas_log.generate_html_report(
+                        &report_path,
+                        format!("Gas Report - Transaction {}", version),
+                    )?,
+                    ReportFormat::ChromeTrace | ReportFormat::Speedscope => {
+                        write_profile(
+                            &GasProfile::new(format!("Transaction {}", version), &gas_log),
+                            &report_path,
+                            *format,
+                        )?;
+                    },
+                }
+            }
+            println!("Gas profiling report saved to {}.", report_path.display());
+        }
+        gas_logs.push(gas_log);
+    }
 
-    println!("Gas profiling report saved to {}.", report_path.display());
+    if args.aggregate {
+        let end_version = args.version + args.limit - 1;
+        let profile = GasProfile::aggregate(
+            format!("Transactions {}-{}", args.version, end_version),
+            &gas_logs,
+        )?;
+        let report_path =
+            Path::new("gas-profiling").join(format!("txns-{}-{}", args.version, end_version));
+        for format in &args.format {
+            write_profile(&profile, &report_path, *format)?;
+        }
+        println!(
+            "Aggregated gas profile of {} transactions saved to {}.",
+            gas_logs.len(),
+            report_path.display()
+        );
+    }
 
     Ok(())
 }
+
+fn write_profile(profile: &GasProfile, report_path: &Path, format: ReportFormat) -> Result<()> {
+    let format = match format {
+        ReportFormat::Html => unreachable!("HTML reports are generated from gas logs"),
+        ReportFormat::ChromeTrace => ProfileFormat::ChromeTrace,
+        ReportFormat::Speedscope => ProfileFormat::Speedscope,
+    };
+    profile.write(report_path.join(format.default_file_name()), format)
+}
@@ -1,10 +1,13 @@
 // Copyright © A-p-t-o-s Foundation
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::{execute_past_transactions, execute_pending_block};
-use anyhow::Result;
+use crate::{dry_run, execute_past_transactions, execute_pending_block};
+use anyhow::{ensure, Result};
+use creditchain_block_executor::conflict_diagnostics::{
+    take_recent_conflict_reports, MAX_RECENT_CONFLICT_REPORTS,
+};
 use clap::Parser;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 #[derive(Parser)]
 #[clap(group(clap::ArgGroup::new("target")
@@ -29,12 +32,55 @@ pub struct Opts {
 
     #[clap(long, num_args = 0.., default_values_t = [1])]
     pub(crate) concurrency_level: Vec<usize>,
+
+    /// If set, records BlockSTM conflict diagnostics (hot keys, dependency chains and
+    /// re-executions) and writes them as JSON to the given file. Reports are only produced
+    /// for parallel (BlockSTMv1) execution, i.e., concurrency levels larger than 1, and only
+    /// the reports of the most recent blocks are kept.
+    #[clap(long)]
+    pub(crate) conflict_report: Option<PathBuf>,
+}
+
+impl Opts {
+    /// Returns true iff conflict diagnostics should be recorded. Fails if a conflict report
+    /// is requested, but none of the executions would produce it.
+    pub(crate) fn enable_conflict_diagnostics(&self) -> Result<bool> {
+        if self.conflict_report.is_none() {
+            return Ok(false);
+        }
+        ensure!(
+            self.concurrency_level.iter().any(|level| *level > 1),
+            "Conflict reports are only produced for parallel execution! Use --concurrency-level larger than 1."
+        );
+        Ok(true)
+    }
+}
+
+/// Writes the conflict reports recorded during execution to the given file, if any.
+pub(crate) fn write_conflict_reports(path: Option<&Path>) -> Result<()> {
+    if let Some(path) = path {
+        let recent_reports = take_recent_conflict_reports();
+        std::fs::write(path, serde_json::to_string_pretty(&recent_reports)?)?;
+        println!(
+            "Wrote {} conflict report(s) to {}",
+            recent_reports.reports.len(),
+            path.display()
+        );
+        if recent_reports.num_dropped_reports > 0 {
+            println!(
+                "Warning: {} older conflict report(s) were dropped! Only the reports of the most recent {} blocks are kept.",
+                recent_reports.num_dropped_reports, MAX_RECENT_CONFLICT_REPORTS
+            );
+        }
+    }
+    Ok(())
 }
 
 #[derive(Parser)]
 pub enum Command {
     ExecutePastTransactions(execute_past_transactions::Command),
     ExecutePendingBlock(execute_pending_block::Command),
+    DryRun(dry_run::Command),
 }
 
 impl Command {
@@ -42,6 +88,7 @@ impl Command {
         match self {
             Command::ExecutePastTransactions(cmd) => cmd.run().await,
             Command::ExecutePendingBlock(cmd) => cmd.run().await,
+            Command::DryRun(cmd) => cmd.run().await,
         }
     }
 }
@@ -1,6 +1,7 @@
 // Copyright © A-p-t-o-s Foundation
 // SPDX-License-Identifier: Apache-2.0
 
+use crate::state_override::{StateOverride, StateOverrideView};
 use anyhow::{bail, format_err};
 use creditchain_block_executor::txn_provider::{default::DefaultTxnProvider, TxnProvider};
 use creditchain_gas_profiling::{GasProfiler, TransactionGasLog};
@@ -12,7 +13,7 @@ use creditchain_types::{
         transaction_slice_metadata::TransactionSliceMetadata,
     },
     contract_event::ContractEvent,
-    state_store::TStateView,
+    state_store::{StateView, TStateView},
     transaction::{
         signature_verified_transaction::SignatureVerifiedTransaction, BlockOutput,
         SignedTransaction, Transaction, TransactionExecutableRef, TransactionInfo,
@@ -34,11 +35,22 @@ use std::{path::Path, sync::Arc, time::Instant};
 
 pub struct CreditChainDebugger {
     debugger: Arc<dyn CreditChainValidatorInterface + Send>,
+    enable_conflict_diagnostics: bool,
 }
 
 impl CreditChainDebugger {
     pub fn new(debugger: Arc<dyn CreditChainValidatorInterface + Send>) -> Self {
-        Self { debugger }
+        Self {
+            debugger,
+            enable_conflict_diagnostics: false,
+        }
+    }
+
+    /// If enabled, parallel block executions record conflict reports, which can be retrieved via
+    /// [creditchain_block_executor::conflict_diagnostics::take_recent_conflict_reports].
+    pub fn with_conflict_diagnostics(mut self, enable: bool) -> Self {
+        self.enable_conflict_diagnostics = enable;
+        self
     }
 
     pub fn rest_client(rest_client: Client) -> anyhow::Result<Self> {
@@ -82,9 +94,13 @@ impl CreditChainDebugger {
         for concurrency_level in concurrency_levels {
             for i in 0..repeat_execution_times {
                 let start_time = Instant::now();
-                let cur_result =
-                    execute_block_no_limit(&txn_provider, &state_view, *concurrency_level)
-                        .map_err(|err| format_err!("Unexpected VM Error: {:?}", err))?;
+                let cur_result = execute_block_no_limit(
+                    &txn_provider,
+                    &state_view,
+                    *concurrency_level,
+                    self.enable_conflict_diagnostics,
+                )
+                .map_err(|err| format_err!("Unexpected VM Error: {:?}", err))?;
 
                 println!(
                     "[{} txns from {}] Finished execution round {}/{} with concurrency_level={} in {}ms",
@@ -116,6 +132,43 @@ impl CreditChainDebugger {
         Ok(result)
     }
 
+    /// Executes the transactions on top of the state at the given version, with the provided
+    /// state overrides applied. Returns the outputs of the original execution (without any
+    /// overrides) and the outputs of the execution with overrides.
+    pub fn execute_transactions_at_version_with_overrides(
+        &self,
+        version: Version,
+        txns: Vec<Transaction>,
+        state_override: &StateOverride,
+        concurrency_level: usize,
+    ) -> anyhow::Result<(Vec<TransactionOutput>, Vec<TransactionOutput>)> {
+        let sig_verified_txns: Vec<SignatureVerifiedTransaction> =
+            txns.into_iter().map(|x| x.into()).collect::<Vec<_>>();
+        let txn_provider = DefaultTxnProvider::new_without_info(sig_verified_txns);
+        let state_view = DebuggerStateView::new(self.debugger.clone(), version);
+
+        print_transaction_stats(txn_provider.get_txns(), version);
+
+        let original_outputs = execute_block_no_limit(
+            &txn_provider,
+            &state_view,
+            concurrency_level,
+            self.enable_conflict_diagnostics,
+        )
+        .map_err(|err| format_err!("Unexpected VM Error: {:?}", err))?;
+
+        let state_view_with_override = StateOverrideView::new(&state_view, state_override);
+        let overridden_outputs = execute_block_no_limit(
+            &txn_provider,
+            &state_view_with_override,
+            concurrency_level,
+            self.enable_conflict_diagnostics,
+        )
+        .map_err(|err| format_err!("Unexpected VM Error: {:?}", err))?;
+
+        Ok((original_outputs, overridden_outputs))
+    }
+
     pub fn execute_transaction_at_version_with_gas_profiler(
         &self,
         version: Version,
@@ -443,16 +496,19 @@ fn is_reconfiguration(vm_output: &TransactionOutput) -> bool {
 
 fn execute_block_no_limit(
     txn_provider: &DefaultTxnProvider<SignatureVerifiedTransaction>,
-    state_view: &DebuggerStateView,
+    state_view: &(impl StateView + Sync),
     concurrency_level: usize,
+    enable_conflict_diagnostics: bool,
 ) -> Result<Vec<TransactionOutput>, VMStatus> {
     let executor = CreditChainVMBlockExecutor::new();
+    let mut local = BlockExecutorLocalConfig::default_with_concurrency_level(concurrency_level);
+    local.enable_conflict_diagnostics = enable_conflict_diagnostics;
     executor
         .execute_block_with_config(
             txn_provider,
             state_view,
             BlockExecutorConfig {
-                local: BlockExecutorLocalConfig::default_with_concurrency_level(concurrency_level),
+                local,
                 onchain: BlockExecutorConfigFromOnchain::new_no_block_limit(),
             },
             TransactionSliceMetadata::unknown(),
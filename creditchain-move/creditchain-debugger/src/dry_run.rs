@@ -0,0 +1,90 @@
+// Copyright © A-p-t-o-s Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    common::Target, creditchain_debugger::CreditChainDebugger, output_diff::TransactionOutputDiff,
+    state_override::StateOverrideConfig,
+};
+use anyhow::{ensure, Result};
+use creditchain_rest_client::Client;
+use clap::Parser;
+use std::path::PathBuf;
+use url::Url;
+
+/// Re-executes past transactions on top of a modified state (e.g., with different resources,
+/// modules, feature flags or gas schedule), and prints how the outputs differ from the original
+/// execution.
+#[derive(Parser)]
+pub struct Command {
+    #[clap(flatten)]
+    target: Target,
+
+    /// Version of the first transaction to execute. Transactions are executed on top of the
+    /// state right before this version.
+    #[clap(long)]
+    begin_version: u64,
+
+    #[clap(long, default_value_t = 1)]
+    limit: u64,
+
+    /// Path to the YAML file with state overrides.
+    #[clap(long)]
+    overrides: PathBuf,
+
+    #[clap(long, default_value_t = 1)]
+    concurrency_level: usize,
+
+    /// If set, prints the differences as JSON.
+    #[clap(long)]
+    json: bool,
+}
+
+impl Command {
+    pub async fn run(self) -> Result<()> {
+        let debugger = if let Some(rest_endpoint) = self.target.rest_endpoint {
+            CreditChainDebugger::rest_client(Client::new(Url::parse(&rest_endpoint)?))?
+        } else if let Some(db_path) = self.target.db_path {
+            CreditChainDebugger::db(db_path)?
+        } else {
+            unreachable!("Must provide one target.");
+        };
+
+        let (txns, _) = debugger
+            .get_committed_transactions(self.begin_version, self.limit)
+            .await?;
+        ensure!(
+            !txns.is_empty(),
+            "No transactions found at version {}",
+            self.begin_version
+        );
+
+        let override_config = StateOverrideConfig::load(&self.overrides)?;
+        let state_override =
+            override_config.resolve(&debugger.state_view_at_version(self.begin_version))?;
+        println!("Overriding {} state value(s)", state_override.len());
+
+        let (original_outputs, overridden_outputs) = debugger
+            .execute_transactions_at_version_with_overrides(
+                self.begin_version,
+                txns,
+                &state_override,
+                self.concurrency_level,
+            )?;
+
+        let diffs: Vec<_> = original_outputs
+            .iter()
+            .zip(overridden_outputs.iter())
+            .enumerate()
+            .map(|(idx, (original, overridden))| {
+                TransactionOutputDiff::new(self.begin_version + idx as u64, original, overridden)
+            })
+            .collect();
+
+        if self.json {
+            println!("{}", serde_json::to_string_pretty(&diffs)?);
+        } else {
+            diffs.iter().for_each(TransactionOutputDiff::println);
+        }
+        Ok(())
+    }
+}
@@ -1,7 +1,10 @@
 // Copyright © A-p-t-o-s Foundation
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::{creditchain_debugger::CreditChainDebugger, common::Opts};
+use crate::{
+    common::{write_conflict_reports, Opts},
+    creditchain_debugger::CreditChainDebugger,
+};
 use anyhow::Result;
 use creditchain_rest_client::Client;
 use clap::Parser;
@@ -37,6 +40,7 @@ impl Command {
         } else {
             unreachable!("Must provide one target.");
         };
+        let debugger = debugger.with_conflict_diagnostics(self.opts.enable_conflict_diagnostics()?);
 
         let result = debugger
             .execute_past_transactions(
@@ -52,6 +56,6 @@ impl Command {
             println!("{result:#?}",);
         }
 
-        Ok(())
+        write_conflict_reports(self.opts.conflict_report.as_deref())
     }
 }
@@ -1,7 +1,10 @@
 // Copyright © A-p-t-o-s Foundation
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::{creditchain_debugger::CreditChainDebugger, common::Opts};
+use crate::{
+    common::{write_conflict_reports, Opts},
+    creditchain_debugger::CreditChainDebugger,
+};
 use anyhow::Result;
 use creditchain_crypto::HashValue;
 use creditchain_logger::info;
@@ -48,6 +51,7 @@ impl Command {
         } else {
             unreachable!("Must provide one target.");
         };
+        let debugger = debugger.with_conflict_diagnostics(self.opts.enable_conflict_diagnostics()?);
 
         let user_txns = if let Some(block_rest_endpoint) = self.block_rest_endpoint {
             info!(
@@ -92,6 +96,6 @@ impl Command {
         )?;
         println!("{txn_outputs:#?}");
 
-        Ok(())
+        write_conflict_reports(self.opts.conflict_report.as_deref())
     }
 }
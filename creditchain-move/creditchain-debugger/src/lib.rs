@@ -4,5 +4,8 @@
 pub mod creditchain_debugger;
 pub mod bcs_txn_decoder;
 pub mod common;
+pub mod dry_run;
 pub mod execute_past_transactions;
 pub mod execute_pending_block;
+pub mod output_diff;
+pub mod state_override;
@@ -0,0 +1,213 @@
+// Copyright © A-p-t-o-s Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use creditchain_types::transaction::{TransactionOutput, Version};
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+/// A value that differs between the original and the overridden execution.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+pub struct Change<T> {
+    pub original: T,
+    pub overridden: T,
+}
+
+impl<T: PartialEq> Change<T> {
+    fn new_if_different(original: T, overridden: T) -> Option<Self> {
+        (original != overridden).then_some(Self {
+            original,
+            overridden,
+        })
+    }
+}
+
+/// Difference of a single write. A [None] side means the key was not written.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+pub struct WriteDiff {
+    pub state_key: String,
+    pub original: Option<String>,
+    pub overridden: Option<String>,
+}
+
+/// Difference of an event at the given position in the emitted events.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+pub struct EventDiff {
+    pub index: usize,
+    pub original: Option<String>,
+    pub overridden: Option<String>,
+}
+
+/// Structured difference between the outputs of a transaction executed with and without state
+/// overrides.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+pub struct TransactionOutputDiff {
+    pub version: Version,
+    pub status: Option<Change<String>>,
+    pub gas_used: Option<Change<u64>>,
+    pub write_set: Vec<WriteDiff>,
+    pub events: Vec<EventDiff>,
+}
+
+impl TransactionOutputDiff {
+    pub fn new(
+        version: Version,
+        original: &TransactionOutput,
+        overridden: &TransactionOutput,
+    ) -> Self {
+        let status = Change::new_if_different(
+            format!("{:?}", original.status()),
+            format!("{:?}", overridden.status()),
+        );
+        let gas_used = Change::new_if_different(original.gas_used(), overridden.gas_used());
+
+        let mut writes: BTreeMap<_, (Option<String>, Option<String>)> = BTreeMap::new();
+        for (state_key, write_op) in original.write_set().write_op_iter() {
+            writes.entry(state_key).or_default().0 = Some(format!("{:?}", write_op));
+        }
+        for (state_key, write_op) in overridden.write_set().write_op_iter() {
+            writes.entry(state_key).or_default().1 = Some(format!("{:?}", write_op));
+        }
+        let write_set = writes
+            .into_iter()
+            .filter(|(_, (original, overridden))| original != overridden)
+            .map(|(state_key, (original, overridden))| WriteDiff {
+                state_key: format!("{:?}", state_key),
+                original,
+                overridden,
+            })
+            .collect();
+
+        let num_events = original.events().len().max(overridden.events().len());
+        let events = (0..num_events)
+            .filter_map(|index| {
+                let original = original.events().get(index);
+                let overridden = overridden.events().get(index);
+                (original != overridden).then(|| EventDiff {
+                    index,
+                    original: original.map(|event| format!("{:?}", event)),
+                    overridden: overridden.map(|event| format!("{:?}", event)),
+                })
+            })
+            .collect();
+
+        Self {
+            version,
+            status,
+            gas_used,
+            write_set,
+            events,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.status.is_none()
+            && self.gas_used.is_none()
+            && self.write_set.is_empty()
+            && self.events.is_empty()
+    }
+
+    pub fn println(&self) {
+        if self.is_empty() {
+            println!("[version {}] no differences", self.version);
+            return;
+        }
+
+        println!("[version {}]", self.version);
+        if let Some(status) = &self.status {
+            println!("  status: {} -> {}", status.original, status.overridden);
+        }
+        if let Some(gas_used) = &self.gas_used {
+            println!("  gas_used: {} -> {}", gas_used.original, gas_used.overridden);
+        }
+        for write in &self.write_set {
+            println!("  write {}", write.state_key);
+            println!("    - {}", write.original.as_deref().unwrap_or("<none>"));
+            println!("    + {}", write.overridden.as_deref().unwrap_or("<none>"));
+        }
+        for event in &self.events {
+            println!("  event #{}", event.index);
+            println!("    - {}", event.original.as_deref().unwrap_or("<none>"));
+            println!("    + {}", event.overridden.as_deref().unwrap_or("<none>"));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use creditchain_types::{
+        contract_event::ContractEvent,
+        state_store::state_key::StateKey,
+        transaction::{ExecutionStatus, TransactionAuxiliaryData, TransactionStatus},
+        write_set::{WriteOp, WriteSetMut},
+    };
+
+    fn output(
+        writes: Vec<(StateKey, WriteOp)>,
+        events: Vec<ContractEvent>,
+        gas_used: u64,
+    ) -> TransactionOutput {
+        TransactionOutput::new(
+            WriteSetMut::new(writes).freeze().unwrap(),
+            events,
+            gas_used,
+            TransactionStatus::Keep(ExecutionStatus::Success),
+            TransactionAuxiliaryData::None,
+        )
+    }
+
+    #[test]
+    fn test_output_diff() {
+        let unchanged_key = StateKey::raw(b"unchanged");
+        let changed_key = StateKey::raw(b"changed");
+        let removed_key = StateKey::raw(b"removed");
+        let write = |byte: u8| WriteOp::legacy_modification(vec![byte].into());
+
+        let original = output(
+            vec![
+                (unchanged_key.clone(), write(0)),
+                (changed_key.clone(), write(1)),
+                (removed_key.clone(), write(2)),
+            ],
+            vec![ContractEvent::new_v2_with_type_tag_str(
+                "0x1::event::EventA",
+                vec![0],
+            )],
+            10,
+        );
+        let overridden = output(
+            vec![(unchanged_key, write(0)), (changed_key.clone(), write(3))],
+            vec![ContractEvent::new_v2_with_type_tag_str(
+                "0x1::event::EventA",
+                vec![0],
+            )],
+            12,
+        );
+
+        let diff = TransactionOutputDiff::new(5, &original, &overridden);
+        assert!(!diff.is_empty());
+        assert!(diff.status.is_none());
+        assert!(diff.events.is_empty());
+        assert_eq!(
+            diff.gas_used,
+            Some(Change {
+                original: 10,
+                overridden: 12
+            })
+        );
+        assert_eq!(diff.write_set.len(), 2);
+        assert!(diff.write_set.contains(&WriteDiff {
+            state_key: format!("{:?}", changed_key),
+            original: Some(format!("{:?}", write(1))),
+            overridden: Some(format!("{:?}", write(3))),
+        }));
+        assert!(diff.write_set.contains(&WriteDiff {
+            state_key: format!("{:?}", removed_key),
+            original: Some(format!("{:?}", write(2))),
+            overridden: None,
+        }));
+
+        let empty_diff = TransactionOutputDiff::new(5, &original, &original);
+        assert!(empty_diff.is_empty());
+    }
+}
@@ -0,0 +1,486 @@
+// Copyright © A-p-t-o-s Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! State overrides for dry-running transactions on top of a modified state. Overrides are
+//! specified in a YAML file, for example:
+//!
+//! ```yaml
+//! resources:
+//!   - address: "0xcafe"
+//!     struct_tag: "0x1::coin::CoinStore<0x1::creditchain_coin::CreditChainCoin>"
+//!     # BCS-serialized resource, hex-encoded. Omit the value to delete the resource.
+//!     # Members of resource groups (e.g., 0x1::object::ObjectCore) are patched inside their
+//!     # group, other members of the group are kept.
+//!     value: "0x..."
+//! table_items:
+//!   - handle: "0x1b854694ae746cdbd8d44186ca4929b2b337df21d1c74633be19b2710552fdca"
+//!     key: "0x..."
+//!     value: "0x..."
+//! # Compiled modules (.mv files), published at their self addresses.
+//! modules:
+//!   - "build/Package/bytecode_modules/module.mv"
+//! enable_features: ["PERMISSIONED_SIGNER"]
+//! disable_features: []
+//! gas_feature_version: 30
+//! gas_schedule:
+//!   txn.max_execution_gas: 920000000
+//! ```
+
+use anyhow::{anyhow, bail, ensure, Context};
+use creditchain_types::{
+    on_chain_config::{FeatureFlag, Features, GasScheduleV2, OnChainConfig},
+    state_store::{
+        state_key::StateKey, state_storage_usage::StateStorageUsage, state_value::StateValue,
+        table::TableHandle, StateView, StateViewResult, TStateView,
+    },
+};
+use creditchain_vm::data_cache::get_resource_group_member_from_metadata;
+use move_binary_format::{access::ModuleAccess, CompiledModule};
+use move_core_types::{account_address::AccountAddress, language_storage::StructTag};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{BTreeMap, HashMap, HashSet},
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+/// Overrides a single resource. If the value is not set, the resource is deleted.
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct ResourceOverride {
+    pub address: AccountAddress,
+    pub struct_tag: String,
+    pub value: Option<String>,
+}
+
+/// Overrides a single table item. If the value is not set, the item is deleted.
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct TableItemOverride {
+    pub handle: AccountAddress,
+    pub key: String,
+    pub value: Option<String>,
+}
+
+/// All state overrides, as specified by the user.
+#[derive(Debug, Default, Deserialize, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct StateOverrideConfig {
+    pub resources: Vec<ResourceOverride>,
+    pub table_items: Vec<TableItemOverride>,
+    /// Paths to compiled modules.
+    pub modules: Vec<PathBuf>,
+    pub enable_features: Vec<String>,
+    pub disable_features: Vec<String>,
+    pub gas_feature_version: Option<u64>,
+    /// Gas schedule entries to override. All entries must exist in the on-chain schedule.
+    pub gas_schedule: BTreeMap<String, u64>,
+}
+
+impl StateOverrideConfig {
+    pub fn load(path: &Path) -> anyhow::Result<Self> {
+        let contents = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read state overrides from {}", path.display()))?;
+        serde_yaml::from_str(&contents)
+            .with_context(|| format!("Failed to parse state overrides from {}", path.display()))
+    }
+
+    /// Resolves the overrides against the given state: on-chain configs are read from the state
+    /// and modified, existing state values keep their metadata.
+    pub fn resolve(&self, state_view: &impl StateView) -> anyhow::Result<StateOverride> {
+        let mut state_override = StateOverride::default();
+
+        for table_item in &self.table_items {
+            let key = decode_hex(&table_item.key)?;
+            let state_key = StateKey::table_item(&TableHandle(table_item.handle), &key);
+            let bytes = table_item.value.as_deref().map(decode_hex).transpose()?;
+            state_override.insert(state_view, state_key, bytes)?;
+        }
+
+        for path in &self.modules {
+            let bytes = std::fs::read(path)
+                .with_context(|| format!("Failed to read module from {}", path.display()))?;
+            let module = CompiledModule::deserialize(&bytes)
+                .map_err(|err| anyhow!("Invalid module at {}: {:?}", path.display(), err))?;
+            let state_key = StateKey::module(module.self_addr(), module.self_name());
+            state_override.insert(state_view, state_key, Some(bytes))?;
+        }
+
+        // Modules are overridden first, so that resource group membership is determined by the
+        // overridden modules.
+        let mut group_members = HashSet::new();
+        for resource in &self.resources {
+            let struct_tag = StructTag::from_str(&resource.struct_tag)
+                .map_err(|err| anyhow!("Invalid struct tag {}: {:?}", resource.struct_tag, err))?;
+            let bytes = resource.value.as_deref().map(decode_hex).transpose()?;
+            match state_override.get_resource_group_tag(state_view, &struct_tag)? {
+                Some(group_tag) => {
+                    ensure!(
+                        group_members.insert((resource.address, struct_tag.clone())),
+                        "Resource {} at {} is overridden more than once",
+                        struct_tag,
+                        resource.address
+                    );
+                    let group_key = StateKey::resource_group(&resource.address, &group_tag);
+                    state_override.insert_group_member(state_view, group_key, struct_tag, bytes)?;
+                },
+                None => {
+                    let state_key = StateKey::resource(&resource.address, &struct_tag)?;
+                    state_override.insert(state_view, state_key, bytes)?;
+                },
+            }
+        }
+
+        if !self.enable_features.is_empty() || !self.disable_features.is_empty() {
+            let parse_features = |names: &[String]| {
+                names
+                    .iter()
+                    .map(|name| {
+                        FeatureFlag::from_str(name)
+                            .map_err(|_| anyhow!("Unknown feature flag: {}", name))
+                    })
+                    .collect::<anyhow::Result<Vec<_>>>()
+            };
+            let enable_features = parse_features(&self.enable_features)?;
+            let disable_features = parse_features(&self.disable_features)?;
+            ensure!(
+                enable_features
+                    .iter()
+                    .all(|f| !disable_features.contains(f)),
+                "Enabled and disabled feature flags cannot overlap"
+            );
+
+            state_override.override_config::<Features, _>(state_view, |features| {
+                enable_features.iter().for_each(|f| features.enable(*f));
+                disable_features.iter().for_each(|f| features.disable(*f));
+                Ok(())
+            })?;
+        }
+
+        if self.gas_feature_version.is_some() || !self.gas_schedule.is_empty() {
+            state_override.override_config::<GasScheduleV2, _>(state_view, |gas_schedule| {
+                if let Some(gas_feature_version) = self.gas_feature_version {
+                    gas_schedule.feature_version = gas_feature_version;
+                }
+                for (name, value) in &self.gas_schedule {
+                    match gas_schedule
+                        .entries
+                        .iter_mut()
+                        .find(|(entry, _)| entry == name)
+                    {
+                        Some((_, entry_value)) => *entry_value = *value,
+                        None => bail!("Gas schedule entry {} does not exist", name),
+                    }
+                }
+                Ok(())
+            })?;
+        }
+
+        Ok(state_override)
+    }
+}
+
+fn decode_hex(s: &str) -> anyhow::Result<Vec<u8>> {
+    hex::decode(s.strip_prefix("0x").unwrap_or(s))
+        .map_err(|err| anyhow!("Invalid hex string {}: {:?}", s, err))
+}
+
+/// State values that shadow the values in the underlying state. A [None] value means that the
+/// state value is deleted.
+#[derive(Clone, Debug, Default)]
+pub struct StateOverride {
+    values: HashMap<StateKey, Option<StateValue>>,
+}
+
+impl StateOverride {
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    fn insert(
+        &mut self,
+        state_view: &impl StateView,
+        state_key: StateKey,
+        bytes: Option<Vec<u8>>,
+    ) -> anyhow::Result<()> {
+        // Keep the metadata of existing values, so that storage fees are computed as if the
+        // value had been modified on-chain.
+        let state_value = match bytes {
+            Some(bytes) => Some(match state_view.get_state_value(&state_key)? {
+                Some(state_value) => state_value.map_bytes(|_| Ok(bytes.into()))?,
+                None => StateValue::new_legacy(bytes.into()),
+            }),
+            None => None,
+        };
+        if self.values.insert(state_key.clone(), state_value).is_some() {
+            bail!("State key {:?} is overridden more than once", state_key);
+        }
+        Ok(())
+    }
+
+    /// Returns the state value, taking the values overridden so far into account.
+    fn get_state_value(
+        &self,
+        state_view: &impl StateView,
+        state_key: &StateKey,
+    ) -> anyhow::Result<Option<StateValue>> {
+        match self.values.get(state_key) {
+            Some(state_value) => Ok(state_value.clone()),
+            None => Ok(state_view.get_state_value(state_key)?),
+        }
+    }
+
+    /// Returns the tag of the resource group the struct is a member of, if any. Membership is
+    /// declared in the metadata of the module defining the struct.
+    fn get_resource_group_tag(
+        &self,
+        state_view: &impl StateView,
+        struct_tag: &StructTag,
+    ) -> anyhow::Result<Option<StructTag>> {
+        let state_key = StateKey::module(&struct_tag.address, &struct_tag.module);
+        let state_value = match self.get_state_value(state_view, &state_key)? {
+            Some(state_value) => state_value,
+            None => return Ok(None),
+        };
+        let module = CompiledModule::deserialize(state_value.bytes())
+            .map_err(|err| anyhow!("Invalid module {}: {:?}", struct_tag.module_id(), err))?;
+        Ok(get_resource_group_member_from_metadata(
+            struct_tag,
+            &module.metadata,
+        ))
+    }
+
+    /// Overrides a single member of a resource group, keeping all other members of the group.
+    /// The group is deleted if its last member is deleted.
+    fn insert_group_member(
+        &mut self,
+        state_view: &impl StateView,
+        group_key: StateKey,
+        member_tag: StructTag,
+        bytes: Option<Vec<u8>>,
+    ) -> anyhow::Result<()> {
+        let state_value = self.get_state_value(state_view, &group_key)?;
+        let mut group: BTreeMap<StructTag, Vec<u8>> = match &state_value {
+            Some(state_value) => bcs::from_bytes(state_value.bytes())
+                .with_context(|| format!("Invalid resource group {:?}", group_key))?,
+            None => BTreeMap::new(),
+        };
+        match bytes {
+            Some(bytes) => group.insert(member_tag, bytes),
+            None => group.remove(&member_tag),
+        };
+
+        let state_value = if group.is_empty() {
+            None
+        } else {
+            let bytes = bcs::to_bytes(&group)?;
+            Some(match state_value {
+                Some(state_value) => state_value.map_bytes(|_| Ok(bytes.into()))?,
+                None => StateValue::new_legacy(bytes.into()),
+            })
+        };
+        self.values.insert(group_key, state_value);
+        Ok(())
+    }
+
+    /// Fetches the on-chain config from the state, and modifies it using the passed function.
+    fn override_config<T, F>(&mut self, state_view: &impl StateView, f: F) -> anyhow::Result<()>
+    where
+        T: OnChainConfig + Serialize,
+        F: FnOnce(&mut T) -> anyhow::Result<()>,
+    {
+        let state_key = StateKey::resource(&T::address(), &T::struct_tag())?;
+        let state_value = state_view
+            .get_state_value(&state_key)?
+            .ok_or_else(|| anyhow!("On-chain config {} does not exist", T::struct_tag()))?;
+
+        let mut config = T::deserialize_into_config(state_value.bytes())?;
+        f(&mut config)?;
+        let bytes = bcs::to_bytes(&config)?;
+
+        let state_value = state_value.map_bytes(|_| Ok(bytes.into()))?;
+        if self.values.insert(state_key, Some(state_value)).is_some() {
+            bail!(
+                "On-chain config {} is overridden more than once",
+                T::struct_tag()
+            );
+        }
+        Ok(())
+    }
+}
+
+/// [StateView] that serves reads from the [StateOverride] first, and falls back to the base
+/// state view otherwise.
+pub struct StateOverrideView<'a, S> {
+    base_view: &'a S,
+    state_override: &'a StateOverride,
+}
+
+impl<'a, S: StateView> StateOverrideView<'a, S> {
+    pub fn new(base_view: &'a S, state_override: &'a StateOverride) -> Self {
+        Self {
+            base_view,
+            state_override,
+        }
+    }
+}
+
+impl<S: StateView> TStateView for StateOverrideView<'_, S> {
+    type Key = StateKey;
+
+    fn get_state_value(&self, state_key: &Self::Key) -> StateViewResult<Option<StateValue>> {
+        match self.state_override.values.get(state_key) {
+            Some(state_value) => Ok(state_value.clone()),
+            None => self.base_view.get_state_value(state_key),
+        }
+    }
+
+    fn get_usage(&self) -> StateViewResult<StateStorageUsage> {
+        self.base_view.get_usage()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use creditchain_types::state_store::MockStateView;
+
+    #[test]
+    fn test_state_override_view() {
+        let existing_key = StateKey::raw(b"existing");
+        let deleted_key = StateKey::raw(b"deleted");
+        let base_view = MockStateView::new(HashMap::from([
+            (existing_key.clone(), StateValue::new_legacy(vec![1].into())),
+            (deleted_key.clone(), StateValue::new_legacy(vec![2].into())),
+        ]));
+
+        let mut state_override = StateOverride::default();
+        state_override
+            .insert(&base_view, existing_key.clone(), Some(vec![3]))
+            .unwrap();
+        state_override
+            .insert(&base_view, deleted_key.clone(), None)
+            .unwrap();
+        assert!(state_override
+            .insert(&base_view, deleted_key.clone(), None)
+            .is_err());
+
+        let view = StateOverrideView::new(&base_view, &state_override);
+        assert_eq!(
+            view.get_state_value_bytes(&existing_key).unwrap().unwrap(),
+            vec![3]
+        );
+        assert!(view.get_state_value(&deleted_key).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_override_resource_group_member() {
+        let address = AccountAddress::random();
+        let group_tag = StructTag::from_str("0x1::object::ObjectGroup").unwrap();
+        let kept_tag = StructTag::from_str("0x1::object::ObjectCore").unwrap();
+        let member_tag = StructTag::from_str("0x1::fungible_asset::FungibleStore").unwrap();
+        let group_key = StateKey::resource_group(&address, &group_tag);
+        let group = BTreeMap::from([(kept_tag.clone(), vec![1]), (member_tag.clone(), vec![2])]);
+        let base_view = MockStateView::new(HashMap::from([(
+            group_key.clone(),
+            StateValue::new_legacy(bcs::to_bytes(&group).unwrap().into()),
+        )]));
+        let get_group = |state_override: &StateOverride| {
+            let view = StateOverrideView::new(&base_view, state_override);
+            view.get_state_value_bytes(&group_key)
+                .unwrap()
+                .map(|bytes| bcs::from_bytes::<BTreeMap<StructTag, Vec<u8>>>(&bytes).unwrap())
+        };
+
+        // Other members of the group are kept
+        let mut state_override = StateOverride::default();
+        state_override
+            .insert_group_member(
+                &base_view,
+                group_key.clone(),
+                member_tag.clone(),
+                Some(vec![3]),
+            )
+            .unwrap();
+        assert_eq!(
+            get_group(&state_override).unwrap(),
+            BTreeMap::from([(kept_tag.clone(), vec![1]), (member_tag.clone(), vec![3])])
+        );
+
+        // The group is deleted once all members are deleted
+        state_override
+            .insert_group_member(&base_view, group_key.clone(), member_tag, None)
+            .unwrap();
+        assert_eq!(
+            get_group(&state_override).unwrap(),
+            BTreeMap::from([(kept_tag.clone(), vec![1])])
+        );
+        state_override
+            .insert_group_member(&base_view, group_key.clone(), kept_tag, None)
+            .unwrap();
+        assert!(get_group(&state_override).is_none());
+    }
+
+    #[test]
+    fn test_resolve_duplicate_config_override() {
+        let features_key =
+            StateKey::resource(&Features::address(), &Features::struct_tag()).unwrap();
+        let features_bytes = bcs::to_bytes(&Features::default()).unwrap();
+        let base_view = MockStateView::new(HashMap::from([(
+            features_key,
+            StateValue::new_legacy(features_bytes.clone().into()),
+        )]));
+
+        // Overriding the features both as a resource and via feature flags is rejected
+        let config = StateOverrideConfig {
+            resources: vec![ResourceOverride {
+                address: *Features::address(),
+                struct_tag: Features::struct_tag().to_canonical_string(),
+                value: Some(hex::encode(features_bytes)),
+            }],
+            enable_features: vec!["PERMISSIONED_SIGNER".to_string()],
+            ..Default::default()
+        };
+        assert!(config.resolve(&base_view).is_err());
+
+        // Either override alone is accepted
+        let resources = config.resources;
+        let config = StateOverrideConfig {
+            resources,
+            ..Default::default()
+        };
+        assert_eq!(config.resolve(&base_view).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_parse_state_override_config() {
+        let config: StateOverrideConfig = serde_yaml::from_str(
+            r#"
+resources:
+  - address: "0xcafe"
+    struct_tag: "0x1::account::Account"
+table_items:
+  - handle: "0x1"
+    key: "0x0102"
+    value: "0x03"
+enable_features: ["PERMISSIONED_SIGNER"]
+gas_schedule:
+  txn.max_execution_gas: 100
+"#,
+        )
+        .unwrap();
+        assert_eq!(config.resources.len(), 1);
+        assert!(config.resources[0].value.is_none());
+        assert_eq!(decode_hex(&config.table_items[0].key).unwrap(), vec![1, 2]);
+        assert_eq!(
+            config.enable_features,
+            vec!["PERMISSIONED_SIGNER".to_string()]
+        );
+        assert_eq!(config.gas_schedule.get("txn.max_execution_gas"), Some(&100));
+        assert!(config.modules.is_empty());
+    }
+}
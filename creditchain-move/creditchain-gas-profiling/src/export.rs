@@ -0,0 +1,598 @@
+// Copyright © A-p-t-o-s Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! Exports gas profiles to machine-readable formats understood by external profiling tools:
+//!   - the Chrome Trace Event format, which can be loaded into `chrome://tracing` or Perfetto;
+//!   - the speedscope file format, which can be loaded into <https://www.speedscope.app>.
+//!
+//! Since gas is not time, the "timeline" of an exported profile is measured in gas: every
+//! frame starts at the cumulative amount of gas charged before it and lasts for as much gas
+//! as was charged within it. Execution & IO costs are measured in internal gas units, storage
+//! fees in Octas.
+
+use crate::{
+    log::{CallFrame, ExecutionAndIOCosts, ExecutionGasEvent, StorageFees},
+    render::Render,
+    TransactionGasLog,
+};
+use anyhow::{bail, Result};
+use creditchain_gas_algebra::GasScalingFactor;
+use serde_json::{json, Map, Value};
+use std::{
+    collections::{hash_map::Entry, HashMap},
+    fmt::{self, Display},
+    fs,
+    path::Path,
+    str::FromStr,
+};
+
+/// Machine-readable formats a [`GasProfile`] can be exported to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProfileFormat {
+    /// Chrome Trace Event format, viewable in `chrome://tracing` or Perfetto.
+    ChromeTrace,
+    /// Speedscope file format, viewable in speedscope.
+    Speedscope,
+}
+
+impl ProfileFormat {
+    /// Default file name for a profile exported in this format.
+    pub fn default_file_name(&self) -> &'static str {
+        match self {
+            Self::ChromeTrace => "trace.json",
+            Self::Speedscope => "speedscope.json",
+        }
+    }
+}
+
+impl Display for ProfileFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", match self {
+            Self::ChromeTrace => "chrome-trace",
+            Self::Speedscope => "speedscope",
+        })
+    }
+}
+
+impl FromStr for ProfileFormat {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Ok(match s {
+            "chrome-trace" => Self::ChromeTrace,
+            "speedscope" => Self::Speedscope,
+            _ => bail!(
+                "unknown profile format '{}', expected 'chrome-trace' or 'speedscope'",
+                s
+            ),
+        })
+    }
+}
+
+/// A node in the call hierarchy of a gas profile.
+///
+/// The cost of a node only accounts for what was charged directly in it, excluding the costs
+/// of its children.
+#[derive(Debug, Clone)]
+struct ProfileNode {
+    name: String,
+    category: &'static str,
+    cost: u64,
+    refund: u64,
+    children: Vec<ProfileNode>,
+}
+
+impl ProfileNode {
+    fn new(name: impl Into<String>, category: &'static str, cost: impl Into<u64>) -> Self {
+        Self {
+            name: name.into(),
+            category,
+            cost: cost.into(),
+            refund: 0,
+            children: vec![],
+        }
+    }
+
+    fn new_with_children(
+        name: impl Into<String>,
+        category: &'static str,
+        children: impl IntoIterator<Item = Self>,
+    ) -> Self {
+        Self {
+            name: name.into(),
+            category,
+            cost: 0,
+            refund: 0,
+            children: children.into_iter().collect(),
+        }
+    }
+
+    fn total(&self) -> u64 {
+        self.cost + self.children.iter().map(|child| child.total()).sum::<u64>()
+    }
+
+    /// Merges siblings with the same name and category, recursively, summing up their costs.
+    fn collapse(mut self) -> Self {
+        let mut indices = HashMap::new();
+        let mut children: Vec<Self> = vec![];
+
+        for child in self.children {
+            match indices.entry((child.name.clone(), child.category)) {
+                Entry::Occupied(entry) => {
+                    let existing = &mut children[*entry.get()];
+                    existing.cost += child.cost;
+                    existing.refund += child.refund;
+                    existing.children.extend(child.children);
+                },
+                Entry::Vacant(entry) => {
+                    entry.insert(children.len());
+                    children.push(child);
+                },
+            }
+        }
+
+        self.children = children.into_iter().map(Self::collapse).collect();
+        self
+    }
+}
+
+impl CallFrame {
+    fn to_profile_node(&self) -> ProfileNode {
+        use ExecutionGasEvent::*;
+
+        let children = self.events.iter().filter_map(|event| match event {
+            Loc(_) => None,
+            Bytecode { op, cost } => Some(ProfileNode::new(
+                format!("{:?}", op).to_ascii_lowercase(),
+                "instruction",
+                *cost,
+            )),
+            CreateTy { cost } => Some(ProfileNode::new("create_ty", "instruction", *cost)),
+            Call(frame) => Some(frame.to_profile_node()),
+            CallNative {
+                module_id,
+                fn_name,
+                ty_args,
+                cost,
+            } => Some(ProfileNode::new(
+                format!(
+                    "{}",
+                    Render(&(module_id, fn_name.as_ident_str(), ty_args.as_slice()))
+                ),
+                "native",
+                *cost,
+            )),
+            LoadResource { addr, ty, cost } => Some(ProfileNode::new(
+                format!("load<{}::{}>", Render(addr), ty.to_canonical_string()),
+                "storage_read",
+                *cost,
+            )),
+        });
+
+        ProfileNode::new_with_children(format!("{}", self.name), "function", children)
+    }
+}
+
+impl ExecutionAndIOCosts {
+    fn to_profile_node(&self) -> ProfileNode {
+        let mut nodes = vec![
+            ProfileNode::new("intrinsic", "intrinsic", self.intrinsic_cost),
+            ProfileNode::new("keyless", "keyless", self.keyless_cost),
+            ProfileNode::new_with_children(
+                "dependencies",
+                "dependency",
+                self.dependencies
+                    .iter()
+                    .map(|dep| ProfileNode::new(dep.render(), "dependency", dep.cost)),
+            ),
+            self.call_graph.to_profile_node(),
+        ];
+
+        let mut ledger_writes = vec![];
+        if let Some(cost) = self.transaction_transient {
+            ledger_writes.push(ProfileNode::new("transaction", "ledger_write", cost));
+        }
+        ledger_writes.push(ProfileNode::new_with_children(
+            "events",
+            "ledger_write",
+            self.events_transient.iter().map(|event| {
+                ProfileNode::new(format!("{}", Render(&event.ty)), "ledger_write", event.cost)
+            }),
+        ));
+        ledger_writes.push(ProfileNode::new_with_children(
+            "state_write_ops",
+            "ledger_write",
+            self.write_set_transient.iter().map(|write| {
+                ProfileNode::new(
+                    format!("{}<{}>", Render(&write.op_type), Render(&write.key)),
+                    "ledger_write",
+                    write.cost,
+                )
+            }),
+        ));
+        nodes.push(ProfileNode::new_with_children(
+            "ledger_writes",
+            "ledger_write",
+            ledger_writes,
+        ));
+
+        ProfileNode::new_with_children("execution & IO", "transaction", nodes)
+    }
+}
+
+impl StorageFees {
+    fn to_profile_node(&self) -> ProfileNode {
+        // TODO: Handle event discounts, same as the flamegraphs.
+        let nodes = vec![
+            ProfileNode::new("transaction", "storage_fee", self.txn_storage),
+            ProfileNode::new_with_children(
+                "write_set",
+                "storage_fee",
+                self.write_set_storage.iter().map(|write| ProfileNode {
+                    refund: write.refund.into(),
+                    ..ProfileNode::new(
+                        format!("{}<{}>", Render(&write.op_type), Render(&write.key)),
+                        "storage_fee",
+                        write.cost,
+                    )
+                }),
+            ),
+            ProfileNode::new_with_children(
+                "events",
+                "storage_fee",
+                self.events.iter().map(|event| {
+                    ProfileNode::new(event.ty.to_canonical_string(), "storage_fee", event.cost)
+                }),
+            ),
+        ];
+
+        ProfileNode::new_with_children("storage", "transaction", nodes)
+    }
+}
+
+/// A gas profile of one or more transactions that can be exported to machine-readable formats.
+///
+/// Unlike the HTML report, the profile preserves the full call hierarchy, including
+/// per-instruction gas, storage reads, native calls and ledger writes.
+#[derive(Debug, Clone)]
+pub struct GasProfile {
+    name: String,
+    gas_scaling_factor: GasScalingFactor,
+    num_transactions: usize,
+    exec_io: ProfileNode,
+    storage: ProfileNode,
+}
+
+impl GasProfile {
+    /// Creates a profile of a single transaction.
+    pub fn new(name: impl Into<String>, log: &TransactionGasLog) -> Self {
+        Self {
+            name: name.into(),
+            gas_scaling_factor: log.exec_io.gas_scaling_factor,
+            num_transactions: 1,
+            exec_io: log.exec_io.to_profile_node(),
+            storage: log.storage.to_profile_node(),
+        }
+    }
+
+    /// Merges the gas logs of many transactions, e.g., of all transactions in a block, into a
+    /// single profile for hot-spot analysis.
+    ///
+    /// Identical call paths are merged and their costs summed up, so the order in which
+    /// instructions are executed is not preserved. All logs must share the same gas scaling
+    /// factor.
+    pub fn aggregate<'a>(
+        name: impl Into<String>,
+        logs: impl IntoIterator<Item = &'a TransactionGasLog>,
+    ) -> Result<Self> {
+        let mut logs = logs.into_iter();
+        let mut profile = match logs.next() {
+            Some(log) => Self::new(name, log),
+            None => bail!("cannot aggregate an empty set of gas logs"),
+        };
+
+        for log in logs {
+            if log.exec_io.gas_scaling_factor != profile.gas_scaling_factor {
+                bail!(
+                    "cannot aggregate gas logs with different gas scaling factors: {} and {}",
+                    profile.gas_scaling_factor,
+                    log.exec_io.gas_scaling_factor
+                );
+            }
+            profile.num_transactions += 1;
+            profile
+                .exec_io
+                .children
+                .extend(log.exec_io.to_profile_node().children);
+            profile
+                .storage
+                .children
+                .extend(log.storage.to_profile_node().children);
+        }
+
+        profile.exec_io = profile.exec_io.collapse();
+        profile.storage = profile.storage.collapse();
+        Ok(profile)
+    }
+
+    /// Converts the profile into the Chrome Trace Event format.
+    ///
+    /// Execution & IO costs and storage fees are rendered as two separate threads, with
+    /// timestamps and durations measured in internal gas units and Octas respectively.
+    pub fn to_chrome_trace(&self) -> Value {
+        fn visit(events: &mut Vec<Value>, tid: u64, node: &ProfileNode, start: u64) {
+            let total = node.total();
+            if total == 0 {
+                return;
+            }
+
+            let mut args = Map::new();
+            args.insert("cost".to_string(), json!(node.cost));
+            args.insert("total".to_string(), json!(total));
+            if node.refund > 0 {
+                args.insert("refund".to_string(), json!(node.refund));
+            }
+            events.push(json!({
+                "name": node.name,
+                "cat": node.category,
+                "ph": "X",
+                "ts": start,
+                "dur": total,
+                "pid": 1,
+                "tid": tid,
+                "args": args,
+            }));
+
+            let mut offset = start + node.cost;
+            for child in &node.children {
+                visit(events, tid, child, offset);
+                offset += child.total();
+            }
+        }
+
+        let mut events = vec![json!({
+            "name": "process_name",
+            "ph": "M",
+            "pid": 1,
+            "args": { "name": self.name },
+        })];
+        for (tid, thread_name, node) in [
+            (1, "execution & IO (internal gas units)", &self.exec_io),
+            (2, "storage fees (Octas)", &self.storage),
+        ] {
+            events.push(json!({
+                "name": "thread_name",
+                "ph": "M",
+                "pid": 1,
+                "tid": tid,
+                "args": { "name": thread_name },
+            }));
+            visit(&mut events, tid, node, 0);
+        }
+
+        json!({
+            "traceEvents": events,
+            "displayTimeUnit": "ns",
+            "otherData": {
+                "gas_scaling_factor": u64::from(self.gas_scaling_factor),
+                "num_transactions": self.num_transactions,
+            },
+        })
+    }
+
+    /// Converts the profile into the speedscope file format.
+    ///
+    /// Execution & IO costs and storage fees are rendered as two separate evented profiles,
+    /// measured in internal gas units and Octas respectively.
+    pub fn to_speedscope(&self) -> Value {
+        struct Frames {
+            frames: Vec<Value>,
+            indices: HashMap<String, usize>,
+        }
+
+        impl Frames {
+            fn index_of(&mut self, name: &str) -> usize {
+                if let Some(idx) = self.indices.get(name) {
+                    return *idx;
+                }
+                let idx = self.frames.len();
+                self.frames.push(json!({ "name": name }));
+                self.indices.insert(name.to_string(), idx);
+                idx
+            }
+        }
+
+        fn visit(frames: &mut Frames, events: &mut Vec<Value>, node: &ProfileNode, start: u64) {
+            let total = node.total();
+            if total == 0 {
+                return;
+            }
+
+            let frame = frames.index_of(&node.name);
+            events.push(json!({ "type": "O", "frame": frame, "at": start }));
+            let mut offset = start + node.cost;
+            for child in &node.children {
+                visit(frames, events, child, offset);
+                offset += child.total();
+            }
+            events.push(json!({ "type": "C", "frame": frame, "at": start + total }));
+        }
+
+        let mut frames = Frames {
+            frames: vec![],
+            indices: HashMap::new(),
+        };
+        let profiles = [
+            ("execution & IO (internal gas units)", &self.exec_io),
+            ("storage fees (Octas)", &self.storage),
+        ]
+        .into_iter()
+        .map(|(name, node)| {
+            let mut events = vec![];
+            visit(&mut frames, &mut events, node, 0);
+            json!({
+                "type": "evented",
+                "name": name,
+                "unit": "none",
+                "startValue": 0,
+                "endValue": node.total(),
+                "events": events,
+            })
+        })
+        .collect::<Vec<_>>();
+
+        json!({
+            "$schema": "https://www.speedscope.app/file-format-schema.json",
+            "name": self.name,
+            "exporter": "creditchain-gas-profiling",
+            "activeProfileIndex": 0,
+            "shared": { "frames": frames.frames },
+            "profiles": profiles,
+        })
+    }
+
+    /// Writes the profile in the given format to a file, creating its parent directories if
+    /// needed.
+    pub fn write(&self, path: impl AsRef<Path>, format: ProfileFormat) -> Result<()> {
+        let path = path.as_ref();
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        let value = match format {
+            ProfileFormat::ChromeTrace => self.to_chrome_trace(),
+            ProfileFormat::Speedscope => self.to_speedscope(),
+        };
+        fs::write(path, serde_json::to_vec(&value)?)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use move_binary_format::file_format_common::Opcodes;
+    use move_core_types::{
+        account_address::AccountAddress, identifier::Identifier, language_storage::ModuleId,
+    };
+
+    fn gas_log(transfer_calls: usize) -> TransactionGasLog {
+        let module_id = ModuleId::new(AccountAddress::ONE, Identifier::new("coin").unwrap());
+        let mut transfer = CallFrame::new_function(
+            module_id.clone(),
+            Identifier::new("transfer").unwrap(),
+            vec![],
+        );
+        transfer.events = vec![
+            ExecutionGasEvent::Bytecode {
+                op: Opcodes::LD_U64,
+                cost: 10.into(),
+            },
+            ExecutionGasEvent::CallNative {
+                module_id: module_id.clone(),
+                fn_name: Identifier::new("emit").unwrap(),
+                ty_args: vec![],
+                cost: 30.into(),
+            },
+        ];
+        let mut entry =
+            CallFrame::new_function(module_id, Identifier::new("main").unwrap(), vec![]);
+        entry.events = (0..transfer_calls)
+            .map(|_| ExecutionGasEvent::Call(transfer.clone()))
+            .collect();
+
+        TransactionGasLog {
+            exec_io: ExecutionAndIOCosts {
+                gas_scaling_factor: 100.into(),
+                total: (5 + 40 * transfer_calls as u64).into(),
+                intrinsic_cost: 5.into(),
+                keyless_cost: 0.into(),
+                dependencies: vec![],
+                call_graph: entry,
+                transaction_transient: None,
+                events_transient: vec![],
+                write_set_transient: vec![],
+            },
+            storage: StorageFees {
+                total: 7.into(),
+                total_refund: 0.into(),
+                write_set_storage: vec![],
+                events: vec![],
+                event_discount: 0.into(),
+                txn_storage: 7.into(),
+            },
+        }
+    }
+
+    #[test]
+    fn chrome_trace_preserves_hierarchy() {
+        let trace = GasProfile::new("txn", &gas_log(2)).to_chrome_trace();
+        let events = trace["traceEvents"].as_array().unwrap();
+
+        let complete = events
+            .iter()
+            .filter(|event| event["ph"] == "X")
+            .map(|event| {
+                (
+                    event["name"].as_str().unwrap(),
+                    event["ts"].as_u64().unwrap(),
+                    event["dur"].as_u64().unwrap(),
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(complete, vec![
+            ("execution & IO", 0, 85),
+            ("intrinsic", 0, 5),
+            ("0x1::coin::main", 5, 80),
+            ("0x1::coin::transfer", 5, 40),
+            ("ld_u64", 5, 10),
+            ("0x1::coin::emit", 15, 30),
+            ("0x1::coin::transfer", 45, 40),
+            ("ld_u64", 45, 10),
+            ("0x1::coin::emit", 55, 30),
+            ("storage", 0, 7),
+            ("transaction", 0, 7),
+        ]);
+        assert_eq!(trace["otherData"]["gas_scaling_factor"], 100);
+    }
+
+    #[test]
+    fn speedscope_events_are_balanced() {
+        let profile = GasProfile::new("txn", &gas_log(1)).to_speedscope();
+        for profile in profile["profiles"].as_array().unwrap() {
+            let mut stack = vec![];
+            for event in profile["events"].as_array().unwrap() {
+                match event["type"].as_str().unwrap() {
+                    "O" => stack.push(event["frame"].as_u64().unwrap()),
+                    "C" => assert_eq!(stack.pop(), event["frame"].as_u64()),
+                    ty => panic!("unexpected event type {}", ty),
+                }
+            }
+            assert!(stack.is_empty());
+        }
+    }
+
+    #[test]
+    fn aggregate_merges_identical_paths() {
+        let logs = [gas_log(1), gas_log(3)];
+        let profile = GasProfile::aggregate("block", &logs).unwrap();
+        assert_eq!(profile.num_transactions, 2);
+        assert_eq!(profile.exec_io.total(), 45 + 125);
+        assert_eq!(profile.storage.total(), 14);
+
+        let main = profile
+            .exec_io
+            .children
+            .iter()
+            .find(|node| node.name == "0x1::coin::main")
+            .unwrap();
+        assert_eq!(main.children.len(), 1);
+        let transfer = &main.children[0];
+        assert_eq!(transfer.total(), 160);
+        assert_eq!(transfer.children.len(), 2);
+        assert_eq!(transfer.children[0].cost, 40);
+        assert_eq!(transfer.children[1].cost, 120);
+
+        assert!(GasProfile::aggregate("empty", []).is_err());
+    }
+}
@@ -0,0 +1,603 @@
+// Copyright © A-p-t-o-s Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! A language-neutral description of the resources, events and view functions of a Move
+//! package, extracted from its compiled modules and their metadata. Language-specific
+//! generators turn a `PackageBindings` into typed bindings.
+
+use anyhow::{anyhow, bail, Result};
+use creditchain_types::vm::module_metadata::get_metadata_from_compiled_code;
+use move_binary_format::{
+    access::ModuleAccess,
+    file_format::{
+        FieldDefinition, FunctionHandle, SignatureToken, StructFieldInformation, StructHandleIndex,
+    },
+    CompiledModule,
+};
+use move_core_types::{
+    account_address::AccountAddress,
+    identifier::Identifier,
+    language_storage::{ModuleId, TypeTag},
+};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fmt,
+};
+
+/// Identifies a Move struct or enum type, without type arguments.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct StructRef {
+    pub address: AccountAddress,
+    pub module: String,
+    pub name: String,
+}
+
+/// A Move type, as seen by the generated bindings.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum BindingType {
+    Bool,
+    U8,
+    U16,
+    U32,
+    U64,
+    U128,
+    U256,
+    Address,
+    Vector(Box<BindingType>),
+    /// `0x1::string::String`.
+    String,
+    /// `0x1::option::Option<T>`, which has the same BCS encoding as an optional value.
+    Option(Box<BindingType>),
+    /// `0x1::object::Object<T>`, which is represented by the address of the object.
+    Object,
+    /// A struct or enum type. Only the non-phantom type arguments are kept, as phantom
+    /// type parameters do not contribute to the layout of a value.
+    Struct(StructRef, Vec<BindingType>),
+    /// A type parameter, identified by its index among all type parameters of the
+    /// enclosing struct or function.
+    TypeParameter(u16),
+}
+
+/// A named field of a struct or of an enum variant.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FieldBinding {
+    pub name: String,
+    pub ty: BindingType,
+}
+
+/// A variant of an enum type.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct VariantBinding {
+    pub name: String,
+    pub fields: Vec<FieldBinding>,
+}
+
+/// The layout of a struct or enum type.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum StructLayout {
+    Struct(Vec<FieldBinding>),
+    Enum(Vec<VariantBinding>),
+}
+
+/// A struct or enum type for which a data type is generated.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct StructBinding {
+    pub id: StructRef,
+    /// Indices of the non-phantom type parameters.
+    pub type_params: Vec<u16>,
+    pub layout: StructLayout,
+    /// Whether this is a resource (has `key`) declared in the package.
+    pub is_resource: bool,
+    /// Whether this is an event (has `#[event]`) declared in the package.
+    pub is_event: bool,
+}
+
+/// A view function declared in the package.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ViewFunctionBinding {
+    pub address: AccountAddress,
+    pub module: String,
+    pub name: String,
+    pub type_param_count: usize,
+    pub params: Vec<BindingType>,
+    pub returns: Vec<BindingType>,
+}
+
+/// Bindings for a Move package.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct PackageBindings {
+    /// Resources and events of the package, along with all the struct types they refer to,
+    /// including types from dependencies. Sorted by `StructRef`.
+    pub structs: Vec<StructBinding>,
+    /// View functions of the package, sorted by module and name.
+    pub views: Vec<ViewFunctionBinding>,
+    /// Resources, events and view functions which could not be represented, with the reason.
+    pub skipped: Vec<String>,
+}
+
+impl fmt::Display for StructRef {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}::{}::{}",
+            self.address.to_hex_literal(),
+            self.module,
+            self.name
+        )
+    }
+}
+
+impl ViewFunctionBinding {
+    /// The fully qualified name of the function, e.g. `0x1::coin::balance`.
+    pub fn full_name(&self) -> String {
+        format!(
+            "{}::{}::{}",
+            self.address.to_hex_literal(),
+            self.module,
+            self.name
+        )
+    }
+}
+
+impl BindingType {
+    /// Converts the type of an entry function argument, as found in ABIs. Returns `None` for
+    /// types which cannot be passed as arguments by the generated builders.
+    pub fn from_type_tag(type_tag: &TypeTag) -> Option<Self> {
+        Some(match type_tag {
+            TypeTag::Bool => BindingType::Bool,
+            TypeTag::U8 => BindingType::U8,
+            TypeTag::U16 => BindingType::U16,
+            TypeTag::U32 => BindingType::U32,
+            TypeTag::U64 => BindingType::U64,
+            TypeTag::U128 => BindingType::U128,
+            TypeTag::U256 => BindingType::U256,
+            TypeTag::Address => BindingType::Address,
+            TypeTag::Vector(type_tag) => {
+                BindingType::Vector(Box::new(Self::from_type_tag(type_tag)?))
+            },
+            TypeTag::Struct(tag) if tag.address == AccountAddress::ONE => {
+                match (tag.module.as_str(), tag.name.as_str(), tag.type_args.as_slice()) {
+                    ("string", "String", []) => BindingType::String,
+                    ("option", "Option", [type_tag]) => {
+                        BindingType::Option(Box::new(Self::from_type_tag(type_tag)?))
+                    },
+                    ("object", "Object", [_]) => BindingType::Object,
+                    _ => return None,
+                }
+            },
+            TypeTag::Struct(_) | TypeTag::Signer | TypeTag::Function(..) => return None,
+        })
+    }
+
+    /// Calls `f` on this type and on every type nested in it.
+    pub fn visit(&self, f: &mut impl FnMut(&BindingType)) {
+        f(self);
+        match self {
+            BindingType::Vector(ty) | BindingType::Option(ty) => ty.visit(f),
+            BindingType::Struct(_, ty_args) => {
+                for ty in ty_args {
+                    ty.visit(f)
+                }
+            },
+            _ => {},
+        }
+    }
+
+    /// Calls `f` on the index of every type parameter appearing in this type.
+    pub fn visit_type_params(&self, f: &mut impl FnMut(u16)) {
+        self.visit(&mut |ty| {
+            if let BindingType::TypeParameter(idx) = ty {
+                f(*idx)
+            }
+        })
+    }
+}
+
+impl PackageBindings {
+    /// Extracts the bindings of the `package` modules. The `dependencies` are used to resolve
+    /// struct types declared outside of the package.
+    pub fn new(package: &[CompiledModule], dependencies: &[CompiledModule]) -> Self {
+        let resolver = Resolver {
+            modules: package
+                .iter()
+                .chain(dependencies)
+                .map(|module| (module.self_id(), module))
+                .collect(),
+        };
+        let mut structs = BTreeMap::new();
+        let mut views = vec![];
+        let mut skipped = vec![];
+        for module in package {
+            let metadata = get_metadata_from_compiled_code(module);
+            for def in module.struct_defs() {
+                let handle = module.struct_handle_at(def.struct_handle);
+                let name = module.identifier_at(handle.name).to_string();
+                let is_resource = handle.abilities.has_key();
+                let is_event = metadata
+                    .as_ref()
+                    .and_then(|metadata| metadata.struct_attributes.get(&name))
+                    .is_some_and(|attrs| attrs.iter().any(|attr| attr.is_event()));
+                if !is_resource && !is_event {
+                    continue;
+                }
+                let id = StructRef {
+                    address: *module.address(),
+                    module: module.name().to_string(),
+                    name,
+                };
+                let mut closure = BTreeMap::new();
+                match resolver.collect(&id, &mut closure, &structs) {
+                    Ok(()) => {
+                        structs.append(&mut closure);
+                        let binding = structs.get_mut(&id).expect("collected struct");
+                        binding.is_resource = is_resource;
+                        binding.is_event = is_event;
+                    },
+                    Err(err) => skipped.push(format!("{}: {}", id, err)),
+                }
+            }
+            for def in module.function_defs() {
+                let handle = module.function_handle_at(def.function);
+                let name = module.identifier_at(handle.name).to_string();
+                let is_view = metadata
+                    .as_ref()
+                    .and_then(|metadata| metadata.fun_attributes.get(&name))
+                    .is_some_and(|attrs| attrs.iter().any(|attr| attr.is_view_function()));
+                if !is_view {
+                    continue;
+                }
+                let mut view = ViewFunctionBinding {
+                    address: *module.address(),
+                    module: module.name().to_string(),
+                    name,
+                    type_param_count: handle.type_parameters.len(),
+                    params: vec![],
+                    returns: vec![],
+                };
+                let mut closure = BTreeMap::new();
+                let result =
+                    resolver.convert_signature(module, handle, &mut view, &mut closure, &structs);
+                match result {
+                    Ok(()) => {
+                        structs.append(&mut closure);
+                        views.push(view);
+                    },
+                    Err(err) => skipped.push(format!("{}: {}", view.full_name(), err)),
+                }
+            }
+        }
+        views.sort_by(|a, b| (&a.module, &a.name).cmp(&(&b.module, &b.name)));
+        PackageBindings {
+            structs: structs.into_values().collect(),
+            views,
+            skipped,
+        }
+    }
+
+    /// Removes enum types and generic struct types, for languages whose bindings cannot
+    /// represent them. Struct types and view functions referring to a removed type or to a type
+    /// parameter are removed as well, and so is `u256` unless `with_u256` is set. All the
+    /// removed items are recorded as skipped.
+    pub fn without_generics_and_enums(mut self, with_u256: bool) -> Self {
+        let mut removed = BTreeMap::new();
+        for binding in &self.structs {
+            if let StructLayout::Enum(_) = binding.layout {
+                removed.insert(binding.id.clone(), "enum types are not supported".to_string());
+            } else if !binding.type_params.is_empty() {
+                removed.insert(binding.id.clone(), "generic types are not supported".to_string());
+            }
+        }
+        loop {
+            let newly_removed = self
+                .structs
+                .iter()
+                .filter(|binding| !removed.contains_key(&binding.id))
+                .filter_map(|binding| {
+                    let fields: Vec<_> = match &binding.layout {
+                        StructLayout::Struct(fields) => fields.iter().collect(),
+                        StructLayout::Enum(variants) => {
+                            variants.iter().flat_map(|v| &v.fields).collect()
+                        },
+                    };
+                    fields
+                        .iter()
+                        .find_map(|field| unsupported_type(&field.ty, &removed, with_u256))
+                        .map(|reason| (binding.id.clone(), reason))
+                })
+                .collect::<Vec<_>>();
+            if newly_removed.is_empty() {
+                break;
+            }
+            removed.extend(newly_removed);
+        }
+        self.structs.retain(|binding| !removed.contains_key(&binding.id));
+        for (id, reason) in &removed {
+            self.skipped.push(format!("{}: {}", id, reason));
+        }
+        let mut views = vec![];
+        for view in std::mem::take(&mut self.views) {
+            match view
+                .params
+                .iter()
+                .chain(&view.returns)
+                .find_map(|ty| unsupported_type(ty, &removed, with_u256))
+            {
+                Some(reason) => self.skipped.push(format!("{}: {}", view.full_name(), reason)),
+                None => views.push(view),
+            }
+        }
+        self.views = views;
+        self
+    }
+
+    /// Returns the structs, such that every struct comes after the structs it refers to.
+    pub fn structs_in_dependency_order(&self) -> Vec<&StructBinding> {
+        fn visit<'a>(
+            binding: &'a StructBinding,
+            structs: &BTreeMap<&StructRef, &'a StructBinding>,
+            ordered: &mut Vec<&'a StructBinding>,
+            visited: &mut BTreeSet<&'a StructRef>,
+        ) {
+            if !visited.insert(&binding.id) {
+                return;
+            }
+            let fields: Vec<_> = match &binding.layout {
+                StructLayout::Struct(fields) => fields.iter().collect(),
+                StructLayout::Enum(variants) => variants.iter().flat_map(|v| &v.fields).collect(),
+            };
+            for field in fields {
+                field.ty.visit(&mut |ty| {
+                    if let BindingType::Struct(id, _) = ty {
+                        if let Some(dependency) = structs.get(id) {
+                            visit(*dependency, structs, ordered, visited)
+                        }
+                    }
+                });
+            }
+            ordered.push(binding);
+        }
+        let structs = self.structs.iter().map(|s| (&s.id, s)).collect();
+        let mut ordered = vec![];
+        let mut visited = BTreeSet::new();
+        for binding in &self.structs {
+            visit(binding, &structs, &mut ordered, &mut visited);
+        }
+        ordered
+    }
+
+    /// Returns a unique name for each module declaring a struct or a view function, keyed by
+    /// the module address and name. This is the module name, unless several modules with the
+    /// same name are declared at different addresses, in which case the address is appended.
+    pub fn module_names(&self) -> BTreeMap<(AccountAddress, String), String> {
+        let modules: BTreeSet<_> = self
+            .structs
+            .iter()
+            .map(|s| (s.id.address, s.id.module.clone()))
+            .chain(self.views.iter().map(|v| (v.address, v.module.clone())))
+            .collect();
+        let mut counts = BTreeMap::<&str, usize>::new();
+        for (_, module) in &modules {
+            *counts.entry(module.as_str()).or_default() += 1;
+        }
+        modules
+            .iter()
+            .map(|(address, module)| {
+                let name = if counts[module.as_str()] > 1 {
+                    format!("{}_{}", module, address.short_str_lossless())
+                } else {
+                    module.clone()
+                };
+                ((*address, module.clone()), name)
+            })
+            .collect()
+    }
+}
+
+/// Returns why `ty` cannot be represented, if it refers to a `removed` struct type, to a type
+/// parameter, or to `u256` while it is not supported.
+fn unsupported_type(
+    ty: &BindingType,
+    removed: &BTreeMap<StructRef, String>,
+    with_u256: bool,
+) -> Option<String> {
+    let mut reason = None;
+    ty.visit(&mut |ty| {
+        if reason.is_some() {
+            return;
+        }
+        reason = match ty {
+            BindingType::TypeParameter(_) => Some("generic types are not supported".to_string()),
+            BindingType::U256 if !with_u256 => Some("type `u256` is not supported".to_string()),
+            BindingType::Struct(id, _) if removed.contains_key(id) => {
+                Some(format!("refers to the skipped type `{}`", id))
+            },
+            _ => None,
+        };
+    });
+    reason
+}
+
+/// Resolves struct types from the loaded modules.
+struct Resolver<'a> {
+    modules: BTreeMap<ModuleId, &'a CompiledModule>,
+}
+
+impl Resolver<'_> {
+    /// Adds the struct `id` and all the struct types it refers to into `closure`, unless
+    /// they are already in `known`.
+    fn collect(
+        &self,
+        id: &StructRef,
+        closure: &mut BTreeMap<StructRef, StructBinding>,
+        known: &BTreeMap<StructRef, StructBinding>,
+    ) -> Result<()> {
+        if known.contains_key(id) || closure.contains_key(id) {
+            return Ok(());
+        }
+        let module_id = ModuleId::new(id.address, Identifier::new(id.module.as_str())?);
+        let module = self
+            .modules
+            .get(&module_id)
+            .ok_or_else(|| anyhow!("module `{}` is not available", module_id))?;
+        let def = module
+            .struct_defs()
+            .iter()
+            .find(|def| {
+                module
+                    .identifier_at(module.struct_handle_at(def.struct_handle).name)
+                    .as_str()
+                    == id.name
+            })
+            .ok_or_else(|| anyhow!("struct `{}` is not declared", id))?;
+        let handle = module.struct_handle_at(def.struct_handle);
+        let convert_fields = |fields: &[FieldDefinition]| {
+            fields
+                .iter()
+                .map(|field| {
+                    Ok(FieldBinding {
+                        name: module.identifier_at(field.name).to_string(),
+                        ty: self.convert(module, &field.signature.0)?,
+                    })
+                })
+                .collect::<Result<Vec<_>>>()
+        };
+        let layout = match &def.field_information {
+            StructFieldInformation::Native => bail!("native struct `{}` is not supported", id),
+            StructFieldInformation::Declared(fields) => {
+                StructLayout::Struct(convert_fields(fields)?)
+            },
+            StructFieldInformation::DeclaredVariants(variants) => StructLayout::Enum(
+                variants
+                    .iter()
+                    .map(|variant| {
+                        Ok(VariantBinding {
+                            name: module.identifier_at(variant.name).to_string(),
+                            fields: convert_fields(&variant.fields)?,
+                        })
+                    })
+                    .collect::<Result<Vec<_>>>()?,
+            ),
+        };
+        let type_params = handle
+            .type_parameters
+            .iter()
+            .enumerate()
+            .filter(|(_, param)| !param.is_phantom)
+            .map(|(idx, _)| idx as u16)
+            .collect();
+        closure.insert(id.clone(), StructBinding {
+            id: id.clone(),
+            type_params,
+            layout: layout.clone(),
+            is_resource: false,
+            is_event: false,
+        });
+        let fields: Vec<_> = match &layout {
+            StructLayout::Struct(fields) => fields.iter().collect(),
+            StructLayout::Enum(variants) => variants.iter().flat_map(|v| &v.fields).collect(),
+        };
+        for field in fields {
+            self.collect_type(&field.ty, closure, known)?;
+        }
+        Ok(())
+    }
+
+    /// Adds all the struct types appearing in `ty` into `closure`.
+    fn collect_type(
+        &self,
+        ty: &BindingType,
+        closure: &mut BTreeMap<StructRef, StructBinding>,
+        known: &BTreeMap<StructRef, StructBinding>,
+    ) -> Result<()> {
+        match ty {
+            BindingType::Vector(ty) | BindingType::Option(ty) => {
+                self.collect_type(ty, closure, known)
+            },
+            BindingType::Struct(id, ty_args) => {
+                self.collect(id, closure, known)?;
+                for ty in ty_args {
+                    self.collect_type(ty, closure, known)?;
+                }
+                Ok(())
+            },
+            _ => Ok(()),
+        }
+    }
+
+    /// Fills in the parameter and return types of the `view` function with the given `handle`,
+    /// and adds the struct types they refer to into `closure`.
+    fn convert_signature(
+        &self,
+        module: &CompiledModule,
+        handle: &FunctionHandle,
+        view: &mut ViewFunctionBinding,
+        closure: &mut BTreeMap<StructRef, StructBinding>,
+        known: &BTreeMap<StructRef, StructBinding>,
+    ) -> Result<()> {
+        for token in &module.signature_at(handle.parameters).0 {
+            view.params.push(self.convert(module, token)?);
+        }
+        for token in &module.signature_at(handle.return_).0 {
+            view.returns.push(self.convert(module, token)?);
+        }
+        for ty in view.params.iter().chain(&view.returns) {
+            self.collect_type(ty, closure, known)?;
+        }
+        Ok(())
+    }
+
+    /// Converts a signature token of `module` into a binding type.
+    fn convert(&self, module: &CompiledModule, token: &SignatureToken) -> Result<BindingType> {
+        use SignatureToken as S;
+        Ok(match token {
+            S::Bool => BindingType::Bool,
+            S::U8 => BindingType::U8,
+            S::U16 => BindingType::U16,
+            S::U32 => BindingType::U32,
+            S::U64 => BindingType::U64,
+            S::U128 => BindingType::U128,
+            S::U256 => BindingType::U256,
+            S::Address => BindingType::Address,
+            S::Vector(ty) => BindingType::Vector(Box::new(self.convert(module, ty)?)),
+            S::TypeParameter(idx) => BindingType::TypeParameter(*idx),
+            S::Struct(idx) => self.convert_struct(module, *idx, &[])?,
+            S::StructInstantiation(idx, ty_args) => self.convert_struct(module, *idx, ty_args)?,
+            S::Signer => bail!("type `signer` is not supported"),
+            S::Reference(_) | S::MutableReference(_) => bail!("reference types are not supported"),
+            S::Function(..) => bail!("function types are not supported"),
+        })
+    }
+
+    fn convert_struct(
+        &self,
+        module: &CompiledModule,
+        idx: StructHandleIndex,
+        ty_args: &[SignatureToken],
+    ) -> Result<BindingType> {
+        let handle = module.struct_handle_at(idx);
+        let module_id = module.module_id_for_handle(module.module_handle_at(handle.module));
+        let id = StructRef {
+            address: *module_id.address(),
+            module: module_id.name().to_string(),
+            name: module.identifier_at(handle.name).to_string(),
+        };
+        if id.address == AccountAddress::ONE {
+            match (id.module.as_str(), id.name.as_str(), ty_args) {
+                ("string", "String", []) => return Ok(BindingType::String),
+                ("option", "Option", [ty]) => {
+                    return Ok(BindingType::Option(Box::new(self.convert(module, ty)?)))
+                },
+                ("object", "Object", [_]) => return Ok(BindingType::Object),
+                _ => {},
+            }
+        }
+        let ty_args = handle
+            .type_parameters
+            .iter()
+            .zip(ty_args)
+            .filter(|(param, _)| !param.is_phantom)
+            .map(|(_, ty)| self.convert(module, ty))
+            .collect::<Result<_>>()?;
+        Ok(BindingType::Struct(id, ty_args))
+    }
+}
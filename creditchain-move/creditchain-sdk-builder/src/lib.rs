@@ -3,35 +3,39 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use creditchain_types::transaction::EntryABI;
+use move_binary_format::CompiledModule;
 use std::{ffi::OsStr, fs, io::Read, path::Path};
 
+pub mod bindings;
 pub mod golang;
+pub mod python;
 pub mod rust;
+pub mod typescript;
 
 /// Internals shared between languages.
 mod common;
 
-fn get_abi_paths(dir: &Path) -> std::io::Result<Vec<String>> {
-    let mut abi_paths = Vec::new();
+fn get_paths(dir: &Path, extension: &str) -> std::io::Result<Vec<String>> {
+    let mut paths = Vec::new();
     if dir.is_dir() {
         for entry in fs::read_dir(dir)? {
             let entry = entry?;
             let path = entry.path();
             if path.is_dir() {
-                abi_paths.append(&mut get_abi_paths(&path)?);
-            } else if let Some("abi") = path.extension().and_then(OsStr::to_str) {
-                abi_paths.push(path.to_str().unwrap().to_string());
+                paths.append(&mut get_paths(&path, extension)?);
+            } else if path.extension().and_then(OsStr::to_str) == Some(extension) {
+                paths.push(path.to_str().unwrap().to_string());
             }
         }
     }
-    Ok(abi_paths)
+    Ok(paths)
 }
 
 /// Read all ABI files the specified directories. This supports both new and old `EntryABI`s.
 pub fn read_abis(dir_paths: &[impl AsRef<Path>]) -> anyhow::Result<Vec<EntryABI>> {
     let mut abis = Vec::<EntryABI>::new();
     for dir in dir_paths.iter() {
-        for path in get_abi_paths(dir.as_ref())? {
+        for path in get_paths(dir.as_ref(), "abi")? {
             let mut buffer = Vec::new();
             let mut f = std::fs::File::open(path)?;
             f.read_to_end(&mut buffer)?;
@@ -56,6 +60,22 @@ pub fn read_abis(dir_paths: &[impl AsRef<Path>]) -> anyhow::Result<Vec<EntryABI>
     Ok(abis)
 }
 
+/// Read all compiled modules (`.mv` files) in the specified directories.
+pub fn read_modules(dir_paths: &[impl AsRef<Path>]) -> anyhow::Result<Vec<CompiledModule>> {
+    let mut modules = Vec::new();
+    for dir in dir_paths.iter() {
+        let mut paths = get_paths(dir.as_ref(), "mv")?;
+        paths.sort();
+        for path in paths {
+            let bytes = fs::read(&path)?;
+            modules.push(CompiledModule::deserialize(&bytes).map_err(|err| {
+                anyhow::anyhow!("Failed to deserialize module `{}`: {:?}", path, err)
+            })?);
+        }
+    }
+    Ok(modules)
+}
+
 /// How to copy ABI-generated source code for a given language.
 pub trait SourceInstaller {
     type Error;
@@ -8,6 +8,7 @@
 //! '''
 
 use clap::{Parser, ValueEnum};
+use creditchain_sdk_builder::bindings::PackageBindings;
 use serde_generate as serdegen;
 use serde_reflection::Registry;
 use std::path::PathBuf;
@@ -16,6 +17,9 @@ use std::path::PathBuf;
 enum Language {
     Rust,
     Go,
+    #[value(name = "typescript")]
+    TypeScript,
+    Python,
 }
 
 #[derive(Debug, Parser)]
@@ -44,7 +48,8 @@ struct Options {
     #[clap(long)]
     module_name: Option<String>,
 
-    /// Optional package name (Python) or module path (Go) of the Serde and BCS runtime dependencies.
+    /// Optional package name (Python) or module path (Go, TypeScript) of the Serde and BCS runtime
+    /// dependencies.
     #[clap(long)]
     serde_package_name: Option<String>,
 
@@ -53,13 +58,28 @@ struct Options {
     #[clap(long, default_value = "0.1.0")]
     creditchain_version_number: String,
 
-    /// Optional package name (Python) or module path (Go) of the `creditchain_types` dependency.
+    /// Optional package name (Python) or module path (Go, TypeScript) of the `creditchain_types`
+    /// dependency.
     #[clap(long)]
     package_name: Option<String>,
+
+    /// Generate typed bindings for the resources, events and view functions of the compiled
+    /// modules (`.mv` files) in these directories, instead of transaction builders.
+    #[clap(long)]
+    bindings_module_dirs: Vec<PathBuf>,
+
+    /// Directories containing the compiled modules of the dependencies, which are used to
+    /// resolve the struct types referred to by the bindings.
+    #[clap(long)]
+    bindings_dependency_dirs: Vec<PathBuf>,
 }
 
 fn main() {
     let options = Options::parse();
+    if !options.bindings_module_dirs.is_empty() {
+        output_package_bindings(&options);
+        return;
+    }
     let abis = creditchain_sdk_builder::read_abis(&options.abi_directories)
         .expect("Failed to read ABI in directory");
 
@@ -83,6 +103,26 @@ fn main() {
                     )
                     .unwrap();
                 },
+                Language::TypeScript => {
+                    creditchain_sdk_builder::typescript::output(
+                        &mut out,
+                        options.serde_package_name.clone(),
+                        options.package_name.clone(),
+                        &abis,
+                        &PackageBindings::default(),
+                    )
+                    .unwrap();
+                },
+                Language::Python => {
+                    creditchain_sdk_builder::python::output(
+                        &mut out,
+                        options.serde_package_name.clone(),
+                        options.package_name.clone(),
+                        &abis,
+                        &PackageBindings::default(),
+                    )
+                    .unwrap();
+                },
             }
             return;
         },
@@ -98,7 +138,18 @@ fn main() {
                     install_dir.clone(),
                     options.serde_package_name.clone(),
                 )),
+                Language::TypeScript => {
+                    Box::new(serdegen::typescript::Installer::new(install_dir.clone()))
+                },
+                Language::Python => Box::new(serdegen::python3::Installer::new(
+                    install_dir.clone(),
+                    options.serde_package_name.clone(),
+                )),
             };
+        if let Language::TypeScript | Language::Python = options.language {
+            installer.install_serde_runtime().unwrap();
+            installer.install_bcs_runtime().unwrap();
+        }
 
         let content =
             std::fs::read_to_string(registry_file).expect("registry file must be readable");
@@ -118,6 +169,8 @@ fn main() {
                 vec!["creditchain-types"],
             ),
             Language::Go => ("aptostypes".to_string(), vec!["aptostypes"]),
+            Language::TypeScript => ("creditchainTypes".to_string(), vec!["creditchainTypes"]),
+            Language::Python => ("creditchain_types".to_string(), vec!["creditchain_types"]),
         };
 
         let config = serdegen::CodeGeneratorConfig::new(package_name)
@@ -138,6 +191,16 @@ fn main() {
                 options.serde_package_name,
                 options.package_name,
             )),
+            Language::TypeScript => Box::new(creditchain_sdk_builder::typescript::Installer::new(
+                install_dir,
+                options.serde_package_name,
+                options.package_name,
+            )),
+            Language::Python => Box::new(creditchain_sdk_builder::python::Installer::new(
+                install_dir,
+                options.serde_package_name,
+                options.package_name,
+            )),
         };
 
     if let Some(ref name) = options.module_name {
@@ -147,6 +210,54 @@ fn main() {
     }
 }
 
+/// Generate bindings, either on stdout or in `<target_source_dir>/<module_name>.<extension>`.
+/// TypeScript and Python bindings also contain the transaction builders of the given ABIs.
+fn output_package_bindings(options: &Options) {
+    let package = creditchain_sdk_builder::read_modules(&options.bindings_module_dirs)
+        .expect("Failed to read modules in directory");
+    let dependencies = creditchain_sdk_builder::read_modules(&options.bindings_dependency_dirs)
+        .expect("Failed to read dependency modules in directory");
+    let bindings = PackageBindings::new(&package, &dependencies);
+    let abis = creditchain_sdk_builder::read_abis(&options.abi_directories)
+        .expect("Failed to read ABI in directory");
+    let extension = match options.language {
+        Language::Rust => "rs",
+        Language::Go => "go",
+        Language::TypeScript => "ts",
+        Language::Python => "py",
+    };
+    let mut out: Box<dyn std::io::Write> = match &options.target_source_dir {
+        None => Box::new(std::io::stdout()),
+        Some(dir) => {
+            let name = options.module_name.as_deref().unwrap_or("bindings");
+            std::fs::create_dir_all(dir).unwrap();
+            Box::new(std::fs::File::create(dir.join(format!("{}.{}", name, extension))).unwrap())
+        },
+    };
+    match options.language {
+        Language::Rust => {
+            creditchain_sdk_builder::rust::output_package_bindings(&mut out, &bindings).unwrap()
+        },
+        Language::Go => panic!("Package bindings are not supported for Go"),
+        Language::TypeScript => creditchain_sdk_builder::typescript::output(
+            &mut out,
+            options.serde_package_name.clone(),
+            options.package_name.clone(),
+            &abis,
+            &bindings,
+        )
+        .unwrap(),
+        Language::Python => creditchain_sdk_builder::python::output(
+            &mut out,
+            options.serde_package_name.clone(),
+            options.package_name.clone(),
+            &abis,
+            &bindings,
+        )
+        .unwrap(),
+    }
+}
+
 #[test]
 fn verify_tool() {
     use clap::CommandFactory;
@@ -0,0 +1,518 @@
+// Copyright © A-p-t-o-s Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    bindings::{BindingType, PackageBindings, StructBinding, StructLayout, ViewFunctionBinding},
+    common,
+};
+use creditchain_types::transaction::{EntryABI, EntryFunctionABI};
+use heck::{ToShoutySnakeCase, ToSnakeCase};
+use move_core_types::account_address::AccountAddress;
+use std::{
+    collections::BTreeMap,
+    io::{Result, Write},
+    path::PathBuf,
+};
+
+/// Output entry function builders for the given ABIs, along with typed bindings for the
+/// resources, events and view functions of a package, in Python. The generated module depends
+/// on the Serde and BCS runtimes, and on the `creditchain_types` module, as installed by
+/// `serde-generate`.
+pub fn output(
+    out: &mut dyn Write,
+    serde_package_name: Option<String>,
+    creditchain_package_name: Option<String>,
+    abis: &[EntryABI],
+    bindings: &PackageBindings,
+) -> Result<()> {
+    // The Python runtime of `serde-generate` has no `u256` type, so only top-level `u256`
+    // arguments of entry functions are supported.
+    let bindings = bindings
+        .clone()
+        .without_generics_and_enums(/* with_u256 */ false);
+    let mut emitter = PythonEmitter {
+        out,
+        module_names: bindings.module_names(),
+    };
+    let mut builders = vec![];
+    let mut skipped = vec![];
+    for abi in common::entry_function_abis(abis) {
+        match entry_function_arguments(&abi) {
+            Ok(args) => builders.push((abi, args)),
+            Err(reason) => skipped.push(reason),
+        }
+    }
+    skipped.extend(bindings.skipped.iter().cloned());
+
+    emitter.output_preamble(
+        serde_package_name.as_deref(),
+        creditchain_package_name.as_deref(),
+        &skipped,
+    )?;
+    for (abi, args) in &builders {
+        emitter.output_entry_function_builder(abi, args)?;
+    }
+    for binding in bindings.structs_in_dependency_order() {
+        emitter.output_struct(binding)?;
+    }
+    for view in &bindings.views {
+        emitter.output_view_function(view)?;
+    }
+    Ok(())
+}
+
+/// Returns the names and types of the arguments of an entry function, or why the entry
+/// function is not supported.
+fn entry_function_arguments(
+    abi: &EntryFunctionABI,
+) -> std::result::Result<Vec<(String, BindingType)>, String> {
+    abi.args()
+        .iter()
+        .map(|arg| {
+            let ty = BindingType::from_type_tag(arg.type_tag()).filter(|ty| {
+                // Top-level `u256` values are serialized by hand.
+                let mut supported = true;
+                if let BindingType::Vector(ty) | BindingType::Option(ty) = ty {
+                    ty.visit(&mut |ty| supported &= *ty != BindingType::U256);
+                }
+                supported
+            });
+            match ty {
+                Some(ty) => Ok((quote_identifier(&arg.name().to_snake_case()), ty)),
+                None => Err(format!(
+                    "{}::{}::{}: argument `{}` has an unsupported type",
+                    abi.module_name().address().to_hex_literal(),
+                    abi.module_name().name(),
+                    abi.name(),
+                    arg.name()
+                )),
+            }
+        })
+        .collect()
+}
+
+/// Shared state for the Python code generator.
+struct PythonEmitter<T> {
+    /// Writer.
+    out: T,
+    /// Prefixes of the generated names, keyed by Move module.
+    module_names: BTreeMap<(AccountAddress, String), String>,
+}
+
+impl<T> PythonEmitter<T>
+where
+    T: Write,
+{
+    fn output_preamble(
+        &mut self,
+        serde_package_name: Option<&str>,
+        creditchain_package_name: Option<&str>,
+        skipped: &[String],
+    ) -> Result<()> {
+        writeln!(
+            self.out,
+            r#"# Typed transaction builders and view function wrappers for Move packages.
+#
+# This code was generated by compiling known Move interfaces ("ABIs") and modules with the tool `creditchain-sdk-builder`.
+
+import typing
+from dataclasses import dataclass"#
+        )?;
+        match serde_package_name {
+            None => writeln!(self.out, "import serde_types as st\nimport bcs")?,
+            Some(package) => writeln!(
+                self.out,
+                "from {0} import serde_types as st\nfrom {0} import bcs",
+                package
+            )?,
+        }
+        match creditchain_package_name {
+            None => writeln!(self.out, "import creditchain_types")?,
+            Some(package) => writeln!(self.out, "from {} import creditchain_types", package)?,
+        }
+        if !skipped.is_empty() {
+            writeln!(self.out)?;
+            for skipped in skipped {
+                writeln!(self.out, "# Skipped {}", skipped)?;
+            }
+        }
+        write!(self.out, "{}", HELPERS)
+    }
+
+    fn output_entry_function_builder(
+        &mut self,
+        abi: &EntryFunctionABI,
+        args: &[(String, BindingType)],
+    ) -> Result<()> {
+        let ty_args = abi
+            .ty_args()
+            .iter()
+            .map(|ty_arg| quote_identifier(&ty_arg.name().to_snake_case()))
+            .collect::<Vec<_>>();
+        let params = ty_args
+            .iter()
+            .map(|name| format!("{}: creditchain_types.TypeTag", name))
+            .chain(
+                args.iter()
+                    .map(|(name, ty)| format!("{}: {}", name, self.quote_type(ty))),
+            )
+            .collect::<Vec<_>>();
+        self.output_function_signature(
+            &format!(
+                "encode_{}_{}",
+                abi.module_name().name().as_str().to_snake_case(),
+                abi.name().to_snake_case()
+            ),
+            &params,
+            "creditchain_types.TransactionPayload",
+        )?;
+        self.output_doc("    ", &common::prepare_doc_string(abi.doc()))?;
+        writeln!(
+            self.out,
+            "    return creditchain_types.TransactionPayload__EntryFunction("
+        )?;
+        writeln!(self.out, "        value=creditchain_types.EntryFunction(")?;
+        writeln!(
+            self.out,
+            "            module=_module_id(\"{}\", \"{}\"),",
+            abi.module_name().address().to_hex(),
+            abi.module_name().name()
+        )?;
+        writeln!(
+            self.out,
+            "            function=creditchain_types.Identifier(value=\"{}\"),",
+            abi.name()
+        )?;
+        writeln!(self.out, "            ty_args=[{}],", ty_args.join(", "))?;
+        self.output_serialized_arguments("            ", args)?;
+        writeln!(self.out, "        )")?;
+        writeln!(self.out, "    )")
+    }
+
+    fn output_struct(&mut self, binding: &StructBinding) -> Result<()> {
+        let StructLayout::Struct(fields) = &binding.layout else {
+            unreachable!("enum types are not supported")
+        };
+        let kind = if binding.is_resource {
+            "Resource"
+        } else if binding.is_event {
+            "Event"
+        } else {
+            "Type"
+        };
+        let name = self.quote_struct_name(binding);
+        writeln!(self.out, "\n\n@dataclass(frozen=True)")?;
+        writeln!(self.out, "class {}:", name)?;
+        writeln!(self.out, "    \"\"\"{} `{}`.\"\"\"", kind, binding.id)?;
+        writeln!(self.out)?;
+        for field in fields {
+            writeln!(
+                self.out,
+                "    {}: {}",
+                quote_identifier(&field.name),
+                self.quote_type(&field.ty)
+            )?;
+        }
+        writeln!(self.out)?;
+        writeln!(self.out, "    def bcs_serialize(self) -> bytes:")?;
+        writeln!(self.out, "        return bcs.serialize(self, {})", name)?;
+        writeln!(self.out)?;
+        writeln!(self.out, "    @staticmethod")?;
+        writeln!(
+            self.out,
+            "    def bcs_deserialize(content: bytes) -> \"{}\":",
+            name
+        )?;
+        writeln!(
+            self.out,
+            "        value, remaining = bcs.deserialize(content, {})",
+            name
+        )?;
+        writeln!(self.out, "        if remaining:")?;
+        writeln!(
+            self.out,
+            "            raise st.DeserializationError(\"Some input couldn't be deserialized\")"
+        )?;
+        writeln!(self.out, "        return value")?;
+        if binding.is_event {
+            writeln!(self.out)?;
+            writeln!(self.out)?;
+            writeln!(
+                self.out,
+                "# Type of the event `{}`, without type arguments.",
+                binding.id
+            )?;
+            writeln!(
+                self.out,
+                "{}_EVENT_TYPE = \"{}\"",
+                name.to_shouty_snake_case(),
+                binding.id
+            )?;
+        }
+        Ok(())
+    }
+
+    fn output_view_function(&mut self, view: &ViewFunctionBinding) -> Result<()> {
+        let prefix = format!(
+            "{}_{}",
+            self.module_names[&(view.address, view.module.clone())],
+            view.name
+        );
+        let ty_args = (0..view.type_param_count)
+            .map(|idx| format!("type_arg_{}", idx))
+            .collect::<Vec<_>>();
+        let args = view
+            .params
+            .iter()
+            .enumerate()
+            .map(|(idx, ty)| (format!("arg_{}", idx), ty.clone()))
+            .collect::<Vec<_>>();
+        let params = ty_args
+            .iter()
+            .map(|name| format!("{}: creditchain_types.TypeTag", name))
+            .chain(
+                args.iter()
+                    .map(|(name, ty)| format!("{}: {}", name, self.quote_type(ty))),
+            )
+            .collect::<Vec<_>>();
+        self.output_function_signature(&format!("{}_view", prefix), &params, "ViewFunction")?;
+        writeln!(
+            self.out,
+            "    \"\"\"Builds a BCS view request for the view function `{}`.\"\"\"",
+            view.full_name()
+        )?;
+        writeln!(self.out, "    return ViewFunction(")?;
+        writeln!(
+            self.out,
+            "        module=_module_id(\"{}\", \"{}\"),",
+            view.address.to_hex(),
+            view.module
+        )?;
+        writeln!(
+            self.out,
+            "        function=creditchain_types.Identifier(value=\"{}\"),",
+            view.name
+        )?;
+        writeln!(self.out, "        ty_args=[{}],", ty_args.join(", "))?;
+        self.output_serialized_arguments("        ", &args)?;
+        writeln!(self.out, "    )")?;
+
+        let (return_type, value_type) = match view.returns.as_slice() {
+            [] => ("None".to_string(), "None".to_string()),
+            [ty] => (self.quote_type(ty), self.quote_type(ty)),
+            tys => {
+                let tys = tys
+                    .iter()
+                    .map(|ty| self.quote_type(ty))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                let ty = format!("typing.Tuple[{}]", tys);
+                (ty.clone(), ty)
+            },
+        };
+        self.output_function_signature(
+            &format!("decode_{}_view", prefix),
+            &["content: bytes".to_string()],
+            &return_type,
+        )?;
+        writeln!(
+            self.out,
+            "    \"\"\"Decodes the BCS-encoded return values of the view function `{}`.\"\"\"",
+            view.full_name()
+        )?;
+        writeln!(
+            self.out,
+            "    return _decode_view_values(content, {}, {})",
+            view.returns.len(),
+            value_type
+        )
+    }
+
+    fn output_doc(&mut self, indentation: &str, doc: &str) -> Result<()> {
+        if doc.is_empty() {
+            return Ok(());
+        }
+        writeln!(self.out, "{}\"\"\"", indentation)?;
+        for line in doc.lines() {
+            if line.is_empty() {
+                writeln!(self.out)?;
+            } else {
+                writeln!(self.out, "{}{}", indentation, line)?;
+            }
+        }
+        writeln!(self.out, "{}\"\"\"", indentation)
+    }
+
+    fn output_function_signature(
+        &mut self,
+        name: &str,
+        params: &[String],
+        return_type: &str,
+    ) -> Result<()> {
+        writeln!(self.out, "\n\ndef {}(", name)?;
+        for param in params {
+            writeln!(self.out, "    {},", param)?;
+        }
+        writeln!(self.out, ") -> {}:", return_type)
+    }
+
+    fn output_serialized_arguments(
+        &mut self,
+        indentation: &str,
+        args: &[(String, BindingType)],
+    ) -> Result<()> {
+        if args.is_empty() {
+            return writeln!(self.out, "{}args=[],", indentation);
+        }
+        writeln!(self.out, "{}args=[", indentation)?;
+        for (name, ty) in args {
+            match ty {
+                BindingType::U256 => writeln!(
+                    self.out,
+                    "{}    {}.to_bytes(32, \"little\"),",
+                    indentation, name
+                )?,
+                _ => writeln!(
+                    self.out,
+                    "{}    bcs.serialize({}, {}),",
+                    indentation,
+                    name,
+                    self.quote_type(ty)
+                )?,
+            }
+        }
+        writeln!(self.out, "{}],", indentation)
+    }
+
+    fn quote_type(&self, ty: &BindingType) -> String {
+        match ty {
+            BindingType::Bool => "bool".into(),
+            BindingType::U8 => "st.uint8".into(),
+            BindingType::U16 => "st.uint16".into(),
+            BindingType::U32 => "st.uint32".into(),
+            BindingType::U64 => "st.uint64".into(),
+            BindingType::U128 => "st.uint128".into(),
+            // Only used for top-level arguments of entry functions.
+            BindingType::U256 => "int".into(),
+            BindingType::Address | BindingType::Object => "creditchain_types.AccountAddress".into(),
+            BindingType::String => "str".into(),
+            BindingType::Vector(ty) => match ty.as_ref() {
+                BindingType::U8 => "bytes".into(),
+                _ => format!("typing.Sequence[{}]", self.quote_type(ty)),
+            },
+            BindingType::Option(ty) => format!("typing.Optional[{}]", self.quote_type(ty)),
+            BindingType::Struct(id, _) => format!(
+                "{}__{}",
+                self.module_names[&(id.address, id.module.clone())],
+                id.name
+            ),
+            BindingType::TypeParameter(_) => unreachable!("generic types are not supported"),
+        }
+    }
+
+    fn quote_struct_name(&self, binding: &StructBinding) -> String {
+        self.quote_type(&BindingType::Struct(binding.id.clone(), vec![]))
+    }
+}
+
+/// Quotes a Move identifier as a Python identifier, escaping keywords.
+fn quote_identifier(ident: &str) -> String {
+    const KEYWORDS: &[&str] = &[
+        "False", "None", "True", "and", "as", "assert", "async", "await", "break", "class",
+        "continue", "def", "del", "elif", "else", "except", "finally", "for", "from", "global",
+        "if", "import", "in", "is", "lambda", "nonlocal", "not", "or", "pass", "raise", "return",
+        "try", "while", "with", "yield",
+    ];
+    if KEYWORDS.contains(&ident) {
+        format!("{}_", ident)
+    } else {
+        ident.to_string()
+    }
+}
+
+/// Helpers shared by the generated builders and bindings.
+const HELPERS: &str = r#"
+
+@dataclass(frozen=True)
+class ViewFunction:
+    """A BCS view request, to be posted to the `view` endpoint of the REST API."""
+
+    module: creditchain_types.ModuleId
+    function: creditchain_types.Identifier
+    ty_args: typing.Sequence[creditchain_types.TypeTag]
+    args: typing.Sequence[bytes]
+
+    def bcs_serialize(self) -> bytes:
+        return bcs.serialize(self, ViewFunction)
+
+
+def _module_id(address: str, name: str) -> creditchain_types.ModuleId:
+    return creditchain_types.ModuleId(
+        address=creditchain_types.AccountAddress(
+            value=tuple(st.uint8(byte) for byte in bytes.fromhex(address))
+        ),
+        name=creditchain_types.Identifier(value=name),
+    )
+
+
+def _decode_view_values(content: bytes, count: int, value_type: typing.Any) -> typing.Any:
+    length, shift = 0, 0
+    while True:
+        if not content:
+            raise st.DeserializationError("Unexpected end of input")
+        byte, content = content[0], content[1:]
+        length |= (byte & 0x7F) << shift
+        shift += 7
+        if byte < 0x80:
+            break
+    if length != count:
+        raise st.DeserializationError(f"Expected {count} return values, got {length}")
+    value = None
+    if value_type is not None:
+        value, content = bcs.deserialize(content, value_type)
+    if content:
+        raise st.DeserializationError("Some input couldn't be deserialized")
+    return value
+"#;
+
+pub struct Installer {
+    install_dir: PathBuf,
+    serde_package_name: Option<String>,
+    creditchain_package_name: Option<String>,
+}
+
+impl Installer {
+    pub fn new(
+        install_dir: PathBuf,
+        serde_package_name: Option<String>,
+        creditchain_package_name: Option<String>,
+    ) -> Self {
+        Installer {
+            install_dir,
+            serde_package_name,
+            creditchain_package_name,
+        }
+    }
+}
+
+impl crate::SourceInstaller for Installer {
+    type Error = Box<dyn std::error::Error>;
+
+    fn install_transaction_builders(
+        &self,
+        name: &str,
+        abis: &[EntryABI],
+    ) -> std::result::Result<(), Self::Error> {
+        let dir_path = self.install_dir.join(name);
+        std::fs::create_dir_all(&dir_path)?;
+        let mut file = std::fs::File::create(dir_path.join("__init__.py"))?;
+        output(
+            &mut file,
+            self.serde_package_name.clone(),
+            self.creditchain_package_name.clone(),
+            abis,
+            &PackageBindings::default(),
+        )?;
+        Ok(())
+    }
+}
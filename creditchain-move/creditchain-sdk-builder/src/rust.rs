@@ -3,7 +3,12 @@
 // SPDX-License-Identifier: Apache-2.0
 
 // TODO[Orderless]: Update the sdk builder to generate transactions with new payload format
-use crate::common;
+use crate::{
+    bindings::{
+        BindingType, PackageBindings, StructBinding, StructLayout, StructRef, ViewFunctionBinding,
+    },
+    common,
+};
 use creditchain_types::transaction::{
     ArgumentABI, EntryABI, EntryFunctionABI, TransactionScriptABI, TypeArgumentABI,
 };
@@ -19,7 +24,7 @@ use serde_generate::{
 };
 use serde_reflection::ContainerFormat;
 use std::{
-    collections::BTreeMap,
+    collections::{BTreeMap, BTreeSet},
     io::{Result, Write},
     path::PathBuf,
     str::FromStr,
@@ -915,6 +920,361 @@ fn decode_{}_argument(arg: TransactionArgument) -> Option<{}> {{
     }
 }
 
+/// Output typed bindings in Rust for the resources, events and view functions of a package.
+/// The generated code depends on `bcs`, `serde`, `move-core-types` and `creditchain-api-types`.
+pub fn output_package_bindings(out: &mut dyn Write, bindings: &PackageBindings) -> Result<()> {
+    let mut emitter = RustBindingsEmitter {
+        out: IndentedWriter::new(out, IndentConfig::Space(4)),
+        module_names: bindings.module_names(),
+    };
+    emitter.output_bindings_preamble(bindings)?;
+    for ((address, module), module_name) in emitter.module_names.clone() {
+        writeln!(
+            emitter.out,
+            "\npub mod {} {{",
+            quote_bindings_ident(&module_name)
+        )?;
+        emitter.out.indent();
+        writeln!(emitter.out, "use super::*;")?;
+        let in_module = |id: &StructRef| id.address == address && id.module == module;
+        for binding in bindings.structs.iter().filter(|s| in_module(&s.id)) {
+            emitter.output_struct_binding(binding)?;
+        }
+        for view in bindings
+            .views
+            .iter()
+            .filter(|v| v.address == address && v.module == module)
+        {
+            emitter.output_view_function_binding(view)?;
+        }
+        emitter.out.unindent();
+        writeln!(emitter.out, "}}")?;
+    }
+    Ok(())
+}
+
+/// Shared state for the generator of Rust package bindings.
+struct RustBindingsEmitter<T> {
+    /// Writer.
+    out: IndentedWriter<T>,
+    /// Names of the generated Rust modules, keyed by Move module.
+    module_names: BTreeMap<(AccountAddress, String), String>,
+}
+
+impl<T> RustBindingsEmitter<T>
+where
+    T: Write,
+{
+    fn output_bindings_preamble(&mut self, bindings: &PackageBindings) -> Result<()> {
+        writeln!(
+            self.out,
+            r#"// Typed bindings for the resources, events and view functions of a Move package.
+//
+// This code was generated from compiled Move modules with the tool `creditchain-sdk-builder`.
+
+#![allow(dead_code)]
+#![allow(unused_imports)]
+#![allow(non_camel_case_types)]
+#![allow(non_snake_case)]
+#![allow(clippy::too_many_arguments)]
+
+use creditchain_api_types::ViewFunction;
+use move_core_types::{{
+    account_address::AccountAddress,
+    ident_str,
+    language_storage::{{ModuleId, TypeTag}},
+    u256::U256,
+}};
+use serde::{{de::DeserializeOwned, Deserialize, Serialize}};"#
+        )?;
+        if !bindings.skipped.is_empty() {
+            writeln!(self.out)?;
+            for skipped in &bindings.skipped {
+                writeln!(self.out, "// Skipped {}", skipped)?;
+            }
+        }
+        writeln!(
+            self.out,
+            r#"
+/// Decodes the BCS-encoded return values of a view function, which are prefixed by their count.
+fn decode_view_values<T: DeserializeOwned>(bytes: &[u8], count: u64) -> bcs::Result<T> {{
+    let mut len = 0u64;
+    let mut pos = 0;
+    loop {{
+        let byte = *bytes.get(pos).ok_or(bcs::Error::Eof)?;
+        if pos >= 10 {{
+            return Err(bcs::Error::IntegerOverflowDuringUleb128Decoding);
+        }}
+        len |= u64::from(byte & 0x7f) << (7 * pos);
+        pos += 1;
+        if byte & 0x80 == 0 {{
+            break;
+        }}
+    }}
+    if len != count {{
+        return Err(bcs::Error::Custom(format!(
+            "expected {{}} return values, got {{}}",
+            count, len
+        )));
+    }}
+    bcs::from_bytes(&bytes[pos..])
+}}"#
+        )
+    }
+
+    fn output_struct_binding(&mut self, binding: &StructBinding) -> Result<()> {
+        let kind = if binding.is_resource {
+            "Resource"
+        } else if binding.is_event {
+            "Event"
+        } else {
+            "Type"
+        };
+        writeln!(self.out, "\n/// {} `{}`.", kind, binding.id)?;
+        writeln!(
+            self.out,
+            "#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]"
+        )?;
+        let name = format!(
+            "{}{}",
+            quote_bindings_ident(&binding.id.name),
+            Self::quote_generics(binding.type_params.iter().map(|idx| format!("T{}", idx)))
+        );
+        match &binding.layout {
+            StructLayout::Struct(fields) => {
+                writeln!(self.out, "pub struct {} {{", name)?;
+                self.out.indent();
+                for field in fields {
+                    writeln!(
+                        self.out,
+                        "pub {}: {},",
+                        quote_bindings_ident(&field.name),
+                        self.quote_binding_type(&binding.id, &field.ty)
+                    )?;
+                }
+            },
+            StructLayout::Enum(variants) => {
+                writeln!(self.out, "pub enum {} {{", name)?;
+                self.out.indent();
+                for variant in variants {
+                    if variant.fields.is_empty() {
+                        writeln!(self.out, "{},", quote_bindings_ident(&variant.name))?;
+                        continue;
+                    }
+                    writeln!(self.out, "{} {{", quote_bindings_ident(&variant.name))?;
+                    self.out.indent();
+                    for field in &variant.fields {
+                        writeln!(
+                            self.out,
+                            "{}: {},",
+                            quote_bindings_ident(&field.name),
+                            self.quote_binding_type(&binding.id, &field.ty)
+                        )?;
+                    }
+                    self.out.unindent();
+                    writeln!(self.out, "}},")?;
+                }
+            },
+        }
+        self.out.unindent();
+        writeln!(self.out, "}}")?;
+        if binding.is_event {
+            writeln!(
+                self.out,
+                "\n/// Type of the event `{}`, without type arguments.",
+                binding.id
+            )?;
+            writeln!(
+                self.out,
+                "pub const {}_EVENT_TYPE: &str = \"{}\";",
+                binding.id.name.to_shouty_snake_case(),
+                binding.id
+            )?;
+        }
+        Ok(())
+    }
+
+    fn output_view_function_binding(&mut self, view: &ViewFunctionBinding) -> Result<()> {
+        let context = StructRef {
+            address: view.address,
+            module: view.module.clone(),
+            name: view.name.clone(),
+        };
+        let mut params = (0..view.type_param_count)
+            .map(|idx| format!("type_arg_{}: TypeTag", idx))
+            .collect::<Vec<_>>();
+        params.extend(
+            view.params
+                .iter()
+                .enumerate()
+                .map(|(idx, ty)| format!("arg_{}: {}", idx, self.quote_binding_type(&context, ty))),
+        );
+        writeln!(
+            self.out,
+            "\n/// Builds a BCS view request for the view function `{}`.",
+            view.full_name()
+        )?;
+        writeln!(
+            self.out,
+            "pub fn {}_view{}({}) -> ViewFunction {{",
+            view.name,
+            Self::quote_generics(
+                Self::type_params(&view.params).map(|idx| format!("T{}: Serialize", idx))
+            ),
+            params.join(", ")
+        )?;
+        self.out.indent();
+        writeln!(self.out, "ViewFunction {{")?;
+        self.out.indent();
+        writeln!(
+            self.out,
+            "module: ModuleId::new({}, ident_str!(\"{}\").to_owned()),",
+            quote_bindings_address(&view.address),
+            view.module
+        )?;
+        writeln!(
+            self.out,
+            "function: ident_str!(\"{}\").to_owned(),",
+            view.name
+        )?;
+        writeln!(
+            self.out,
+            "ty_args: vec![{}],",
+            (0..view.type_param_count)
+                .map(|idx| format!("type_arg_{}", idx))
+                .collect::<Vec<_>>()
+                .join(", ")
+        )?;
+        writeln!(
+            self.out,
+            "args: vec![{}],",
+            (0..view.params.len())
+                .map(|idx| format!("bcs::to_bytes(&arg_{}).unwrap()", idx))
+                .collect::<Vec<_>>()
+                .join(", ")
+        )?;
+        self.out.unindent();
+        writeln!(self.out, "}}")?;
+        self.out.unindent();
+        writeln!(self.out, "}}")?;
+
+        let returns = view
+            .returns
+            .iter()
+            .map(|ty| self.quote_binding_type(&context, ty))
+            .collect::<Vec<_>>();
+        let return_type = if returns.len() == 1 {
+            returns[0].clone()
+        } else {
+            format!("({})", returns.join(", "))
+        };
+        writeln!(
+            self.out,
+            "\n/// Decodes the BCS-encoded return values of the view function `{}`.",
+            view.full_name()
+        )?;
+        writeln!(
+            self.out,
+            "pub fn decode_{}_view{}(bytes: &[u8]) -> bcs::Result<{}> {{",
+            view.name,
+            Self::quote_generics(
+                Self::type_params(&view.returns).map(|idx| format!("T{}: DeserializeOwned", idx))
+            ),
+            return_type
+        )?;
+        self.out.indent();
+        writeln!(self.out, "decode_view_values(bytes, {})", returns.len())?;
+        self.out.unindent();
+        writeln!(self.out, "}}")
+    }
+
+    /// Returns the type parameters appearing in `types`, sorted.
+    fn type_params(types: &[BindingType]) -> impl Iterator<Item = u16> {
+        let mut params = BTreeSet::new();
+        for ty in types {
+            ty.visit_type_params(&mut |idx| {
+                params.insert(idx);
+            });
+        }
+        params.into_iter()
+    }
+
+    fn quote_generics(params: impl Iterator<Item = String>) -> String {
+        let params = params.collect::<Vec<_>>();
+        if params.is_empty() {
+            String::new()
+        } else {
+            format!("<{}>", params.join(", "))
+        }
+    }
+
+    /// Quotes `ty`, as it appears in the Rust module generated for the module of `context`.
+    fn quote_binding_type(&self, context: &StructRef, ty: &BindingType) -> String {
+        match ty {
+            BindingType::Bool => "bool".into(),
+            BindingType::U8 => "u8".into(),
+            BindingType::U16 => "u16".into(),
+            BindingType::U32 => "u32".into(),
+            BindingType::U64 => "u64".into(),
+            BindingType::U128 => "u128".into(),
+            BindingType::U256 => "U256".into(),
+            BindingType::Address | BindingType::Object => "AccountAddress".into(),
+            BindingType::String => "String".into(),
+            BindingType::Vector(ty) => format!("Vec<{}>", self.quote_binding_type(context, ty)),
+            BindingType::Option(ty) => {
+                format!("Option<{}>", self.quote_binding_type(context, ty))
+            },
+            BindingType::TypeParameter(idx) => format!("T{}", idx),
+            BindingType::Struct(id, ty_args) => {
+                let name = quote_bindings_ident(&id.name);
+                let path = if id.address == context.address && id.module == context.module {
+                    name
+                } else {
+                    let module_name = &self.module_names[&(id.address, id.module.clone())];
+                    format!("super::{}::{}", quote_bindings_ident(module_name), name)
+                };
+                format!(
+                    "{}{}",
+                    path,
+                    Self::quote_generics(
+                        ty_args
+                            .iter()
+                            .map(|ty| self.quote_binding_type(context, ty))
+                    )
+                )
+            },
+        }
+    }
+}
+
+/// Quotes a Move identifier as a Rust identifier, escaping Rust keywords.
+fn quote_bindings_ident(ident: &str) -> String {
+    const KEYWORDS: &[&str] = &[
+        "abstract", "as", "async", "await", "become", "box", "break", "const", "continue", "do",
+        "dyn", "else", "enum", "extern", "false", "final", "fn", "for", "if", "impl", "in", "let",
+        "loop", "macro", "match", "mod", "move", "mut", "override", "priv", "pub", "ref", "return",
+        "static", "struct", "trait", "true", "try", "type", "typeof", "unsafe", "unsized", "use",
+        "virtual", "where", "while", "yield",
+    ];
+    if KEYWORDS.contains(&ident) {
+        format!("r#{}", ident)
+    } else {
+        ident.to_string()
+    }
+}
+
+fn quote_bindings_address(address: &AccountAddress) -> String {
+    format!(
+        "AccountAddress::new([{}])",
+        address
+            .to_vec()
+            .iter()
+            .map(|x| format!("{}", x))
+            .collect::<Vec<_>>()
+            .join(", ")
+    )
+}
+
 pub struct Installer {
     install_dir: PathBuf,
     creditchain_types_version: String,
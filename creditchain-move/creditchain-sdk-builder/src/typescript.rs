@@ -0,0 +1,688 @@
+// Copyright © A-p-t-o-s Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    bindings::{BindingType, PackageBindings, StructBinding, StructLayout, ViewFunctionBinding},
+    common,
+};
+use creditchain_types::transaction::{EntryABI, EntryFunctionABI};
+use heck::{ToLowerCamelCase, ToShoutySnakeCase, ToUpperCamelCase};
+use move_core_types::account_address::AccountAddress;
+use std::{
+    collections::BTreeMap,
+    io::{Result, Write},
+    path::PathBuf,
+};
+
+/// Output entry function builders for the given ABIs, along with typed bindings for the
+/// resources, events and view functions of a package, in TypeScript. The generated module
+/// depends on the Serde and BCS runtimes, and on the `creditchainTypes` module, as installed
+/// by `serde-generate`.
+pub fn output(
+    out: &mut dyn Write,
+    serde_module_path: Option<String>,
+    creditchain_module_path: Option<String>,
+    abis: &[EntryABI],
+    bindings: &PackageBindings,
+) -> Result<()> {
+    let bindings = bindings
+        .clone()
+        .without_generics_and_enums(/* with_u256 */ true);
+    let mut emitter = TypeScriptEmitter {
+        out,
+        module_names: bindings.module_names(),
+    };
+    let mut builders = vec![];
+    let mut skipped = vec![];
+    for abi in common::entry_function_abis(abis) {
+        match entry_function_arguments(&abi) {
+            Ok(args) => builders.push((abi, args)),
+            Err(reason) => skipped.push(reason),
+        }
+    }
+    skipped.extend(bindings.skipped.iter().cloned());
+
+    emitter.output_preamble(
+        serde_module_path.as_deref().unwrap_or(".."),
+        creditchain_module_path
+            .as_deref()
+            .unwrap_or("../creditchainTypes"),
+        &skipped,
+    )?;
+    for (abi, args) in &builders {
+        emitter.output_entry_function_builder(abi, args)?;
+    }
+    for ((address, module), module_name) in emitter.module_names.clone() {
+        writeln!(emitter.out, "\nexport namespace {} {{", module_name)?;
+        let mut first = true;
+        let mut separate = |emitter: &mut TypeScriptEmitter<_>| {
+            if !std::mem::take(&mut first) {
+                writeln!(emitter.out)?;
+            }
+            Ok::<_, std::io::Error>(())
+        };
+        for binding in bindings
+            .structs
+            .iter()
+            .filter(|s| s.id.address == address && s.id.module == module)
+        {
+            separate(&mut emitter)?;
+            emitter.output_struct(binding)?;
+        }
+        for view in bindings
+            .views
+            .iter()
+            .filter(|v| v.address == address && v.module == module)
+        {
+            separate(&mut emitter)?;
+            emitter.output_view_function(view)?;
+        }
+        writeln!(emitter.out, "}}")?;
+    }
+    Ok(())
+}
+
+/// Returns the names and types of the arguments of an entry function, or why the entry
+/// function is not supported.
+fn entry_function_arguments(
+    abi: &EntryFunctionABI,
+) -> std::result::Result<Vec<(String, BindingType)>, String> {
+    abi.args()
+        .iter()
+        .map(|arg| match BindingType::from_type_tag(arg.type_tag()) {
+            Some(ty) => Ok((quote_identifier(&arg.name().to_lower_camel_case()), ty)),
+            None => Err(format!(
+                "{}::{}::{}: argument `{}` has an unsupported type",
+                abi.module_name().address().to_hex_literal(),
+                abi.module_name().name(),
+                abi.name(),
+                arg.name()
+            )),
+        })
+        .collect()
+}
+
+/// Shared state for the TypeScript code generator.
+struct TypeScriptEmitter<T> {
+    /// Writer.
+    out: T,
+    /// Names of the generated namespaces, keyed by Move module.
+    module_names: BTreeMap<(AccountAddress, String), String>,
+}
+
+impl<T> TypeScriptEmitter<T>
+where
+    T: Write,
+{
+    fn output_preamble(
+        &mut self,
+        serde_module_path: &str,
+        creditchain_module_path: &str,
+        skipped: &[String],
+    ) -> Result<()> {
+        writeln!(
+            self.out,
+            r#"// Typed transaction builders and view function wrappers for Move packages.
+//
+// This code was generated by compiling known Move interfaces ("ABIs") and modules with the tool `creditchain-sdk-builder`.
+
+import {{ Serializer, Deserializer }} from "{0}/serde/mod.ts";
+import {{ BcsSerializer, BcsDeserializer }} from "{0}/bcs/mod.ts";
+import * as CreditChainTypes from "{1}/mod.ts";"#,
+            serde_module_path, creditchain_module_path
+        )?;
+        if !skipped.is_empty() {
+            writeln!(self.out)?;
+            for skipped in skipped {
+                writeln!(self.out, "// Skipped {}", skipped)?;
+            }
+        }
+        write!(self.out, "{}", HELPERS)
+    }
+
+    fn output_entry_function_builder(
+        &mut self,
+        abi: &EntryFunctionABI,
+        args: &[(String, BindingType)],
+    ) -> Result<()> {
+        writeln!(self.out)?;
+        self.output_doc("", &common::prepare_doc_string(abi.doc()))?;
+        let ty_args = abi
+            .ty_args()
+            .iter()
+            .map(|ty_arg| quote_identifier(&ty_arg.name().to_lower_camel_case()))
+            .collect::<Vec<_>>();
+        let params = ty_args
+            .iter()
+            .map(|name| format!("{}: CreditChainTypes.TypeTag", name))
+            .chain(
+                args.iter()
+                    .map(|(name, ty)| format!("{}: {}", name, self.quote_type(ty))),
+            )
+            .collect::<Vec<_>>();
+        self.output_function_signature(
+            "",
+            &format!(
+                "encode{}{}",
+                abi.module_name().name().to_string().to_upper_camel_case(),
+                abi.name().to_upper_camel_case()
+            ),
+            &params,
+            "CreditChainTypes.TransactionPayload",
+        )?;
+        writeln!(
+            self.out,
+            "  return new CreditChainTypes.TransactionPayloadVariantEntryFunction("
+        )?;
+        writeln!(self.out, "    new CreditChainTypes.EntryFunction(")?;
+        writeln!(
+            self.out,
+            "      moduleId(\"{}\", \"{}\"),",
+            abi.module_name().address().to_hex(),
+            abi.module_name().name()
+        )?;
+        writeln!(
+            self.out,
+            "      new CreditChainTypes.Identifier(\"{}\"),",
+            abi.name()
+        )?;
+        writeln!(self.out, "      [{}],", ty_args.join(", "))?;
+        self.output_serialized_arguments("      ", args)?;
+        writeln!(self.out, "    ),")?;
+        writeln!(self.out, "  );")?;
+        writeln!(self.out, "}}")
+    }
+
+    fn output_struct(&mut self, binding: &StructBinding) -> Result<()> {
+        let StructLayout::Struct(fields) = &binding.layout else {
+            unreachable!("enum types are not supported")
+        };
+        let kind = if binding.is_resource {
+            "Resource"
+        } else if binding.is_event {
+            "Event"
+        } else {
+            "Type"
+        };
+        let name = &binding.id.name;
+        let fields = fields
+            .iter()
+            .map(|field| (quote_identifier(&field.name), &field.ty))
+            .collect::<Vec<_>>();
+        writeln!(self.out, "  /** {} `{}`. */", kind, binding.id)?;
+        writeln!(self.out, "  export class {} {{", name)?;
+        writeln!(self.out, "    constructor(")?;
+        for (field, ty) in &fields {
+            writeln!(self.out, "      public {}: {},", field, self.quote_type(ty))?;
+        }
+        writeln!(self.out, "    ) {{}}")?;
+        writeln!(self.out)?;
+        writeln!(
+            self.out,
+            "    public serialize(serializer: Serializer): void {{"
+        )?;
+        for (field, ty) in &fields {
+            writeln!(
+                self.out,
+                "      {};",
+                self.quote_serialize(ty, &format!("this.{}", field))
+            )?;
+        }
+        writeln!(self.out, "    }}")?;
+        writeln!(self.out)?;
+        writeln!(
+            self.out,
+            "    static deserialize(deserializer: Deserializer): {} {{",
+            name
+        )?;
+        writeln!(self.out, "      return new {}(", name)?;
+        for (_, ty) in &fields {
+            writeln!(self.out, "        {},", self.quote_deserialize(ty))?;
+        }
+        writeln!(self.out, "      );")?;
+        writeln!(self.out, "    }}")?;
+        writeln!(self.out, "  }}")?;
+        if binding.is_event {
+            writeln!(self.out)?;
+            writeln!(
+                self.out,
+                "  /** Type of the event `{}`, without type arguments. */",
+                binding.id
+            )?;
+            writeln!(
+                self.out,
+                "  export const {}_EVENT_TYPE = \"{}\";",
+                name.to_shouty_snake_case(),
+                binding.id
+            )?;
+        }
+        Ok(())
+    }
+
+    fn output_view_function(&mut self, view: &ViewFunctionBinding) -> Result<()> {
+        let ty_args = (0..view.type_param_count)
+            .map(|idx| format!("typeArg{}", idx))
+            .collect::<Vec<_>>();
+        let args = view
+            .params
+            .iter()
+            .enumerate()
+            .map(|(idx, ty)| (format!("arg{}", idx), ty.clone()))
+            .collect::<Vec<_>>();
+        let params = ty_args
+            .iter()
+            .map(|name| format!("{}: CreditChainTypes.TypeTag", name))
+            .chain(
+                args.iter()
+                    .map(|(name, ty)| format!("{}: {}", name, self.quote_type(ty))),
+            )
+            .collect::<Vec<_>>();
+        writeln!(
+            self.out,
+            "  /** Builds a BCS view request for the view function `{}`. */",
+            view.full_name()
+        )?;
+        self.output_function_signature(
+            "  ",
+            &format!("{}View", view.name.to_lower_camel_case()),
+            &params,
+            "ViewFunction",
+        )?;
+        writeln!(self.out, "    return new ViewFunction(")?;
+        writeln!(
+            self.out,
+            "      moduleId(\"{}\", \"{}\"),",
+            view.address.to_hex(),
+            view.module
+        )?;
+        writeln!(
+            self.out,
+            "      new CreditChainTypes.Identifier(\"{}\"),",
+            view.name
+        )?;
+        writeln!(self.out, "      [{}],", ty_args.join(", "))?;
+        self.output_serialized_arguments("      ", &args)?;
+        writeln!(self.out, "    );")?;
+        writeln!(self.out, "  }}")?;
+
+        let return_type = match view.returns.as_slice() {
+            [] => "void".to_string(),
+            [ty] => self.quote_type(ty),
+            tys => format!(
+                "[{}]",
+                tys.iter()
+                    .map(|ty| self.quote_type(ty))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+        };
+        writeln!(self.out)?;
+        writeln!(
+            self.out,
+            "  /** Decodes the BCS-encoded return values of the view function `{}`. */",
+            view.full_name()
+        )?;
+        writeln!(
+            self.out,
+            "  export function decode{}View(bytes: Uint8Array): {} {{",
+            view.name.to_upper_camel_case(),
+            return_type
+        )?;
+        writeln!(
+            self.out,
+            "    const deserializer = new BcsDeserializer(bytes);"
+        )?;
+        writeln!(
+            self.out,
+            "    expectViewValues(deserializer, {});",
+            view.returns.len()
+        )?;
+        match view.returns.as_slice() {
+            [] => {},
+            [ty] => writeln!(self.out, "    return {};", self.quote_deserialize(ty))?,
+            tys => writeln!(
+                self.out,
+                "    return [{}];",
+                tys.iter()
+                    .map(|ty| self.quote_deserialize(ty))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            )?,
+        }
+        writeln!(self.out, "  }}")
+    }
+
+    fn output_doc(&mut self, indentation: &str, doc: &str) -> Result<()> {
+        if doc.is_empty() {
+            return Ok(());
+        }
+        writeln!(self.out, "{}/**", indentation)?;
+        for line in doc.lines() {
+            if line.is_empty() {
+                writeln!(self.out, "{} *", indentation)?;
+            } else {
+                writeln!(self.out, "{} * {}", indentation, line)?;
+            }
+        }
+        writeln!(self.out, "{} */", indentation)
+    }
+
+    fn output_function_signature(
+        &mut self,
+        indentation: &str,
+        name: &str,
+        params: &[String],
+        return_type: &str,
+    ) -> Result<()> {
+        if params.is_empty() {
+            return writeln!(
+                self.out,
+                "{}export function {}(): {} {{",
+                indentation, name, return_type
+            );
+        }
+        writeln!(self.out, "{}export function {}(", indentation, name)?;
+        for param in params {
+            writeln!(self.out, "{}  {},", indentation, param)?;
+        }
+        writeln!(self.out, "{}): {} {{", indentation, return_type)
+    }
+
+    fn output_serialized_arguments(
+        &mut self,
+        indentation: &str,
+        args: &[(String, BindingType)],
+    ) -> Result<()> {
+        if args.is_empty() {
+            return writeln!(self.out, "{}[],", indentation);
+        }
+        writeln!(self.out, "{}[", indentation)?;
+        for (name, ty) in args {
+            writeln!(
+                self.out,
+                "{}  serializeToBytes((serializer) => {}),",
+                indentation,
+                self.quote_serialize(ty, name)
+            )?;
+        }
+        writeln!(self.out, "{}],", indentation)
+    }
+
+    fn quote_type(&self, ty: &BindingType) -> String {
+        match ty {
+            BindingType::Bool => "boolean".into(),
+            BindingType::U8 | BindingType::U16 | BindingType::U32 => "number".into(),
+            BindingType::U64 | BindingType::U128 | BindingType::U256 => "bigint".into(),
+            BindingType::Address | BindingType::Object => "CreditChainTypes.AccountAddress".into(),
+            BindingType::String => "string".into(),
+            BindingType::Vector(ty) => match ty.as_ref() {
+                BindingType::U8 => "Uint8Array".into(),
+                BindingType::Option(_) => format!("({})[]", self.quote_type(ty)),
+                _ => format!("{}[]", self.quote_type(ty)),
+            },
+            BindingType::Option(ty) => format!("{} | null", self.quote_type(ty)),
+            BindingType::Struct(id, _) => {
+                format!(
+                    "{}.{}",
+                    self.module_names[&(id.address, id.module.clone())],
+                    id.name
+                )
+            },
+            BindingType::TypeParameter(_) => unreachable!("generic types are not supported"),
+        }
+    }
+
+    /// Quotes an expression serializing `value` of type `ty` with `serializer`.
+    fn quote_serialize(&self, ty: &BindingType, value: &str) -> String {
+        match ty {
+            BindingType::Bool => format!("serializer.serializeBool({})", value),
+            BindingType::U8 => format!("serializer.serializeU8({})", value),
+            BindingType::U16 => format!("serializer.serializeU16({})", value),
+            BindingType::U32 => format!("serializer.serializeU32({})", value),
+            BindingType::U64 => format!("serializer.serializeU64({})", value),
+            BindingType::U128 => format!("serializer.serializeU128({})", value),
+            BindingType::U256 => format!("serializeU256(serializer, {})", value),
+            BindingType::String => format!("serializer.serializeStr({})", value),
+            BindingType::Vector(ty) => match ty.as_ref() {
+                BindingType::U8 => format!("serializer.serializeBytes({})", value),
+                _ => format!(
+                    "serializeVector(serializer, {}, (e) => {})",
+                    value,
+                    self.quote_serialize(ty, "e")
+                ),
+            },
+            BindingType::Option(ty) => format!(
+                "serializeOption(serializer, {}, (e) => {})",
+                value,
+                self.quote_serialize(ty, "e")
+            ),
+            BindingType::Address | BindingType::Object | BindingType::Struct(..) => {
+                format!("{}.serialize(serializer)", value)
+            },
+            BindingType::TypeParameter(_) => unreachable!("generic types are not supported"),
+        }
+    }
+
+    /// Quotes an expression deserializing a value of type `ty` with `deserializer`.
+    fn quote_deserialize(&self, ty: &BindingType) -> String {
+        match ty {
+            BindingType::Bool => "deserializer.deserializeBool()".into(),
+            BindingType::U8 => "deserializer.deserializeU8()".into(),
+            BindingType::U16 => "deserializer.deserializeU16()".into(),
+            BindingType::U32 => "deserializer.deserializeU32()".into(),
+            BindingType::U64 => "deserializer.deserializeU64()".into(),
+            BindingType::U128 => "deserializer.deserializeU128()".into(),
+            BindingType::U256 => "deserializeU256(deserializer)".into(),
+            BindingType::Address | BindingType::Object => {
+                "CreditChainTypes.AccountAddress.deserialize(deserializer)".into()
+            },
+            BindingType::String => "deserializer.deserializeStr()".into(),
+            BindingType::Vector(ty) => match ty.as_ref() {
+                BindingType::U8 => "deserializer.deserializeBytes()".into(),
+                _ => format!(
+                    "deserializeVector(deserializer, () => {})",
+                    self.quote_deserialize(ty)
+                ),
+            },
+            BindingType::Option(ty) => format!(
+                "deserializeOption(deserializer, () => {})",
+                self.quote_deserialize(ty)
+            ),
+            BindingType::Struct(..) => format!("{}.deserialize(deserializer)", self.quote_type(ty)),
+            BindingType::TypeParameter(_) => unreachable!("generic types are not supported"),
+        }
+    }
+}
+
+/// Quotes a Move identifier as a TypeScript identifier, escaping reserved words.
+fn quote_identifier(ident: &str) -> String {
+    const RESERVED_WORDS: &[&str] = &[
+        "await",
+        "break",
+        "case",
+        "catch",
+        "class",
+        "const",
+        "continue",
+        "debugger",
+        "default",
+        "delete",
+        "do",
+        "else",
+        "enum",
+        "export",
+        "extends",
+        "false",
+        "finally",
+        "for",
+        "function",
+        "if",
+        "implements",
+        "import",
+        "in",
+        "instanceof",
+        "interface",
+        "let",
+        "new",
+        "null",
+        "package",
+        "private",
+        "protected",
+        "public",
+        "return",
+        "static",
+        "super",
+        "switch",
+        "this",
+        "throw",
+        "true",
+        "try",
+        "typeof",
+        "var",
+        "void",
+        "while",
+        "with",
+        "yield",
+    ];
+    if RESERVED_WORDS.contains(&ident) {
+        format!("{}_", ident)
+    } else {
+        ident.to_string()
+    }
+}
+
+/// Helpers shared by the generated builders and bindings.
+const HELPERS: &str = r#"
+/** A BCS view request, to be posted to the `view` endpoint of the REST API. */
+export class ViewFunction {
+  constructor(
+    public module: CreditChainTypes.ModuleId,
+    public function_: CreditChainTypes.Identifier,
+    public tyArgs: CreditChainTypes.TypeTag[],
+    public args: Uint8Array[],
+  ) {}
+
+  public serialize(serializer: Serializer): void {
+    this.module.serialize(serializer);
+    this.function_.serialize(serializer);
+    serializeVector(serializer, this.tyArgs, (e) => e.serialize(serializer));
+    serializeVector(serializer, this.args, (e) => serializer.serializeBytes(e));
+  }
+
+  public bcsSerialize(): Uint8Array {
+    return serializeToBytes((serializer) => this.serialize(serializer));
+  }
+}
+
+function moduleId(address: string, name: string): CreditChainTypes.ModuleId {
+  const bytes = address.match(/../g)!.map((byte) => [parseInt(byte, 16)] as [number]);
+  return new CreditChainTypes.ModuleId(
+    new CreditChainTypes.AccountAddress(bytes),
+    new CreditChainTypes.Identifier(name),
+  );
+}
+
+function serializeToBytes(serialize: (serializer: Serializer) => void): Uint8Array {
+  const serializer = new BcsSerializer();
+  serialize(serializer);
+  return serializer.getBytes();
+}
+
+function serializeVector<T>(
+  serializer: Serializer,
+  values: T[],
+  serializeElement: (value: T) => void,
+): void {
+  serializer.serializeLen(values.length);
+  for (const value of values) {
+    serializeElement(value);
+  }
+}
+
+function deserializeVector<T>(deserializer: Deserializer, deserializeElement: () => T): T[] {
+  const length = deserializer.deserializeLen();
+  const values: T[] = [];
+  for (let i = 0; i < length; i++) {
+    values.push(deserializeElement());
+  }
+  return values;
+}
+
+function serializeOption<T>(
+  serializer: Serializer,
+  value: T | null,
+  serializeElement: (value: T) => void,
+): void {
+  serializer.serializeOptionTag(value !== null);
+  if (value !== null) {
+    serializeElement(value);
+  }
+}
+
+function deserializeOption<T>(deserializer: Deserializer, deserializeElement: () => T): T | null {
+  return deserializer.deserializeOptionTag() ? deserializeElement() : null;
+}
+
+function serializeU256(serializer: Serializer, value: bigint): void {
+  for (let i = 0; i < 32; i++) {
+    serializer.serializeU8(Number((value >> BigInt(8 * i)) & BigInt(0xff)));
+  }
+}
+
+function deserializeU256(deserializer: Deserializer): bigint {
+  let value = BigInt(0);
+  for (let i = 0; i < 32; i++) {
+    value |= BigInt(deserializer.deserializeU8()) << BigInt(8 * i);
+  }
+  return value;
+}
+
+function expectViewValues(deserializer: Deserializer, count: number): void {
+  const length = deserializer.deserializeLen();
+  if (length !== count) {
+    throw new Error(`expected ${count} return values, got ${length}`);
+  }
+}
+"#;
+
+pub struct Installer {
+    install_dir: PathBuf,
+    serde_module_path: Option<String>,
+    creditchain_module_path: Option<String>,
+}
+
+impl Installer {
+    pub fn new(
+        install_dir: PathBuf,
+        serde_module_path: Option<String>,
+        creditchain_module_path: Option<String>,
+    ) -> Self {
+        Installer {
+            install_dir,
+            serde_module_path,
+            creditchain_module_path,
+        }
+    }
+}
+
+impl crate::SourceInstaller for Installer {
+    type Error = Box<dyn std::error::Error>;
+
+    fn install_transaction_builders(
+        &self,
+        name: &str,
+        abis: &[EntryABI],
+    ) -> std::result::Result<(), Self::Error> {
+        let dir_path = self.install_dir.join(name);
+        std::fs::create_dir_all(&dir_path)?;
+        let mut file = std::fs::File::create(dir_path.join("mod.ts"))?;
+        output(
+            &mut file,
+            self.serde_module_path.clone(),
+            self.creditchain_module_path.clone(),
+            abis,
+            &PackageBindings::default(),
+        )?;
+        Ok(())
+    }
+}
@@ -0,0 +1,65 @@
+// Copyright © A-p-t-o-s Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use creditchain_sdk_builder::bindings::{BindingType, PackageBindings, StructRef};
+use move_binary_format::access::ModuleAccess;
+use move_core_types::account_address::AccountAddress;
+
+fn coin_bindings() -> PackageBindings {
+    let modules = creditchain_cached_packages::head_release_bundle().compiled_modules();
+    let (package, _): (Vec<_>, Vec<_>) = modules
+        .iter()
+        .cloned()
+        .partition(|module| module.name().as_str() == "coin");
+    PackageBindings::new(&package, &modules)
+}
+
+fn coin_struct(name: &str) -> StructRef {
+    StructRef {
+        address: AccountAddress::ONE,
+        module: "coin".to_string(),
+        name: name.to_string(),
+    }
+}
+
+#[test]
+fn test_package_bindings() {
+    let bindings = coin_bindings();
+    let find = |name: &str| {
+        bindings
+            .structs
+            .iter()
+            .find(|s| s.id == coin_struct(name))
+            .unwrap()
+    };
+    assert!(find("CoinStore").is_resource);
+    assert!(find("Deposit").is_event);
+    // Structs of dependencies are included, but are neither resources nor events.
+    assert!(bindings.structs.iter().any(|s| s.id.module == "event"
+        && s.id.name == "EventHandle"
+        && !s.is_resource
+        && !s.is_event));
+
+    let balance = bindings.views.iter().find(|v| v.name == "balance").unwrap();
+    assert_eq!(balance.type_param_count, 1);
+    assert_eq!(balance.params, vec![BindingType::Address]);
+    assert_eq!(balance.returns, vec![BindingType::U64]);
+}
+
+#[test]
+fn test_rust_package_bindings() {
+    let mut out = Vec::new();
+    creditchain_sdk_builder::rust::output_package_bindings(&mut out, &coin_bindings()).unwrap();
+    let code = String::from_utf8(out).unwrap();
+    for expected in [
+        "pub mod coin {",
+        "pub struct CoinStore {",
+        "pub coin: Coin,",
+        "pub deposit_events: super::event::EventHandle,",
+        "pub const DEPOSIT_EVENT_TYPE: &str = \"0x1::coin::Deposit\";",
+        "pub fn balance_view(type_arg_0: TypeTag, arg_0: AccountAddress) -> ViewFunction {",
+        "pub fn decode_balance_view(bytes: &[u8]) -> bcs::Result<u64> {",
+    ] {
+        assert!(code.contains(expected), "missing `{}` in:\n{}", expected, code);
+    }
+}
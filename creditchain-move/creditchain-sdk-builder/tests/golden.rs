@@ -0,0 +1,180 @@
+// Copyright © A-p-t-o-s Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use creditchain_sdk_builder::bindings::{
+    BindingType, FieldBinding, PackageBindings, StructBinding, StructLayout, StructRef,
+    VariantBinding, ViewFunctionBinding,
+};
+use creditchain_types::transaction::{ArgumentABI, EntryABI, EntryFunctionABI, TypeArgumentABI};
+use goldenfile::Mint;
+use move_core_types::{
+    account_address::AccountAddress,
+    identifier::Identifier,
+    language_storage::{ModuleId, StructTag, TypeTag},
+};
+use std::str::FromStr;
+
+const GOLDEN_DIR_PATH: &str = "tests/goldens";
+
+fn entry_function(
+    module: &str,
+    name: &str,
+    doc: &str,
+    ty_args: &[&str],
+    args: &[(&str, TypeTag)],
+) -> EntryABI {
+    EntryABI::EntryFunction(EntryFunctionABI::new(
+        name.to_string(),
+        ModuleId::new(AccountAddress::ONE, Identifier::new(module).unwrap()),
+        doc.to_string(),
+        ty_args
+            .iter()
+            .map(|name| TypeArgumentABI::new(name.to_string()))
+            .collect(),
+        args.iter()
+            .map(|(name, type_tag)| ArgumentABI::new(name.to_string(), type_tag.clone()))
+            .collect(),
+    ))
+}
+
+fn abis() -> Vec<EntryABI> {
+    let string = TypeTag::Struct(Box::new(StructTag::from_str("0x1::string::String").unwrap()));
+    vec![
+        entry_function(
+            "coin",
+            "transfer",
+            "Transfers `amount` of coins `CoinType` to `to`.",
+            &["CoinType"],
+            &[("to", TypeTag::Address), ("amount", TypeTag::U64)],
+        ),
+        entry_function("code", "publish_package_txn", "", &[], &[
+            ("metadata_serialized", TypeTag::Vector(Box::new(TypeTag::U8))),
+            ("code", TypeTag::Vector(Box::new(TypeTag::Vector(Box::new(TypeTag::U8))))),
+        ]),
+        entry_function(
+            "profile",
+            "update",
+            "Updates the profile.\n\nThe score is stored as is.",
+            &[],
+            &[
+                ("name", string),
+                ("score", TypeTag::U256),
+                ("scores", TypeTag::Vector(Box::new(TypeTag::U256))),
+            ],
+        ),
+    ]
+}
+
+fn profile_struct(name: &str) -> StructRef {
+    StructRef {
+        address: AccountAddress::ONE,
+        module: "profile".to_string(),
+        name: name.to_string(),
+    }
+}
+
+fn field(name: &str, ty: BindingType) -> FieldBinding {
+    FieldBinding {
+        name: name.to_string(),
+        ty,
+    }
+}
+
+fn view(
+    name: &str,
+    type_param_count: usize,
+    params: Vec<BindingType>,
+    returns: Vec<BindingType>,
+) -> ViewFunctionBinding {
+    ViewFunctionBinding {
+        address: AccountAddress::ONE,
+        module: "profile".to_string(),
+        name: name.to_string(),
+        type_param_count,
+        params,
+        returns,
+    }
+}
+
+fn bindings() -> PackageBindings {
+    let badge = BindingType::Struct(profile_struct("Badge"), vec![]);
+    let choice = BindingType::Struct(profile_struct("Choice"), vec![]);
+    PackageBindings {
+        structs: vec![
+            StructBinding {
+                id: profile_struct("Badge"),
+                type_params: vec![],
+                layout: StructLayout::Struct(vec![
+                    field("id", BindingType::U64),
+                    field("label", BindingType::Vector(Box::new(BindingType::U8))),
+                ]),
+                is_resource: false,
+                is_event: false,
+            },
+            StructBinding {
+                id: profile_struct("Choice"),
+                type_params: vec![],
+                layout: StructLayout::Enum(vec![
+                    VariantBinding {
+                        name: "Yes".to_string(),
+                        fields: vec![],
+                    },
+                    VariantBinding {
+                        name: "No".to_string(),
+                        fields: vec![field("reason", BindingType::String)],
+                    },
+                ]),
+                is_resource: false,
+                is_event: false,
+            },
+            StructBinding {
+                id: profile_struct("Profile"),
+                type_params: vec![],
+                layout: StructLayout::Struct(vec![
+                    field("name", BindingType::String),
+                    field("level", BindingType::U8),
+                    field("badges", BindingType::Vector(Box::new(badge))),
+                    field("owner", BindingType::Option(Box::new(BindingType::Address))),
+                ]),
+                is_resource: true,
+                is_event: false,
+            },
+            StructBinding {
+                id: profile_struct("ProfileUpdated"),
+                type_params: vec![],
+                layout: StructLayout::Struct(vec![
+                    field("owner", BindingType::Address),
+                    field("level", BindingType::U8),
+                ]),
+                is_resource: false,
+                is_event: true,
+            },
+        ],
+        views: vec![
+            view("badge_count", 1, vec![BindingType::Address], vec![BindingType::U64]),
+            view("choice", 0, vec![BindingType::Address], vec![choice]),
+            view("level", 0, vec![BindingType::Address], vec![BindingType::U8]),
+            view("summary", 0, vec![BindingType::Address], vec![
+                BindingType::String,
+                BindingType::U64,
+                BindingType::Bool,
+            ]),
+        ],
+        skipped: vec![],
+    }
+}
+
+#[test]
+fn test_typescript_golden() {
+    let mut mint = Mint::new(GOLDEN_DIR_PATH);
+    let mut file = mint.new_goldenfile("typescript.ts").unwrap();
+    creditchain_sdk_builder::typescript::output(&mut file, None, None, &abis(), &bindings())
+        .unwrap();
+}
+
+#[test]
+fn test_python_golden() {
+    let mut mint = Mint::new(GOLDEN_DIR_PATH);
+    let mut file = mint.new_goldenfile("python.py").unwrap();
+    creditchain_sdk_builder::python::output(&mut file, None, None, &abis(), &bindings()).unwrap();
+}
@@ -0,0 +1,217 @@
+# Typed transaction builders and view function wrappers for Move packages.
+#
+# This code was generated by compiling known Move interfaces ("ABIs") and modules with the tool `creditchain-sdk-builder`.
+
+import typing
+from dataclasses import dataclass
+import serde_types as st
+import bcs
+import creditchain_types
+
+# Skipped 0x1::profile::update: argument `scores` has an unsupported type
+# Skipped 0x1::profile::Choice: enum types are not supported
+# Skipped 0x1::profile::choice: refers to the skipped type `0x1::profile::Choice`
+
+
+@dataclass(frozen=True)
+class ViewFunction:
+    """A BCS view request, to be posted to the `view` endpoint of the REST API."""
+
+    module: creditchain_types.ModuleId
+    function: creditchain_types.Identifier
+    ty_args: typing.Sequence[creditchain_types.TypeTag]
+    args: typing.Sequence[bytes]
+
+    def bcs_serialize(self) -> bytes:
+        return bcs.serialize(self, ViewFunction)
+
+
+def _module_id(address: str, name: str) -> creditchain_types.ModuleId:
+    return creditchain_types.ModuleId(
+        address=creditchain_types.AccountAddress(
+            value=tuple(st.uint8(byte) for byte in bytes.fromhex(address))
+        ),
+        name=creditchain_types.Identifier(value=name),
+    )
+
+
+def _decode_view_values(content: bytes, count: int, value_type: typing.Any) -> typing.Any:
+    length, shift = 0, 0
+    while True:
+        if not content:
+            raise st.DeserializationError("Unexpected end of input")
+        byte, content = content[0], content[1:]
+        length |= (byte & 0x7F) << shift
+        shift += 7
+        if byte < 0x80:
+            break
+    if length != count:
+        raise st.DeserializationError(f"Expected {count} return values, got {length}")
+    value = None
+    if value_type is not None:
+        value, content = bcs.deserialize(content, value_type)
+    if content:
+        raise st.DeserializationError("Some input couldn't be deserialized")
+    return value
+
+
+def encode_coin_transfer(
+    coin_type: creditchain_types.TypeTag,
+    to: creditchain_types.AccountAddress,
+    amount: st.uint64,
+) -> creditchain_types.TransactionPayload:
+    """
+    Transfers `amount` of coins `CoinType` to `to`.
+    """
+    return creditchain_types.TransactionPayload__EntryFunction(
+        value=creditchain_types.EntryFunction(
+            module=_module_id("0000000000000000000000000000000000000000000000000000000000000001", "coin"),
+            function=creditchain_types.Identifier(value="transfer"),
+            ty_args=[coin_type],
+            args=[
+                bcs.serialize(to, creditchain_types.AccountAddress),
+                bcs.serialize(amount, st.uint64),
+            ],
+        )
+    )
+
+
+def encode_code_publish_package_txn(
+    metadata_serialized: bytes,
+    code: typing.Sequence[bytes],
+) -> creditchain_types.TransactionPayload:
+    return creditchain_types.TransactionPayload__EntryFunction(
+        value=creditchain_types.EntryFunction(
+            module=_module_id("0000000000000000000000000000000000000000000000000000000000000001", "code"),
+            function=creditchain_types.Identifier(value="publish_package_txn"),
+            ty_args=[],
+            args=[
+                bcs.serialize(metadata_serialized, bytes),
+                bcs.serialize(code, typing.Sequence[bytes]),
+            ],
+        )
+    )
+
+
+@dataclass(frozen=True)
+class profile__Badge:
+    """Type `0x1::profile::Badge`."""
+
+    id: st.uint64
+    label: bytes
+
+    def bcs_serialize(self) -> bytes:
+        return bcs.serialize(self, profile__Badge)
+
+    @staticmethod
+    def bcs_deserialize(content: bytes) -> "profile__Badge":
+        value, remaining = bcs.deserialize(content, profile__Badge)
+        if remaining:
+            raise st.DeserializationError("Some input couldn't be deserialized")
+        return value
+
+
+@dataclass(frozen=True)
+class profile__Profile:
+    """Resource `0x1::profile::Profile`."""
+
+    name: str
+    level: st.uint8
+    badges: typing.Sequence[profile__Badge]
+    owner: typing.Optional[creditchain_types.AccountAddress]
+
+    def bcs_serialize(self) -> bytes:
+        return bcs.serialize(self, profile__Profile)
+
+    @staticmethod
+    def bcs_deserialize(content: bytes) -> "profile__Profile":
+        value, remaining = bcs.deserialize(content, profile__Profile)
+        if remaining:
+            raise st.DeserializationError("Some input couldn't be deserialized")
+        return value
+
+
+@dataclass(frozen=True)
+class profile__ProfileUpdated:
+    """Event `0x1::profile::ProfileUpdated`."""
+
+    owner: creditchain_types.AccountAddress
+    level: st.uint8
+
+    def bcs_serialize(self) -> bytes:
+        return bcs.serialize(self, profile__ProfileUpdated)
+
+    @staticmethod
+    def bcs_deserialize(content: bytes) -> "profile__ProfileUpdated":
+        value, remaining = bcs.deserialize(content, profile__ProfileUpdated)
+        if remaining:
+            raise st.DeserializationError("Some input couldn't be deserialized")
+        return value
+
+
+# Type of the event `0x1::profile::ProfileUpdated`, without type arguments.
+PROFILE_PROFILE_UPDATED_EVENT_TYPE = "0x1::profile::ProfileUpdated"
+
+
+def profile_badge_count_view(
+    type_arg_0: creditchain_types.TypeTag,
+    arg_0: creditchain_types.AccountAddress,
+) -> ViewFunction:
+    """Builds a BCS view request for the view function `0x1::profile::badge_count`."""
+    return ViewFunction(
+        module=_module_id("0000000000000000000000000000000000000000000000000000000000000001", "profile"),
+        function=creditchain_types.Identifier(value="badge_count"),
+        ty_args=[type_arg_0],
+        args=[
+            bcs.serialize(arg_0, creditchain_types.AccountAddress),
+        ],
+    )
+
+
+def decode_profile_badge_count_view(
+    content: bytes,
+) -> st.uint64:
+    """Decodes the BCS-encoded return values of the view function `0x1::profile::badge_count`."""
+    return _decode_view_values(content, 1, st.uint64)
+
+
+def profile_level_view(
+    arg_0: creditchain_types.AccountAddress,
+) -> ViewFunction:
+    """Builds a BCS view request for the view function `0x1::profile::level`."""
+    return ViewFunction(
+        module=_module_id("0000000000000000000000000000000000000000000000000000000000000001", "profile"),
+        function=creditchain_types.Identifier(value="level"),
+        ty_args=[],
+        args=[
+            bcs.serialize(arg_0, creditchain_types.AccountAddress),
+        ],
+    )
+
+
+def decode_profile_level_view(
+    content: bytes,
+) -> st.uint8:
+    """Decodes the BCS-encoded return values of the view function `0x1::profile::level`."""
+    return _decode_view_values(content, 1, st.uint8)
+
+
+def profile_summary_view(
+    arg_0: creditchain_types.AccountAddress,
+) -> ViewFunction:
+    """Builds a BCS view request for the view function `0x1::profile::summary`."""
+    return ViewFunction(
+        module=_module_id("0000000000000000000000000000000000000000000000000000000000000001", "profile"),
+        function=creditchain_types.Identifier(value="summary"),
+        ty_args=[],
+        args=[
+            bcs.serialize(arg_0, creditchain_types.AccountAddress),
+        ],
+    )
+
+
+def decode_profile_summary_view(
+    content: bytes,
+) -> typing.Tuple[str, st.uint64, bool]:
+    """Decodes the BCS-encoded return values of the view function `0x1::profile::summary`."""
+    return _decode_view_values(content, 3, typing.Tuple[str, st.uint64, bool])
@@ -0,0 +1,298 @@
+// Typed transaction builders and view function wrappers for Move packages.
+//
+// This code was generated by compiling known Move interfaces ("ABIs") and modules with the tool `creditchain-sdk-builder`.
+
+import { Serializer, Deserializer } from "../serde/mod.ts";
+import { BcsSerializer, BcsDeserializer } from "../bcs/mod.ts";
+import * as CreditChainTypes from "../creditchainTypes/mod.ts";
+
+// Skipped 0x1::profile::Choice: enum types are not supported
+// Skipped 0x1::profile::choice: refers to the skipped type `0x1::profile::Choice`
+
+/** A BCS view request, to be posted to the `view` endpoint of the REST API. */
+export class ViewFunction {
+  constructor(
+    public module: CreditChainTypes.ModuleId,
+    public function_: CreditChainTypes.Identifier,
+    public tyArgs: CreditChainTypes.TypeTag[],
+    public args: Uint8Array[],
+  ) {}
+
+  public serialize(serializer: Serializer): void {
+    this.module.serialize(serializer);
+    this.function_.serialize(serializer);
+    serializeVector(serializer, this.tyArgs, (e) => e.serialize(serializer));
+    serializeVector(serializer, this.args, (e) => serializer.serializeBytes(e));
+  }
+
+  public bcsSerialize(): Uint8Array {
+    return serializeToBytes((serializer) => this.serialize(serializer));
+  }
+}
+
+function moduleId(address: string, name: string): CreditChainTypes.ModuleId {
+  const bytes = address.match(/../g)!.map((byte) => [parseInt(byte, 16)] as [number]);
+  return new CreditChainTypes.ModuleId(
+    new CreditChainTypes.AccountAddress(bytes),
+    new CreditChainTypes.Identifier(name),
+  );
+}
+
+function serializeToBytes(serialize: (serializer: Serializer) => void): Uint8Array {
+  const serializer = new BcsSerializer();
+  serialize(serializer);
+  return serializer.getBytes();
+}
+
+function serializeVector<T>(
+  serializer: Serializer,
+  values: T[],
+  serializeElement: (value: T) => void,
+): void {
+  serializer.serializeLen(values.length);
+  for (const value of values) {
+    serializeElement(value);
+  }
+}
+
+function deserializeVector<T>(deserializer: Deserializer, deserializeElement: () => T): T[] {
+  const length = deserializer.deserializeLen();
+  const values: T[] = [];
+  for (let i = 0; i < length; i++) {
+    values.push(deserializeElement());
+  }
+  return values;
+}
+
+function serializeOption<T>(
+  serializer: Serializer,
+  value: T | null,
+  serializeElement: (value: T) => void,
+): void {
+  serializer.serializeOptionTag(value !== null);
+  if (value !== null) {
+    serializeElement(value);
+  }
+}
+
+function deserializeOption<T>(deserializer: Deserializer, deserializeElement: () => T): T | null {
+  return deserializer.deserializeOptionTag() ? deserializeElement() : null;
+}
+
+function serializeU256(serializer: Serializer, value: bigint): void {
+  for (let i = 0; i < 32; i++) {
+    serializer.serializeU8(Number((value >> BigInt(8 * i)) & BigInt(0xff)));
+  }
+}
+
+function deserializeU256(deserializer: Deserializer): bigint {
+  let value = BigInt(0);
+  for (let i = 0; i < 32; i++) {
+    value |= BigInt(deserializer.deserializeU8()) << BigInt(8 * i);
+  }
+  return value;
+}
+
+function expectViewValues(deserializer: Deserializer, count: number): void {
+  const length = deserializer.deserializeLen();
+  if (length !== count) {
+    throw new Error(`expected ${count} return values, got ${length}`);
+  }
+}
+
+/**
+ * Transfers `amount` of coins `CoinType` to `to`.
+ */
+export function encodeCoinTransfer(
+  coinType: CreditChainTypes.TypeTag,
+  to: CreditChainTypes.AccountAddress,
+  amount: bigint,
+): CreditChainTypes.TransactionPayload {
+  return new CreditChainTypes.TransactionPayloadVariantEntryFunction(
+    new CreditChainTypes.EntryFunction(
+      moduleId("0000000000000000000000000000000000000000000000000000000000000001", "coin"),
+      new CreditChainTypes.Identifier("transfer"),
+      [coinType],
+      [
+        serializeToBytes((serializer) => to.serialize(serializer)),
+        serializeToBytes((serializer) => serializer.serializeU64(amount)),
+      ],
+    ),
+  );
+}
+
+export function encodeCodePublishPackageTxn(
+  metadataSerialized: Uint8Array,
+  code: Uint8Array[],
+): CreditChainTypes.TransactionPayload {
+  return new CreditChainTypes.TransactionPayloadVariantEntryFunction(
+    new CreditChainTypes.EntryFunction(
+      moduleId("0000000000000000000000000000000000000000000000000000000000000001", "code"),
+      new CreditChainTypes.Identifier("publish_package_txn"),
+      [],
+      [
+        serializeToBytes((serializer) => serializer.serializeBytes(metadataSerialized)),
+        serializeToBytes((serializer) => serializeVector(serializer, code, (e) => serializer.serializeBytes(e))),
+      ],
+    ),
+  );
+}
+
+/**
+ * Updates the profile.
+ *
+ * The score is stored as is.
+ */
+export function encodeProfileUpdate(
+  name: string,
+  score: bigint,
+  scores: bigint[],
+): CreditChainTypes.TransactionPayload {
+  return new CreditChainTypes.TransactionPayloadVariantEntryFunction(
+    new CreditChainTypes.EntryFunction(
+      moduleId("0000000000000000000000000000000000000000000000000000000000000001", "profile"),
+      new CreditChainTypes.Identifier("update"),
+      [],
+      [
+        serializeToBytes((serializer) => serializer.serializeStr(name)),
+        serializeToBytes((serializer) => serializeU256(serializer, score)),
+        serializeToBytes((serializer) => serializeVector(serializer, scores, (e) => serializeU256(serializer, e))),
+      ],
+    ),
+  );
+}
+
+export namespace profile {
+  /** Type `0x1::profile::Badge`. */
+  export class Badge {
+    constructor(
+      public id: bigint,
+      public label: Uint8Array,
+    ) {}
+
+    public serialize(serializer: Serializer): void {
+      serializer.serializeU64(this.id);
+      serializer.serializeBytes(this.label);
+    }
+
+    static deserialize(deserializer: Deserializer): Badge {
+      return new Badge(
+        deserializer.deserializeU64(),
+        deserializer.deserializeBytes(),
+      );
+    }
+  }
+
+  /** Resource `0x1::profile::Profile`. */
+  export class Profile {
+    constructor(
+      public name: string,
+      public level: number,
+      public badges: profile.Badge[],
+      public owner: CreditChainTypes.AccountAddress | null,
+    ) {}
+
+    public serialize(serializer: Serializer): void {
+      serializer.serializeStr(this.name);
+      serializer.serializeU8(this.level);
+      serializeVector(serializer, this.badges, (e) => e.serialize(serializer));
+      serializeOption(serializer, this.owner, (e) => e.serialize(serializer));
+    }
+
+    static deserialize(deserializer: Deserializer): Profile {
+      return new Profile(
+        deserializer.deserializeStr(),
+        deserializer.deserializeU8(),
+        deserializeVector(deserializer, () => profile.Badge.deserialize(deserializer)),
+        deserializeOption(deserializer, () => CreditChainTypes.AccountAddress.deserialize(deserializer)),
+      );
+    }
+  }
+
+  /** Event `0x1::profile::ProfileUpdated`. */
+  export class ProfileUpdated {
+    constructor(
+      public owner: CreditChainTypes.AccountAddress,
+      public level: number,
+    ) {}
+
+    public serialize(serializer: Serializer): void {
+      this.owner.serialize(serializer);
+      serializer.serializeU8(this.level);
+    }
+
+    static deserialize(deserializer: Deserializer): ProfileUpdated {
+      return new ProfileUpdated(
+        CreditChainTypes.AccountAddress.deserialize(deserializer),
+        deserializer.deserializeU8(),
+      );
+    }
+  }
+
+  /** Type of the event `0x1::profile::ProfileUpdated`, without type arguments. */
+  export const PROFILE_UPDATED_EVENT_TYPE = "0x1::profile::ProfileUpdated";
+
+  /** Builds a BCS view request for the view function `0x1::profile::badge_count`. */
+  export function badgeCountView(
+    typeArg0: CreditChainTypes.TypeTag,
+    arg0: CreditChainTypes.AccountAddress,
+  ): ViewFunction {
+    return new ViewFunction(
+      moduleId("0000000000000000000000000000000000000000000000000000000000000001", "profile"),
+      new CreditChainTypes.Identifier("badge_count"),
+      [typeArg0],
+      [
+        serializeToBytes((serializer) => arg0.serialize(serializer)),
+      ],
+    );
+  }
+
+  /** Decodes the BCS-encoded return values of the view function `0x1::profile::badge_count`. */
+  export function decodeBadgeCountView(bytes: Uint8Array): bigint {
+    const deserializer = new BcsDeserializer(bytes);
+    expectViewValues(deserializer, 1);
+    return deserializer.deserializeU64();
+  }
+
+  /** Builds a BCS view request for the view function `0x1::profile::level`. */
+  export function levelView(
+    arg0: CreditChainTypes.AccountAddress,
+  ): ViewFunction {
+    return new ViewFunction(
+      moduleId("0000000000000000000000000000000000000000000000000000000000000001", "profile"),
+      new CreditChainTypes.Identifier("level"),
+      [],
+      [
+        serializeToBytes((serializer) => arg0.serialize(serializer)),
+      ],
+    );
+  }
+
+  /** Decodes the BCS-encoded return values of the view function `0x1::profile::level`. */
+  export function decodeLevelView(bytes: Uint8Array): number {
+    const deserializer = new BcsDeserializer(bytes);
+    expectViewValues(deserializer, 1);
+    return deserializer.deserializeU8();
+  }
+
+  /** Builds a BCS view request for the view function `0x1::profile::summary`. */
+  export function summaryView(
+    arg0: CreditChainTypes.AccountAddress,
+  ): ViewFunction {
+    return new ViewFunction(
+      moduleId("0000000000000000000000000000000000000000000000000000000000000001", "profile"),
+      new CreditChainTypes.Identifier("summary"),
+      [],
+      [
+        serializeToBytes((serializer) => arg0.serialize(serializer)),
+      ],
+    );
+  }
+
+  /** Decodes the BCS-encoded return values of the view function `0x1::profile::summary`. */
+  export function decodeSummaryView(bytes: Uint8Array): [string, bigint, boolean] {
+    const deserializer = new BcsDeserializer(bytes);
+    expectViewValues(deserializer, 3);
+    return [deserializer.deserializeStr(), deserializer.deserializeU64(), deserializer.deserializeBool()];
+  }
+}
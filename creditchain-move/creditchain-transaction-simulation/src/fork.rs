@@ -0,0 +1,529 @@
+// Copyright © A-p-t-o-s Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! Fork sessions, for simulating transactions on top of a real chain state.
+//!
+//! A [`ForkSession`] pins a version of a remote chain, accessed through a REST endpoint or a
+//! local DB, and lazily fetches state values as they are read. Locally executed transactions are
+//! stacked on top of the remote state. Both the fetched values and the local changes are stored
+//! in the session directory, so that a session can be re-opened later without re-fetching the
+//! state from the remote chain.
+
+use crate::{Account, AccountData, DeltaStateStore, SimulationStateStore};
+use anyhow::{anyhow, bail, ensure, Context, Result};
+use creditchain_block_executor::txn_provider::default::DefaultTxnProvider;
+use creditchain_rest_client::Client;
+use creditchain_types::{
+    account_address::AccountAddress,
+    account_config::{
+        primary_apt_store, CoinStoreResource, ConcurrentFungibleBalanceResource,
+        FungibleStoreResource, ObjectGroupResource,
+    },
+    on_chain_config::CurrentTimeMicroseconds,
+    state_store::{
+        state_key::StateKey, state_storage_usage::StateStorageUsage, state_value::StateValue,
+        StateViewId, StateViewResult, TStateView,
+    },
+    transaction::{SignedTransaction, Transaction, TransactionOutput, TransactionStatus, Version},
+    CreditChainCoinType,
+};
+use creditchain_validator_interface::{
+    CreditChainValidatorInterface, DBDebuggerInterface, DebuggerStateView, RestDebuggerInterface,
+};
+use creditchain_vm::{creditchain_vm::CreditChainVMBlockExecutor, VMBlockExecutor};
+use move_core_types::{language_storage::StructTag, move_resource::MoveStructType};
+use parking_lot::RwLock;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::{
+    collections::{BTreeMap, HashMap},
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::Duration,
+};
+use url::Url;
+
+const FORK_CONFIG_FILE: &str = "fork_config.bcs";
+const REMOTE_CACHE_FILE: &str = "remote_cache.bcs";
+const SESSION_STATE_FILE: &str = "session_state.bcs";
+
+/***************************************************************************************************
+ * Fork Source
+ *
+ **************************************************************************************************/
+/// The remote chain a session is forked from.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub enum ForkSource {
+    /// Endpoint of a node's REST API.
+    Rest(Url),
+    /// Path to a local DB.
+    Db(PathBuf),
+}
+
+impl ForkSource {
+    fn connect(&self) -> Result<Arc<dyn CreditChainValidatorInterface + Send>> {
+        Ok(match self {
+            Self::Rest(url) => Arc::new(RestDebuggerInterface::new(Client::new(url.clone()))),
+            Self::Db(path) => Arc::new(DBDebuggerInterface::open(path)?),
+        })
+    }
+}
+
+/// Configuration of a session, stored in the session directory.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+struct ForkConfig {
+    /// [`None`] if the session was created from a custom validator interface.
+    source: Option<ForkSource>,
+    version: Version,
+}
+
+/// Local changes of a session, stored in the session directory.
+#[derive(Default, Deserialize, Serialize)]
+struct SessionState {
+    delta: HashMap<StateKey, Option<StateValue>>,
+    snapshots: Vec<HashMap<StateKey, Option<StateValue>>>,
+}
+
+/***************************************************************************************************
+ * Fork State View
+ *
+ **************************************************************************************************/
+/// A state view of the remote chain at the pinned version.
+///
+/// State values are fetched lazily and cached, so that every value is fetched at most once per
+/// session directory.
+pub struct ForkStateView {
+    remote: DebuggerStateView,
+    version: Version,
+    cache: RwLock<HashMap<StateKey, Option<StateValue>>>,
+}
+
+impl ForkStateView {
+    /// Creates a view of the state right after the transaction at `version` has been committed.
+    ///
+    /// Must be called from within a Tokio runtime.
+    fn new(
+        interface: Arc<dyn CreditChainValidatorInterface + Send>,
+        version: Version,
+        cache: HashMap<StateKey, Option<StateValue>>,
+    ) -> Self {
+        Self {
+            // The debugger state view reads the state right before the given version.
+            remote: DebuggerStateView::new(interface, version + 1),
+            version,
+            cache: RwLock::new(cache),
+        }
+    }
+
+    /// Returns the pinned version.
+    pub fn version(&self) -> Version {
+        self.version
+    }
+
+    /// Returns the number of state values fetched from the remote chain so far.
+    pub fn num_cached_values(&self) -> usize {
+        self.cache.read().len()
+    }
+}
+
+impl TStateView for ForkStateView {
+    type Key = StateKey;
+
+    fn id(&self) -> StateViewId {
+        StateViewId::Miscellaneous
+    }
+
+    fn get_state_value(&self, state_key: &Self::Key) -> StateViewResult<Option<StateValue>> {
+        if let Some(value) = self.cache.read().get(state_key) {
+            return Ok(value.clone());
+        }
+
+        let value = self.remote.get_state_value(state_key)?;
+        self.cache.write().insert(state_key.clone(), value.clone());
+        Ok(value)
+    }
+
+    fn get_usage(&self) -> StateViewResult<StateStorageUsage> {
+        Ok(StateStorageUsage::Untracked)
+    }
+
+    fn next_version(&self) -> Version {
+        self.version + 1
+    }
+}
+
+/***************************************************************************************************
+ * Fork Session
+ *
+ **************************************************************************************************/
+/// Identifies a snapshot of the local changes of a [`ForkSession`].
+pub type SnapshotId = usize;
+
+/// A persistent simulation session on top of a remote chain state, pinned at a version.
+///
+/// The session state is only written to disk when [`ForkSession::persist`] is called. Sessions
+/// must be created and opened from within a multi-threaded Tokio runtime, which is used to fetch
+/// remote state values.
+pub struct ForkSession {
+    dir: PathBuf,
+    config: ForkConfig,
+    state_store: DeltaStateStore<ForkStateView>,
+    snapshots: Vec<HashMap<StateKey, Option<StateValue>>>,
+}
+
+impl ForkSession {
+    /// Creates a new session in `dir`, forked from the given source. If no version is provided,
+    /// the latest version of the remote chain is used.
+    pub async fn new(
+        dir: impl AsRef<Path>,
+        source: ForkSource,
+        version: Option<Version>,
+    ) -> Result<Self> {
+        let interface = source.connect()?;
+        Self::create(dir.as_ref(), Some(source), interface, version).await
+    }
+
+    /// Creates a new session in `dir`, forked from a custom validator interface. Sessions created
+    /// this way must be re-opened with [`ForkSession::open_with_interface`].
+    pub async fn new_with_interface(
+        dir: impl AsRef<Path>,
+        interface: Arc<dyn CreditChainValidatorInterface + Send>,
+        version: Option<Version>,
+    ) -> Result<Self> {
+        Self::create(dir.as_ref(), None, interface, version).await
+    }
+
+    /// Re-opens a session previously persisted in `dir`.
+    pub fn open(dir: impl AsRef<Path>) -> Result<Self> {
+        let dir = dir.as_ref();
+        let config: ForkConfig = read_bcs_file(&dir.join(FORK_CONFIG_FILE))?;
+        let interface = match &config.source {
+            Some(source) => source.connect()?,
+            None => bail!(
+                "Session at {} was created from a custom interface, use open_with_interface",
+                dir.display()
+            ),
+        };
+        Self::load(dir, config, interface)
+    }
+
+    /// Re-opens a session previously persisted in `dir`, fetching remote state values from the
+    /// given validator interface.
+    pub fn open_with_interface(
+        dir: impl AsRef<Path>,
+        interface: Arc<dyn CreditChainValidatorInterface + Send>,
+    ) -> Result<Self> {
+        let dir = dir.as_ref();
+        let config = read_bcs_file(&dir.join(FORK_CONFIG_FILE))?;
+        Self::load(dir, config, interface)
+    }
+
+    async fn create(
+        dir: &Path,
+        source: Option<ForkSource>,
+        interface: Arc<dyn CreditChainValidatorInterface + Send>,
+        version: Option<Version>,
+    ) -> Result<Self> {
+        ensure!(
+            !dir.join(FORK_CONFIG_FILE).exists(),
+            "A session already exists at {}",
+            dir.display()
+        );
+        std::fs::create_dir_all(dir)
+            .with_context(|| format!("Failed to create session directory {}", dir.display()))?;
+
+        let latest_version = interface.get_latest_ledger_info_version().await?;
+        let version = version.unwrap_or(latest_version);
+        ensure!(
+            version <= latest_version,
+            "Cannot fork at version {}, the latest version is {}",
+            version,
+            latest_version
+        );
+
+        let config = ForkConfig { source, version };
+        write_bcs_file(&dir.join(FORK_CONFIG_FILE), &config)?;
+
+        let session = Self {
+            dir: dir.to_path_buf(),
+            config,
+            state_store: DeltaStateStore::new_with_base(ForkStateView::new(
+                interface,
+                version,
+                HashMap::new(),
+            )),
+            snapshots: vec![],
+        };
+        session.persist()?;
+        Ok(session)
+    }
+
+    fn load(
+        dir: &Path,
+        config: ForkConfig,
+        interface: Arc<dyn CreditChainValidatorInterface + Send>,
+    ) -> Result<Self> {
+        let cache = read_bcs_file(&dir.join(REMOTE_CACHE_FILE))?;
+        let state: SessionState = read_bcs_file(&dir.join(SESSION_STATE_FILE))?;
+
+        let state_store =
+            DeltaStateStore::new_with_base(ForkStateView::new(interface, config.version, cache));
+        state_store.set_delta(state.delta);
+
+        Ok(Self {
+            dir: dir.to_path_buf(),
+            config,
+            state_store,
+            snapshots: state.snapshots,
+        })
+    }
+
+    /// Writes the fetched remote state values and the local changes to the session directory.
+    pub fn persist(&self) -> Result<()> {
+        write_bcs_file(
+            &self.dir.join(REMOTE_CACHE_FILE),
+            &*self.state_store.base().cache.read(),
+        )?;
+        write_bcs_file(&self.dir.join(SESSION_STATE_FILE), &SessionState {
+            delta: self.state_store.delta(),
+            snapshots: self.snapshots.clone(),
+        })
+    }
+
+    /// Returns the version of the remote chain the session is forked from.
+    pub fn version(&self) -> Version {
+        self.config.version
+    }
+
+    /// Returns the session state, which can be used to read and modify state values directly.
+    pub fn state_store(&self) -> &DeltaStateStore<ForkStateView> {
+        &self.state_store
+    }
+
+    /// Executes the transactions as a block on top of the session state, and applies the outputs
+    /// of the kept transactions.
+    pub fn execute_transactions(
+        &self,
+        txns: Vec<SignedTransaction>,
+    ) -> Result<Vec<TransactionOutput>> {
+        let txns = txns
+            .into_iter()
+            .map(|txn| Transaction::UserTransaction(txn).into())
+            .collect();
+        let txn_provider = DefaultTxnProvider::new_without_info(txns);
+        let outputs = CreditChainVMBlockExecutor::new()
+            .execute_block_no_limit(&txn_provider, &self.state_store)
+            .map_err(|status| anyhow!("Failed to execute transactions: {:?}", status))?;
+
+        for output in &outputs {
+            if let TransactionStatus::Keep(_) = output.status() {
+                self.state_store.apply_write_set(output.write_set())?;
+            }
+        }
+        Ok(outputs)
+    }
+
+    /// Takes a snapshot of the local changes, which can be restored with
+    /// [`ForkSession::rollback`].
+    pub fn snapshot(&mut self) -> SnapshotId {
+        self.snapshots.push(self.state_store.delta());
+        self.snapshots.len() - 1
+    }
+
+    /// Restores the local changes to the given snapshot. Snapshots taken after it are discarded,
+    /// while the snapshot itself is kept so that it can be restored again.
+    pub fn rollback(&mut self, snapshot_id: SnapshotId) -> Result<()> {
+        let delta = self
+            .snapshots
+            .get(snapshot_id)
+            .cloned()
+            .ok_or_else(|| anyhow!("Snapshot {} does not exist", snapshot_id))?;
+        self.state_store.set_delta(delta);
+        self.snapshots.truncate(snapshot_id + 1);
+        Ok(())
+    }
+
+    /// Returns the on-chain block timestamp, in microseconds.
+    pub fn block_timestamp_usecs(&self) -> Result<u64> {
+        Ok(self
+            .state_store
+            .get_on_chain_config::<CurrentTimeMicroseconds>()?
+            .microseconds)
+    }
+
+    /// Sets the on-chain block timestamp. Note that the timestamp is not updated by executed
+    /// transactions, as no block prologue is executed.
+    pub fn set_block_timestamp_usecs(&self, timestamp_usecs: u64) -> Result<()> {
+        self.state_store
+            .modify_on_chain_config(|timestamp: &mut CurrentTimeMicroseconds| {
+                timestamp.microseconds = timestamp_usecs;
+                Ok(())
+            })
+    }
+
+    /// Moves the on-chain block timestamp forward by the given duration.
+    pub fn fast_forward(&self, duration: Duration) -> Result<()> {
+        let timestamp_usecs = self
+            .block_timestamp_usecs()?
+            .checked_add(duration.as_micros().try_into()?)
+            .ok_or_else(|| anyhow!("Block timestamp overflow"))?;
+        self.set_block_timestamp_usecs(timestamp_usecs)
+    }
+
+    /// Creates a new account with the given balance.
+    pub fn create_and_fund_account(&self, balance: u64) -> Result<AccountData> {
+        self.state_store
+            .store_and_fund_account(Account::new(), balance, 0)
+    }
+
+    /// Returns the balance of an existing account, held either in a coin store or in the primary
+    /// fungible store.
+    pub fn balance(&self, addr: AccountAddress) -> Result<u64> {
+        if let Some(coin_store) = self
+            .state_store
+            .get_resource::<CoinStoreResource<CreditChainCoinType>>(addr)?
+        {
+            return Ok(coin_store.coin());
+        }
+
+        let group = self.primary_store_group(addr)?;
+        if let Some(balance) = group.get(&ConcurrentFungibleBalanceResource::struct_tag()) {
+            return Ok(bcs::from_bytes::<ConcurrentFungibleBalanceResource>(balance)?.balance());
+        }
+        match group.get(&FungibleStoreResource::struct_tag()) {
+            Some(store) => Ok(bcs::from_bytes::<FungibleStoreResource>(store)?.balance()),
+            None => Ok(0),
+        }
+    }
+
+    /// Adds `amount` to the balance of an existing account. Note that the total supply is not
+    /// updated.
+    pub fn fund_account(&self, addr: AccountAddress, amount: u64) -> Result<()> {
+        let add = |balance: u64| {
+            balance
+                .checked_add(amount)
+                .ok_or_else(|| anyhow!("Balance of account {} overflows", addr))
+        };
+
+        if self
+            .state_store
+            .get_resource::<CoinStoreResource<CreditChainCoinType>>(addr)?
+            .is_some()
+        {
+            return self.state_store.modify_resource(
+                addr,
+                |coin_store: &mut CoinStoreResource<CreditChainCoinType>| {
+                    coin_store.set_coin(add(coin_store.coin())?);
+                    Ok(())
+                },
+            );
+        }
+
+        let mut group = self.primary_store_group(addr)?;
+        let concurrent_tag = ConcurrentFungibleBalanceResource::struct_tag();
+        let store_tag = FungibleStoreResource::struct_tag();
+        if let Some(bytes) = group.get_mut(&concurrent_tag) {
+            let balance = bcs::from_bytes::<ConcurrentFungibleBalanceResource>(bytes)?.balance();
+            *bytes = bcs::to_bytes(&ConcurrentFungibleBalanceResource::new(add(balance)?))?;
+        } else if let Some(bytes) = group.get_mut(&store_tag) {
+            let mut store = bcs::from_bytes::<FungibleStoreResource>(bytes)?;
+            store.balance = add(store.balance)?;
+            *bytes = bcs::to_bytes(&store)?;
+        } else {
+            bail!("Account {} has no coin store or primary fungible store", addr);
+        }
+
+        let state_key = primary_store_group_key(addr);
+        let state_value = self
+            .state_store
+            .get_state_value(&state_key)?
+            .ok_or_else(|| anyhow!("Primary fungible store of {} does not exist", addr))?
+            .map_bytes(|_| Ok(bcs::to_bytes(&group)?.into()))?;
+        self.state_store.set_state_value(state_key, state_value)
+    }
+
+    fn primary_store_group(&self, addr: AccountAddress) -> Result<BTreeMap<StructTag, Vec<u8>>> {
+        match self
+            .state_store
+            .get_state_value_bytes(&primary_store_group_key(addr))?
+        {
+            Some(bytes) => Ok(bcs::from_bytes(&bytes)?),
+            None => Ok(BTreeMap::new()),
+        }
+    }
+}
+
+fn primary_store_group_key(addr: AccountAddress) -> StateKey {
+    StateKey::resource_group(&primary_apt_store(addr), &ObjectGroupResource::struct_tag())
+}
+
+fn read_bcs_file<T: DeserializeOwned>(path: &Path) -> Result<T> {
+    let bytes =
+        std::fs::read(path).with_context(|| format!("Failed to read {}", path.display()))?;
+    bcs::from_bytes(&bytes).with_context(|| format!("Failed to deserialize {}", path.display()))
+}
+
+fn write_bcs_file<T: Serialize>(path: &Path, value: &T) -> Result<()> {
+    std::fs::write(path, bcs::to_bytes(value)?)
+        .with_context(|| format!("Failed to write {}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use creditchain_api_test_context::new_test_context;
+    use creditchain_config::config::NodeConfig;
+    use creditchain_types::transaction::ExecutionStatus;
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_fork_session() {
+        let mut context =
+            new_test_context("test_fork_session".to_string(), NodeConfig::default(), false);
+        let mut sender = context.create_account().await;
+        let receiver = context.gen_account();
+        let interface = Arc::new(DBDebuggerInterface::new(context.db.clone()));
+
+        let dir = tempfile::tempdir().unwrap();
+        let mut session = ForkSession::new_with_interface(dir.path(), interface.clone(), None)
+            .await
+            .unwrap();
+        let initial_balance = session.balance(sender.address()).unwrap();
+        assert!(initial_balance > 0);
+
+        // Funding and time travel are rolled back to the snapshot.
+        let snapshot_id = session.snapshot();
+        let timestamp_usecs = session.block_timestamp_usecs().unwrap();
+        session.fund_account(sender.address(), 1_000).unwrap();
+        session.fast_forward(Duration::from_secs(60)).unwrap();
+        assert_eq!(
+            session.balance(sender.address()).unwrap(),
+            initial_balance + 1_000
+        );
+        assert_eq!(
+            session.block_timestamp_usecs().unwrap(),
+            timestamp_usecs + 60_000_000
+        );
+        session.rollback(snapshot_id).unwrap();
+        assert_eq!(session.balance(sender.address()).unwrap(), initial_balance);
+        assert_eq!(session.block_timestamp_usecs().unwrap(), timestamp_usecs);
+
+        // Locally executed transactions are not committed to the remote chain.
+        let txn = context.account_transfer_to(&mut sender, receiver.address(), 100);
+        let outputs = session.execute_transactions(vec![txn]).unwrap();
+        assert_eq!(
+            outputs[0].status(),
+            &TransactionStatus::Keep(ExecutionStatus::Success)
+        );
+        assert_eq!(session.balance(receiver.address()).unwrap(), 100);
+        assert_eq!(context.get_apt_balance(receiver.address()).await, 0);
+
+        // Re-opened sessions keep the local changes.
+        session.persist().unwrap();
+        let version = session.version();
+        drop(session);
+        let session = ForkSession::open_with_interface(dir.path(), interface).unwrap();
+        assert_eq!(session.version(), version);
+        assert!(session.state_store().base().num_cached_values() > 0);
+        assert_eq!(session.balance(receiver.address()).unwrap(), 100);
+        assert!(ForkSession::open(dir.path()).is_err());
+    }
+}
@@ -19,18 +19,25 @@
 //!   - [`InMemoryStateStore`]
 //!   - [`DeltaStateStore`]
 //!
+//! With the `fork` feature enabled, [`ForkSession`] can be used to simulate transactions on top
+//! of the state of a real chain, fetched from a REST endpoint or a local DB.
+//!
 //! ## Usage
 //! To perform transaction-based simulations, it is recommended to use [`SimulationStateStore`] to:
 //! - Ensure portability across different implementations.
 //! - Leverage built-in utility functions for streamlined resource/config access.
 
 mod account;
+#[cfg(feature = "fork")]
+mod fork;
 mod genesis;
 mod state_store;
 
 pub use account::{
     Account, AccountData, AccountPublicKey, CoinStore, FungibleStore, TransactionBuilder,
 };
+#[cfg(feature = "fork")]
+pub use fork::{ForkSession, ForkSource, ForkStateView, SnapshotId};
 pub use genesis::{
     GENESIS_CHANGE_SET_HEAD, GENESIS_CHANGE_SET_MAINNET, GENESIS_CHANGE_SET_TESTNET,
 };
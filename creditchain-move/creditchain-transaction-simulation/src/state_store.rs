@@ -437,6 +437,22 @@ impl<V> DeltaStateStore<V> {
             states: RwLock::new(state_vals.into_iter().map(|(k, v)| (k, Some(v))).collect()),
         }
     }
+
+    /// Returns the base state view.
+    pub fn base(&self) -> &V {
+        &self.base
+    }
+
+    /// Returns a copy of the state changes stacked on top of the base state view. A [`None`]
+    /// value means that the state value has been removed.
+    pub fn delta(&self) -> HashMap<StateKey, Option<StateValue>> {
+        self.states.read().clone()
+    }
+
+    /// Replaces all state changes stacked on top of the base state view.
+    pub fn set_delta(&self, delta: HashMap<StateKey, Option<StateValue>>) {
+        *self.states.write() = delta;
+    }
 }
 
 impl<V> Clone for DeltaStateStore<V>
@@ -16,6 +16,7 @@ use creditchain_transaction_simulation::{
     InMemoryStateStore, SimulationStateStore, GENESIS_CHANGE_SET_HEAD,
 };
 use creditchain_types::{
+    access_path::Path as AccessPathPath,
     account_config::{
         creditchain_test_root_address, primary_apt_store, AccountResource, FungibleStoreResource,
         ObjectGroupResource,
@@ -23,7 +24,11 @@ use creditchain_types::{
     block_metadata::BlockMetadata,
     chain_id::ChainId,
     contract_event::ContractEvent,
-    state_store::{state_key::StateKey, table::TableHandle, MoveResourceExt, TStateView},
+    state_store::{
+        state_key::{inner::StateKeyInner, StateKey},
+        table::TableHandle,
+        MoveResourceExt, TStateView,
+    },
     transaction::{
         signature_verified_transaction::into_signature_verified_block,
         EntryFunction as TransactionEntryFunction, ExecutionStatus, RawTransaction,
@@ -89,6 +94,7 @@ struct CreditChainTestAdapter<'a> {
     default_syntax: SyntaxChoice,
     private_key_mapping: BTreeMap<String, Ed25519PrivateKey>,
     run_config: TestRunConfig,
+    checkpoints: BTreeMap<String, InMemoryStateStore>,
 }
 
 /// Parameters *required* to create a transaction.
@@ -191,6 +197,65 @@ struct ViewTableCommand {
     key_value: serde_json::Value,
 }
 
+/// Command to dump all resources stored at an account, including members of resource groups.
+#[derive(Parser, Debug)]
+struct DumpResourcesCommand {
+    #[clap(long = "address", value_parser = ParsedAddress::parse)]
+    address: ParsedAddress,
+
+    /// Only dump resources whose types are declared at this address.
+    #[clap(long = "module_address", value_parser = ParsedAddress::parse)]
+    module_address: Option<ParsedAddress>,
+}
+
+/// Command to dump all items of a table, sorted by key.
+#[derive(Parser, Debug)]
+struct DumpTableCommand {
+    #[clap(long = "table_handle")]
+    table_handle: AccountAddress,
+
+    #[clap(long = "key_type", value_parser = parse_type_tag)]
+    key_type: TypeTag,
+
+    #[clap(long = "value_type", value_parser = parse_type_tag)]
+    value_type: TypeTag,
+}
+
+/// Command to save the current state under a name, replacing any checkpoint with the same name.
+#[derive(Parser, Debug)]
+struct SaveCheckpointCommand {
+    #[clap(long = "name")]
+    name: String,
+}
+
+/// Command to reset the state to a saved checkpoint. Only the storage is restored: modules
+/// compiled since the checkpoint remain known to the compiler.
+#[derive(Parser, Debug)]
+struct RestoreCheckpointCommand {
+    #[clap(long = "name")]
+    name: String,
+}
+
+/// Command to show the resources and table items which changed since a checkpoint was saved.
+#[derive(Parser, Debug)]
+struct DiffCheckpointCommand {
+    #[clap(long = "name")]
+    name: String,
+
+    /// Only compare resources stored at this address. Table items are then skipped, unless
+    /// `table_handle` is given.
+    #[clap(long = "address", value_parser = ParsedAddress::parse)]
+    address: Option<ParsedAddress>,
+
+    /// Only compare resources whose types are declared at this address.
+    #[clap(long = "module_address", value_parser = ParsedAddress::parse)]
+    module_address: Option<ParsedAddress>,
+
+    /// Only compare the items of this table.
+    #[clap(long = "table_handle")]
+    table_handle: Option<AccountAddress>,
+}
+
 fn parse_value(input: &str) -> Result<serde_json::Value, serde_json::Error> {
     serde_json::from_str(input)
 }
@@ -203,6 +268,21 @@ enum CreditChainSubCommand {
 
     #[clap(name = "view_table")]
     ViewTableCommand(ViewTableCommand),
+
+    #[clap(name = "dump_resources")]
+    DumpResourcesCommand(DumpResourcesCommand),
+
+    #[clap(name = "dump_table")]
+    DumpTableCommand(DumpTableCommand),
+
+    #[clap(name = "save_checkpoint")]
+    SaveCheckpointCommand(SaveCheckpointCommand),
+
+    #[clap(name = "restore_checkpoint")]
+    RestoreCheckpointCommand(RestoreCheckpointCommand),
+
+    #[clap(name = "diff_checkpoint")]
+    DiffCheckpointCommand(DiffCheckpointCommand),
 }
 
 /**
@@ -661,6 +741,7 @@ impl<'a> MoveTestAdapter<'a> for CreditChainTestAdapter<'a> {
             storage,
             private_key_mapping,
             run_config,
+            checkpoints: BTreeMap::new(),
         };
 
         for (_, addr) in additional_named_address_mapping {
@@ -1028,6 +1109,71 @@ impl<'a> MoveTestAdapter<'a> for CreditChainTestAdapter<'a> {
 
                 Ok(Some(serde_json::to_string(&move_value).unwrap()))
             },
+            CreditChainSubCommand::DumpResourcesCommand(dump_cmd) => {
+                let address = self.compiled_state().resolve_address(&dump_cmd.address);
+                let module_address = dump_cmd
+                    .module_address
+                    .map(|addr| self.compiled_state().resolve_address(&addr));
+                let resources = render_resources(&self.storage, Some(address), module_address)?;
+                if resources.is_empty() {
+                    return Ok(Some("[No Resources Exist]".to_owned()));
+                }
+                Ok(Some(resources.into_values().collect::<Vec<_>>().join("\n")))
+            },
+            CreditChainSubCommand::DumpTableCommand(dump_cmd) => {
+                let handle = TableHandle(dump_cmd.table_handle);
+                let annotator = CreditChainValueAnnotator::new(&self.storage);
+                let mut items = BTreeMap::new();
+                for (state_key, state_value) in self.storage.to_btree_map() {
+                    match state_key.inner() {
+                        StateKeyInner::TableItem { handle: h, key } if *h == handle => {
+                            let key = annotator.view_value(&dump_cmd.key_type, key)?;
+                            let value =
+                                annotator.view_value(&dump_cmd.value_type, state_value.bytes())?;
+                            items.insert(key.to_string(), value.to_string());
+                        },
+                        _ => (),
+                    }
+                }
+                if items.is_empty() {
+                    return Ok(Some("[No Table Items Exist]".to_owned()));
+                }
+                let items = items
+                    .into_iter()
+                    .map(|(key, value)| format!("{} => {}", key, value))
+                    .collect::<Vec<_>>();
+                Ok(Some(items.join("\n")))
+            },
+            CreditChainSubCommand::SaveCheckpointCommand(save_cmd) => {
+                self.checkpoints.insert(save_cmd.name, self.storage.clone());
+                Ok(None)
+            },
+            CreditChainSubCommand::RestoreCheckpointCommand(restore_cmd) => {
+                let checkpoint = self
+                    .checkpoints
+                    .get(&restore_cmd.name)
+                    .ok_or_else(|| format_err!("Unknown checkpoint '{}'.", restore_cmd.name))?;
+                self.storage = checkpoint.clone();
+                Ok(None)
+            },
+            CreditChainSubCommand::DiffCheckpointCommand(diff_cmd) => {
+                let address = diff_cmd
+                    .address
+                    .map(|addr| self.compiled_state().resolve_address(&addr));
+                let module_address = diff_cmd
+                    .module_address
+                    .map(|addr| self.compiled_state().resolve_address(&addr));
+                let checkpoint = self
+                    .checkpoints
+                    .get(&diff_cmd.name)
+                    .ok_or_else(|| format_err!("Unknown checkpoint '{}'.", diff_cmd.name))?;
+                let table_handle = diff_cmd.table_handle.map(TableHandle);
+                let before = render_state(checkpoint, address, module_address, table_handle)?;
+                let after = render_state(&self.storage, address, module_address, table_handle)?;
+                Ok(Some(render_diff(&before, &after).unwrap_or_else(|| {
+                    format!("No changes since checkpoint '{}'.", diff_cmd.name)
+                })))
+            },
         }
     }
 
@@ -1089,6 +1235,126 @@ fn render_events(events: &[ContractEvent]) -> Option<String> {
     }
 }
 
+/// Renders the resources in `storage` in a normalized form, keyed by the account they are stored
+/// at and their type, and optionally restricted to one account and to types declared at one
+/// address. Members of resource groups are rendered as individual resources.
+fn render_resources(
+    storage: &InMemoryStateStore,
+    address: Option<AccountAddress>,
+    module_address: Option<AccountAddress>,
+) -> Result<BTreeMap<(AccountAddress, String), String>> {
+    let annotator = CreditChainValueAnnotator::new(storage);
+    let mut resources = BTreeMap::new();
+    for (state_key, state_value) in storage.to_btree_map() {
+        let StateKeyInner::AccessPath(access_path) = state_key.inner() else {
+            continue;
+        };
+        if address.is_some_and(|address| address != access_path.address) {
+            continue;
+        }
+        let members = match access_path.get_path() {
+            AccessPathPath::Code(_) => continue,
+            AccessPathPath::Resource(tag) => vec![(tag, state_value.bytes().to_vec())],
+            AccessPathPath::ResourceGroup(_) => {
+                bcs::from_bytes::<BTreeMap<StructTag, Vec<u8>>>(state_value.bytes())?
+                    .into_iter()
+                    .collect()
+            },
+        };
+        for (tag, bytes) in members {
+            if module_address.is_some_and(|module_address| module_address != tag.address) {
+                continue;
+            }
+            let annotated = annotator.view_resource(&tag, &bytes)?;
+            resources.insert(
+                (access_path.address, tag.to_canonical_string()),
+                annotated.to_string(),
+            );
+        }
+    }
+    Ok(resources)
+}
+
+/// Renders the resources and table items in `storage` for comparison, keyed by a label. Table
+/// items cannot be decoded without their types, so they are rendered as hex. They are restricted
+/// to one table if `table_handle` is given, and otherwise skipped when restricting the resources
+/// to one account.
+fn render_state(
+    storage: &InMemoryStateStore,
+    address: Option<AccountAddress>,
+    module_address: Option<AccountAddress>,
+    table_handle: Option<TableHandle>,
+) -> Result<BTreeMap<String, String>> {
+    let mut state = render_resources(storage, address, module_address)?
+        .into_iter()
+        .map(|((address, tag), value)| (format!("{} at {}", tag, address.to_hex_literal()), value))
+        .collect::<BTreeMap<_, _>>();
+    if address.is_none() || table_handle.is_some() {
+        for (state_key, state_value) in storage.to_btree_map() {
+            if let StateKeyInner::TableItem { handle, key } = state_key.inner() {
+                if table_handle.is_some_and(|table_handle| table_handle != *handle) {
+                    continue;
+                }
+                state.insert(
+                    format!(
+                        "table item 0x{} in {}",
+                        hex::encode(key),
+                        handle.0.to_hex_literal()
+                    ),
+                    format!("0x{}", hex::encode(state_value.bytes())),
+                );
+            }
+        }
+    }
+    Ok(state)
+}
+
+/// Renders the differences between two states, line by line. Returns `None` if they are equal.
+fn render_diff(
+    before: &BTreeMap<String, String>,
+    after: &BTreeMap<String, String>,
+) -> Option<String> {
+    let labels = before.keys().chain(after.keys()).collect::<BTreeSet<_>>();
+    let mut sections = vec![];
+    for label in labels {
+        let mut section = vec![];
+        match (before.get(label), after.get(label)) {
+            (Some(old), Some(new)) if old == new => continue,
+            (Some(old), Some(new)) => {
+                section.push(format!("changed {}:", label));
+                let old_lines = old.lines().collect::<Vec<_>>();
+                let new_lines = new.lines().collect::<Vec<_>>();
+                if old_lines.len() == new_lines.len() {
+                    for (old_line, new_line) in old_lines.iter().zip(new_lines.iter()) {
+                        if old_line != new_line {
+                            section.push(format!("- {}", old_line));
+                            section.push(format!("+ {}", new_line));
+                        }
+                    }
+                } else {
+                    section.extend(old_lines.iter().map(|line| format!("- {}", line)));
+                    section.extend(new_lines.iter().map(|line| format!("+ {}", line)));
+                }
+            },
+            (Some(old), None) => {
+                section.push(format!("removed {}:", label));
+                section.extend(old.lines().map(|line| format!("- {}", line)));
+            },
+            (None, Some(new)) => {
+                section.push(format!("added {}:", label));
+                section.extend(new.lines().map(|line| format!("+ {}", line)));
+            },
+            (None, None) => unreachable!(),
+        }
+        sections.push(section.join("\n"));
+    }
+    if sections.is_empty() {
+        None
+    } else {
+        Some(sections.join("\n"))
+    }
+}
+
 fn precompiled_v2_framework() -> &'static PrecompiledFilesModules {
     &PRECOMPILED_CREDITCHAIN_FRAMEWORK_V2
 }
@@ -0,0 +1,52 @@
+//# init --addresses Alice=0xf75daa73fc071f93593335eb9033da804777eb94491650dd3f095ce6f778acb6
+//#      --private-keys Alice=56a26140eb233750cd14fb168c3eb4bd0782b099cde626ec8aff7f3cceb6364f
+
+//# publish --private-key Alice
+module Alice::counter {
+    use std::signer;
+    use creditchain_std::table::{Self, Table};
+
+    struct Counter has key {
+        value: u64,
+    }
+
+    /// The value of the counter after each bump, keyed by the bump.
+    struct History has key {
+        values: Table<u64, u64>,
+    }
+
+    public entry fun initialize(sender: &signer) {
+        move_to(sender, Counter { value: 0 });
+        move_to(sender, History { values: table::new() });
+    }
+
+    public entry fun bump(sender: &signer) acquires Counter, History {
+        let addr = signer::address_of(sender);
+        let counter = borrow_global_mut<Counter>(addr);
+        counter.value = counter.value + 1;
+        let history = borrow_global_mut<History>(addr);
+        table::add(&mut history.values, counter.value, counter.value * 10);
+    }
+}
+
+//# run --signers Alice -- Alice::counter::initialize
+
+//# save_checkpoint --name initialized
+
+//# run --signers Alice -- Alice::counter::bump
+
+//# run --signers Alice -- Alice::counter::bump
+
+//# dump_resources --address Alice --module_address Alice
+
+//# dump_table --table_handle 0x7149d03b55a7e6ba1e3a6f5dad55aeb10887921189647d35dd86277f17055a99 --key_type u64 --value_type u64
+
+//# diff_checkpoint --name initialized --address Alice --module_address Alice --table_handle 0x7149d03b55a7e6ba1e3a6f5dad55aeb10887921189647d35dd86277f17055a99
+
+//# restore_checkpoint --name initialized
+
+//# dump_resources --address Alice --module_address Alice
+
+//# dump_table --table_handle 0x7149d03b55a7e6ba1e3a6f5dad55aeb10887921189647d35dd86277f17055a99 --key_type u64 --value_type u64
+
+//# diff_checkpoint --name initialized --address Alice --module_address Alice --table_handle 0x7149d03b55a7e6ba1e3a6f5dad55aeb10887921189647d35dd86277f17055a99
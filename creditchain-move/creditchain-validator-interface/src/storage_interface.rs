@@ -22,6 +22,10 @@ use std::{collections::HashMap, path::Path, sync::Arc};
 pub struct DBDebuggerInterface(Arc<dyn DbReader>);
 
 impl DBDebuggerInterface {
+    pub fn new(db: Arc<dyn DbReader>) -> Self {
+        Self(db)
+    }
+
     pub fn open<P: AsRef<Path> + Clone>(db_root_path: P) -> Result<Self> {
         Ok(Self(Arc::new(
             CreditChainDB::open(
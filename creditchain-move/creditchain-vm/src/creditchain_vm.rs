@@ -153,6 +153,7 @@ static EXECUTION_CONCURRENCY_LEVEL: OnceCell<usize> = OnceCell::new();
 static NUM_EXECUTION_SHARD: OnceCell<usize> = OnceCell::new();
 static NUM_PROOF_READING_THREADS: OnceCell<usize> = OnceCell::new();
 static DISCARD_FAILED_BLOCKS: OnceCell<bool> = OnceCell::new();
+static CONFLICT_DIAGNOSTICS: OnceCell<bool> = OnceCell::new();
 static PROCESSED_TRANSACTIONS_DETAILED_COUNTERS: OnceCell<bool> = OnceCell::new();
 
 macro_rules! deprecated_module_bundle {
@@ -427,6 +428,20 @@ impl CreditChainVM {
         }
     }
 
+    /// Sets runtime config when invoked the first time.
+    pub fn set_conflict_diagnostics(enable: bool) {
+        // Only the first call succeeds, due to OnceCell semantics.
+        CONFLICT_DIAGNOSTICS.set(enable).ok();
+    }
+
+    /// Get the conflict diagnostics flag if already set, otherwise return default (false)
+    pub fn get_conflict_diagnostics() -> bool {
+        match CONFLICT_DIAGNOSTICS.get() {
+            Some(enable) => *enable,
+            None => false,
+        }
+    }
+
     /// Sets the # of async proof reading threads.
     pub fn set_num_proof_reading_threads_once(mut num_threads: usize) {
         // TODO(grao): Do more analysis to tune this magic number.
@@ -2952,6 +2967,7 @@ impl VMBlockExecutor for CreditChainVMBlockExecutor {
                 concurrency_level: CreditChainVM::get_concurrency_level(),
                 allow_fallback: true,
                 discard_failed_blocks: CreditChainVM::get_discard_failed_blocks(),
+                enable_conflict_diagnostics: CreditChainVM::get_conflict_diagnostics(),
                 module_cache_config: BlockExecutorModuleCacheLocalConfig::default(),
             },
             onchain: onchain_config,
@@ -114,6 +114,78 @@ fn test_view_attribute_on_non_view() {
     assert_success!(h.publish_package(&account, path.path()));
 }
 
+#[test]
+fn test_invariant_attribute() {
+    let mut h = MoveHarness::new();
+    let account = h.new_account_at(AccountAddress::from_hex_literal("0xf00d").unwrap());
+
+    let mut builder = PackageBuilder::new("Package");
+    builder.add_source(
+        "m.move",
+        r#"
+        module 0xf00d::M {
+            #[view]
+            #[invariant]
+            fun holds(): bool { true }
+        }
+        "#,
+    );
+    let path = builder.write_to_temp().unwrap();
+    assert_success!(h.publish_package(&account, path.path()));
+}
+
+#[test]
+fn test_invariant_attribute_on_non_view() {
+    assert_invariant_rejected(
+        r#"
+        module 0xf00d::M {
+            #[invariant]
+            fun holds(): bool { true }
+        }
+        "#,
+    );
+}
+
+#[test]
+fn test_invariant_attribute_with_parameters() {
+    assert_invariant_rejected(
+        r#"
+        module 0xf00d::M {
+            #[view]
+            #[invariant]
+            fun holds(value: u64): bool { value > 0 }
+        }
+        "#,
+    );
+}
+
+#[test]
+fn test_invariant_attribute_with_non_bool_result() {
+    assert_invariant_rejected(
+        r#"
+        module 0xf00d::M {
+            #[view]
+            #[invariant]
+            fun holds(): u64 { 1 }
+        }
+        "#,
+    );
+}
+
+fn assert_invariant_rejected(source: &str) {
+    let mut builder = PackageBuilder::new("Package");
+    builder.add_source("m.move", source);
+    let path = builder.write_to_temp().unwrap();
+    let err = BuiltPackage::build(path.path().to_path_buf(), BuildOptions::default())
+        .err()
+        .expect("extended checks must reject the invariant function");
+    assert!(
+        err.to_string().contains("extended checks failed"),
+        "{:#}",
+        err
+    );
+}
+
 #[test]
 fn test_bad_attribute_in_code() {
     let mut h = MoveHarness::new();
@@ -96,6 +96,9 @@ pub struct BuildOptions {
     pub docgen_options: Option<DocgenOptions>,
     #[clap(long)]
     pub skip_fetch_latest_git_deps: bool,
+    /// Fail instead of accessing the network to fetch dependencies.
+    #[clap(long)]
+    pub offline: bool,
     #[clap(long)]
     pub bytecode_version: Option<u32>,
     #[clap(long, value_parser = clap::value_parser!(CompilerVersion))]
@@ -130,6 +133,7 @@ impl Default for BuildOptions {
             // This is false by default, because it could accidentally pull new dependencies
             // while in a test (and cause some havoc)
             skip_fetch_latest_git_deps: false,
+            offline: false,
             bytecode_version: None,
             compiler_version: None,
             language_version: None,
@@ -209,6 +213,7 @@ pub fn build_model(
         force_recompilation: false,
         fetch_deps_only: false,
         skip_fetch_latest_git_deps: true,
+        offline: false,
         compiler_config: CompilerConfig {
             bytecode_version,
             compiler_version,
@@ -259,6 +264,7 @@ impl BuiltPackage {
             force_recompilation: false,
             fetch_deps_only: false,
             skip_fetch_latest_git_deps: options.skip_fetch_latest_git_deps,
+            offline: options.offline,
             compiler_config: CompilerConfig {
                 bytecode_version,
                 compiler_version,
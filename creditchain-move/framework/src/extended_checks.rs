@@ -38,6 +38,7 @@ use std::{
 const ALLOW_UNSAFE_RANDOMNESS_ATTRIBUTE: &str = "lint::allow_unsafe_randomness";
 const FMT_SKIP_ATTRIBUTE: &str = "fmt::skip";
 const INIT_MODULE_FUN: &str = "init_module";
+const INVARIANT_FUN_ATTRIBUTE: &str = "invariant";
 const LEGACY_ENTRY_FUN_ATTRIBUTE: &str = "legacy_entry_fun";
 const ERROR_PREFIX: &str = "E";
 const EVENT_STRUCT_ATTRIBUTE: &str = "event";
@@ -54,7 +55,7 @@ const RANDOMNESS_MODULE_NAME: &str = "randomness";
 
 // top-level attribute names, only.
 pub fn get_all_attribute_names() -> &'static BTreeSet<String> {
-    const ALL_ATTRIBUTE_NAMES: [&str; 9] = [
+    const ALL_ATTRIBUTE_NAMES: [&str; 10] = [
         ALLOW_UNSAFE_RANDOMNESS_ATTRIBUTE,
         FMT_SKIP_ATTRIBUTE,
         LEGACY_ENTRY_FUN_ATTRIBUTE,
@@ -64,6 +65,7 @@ pub fn get_all_attribute_names() -> &'static BTreeSet<String> {
         VIEW_FUN_ATTRIBUTE,
         EVENT_STRUCT_ATTRIBUTE,
         RANDOMNESS_ATTRIBUTE,
+        INVARIANT_FUN_ATTRIBUTE,
     ];
 
     fn extended_attribute_names() -> BTreeSet<String> {
@@ -91,6 +93,28 @@ pub fn run_extended_checks(env: &GlobalEnv) -> BTreeMap<ModuleId, RuntimeModuleM
     checker.output
 }
 
+/// Returns the `#[invariant]` functions of the primary target modules in `env`. These are view
+/// functions without parameters returning a `bool`, which `creditchain move fuzz` checks after
+/// every call it makes.
+pub fn get_invariant_functions(env: &GlobalEnv) -> Vec<(ModuleId, Identifier)> {
+    let checker = ExtendedChecker::new(env);
+    let mut functions = vec![];
+    for ref module in env.get_modules() {
+        if !module.is_primary_target() {
+            continue;
+        }
+        for ref fun in module.get_functions() {
+            if checker.has_attribute(fun, INVARIANT_FUN_ATTRIBUTE) {
+                functions.push((
+                    checker.get_runtime_module_id(module),
+                    Identifier::new(fun.get_simple_name_string().to_string()).unwrap(),
+                ));
+            }
+        }
+    }
+    functions
+}
+
 /// Configures the move-cli unit test validation hook to run the extended checker.
 pub fn configure_extended_checks_for_unit_test() {
     fn validate(env: &GlobalEnv) {
@@ -129,6 +153,7 @@ impl<'a> ExtendedChecker<'a> {
                 self.check_and_record_resource_groups(module);
                 self.check_and_record_resource_group_members(module);
                 self.check_and_record_view_functions(module);
+                self.check_invariant_functions(module);
                 self.check_entry_functions(module);
                 self.check_and_record_unbiasabale_entry_functions(module);
                 self.check_unsafe_randomness_usage(module);
@@ -750,6 +775,37 @@ impl ExtendedChecker<'_> {
     }
 }
 
+// ----------------------------------------------------------------------------------
+// Invariant Functions
+
+impl ExtendedChecker<'_> {
+    fn check_invariant_functions(&self, module: &ModuleEnv) {
+        for ref fun in module.get_functions() {
+            if !self.has_attribute(fun, INVARIANT_FUN_ATTRIBUTE) {
+                continue;
+            }
+            if !self.has_attribute(fun, VIEW_FUN_ATTRIBUTE) {
+                self.env.error(
+                    &fun.get_id_loc(),
+                    "`#[invariant]` function must also be a `#[view]` function",
+                )
+            }
+            if fun.get_parameter_count() > 0 {
+                self.env.error(
+                    &fun.get_id_loc(),
+                    "`#[invariant]` function cannot have parameters",
+                )
+            }
+            if fun.get_result_type() != Type::Primitive(PrimitiveType::Bool) {
+                self.env.error(
+                    &fun.get_id_loc(),
+                    "`#[invariant]` function must return a single `bool`",
+                )
+            }
+        }
+    }
+}
+
 // ----------------------------------------------------------------------------------
 // Events
 
@@ -0,0 +1,149 @@
+// Copyright (c) A-p-t-o-s Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    commands::init_logger_and_metrics,
+    execution::execute_workloads,
+    fees::FeeComparison,
+    state_view::ReadSet,
+    workload::{TransactionBlock, Workload},
+};
+use anyhow::{anyhow, bail};
+use creditchain_logger::Level;
+use clap::Parser;
+use std::path::PathBuf;
+use tokio::fs;
+
+#[derive(Parser)]
+#[command(
+    about = "Compares fees charged to transactions executed on different states, e.g., under the \
+             on-chain and a candidate gas schedule"
+)]
+pub struct CompareFeesCommand {
+    #[clap(long, default_value_t = Level::Error)]
+    log_level: Level,
+
+    #[clap(long, help = "File where the transactions are saved")]
+    transactions_file: String,
+
+    #[clap(long, help = "File where the baseline input states are saved")]
+    inputs_file: String,
+
+    #[clap(
+        long,
+        help = "File where the candidate input states are saved, e.g., initialized with a \
+                candidate gas schedule"
+    )]
+    other_inputs_file: String,
+
+    #[clap(
+        long,
+        default_value_t = 1,
+        help = "Concurrency level for block execution"
+    )]
+    concurrency_level: usize,
+
+    #[clap(
+        long,
+        default_value_t = 10,
+        help = "Number of transactions with the largest change in fees to print"
+    )]
+    num_top_transactions: usize,
+
+    #[clap(
+        long,
+        help = "If set, the full comparison including fees of every transaction is saved to this \
+                file as JSON"
+    )]
+    output_file: Option<String>,
+}
+
+impl CompareFeesCommand {
+    pub async fn compare_fees(self) -> anyhow::Result<()> {
+        init_logger_and_metrics(self.log_level);
+
+        let txn_blocks_bytes = fs::read(PathBuf::from(&self.transactions_file)).await?;
+        let txn_blocks: Vec<TransactionBlock> = bcs::from_bytes(&txn_blocks_bytes)
+            .map_err(|err| anyhow!("Error when deserializing blocks of transactions: {:?}", err))?;
+        if txn_blocks.is_empty() {
+            bail!("There must be at least one transaction to execute");
+        }
+
+        let inputs_read_set_bytes = fs::read(PathBuf::from(&self.inputs_file)).await?;
+        let inputs_read_set: Vec<ReadSet> = bcs::from_bytes(&inputs_read_set_bytes)
+            .map_err(|err| anyhow!("Error when deserializing inputs: {:?}", err))?;
+
+        let other_inputs_read_set_bytes = fs::read(PathBuf::from(&self.other_inputs_file)).await?;
+        let other_inputs_read_set: Vec<ReadSet> = bcs::from_bytes(&other_inputs_read_set_bytes)
+            .map_err(|err| anyhow!("Error when deserializing other inputs: {:?}", err))?;
+
+        // Keep user transactions, so that fees can be attributed to entry functions.
+        let begin_version = txn_blocks[0].begin_version;
+        let user_txns = txn_blocks
+            .iter()
+            .map(|txn_block| {
+                txn_block
+                    .transactions
+                    .iter()
+                    .map(|txn| txn.try_as_signed_user_txn().cloned())
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+
+        let workloads = txn_blocks
+            .into_iter()
+            .map(Workload::from)
+            .collect::<Vec<_>>();
+
+        // Ensure the number of blocks matches.
+        if workloads.len() != inputs_read_set.len()
+            || inputs_read_set.len() != other_inputs_read_set.len()
+        {
+            bail!(
+                "Number of blocks of transactions does not match the number of pre-block states: \
+                there {} blocks, but {} and {} input states",
+                workloads.len(),
+                inputs_read_set.len(),
+                other_inputs_read_set.len()
+            );
+        }
+
+        let outputs = execute_workloads(&workloads, &inputs_read_set, self.concurrency_level);
+        let other_outputs =
+            execute_workloads(&workloads, &other_inputs_read_set, self.concurrency_level);
+
+        let mut version = begin_version;
+        let mut comparison = FeeComparison::default();
+        for ((outputs, other_outputs), user_txns) in
+            outputs.into_iter().zip(other_outputs).zip(user_txns)
+        {
+            for ((output, other_output), user_txn) in
+                outputs.iter().zip(other_outputs.iter()).zip(user_txns)
+            {
+                if let Some(user_txn) = user_txn {
+                    comparison.add_transaction(version, &user_txn, output, other_output);
+                }
+                version += 1;
+            }
+        }
+
+        comparison.println(self.num_top_transactions);
+        if let Some(output_file) = &self.output_file {
+            let json = serde_json::to_vec_pretty(&comparison)
+                .map_err(|err| anyhow!("Error when serializing fee comparison: {:?}", err))?;
+            fs::write(PathBuf::from(output_file), json).await?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn verify_tool() {
+        use clap::CommandFactory;
+        CompareFeesCommand::command().debug_assert();
+    }
+}
@@ -4,14 +4,12 @@
 use crate::{
     commands::init_logger_and_metrics,
     diff::TransactionDiffBuilder,
-    execution::execute_workload,
+    execution::execute_workloads,
     state_view::ReadSet,
     workload::{TransactionBlock, Workload},
 };
 use anyhow::{anyhow, bail};
 use creditchain_logger::Level;
-use creditchain_types::transaction::TransactionOutput;
-use creditchain_vm::{creditchain_vm::CreditChainVMBlockExecutor, VMBlockExecutor};
 use clap::Parser;
 use std::path::PathBuf;
 use tokio::fs;
@@ -101,8 +99,9 @@ impl DiffCommand {
             );
         }
 
-        let outputs = self.compute_outputs(&workloads, &inputs_read_set);
-        let other_outputs = self.compute_outputs(&workloads, &other_inputs_read_set);
+        let outputs = execute_workloads(&workloads, &inputs_read_set, self.concurrency_level);
+        let other_outputs =
+            execute_workloads(&workloads, &other_inputs_read_set, self.concurrency_level);
 
         let mut version = workloads[0]
             .transaction_slice_metadata
@@ -147,22 +146,6 @@ impl DiffCommand {
 
         Ok(())
     }
-
-    /// Returns outputs for the specified blocks of transactions and inputs.
-    fn compute_outputs(
-        &self,
-        workloads: &[Workload],
-        inputs: &[ReadSet],
-    ) -> Vec<Vec<TransactionOutput>> {
-        let executor = CreditChainVMBlockExecutor::new();
-        workloads
-            .iter()
-            .zip(inputs)
-            .map(|(workload, input)| {
-                execute_workload(&executor, workload, input, self.concurrency_level)
-            })
-            .collect()
-    }
 }
 
 #[cfg(test)]
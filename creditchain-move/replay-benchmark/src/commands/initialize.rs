@@ -4,7 +4,7 @@
 use crate::{
     commands::{build_debugger, init_logger_and_metrics, RestAPI},
     generator::InputOutputDiffGenerator,
-    overrides::OverrideConfig,
+    overrides::{load_gas_schedule, OverrideConfig},
     workload::TransactionBlock,
 };
 use anyhow::anyhow;
@@ -55,6 +55,14 @@ pub struct InitializeCommand {
     )]
     gas_feature_version: Option<u64>,
 
+    #[clap(
+        long,
+        help = "Path to the gas schedule (GasScheduleV2) used instead of the on-chain one, e.g., \
+                a candidate for a governance proposal. The file is parsed as JSON if it has the \
+                .json extension, and as BCS otherwise"
+    )]
+    gas_schedule: Option<PathBuf>,
+
     #[clap(
         long,
         num_args = 1..,
@@ -77,14 +85,19 @@ impl InitializeCommand {
         })?;
 
         // TODO:
-        //   1. Override gas schedule, to track the costs of charging gas or tracking limits.
-        //   2. BlockExecutorConfigFromOnchain to experiment with different block cutting based
+        //   1. BlockExecutorConfigFromOnchain to experiment with different block cutting based
         //      on gas limits?.
-        //   3. Build options for package overrides.
+        //   2. Build options for package overrides.
+        let gas_schedule = self
+            .gas_schedule
+            .as_deref()
+            .map(load_gas_schedule)
+            .transpose()?;
         let override_config = OverrideConfig::new(
             self.enable_features,
             self.disable_features,
             self.gas_feature_version,
+            gas_schedule,
             self.override_packages,
         )?;
 
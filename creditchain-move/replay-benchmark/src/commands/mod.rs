@@ -7,12 +7,14 @@ use creditchain_push_metrics::MetricsPusher;
 use creditchain_rest_client::{CreditChainBaseUrl, Client};
 pub use benchmark::BenchmarkCommand;
 use clap::Parser;
+pub use compare_fees::CompareFeesCommand;
 pub use diff::DiffCommand;
 pub use download::DownloadCommand;
 pub use initialize::InitializeCommand;
 use url::Url;
 
 mod benchmark;
+mod compare_fees;
 mod diff;
 mod download;
 mod initialize;
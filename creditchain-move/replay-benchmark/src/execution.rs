@@ -1,7 +1,7 @@
 // Copyright (c) A-p-t-o-s Foundation
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::workload::Workload;
+use crate::{state_view::ReadSet, workload::Workload};
 use creditchain_types::{
     block_executor::config::{
         BlockExecutorConfig, BlockExecutorConfigFromOnchain, BlockExecutorLocalConfig,
@@ -10,7 +10,7 @@ use creditchain_types::{
     state_store::StateView,
     transaction::TransactionOutput,
 };
-use creditchain_vm::creditchain_vm::CreditChainVMBlockExecutor;
+use creditchain_vm::{creditchain_vm::CreditChainVMBlockExecutor, VMBlockExecutor};
 
 /// Runs a block of transactions from the workload on top of the specified state (sequentially or
 /// in parallel). Block execution should never fail.
@@ -26,6 +26,7 @@ pub(crate) fn execute_workload(
             concurrency_level,
             allow_fallback: true,
             discard_failed_blocks: false,
+            enable_conflict_diagnostics: false,
             module_cache_config: BlockExecutorModuleCacheLocalConfig::default(),
         },
         // For replay, there is no block limit.
@@ -47,3 +48,18 @@ pub(crate) fn execute_workload(
         })
         .into_transaction_outputs_forced()
 }
+
+/// Runs all blocks of transactions from the workloads, each on top of the corresponding input
+/// state.
+pub(crate) fn execute_workloads(
+    workloads: &[Workload],
+    inputs: &[ReadSet],
+    concurrency_level: usize,
+) -> Vec<Vec<TransactionOutput>> {
+    let executor = CreditChainVMBlockExecutor::new();
+    workloads
+        .iter()
+        .zip(inputs)
+        .map(|(workload, input)| execute_workload(&executor, workload, input, concurrency_level))
+        .collect()
+}
@@ -0,0 +1,346 @@
+// Copyright (c) A-p-t-o-s Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use creditchain_types::transaction::{
+    ExecutionStatus, SignedTransaction, TransactionExecutableRef, TransactionOutput,
+    TransactionStatus, Version,
+};
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+/// Fees charged to a transaction in octas, split by category. Storage fees do not include
+/// refunds.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Serialize)]
+pub(crate) struct Fees {
+    pub(crate) execution: u64,
+    pub(crate) io: u64,
+    pub(crate) storage: u64,
+    pub(crate) total: u64,
+}
+
+impl Fees {
+    fn new(output: &TransactionOutput, gas_unit_price: u64) -> Self {
+        match output.try_extract_fee_statement().ok().flatten() {
+            Some(fee_statement) => Self {
+                execution: fee_statement
+                    .execution_gas_used()
+                    .saturating_mul(gas_unit_price),
+                io: fee_statement.io_gas_used().saturating_mul(gas_unit_price),
+                storage: fee_statement.storage_fee_used(),
+                total: fee_statement.gas_used().saturating_mul(gas_unit_price),
+            },
+            // Without a fee statement, only the total can be accounted for.
+            None => Self {
+                total: output.gas_used().saturating_mul(gas_unit_price),
+                ..Self::default()
+            },
+        }
+    }
+
+    fn add(&mut self, other: &Fees) {
+        self.execution = self.execution.saturating_add(other.execution);
+        self.io = self.io.saturating_add(other.io);
+        self.storage = self.storage.saturating_add(other.storage);
+        self.total = self.total.saturating_add(other.total);
+    }
+}
+
+/// Difference between candidate and baseline fees, in octas.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Serialize)]
+pub(crate) struct FeesDelta {
+    pub(crate) execution: i64,
+    pub(crate) io: i64,
+    pub(crate) storage: i64,
+    pub(crate) total: i64,
+}
+
+impl FeesDelta {
+    fn new(baseline: &Fees, candidate: &Fees) -> Self {
+        let delta = |baseline: u64, candidate: u64| candidate as i64 - baseline as i64;
+        Self {
+            execution: delta(baseline.execution, candidate.execution),
+            io: delta(baseline.io, candidate.io),
+            storage: delta(baseline.storage, candidate.storage),
+            total: delta(baseline.total, candidate.total),
+        }
+    }
+}
+
+/// Fees charged to a single transaction under the baseline and the candidate states.
+#[derive(Clone, Debug, Serialize)]
+pub(crate) struct TransactionFees {
+    pub(crate) version: Version,
+    pub(crate) entry_function: String,
+    pub(crate) baseline: Fees,
+    pub(crate) candidate: Fees,
+    pub(crate) delta: FeesDelta,
+    /// True if the transaction runs out of gas only under the candidate state.
+    pub(crate) newly_out_of_gas: bool,
+}
+
+/// Fees charged to a group of transactions under the baseline and the candidate states.
+#[derive(Clone, Debug, Default, Serialize)]
+pub(crate) struct AggregatedFees {
+    pub(crate) num_transactions: usize,
+    pub(crate) baseline: Fees,
+    pub(crate) candidate: Fees,
+    pub(crate) delta: FeesDelta,
+}
+
+impl AggregatedFees {
+    fn add(&mut self, txn_fees: &TransactionFees) {
+        self.num_transactions += 1;
+        self.baseline.add(&txn_fees.baseline);
+        self.candidate.add(&txn_fees.candidate);
+        self.delta = FeesDelta::new(&self.baseline, &self.candidate);
+    }
+}
+
+/// Compares fees charged to user transactions executed on different states, e.g., under the
+/// current gas schedule and a candidate one. Fees are reported per transaction, per entry
+/// function and per category.
+#[derive(Default, Serialize)]
+pub(crate) struct FeeComparison {
+    total: AggregatedFees,
+    per_entry_function: BTreeMap<String, AggregatedFees>,
+    transactions: Vec<TransactionFees>,
+}
+
+impl FeeComparison {
+    pub(crate) fn add_transaction(
+        &mut self,
+        version: Version,
+        txn: &SignedTransaction,
+        baseline_output: &TransactionOutput,
+        candidate_output: &TransactionOutput,
+    ) {
+        let baseline = Fees::new(baseline_output, txn.gas_unit_price());
+        let candidate = Fees::new(candidate_output, txn.gas_unit_price());
+        let txn_fees = TransactionFees {
+            version,
+            entry_function: entry_function_name(txn),
+            baseline,
+            candidate,
+            delta: FeesDelta::new(&baseline, &candidate),
+            newly_out_of_gas: !is_out_of_gas(baseline_output) && is_out_of_gas(candidate_output),
+        };
+
+        self.total.add(&txn_fees);
+        self.per_entry_function
+            .entry(txn_fees.entry_function.clone())
+            .or_default()
+            .add(&txn_fees);
+        self.transactions.push(txn_fees);
+    }
+
+    /// Returns all transactions that run out of gas only under the candidate state.
+    pub(crate) fn newly_out_of_gas(&self) -> impl Iterator<Item = &TransactionFees> {
+        self.transactions
+            .iter()
+            .filter(|txn_fees| txn_fees.newly_out_of_gas)
+    }
+
+    /// Prints the total fees per category, fees per entry function, transactions that newly run
+    /// out of gas, and the transactions with the largest fee changes.
+    pub(crate) fn println(&self, num_top_transactions: usize) {
+        println!("Fees (octas) for {} user transactions:", self.total.num_transactions);
+        println!(
+            "{:>12} {:>20} {:>20} {:>20} {:>10}",
+            "category", "baseline", "candidate", "delta", "change"
+        );
+        let categories = [
+            ("execution", self.total.baseline.execution, self.total.candidate.execution),
+            ("io", self.total.baseline.io, self.total.candidate.io),
+            ("storage", self.total.baseline.storage, self.total.candidate.storage),
+            ("total", self.total.baseline.total, self.total.candidate.total),
+        ];
+        for (category, baseline, candidate) in categories {
+            println!(
+                "{:>12} {:>20} {:>20} {:>20} {:>10}",
+                category,
+                baseline,
+                candidate,
+                candidate as i64 - baseline as i64,
+                percentage_change(baseline, candidate)
+            );
+        }
+
+        let mut per_entry_function = self.per_entry_function.iter().collect::<Vec<_>>();
+        per_entry_function.sort_by_key(|(_, fees)| std::cmp::Reverse(fees.delta.total.abs()));
+        println!();
+        println!("Fees (octas) per entry function, by the largest change in total fees:");
+        for (entry_function, fees) in per_entry_function {
+            println!(
+                "  {} ({} txns): {} -> {} ({:+}, {}) [execution {:+}, io {:+}, storage {:+}]",
+                entry_function,
+                fees.num_transactions,
+                fees.baseline.total,
+                fees.candidate.total,
+                fees.delta.total,
+                percentage_change(fees.baseline.total, fees.candidate.total),
+                fees.delta.execution,
+                fees.delta.io,
+                fees.delta.storage,
+            );
+        }
+
+        let newly_out_of_gas = self.newly_out_of_gas().collect::<Vec<_>>();
+        println!();
+        println!("Transactions that newly run out of gas: {}", newly_out_of_gas.len());
+        for txn_fees in newly_out_of_gas {
+            println!("  {} ({})", txn_fees.version, txn_fees.entry_function);
+        }
+
+        let mut transactions = self.transactions.iter().collect::<Vec<_>>();
+        transactions.sort_by_key(|txn_fees| std::cmp::Reverse(txn_fees.delta.total.abs()));
+        println!();
+        println!("Top {} transactions by the largest change in total fees:", num_top_transactions);
+        for txn_fees in transactions.into_iter().take(num_top_transactions) {
+            println!(
+                "  {} ({}): {} -> {} ({:+})",
+                txn_fees.version,
+                txn_fees.entry_function,
+                txn_fees.baseline.total,
+                txn_fees.candidate.total,
+                txn_fees.delta.total,
+            );
+        }
+    }
+}
+
+fn entry_function_name(txn: &SignedTransaction) -> String {
+    match txn.executable_ref() {
+        Ok(TransactionExecutableRef::EntryFunction(entry_function)) => format!(
+            "{}::{}",
+            entry_function.module().short_str_lossless(),
+            entry_function.function()
+        ),
+        Ok(TransactionExecutableRef::Script(_)) => "<script>".to_string(),
+        Ok(TransactionExecutableRef::Empty) | Err(_) => "<other>".to_string(),
+    }
+}
+
+fn is_out_of_gas(output: &TransactionOutput) -> bool {
+    matches!(output.status(), TransactionStatus::Keep(ExecutionStatus::OutOfGas))
+}
+
+fn percentage_change(baseline: u64, candidate: u64) -> String {
+    if baseline == 0 {
+        return "n/a".to_string();
+    }
+    let change = (candidate as f64 - baseline as f64) / baseline as f64 * 100.0;
+    format!("{:+.2}%", change)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use creditchain_crypto::{
+        ed25519::{Ed25519PrivateKey, Ed25519PublicKey, Ed25519Signature},
+        Uniform,
+    };
+    use creditchain_types::{
+        chain_id::ChainId,
+        contract_event::{ContractEvent, FEE_STATEMENT_EVENT_TYPE},
+        fee_statement::FeeStatement,
+        transaction::{EntryFunction, RawTransaction, TransactionAuxiliaryData},
+        write_set::WriteSet,
+    };
+    use move_core_types::{
+        account_address::AccountAddress, identifier::Identifier, language_storage::ModuleId,
+    };
+    use rand::{rngs::StdRng, SeedableRng};
+
+    fn user_transaction(function: &str, gas_unit_price: u64) -> SignedTransaction {
+        let entry_func = EntryFunction::new(
+            ModuleId::new(AccountAddress::ONE, Identifier::new("foo").unwrap()),
+            Identifier::new(function).unwrap(),
+            vec![],
+            vec![],
+        );
+        let raw_txn = RawTransaction::new_entry_function(
+            AccountAddress::ONE,
+            0,
+            entry_func,
+            100,
+            gas_unit_price,
+            10,
+            ChainId::test(),
+        );
+
+        let mut rng = StdRng::from_seed([0; 32]);
+        let pub_key = Ed25519PublicKey::from(&Ed25519PrivateKey::generate(&mut rng));
+        SignedTransaction::new(raw_txn, pub_key, Ed25519Signature::dummy_signature())
+    }
+
+    fn output(fee_statement: FeeStatement, status: ExecutionStatus) -> TransactionOutput {
+        let event = ContractEvent::new_v2(
+            FEE_STATEMENT_EVENT_TYPE.clone(),
+            bcs::to_bytes(&fee_statement).unwrap(),
+        )
+        .unwrap();
+        TransactionOutput::new(
+            WriteSet::new(vec![]).unwrap(),
+            vec![event],
+            fee_statement.gas_used(),
+            TransactionStatus::Keep(status),
+            TransactionAuxiliaryData::None,
+        )
+    }
+
+    #[test]
+    fn test_fee_comparison() {
+        let mut comparison = FeeComparison::default();
+
+        // Fees go up, storage fee is charged in octas.
+        comparison.add_transaction(
+            10,
+            &user_transaction("foo_func", 2),
+            &output(FeeStatement::new(15, 5, 5, 10, 0), ExecutionStatus::Success),
+            &output(FeeStatement::new(20, 10, 5, 10, 0), ExecutionStatus::Success),
+        );
+        // Transaction runs out of gas with the candidate.
+        comparison.add_transaction(
+            11,
+            &user_transaction("foo_func", 1),
+            &output(FeeStatement::new(10, 5, 5, 0, 0), ExecutionStatus::Success),
+            &output(FeeStatement::new(8, 4, 4, 0, 0), ExecutionStatus::OutOfGas),
+        );
+        // Transaction out of gas in both cases.
+        comparison.add_transaction(
+            12,
+            &user_transaction("bar_func", 1),
+            &output(FeeStatement::new(5, 5, 0, 0, 0), ExecutionStatus::OutOfGas),
+            &output(FeeStatement::new(5, 5, 0, 0, 0), ExecutionStatus::OutOfGas),
+        );
+
+        assert_eq!(comparison.transactions[0].baseline, Fees {
+            execution: 10,
+            io: 10,
+            storage: 10,
+            total: 30,
+        });
+        assert_eq!(comparison.transactions[0].delta, FeesDelta {
+            execution: 10,
+            io: 0,
+            storage: 0,
+            total: 10,
+        });
+
+        let newly_out_of_gas = comparison
+            .newly_out_of_gas()
+            .map(|txn_fees| txn_fees.version)
+            .collect::<Vec<_>>();
+        assert_eq!(newly_out_of_gas, vec![11]);
+
+        let foo_fees = comparison
+            .per_entry_function
+            .get("0x1::foo::foo_func")
+            .unwrap();
+        assert_eq!(foo_fees.num_transactions, 2);
+        assert_eq!(foo_fees.delta.total, 8);
+        assert_eq!(comparison.total.num_transactions, 3);
+        assert_eq!(comparison.total.delta.execution, 9);
+        assert_eq!(comparison.total.delta.io, -1);
+    }
+}
@@ -4,6 +4,7 @@
 pub mod commands;
 mod diff;
 mod execution;
+mod fees;
 mod generator;
 mod overrides;
 mod runner;
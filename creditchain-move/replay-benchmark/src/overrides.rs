@@ -7,9 +7,10 @@
 //!   1. enabling feature flags,
 //!   2. disabling feature flags,
 //!   3. overriding gas feature version,
-//!   4. changing modules (bytecode, metadata, etc.) and package information.
+//!   4. replacing the gas schedule, e.g., with a candidate for a governance proposal,
+//!   5. changing modules (bytecode, metadata, etc.) and package information.
 
-use anyhow::bail;
+use anyhow::{anyhow, bail, Context};
 use creditchain_framework::{natives::code::PackageRegistry, BuildOptions, BuiltPackage};
 use creditchain_gas_schedule::LATEST_GAS_FEATURE_VERSION;
 use creditchain_logger::{error, warn};
@@ -20,7 +21,7 @@ use creditchain_types::{
 use serde::Serialize;
 use std::{
     collections::{BTreeSet, HashMap},
-    path::PathBuf,
+    path::{Path, PathBuf},
 };
 
 /// Stores information about compiled Move packages and the build options used to create them. Used
@@ -53,6 +54,9 @@ pub struct OverrideConfig {
     additional_disabled_features: Vec<FeatureFlag>,
     /// Gas feature version to use. Invariant: must be at most the latest version.
     gas_feature_version: Option<u64>,
+    /// Gas schedule to use instead of the on-chain one. If the gas feature version is also set,
+    /// it takes precedence over the feature version of this schedule.
+    gas_schedule: Option<GasScheduleV2>,
     /// Information about overridden packages.
     package_override: PackageOverride,
 }
@@ -62,6 +66,7 @@ impl OverrideConfig {
         additional_enabled_features: Vec<FeatureFlag>,
         additional_disabled_features: Vec<FeatureFlag>,
         gas_feature_version: Option<u64>,
+        gas_schedule: Option<GasScheduleV2>,
         override_packages: Vec<String>,
     ) -> anyhow::Result<Self> {
         let build_options = BuildOptions::move_2();
@@ -79,11 +84,18 @@ impl OverrideConfig {
                 LATEST_GAS_FEATURE_VERSION
             );
         }
+        if matches!(&gas_schedule, Some(s) if s.feature_version > LATEST_GAS_FEATURE_VERSION) {
+            warn!(
+                "Gas schedule's feature version is greater than the latest one: {}",
+                LATEST_GAS_FEATURE_VERSION
+            );
+        }
 
         Ok(Self {
             additional_enabled_features,
             additional_disabled_features,
             gas_feature_version,
+            gas_schedule,
             package_override,
         })
     }
@@ -116,14 +128,19 @@ impl OverrideConfig {
             state_override.insert(features_state_key, features_state_value);
         }
 
-        // Gas feature override.
-        if let Some(gas_feature_version) = self.gas_feature_version {
+        // Gas schedule and gas feature override.
+        if self.gas_schedule.is_some() || self.gas_feature_version.is_some() {
             // Only support V2 gas schedule which has gas feature versions. Otherwise, V1 has 0
             // version at all times, and most likely it has been so long ago we will not replay
             // these transactions.
             let (gas_schedule_state_key, gas_schedule_state_value) =
                 config_override::<GasScheduleV2, _>(state_view, |gas_schedule| {
-                    gas_schedule.feature_version = gas_feature_version;
+                    if let Some(new_gas_schedule) = &self.gas_schedule {
+                        *gas_schedule = new_gas_schedule.clone();
+                    }
+                    if let Some(gas_feature_version) = self.gas_feature_version {
+                        gas_schedule.feature_version = gas_feature_version;
+                    }
                 });
             state_override.insert(gas_schedule_state_key, gas_schedule_state_value);
         }
@@ -249,6 +266,21 @@ impl OverrideConfig {
     }
 }
 
+/// Loads a gas schedule from the file. Files with `.json` extension are parsed as JSON, all other
+/// files are expected to contain a BCS-serialized [GasScheduleV2] (e.g., as in the blob used by
+/// gas schedule update proposals).
+pub(crate) fn load_gas_schedule(path: &Path) -> anyhow::Result<GasScheduleV2> {
+    let bytes = std::fs::read(path)
+        .with_context(|| format!("Failed to read gas schedule from {}", path.display()))?;
+    if path.extension().is_some_and(|ext| ext == "json") {
+        serde_json::from_slice(&bytes)
+            .map_err(|err| anyhow!("Error when parsing JSON gas schedule: {:?}", err))
+    } else {
+        bcs::from_bytes(&bytes)
+            .map_err(|err| anyhow!("Error when deserializing gas schedule: {:?}", err))
+    }
+}
+
 /// Returns the state key for on-chain config type.
 fn config_state_key<T: OnChainConfig>() -> StateKey {
     StateKey::resource(T::address(), &T::struct_tag())
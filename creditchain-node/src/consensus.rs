@@ -16,7 +16,9 @@ use creditchain_consensus::{
             observer_client::ConsensusObserverClient,
             observer_message::ConsensusObserverMessage,
         },
-        publisher::consensus_publisher::ConsensusPublisher,
+        publisher::{
+            consensus_publisher::ConsensusPublisher, grpc_service::ConsensusObserverGrpcPublisher,
+        },
     },
     consensus_provider::start_consensus_observer,
     network_interface::ConsensusMsg,
@@ -254,9 +256,19 @@ fn create_consensus_publisher(
     let runtime = creditchain_runtimes::spawn_named_runtime("publisher".into(), None);
 
     // Create the consensus publisher
-    let (consensus_publisher, outbound_message_receiver) =
+    let (mut consensus_publisher, outbound_message_receiver) =
         ConsensusPublisher::new(node_config.consensus_observer, consensus_observer_client);
 
+    // Create and start the gRPC publisher for external subscribers (if enabled)
+    if node_config.consensus_observer_grpc.enabled {
+        let (grpc_publisher, grpc_message_receiver) = ConsensusObserverGrpcPublisher::new(
+            node_config.consensus_observer_grpc.clone(),
+            node_config.consensus_observer.max_network_channel_size as usize,
+        );
+        runtime.spawn(grpc_publisher.clone().start(grpc_message_receiver));
+        consensus_publisher = consensus_publisher.with_grpc_publisher(grpc_publisher);
+    }
+
     // Start the consensus publisher
     runtime.spawn(
         consensus_publisher
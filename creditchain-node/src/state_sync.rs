@@ -4,7 +4,9 @@
 use crate::network::ApplicationNetworkInterfaces;
 use creditchain_config::config::{NodeConfig, StateSyncConfig};
 use creditchain_consensus_notifications::ConsensusNotifier;
-use creditchain_data_client::{client::CreditChainDataClient, poller};
+use creditchain_data_client::{
+    client::CreditChainDataClient, peer_states::PeerStatesStorageInterface, poller,
+};
 use creditchain_data_streaming_service::{
     streaming_client::{new_streaming_service_client_listener_pair, StreamingServiceClient},
     streaming_service::DataStreamingService,
@@ -141,19 +143,25 @@ pub fn start_state_sync_and_get_notification_handles(
     let network_client = storage_network_interfaces.network_client;
     let network_service_events = storage_network_interfaces.network_service_events;
 
+    // Create the persistent metadata storage (this is also used by the data client)
+    let metadata_storage = PersistentMetadataStorage::new(&node_config.storage.dir());
+
     // Start the data client
     let peers_and_metadata = network_client.get_peers_and_metadata();
-    let (creditchain_data_client, creditchain_data_client_runtime) =
-        setup_creditchain_data_client(node_config, network_client, db_rw.reader.clone())?;
+    let (creditchain_data_client, creditchain_data_client_runtime) = setup_creditchain_data_client(
+        node_config,
+        network_client,
+        db_rw.reader.clone(),
+        Arc::new(metadata_storage.clone()),
+    )?;
 
     // Start the data streaming service
     let state_sync_config = node_config.state_sync;
     let (streaming_service_client, streaming_service_runtime) =
         setup_data_streaming_service(state_sync_config, creditchain_data_client.clone())?;
 
-    // Create the chunk executor and persistent storage
+    // Create the chunk executor
     let chunk_executor = Arc::new(ChunkExecutor::<CreditChainVMBlockExecutor>::new(db_rw.clone()));
-    let metadata_storage = PersistentMetadataStorage::new(&node_config.storage.dir());
 
     // Create notification senders and listeners for mempool, consensus and the storage service
     let (mempool_notifier, mempool_listener) =
@@ -241,6 +249,7 @@ fn setup_creditchain_data_client(
     node_config: &NodeConfig,
     network_client: NetworkClient<StorageServiceMessage>,
     storage: Arc<dyn DbReader>,
+    peer_states_storage: Arc<dyn PeerStatesStorageInterface>,
 ) -> anyhow::Result<(CreditChainDataClient, Runtime)> {
     // Create the storage service client
     let storage_service_client = StorageServiceClient::new(network_client);
@@ -255,6 +264,7 @@ fn setup_creditchain_data_client(
         TimeService::real(),
         storage,
         storage_service_client,
+        Some(peer_states_storage),
         Some(creditchain_data_client_runtime.handle().clone()),
     );
     creditchain_data_client_runtime.spawn(poller::start_poller(data_summary_poller));
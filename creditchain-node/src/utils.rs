@@ -61,6 +61,7 @@ pub fn set_creditchain_vm_configurations(node_config: &NodeConfig) {
     };
     CreditChainVM::set_concurrency_level_once(effective_concurrency_level as usize);
     CreditChainVM::set_discard_failed_blocks(node_config.execution.discard_failed_blocks);
+    CreditChainVM::set_conflict_diagnostics(node_config.execution.blockstm_conflict_diagnostics);
     CreditChainVM::set_num_proof_reading_threads_once(
         node_config.execution.num_proof_reading_threads as usize,
     );
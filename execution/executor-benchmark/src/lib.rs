@@ -12,6 +12,7 @@ pub mod measurements;
 mod metrics;
 pub mod native;
 pub mod pipeline;
+pub mod replay;
 pub mod transaction_committer;
 pub mod transaction_executor;
 pub mod transaction_generator;
@@ -166,6 +166,7 @@ impl PipelineOpt {
             partitioner_config: self.sharding_opt.partitioner_config(),
             num_sig_verify_threads: self.num_sig_verify_threads,
             print_transactions: false,
+            collect_block_executor_stats: false,
         }
     }
 }
@@ -418,6 +419,48 @@ enum Command {
             Sample usage: --enable-feature=V1 --disable-feature=V2 V3 where V1, V2, V3 are FeatureFlag enum variants.")]
         disable_feature: Vec<FeatureFlag>,
     },
+    /// Exports a range of recorded blocks from a source DB into a block trace file
+    ///
+    /// A trace never crosses an epoch boundary: the export stops before the first block
+    /// that ends an epoch, so the trace may hold fewer blocks than requested. To replay
+    /// the blocks of a later epoch, start the export after the boundary.
+    ExportBlockTrace {
+        #[clap(long, value_parser)]
+        source_data_dir: PathBuf,
+
+        /// The trace starts at the first block at (or after) this version
+        #[clap(long)]
+        begin_version: u64,
+
+        /// The max number of blocks to export (the export also stops at the end of the epoch)
+        #[clap(long, default_value_t = 1000)]
+        blocks: usize,
+
+        #[clap(long, value_parser)]
+        trace_file: PathBuf,
+    },
+    /// Creates a DB with the state right before the first block in the block trace
+    CreatePreStateDb {
+        #[clap(long, value_parser)]
+        source_data_dir: PathBuf,
+
+        #[clap(long, value_parser)]
+        trace_file: PathBuf,
+
+        #[clap(long, value_parser)]
+        data_dir: PathBuf,
+    },
+    /// Replays the blocks in the block trace on top of the pre-state DB
+    ReplayBlockTrace {
+        #[clap(long, value_parser)]
+        trace_file: PathBuf,
+
+        #[clap(long, value_parser)]
+        data_dir: PathBuf,
+
+        #[clap(long, value_parser)]
+        checkpoint_dir: PathBuf,
+    },
     AddAccounts {
         #[clap(long, value_parser)]
         data_dir: PathBuf,
@@ -540,6 +583,49 @@ where
                 opt.use_keyless_accounts,
             );
         },
+        Command::ExportBlockTrace {
+            source_data_dir,
+            begin_version,
+            blocks,
+            trace_file,
+        } => {
+            creditchain_executor_benchmark::replay::export_block_trace(
+                source_data_dir,
+                begin_version,
+                blocks,
+                trace_file,
+                opt.enable_storage_sharding,
+            )
+            .expect("Failed to export the block trace");
+        },
+        Command::CreatePreStateDb {
+            source_data_dir,
+            trace_file,
+            data_dir,
+        } => {
+            creditchain_executor_benchmark::replay::create_pre_state_db(
+                source_data_dir,
+                trace_file,
+                data_dir,
+                opt.enable_storage_sharding,
+            )
+            .expect("Failed to create the pre-state DB");
+        },
+        Command::ReplayBlockTrace {
+            trace_file,
+            data_dir,
+            checkpoint_dir,
+        } => {
+            creditchain_executor_benchmark::replay::replay_block_trace::<E>(
+                trace_file,
+                data_dir,
+                checkpoint_dir,
+                opt.pruner_opt.pruner_config(),
+                opt.enable_storage_sharding,
+                opt.pipeline_opt.pipeline_config(),
+            )
+            .expect("Failed to replay the block trace");
+        },
         Command::AddAccounts {
             data_dir,
             checkpoint_dir,
@@ -417,6 +417,80 @@ impl OverallMeasurement {
     }
 }
 
+#[derive(Debug, Clone)]
+pub(crate) struct BlockExecutorMeasuring {
+    start_time: Instant,
+    num_dependency_waits: u64,
+    num_speculative_aborts: u64,
+    num_sequential_executions: u64,
+}
+
+impl BlockExecutorMeasuring {
+    pub fn start() -> Self {
+        Self {
+            start_time: Instant::now(),
+            num_dependency_waits: Self::num_dependency_waits(),
+            num_speculative_aborts: block_executor_counters::SPECULATIVE_ABORT_COUNT.get(),
+            num_sequential_executions: Self::num_sequential_executions(),
+        }
+    }
+
+    /// Returns the stats of the block executed since the measuring started. Note: the
+    /// block executor counters are global, so this is only accurate if no other block
+    /// was executed concurrently (which holds for the single execution stage thread).
+    pub fn elapsed(self, block_index: usize, num_txns: u64) -> BlockExecutorStats {
+        BlockExecutorStats {
+            block_index,
+            num_txns,
+            execution_time: self.start_time.elapsed().as_secs_f64(),
+            num_dependency_waits: Self::num_dependency_waits() - self.num_dependency_waits,
+            num_speculative_aborts: block_executor_counters::SPECULATIVE_ABORT_COUNT.get()
+                - self.num_speculative_aborts,
+            num_sequential_executions: Self::num_sequential_executions()
+                - self.num_sequential_executions,
+        }
+    }
+
+    fn num_dependency_waits() -> u64 {
+        block_executor_counters::DEPENDENCY_WAIT_SECONDS.get_sample_count()
+    }
+
+    fn num_sequential_executions() -> u64 {
+        block_executor_counters::BLOCK_COMMITTED_TXNS
+            .with_label_values(&[block_executor_counters::Mode::SEQUENTIAL])
+            .get_sample_count()
+    }
+}
+
+/// Block-STM statistics for the execution of a single block
+#[derive(Debug, Clone)]
+pub struct BlockExecutorStats {
+    /// The index of the block in the pipeline
+    pub block_index: usize,
+    /// The number of input transactions in the block
+    pub num_txns: u64,
+    /// The time (in seconds) spent executing the block
+    pub execution_time: f64,
+    /// The number of times a transaction had to wait on an estimate written
+    /// by an earlier transaction (i.e., a read-write conflict was detected)
+    pub num_dependency_waits: u64,
+    /// The number of speculative aborts (i.e., failed validations and re-executions)
+    pub num_speculative_aborts: u64,
+    /// The number of times the block was executed sequentially (e.g., due to a
+    /// fallback from parallel execution)
+    pub num_sequential_executions: u64,
+}
+
+impl BlockExecutorStats {
+    pub fn speculative_aborts_per_txn(&self) -> f64 {
+        self.num_speculative_aborts as f64 / (self.num_txns as f64).max(1.0)
+    }
+
+    pub fn tps(&self) -> f64 {
+        self.num_txns as f64 / self.execution_time
+    }
+}
+
 pub struct EventMeasurements {
     pub staged_events: BTreeMap<(usize, StructTag), usize>,
 }
@@ -4,7 +4,9 @@
 use crate::{
     block_preparation::BlockPreparationStage,
     ledger_update_stage::{CommitProcessing, LedgerUpdateStage},
-    measurements::{EventMeasurements, OverallMeasuring},
+    measurements::{
+        BlockExecutorMeasuring, BlockExecutorStats, EventMeasurements, OverallMeasuring,
+    },
     metrics::NUM_TXNS,
     OverallMeasurement, TransactionCommitter, TransactionExecutor,
 };
@@ -50,6 +52,8 @@ pub struct PipelineConfig {
     pub num_sig_verify_threads: usize,
 
     pub print_transactions: bool,
+    /// Whether to collect the Block-STM statistics of each executed block
+    pub collect_block_executor_stats: bool,
 }
 
 pub struct Pipeline<V> {
@@ -58,6 +62,7 @@ pub struct Pipeline<V> {
     start_pipeline_tx: Option<SyncSender<()>>,
     staged_result: Arc<Mutex<Vec<OverallMeasurement>>>,
     staged_events: Arc<Mutex<BTreeMap<(usize, StructTag), usize>>>,
+    block_executor_stats: Arc<Mutex<Vec<BlockExecutorStats>>>,
 }
 
 impl<V> Pipeline<V>
@@ -151,6 +156,10 @@ where
         let staged_result = Arc::new(Mutex::new(Vec::new()));
         let staged_result_clone = staged_result.clone();
 
+        let collect_block_executor_stats = config.collect_block_executor_stats;
+        let block_executor_stats = Arc::new(Mutex::new(Vec::new()));
+        let block_executor_stats_clone = block_executor_stats.clone();
+
         let preparation_thread = std::thread::Builder::new()
             .name("block_preparation".to_string())
             .spawn(move || {
@@ -180,6 +189,7 @@ where
                 start_execution_rx.map(|rx| rx.recv());
                 let overall_measuring = OverallMeasuring::start();
                 let mut executed = 0;
+                let mut block_index = 0;
 
                 let mut stage_index = 0;
                 let mut stage_overall_measuring = overall_measuring.clone();
@@ -213,8 +223,16 @@ where
                     info!("Received block of size {:?} to execute", block_size);
                     executed += block_size;
                     stage_executed += block_size;
+                    let block_executor_measuring =
+                        collect_block_executor_stats.then(BlockExecutorMeasuring::start);
                     exe.execute_block(current_block_start_time, partition_time, block, stage_index);
                     info!("Finished executing block");
+                    if let Some(block_executor_measuring) = block_executor_measuring {
+                        block_executor_stats_clone
+                            .lock()
+                            .push(block_executor_measuring.elapsed(block_index, block_size));
+                    }
+                    block_index += 1;
 
                     // Empty blocks indicate the end of a stage.
                     // Print the accumulated stage stats at that point.
@@ -308,6 +326,7 @@ where
                 start_pipeline_tx,
                 staged_result,
                 staged_events,
+                block_executor_stats,
             },
             raw_block_sender,
         )
@@ -317,6 +336,12 @@ where
         self.start_pipeline_tx.as_ref().map(|tx| tx.send(()));
     }
 
+    /// Returns the Block-STM statistics of the executed blocks. These are only
+    /// collected if enabled in the pipeline config.
+    pub fn block_executor_stats(&self) -> Arc<Mutex<Vec<BlockExecutorStats>>> {
+        self.block_executor_stats.clone()
+    }
+
     pub fn join(self) -> (Option<u64>, Vec<OverallMeasurement>, EventMeasurements) {
         let mut counts = vec![];
         for handle in self.join_handles {
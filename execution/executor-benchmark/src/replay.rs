@@ -0,0 +1,548 @@
+// Copyright © CreditChain Research Team
+// SPDX-License-Identifier: Apache-2.0
+
+//! Replays recorded blocks (e.g., from mainnet) through the benchmark pipeline.
+//!
+//! Replaying a range of blocks works in three steps:
+//! 1. The blocks are exported from a source DB into a block trace file. To
+//!    replay blocks from a backup, first restore the backup into a DB
+//!    (e.g., using the db-tool).
+//! 2. A pre-state DB is created from the source DB, i.e., a copy of the
+//!    source DB truncated to the version right before the first recorded block.
+//! 3. The recorded blocks are executed on top of the pre-state DB, and the
+//!    throughput and per-block Block-STM statistics are reported.
+
+use crate::{
+    create_checkpoint, init_db,
+    measurements::{BlockExecutorStats, OverallMeasurement, OverallMeasuring},
+    pipeline::{Pipeline, PipelineConfig},
+    SingleRunResults,
+};
+use anyhow::{bail, ensure, Result};
+use creditchain_config::config::{NodeConfig, PrunerConfig, NO_OP_STORAGE_PRUNER_CONFIG};
+use creditchain_db::{db_debugger::truncate, CreditChainDB};
+use creditchain_executor::block_executor::BlockExecutor;
+use creditchain_logger::info;
+use creditchain_storage_interface::{DbReader, MAX_REQUEST_LIMIT};
+use creditchain_types::{
+    contract_event::ContractEvent,
+    transaction::{Transaction, Version},
+};
+use creditchain_vm::VMBlockExecutor;
+use clap::Parser;
+use serde::{Deserialize, Serialize};
+use std::{fs, path::Path, time::Instant};
+
+/// The number of blocks with the most speculative aborts to print at the end of a replay
+const NUM_MOST_CONFLICTING_BLOCKS_TO_PRINT: usize = 10;
+
+/// A single block recorded from a source DB
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct RecordedBlock {
+    /// The version of the first transaction in the block
+    pub first_version: Version,
+    /// The transactions in the block (excluding the block ending transaction,
+    /// which is re-generated by the executor).
+    pub transactions: Vec<Transaction>,
+}
+
+/// A consecutive range of blocks recorded from a source DB
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct BlockTrace {
+    pub blocks: Vec<RecordedBlock>,
+}
+
+impl BlockTrace {
+    /// Reads the block trace from the given file
+    pub fn read_from_file(trace_file: impl AsRef<Path>) -> Result<Self> {
+        let trace_bytes = fs::read(trace_file)?;
+        let block_trace: BlockTrace = bcs::from_bytes(&trace_bytes)?;
+        ensure!(!block_trace.blocks.is_empty(), "The block trace is empty!");
+        Ok(block_trace)
+    }
+
+    /// Writes the block trace to the given file
+    pub fn write_to_file(&self, trace_file: impl AsRef<Path>) -> Result<()> {
+        fs::write(trace_file, bcs::to_bytes(self)?)?;
+        Ok(())
+    }
+
+    /// Returns the version of the first transaction in the trace
+    pub fn first_version(&self) -> Version {
+        self.blocks[0].first_version
+    }
+
+    /// Returns the total number of transactions in the trace
+    pub fn num_transactions(&self) -> usize {
+        self.blocks
+            .iter()
+            .map(|block| block.transactions.len())
+            .sum()
+    }
+}
+
+/// Groups the given transactions (and their events) into complete blocks, starting
+/// at the first block start. Block ending transactions are removed, as the executor
+/// re-generates them. Grouping stops at the first transaction that ends an epoch (as
+/// the executor would treat all subsequent blocks as a reconfiguration suffix), or
+/// once the max number of blocks has been recorded. Incomplete blocks (including the
+/// block ending the epoch, which has no block ending transaction) are dropped.
+pub fn group_transactions_into_blocks(
+    first_version: Version,
+    transactions_and_events: impl IntoIterator<Item = Result<(Transaction, Vec<ContractEvent>)>>,
+    max_num_blocks: usize,
+) -> Result<Vec<RecordedBlock>> {
+    let mut blocks = vec![];
+    let mut current_block: Option<RecordedBlock> = None;
+
+    for (index, transaction_and_events) in transactions_and_events.into_iter().enumerate() {
+        let (transaction, events) = transaction_and_events?;
+        let version = first_version + index as Version;
+
+        // Skip all transactions until the first block start
+        if transaction.is_block_start() {
+            current_block = Some(RecordedBlock {
+                first_version: version,
+                transactions: vec![],
+            });
+        }
+        let Some(block) = current_block.as_mut() else {
+            continue;
+        };
+
+        // Stop at the epoch change, as the replay would miss the reconfiguration
+        if events.iter().any(|event| event.is_new_epoch_event()) {
+            info!(
+                "The block at version {} ends an epoch. Stopping the trace.",
+                block.first_version
+            );
+            break;
+        }
+
+        // Check if the block has ended, or add the transaction to the block
+        if transaction.is_non_reconfig_block_ending() {
+            blocks.push(current_block.take().unwrap());
+            if blocks.len() >= max_num_blocks {
+                break;
+            }
+        } else {
+            block.transactions.push(transaction);
+        }
+    }
+
+    Ok(blocks)
+}
+
+/// Exports (up to) the given number of blocks from the source DB, starting at
+/// the first block at (or after) the given version, and writes them to the
+/// trace file.
+pub fn export_block_trace(
+    source_dir: impl AsRef<Path>,
+    begin_version: Version,
+    num_blocks: usize,
+    trace_file: impl AsRef<Path>,
+    enable_storage_sharding: bool,
+) -> Result<()> {
+    // Open the source DB (in read-only mode)
+    let config = create_node_config(
+        source_dir,
+        NO_OP_STORAGE_PRUNER_CONFIG,
+        enable_storage_sharding,
+    );
+    let source_db = CreditChainDB::open(
+        config.storage.get_dir_paths(),
+        true, /* readonly */
+        config.storage.storage_pruner_config,
+        config.storage.rocksdb_configs,
+        false, /* indexer */
+        config.storage.buffered_state_target_items,
+        config.storage.max_num_nodes_per_lru_cache_shard,
+        None,
+    )?;
+
+    // Read the transactions and events from the source DB
+    let synced_version = source_db.expect_synced_version();
+    ensure!(
+        begin_version > 0 && begin_version <= synced_version,
+        "The begin version ({}) must be in the range [1, {}]!",
+        begin_version,
+        synced_version
+    );
+    let transactions_and_events =
+        read_transactions_and_events(&source_db, begin_version, synced_version);
+
+    // Group the transactions into blocks and write the trace. The transactions are
+    // read lazily, so reading stops once enough blocks have been collected.
+    let blocks =
+        group_transactions_into_blocks(begin_version, transactions_and_events, num_blocks)?;
+    if blocks.is_empty() {
+        bail!(
+            "No complete blocks were found in the source DB, starting at version {}!",
+            begin_version
+        );
+    }
+    let block_trace = BlockTrace { blocks };
+    block_trace.write_to_file(trace_file)?;
+
+    info!(
+        "Exported {} blocks ({} transactions), starting at version {}.",
+        block_trace.blocks.len(),
+        block_trace.num_transactions(),
+        block_trace.first_version()
+    );
+    Ok(())
+}
+
+/// Returns an iterator over the transactions (and their events) in the given DB, from
+/// the begin version up to (and including) the end version. The DB rejects requests for
+/// more than `MAX_REQUEST_LIMIT` transactions, so they are read in chunks of that size.
+fn read_transactions_and_events(
+    db: &CreditChainDB,
+    begin_version: Version,
+    end_version: Version,
+) -> impl Iterator<Item = Result<(Transaction, Vec<ContractEvent>)>> + '_ {
+    (begin_version..=end_version)
+        .step_by(MAX_REQUEST_LIMIT as usize)
+        .flat_map(move |chunk_begin_version| {
+            let chunk_size = MAX_REQUEST_LIMIT.min(end_version - chunk_begin_version + 1);
+            match read_transactions_and_events_chunk(db, chunk_begin_version, chunk_size) {
+                Ok(chunk) => chunk.into_iter().map(Ok).collect(),
+                Err(error) => vec![Err(error)],
+            }
+        })
+}
+
+/// Reads the given number of transactions (and their events), starting at the given version
+fn read_transactions_and_events_chunk(
+    db: &CreditChainDB,
+    begin_version: Version,
+    num_transactions: u64,
+) -> Result<Vec<(Transaction, Vec<ContractEvent>)>> {
+    let transactions = db.get_transaction_iterator(begin_version, num_transactions)?;
+    let events = db.get_events_iterator(begin_version, num_transactions)?;
+    transactions
+        .zip(events)
+        .map(|(transaction, events)| Ok((transaction?, events?)))
+        .collect()
+}
+
+/// Creates the pre-state DB for the given block trace, by copying the source DB
+/// and truncating it to the version right before the first block in the trace.
+pub fn create_pre_state_db(
+    source_dir: impl AsRef<Path>,
+    trace_file: impl AsRef<Path>,
+    pre_state_dir: impl AsRef<Path>,
+    enable_storage_sharding: bool,
+) -> Result<()> {
+    ensure!(
+        source_dir.as_ref() != pre_state_dir.as_ref(),
+        "The pre-state DB cannot be created in the source DB directory!"
+    );
+
+    // Copy the source DB into the pre-state DB directory
+    let block_trace = BlockTrace::read_from_file(trace_file)?;
+    create_checkpoint(
+        source_dir.as_ref(),
+        pre_state_dir.as_ref(),
+        enable_storage_sharding,
+    );
+
+    // Truncate the pre-state DB to the version right before the trace
+    let target_version = block_trace.first_version() - 1;
+    let mut truncate_args = vec![
+        "truncate".to_string(),
+        "--db-dir".to_string(),
+        pre_state_dir.as_ref().display().to_string(),
+        "--target-version".to_string(),
+        target_version.to_string(),
+        "--opt-out-backup-checkpoint".to_string(),
+    ];
+    if enable_storage_sharding {
+        truncate_args.push("--enable-storage-sharding".to_string());
+    }
+    truncate::Cmd::try_parse_from(truncate_args)?.run()?;
+
+    // Verify the pre-state DB was truncated to the expected version
+    let config = create_node_config(
+        pre_state_dir,
+        NO_OP_STORAGE_PRUNER_CONFIG,
+        enable_storage_sharding,
+    );
+    let synced_version = init_db(&config).reader.expect_synced_version();
+    ensure!(
+        synced_version == target_version,
+        "Failed to truncate the pre-state DB! Expected version: {}, found version: {}",
+        target_version,
+        synced_version
+    );
+
+    info!("Created the pre-state DB at version {}.", synced_version);
+    Ok(())
+}
+
+/// Replays the blocks in the given trace on top of the pre-state DB (using a
+/// copy of the DB in the checkpoint directory), and reports the throughput and
+/// the Block-STM statistics of each block.
+pub fn replay_block_trace<V>(
+    trace_file: impl AsRef<Path>,
+    pre_state_dir: impl AsRef<Path>,
+    checkpoint_dir: impl AsRef<Path>,
+    pruner_config: PrunerConfig,
+    enable_storage_sharding: bool,
+    mut pipeline_config: PipelineConfig,
+) -> Result<SingleRunResults>
+where
+    V: VMBlockExecutor + 'static,
+{
+    // Read the trace and open a copy of the pre-state DB
+    let block_trace = BlockTrace::read_from_file(trace_file)?;
+    create_checkpoint(
+        pre_state_dir.as_ref(),
+        checkpoint_dir.as_ref(),
+        enable_storage_sharding,
+    );
+    let config = create_node_config(checkpoint_dir, pruner_config, enable_storage_sharding);
+    let db = init_db(&config);
+
+    // Verify the DB state matches the start of the trace
+    let start_version = db.reader.expect_synced_version();
+    ensure!(
+        start_version + 1 == block_trace.first_version(),
+        "The pre-state DB doesn't match the block trace! DB version: {}, first trace version: {}",
+        start_version,
+        block_trace.first_version()
+    );
+
+    // Recorded blocks contain failed transactions, so aborts must be allowed
+    pipeline_config.allow_aborts = true;
+    pipeline_config.collect_block_executor_stats = true;
+
+    // Create the pipeline
+    let num_blocks = block_trace.blocks.len();
+    let executor = BlockExecutor::<V>::new(db.clone());
+    let (pipeline, block_sender) =
+        Pipeline::new(executor, start_version, &pipeline_config, Some(num_blocks));
+    let block_executor_stats = pipeline.block_executor_stats();
+
+    // Send the recorded blocks through the pipeline
+    let mut overall_measuring = OverallMeasuring::start();
+    let first_versions: Vec<_> = block_trace
+        .blocks
+        .iter()
+        .map(|block| block.first_version)
+        .collect();
+    for block in block_trace.blocks {
+        block_sender.send(block.transactions)?;
+    }
+    drop(block_sender);
+
+    // Wait for the pipeline to finish
+    if pipeline_config.generate_then_execute {
+        overall_measuring.start_time = Instant::now();
+    }
+    pipeline.start_pipeline_processing();
+    info!("Waiting for pipeline to finish");
+    let (num_pipeline_txns, staged_results, staged_events) = pipeline.join();
+
+    // Calculate and print the overall results (excluding the block ending transactions)
+    let num_txns = if !pipeline_config.skip_commit {
+        db.reader.expect_synced_version() - start_version - num_blocks as u64
+    } else {
+        num_pipeline_txns.unwrap_or_default()
+    };
+    let overall_results =
+        overall_measuring.elapsed("Overall".to_string(), "replay".to_string(), num_txns);
+    overall_results.print_end();
+    OverallMeasurement::print_end_table(&staged_results, &overall_results);
+
+    // Print the Block-STM statistics of each block
+    print_block_executor_stats(&first_versions, &block_executor_stats.lock());
+
+    Ok(SingleRunResults {
+        measurements: overall_results,
+        per_stage_measurements: staged_results,
+        per_stage_events: staged_events,
+    })
+}
+
+/// Prints the Block-STM statistics of each replayed block, followed
+/// by the blocks with the most speculative aborts (per transaction).
+fn print_block_executor_stats(first_versions: &[Version], block_stats: &[BlockExecutorStats]) {
+    fn print_header() {
+        println!(
+            "{: >12}{: >12}{: >12}{: >12}{: >12}{: >12}{: >12}{: >12}",
+            "version", "txns", "exe_ms", "TPS", "dep_waits", "aborts", "aborts/txn", "seq_exe"
+        );
+    }
+    fn print_row(first_version: Version, stats: &BlockExecutorStats) {
+        println!(
+            "{: >12}{: >12}{: >12.2}{: >12.2}{: >12}{: >12}{: >12.3}{: >12}",
+            first_version,
+            stats.num_txns,
+            stats.execution_time * 1000.0,
+            stats.tps(),
+            stats.num_dependency_waits,
+            stats.num_speculative_aborts,
+            stats.speculative_aborts_per_txn(),
+            stats.num_sequential_executions,
+        );
+    }
+
+    println!("Per-block execution stats:");
+    print_header();
+    for stats in block_stats {
+        print_row(first_versions[stats.block_index], stats);
+    }
+
+    println!("Blocks with the most speculative aborts per transaction:");
+    print_header();
+    let mut most_conflicting_blocks: Vec<_> = block_stats.iter().collect();
+    most_conflicting_blocks.sort_by(|stats_1, stats_2| {
+        stats_2
+            .speculative_aborts_per_txn()
+            .total_cmp(&stats_1.speculative_aborts_per_txn())
+    });
+    for stats in most_conflicting_blocks
+        .into_iter()
+        .take(NUM_MOST_CONFLICTING_BLOCKS_TO_PRINT)
+    {
+        print_row(first_versions[stats.block_index], stats);
+    }
+}
+
+/// Creates a node config with the given storage settings
+fn create_node_config(
+    db_dir: impl AsRef<Path>,
+    pruner_config: PrunerConfig,
+    enable_storage_sharding: bool,
+) -> NodeConfig {
+    let mut config = NodeConfig::default();
+    config.storage.dir = db_dir.as_ref().to_path_buf();
+    config.storage.storage_pruner_config = pruner_config;
+    config.storage.rocksdb_configs.enable_storage_sharding = enable_storage_sharding;
+    config
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::replay::{group_transactions_into_blocks, BlockTrace, RecordedBlock};
+    use creditchain_crypto::HashValue;
+    use creditchain_temppath::TempPath;
+    use creditchain_types::{
+        account_address::AccountAddress,
+        account_config::NEW_EPOCH_EVENT_V2_MOVE_TYPE_TAG,
+        block_metadata::BlockMetadata,
+        contract_event::ContractEvent,
+        transaction::{ChangeSet, Transaction, Version, WriteSetPayload},
+        write_set::WriteSet,
+    };
+
+    #[test]
+    fn test_group_transactions_into_blocks() {
+        // Create several blocks (starting in the middle of a block)
+        let transactions = vec![
+            create_user_transaction_placeholder(),
+            create_block_ending_transaction(),
+            create_block_start_transaction(1),
+            create_block_ending_transaction(),
+            create_block_start_transaction(2),
+            create_user_transaction_placeholder(),
+            create_block_ending_transaction(),
+            create_block_start_transaction(3),
+        ];
+        let transactions_and_events = transactions
+            .into_iter()
+            .map(|transaction| (transaction, vec![]))
+            .collect::<Vec<_>>();
+
+        // Group the transactions and verify only the complete blocks are recorded
+        let blocks = group_transactions_into_blocks(
+            10,
+            transactions_and_events.clone().into_iter().map(Ok),
+            100,
+        )
+        .unwrap();
+        assert_eq!(blocks.len(), 2);
+        assert_eq!(blocks[0].first_version, 12);
+        assert_eq!(blocks[0].transactions.len(), 1);
+        assert_eq!(blocks[1].first_version, 14);
+        assert_eq!(blocks[1].transactions.len(), 2);
+
+        // Verify the max number of blocks is respected
+        let blocks =
+            group_transactions_into_blocks(10, transactions_and_events.into_iter().map(Ok), 1)
+                .unwrap();
+        assert_eq!(blocks.len(), 1);
+        assert_eq!(blocks[0].first_version, 12);
+    }
+
+    #[test]
+    fn test_group_transactions_into_blocks_epoch_change() {
+        // Create several blocks, where the second block ends the epoch. As on chain, the
+        // reconfiguration block has no block ending transaction, and is directly followed
+        // by the first block of the next epoch.
+        let new_epoch_event =
+            ContractEvent::new_v2(NEW_EPOCH_EVENT_V2_MOVE_TYPE_TAG.clone(), vec![]).unwrap();
+        let transactions_and_events = vec![
+            Ok((create_block_start_transaction(1), vec![])),
+            Ok((create_block_ending_transaction(), vec![])),
+            Ok((create_block_start_transaction(2), vec![])),
+            Ok((create_user_transaction_placeholder(), vec![new_epoch_event])),
+            Ok((create_block_start_transaction(3), vec![])),
+            Ok((create_block_ending_transaction(), vec![])),
+            Ok((create_block_start_transaction(4), vec![])),
+            Ok((create_block_ending_transaction(), vec![])),
+        ];
+
+        // Group the transactions and verify the trace stops before the epoch change
+        let blocks = group_transactions_into_blocks(0, transactions_and_events, 100).unwrap();
+        assert_eq!(blocks.len(), 1);
+        assert_eq!(blocks[0].first_version, 0);
+    }
+
+    #[test]
+    fn test_block_trace_file() {
+        // Create a block trace
+        let block_trace = BlockTrace {
+            blocks: vec![RecordedBlock {
+                first_version: 100,
+                transactions: vec![create_block_start_transaction(1)],
+            }],
+        };
+
+        // Write the trace to a file, read it back and verify it matches
+        let trace_file = TempPath::new();
+        block_trace.write_to_file(trace_file.path()).unwrap();
+        let read_block_trace = BlockTrace::read_from_file(trace_file.path()).unwrap();
+        assert_eq!(read_block_trace, block_trace);
+        assert_eq!(read_block_trace.first_version(), 100);
+        assert_eq!(read_block_trace.num_transactions(), 1);
+    }
+
+    /// Creates a block metadata transaction for the given round
+    fn create_block_start_transaction(round: u64) -> Transaction {
+        Transaction::BlockMetadata(BlockMetadata::new(
+            HashValue::random(),
+            1,
+            round,
+            AccountAddress::random(),
+            vec![],
+            vec![],
+            round as Version,
+        ))
+    }
+
+    /// Creates a block ending (state checkpoint) transaction
+    fn create_block_ending_transaction() -> Transaction {
+        Transaction::StateCheckpoint(HashValue::random())
+    }
+
+    /// Creates a transaction that stands in for a user transaction (the
+    /// grouping logic only cares about block starts and endings).
+    fn create_user_transaction_placeholder() -> Transaction {
+        Transaction::GenesisTransaction(WriteSetPayload::Direct(ChangeSet::new(
+            WriteSet::default(),
+            vec![],
+        )))
+    }
+}
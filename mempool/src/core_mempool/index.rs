@@ -516,11 +516,15 @@ impl MultiBucketTimelineIndex {
 
     #[inline]
     pub(crate) fn get_bucket(&self, ranking_score: RankingScore) -> &str {
-        let index = self
-            .bucket_mins
+        self.bucket_mins_to_string[self.get_bucket_index(ranking_score)].as_str()
+    }
+
+    /// Returns the index of the timeline (i.e., broadcast bucket) for the given ranking score
+    #[inline]
+    pub(crate) fn get_bucket_index(&self, ranking_score: RankingScore) -> usize {
+        self.bucket_mins
             .binary_search(&ranking_score)
-            .unwrap_or_else(|i| i - 1);
-        self.bucket_mins_to_string[index].as_str()
+            .unwrap_or_else(|i| i - 1)
     }
 }
 
@@ -0,0 +1,208 @@
+// Copyright © CreditChain Research Team
+// SPDX-License-Identifier: Apache-2.0
+
+//! Tracks the lifecycle of recently accepted transactions (e.g., when they were parked,
+//! broadcast and pulled by consensus), so that clients can tell why a transaction is pending.
+use creditchain_config::network_id::PeerNetworkId;
+use creditchain_crypto::HashValue;
+use creditchain_types::vm_status::DiscardedVMStatus;
+use serde::Serialize;
+use std::{
+    collections::{HashMap, HashSet, VecDeque},
+    time::SystemTime,
+};
+
+/// The maximum number of lifecycle events tracked for a single transaction
+const MAX_EVENTS_PER_TRANSACTION: usize = 32;
+
+/// A lifecycle state of a transaction (as observed by this node)
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub enum TransactionLifecycleState {
+    /// The transaction was accepted into mempool
+    Accepted,
+    /// The transaction was parked, waiting for the given (missing) sequence number
+    Parked { expected_sequence_number: u64 },
+    /// The transaction was broadcast to the given number of peers
+    Broadcast { num_peers: usize },
+    /// The transaction was pulled by consensus (e.g., into a quorum store batch)
+    PulledIntoBatch,
+    /// The transaction was included in a block proposal
+    Proposed,
+    /// The transaction was rejected by the VM during execution
+    Rejected { reason: DiscardedVMStatus },
+    /// The transaction was evicted from mempool (e.g., by an operator, or to make room for
+    /// other transactions)
+    Evicted,
+    /// The transaction expired and was garbage collected
+    Expired,
+}
+
+/// A lifecycle state of a transaction, and the time at which it was entered
+#[derive(Clone, Debug, Serialize)]
+pub struct TransactionLifecycleEvent {
+    pub state: TransactionLifecycleState,
+    pub timestamp: SystemTime,
+}
+
+/// The lifecycle of a single tracked transaction
+#[derive(Default)]
+struct TransactionLifecycle {
+    events: Vec<TransactionLifecycleEvent>,
+    broadcast_peers: HashSet<PeerNetworkId>,
+}
+
+/// A bounded tracker of transaction lifecycles. Transactions are tracked from the
+/// time they are accepted, and the oldest transactions are evicted once full.
+pub(crate) struct TransactionLifecycleTracker {
+    capacity: usize,
+    lifecycles: HashMap<HashValue, TransactionLifecycle>,
+    insertion_order: VecDeque<HashValue>,
+}
+
+impl TransactionLifecycleTracker {
+    pub(crate) fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            lifecycles: HashMap::new(),
+            insertion_order: VecDeque::new(),
+        }
+    }
+
+    /// Starts tracking the given transaction (as accepted)
+    pub(crate) fn record_accepted(&mut self, hash: HashValue) {
+        if self.capacity == 0 || self.lifecycles.contains_key(&hash) {
+            return;
+        }
+
+        // Evict the oldest transactions if the tracker is full
+        while self.insertion_order.len() >= self.capacity {
+            if let Some(oldest_hash) = self.insertion_order.pop_front() {
+                self.lifecycles.remove(&oldest_hash);
+            }
+        }
+
+        self.insertion_order.push_back(hash);
+        self.lifecycles
+            .entry(hash)
+            .or_default()
+            .events
+            .push(TransactionLifecycleEvent {
+                state: TransactionLifecycleState::Accepted,
+                timestamp: SystemTime::now(),
+            });
+    }
+
+    /// Records the given state for the transaction (if it is tracked). Repeated
+    /// states are ignored.
+    pub(crate) fn record(&mut self, hash: &HashValue, state: TransactionLifecycleState) {
+        if let Some(lifecycle) = self.lifecycles.get_mut(hash) {
+            let is_repeated = lifecycle
+                .events
+                .last()
+                .is_some_and(|event| event.state == state);
+            if !is_repeated && lifecycle.events.len() < MAX_EVENTS_PER_TRANSACTION {
+                lifecycle.events.push(TransactionLifecycleEvent {
+                    state,
+                    timestamp: SystemTime::now(),
+                });
+            }
+        }
+    }
+
+    /// Records that the transaction was broadcast to the given peer (if it is tracked).
+    /// A single broadcast event is kept per transaction, with the number of distinct peers.
+    pub(crate) fn record_broadcast(&mut self, hash: &HashValue, peer: PeerNetworkId) {
+        let Some(lifecycle) = self.lifecycles.get_mut(hash) else {
+            return;
+        };
+        if !lifecycle.broadcast_peers.insert(peer) {
+            return; // The transaction was already broadcast to the peer
+        }
+
+        let num_peers = lifecycle.broadcast_peers.len();
+        let broadcast_event = lifecycle
+            .events
+            .iter_mut()
+            .find(|event| matches!(event.state, TransactionLifecycleState::Broadcast { .. }));
+        match broadcast_event {
+            Some(event) => event.state = TransactionLifecycleState::Broadcast { num_peers },
+            None => self.record(hash, TransactionLifecycleState::Broadcast { num_peers }),
+        }
+    }
+
+    /// Returns the lifecycle events of the given transaction (if it is tracked)
+    pub(crate) fn get_lifecycle(&self, hash: &HashValue) -> Option<Vec<TransactionLifecycleEvent>> {
+        self.lifecycles
+            .get(hash)
+            .map(|lifecycle| lifecycle.events.clone())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use creditchain_config::network_id::NetworkId;
+    use creditchain_types::PeerId;
+
+    #[test]
+    fn test_lifecycle_tracking() {
+        let mut tracker = TransactionLifecycleTracker::new(10);
+
+        // Untracked transactions are ignored
+        let hash = HashValue::random();
+        tracker.record(&hash, TransactionLifecycleState::Proposed);
+        assert!(tracker.get_lifecycle(&hash).is_none());
+
+        // Record several states (including repeated states)
+        tracker.record_accepted(hash);
+        let parked = TransactionLifecycleState::Parked {
+            expected_sequence_number: 5,
+        };
+        tracker.record(&hash, parked.clone());
+        tracker.record(&hash, parked.clone());
+        tracker.record(&hash, TransactionLifecycleState::PulledIntoBatch);
+        let states: Vec<_> = tracker
+            .get_lifecycle(&hash)
+            .unwrap()
+            .into_iter()
+            .map(|event| event.state)
+            .collect();
+        assert_eq!(states, vec![
+            TransactionLifecycleState::Accepted,
+            parked,
+            TransactionLifecycleState::PulledIntoBatch
+        ]);
+
+        // Broadcasts are counted per distinct peer
+        let peer_1 = PeerNetworkId::new(NetworkId::Public, PeerId::random());
+        let peer_2 = PeerNetworkId::new(NetworkId::Public, PeerId::random());
+        tracker.record_broadcast(&hash, peer_1);
+        tracker.record_broadcast(&hash, peer_1);
+        tracker.record_broadcast(&hash, peer_2);
+        let events = tracker.get_lifecycle(&hash).unwrap();
+        assert_eq!(events.len(), 4);
+        assert_eq!(
+            events.last().unwrap().state,
+            TransactionLifecycleState::Broadcast { num_peers: 2 }
+        );
+    }
+
+    #[test]
+    fn test_lifecycle_eviction() {
+        let mut tracker = TransactionLifecycleTracker::new(2);
+        let hashes: Vec<_> = (0..3).map(|_| HashValue::random()).collect();
+        for hash in &hashes {
+            tracker.record_accepted(*hash);
+        }
+
+        // The oldest transaction is evicted
+        assert!(tracker.get_lifecycle(&hashes[0]).is_none());
+        assert!(tracker.get_lifecycle(&hashes[1]).is_some());
+        assert!(tracker.get_lifecycle(&hashes[2]).is_some());
+
+        // Nothing is tracked if the tracker is disabled
+        let mut tracker = TransactionLifecycleTracker::new(0);
+        tracker.record_accepted(hashes[0]);
+        assert!(tracker.get_lifecycle(&hashes[0]).is_none());
+    }
+}
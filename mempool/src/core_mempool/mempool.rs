@@ -7,7 +7,8 @@
 use crate::{
     core_mempool::{
         index::TxnPointer,
-        transaction::{InsertionInfo, MempoolTransaction, TimelineState},
+        lifecycle::{TransactionLifecycleEvent, TransactionLifecycleState},
+        transaction::{InsertionInfo, MempoolTransaction, MempoolTransactionInfo, TimelineState},
         transaction_store::{sender_bucket, TransactionStore},
     },
     counters,
@@ -17,7 +18,8 @@ use crate::{
         MempoolSenderBucket, MultiBucketTimelineIndexIds, TimelineIndexIdentifier,
     },
 };
-use creditchain_config::config::NodeConfig;
+use anyhow::ensure;
+use creditchain_config::{config::NodeConfig, network_id::PeerNetworkId};
 use creditchain_consensus_types::common::{TransactionInProgress, TransactionSummary};
 use creditchain_crypto::HashValue;
 use creditchain_logger::prelude::*;
@@ -33,11 +35,17 @@ use std::{
     time::{Duration, Instant, SystemTime},
 };
 
+// The max number of senders that can be blocked at the same time
+pub(crate) const MAX_NUM_BLOCKED_SENDERS: usize = 10_000;
+
 pub struct Mempool {
     // Stores the metadata of all transactions in mempool (of all states).
     pub(crate) transactions: TransactionStore,
 
     pub system_transaction_timeout: Duration,
+
+    // Senders whose transactions are rejected (until the given time), e.g., by an operator
+    blocked_senders: HashMap<AccountAddress, SystemTime>,
 }
 
 impl Mempool {
@@ -47,6 +55,7 @@ impl Mempool {
             system_transaction_timeout: Duration::from_secs(
                 config.mempool.system_transaction_timeout_secs,
             ),
+            blocked_senders: HashMap::new(),
         }
     }
 
@@ -117,6 +126,11 @@ impl Mempool {
         hash: &HashValue,
         reason: &DiscardedVMStatus,
     ) {
+        self.transactions.record_lifecycle_state(
+            hash,
+            TransactionLifecycleState::Rejected { reason: *reason },
+        );
+
         if *reason == DiscardedVMStatus::SEQUENCE_NUMBER_TOO_NEW {
             self.log_reject_transaction(sender, replay_protector, counters::COMMIT_IGNORED_LABEL);
             // Do not remove the transaction from mempool
@@ -307,6 +321,14 @@ impl Mempool {
             committed_seq_number = account_sequence_number
         );
 
+        // don't accept transactions from blocked senders
+        if let Some(blocked_until) = self.get_sender_block_expiration(&txn.sender()) {
+            return MempoolStatus::new(MempoolStatusCode::RejectedByFilter).with_message(format!(
+                "transaction sender is blocked until {} (secs since epoch)",
+                creditchain_infallible::duration_since_epoch_at(&blocked_until).as_secs(),
+            ));
+        }
+
         if let ReplayProtector::SequenceNumber(txn_seq_num) = txn.replay_protector() {
             // don't accept old transactions (e.g. seq is less than account's current seq_number)
             match &account_sequence_number {
@@ -587,10 +609,11 @@ impl Mempool {
 
     /// Periodic core mempool garbage collection.
     /// Removes all expired transactions and clears expired entries in metrics
-    /// cache and sequence number cache.
+    /// cache, sequence number cache and blocked senders.
     pub(crate) fn gc(&mut self) {
         let now = creditchain_infallible::duration_since_epoch();
         self.transactions.gc_by_system_ttl(now);
+        self.gc_blocked_senders();
     }
 
     /// Garbage collection based on client-specified expiration time.
@@ -648,6 +671,146 @@ impl Mempool {
         self.transactions.gen_snapshot()
     }
 
+    /// Returns the senders of all transactions in mempool (e.g., for persisting them to disk)
+    pub(crate) fn get_senders(&self) -> Vec<AccountAddress> {
+        self.transactions.get_senders()
+    }
+
+    /// Returns all transactions in mempool of the given senders
+    pub(crate) fn get_transactions_of_senders(
+        &self,
+        senders: &[AccountAddress],
+    ) -> Vec<SignedTransaction> {
+        self.transactions.get_transactions_of_senders(senders)
+    }
+
+    /// Returns the detailed state of all transactions in mempool for the given account
+    pub(crate) fn get_transaction_infos_by_account(
+        &self,
+        address: &AccountAddress,
+    ) -> Vec<MempoolTransactionInfo> {
+        self.transactions.get_transaction_infos_by_account(address)
+    }
+
+    /// Returns the detailed state of the transaction with the given hash (if it exists)
+    pub(crate) fn get_transaction_info_by_hash(
+        &self,
+        hash: HashValue,
+    ) -> Option<MempoolTransactionInfo> {
+        self.transactions.get_transaction_info_by_hash(hash)
+    }
+
+    /// Records the given lifecycle state for the transactions (if they are tracked)
+    pub(crate) fn record_lifecycle_state(
+        &mut self,
+        hashes: impl Iterator<Item = HashValue>,
+        state: TransactionLifecycleState,
+    ) {
+        for hash in hashes {
+            self.transactions
+                .record_lifecycle_state(&hash, state.clone());
+        }
+    }
+
+    /// Records that the given transactions were broadcast to the peer
+    pub(crate) fn record_broadcast(
+        &mut self,
+        hashes: impl Iterator<Item = HashValue>,
+        peer: PeerNetworkId,
+    ) {
+        self.transactions.record_broadcast(hashes, peer);
+    }
+
+    /// Returns the lifecycle events of the given transaction (if it was recently accepted)
+    pub(crate) fn get_transaction_lifecycle(
+        &self,
+        hash: &HashValue,
+    ) -> Option<Vec<TransactionLifecycleEvent>> {
+        self.transactions.get_lifecycle(hash)
+    }
+
+    /// Evicts the transaction with the given hash. Returns true iff the transaction was found.
+    pub(crate) fn evict_transaction(&mut self, hash: HashValue) -> bool {
+        match self.transactions.evict_transaction(hash) {
+            Some((sender, replay_protector)) => {
+                counters::CORE_MEMPOOL_ADMIN_EVICTED_TXNS.inc();
+                info!(
+                    LogSchema::new(LogEntry::AdminAction)
+                        .txns(TxnsLog::new_txn(sender, replay_protector)),
+                    hash = hash,
+                    "Evicted transaction from mempool"
+                );
+                true
+            },
+            None => false,
+        }
+    }
+
+    /// Evicts all transactions of the given sender, and returns the evicted transaction hashes
+    pub(crate) fn evict_sender(&mut self, sender: &AccountAddress) -> Vec<HashValue> {
+        let evicted_hashes = self.transactions.evict_account_transactions(sender);
+        counters::CORE_MEMPOOL_ADMIN_EVICTED_TXNS.inc_by(evicted_hashes.len() as u64);
+        info!(
+            LogSchema::new(LogEntry::AdminAction).account(*sender),
+            num_txns = evicted_hashes.len(),
+            "Evicted all sender transactions from mempool"
+        );
+        evicted_hashes
+    }
+
+    /// Rejects all new transactions from the given sender for the given duration.
+    /// Fails if too many senders are already blocked (and the sender is not one of them).
+    pub(crate) fn block_sender(
+        &mut self,
+        sender: AccountAddress,
+        duration: Duration,
+    ) -> anyhow::Result<()> {
+        // Verify that there is room to block the sender (removing expired blocks first)
+        if !self.blocked_senders.contains_key(&sender)
+            && self.blocked_senders.len() >= MAX_NUM_BLOCKED_SENDERS
+        {
+            self.gc_blocked_senders();
+            ensure!(
+                self.blocked_senders.len() < MAX_NUM_BLOCKED_SENDERS,
+                "Unable to block sender {}! The max number of blocked senders ({}) has been reached.",
+                sender,
+                MAX_NUM_BLOCKED_SENDERS
+            );
+        }
+
+        let blocked_until = SystemTime::now() + duration;
+        self.blocked_senders.insert(sender, blocked_until);
+        info!(
+            LogSchema::new(LogEntry::AdminAction).account(sender),
+            blocked_secs = duration.as_secs(),
+            "Blocked sender from submitting transactions to mempool"
+        );
+        Ok(())
+    }
+
+    /// Removes all expired blocks of senders
+    fn gc_blocked_senders(&mut self) {
+        let now = SystemTime::now();
+        self.blocked_senders
+            .retain(|_, blocked_until| *blocked_until > now);
+    }
+
+    /// Returns the time until which the sender is blocked (if it is blocked).
+    /// Expired blocks are removed.
+    fn get_sender_block_expiration(&mut self, sender: &AccountAddress) -> Option<SystemTime> {
+        let blocked_until = *self.blocked_senders.get(sender)?;
+        if blocked_until <= SystemTime::now() {
+            self.blocked_senders.remove(sender);
+            return None;
+        }
+        Some(blocked_until)
+    }
+
+    #[cfg(test)]
+    pub fn get_num_blocked_senders(&self) -> usize {
+        self.blocked_senders.len()
+    }
+
     #[cfg(test)]
     pub fn get_parking_lot_size(&self) -> usize {
         self.transactions.get_parking_lot_size()
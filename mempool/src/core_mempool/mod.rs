@@ -3,16 +3,21 @@
 // SPDX-License-Identifier: Apache-2.0
 
 mod index;
+mod lifecycle;
 mod mempool;
 pub mod transaction;
 mod transaction_store;
 
 pub use self::{
-    index::TimelineId, mempool::Mempool as CoreMempool, transaction::TimelineState,
+    index::TimelineId,
+    lifecycle::{TransactionLifecycleEvent, TransactionLifecycleState},
+    mempool::Mempool as CoreMempool,
+    transaction::{MempoolTransactionInfo, TimelineState},
     transaction_store::TXN_INDEX_ESTIMATED_BYTES,
 };
 #[cfg(test)]
 pub use self::{
+    mempool::MAX_NUM_BLOCKED_SENDERS,
     transaction::{MempoolTransaction, SubmittedBy},
     transaction_store::sender_bucket,
 };
@@ -2,7 +2,12 @@
 // Parts of the project are originally copyright © Meta Platforms, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::{core_mempool::TXN_INDEX_ESTIMATED_BYTES, counters, network::BroadcastPeerPriority};
+use crate::{
+    core_mempool::{TimelineId, TXN_INDEX_ESTIMATED_BYTES},
+    counters,
+    network::BroadcastPeerPriority,
+    shared_mempool::types::MempoolSenderBucket,
+};
 use creditchain_crypto::HashValue;
 use creditchain_types::{
     account_address::AccountAddress,
@@ -147,6 +152,31 @@ impl InsertionInfo {
     }
 }
 
+/// The detailed state of a transaction in mempool (e.g., for inspection by node operators)
+#[derive(Clone, Debug, Serialize)]
+pub struct MempoolTransactionInfo {
+    pub hash: HashValue,
+    pub sender: AccountAddress,
+    pub replay_protector: ReplayProtector,
+    pub gas_unit_price: u64,
+    pub ranking_score: u64,
+    pub expiration_timestamp_secs: u64,
+    /// Whether the transaction is in the parking lot (i.e., it is not ready for consensus)
+    pub parked: bool,
+    /// The reason the transaction is parked (if it is parked)
+    pub park_reason: Option<String>,
+    /// The position of the transaction in the broadcast timeline (if it is ready for broadcast)
+    pub timeline_id: Option<TimelineId>,
+    /// Whether the transaction will ever be broadcast (see `TimelineState::NonQualified`)
+    pub broadcast_eligible: bool,
+    pub sender_bucket: MempoolSenderBucket,
+    pub timeline_bucket_index: usize,
+    pub submitted_by: &'static str,
+    pub insertion_time_usecs: u64,
+    pub ready_time_usecs: u64,
+    pub park_time_usecs: Option<u64>,
+}
+
 #[cfg(test)]
 mod test {
     use crate::{
@@ -8,8 +8,11 @@ use crate::{
             AccountTransactions, MultiBucketTimelineIndex, ParkingLotIndex, PriorityIndex,
             PriorityQueueIter, TTLIndex,
         },
+        lifecycle::{
+            TransactionLifecycleEvent, TransactionLifecycleState, TransactionLifecycleTracker,
+        },
         mempool::Mempool,
-        transaction::{InsertionInfo, MempoolTransaction, TimelineState},
+        transaction::{InsertionInfo, MempoolTransaction, MempoolTransactionInfo, TimelineState},
     },
     counters::{self, BROADCAST_BATCHED_LABEL, BROADCAST_READY_LABEL, CONSENSUS_READY_LABEL},
     logging::{LogEntry, LogEvent, LogSchema, TxnsLog},
@@ -18,7 +21,7 @@ use crate::{
         MempoolSenderBucket, MultiBucketTimelineIndexIds, TimelineIndexIdentifier,
     },
 };
-use creditchain_config::config::MempoolConfig;
+use creditchain_config::{config::MempoolConfig, network_id::PeerNetworkId};
 use creditchain_crypto::HashValue;
 use creditchain_logger::{prelude::*, Level};
 use creditchain_types::{
@@ -86,6 +89,8 @@ pub struct TransactionStore {
     hash_index: HashMap<HashValue, (AccountAddress, ReplayProtector)>,
     // estimated size in bytes
     size_bytes: usize,
+    // Lifecycle states of recently accepted transactions (including those no longer in mempool)
+    lifecycle_tracker: TransactionLifecycleTracker,
 
     // configuration
     capacity: usize,
@@ -127,6 +132,7 @@ impl TransactionStore {
             hash_index: HashMap::new(),
             // estimated size in bytes
             size_bytes: 0,
+            lifecycle_tracker: TransactionLifecycleTracker::new(config.lifecycle_tracker_capacity),
 
             // configuration
             capacity: config.capacity,
@@ -353,6 +359,8 @@ impl TransactionStore {
                 self.account_sequence_numbers.insert(address, acc_seq_num);
             }
             self.size_bytes += txn.get_estimated_bytes();
+            self.lifecycle_tracker
+                .record_accepted(txn.get_committed_hash());
             txns.insert(txn);
             self.track_indices();
         }
@@ -437,6 +445,10 @@ impl TransactionStore {
                     );
                     evicted_bytes += txn.get_estimated_bytes() as u64;
                     evicted_txns += 1;
+                    self.lifecycle_tracker.record(
+                        &txn.get_committed_hash(),
+                        TransactionLifecycleState::Evicted,
+                    );
                     self.index_remove(&txn);
                     if !self.is_full() {
                         break;
@@ -618,6 +630,12 @@ impl TransactionStore {
                     TimelineState::Ready(_) => {},
                     _ => {
                         self.parking_lot_index.insert(txn);
+                        self.lifecycle_tracker.record(
+                            &txn.get_committed_hash(),
+                            TransactionLifecycleState::Parked {
+                                expected_sequence_number: min_seq,
+                            },
+                        );
                         parking_lot_txns += 1;
                     },
                 }
@@ -958,6 +976,12 @@ impl TransactionStore {
                     // mark all following txns as non-ready, i.e. park them
                     for (_, t) in txns.seq_num_range_mut((park_range_start, park_range_end)) {
                         self.parking_lot_index.insert(t);
+                        self.lifecycle_tracker.record(
+                            &t.get_committed_hash(),
+                            TransactionLifecycleState::Parked {
+                                expected_sequence_number: seq_num,
+                            },
+                        );
                         self.priority_index.remove(t);
                         let sender_bucket = sender_bucket(&t.get_sender(), self.num_sender_buckets);
                         self.timeline_index
@@ -993,6 +1017,10 @@ impl TransactionStore {
                     }
 
                     // remove txn
+                    self.lifecycle_tracker.record(
+                        &txn.get_committed_hash(),
+                        TransactionLifecycleState::Expired,
+                    );
                     self.index_remove(&txn);
                 }
             }
@@ -1039,6 +1067,217 @@ impl TransactionStore {
         txns_log
     }
 
+    /// Returns the senders of all transactions in the store
+    pub(crate) fn get_senders(&self) -> Vec<AccountAddress> {
+        self.transactions.keys().copied().collect()
+    }
+
+    /// Returns all transactions in the store of the given senders (grouped by account)
+    pub(crate) fn get_transactions_of_senders(
+        &self,
+        senders: &[AccountAddress],
+    ) -> Vec<SignedTransaction> {
+        senders
+            .iter()
+            .filter_map(|sender| self.transactions.get(sender))
+            .flat_map(|txns| txns.values().map(|txn| txn.txn.clone()))
+            .collect()
+    }
+
+    /// Returns the detailed state of all transactions in the store for the given account
+    pub(crate) fn get_transaction_infos_by_account(
+        &self,
+        address: &AccountAddress,
+    ) -> Vec<MempoolTransactionInfo> {
+        self.transactions
+            .get(address)
+            .map(|txns| {
+                txns.values()
+                    .map(|txn| self.get_transaction_info(txn))
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Returns the detailed state of the transaction with the given hash (if it exists)
+    pub(crate) fn get_transaction_info_by_hash(
+        &self,
+        hash: HashValue,
+    ) -> Option<MempoolTransactionInfo> {
+        let (address, replay_protector) = self.hash_index.get(&hash)?;
+        self.get_mempool_txn(address, *replay_protector)
+            .map(|txn| self.get_transaction_info(txn))
+    }
+
+    /// Returns the detailed state of the given transaction
+    fn get_transaction_info(&self, txn: &MempoolTransaction) -> MempoolTransactionInfo {
+        let sender = txn.get_sender();
+        let replay_protector = txn.get_replay_protector();
+        let hash = txn.get_committed_hash();
+
+        // Identify the reason the transaction is parked (if it is)
+        let parked = self
+            .parking_lot_index
+            .contains(&sender, replay_protector, hash);
+        let park_reason = parked.then(|| match replay_protector {
+            ReplayProtector::SequenceNumber(sequence_number) => {
+                let account_sequence_number =
+                    self.get_account_sequence_number(&sender).map_or(0, |v| *v);
+                let mut missing_sequence_number = account_sequence_number;
+                while missing_sequence_number < sequence_number
+                    && self
+                        .get_mempool_txn(
+                            &sender,
+                            ReplayProtector::SequenceNumber(missing_sequence_number),
+                        )
+                        .is_some()
+                {
+                    missing_sequence_number += 1;
+                }
+                format!(
+                    "Waiting for sequence number {} (account sequence number: {})",
+                    missing_sequence_number, account_sequence_number
+                )
+            },
+            ReplayProtector::Nonce(_) => "Unknown".to_string(),
+        });
+
+        // Identify the broadcast timeline of the transaction
+        let sender_bucket = sender_bucket(&sender, self.num_sender_buckets);
+        let timeline_bucket_index = self
+            .timeline_index
+            .get(&sender_bucket)
+            .map_or(0, |timeline| timeline.get_bucket_index(txn.ranking_score));
+        let timeline_id = match txn.timeline_state {
+            TimelineState::Ready(timeline_id) => Some(timeline_id),
+            _ => None,
+        };
+
+        let insertion_info = &txn.insertion_info;
+        MempoolTransactionInfo {
+            hash,
+            sender,
+            replay_protector,
+            gas_unit_price: txn.txn.gas_unit_price(),
+            ranking_score: txn.ranking_score,
+            expiration_timestamp_secs: txn.txn.expiration_timestamp_secs(),
+            parked,
+            park_reason,
+            timeline_id,
+            broadcast_eligible: txn.timeline_state != TimelineState::NonQualified,
+            sender_bucket,
+            timeline_bucket_index,
+            submitted_by: insertion_info.submitted_by_label(),
+            insertion_time_usecs: creditchain_infallible::duration_since_epoch_at(
+                &insertion_info.insertion_time,
+            )
+            .as_micros() as u64,
+            ready_time_usecs: creditchain_infallible::duration_since_epoch_at(
+                &insertion_info.ready_time,
+            )
+            .as_micros() as u64,
+            park_time_usecs: insertion_info.park_time.map(|park_time| {
+                creditchain_infallible::duration_since_epoch_at(&park_time).as_micros() as u64
+            }),
+        }
+    }
+
+    /// Records the given lifecycle state for the transaction (if it is tracked)
+    pub(crate) fn record_lifecycle_state(
+        &mut self,
+        hash: &HashValue,
+        state: TransactionLifecycleState,
+    ) {
+        self.lifecycle_tracker.record(hash, state);
+    }
+
+    /// Records that the given transactions were broadcast to the peer
+    pub(crate) fn record_broadcast(
+        &mut self,
+        hashes: impl Iterator<Item = HashValue>,
+        peer: PeerNetworkId,
+    ) {
+        for hash in hashes {
+            self.lifecycle_tracker.record_broadcast(&hash, peer);
+        }
+    }
+
+    /// Returns the lifecycle events of the given transaction (if it is tracked)
+    pub(crate) fn get_lifecycle(&self, hash: &HashValue) -> Option<Vec<TransactionLifecycleEvent>> {
+        self.lifecycle_tracker.get_lifecycle(hash)
+    }
+
+    /// Evicts the transaction with the given hash (if it exists). Any subsequent
+    /// sequence number transactions of the same account are parked.
+    pub(crate) fn evict_transaction(
+        &mut self,
+        hash: HashValue,
+    ) -> Option<(AccountAddress, ReplayProtector)> {
+        let (address, replay_protector) = *self.hash_index.get(&hash)?;
+        let txns = self.transactions.get_mut(&address)?;
+
+        // Park all subsequent transactions (they can no longer be executed)
+        if let ReplayProtector::SequenceNumber(sequence_number) = replay_protector {
+            for (_, txn) in
+                txns.seq_num_range_mut((Bound::Excluded(sequence_number), Bound::Unbounded))
+            {
+                self.parking_lot_index.insert(txn);
+                self.lifecycle_tracker.record(
+                    &txn.get_committed_hash(),
+                    TransactionLifecycleState::Parked {
+                        expected_sequence_number: sequence_number,
+                    },
+                );
+                self.priority_index.remove(txn);
+                self.timeline_index
+                    .get_mut(&sender_bucket(&address, self.num_sender_buckets))
+                    .expect("Unable to get the timeline index for the sender bucket")
+                    .remove(txn);
+                if let TimelineState::Ready(_) = txn.timeline_state {
+                    txn.timeline_state = TimelineState::NotReady;
+                }
+            }
+        }
+
+        // Remove the transaction
+        let txn = txns.remove(&replay_protector)?;
+        self.lifecycle_tracker
+            .record(&hash, TransactionLifecycleState::Evicted);
+        self.index_remove(&txn);
+        Some((address, replay_protector))
+    }
+
+    /// Evicts all transactions of the given account, and returns their hashes
+    pub(crate) fn evict_account_transactions(
+        &mut self,
+        address: &AccountAddress,
+    ) -> Vec<HashValue> {
+        let Some(txns) = self.transactions.get(address) else {
+            return vec![];
+        };
+
+        let mut evicted_hashes = vec![];
+        let replay_protectors: Vec<_> = txns
+            .values()
+            .map(|txn| txn.get_replay_protector())
+            .collect();
+        for replay_protector in replay_protectors {
+            let removed_txn = self
+                .transactions
+                .get_mut(address)
+                .and_then(|txns| txns.remove(&replay_protector));
+            if let Some(txn) = removed_txn {
+                evicted_hashes.push(txn.get_committed_hash());
+                self.lifecycle_tracker.record(
+                    &txn.get_committed_hash(),
+                    TransactionLifecycleState::Evicted,
+                );
+                self.index_remove(&txn);
+            }
+        }
+        evicted_hashes
+    }
+
     #[cfg(test)]
     pub(crate) fn get_parking_lot_size(&self) -> usize {
         self.parking_lot_index.size()
@@ -101,6 +101,11 @@ pub const SUBMITTED_BY_CLIENT_LABEL: &str = "client";
 pub const SUBMITTED_BY_DOWNSTREAM_LABEL: &str = "downstream";
 pub const SUBMITTED_BY_PEER_VALIDATOR_LABEL: &str = "peer_validator";
 
+// Results for transactions restored from the mempool journal
+pub const JOURNAL_ACCEPTED_LABEL: &str = "accepted";
+pub const JOURNAL_EXPIRED_LABEL: &str = "expired";
+pub const JOURNAL_REJECTED_LABEL: &str = "rejected";
+
 // Histogram buckets with a large range of 0-500s and some constant sized buckets between:
 // 0-1.5s (every 25ms), 1.5-2s (every 100ms), 2-5s (250ms), 5-10s (1s), and 10-25s (2.5s).
 const MEMPOOL_LATENCY_BUCKETS: &[f64] = &[
@@ -179,6 +184,15 @@ pub static CORE_MEMPOOL_REMOVED_TXNS: Lazy<IntCounter> = Lazy::new(|| {
     .unwrap()
 });
 
+/// Counter tracking number of txns evicted from core mempool by an operator
+pub static CORE_MEMPOOL_ADMIN_EVICTED_TXNS: Lazy<IntCounter> = Lazy::new(|| {
+    register_int_counter!(
+        "creditchain_core_mempool_admin_evicted_txns_count",
+        "Number of txns evicted from core mempool by an operator"
+    )
+    .unwrap()
+});
+
 /// Counter tracking number of txns received that are idempotent duplicates
 pub static CORE_MEMPOOL_IDEMPOTENT_TXNS: Lazy<IntCounter> = Lazy::new(|| {
     register_int_counter!(
@@ -673,6 +687,25 @@ pub fn invalid_ack_inc(network_id: NetworkId, label: &'static str) {
         .inc();
 }
 
+/// Counter for transactions restored from the mempool journal on startup
+pub static MEMPOOL_JOURNAL_RESTORED_TXNS: Lazy<IntCounterVec> = Lazy::new(|| {
+    register_int_counter_vec!(
+        "creditchain_mempool_journal_restored_txns",
+        "Number of transactions restored from the mempool journal, by result",
+        &["result"]
+    )
+    .unwrap()
+});
+
+/// Counter for number of times a mempool journal read or write resulted in error
+pub static MEMPOOL_JOURNAL_ERROR: Lazy<IntCounter> = Lazy::new(|| {
+    register_int_counter!(
+        "creditchain_mempool_journal_error_count",
+        "Number of times a mempool journal read or write error was encountered"
+    )
+    .unwrap()
+});
+
 /// Counter for number of times a DB read resulted in error
 pub static DB_ERROR: Lazy<IntCounter> = Lazy::new(|| {
     register_int_counter!(
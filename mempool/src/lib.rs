@@ -58,6 +58,9 @@
 
 #[cfg(any(test, feature = "fuzzing"))]
 mod tests;
+pub use core_mempool::{
+    MempoolTransactionInfo, TransactionLifecycleEvent, TransactionLifecycleState,
+};
 pub use shared_mempool::{
     bootstrap, network,
     network::MempoolSyncMsg,
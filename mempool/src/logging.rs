@@ -189,7 +189,9 @@ pub enum LogEntry {
     DBError,
     UnexpectedNetworkMsg,
     MempoolSnapshot,
+    MempoolJournal,
     TransactionFilter,
+    AdminAction,
 }
 
 #[derive(Clone, Copy, Serialize)]
@@ -104,6 +104,15 @@ pub(crate) async fn coordinator<NetworkClient, TransactionValidator, ConfigProvi
     )
     .await;
 
+    // Restore the transactions journaled before the last shutdown (if persistence is
+    // enabled), without holding up the event loop. The journal is only compacted once
+    // this has completed.
+    if let Some(journal) = smp.journal.clone() {
+        bounded_executor
+            .spawn(tasks::process_journaled_transactions(smp.clone(), journal))
+            .await;
+    }
+
     loop {
         let _timer = counters::MAIN_LOOP.start_timer();
         ::futures::select! {
@@ -222,6 +231,57 @@ async fn handle_client_request<NetworkClient, TransactionValidator>(
                 .spawn(tasks::process_parking_lot_addresses(smp.clone(), callback))
                 .await;
         },
+        MempoolClientRequest::GetTransactionLifecycle(hash, callback) => {
+            bounded_executor
+                .spawn(tasks::process_transaction_lifecycle(
+                    smp.clone(),
+                    hash,
+                    callback,
+                ))
+                .await;
+        },
+        MempoolClientRequest::GetAccountTransactionInfos(address, callback) => {
+            bounded_executor
+                .spawn(tasks::process_account_transaction_infos(
+                    smp.clone(),
+                    address,
+                    callback,
+                ))
+                .await;
+        },
+        MempoolClientRequest::GetTransactionInfoByHash(hash, callback) => {
+            bounded_executor
+                .spawn(tasks::process_transaction_info_by_hash(
+                    smp.clone(),
+                    hash,
+                    callback,
+                ))
+                .await;
+        },
+        MempoolClientRequest::EvictTransaction(hash, callback) => {
+            bounded_executor
+                .spawn(tasks::process_evict_transaction(
+                    smp.clone(),
+                    hash,
+                    callback,
+                ))
+                .await;
+        },
+        MempoolClientRequest::EvictSender(sender, callback) => {
+            bounded_executor
+                .spawn(tasks::process_evict_sender(smp.clone(), sender, callback))
+                .await;
+        },
+        MempoolClientRequest::BlockSender(sender, duration, callback) => {
+            bounded_executor
+                .spawn(tasks::process_block_sender(
+                    smp.clone(),
+                    sender,
+                    duration,
+                    callback,
+                ))
+                .await;
+        },
     }
 }
 
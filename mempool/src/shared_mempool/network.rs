@@ -5,6 +5,7 @@
 //! Interface between Mempool and Network layers.
 
 use crate::{
+    core_mempool::MempoolTransactionInfo,
     counters,
     logging::{LogEntry, LogEvent, LogSchema},
     shared_mempool::{
@@ -105,6 +106,18 @@ impl Display for BroadcastPeerPriority {
     }
 }
 
+/// The broadcast status of a single transaction to a single peer
+#[derive(Clone, Debug, Serialize)]
+pub struct PeerBroadcastStatus {
+    pub peer: String,
+    /// Whether the transaction has been broadcast to the peer
+    pub broadcast: bool,
+    /// Whether broadcasting to the peer is in backoff mode
+    pub backoff_mode: bool,
+    /// The number of broadcasts to the peer that have not yet been acked
+    pub num_pending_acks: usize,
+}
+
 #[derive(Clone, Debug)]
 pub(crate) struct MempoolNetworkInterface<NetworkClient> {
     network_client: NetworkClient,
@@ -382,7 +395,7 @@ impl<NetworkClient: NetworkClientInterface<MempoolSyncMsg>> MempoolNetworkInterf
         // Sync peer's pending broadcasts with latest mempool state.
         // A pending or retry broadcast might become empty if the corresponding txns were committed through
         // another peer, so don't track broadcasts for committed txns.
-        let mempool = smp.mempool.lock();
+        let mut mempool = smp.mempool.lock();
         state.broadcast_info.sent_messages = state
             .broadcast_info
             .sent_messages
@@ -551,6 +564,10 @@ impl<NetworkClient: NetworkClientInterface<MempoolSyncMsg>> MempoolNetworkInterf
         if transactions.is_empty() {
             return Err(BroadcastError::NoTransactions(peer));
         }
+        mempool.record_broadcast(
+            transactions.iter().map(|(txn, _, _)| txn.committed_hash()),
+            peer,
+        );
 
         Ok((message_id, transactions, metric_label))
     }
@@ -663,6 +680,35 @@ impl<NetworkClient: NetworkClientInterface<MempoolSyncMsg>> MempoolNetworkInterf
         Ok(())
     }
 
+    /// Returns the broadcast status of the given transaction for all peers
+    pub fn get_peer_broadcast_statuses(
+        &self,
+        txn_info: &MempoolTransactionInfo,
+    ) -> Vec<PeerBroadcastStatus> {
+        self.sync_states
+            .read()
+            .iter()
+            .map(|(peer, state)| {
+                // The transaction has been broadcast iff the peer's timeline has passed it
+                let broadcast = txn_info.timeline_id.is_some_and(|timeline_id| {
+                    state
+                        .timelines
+                        .get(&txn_info.sender_bucket)
+                        .and_then(|timeline| {
+                            timeline.id_per_bucket.get(txn_info.timeline_bucket_index)
+                        })
+                        .is_some_and(|broadcast_timeline_id| timeline_id <= *broadcast_timeline_id)
+                });
+                PeerBroadcastStatus {
+                    peer: peer.to_string(),
+                    broadcast,
+                    backoff_mode: state.broadcast_info.backoff_mode,
+                    num_pending_acks: state.broadcast_info.sent_messages.len(),
+                }
+            })
+            .collect()
+    }
+
     pub fn sync_states_exists(&self, peer: &PeerNetworkId) -> bool {
         self.sync_states.read().get(peer).is_some()
     }
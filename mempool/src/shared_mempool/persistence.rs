@@ -0,0 +1,319 @@
+// Copyright © CreditChain Research Team
+// SPDX-License-Identifier: Apache-2.0
+
+//! An optional on-disk journal of the transactions accepted by mempool.
+//!
+//! Accepted transactions are appended to the journal as they are inserted. The
+//! journal is periodically compacted into a snapshot of the transactions in core
+//! mempool, so it never needs to record removals: committed and expired
+//! transactions are dropped when the journal is reloaded (and re-validated) on startup.
+//!
+//! All file I/O happens on a dedicated writer thread, so that journaling never blocks
+//! transaction processing. Appends are best-effort: if the writer falls behind, appends
+//! are dropped, and the transactions are only persisted by the next snapshot.
+use crate::{
+    core_mempool::CoreMempool,
+    counters,
+    logging::{LogEntry, LogSchema},
+};
+use anyhow::{Context, Result};
+use creditchain_infallible::Mutex;
+use creditchain_logger::prelude::*;
+use creditchain_types::transaction::SignedTransaction;
+use std::{
+    collections::HashSet,
+    fs::{self, File, OpenOptions},
+    io::{BufReader, BufWriter, ErrorKind, Read, Write},
+    path::{Path, PathBuf},
+    sync::{
+        mpsc::{self, Receiver, RecvTimeoutError, SyncSender, TrySendError},
+        Arc,
+    },
+    thread,
+    time::{Duration, Instant},
+};
+
+// The file names used by the journal
+const JOURNAL_FILE_NAME: &str = "journal";
+const SNAPSHOT_FILE_NAME: &str = "snapshot";
+const SNAPSHOT_TMP_FILE_NAME: &str = "snapshot.tmp";
+
+// The maximum size of a single record. This is well above the maximum transaction
+// size, so any larger length prefix can only come from a corrupt file.
+const MAX_RECORD_SIZE_BYTES: usize = 16 * 1024 * 1024;
+
+// The maximum number of pending commands for the writer thread
+const MAX_PENDING_COMMANDS: usize = 1024;
+
+// The number of senders whose transactions are copied out of core mempool at
+// once when writing a snapshot (the core mempool lock is released in between).
+const SNAPSHOT_SENDER_CHUNK_SIZE: usize = 100;
+
+/// A command for the journal writer thread
+enum JournalCommand {
+    Append(Vec<SignedTransaction>),
+    Snapshot,
+    #[cfg(test)]
+    Flush(SyncSender<()>),
+}
+
+/// The on-disk journal of accepted mempool transactions. Each record is
+/// a BCS serialized transaction, prefixed by its length (as a little endian u32).
+pub(crate) struct MempoolJournal {
+    journal_path: PathBuf,
+    snapshot_path: PathBuf,
+
+    // The sender for commands to the journal writer thread
+    command_sender: SyncSender<JournalCommand>,
+}
+
+impl MempoolJournal {
+    /// Opens (or creates) the journal in the given directory and starts the writer
+    /// thread. Periodic snapshots only start after the first requested snapshot, i.e.,
+    /// once the journaled transactions have been restored into mempool.
+    pub(crate) fn open(
+        dir: &Path,
+        mempool: Arc<Mutex<CoreMempool>>,
+        snapshot_interval: Duration,
+    ) -> Result<Self> {
+        let writer = JournalWriter::open(dir)?;
+        let journal_path = writer.journal_path.clone();
+        let snapshot_path = writer.snapshot_path.clone();
+
+        let (command_sender, command_receiver) = mpsc::sync_channel(MAX_PENDING_COMMANDS);
+        thread::Builder::new()
+            .name("mempool-journal".into())
+            .spawn(move || writer.run(mempool, command_receiver, snapshot_interval))
+            .context("Failed to spawn the mempool journal writer thread")?;
+
+        Ok(Self {
+            journal_path,
+            snapshot_path,
+            command_sender,
+        })
+    }
+
+    /// Appends the given (accepted) transactions to the journal. This does not block:
+    /// an error is returned if the writer thread is too far behind to accept them.
+    pub(crate) fn append(&self, transactions: Vec<SignedTransaction>) -> Result<()> {
+        if transactions.is_empty() {
+            return Ok(());
+        }
+        self.send_command(JournalCommand::Append(transactions))
+    }
+
+    /// Requests a snapshot of all transactions in core mempool (which also truncates the journal)
+    pub(crate) fn request_snapshot(&self) -> Result<()> {
+        self.send_command(JournalCommand::Snapshot)
+    }
+
+    /// Waits until the writer thread has processed all previously sent commands
+    #[cfg(test)]
+    pub(crate) fn flush(&self) {
+        let (sender, receiver) = mpsc::sync_channel(1);
+        self.command_sender
+            .send(JournalCommand::Flush(sender))
+            .unwrap();
+        receiver.recv().unwrap();
+    }
+
+    fn send_command(&self, command: JournalCommand) -> Result<()> {
+        self.command_sender
+            .try_send(command)
+            .map_err(|error| match error {
+                TrySendError::Full(_) => anyhow::anyhow!("The mempool journal writer is full!"),
+                TrySendError::Disconnected(_) => {
+                    anyhow::anyhow!("The mempool journal writer has stopped!")
+                },
+            })
+    }
+
+    /// Loads all transactions in the snapshot and the journal (without duplicates).
+    /// Note: the transactions may since have expired or been committed.
+    pub(crate) fn load(&self) -> Result<Vec<SignedTransaction>> {
+        let mut transaction_hashes = HashSet::new();
+        let mut transactions = vec![];
+        for path in [&self.snapshot_path, &self.journal_path] {
+            if !path.exists() {
+                continue;
+            }
+            for transaction in read_records(path)? {
+                if transaction_hashes.insert(transaction.committed_hash()) {
+                    transactions.push(transaction);
+                }
+            }
+        }
+        Ok(transactions)
+    }
+}
+
+/// The journal files, owned by the writer thread
+struct JournalWriter {
+    dir: PathBuf,
+    journal_path: PathBuf,
+    snapshot_path: PathBuf,
+    snapshot_tmp_path: PathBuf,
+    journal_writer: BufWriter<File>,
+}
+
+impl JournalWriter {
+    fn open(dir: &Path) -> Result<Self> {
+        fs::create_dir_all(dir)
+            .with_context(|| format!("Failed to create the mempool journal dir: {:?}", dir))?;
+
+        let journal_path = dir.join(JOURNAL_FILE_NAME);
+        let journal_file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&journal_path)
+            .with_context(|| format!("Failed to open the mempool journal: {:?}", journal_path))?;
+
+        Ok(Self {
+            dir: dir.to_path_buf(),
+            journal_path,
+            snapshot_path: dir.join(SNAPSHOT_FILE_NAME),
+            snapshot_tmp_path: dir.join(SNAPSHOT_TMP_FILE_NAME),
+            journal_writer: BufWriter::new(journal_file),
+        })
+    }
+
+    /// Processes commands until the journal is dropped
+    fn run(
+        mut self,
+        mempool: Arc<Mutex<CoreMempool>>,
+        command_receiver: Receiver<JournalCommand>,
+        snapshot_interval: Duration,
+    ) {
+        let mut next_snapshot_time: Option<Instant> = None;
+        loop {
+            let command = match next_snapshot_time {
+                Some(snapshot_time) => {
+                    let timeout = snapshot_time.saturating_duration_since(Instant::now());
+                    match command_receiver.recv_timeout(timeout) {
+                        Ok(command) => command,
+                        Err(RecvTimeoutError::Timeout) => JournalCommand::Snapshot,
+                        Err(RecvTimeoutError::Disconnected) => return,
+                    }
+                },
+                None => match command_receiver.recv() {
+                    Ok(command) => command,
+                    Err(_) => return,
+                },
+            };
+
+            match command {
+                JournalCommand::Append(transactions) => {
+                    if let Err(error) = self.append(&transactions) {
+                        counters::MEMPOOL_JOURNAL_ERROR.inc();
+                        error!(LogSchema::new(LogEntry::MempoolJournal)
+                            .error(&error)
+                            .message("Failed to append transactions to the mempool journal!"));
+                    }
+                },
+                JournalCommand::Snapshot => {
+                    match self.write_snapshot(&mempool) {
+                        Ok(num_txns) => {
+                            debug!(LogSchema::new(LogEntry::MempoolJournal).num_txns(num_txns));
+                        },
+                        Err(error) => {
+                            counters::MEMPOOL_JOURNAL_ERROR.inc();
+                            error!(LogSchema::new(LogEntry::MempoolJournal)
+                                .error(&error)
+                                .message("Failed to write the mempool journal snapshot!"));
+                        },
+                    }
+                    next_snapshot_time = Some(Instant::now() + snapshot_interval);
+                },
+                #[cfg(test)]
+                JournalCommand::Flush(sender) => {
+                    let _ = sender.send(());
+                },
+            }
+        }
+    }
+
+    /// Appends the given transactions to the journal. The records are handed to
+    /// the OS, but are only synced to disk with the next snapshot.
+    fn append(&mut self, transactions: &[SignedTransaction]) -> Result<()> {
+        for transaction in transactions {
+            write_record(&mut self.journal_writer, transaction)?;
+        }
+        self.journal_writer.flush()?;
+        Ok(())
+    }
+
+    /// Writes a snapshot of all transactions in core mempool and truncates the
+    /// journal. Returns the number of transactions in the snapshot.
+    ///
+    /// The transactions are copied out of core mempool a few senders at a time, so the
+    /// core mempool lock is never held for the whole copy. A transaction inserted while
+    /// the snapshot is taken may be missed by it, but it is then journaled by an append
+    /// queued behind this snapshot, i.e., after the journal has been truncated.
+    fn write_snapshot(&mut self, mempool: &Mutex<CoreMempool>) -> Result<usize> {
+        let senders = mempool.lock().get_senders();
+
+        // Write the snapshot to a temporary file and atomically move it into place
+        let snapshot_file = File::create(&self.snapshot_tmp_path)?;
+        let mut snapshot_writer = BufWriter::new(snapshot_file);
+        let mut num_transactions = 0;
+        for senders in senders.chunks(SNAPSHOT_SENDER_CHUNK_SIZE) {
+            let transactions = mempool.lock().get_transactions_of_senders(senders);
+            for transaction in &transactions {
+                write_record(&mut snapshot_writer, transaction)?;
+            }
+            num_transactions += transactions.len();
+        }
+        snapshot_writer.flush()?;
+        snapshot_writer.get_ref().sync_all()?;
+        fs::rename(&self.snapshot_tmp_path, &self.snapshot_path)?;
+        File::open(&self.dir)?.sync_all()?;
+
+        // The snapshot now covers everything in the journal, so the journal can be truncated
+        self.journal_writer.flush()?;
+        self.journal_writer.get_ref().set_len(0)?;
+        self.journal_writer.get_ref().sync_all()?;
+
+        Ok(num_transactions)
+    }
+}
+
+/// Writes the given transaction as a length-prefixed record
+fn write_record(writer: &mut impl Write, transaction: &SignedTransaction) -> Result<()> {
+    let bytes = bcs::to_bytes(transaction)?;
+    writer.write_all(&(bytes.len() as u32).to_le_bytes())?;
+    writer.write_all(&bytes)?;
+    Ok(())
+}
+
+/// Reads all transaction records in the given file. A truncated record at
+/// the end of the file (e.g., due to a crash mid-write) is ignored, and so
+/// is everything after a corrupt record.
+fn read_records(path: &Path) -> Result<Vec<SignedTransaction>> {
+    let mut reader = BufReader::new(File::open(path)?);
+    let mut transactions = vec![];
+    loop {
+        let mut length_bytes = [0u8; 4];
+        match reader.read_exact(&mut length_bytes) {
+            Ok(()) => {},
+            Err(error) if error.kind() == ErrorKind::UnexpectedEof => break,
+            Err(error) => return Err(error.into()),
+        }
+
+        let length = u32::from_le_bytes(length_bytes) as usize;
+        if length > MAX_RECORD_SIZE_BYTES {
+            break; // The remainder of the file is corrupt
+        }
+        let mut bytes = vec![0u8; length];
+        match reader.read_exact(&mut bytes) {
+            Ok(()) => {},
+            Err(error) if error.kind() == ErrorKind::UnexpectedEof => break,
+            Err(error) => return Err(error.into()),
+        }
+
+        match bcs::from_bytes(&bytes) {
+            Ok(transaction) => transactions.push(transaction),
+            Err(_) => break, // The remainder of the file is corrupt
+        }
+    }
+    Ok(transactions)
+}
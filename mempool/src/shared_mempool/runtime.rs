@@ -4,9 +4,11 @@
 
 use crate::{
     core_mempool::CoreMempool,
+    logging::{LogEntry, LogSchema},
     network::MempoolSyncMsg,
     shared_mempool::{
         coordinator::{coordinator, gc_coordinator, snapshot_job},
+        persistence::MempoolJournal,
         types::{MempoolEventsReceiver, SharedMempool, SharedMempoolNotification},
     },
     QuorumStoreRequest,
@@ -14,7 +16,7 @@ use crate::{
 use creditchain_config::config::{NodeConfig, NodeType};
 use creditchain_event_notifications::{DbBackedOnChainConfig, ReconfigNotificationListener};
 use creditchain_infallible::{Mutex, RwLock};
-use creditchain_logger::Level;
+use creditchain_logger::{error, Level};
 use creditchain_mempool_notifications::MempoolNotificationListener;
 use creditchain_network::application::{
     interface::{NetworkClient, NetworkServiceEvents},
@@ -24,7 +26,7 @@ use creditchain_storage_interface::DbReader;
 use creditchain_types::on_chain_config::OnChainConfigProvider;
 use creditchain_vm_validator::vm_validator::{PooledVMValidator, TransactionValidation};
 use futures::channel::mpsc::{Receiver, UnboundedSender};
-use std::sync::Arc;
+use std::{sync::Arc, time::Duration};
 use tokio::runtime::{Handle, Runtime};
 
 /// Bootstrap of SharedMempool.
@@ -62,6 +64,7 @@ pub(crate) fn start_shared_mempool<TransactionValidator, ConfigProvider>(
             validator,
             subscribers,
             node_type,
+            open_mempool_journal(config, mempool.clone()),
         );
 
     executor.spawn(coordinator(
@@ -89,6 +92,29 @@ pub(crate) fn start_shared_mempool<TransactionValidator, ConfigProvider>(
     }
 }
 
+/// Opens the mempool journal (if persistence is enabled). If the journal
+/// cannot be opened, mempool continues without persistence.
+fn open_mempool_journal(
+    config: &NodeConfig,
+    mempool: Arc<Mutex<CoreMempool>>,
+) -> Option<Arc<MempoolJournal>> {
+    if !config.mempool.enable_persistence {
+        return None;
+    }
+
+    let journal_dir = config.base.data_dir.join(&config.mempool.persistence_dir);
+    let snapshot_interval = Duration::from_secs(config.mempool.persistence_snapshot_interval_secs);
+    match MempoolJournal::open(&journal_dir, mempool, snapshot_interval) {
+        Ok(journal) => Some(Arc::new(journal)),
+        Err(error) => {
+            error!(LogSchema::new(LogEntry::MempoolJournal)
+                .error(&error)
+                .message("Failed to open the mempool journal! Persistence is disabled."));
+            None
+        },
+    }
+}
+
 pub fn bootstrap(
     config: &NodeConfig,
     db: Arc<dyn DbReader>,
@@ -5,11 +5,15 @@
 //! Tasks that are executed by coordinators (short-lived compared to coordinators)
 use super::types::MempoolMessageId;
 use crate::{
-    core_mempool::{CoreMempool, TimelineState},
+    core_mempool::{
+        CoreMempool, MempoolTransactionInfo, TimelineState, TransactionLifecycleEvent,
+        TransactionLifecycleState,
+    },
     counters,
     logging::{LogEntry, LogEvent, LogSchema},
-    network::{BroadcastError, BroadcastPeerPriority, MempoolSyncMsg},
+    network::{BroadcastError, BroadcastPeerPriority, MempoolSyncMsg, PeerBroadcastStatus},
     shared_mempool::{
+        persistence::MempoolJournal,
         types::{
             notify_subscribers, ScheduledBroadcast, SharedMempool, SharedMempoolNotification,
             SubmissionStatusBundle,
@@ -170,6 +174,110 @@ pub(crate) async fn process_parking_lot_addresses<NetworkClient, TransactionVali
     }
 }
 
+/// Processes request for the lifecycle events of a transaction
+pub(crate) async fn process_transaction_lifecycle<NetworkClient, TransactionValidator>(
+    smp: SharedMempool<NetworkClient, TransactionValidator>,
+    hash: HashValue,
+    callback: oneshot::Sender<Option<Vec<TransactionLifecycleEvent>>>,
+) where
+    NetworkClient: NetworkClientInterface<MempoolSyncMsg>,
+    TransactionValidator: TransactionValidation + 'static,
+{
+    let lifecycle = smp.mempool.lock().get_transaction_lifecycle(&hash);
+
+    if callback.send(lifecycle).is_err() {
+        warn!(LogSchema::event_log(
+            LogEntry::GetTransaction,
+            LogEvent::CallbackFail
+        ));
+        counters::CLIENT_CALLBACK_FAIL.inc();
+    }
+}
+
+/// Processes request for the detailed state of all transactions of an account
+pub(crate) async fn process_account_transaction_infos<NetworkClient, TransactionValidator>(
+    smp: SharedMempool<NetworkClient, TransactionValidator>,
+    address: AccountAddress,
+    callback: oneshot::Sender<Vec<MempoolTransactionInfo>>,
+) where
+    NetworkClient: NetworkClientInterface<MempoolSyncMsg>,
+    TransactionValidator: TransactionValidation + 'static,
+{
+    let txn_infos = smp
+        .mempool
+        .lock()
+        .get_transaction_infos_by_account(&address);
+    send_admin_response(callback, txn_infos);
+}
+
+/// Processes request for the detailed state (and broadcast status) of a transaction
+pub(crate) async fn process_transaction_info_by_hash<NetworkClient, TransactionValidator>(
+    smp: SharedMempool<NetworkClient, TransactionValidator>,
+    hash: HashValue,
+    callback: oneshot::Sender<Option<(MempoolTransactionInfo, Vec<PeerBroadcastStatus>)>>,
+) where
+    NetworkClient: NetworkClientInterface<MempoolSyncMsg>,
+    TransactionValidator: TransactionValidation + 'static,
+{
+    let txn_info = smp.mempool.lock().get_transaction_info_by_hash(hash);
+    let response = txn_info.map(|txn_info| {
+        let broadcast_statuses = smp.network_interface.get_peer_broadcast_statuses(&txn_info);
+        (txn_info, broadcast_statuses)
+    });
+    send_admin_response(callback, response);
+}
+
+/// Processes request to evict a transaction from mempool
+pub(crate) async fn process_evict_transaction<NetworkClient, TransactionValidator>(
+    smp: SharedMempool<NetworkClient, TransactionValidator>,
+    hash: HashValue,
+    callback: oneshot::Sender<bool>,
+) where
+    NetworkClient: NetworkClientInterface<MempoolSyncMsg>,
+    TransactionValidator: TransactionValidation + 'static,
+{
+    let evicted = smp.mempool.lock().evict_transaction(hash);
+    send_admin_response(callback, evicted);
+}
+
+/// Processes request to evict all transactions of a sender from mempool
+pub(crate) async fn process_evict_sender<NetworkClient, TransactionValidator>(
+    smp: SharedMempool<NetworkClient, TransactionValidator>,
+    sender: AccountAddress,
+    callback: oneshot::Sender<Vec<HashValue>>,
+) where
+    NetworkClient: NetworkClientInterface<MempoolSyncMsg>,
+    TransactionValidator: TransactionValidation + 'static,
+{
+    let evicted_hashes = smp.mempool.lock().evict_sender(&sender);
+    send_admin_response(callback, evicted_hashes);
+}
+
+/// Processes request to temporarily block all new transactions from a sender
+pub(crate) async fn process_block_sender<NetworkClient, TransactionValidator>(
+    smp: SharedMempool<NetworkClient, TransactionValidator>,
+    sender: AccountAddress,
+    duration: Duration,
+    callback: oneshot::Sender<Result<()>>,
+) where
+    NetworkClient: NetworkClientInterface<MempoolSyncMsg>,
+    TransactionValidator: TransactionValidation + 'static,
+{
+    let result = smp.mempool.lock().block_sender(sender, duration);
+    send_admin_response(callback, result);
+}
+
+/// Sends the response to an admin (i.e., operator) request
+fn send_admin_response<T>(callback: oneshot::Sender<T>, response: T) {
+    if callback.send(response).is_err() {
+        warn!(LogSchema::event_log(
+            LogEntry::AdminAction,
+            LogEvent::CallbackFail
+        ));
+        counters::CLIENT_CALLBACK_FAIL.inc();
+    }
+}
+
 /// Processes get transaction by hash request by client.
 pub(crate) async fn process_client_get_transaction<NetworkClient, TransactionValidator>(
     smp: SharedMempool<NetworkClient, TransactionValidator>,
@@ -386,10 +494,115 @@ where
         &mut statuses,
         client_submitted,
     );
+    if let Some(journal) = &smp.journal {
+        journal_accepted_transactions(journal, &statuses);
+    }
     notify_subscribers(SharedMempoolNotification::NewTransactions, &smp.subscribers);
     statuses
 }
 
+/// Appends the transactions that were accepted into mempool to the journal
+fn journal_accepted_transactions(journal: &MempoolJournal, statuses: &[SubmissionStatusBundle]) {
+    let accepted_transactions: Vec<_> = statuses
+        .iter()
+        .filter(|(_, (mempool_status, _))| mempool_status.code == MempoolStatusCode::Accepted)
+        .map(|(transaction, _)| transaction.clone())
+        .collect();
+    if let Err(error) = journal.append(accepted_transactions) {
+        // The transactions are still persisted by the next snapshot
+        counters::MEMPOOL_JOURNAL_ERROR.inc();
+        sample!(
+            SampleRate::Duration(Duration::from_secs(10)),
+            warn!(LogSchema::new(LogEntry::MempoolJournal)
+                .error(&error)
+                .message("Failed to append transactions to the mempool journal!"))
+        );
+    }
+}
+
+/// Restores the transactions in the mempool journal (e.g., after a node restart).
+/// The transactions are re-validated against the latest state, so expired and
+/// committed transactions are dropped. The rest are re-inserted (and re-broadcast).
+pub(crate) async fn process_journaled_transactions<NetworkClient, TransactionValidator>(
+    smp: SharedMempool<NetworkClient, TransactionValidator>,
+    journal: Arc<MempoolJournal>,
+) where
+    NetworkClient: NetworkClientInterface<MempoolSyncMsg>,
+    TransactionValidator: TransactionValidation,
+{
+    let transactions = match journal.load() {
+        Ok(transactions) => transactions,
+        Err(error) => {
+            counters::MEMPOOL_JOURNAL_ERROR.inc();
+            error!(LogSchema::new(LogEntry::MempoolJournal)
+                .error(&error)
+                .message("Failed to load the mempool journal!"));
+            return;
+        },
+    };
+
+    // Drop the transactions that have already expired
+    let now_secs = creditchain_infallible::duration_since_epoch().as_secs();
+    let (unexpired_transactions, expired_transactions): (Vec<_>, Vec<_>) = transactions
+        .into_iter()
+        .partition(|transaction| transaction.expiration_timestamp_secs() > now_secs);
+
+    // Re-validate and insert the remaining transactions (in batches)
+    let ineligible_for_broadcast =
+        smp.network_interface.is_validator() && !smp.broadcast_within_validator_network();
+    let timeline_state = if ineligible_for_broadcast {
+        TimelineState::NonQualified
+    } else {
+        TimelineState::NotReady
+    };
+    let num_unexpired_transactions = unexpired_transactions.len();
+    let mut num_accepted_transactions = 0;
+    let batch_size = cmp::max(smp.config.shared_mempool_batch_size, 1);
+    for batch in unexpired_transactions.chunks(batch_size) {
+        let statuses = process_incoming_transactions(
+            &smp,
+            batch
+                .iter()
+                .map(|transaction| (transaction.clone(), None, None))
+                .collect(),
+            timeline_state,
+            false,
+        );
+        num_accepted_transactions += statuses
+            .iter()
+            .filter(|(_, (mempool_status, _))| mempool_status.code == MempoolStatusCode::Accepted)
+            .count();
+    }
+
+    // Update the restoration metrics
+    let num_rejected_transactions = num_unexpired_transactions - num_accepted_transactions;
+    for (label, num_transactions) in [
+        (counters::JOURNAL_ACCEPTED_LABEL, num_accepted_transactions),
+        (counters::JOURNAL_EXPIRED_LABEL, expired_transactions.len()),
+        (counters::JOURNAL_REJECTED_LABEL, num_rejected_transactions),
+    ] {
+        counters::MEMPOOL_JOURNAL_RESTORED_TXNS
+            .with_label_values(&[label])
+            .inc_by(num_transactions as u64);
+    }
+    info!(LogSchema::new(LogEntry::MempoolJournal)
+        .num_txns(num_accepted_transactions)
+        .message(&format!(
+            "Restored transactions from the mempool journal! Expired: {}, rejected: {}",
+            expired_transactions.len(),
+            num_rejected_transactions
+        )));
+
+    // Compact the journal, so that it only holds the restored transactions. This
+    // also starts the periodic snapshots (on the journal writer thread).
+    if let Err(error) = journal.request_snapshot() {
+        counters::MEMPOOL_JOURNAL_ERROR.inc();
+        error!(LogSchema::new(LogEntry::MempoolJournal)
+            .error(&error)
+            .message("Failed to request a mempool journal snapshot!"));
+    }
+}
+
 /// Filters transactions based on the transaction filter configuration. Any
 /// transactions that are filtered out will have their statuses marked accordingly.
 fn filter_transactions(
@@ -659,6 +872,10 @@ pub(crate) fn process_quorum_store_request<NetworkClient, TransactionValidator>(
                 );
                 txns =
                     mempool.get_batch(max_txns, max_bytes, return_non_full, exclude_transactions);
+                mempool.record_lifecycle_state(
+                    txns.iter().map(|txn| txn.committed_hash()),
+                    TransactionLifecycleState::PulledIntoBatch,
+                );
             }
 
             // mempool_service_transactions is logged inside get_batch
@@ -681,6 +898,13 @@ pub(crate) fn process_quorum_store_request<NetworkClient, TransactionValidator>(
                 counters::COMMIT_CONSENSUS_LABEL,
             )
         },
+        QuorumStoreRequest::ProposedNotification(hashes) => {
+            // No response is expected, so only the lifecycle needs to be updated
+            smp.mempool
+                .lock()
+                .record_lifecycle_state(hashes.into_iter(), TransactionLifecycleState::Proposed);
+            return;
+        },
     };
     // Send back to callback
     let result = if callback.send(Ok(resp)).is_err() {
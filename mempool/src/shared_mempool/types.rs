@@ -4,9 +4,9 @@
 
 //! Objects used by/related to shared mempool
 use crate::{
-    core_mempool::{CoreMempool, TimelineId},
-    network::{MempoolNetworkInterface, MempoolSyncMsg},
-    shared_mempool::use_case_history::UseCaseHistory,
+    core_mempool::{CoreMempool, MempoolTransactionInfo, TimelineId, TransactionLifecycleEvent},
+    network::{MempoolNetworkInterface, MempoolSyncMsg, PeerBroadcastStatus},
+    shared_mempool::{persistence::MempoolJournal, use_case_history::UseCaseHistory},
 };
 use anyhow::Result;
 use creditchain_config::{
@@ -38,7 +38,7 @@ use std::{
     pin::Pin,
     sync::Arc,
     task::Waker,
-    time::{Instant, SystemTime},
+    time::{Duration, Instant, SystemTime},
 };
 use tokio::runtime::Handle;
 
@@ -57,6 +57,7 @@ pub(crate) struct SharedMempool<NetworkClient, TransactionValidator> {
     pub broadcast_within_validator_network: Arc<RwLock<bool>>,
     pub use_case_history: Arc<Mutex<UseCaseHistory>>,
     pub transaction_filter_config: TransactionFilterConfig,
+    pub journal: Option<Arc<MempoolJournal>>,
 }
 
 impl<
@@ -73,6 +74,7 @@ impl<
         validator: Arc<RwLock<TransactionValidator>>,
         subscribers: Vec<UnboundedSender<SharedMempoolNotification>>,
         node_type: NodeType,
+        journal: Option<Arc<MempoolJournal>>,
     ) -> Self {
         let network_interface =
             MempoolNetworkInterface::new(network_client, node_type, config.clone());
@@ -90,6 +92,7 @@ impl<
             broadcast_within_validator_network: Arc::new(RwLock::new(true)),
             use_case_history: Arc::new(Mutex::new(use_case_history)),
             transaction_filter_config,
+            journal,
         }
     }
 
@@ -195,6 +198,12 @@ pub enum QuorumStoreRequest {
         // callback to respond to
         oneshot::Sender<Result<QuorumStoreResponse>>,
     ),
+    /// Notifications about proposed txns (used only to track the txn lifecycle, so no
+    /// response is sent).
+    ProposedNotification(
+        // hashes of the proposed transactions
+        Vec<HashValue>,
+    ),
 }
 
 impl fmt::Display for QuorumStoreRequest {
@@ -221,6 +230,12 @@ impl fmt::Display for QuorumStoreRequest {
                     rejected_txns.len()
                 )
             },
+            QuorumStoreRequest::ProposedNotification(proposed_txns) => {
+                format!(
+                    "ProposedNotification [proposed_txns_length: {}]",
+                    proposed_txns.len()
+                )
+            },
         };
         write!(f, "{}", payload)
     }
@@ -246,6 +261,26 @@ pub enum MempoolClientRequest {
     /// Retrieves all addresses with transactions in the mempool's parking lot and
     /// the number of transactions for each address
     GetAddressesFromParkingLot(oneshot::Sender<Vec<(AccountAddress, u64)>>),
+    /// Retrieves the lifecycle events of a recently accepted transaction using its hash
+    GetTransactionLifecycle(
+        HashValue,
+        oneshot::Sender<Option<Vec<TransactionLifecycleEvent>>>,
+    ),
+    /// Retrieves the detailed state of all transactions in the mempool for the given account
+    GetAccountTransactionInfos(AccountAddress, oneshot::Sender<Vec<MempoolTransactionInfo>>),
+    /// Retrieves the detailed state (and per-peer broadcast status) of a transaction
+    /// in the mempool using its hash
+    GetTransactionInfoByHash(
+        HashValue,
+        oneshot::Sender<Option<(MempoolTransactionInfo, Vec<PeerBroadcastStatus>)>>,
+    ),
+    /// Evicts a transaction from the mempool using its hash. Returns true iff it was found.
+    EvictTransaction(HashValue, oneshot::Sender<bool>),
+    /// Evicts all transactions of the given account from the mempool, and returns their hashes
+    EvictSender(AccountAddress, oneshot::Sender<Vec<HashValue>>),
+    /// Rejects all new transactions from the given account for the given duration
+    /// (fails if too many accounts are already blocked)
+    BlockSender(AccountAddress, Duration, oneshot::Sender<Result<()>>),
 }
 
 pub type MempoolClientSender = mpsc::Sender<MempoolClientRequest>;
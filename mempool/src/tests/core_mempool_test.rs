@@ -3,7 +3,10 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
-    core_mempool::{sender_bucket, CoreMempool, MempoolTransaction, SubmittedBy, TimelineState},
+    core_mempool::{
+        sender_bucket, CoreMempool, MempoolTransaction, SubmittedBy, TimelineState,
+        TransactionLifecycleState, MAX_NUM_BLOCKED_SENDERS,
+    },
     network::BroadcastPeerPriority,
     tests::common::{
         add_signed_txn, add_txn, add_txns_to_mempool, setup_mempool,
@@ -1760,3 +1763,163 @@ fn test_include_gas_upgraded() {
     });
     assert_eq!(batch.len(), 0);
 }
+
+#[test]
+fn test_inspect_and_evict_transactions() {
+    let (mut mempool, _) = setup_mempool();
+    let transactions = add_txns_to_mempool(&mut mempool, vec![
+        TestTransaction::new(0, ReplayProtector::SequenceNumber(0), 1),
+        TestTransaction::new(0, ReplayProtector::SequenceNumber(1), 1),
+        TestTransaction::new(0, ReplayProtector::SequenceNumber(2), 1),
+        TestTransaction::new(1, ReplayProtector::SequenceNumber(0), 1),
+    ]);
+    let sender = transactions[0].sender();
+
+    // All transactions of the sender are ready
+    let infos = mempool.get_transaction_infos_by_account(&sender);
+    assert_eq!(infos.len(), 3);
+    assert!(infos.iter().all(|info| !info.parked));
+
+    // Evicting a transaction parks all subsequent transactions of the sender
+    assert!(mempool.evict_transaction(transactions[1].committed_hash()));
+    assert!(!mempool.evict_transaction(transactions[1].committed_hash()));
+    assert!(mempool
+        .get_transaction_info_by_hash(transactions[1].committed_hash())
+        .is_none());
+    let info = mempool
+        .get_transaction_info_by_hash(transactions[2].committed_hash())
+        .unwrap();
+    assert!(info.parked);
+    assert!(info.park_reason.unwrap().contains("sequence number 1"));
+    assert_eq!(mempool.get_parking_lot_size(), 1);
+
+    // Evicting the sender removes all of its transactions
+    let evicted_hashes = mempool.evict_sender(&sender);
+    assert_eq!(evicted_hashes.len(), 2);
+    assert!(mempool.get_transaction_infos_by_account(&sender).is_empty());
+    assert_eq!(mempool.get_parking_lot_size(), 0);
+    assert!(mempool
+        .get_transaction_info_by_hash(transactions[3].committed_hash())
+        .is_some());
+}
+
+#[test]
+fn test_block_sender() {
+    let (mut mempool, _) = setup_mempool();
+
+    // Transactions from a blocked sender are rejected
+    let txn = TestTransaction::new(0, ReplayProtector::SequenceNumber(0), 1);
+    let sender = txn.make_signed_transaction().sender();
+    mempool
+        .block_sender(sender, Duration::from_secs(60))
+        .unwrap();
+    assert!(add_txn(&mut mempool, txn).is_err());
+    assert!(add_txn(
+        &mut mempool,
+        TestTransaction::new(1, ReplayProtector::SequenceNumber(0), 1)
+    )
+    .is_ok());
+
+    // Transactions are accepted again once the block expires
+    let txn = TestTransaction::new(2, ReplayProtector::SequenceNumber(0), 1);
+    let sender = txn.make_signed_transaction().sender();
+    mempool.block_sender(sender, Duration::ZERO).unwrap();
+    assert!(add_txn(&mut mempool, txn).is_ok());
+}
+
+#[test]
+fn test_block_sender_limit() {
+    let (mut mempool, _) = setup_mempool();
+
+    // Block the max number of senders (with expired blocks)
+    for _ in 0..MAX_NUM_BLOCKED_SENDERS {
+        mempool
+            .block_sender(AccountAddress::random(), Duration::ZERO)
+            .unwrap();
+    }
+    assert_eq!(mempool.get_num_blocked_senders(), MAX_NUM_BLOCKED_SENDERS);
+
+    // Verify that expired blocks are removed to make room for new senders
+    let sender = AccountAddress::random();
+    mempool
+        .block_sender(sender, Duration::from_secs(60))
+        .unwrap();
+    assert_eq!(mempool.get_num_blocked_senders(), 1);
+
+    // Block the max number of senders (with active blocks)
+    for _ in 1..MAX_NUM_BLOCKED_SENDERS {
+        mempool
+            .block_sender(AccountAddress::random(), Duration::from_secs(60))
+            .unwrap();
+    }
+
+    // Verify that new senders can't be blocked, but existing blocks can be updated
+    assert!(mempool
+        .block_sender(AccountAddress::random(), Duration::from_secs(60))
+        .is_err());
+    mempool
+        .block_sender(sender, Duration::from_secs(120))
+        .unwrap();
+    assert_eq!(mempool.get_num_blocked_senders(), MAX_NUM_BLOCKED_SENDERS);
+
+    // Expire a block and verify that it is removed by the periodic gc
+    mempool.block_sender(sender, Duration::ZERO).unwrap();
+    mempool.gc();
+    assert_eq!(
+        mempool.get_num_blocked_senders(),
+        MAX_NUM_BLOCKED_SENDERS - 1
+    );
+}
+
+#[test]
+fn test_transaction_lifecycle() {
+    let mut pool = setup_mempool().0;
+
+    // Insert a transaction that's going to be expired, and a transaction after it
+    let expiring_txn = TestTransaction::new(1, ReplayProtector::SequenceNumber(0), 1)
+        .make_signed_transaction_with_expiration_time(0);
+    add_signed_txn(&mut pool, expiring_txn.clone()).unwrap();
+    let txn = add_txn(
+        &mut pool,
+        TestTransaction::new(1, ReplayProtector::SequenceNumber(1), 1),
+    )
+    .unwrap();
+
+    // GC the expired transaction, which parks the transaction after it
+    pool.gc_by_expiration_time(Duration::from_secs(1));
+    let states: Vec<_> = pool
+        .get_transaction_lifecycle(&expiring_txn.committed_hash())
+        .unwrap()
+        .into_iter()
+        .map(|event| event.state)
+        .collect();
+    assert_eq!(states, vec![
+        TransactionLifecycleState::Accepted,
+        TransactionLifecycleState::Expired
+    ]);
+    let states: Vec<_> = pool
+        .get_transaction_lifecycle(&txn.committed_hash())
+        .unwrap()
+        .into_iter()
+        .map(|event| event.state)
+        .collect();
+    assert_eq!(states, vec![
+        TransactionLifecycleState::Accepted,
+        TransactionLifecycleState::Parked {
+            expected_sequence_number: 0
+        }
+    ]);
+
+    // Evicted transactions keep their lifecycle
+    assert!(pool.evict_transaction(txn.committed_hash()));
+    let states: Vec<_> = pool
+        .get_transaction_lifecycle(&txn.committed_hash())
+        .unwrap()
+        .into_iter()
+        .map(|event| event.state)
+        .collect();
+    assert_eq!(states.last(), Some(&TransactionLifecycleState::Evicted));
+
+    // Unknown transactions have no lifecycle
+    assert!(pool.get_transaction_lifecycle(&HashValue::random()).is_none());
+}
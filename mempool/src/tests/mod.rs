@@ -16,6 +16,8 @@ mod multi_node_test;
 #[cfg(test)]
 mod node;
 #[cfg(test)]
+mod persistence_test;
+#[cfg(test)]
 mod shared_mempool_test;
 
 pub mod fuzzing;
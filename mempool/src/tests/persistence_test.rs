@@ -0,0 +1,253 @@
+// Copyright © CreditChain Research Team
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    core_mempool::CoreMempool,
+    network::MempoolSyncMsg,
+    shared_mempool::{persistence::MempoolJournal, tasks, types::SharedMempool},
+    tests::common::{add_txn, setup_mempool, TestTransaction},
+};
+use creditchain_config::{
+    config::{NodeConfig, NodeType},
+    network_id::NetworkId,
+};
+use creditchain_infallible::{Mutex, RwLock};
+use creditchain_network::{
+    application::{interface::NetworkClient, storage::PeersAndMetadata},
+    protocols::wire::handshake::v1::ProtocolId::MempoolDirectSend,
+};
+use creditchain_storage_interface::mock::MockDbReaderWriter;
+use creditchain_temppath::TempPath;
+use creditchain_types::transaction::ReplayProtector;
+use creditchain_vm_validator::mocks::mock_vm_validator::{
+    MockVMValidator, SEQ_NUMBER_TOO_OLD_TEST_ADD,
+};
+use std::{
+    collections::{HashMap, HashSet},
+    fs::OpenOptions,
+    io::Write,
+    path::Path,
+    sync::Arc,
+    time::Duration,
+};
+
+// A snapshot interval long enough that periodic snapshots never run in the tests
+const SNAPSHOT_INTERVAL: Duration = Duration::from_secs(3600);
+
+/// Opens a journal in the given directory for the given mempool
+fn open_journal(dir: &Path, mempool: Arc<Mutex<CoreMempool>>) -> MempoolJournal {
+    MempoolJournal::open(dir, mempool, SNAPSHOT_INTERVAL).unwrap()
+}
+
+/// Opens a journal in the given directory for an empty mempool
+fn open_journal_with_empty_mempool(dir: &Path) -> MempoolJournal {
+    let (mempool, _) = setup_mempool();
+    open_journal(dir, Arc::new(Mutex::new(mempool)))
+}
+
+/// Creates a shared mempool (with a mock validator and DB) for the given mempool and journal
+fn create_shared_mempool(
+    config: &NodeConfig,
+    mempool: Arc<Mutex<CoreMempool>>,
+    journal: Arc<MempoolJournal>,
+) -> SharedMempool<NetworkClient<MempoolSyncMsg>, MockVMValidator> {
+    let network_client = NetworkClient::new(
+        vec![MempoolDirectSend],
+        vec![],
+        HashMap::new(),
+        PeersAndMetadata::new(&[NetworkId::Validator]),
+    );
+    SharedMempool::new(
+        mempool,
+        config.mempool.clone(),
+        config.transaction_filters.mempool_filter.clone(),
+        network_client,
+        Arc::new(MockDbReaderWriter),
+        Arc::new(RwLock::new(MockVMValidator)),
+        vec![],
+        NodeType::extract_from_config(config),
+        Some(journal),
+    )
+}
+
+#[test]
+fn test_journal_append_and_load() {
+    // Open a journal in a temporary directory
+    let journal_dir = TempPath::new();
+    let journal = open_journal_with_empty_mempool(journal_dir.path());
+
+    // Append several transactions (including a duplicate)
+    let transactions: Vec<_> = (0..5)
+        .map(|i| {
+            TestTransaction::new(i, ReplayProtector::SequenceNumber(0), 1).make_signed_transaction()
+        })
+        .collect();
+    journal.append(transactions[0..3].to_vec()).unwrap();
+    journal.append(transactions[2..5].to_vec()).unwrap();
+    journal.flush();
+
+    // Verify that all transactions are loaded (without duplicates)
+    assert_eq!(journal.load().unwrap(), transactions);
+
+    // Verify that the journal can be reopened and reloaded
+    drop(journal);
+    let journal = open_journal_with_empty_mempool(journal_dir.path());
+    assert_eq!(journal.load().unwrap(), transactions);
+}
+
+#[test]
+fn test_journal_truncated_record() {
+    // Open a journal and append a transaction
+    let journal_dir = TempPath::new();
+    let journal = open_journal_with_empty_mempool(journal_dir.path());
+    let transaction =
+        TestTransaction::new(0, ReplayProtector::SequenceNumber(0), 1).make_signed_transaction();
+    journal.append(vec![transaction.clone()]).unwrap();
+    journal.flush();
+
+    // Append a partial record (e.g., due to a crash mid-write)
+    let mut journal_file = OpenOptions::new()
+        .append(true)
+        .open(journal_dir.path().join("journal"))
+        .unwrap();
+    journal_file.write_all(&[100, 0, 0, 0, 1, 2, 3]).unwrap();
+
+    // Verify that the truncated record is ignored
+    assert_eq!(journal.load().unwrap(), vec![transaction]);
+}
+
+#[test]
+fn test_journal_oversized_record() {
+    // Open a journal and append a transaction
+    let journal_dir = TempPath::new();
+    let journal = open_journal_with_empty_mempool(journal_dir.path());
+    let transaction =
+        TestTransaction::new(0, ReplayProtector::SequenceNumber(0), 1).make_signed_transaction();
+    journal.append(vec![transaction.clone()]).unwrap();
+    journal.flush();
+
+    // Append a corrupt length prefix (of ~4 GiB) followed by more data
+    let mut journal_file = OpenOptions::new()
+        .append(true)
+        .open(journal_dir.path().join("journal"))
+        .unwrap();
+    journal_file
+        .write_all(&[0xff, 0xff, 0xff, 0xff, 1, 2, 3])
+        .unwrap();
+
+    // Verify that reading stops at the corrupt record
+    assert_eq!(journal.load().unwrap(), vec![transaction]);
+}
+
+#[test]
+fn test_journal_snapshot_compaction() {
+    // Create a mempool with several transactions
+    let (mut mempool, _) = setup_mempool();
+    let mempool_transactions: Vec<_> = (0..3)
+        .map(|i| {
+            add_txn(
+                &mut mempool,
+                TestTransaction::new(i, ReplayProtector::SequenceNumber(0), 1),
+            )
+            .unwrap()
+        })
+        .collect();
+    let mempool = Arc::new(Mutex::new(mempool));
+
+    // Open a journal and append the mempool transactions and a transaction that is no longer in mempool
+    let journal_dir = TempPath::new();
+    let journal = open_journal(journal_dir.path(), mempool);
+    let removed_transaction =
+        TestTransaction::new(10, ReplayProtector::SequenceNumber(0), 1).make_signed_transaction();
+    journal.append(mempool_transactions.clone()).unwrap();
+    journal.append(vec![removed_transaction]).unwrap();
+
+    // Write a snapshot and verify that the journal is truncated
+    journal.request_snapshot().unwrap();
+    journal.flush();
+    let journal_file_len = std::fs::metadata(journal_dir.path().join("journal"))
+        .unwrap()
+        .len();
+    assert_eq!(journal_file_len, 0);
+
+    // Append a new transaction and verify that only the mempool transactions
+    // and the new transaction are loaded.
+    let new_transaction =
+        TestTransaction::new(20, ReplayProtector::SequenceNumber(0), 1).make_signed_transaction();
+    journal.append(vec![new_transaction.clone()]).unwrap();
+    journal.flush();
+    let loaded_hashes: HashSet<_> = journal
+        .load()
+        .unwrap()
+        .iter()
+        .map(|transaction| transaction.committed_hash())
+        .collect();
+    let expected_hashes: HashSet<_> = mempool_transactions
+        .iter()
+        .chain([&new_transaction])
+        .map(|transaction| transaction.committed_hash())
+        .collect();
+    assert_eq!(loaded_hashes, expected_hashes);
+}
+
+#[tokio::test]
+async fn test_journal_restore() {
+    // Open a journal for an empty mempool
+    let config = NodeConfig::default();
+    let mempool = Arc::new(Mutex::new(CoreMempool::new(&config)));
+    let journal_dir = TempPath::new();
+    let journal = Arc::new(open_journal(journal_dir.path(), mempool.clone()));
+
+    // Journal an expired transaction, a transaction that has since been committed
+    // (i.e., its sequence number is now too old) and several pending transactions.
+    let expired_transaction = TestTransaction::new(0, ReplayProtector::SequenceNumber(0), 1)
+        .make_signed_transaction_with_expiration_time(0);
+    let committed_transaction = TestTransaction::new_with_address(
+        SEQ_NUMBER_TOO_OLD_TEST_ADD,
+        ReplayProtector::SequenceNumber(0),
+        1,
+    )
+    .make_signed_transaction();
+    let pending_transactions: Vec<_> = (1..4)
+        .map(|i| {
+            TestTransaction::new(i, ReplayProtector::SequenceNumber(0), 1).make_signed_transaction()
+        })
+        .collect();
+    journal
+        .append(vec![
+            expired_transaction.clone(),
+            committed_transaction.clone(),
+        ])
+        .unwrap();
+    journal.append(pending_transactions.clone()).unwrap();
+    journal.flush();
+
+    // Restore the journaled transactions
+    let smp = create_shared_mempool(&config, mempool.clone(), journal.clone());
+    tasks::process_journaled_transactions(smp, journal.clone()).await;
+
+    // Verify that only the pending transactions were re-inserted into mempool
+    {
+        let mempool = mempool.lock();
+        for transaction in &pending_transactions {
+            assert!(mempool.get_by_hash(transaction.committed_hash()).is_some());
+        }
+        for transaction in [&expired_transaction, &committed_transaction] {
+            assert!(mempool.get_by_hash(transaction.committed_hash()).is_none());
+        }
+    }
+
+    // Verify that the journal was compacted to the restored transactions
+    journal.flush();
+    let loaded_hashes: HashSet<_> = journal
+        .load()
+        .unwrap()
+        .iter()
+        .map(|transaction| transaction.committed_hash())
+        .collect();
+    let expected_hashes: HashSet<_> = pending_transactions
+        .iter()
+        .map(|transaction| transaction.committed_hash())
+        .collect();
+    assert_eq!(loaded_hashes, expected_hashes);
+}
@@ -0,0 +1,128 @@
+// Copyright (c) CreditChain Research Team
+// SPDX-License-Identifier: Apache-2.0
+
+// @generated
+// This file is @generated by prost-build.
+/// Subscribes to the consensus updates published by the node. Authentication is
+/// done through the `authorization` request metadata, i.e., `Bearer <passcode>`.
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, Copy, PartialEq, ::prost::Message)]
+pub struct SubscribeRequest {
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SubscribeResponse {
+    #[prost(enumeration="ObserverMessageType", tag="1")]
+    pub message_type: i32,
+    /// Epoch and round of the block the message refers to.
+    #[prost(uint64, tag="2")]
+    pub epoch: u64,
+    #[prost(uint64, tag="3")]
+    pub round: u64,
+    /// BCS serialized ConsensusObserverDirectSend message. Ordered blocks and commit
+    /// decisions carry the ledger info signed by a quorum of the validator set, so
+    /// subscribers can verify ordering against the validator set of the epoch.
+    #[prost(bytes="vec", tag="4")]
+    pub bcs_message: ::prost::alloc::vec::Vec<u8>,
+}
+/// The type of the consensus observer message carried by a SubscribeResponse.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum ObserverMessageType {
+    Unspecified = 0,
+    OrderedBlock = 1,
+    CommitDecision = 2,
+    BlockPayload = 3,
+    OrderedBlockWithWindow = 4,
+}
+impl ObserverMessageType {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            ObserverMessageType::Unspecified => "OBSERVER_MESSAGE_TYPE_UNSPECIFIED",
+            ObserverMessageType::OrderedBlock => "OBSERVER_MESSAGE_TYPE_ORDERED_BLOCK",
+            ObserverMessageType::CommitDecision => "OBSERVER_MESSAGE_TYPE_COMMIT_DECISION",
+            ObserverMessageType::BlockPayload => "OBSERVER_MESSAGE_TYPE_BLOCK_PAYLOAD",
+            ObserverMessageType::OrderedBlockWithWindow => "OBSERVER_MESSAGE_TYPE_ORDERED_BLOCK_WITH_WINDOW",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "OBSERVER_MESSAGE_TYPE_UNSPECIFIED" => Some(Self::Unspecified),
+            "OBSERVER_MESSAGE_TYPE_ORDERED_BLOCK" => Some(Self::OrderedBlock),
+            "OBSERVER_MESSAGE_TYPE_COMMIT_DECISION" => Some(Self::CommitDecision),
+            "OBSERVER_MESSAGE_TYPE_BLOCK_PAYLOAD" => Some(Self::BlockPayload),
+            "OBSERVER_MESSAGE_TYPE_ORDERED_BLOCK_WITH_WINDOW" => Some(Self::OrderedBlockWithWindow),
+            _ => None,
+        }
+    }
+}
+/// Encoded file descriptor set for the `creditchain.consensus_observer.v1` package
+pub const FILE_DESCRIPTOR_SET: &[u8] = &[
+    0x0a, 0xad, 0x07, 0x0a, 0x3a, 0x63, 0x72, 0x65, 0x64, 0x69, 0x74, 0x63, 0x68, 0x61, 0x69, 0x6e,
+    0x2f, 0x63, 0x6f, 0x6e, 0x73, 0x65, 0x6e, 0x73, 0x75, 0x73, 0x5f, 0x6f, 0x62, 0x73, 0x65, 0x72,
+    0x76, 0x65, 0x72, 0x2f, 0x76, 0x31, 0x2f, 0x63, 0x6f, 0x6e, 0x73, 0x65, 0x6e, 0x73, 0x75, 0x73,
+    0x5f, 0x6f, 0x62, 0x73, 0x65, 0x72, 0x76, 0x65, 0x72, 0x2e, 0x70, 0x72, 0x6f, 0x74, 0x6f, 0x12,
+    0x21, 0x63, 0x72, 0x65, 0x64, 0x69, 0x74, 0x63, 0x68, 0x61, 0x69, 0x6e, 0x2e, 0x63, 0x6f, 0x6e,
+    0x73, 0x65, 0x6e, 0x73, 0x75, 0x73, 0x5f, 0x6f, 0x62, 0x73, 0x65, 0x72, 0x76, 0x65, 0x72, 0x2e,
+    0x76, 0x31, 0x22, 0x12, 0x0a, 0x10, 0x53, 0x75, 0x62, 0x73, 0x63, 0x72, 0x69, 0x62, 0x65, 0x52,
+    0x65, 0x71, 0x75, 0x65, 0x73, 0x74, 0x22, 0xc3, 0x01, 0x0a, 0x11, 0x53, 0x75, 0x62, 0x73, 0x63,
+    0x72, 0x69, 0x62, 0x65, 0x52, 0x65, 0x73, 0x70, 0x6f, 0x6e, 0x73, 0x65, 0x12, 0x59, 0x0a, 0x0c,
+    0x6d, 0x65, 0x73, 0x73, 0x61, 0x67, 0x65, 0x5f, 0x74, 0x79, 0x70, 0x65, 0x18, 0x01, 0x20, 0x01,
+    0x28, 0x0e, 0x32, 0x36, 0x2e, 0x63, 0x72, 0x65, 0x64, 0x69, 0x74, 0x63, 0x68, 0x61, 0x69, 0x6e,
+    0x2e, 0x63, 0x6f, 0x6e, 0x73, 0x65, 0x6e, 0x73, 0x75, 0x73, 0x5f, 0x6f, 0x62, 0x73, 0x65, 0x72,
+    0x76, 0x65, 0x72, 0x2e, 0x76, 0x31, 0x2e, 0x4f, 0x62, 0x73, 0x65, 0x72, 0x76, 0x65, 0x72, 0x4d,
+    0x65, 0x73, 0x73, 0x61, 0x67, 0x65, 0x54, 0x79, 0x70, 0x65, 0x52, 0x0b, 0x6d, 0x65, 0x73, 0x73,
+    0x61, 0x67, 0x65, 0x54, 0x79, 0x70, 0x65, 0x12, 0x18, 0x0a, 0x05, 0x65, 0x70, 0x6f, 0x63, 0x68,
+    0x18, 0x02, 0x20, 0x01, 0x28, 0x04, 0x42, 0x02, 0x30, 0x01, 0x52, 0x05, 0x65, 0x70, 0x6f, 0x63,
+    0x68, 0x12, 0x18, 0x0a, 0x05, 0x72, 0x6f, 0x75, 0x6e, 0x64, 0x18, 0x03, 0x20, 0x01, 0x28, 0x04,
+    0x42, 0x02, 0x30, 0x01, 0x52, 0x05, 0x72, 0x6f, 0x75, 0x6e, 0x64, 0x12, 0x1f, 0x0a, 0x0b, 0x62,
+    0x63, 0x73, 0x5f, 0x6d, 0x65, 0x73, 0x73, 0x61, 0x67, 0x65, 0x18, 0x04, 0x20, 0x01, 0x28, 0x0c,
+    0x52, 0x0a, 0x62, 0x63, 0x73, 0x4d, 0x65, 0x73, 0x73, 0x61, 0x67, 0x65, 0x2a, 0xee, 0x01, 0x0a,
+    0x13, 0x4f, 0x62, 0x73, 0x65, 0x72, 0x76, 0x65, 0x72, 0x4d, 0x65, 0x73, 0x73, 0x61, 0x67, 0x65,
+    0x54, 0x79, 0x70, 0x65, 0x12, 0x25, 0x0a, 0x21, 0x4f, 0x42, 0x53, 0x45, 0x52, 0x56, 0x45, 0x52,
+    0x5f, 0x4d, 0x45, 0x53, 0x53, 0x41, 0x47, 0x45, 0x5f, 0x54, 0x59, 0x50, 0x45, 0x5f, 0x55, 0x4e,
+    0x53, 0x50, 0x45, 0x43, 0x49, 0x46, 0x49, 0x45, 0x44, 0x10, 0x00, 0x12, 0x27, 0x0a, 0x23, 0x4f,
+    0x42, 0x53, 0x45, 0x52, 0x56, 0x45, 0x52, 0x5f, 0x4d, 0x45, 0x53, 0x53, 0x41, 0x47, 0x45, 0x5f,
+    0x54, 0x59, 0x50, 0x45, 0x5f, 0x4f, 0x52, 0x44, 0x45, 0x52, 0x45, 0x44, 0x5f, 0x42, 0x4c, 0x4f,
+    0x43, 0x4b, 0x10, 0x01, 0x12, 0x29, 0x0a, 0x25, 0x4f, 0x42, 0x53, 0x45, 0x52, 0x56, 0x45, 0x52,
+    0x5f, 0x4d, 0x45, 0x53, 0x53, 0x41, 0x47, 0x45, 0x5f, 0x54, 0x59, 0x50, 0x45, 0x5f, 0x43, 0x4f,
+    0x4d, 0x4d, 0x49, 0x54, 0x5f, 0x44, 0x45, 0x43, 0x49, 0x53, 0x49, 0x4f, 0x4e, 0x10, 0x02, 0x12,
+    0x27, 0x0a, 0x23, 0x4f, 0x42, 0x53, 0x45, 0x52, 0x56, 0x45, 0x52, 0x5f, 0x4d, 0x45, 0x53, 0x53,
+    0x41, 0x47, 0x45, 0x5f, 0x54, 0x59, 0x50, 0x45, 0x5f, 0x42, 0x4c, 0x4f, 0x43, 0x4b, 0x5f, 0x50,
+    0x41, 0x59, 0x4c, 0x4f, 0x41, 0x44, 0x10, 0x03, 0x12, 0x33, 0x0a, 0x2f, 0x4f, 0x42, 0x53, 0x45,
+    0x52, 0x56, 0x45, 0x52, 0x5f, 0x4d, 0x45, 0x53, 0x53, 0x41, 0x47, 0x45, 0x5f, 0x54, 0x59, 0x50,
+    0x45, 0x5f, 0x4f, 0x52, 0x44, 0x45, 0x52, 0x45, 0x44, 0x5f, 0x42, 0x4c, 0x4f, 0x43, 0x4b, 0x5f,
+    0x57, 0x49, 0x54, 0x48, 0x5f, 0x57, 0x49, 0x4e, 0x44, 0x4f, 0x57, 0x10, 0x04, 0x32, 0x94, 0x01,
+    0x0a, 0x18, 0x43, 0x6f, 0x6e, 0x73, 0x65, 0x6e, 0x73, 0x75, 0x73, 0x4f, 0x62, 0x73, 0x65, 0x72,
+    0x76, 0x65, 0x72, 0x53, 0x65, 0x72, 0x76, 0x69, 0x63, 0x65, 0x12, 0x78, 0x0a, 0x09, 0x53, 0x75,
+    0x62, 0x73, 0x63, 0x72, 0x69, 0x62, 0x65, 0x12, 0x33, 0x2e, 0x63, 0x72, 0x65, 0x64, 0x69, 0x74,
+    0x63, 0x68, 0x61, 0x69, 0x6e, 0x2e, 0x63, 0x6f, 0x6e, 0x73, 0x65, 0x6e, 0x73, 0x75, 0x73, 0x5f,
+    0x6f, 0x62, 0x73, 0x65, 0x72, 0x76, 0x65, 0x72, 0x2e, 0x76, 0x31, 0x2e, 0x53, 0x75, 0x62, 0x73,
+    0x63, 0x72, 0x69, 0x62, 0x65, 0x52, 0x65, 0x71, 0x75, 0x65, 0x73, 0x74, 0x1a, 0x34, 0x2e, 0x63,
+    0x72, 0x65, 0x64, 0x69, 0x74, 0x63, 0x68, 0x61, 0x69, 0x6e, 0x2e, 0x63, 0x6f, 0x6e, 0x73, 0x65,
+    0x6e, 0x73, 0x75, 0x73, 0x5f, 0x6f, 0x62, 0x73, 0x65, 0x72, 0x76, 0x65, 0x72, 0x2e, 0x76, 0x31,
+    0x2e, 0x53, 0x75, 0x62, 0x73, 0x63, 0x72, 0x69, 0x62, 0x65, 0x52, 0x65, 0x73, 0x70, 0x6f, 0x6e,
+    0x73, 0x65, 0x30, 0x01, 0x42, 0xe1, 0x01, 0x0a, 0x25, 0x63, 0x6f, 0x6d, 0x2e, 0x63, 0x72, 0x65,
+    0x64, 0x69, 0x74, 0x63, 0x68, 0x61, 0x69, 0x6e, 0x2e, 0x63, 0x6f, 0x6e, 0x73, 0x65, 0x6e, 0x73,
+    0x75, 0x73, 0x5f, 0x6f, 0x62, 0x73, 0x65, 0x72, 0x76, 0x65, 0x72, 0x2e, 0x76, 0x31, 0x42, 0x16,
+    0x43, 0x6f, 0x6e, 0x73, 0x65, 0x6e, 0x73, 0x75, 0x73, 0x4f, 0x62, 0x73, 0x65, 0x72, 0x76, 0x65,
+    0x72, 0x50, 0x72, 0x6f, 0x74, 0x6f, 0x50, 0x01, 0xa2, 0x02, 0x03, 0x43, 0x43, 0x58, 0xaa, 0x02,
+    0x20, 0x43, 0x72, 0x65, 0x64, 0x69, 0x74, 0x63, 0x68, 0x61, 0x69, 0x6e, 0x2e, 0x43, 0x6f, 0x6e,
+    0x73, 0x65, 0x6e, 0x73, 0x75, 0x73, 0x4f, 0x62, 0x73, 0x65, 0x72, 0x76, 0x65, 0x72, 0x2e, 0x56,
+    0x31, 0xca, 0x02, 0x20, 0x43, 0x72, 0x65, 0x64, 0x69, 0x74, 0x63, 0x68, 0x61, 0x69, 0x6e, 0x5c,
+    0x43, 0x6f, 0x6e, 0x73, 0x65, 0x6e, 0x73, 0x75, 0x73, 0x4f, 0x62, 0x73, 0x65, 0x72, 0x76, 0x65,
+    0x72, 0x5c, 0x56, 0x31, 0xe2, 0x02, 0x2c, 0x43, 0x72, 0x65, 0x64, 0x69, 0x74, 0x63, 0x68, 0x61,
+    0x69, 0x6e, 0x5c, 0x43, 0x6f, 0x6e, 0x73, 0x65, 0x6e, 0x73, 0x75, 0x73, 0x4f, 0x62, 0x73, 0x65,
+    0x72, 0x76, 0x65, 0x72, 0x5c, 0x56, 0x31, 0x5c, 0x47, 0x50, 0x42, 0x4d, 0x65, 0x74, 0x61, 0x64,
+    0x61, 0x74, 0x61, 0xea, 0x02, 0x22, 0x43, 0x72, 0x65, 0x64, 0x69, 0x74, 0x63, 0x68, 0x61, 0x69,
+    0x6e, 0x3a, 0x3a, 0x43, 0x6f, 0x6e, 0x73, 0x65, 0x6e, 0x73, 0x75, 0x73, 0x4f, 0x62, 0x73, 0x65,
+    0x72, 0x76, 0x65, 0x72, 0x3a, 0x3a, 0x56, 0x31, 0x62, 0x06, 0x70, 0x72, 0x6f, 0x74, 0x6f, 0x33,
+];
+include!("creditchain.consensus_observer.v1.serde.rs");
+include!("creditchain.consensus_observer.v1.tonic.rs");
+// @@protoc_insertion_point(module)
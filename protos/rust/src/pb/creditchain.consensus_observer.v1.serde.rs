@@ -0,0 +1,309 @@
+// Copyright (c) CreditChain Research Team
+// SPDX-License-Identifier: Apache-2.0
+
+// @generated
+impl serde::Serialize for ObserverMessageType {
+    #[allow(deprecated)]
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let variant = match self {
+            Self::Unspecified => "OBSERVER_MESSAGE_TYPE_UNSPECIFIED",
+            Self::OrderedBlock => "OBSERVER_MESSAGE_TYPE_ORDERED_BLOCK",
+            Self::CommitDecision => "OBSERVER_MESSAGE_TYPE_COMMIT_DECISION",
+            Self::BlockPayload => "OBSERVER_MESSAGE_TYPE_BLOCK_PAYLOAD",
+            Self::OrderedBlockWithWindow => "OBSERVER_MESSAGE_TYPE_ORDERED_BLOCK_WITH_WINDOW",
+        };
+        serializer.serialize_str(variant)
+    }
+}
+impl<'de> serde::Deserialize<'de> for ObserverMessageType {
+    #[allow(deprecated)]
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        const FIELDS: &[&str] = &[
+            "OBSERVER_MESSAGE_TYPE_UNSPECIFIED",
+            "OBSERVER_MESSAGE_TYPE_ORDERED_BLOCK",
+            "OBSERVER_MESSAGE_TYPE_COMMIT_DECISION",
+            "OBSERVER_MESSAGE_TYPE_BLOCK_PAYLOAD",
+            "OBSERVER_MESSAGE_TYPE_ORDERED_BLOCK_WITH_WINDOW",
+        ];
+
+        struct GeneratedVisitor;
+
+        impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+            type Value = ObserverMessageType;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                write!(formatter, "expected one of: {:?}", &FIELDS)
+            }
+
+            fn visit_i64<E>(self, v: i64) -> std::result::Result<Self::Value, E>
+            where
+                E: serde::de::Error,
+            {
+                use std::convert::TryFrom;
+                i32::try_from(v)
+                    .ok()
+                    .and_then(ObserverMessageType::from_i32)
+                    .ok_or_else(|| {
+                        serde::de::Error::invalid_value(serde::de::Unexpected::Signed(v), &self)
+                    })
+            }
+
+            fn visit_u64<E>(self, v: u64) -> std::result::Result<Self::Value, E>
+            where
+                E: serde::de::Error,
+            {
+                use std::convert::TryFrom;
+                i32::try_from(v)
+                    .ok()
+                    .and_then(ObserverMessageType::from_i32)
+                    .ok_or_else(|| {
+                        serde::de::Error::invalid_value(serde::de::Unexpected::Unsigned(v), &self)
+                    })
+            }
+
+            fn visit_str<E>(self, value: &str) -> std::result::Result<Self::Value, E>
+            where
+                E: serde::de::Error,
+            {
+                match value {
+                    "OBSERVER_MESSAGE_TYPE_UNSPECIFIED" => Ok(ObserverMessageType::Unspecified),
+                    "OBSERVER_MESSAGE_TYPE_ORDERED_BLOCK" => Ok(ObserverMessageType::OrderedBlock),
+                    "OBSERVER_MESSAGE_TYPE_COMMIT_DECISION" => Ok(ObserverMessageType::CommitDecision),
+                    "OBSERVER_MESSAGE_TYPE_BLOCK_PAYLOAD" => Ok(ObserverMessageType::BlockPayload),
+                    "OBSERVER_MESSAGE_TYPE_ORDERED_BLOCK_WITH_WINDOW" => Ok(ObserverMessageType::OrderedBlockWithWindow),
+                    _ => Err(serde::de::Error::unknown_variant(value, FIELDS)),
+                }
+            }
+        }
+        deserializer.deserialize_any(GeneratedVisitor)
+    }
+}
+impl serde::Serialize for SubscribeRequest {
+    #[allow(deprecated)]
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeStruct;
+        let len = 0;
+        let struct_ser = serializer.serialize_struct("creditchain.consensus_observer.v1.SubscribeRequest", len)?;
+        struct_ser.end()
+    }
+}
+impl<'de> serde::Deserialize<'de> for SubscribeRequest {
+    #[allow(deprecated)]
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        const FIELDS: &[&str] = &[
+        ];
+
+        #[allow(clippy::enum_variant_names)]
+        enum GeneratedField {
+        }
+        impl<'de> serde::Deserialize<'de> for GeneratedField {
+            fn deserialize<D>(deserializer: D) -> std::result::Result<GeneratedField, D::Error>
+            where
+                D: serde::Deserializer<'de>,
+            {
+                struct GeneratedVisitor;
+
+                impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+                    type Value = GeneratedField;
+
+                    fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                        write!(formatter, "expected one of: {:?}", &FIELDS)
+                    }
+
+                    #[allow(unused_variables)]
+                    fn visit_str<E>(self, value: &str) -> std::result::Result<GeneratedField, E>
+                    where
+                        E: serde::de::Error,
+                    {
+                            Err(serde::de::Error::unknown_field(value, FIELDS))
+                    }
+                }
+                deserializer.deserialize_identifier(GeneratedVisitor)
+            }
+        }
+        struct GeneratedVisitor;
+        impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+            type Value = SubscribeRequest;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                formatter.write_str("struct creditchain.consensus_observer.v1.SubscribeRequest")
+            }
+
+            fn visit_map<V>(self, mut map: V) -> std::result::Result<SubscribeRequest, V::Error>
+                where
+                    V: serde::de::MapAccess<'de>,
+            {
+                while map.next_key::<GeneratedField>()?.is_some() {
+                    let _ = map.next_value::<serde::de::IgnoredAny>()?;
+                }
+                Ok(SubscribeRequest {
+                })
+            }
+        }
+        deserializer.deserialize_struct("creditchain.consensus_observer.v1.SubscribeRequest", FIELDS, GeneratedVisitor)
+    }
+}
+impl serde::Serialize for SubscribeResponse {
+    #[allow(deprecated)]
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeStruct;
+        let mut len = 0;
+        if self.message_type != 0 {
+            len += 1;
+        }
+        if self.epoch != 0 {
+            len += 1;
+        }
+        if self.round != 0 {
+            len += 1;
+        }
+        if !self.bcs_message.is_empty() {
+            len += 1;
+        }
+        let mut struct_ser = serializer.serialize_struct("creditchain.consensus_observer.v1.SubscribeResponse", len)?;
+        if self.message_type != 0 {
+            let v = ObserverMessageType::from_i32(self.message_type)
+                .ok_or_else(|| serde::ser::Error::custom(format!("Invalid variant {}", self.message_type)))?;
+            struct_ser.serialize_field("messageType", &v)?;
+        }
+        if self.epoch != 0 {
+            struct_ser.serialize_field("epoch", ToString::to_string(&self.epoch).as_str())?;
+        }
+        if self.round != 0 {
+            struct_ser.serialize_field("round", ToString::to_string(&self.round).as_str())?;
+        }
+        if !self.bcs_message.is_empty() {
+            struct_ser.serialize_field("bcsMessage", pbjson::private::base64::encode(&self.bcs_message).as_str())?;
+        }
+        struct_ser.end()
+    }
+}
+impl<'de> serde::Deserialize<'de> for SubscribeResponse {
+    #[allow(deprecated)]
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        const FIELDS: &[&str] = &[
+            "message_type",
+            "messageType",
+            "epoch",
+            "round",
+            "bcs_message",
+            "bcsMessage",
+        ];
+
+        #[allow(clippy::enum_variant_names)]
+        enum GeneratedField {
+            MessageType,
+            Epoch,
+            Round,
+            BcsMessage,
+        }
+        impl<'de> serde::Deserialize<'de> for GeneratedField {
+            fn deserialize<D>(deserializer: D) -> std::result::Result<GeneratedField, D::Error>
+            where
+                D: serde::Deserializer<'de>,
+            {
+                struct GeneratedVisitor;
+
+                impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+                    type Value = GeneratedField;
+
+                    fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                        write!(formatter, "expected one of: {:?}", &FIELDS)
+                    }
+
+                    #[allow(unused_variables)]
+                    fn visit_str<E>(self, value: &str) -> std::result::Result<GeneratedField, E>
+                    where
+                        E: serde::de::Error,
+                    {
+                        match value {
+                            "messageType" | "message_type" => Ok(GeneratedField::MessageType),
+                            "epoch" => Ok(GeneratedField::Epoch),
+                            "round" => Ok(GeneratedField::Round),
+                            "bcsMessage" | "bcs_message" => Ok(GeneratedField::BcsMessage),
+                            _ => Err(serde::de::Error::unknown_field(value, FIELDS)),
+                        }
+                    }
+                }
+                deserializer.deserialize_identifier(GeneratedVisitor)
+            }
+        }
+        struct GeneratedVisitor;
+        impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+            type Value = SubscribeResponse;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                formatter.write_str("struct creditchain.consensus_observer.v1.SubscribeResponse")
+            }
+
+            fn visit_map<V>(self, mut map: V) -> std::result::Result<SubscribeResponse, V::Error>
+                where
+                    V: serde::de::MapAccess<'de>,
+            {
+                let mut message_type__ = None;
+                let mut epoch__ = None;
+                let mut round__ = None;
+                let mut bcs_message__ = None;
+                while let Some(k) = map.next_key()? {
+                    match k {
+                        GeneratedField::MessageType => {
+                            if message_type__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("messageType"));
+                            }
+                            message_type__ = Some(map.next_value::<ObserverMessageType>()? as i32);
+                        }
+                        GeneratedField::Epoch => {
+                            if epoch__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("epoch"));
+                            }
+                            epoch__ =
+                                Some(map.next_value::<::pbjson::private::NumberDeserialize<_>>()?.0)
+                            ;
+                        }
+                        GeneratedField::Round => {
+                            if round__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("round"));
+                            }
+                            round__ =
+                                Some(map.next_value::<::pbjson::private::NumberDeserialize<_>>()?.0)
+                            ;
+                        }
+                        GeneratedField::BcsMessage => {
+                            if bcs_message__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("bcsMessage"));
+                            }
+                            bcs_message__ =
+                                Some(map.next_value::<::pbjson::private::BytesDeserialize<_>>()?.0)
+                            ;
+                        }
+                    }
+                }
+                Ok(SubscribeResponse {
+                    message_type: message_type__.unwrap_or_default(),
+                    epoch: epoch__.unwrap_or_default(),
+                    round: round__.unwrap_or_default(),
+                    bcs_message: bcs_message__.unwrap_or_default(),
+                })
+            }
+        }
+        deserializer.deserialize_struct("creditchain.consensus_observer.v1.SubscribeResponse", FIELDS, GeneratedVisitor)
+    }
+}
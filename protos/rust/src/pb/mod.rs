@@ -3,6 +3,13 @@
 
 // @generated
 pub mod creditchain {
+    pub mod consensus_observer {
+        // @@protoc_insertion_point(attribute:creditchain.consensus_observer.v1)
+        pub mod v1 {
+            include!("creditchain.consensus_observer.v1.rs");
+            // @@protoc_insertion_point(creditchain.consensus_observer.v1)
+        }
+    }
     pub mod indexer {
         // @@protoc_insertion_point(attribute:creditchain.indexer.v1)
         pub mod v1 {
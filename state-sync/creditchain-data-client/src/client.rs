@@ -13,7 +13,7 @@ use crate::{
     metrics::{
         increment_request_counter, set_gauge, start_request_timer, PRIORITIZED_PEER, REGULAR_PEER,
     },
-    peer_states::{ErrorType, PeerStates},
+    peer_states::{ErrorType, PeerStates, PeerStatesStorageInterface},
     poller::DataSummaryPoller,
     priority,
     priority::PeerPriority,
@@ -45,7 +45,7 @@ use creditchain_storage_service_types::{
     responses::{StorageServerSummary, StorageServiceResponse, TransactionOrOutputListWithProofV2},
     Epoch, StorageServiceMessage,
 };
-use creditchain_time_service::TimeService;
+use creditchain_time_service::{TimeService, TimeServiceTrait};
 use creditchain_types::{
     epoch_change::EpochChangeProof,
     ledger_info::LedgerInfoWithSignatures,
@@ -99,6 +99,8 @@ pub struct CreditChainDataClient {
     active_subscription_state: Arc<Mutex<Option<SubscriptionState>>>,
     /// All of the data-client specific data we have on each network peer.
     peer_states: Arc<PeerStates>,
+    /// The storage used to persist peer states across restarts (if any).
+    peer_states_storage: Option<Arc<dyn PeerStatesStorageInterface>>,
     /// A cached, aggregate data summary of all unbanned peers' data summaries.
     global_summary_cache: Arc<ArcSwap<GlobalDataSummary>>,
     /// Used for generating the next request/response id.
@@ -114,6 +116,7 @@ impl CreditChainDataClient {
         time_service: TimeService,
         storage: Arc<dyn DbReader>,
         storage_service_client: StorageServiceClient<NetworkClient<StorageServiceMessage>>,
+        peer_states_storage: Option<Arc<dyn PeerStatesStorageInterface>>,
         runtime: Option<Handle>,
     ) -> (Self, DataSummaryPoller) {
         // Wrap the configs in an Arc (to be shared across components)
@@ -127,11 +130,15 @@ impl CreditChainDataClient {
             storage_service_client: storage_service_client.clone(),
             active_subscription_state: Arc::new(Mutex::new(None)),
             peer_states: Arc::new(PeerStates::new(data_client_config.clone())),
+            peer_states_storage,
             global_summary_cache: Arc::new(ArcSwap::from(Arc::new(GlobalDataSummary::empty()))),
             response_id_generator: Arc::new(U64IdGenerator::new()),
             time_service: time_service.clone(),
         };
 
+        // Restore any previously persisted peer states
+        data_client.restore_peer_states();
+
         // Create the data summary poller
         let data_summary_poller = DataSummaryPoller::new(
             data_client_config,
@@ -160,6 +167,67 @@ impl CreditChainDataClient {
         self.storage_service_client.get_peers_and_metadata()
     }
 
+    /// Returns the peer states storage iff peer state persistence is enabled
+    fn get_peer_states_storage(&self) -> Option<&Arc<dyn PeerStatesStorageInterface>> {
+        if self
+            .data_client_config
+            .peer_score_persistence_config
+            .enable_persistence
+        {
+            self.peer_states_storage.as_ref()
+        } else {
+            None
+        }
+    }
+
+    /// Persists a snapshot of the current peer states to storage (if enabled)
+    pub fn persist_peer_states(&self) {
+        if let Some(peer_states_storage) = self.get_peer_states_storage() {
+            let persisted_at_usecs = self.time_service.now_unix_time().as_micros() as u64;
+            let persisted_peer_states = self
+                .peer_states
+                .get_persisted_peer_states(persisted_at_usecs);
+            if let Err(error) = peer_states_storage.persist_peer_states(persisted_peer_states) {
+                warn!(
+                    (LogSchema::new(LogEntry::PeerStates)
+                        .event(LogEvent::PeerStatesPersistence)
+                        .message("Failed to persist the peer states!")
+                        .error(&error))
+                );
+            }
+        }
+    }
+
+    /// Restores the previously persisted peer states from storage (if enabled)
+    fn restore_peer_states(&self) {
+        if let Some(peer_states_storage) = self.get_peer_states_storage() {
+            match peer_states_storage.get_persisted_peer_states() {
+                Ok(Some(persisted_peer_states)) => {
+                    info!(
+                        (LogSchema::new(LogEntry::PeerStates)
+                            .event(LogEvent::PeerStatesRestoration)
+                            .message(&format!(
+                                "Restoring {} persisted peer states!",
+                                persisted_peer_states.peer_states.len()
+                            )))
+                    );
+                    let current_time_usecs = self.time_service.now_unix_time().as_micros() as u64;
+                    self.peer_states
+                        .restore_persisted_peer_states(persisted_peer_states, current_time_usecs);
+                },
+                Ok(None) => {}, // No peer states were persisted
+                Err(error) => {
+                    warn!(
+                        (LogSchema::new(LogEntry::PeerStates)
+                            .event(LogEvent::PeerStatesRestoration)
+                            .message("Failed to restore the persisted peer states!")
+                            .error(&error))
+                    );
+                },
+            }
+        }
+    }
+
     /// Returns true iff transaction v2 is enabled, and
     /// the client should use transaction v2 data requests.
     fn is_transaction_v2_enabled(&self) -> bool {
@@ -236,8 +304,9 @@ impl CreditChainDataClient {
         let all_connected_peers = self.get_all_connected_peers()?;
 
         // Garbage collect the disconnected peers
+        let current_time_usecs = self.time_service.now_unix_time().as_micros() as u64;
         self.peer_states
-            .garbage_collect_peer_states(all_connected_peers);
+            .garbage_collect_peer_states(all_connected_peers, current_time_usecs);
 
         Ok(())
     }
@@ -787,6 +856,7 @@ impl CreditChainDataClient {
         self.update_sent_request_metrics(peer, &request);
 
         // Send the request and process the result
+        let request_start_time = self.time_service.now();
         let result = self
             .storage_service_client
             .send_request(
@@ -805,8 +875,12 @@ impl CreditChainDataClient {
                         .peer(&peer))
                 );
 
-                // Update the received response metrics
+                // Update the received response metrics and latencies
                 self.update_received_response_metrics(peer, &request);
+                self.peer_states.record_response_latency(
+                    peer,
+                    self.time_service.now().duration_since(request_start_time),
+                );
 
                 // For now, record all responses that at least pass the data
                 // client layer successfully. An alternative might also have the
@@ -59,6 +59,8 @@ pub enum LogEvent {
     PeerPollingError,
     PeerRequestResponseCounts,
     PeerSelectionError,
+    PeerStatesPersistence,
+    PeerStatesRestoration,
     PriorityAndRegularPeers,
     PriorityPeerCategories,
     ResponseError,
@@ -2,6 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
+    error::Error,
     global_summary::{AdvertisedData, GlobalDataSummary, OptimalChunkSizes},
     interface::ResponseError,
     logging::{LogEntry, LogEvent, LogSchema},
@@ -17,6 +18,7 @@ use creditchain_storage_service_types::{
 };
 use creditchain_time_service::TimeService;
 use dashmap::DashMap;
+use serde::{Deserialize, Serialize};
 use std::{
     cmp::min,
     collections::{BTreeMap, HashSet},
@@ -41,6 +43,123 @@ const NOT_USEFUL_MULTIPLIER: f64 = 0.95;
 const MALICIOUS_MULTIPLIER: f64 = 0.8;
 /// Ignore a peer when their score dips below this threshold.
 const IGNORE_PEER_THRESHOLD: f64 = 25.0;
+/// The scaling factor used to persist scores (BCS doesn't support floats).
+const PERSISTED_SCORE_SCALING_FACTOR: f64 = 1000.0;
+
+/// The upper bounds (in ms) of the response latency histogram buckets. The
+/// last bucket (i.e., for all larger latencies) is implicit.
+const RESPONSE_LATENCY_BUCKET_BOUNDS_MS: [u64; 8] = [50, 100, 250, 500, 1000, 2500, 5000, 10_000];
+
+/// The storage interface used by the data client to persist peer states
+/// across restarts. This allows the node to remember which peers are slow
+/// or have served bad data (e.g., when bootstrapping after a reboot).
+pub trait PeerStatesStorageInterface: Send + Sync {
+    /// Returns the previously persisted peer states (if any)
+    fn get_persisted_peer_states(&self) -> Result<Option<PersistedPeerStates>, Error>;
+
+    /// Persists the given peer states (overwriting any previously persisted states)
+    fn persist_peer_states(&self, persisted_peer_states: PersistedPeerStates) -> Result<(), Error>;
+}
+
+/// A snapshot of all peer states, as persisted to storage
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+pub struct PersistedPeerStates {
+    pub persisted_at_usecs: u64, // The unix time (in microseconds) of the snapshot
+    pub peer_states: BTreeMap<PeerNetworkId, PersistedPeerState>,
+}
+
+/// The state of a single peer, as persisted to storage
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct PersistedPeerState {
+    pub bad_responses_by_type: BTreeMap<String, u64>, // The bad responses (by error type)
+    pub response_latency_histogram: ResponseLatencyHistogram, // The observed response latencies
+    pub scaled_score: u64, // The peer score (multiplied by the scaling factor)
+}
+
+impl PersistedPeerState {
+    /// Returns the (unscaled) peer score
+    pub fn get_score(&self) -> f64 {
+        (self.scaled_score as f64) / PERSISTED_SCORE_SCALING_FACTOR
+    }
+
+    /// Decays the persisted state using the given decay factor (between 0 and 1).
+    /// The score moves towards the starting score, and all counts shrink.
+    fn decay(&mut self, decay_factor: f64) {
+        let decayed_score = STARTING_SCORE + (self.get_score() - STARTING_SCORE) * decay_factor;
+        self.scaled_score = (decayed_score * PERSISTED_SCORE_SCALING_FACTOR) as u64;
+
+        for count in self.bad_responses_by_type.values_mut() {
+            *count = decay_count(*count, decay_factor);
+        }
+        self.bad_responses_by_type.retain(|_, count| *count > 0);
+        self.response_latency_histogram.decay(decay_factor);
+    }
+}
+
+/// A simple histogram of the response latencies observed for a peer
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct ResponseLatencyHistogram {
+    bucket_counts: Vec<u64>, // The counts for each bucket (the last bucket is unbounded)
+}
+
+impl Default for ResponseLatencyHistogram {
+    fn default() -> Self {
+        Self {
+            bucket_counts: vec![0; RESPONSE_LATENCY_BUCKET_BOUNDS_MS.len() + 1],
+        }
+    }
+}
+
+impl ResponseLatencyHistogram {
+    /// Records the given response latency in the histogram
+    fn record_latency(&mut self, latency: Duration) {
+        let latency_ms = latency.as_millis() as u64;
+        let bucket_index = RESPONSE_LATENCY_BUCKET_BOUNDS_MS
+            .iter()
+            .position(|bucket_bound_ms| latency_ms <= *bucket_bound_ms)
+            .unwrap_or(RESPONSE_LATENCY_BUCKET_BOUNDS_MS.len());
+
+        // Ensure the histogram has the expected number of buckets (it may
+        // have been persisted with a different set of buckets).
+        self.bucket_counts
+            .resize(RESPONSE_LATENCY_BUCKET_BOUNDS_MS.len() + 1, 0);
+        self.bucket_counts[bucket_index] += 1;
+    }
+
+    /// Decays all bucket counts using the given decay factor
+    fn decay(&mut self, decay_factor: f64) {
+        for count in self.bucket_counts.iter_mut() {
+            *count = decay_count(*count, decay_factor);
+        }
+    }
+
+    /// Returns the total number of latencies recorded in the histogram
+    pub fn get_num_latencies(&self) -> u64 {
+        self.bucket_counts.iter().sum()
+    }
+
+    /// Returns the bucket counts in the histogram, keyed by bucket label
+    pub fn get_bucket_counts(&self) -> BTreeMap<String, u64> {
+        let mut bucket_counts = BTreeMap::new();
+        for (bucket_index, count) in self.bucket_counts.iter().enumerate() {
+            if *count == 0 {
+                continue; // Skip empty buckets
+            }
+
+            let bucket_label = match RESPONSE_LATENCY_BUCKET_BOUNDS_MS.get(bucket_index) {
+                Some(bucket_bound_ms) => format!("<= {:05} ms", bucket_bound_ms),
+                None => "> max".into(),
+            };
+            bucket_counts.insert(bucket_label, *count);
+        }
+        bucket_counts
+    }
+}
+
+/// Decays the given count using the given decay factor
+fn decay_count(count: u64, decay_factor: f64) -> u64 {
+    ((count as f64) * decay_factor) as u64
+}
 
 pub enum ErrorType {
     /// A response or error that's not actively malicious but also doesn't help
@@ -51,6 +170,16 @@ pub enum ErrorType {
     Malicious,
 }
 
+impl ErrorType {
+    /// Returns a summary label for the error type
+    pub fn get_label(&self) -> &'static str {
+        match self {
+            ErrorType::NotUseful => "not_useful",
+            ErrorType::Malicious => "malicious",
+        }
+    }
+}
+
 impl From<ResponseError> for ErrorType {
     fn from(error: ResponseError) -> Self {
         match error {
@@ -64,12 +193,21 @@ impl From<ResponseError> for ErrorType {
 
 #[derive(Clone, Debug)]
 pub struct PeerState {
+    /// The number of bad responses received from this peer (by error type)
+    bad_responses_by_type: Arc<DashMap<String, u64>>,
+
     /// The data client configuration
     data_client_config: Arc<CreditChainDataClientConfig>,
 
     /// The number of responses received from this peer (by data request label)
     received_responses_by_type: Arc<DashMap<String, u64>>,
 
+    /// The histogram of response latencies observed for this peer
+    response_latency_histogram: ResponseLatencyHistogram,
+
+    /// True iff the state was restored from a previously persisted state
+    restored_from_storage: bool,
+
     /// The number of requests sent to this peer (by data request label)
     sent_requests_by_type: Arc<DashMap<String, u64>>,
 
@@ -84,13 +222,33 @@ pub struct PeerState {
 impl PeerState {
     pub fn new(data_client_config: Arc<CreditChainDataClientConfig>) -> Self {
         Self {
+            bad_responses_by_type: Arc::new(DashMap::new()),
             data_client_config,
             received_responses_by_type: Arc::new(DashMap::new()),
+            response_latency_histogram: ResponseLatencyHistogram::default(),
+            restored_from_storage: false,
             sent_requests_by_type: Arc::new(DashMap::new()),
             storage_summary: None,
             score: STARTING_SCORE,
         }
     }
+
+    /// Creates a new peer state from a previously persisted state
+    pub fn new_from_persisted_state(
+        data_client_config: Arc<CreditChainDataClientConfig>,
+        persisted_peer_state: PersistedPeerState,
+    ) -> Self {
+        let score = persisted_peer_state.get_score().clamp(MIN_SCORE, MAX_SCORE);
+        Self {
+            bad_responses_by_type: Arc::new(
+                persisted_peer_state.bad_responses_by_type.into_iter().collect(),
+            ),
+            response_latency_histogram: persisted_peer_state.response_latency_histogram,
+            restored_from_storage: true,
+            score,
+            ..Self::new(data_client_config)
+        }
+    }
 }
 
 impl PeerState {
@@ -102,6 +260,14 @@ impl PeerState {
             .or_insert(1);
     }
 
+    /// Increments the bad response counter for the given label
+    fn increment_bad_response_counter(&mut self, error_label: String) {
+        self.bad_responses_by_type
+            .entry(error_label)
+            .and_modify(|counter| *counter += 1)
+            .or_insert(1);
+    }
+
     /// Increments the sent request counter for the given label
     fn increment_sent_request_counter(&mut self, request_label: String) {
         self.sent_requests_by_type
@@ -139,6 +305,59 @@ impl PeerState {
         sorted_responses_by_type
     }
 
+    /// Returns a sorted copy of the bad responses by type map
+    pub fn get_bad_responses_by_type(&self) -> BTreeMap<String, u64> {
+        let mut sorted_bad_responses_by_type = BTreeMap::new();
+        for bad_response in self.bad_responses_by_type.iter() {
+            sorted_bad_responses_by_type
+                .insert(bad_response.key().clone(), *bad_response.value());
+        }
+        sorted_bad_responses_by_type
+    }
+
+    /// Returns the histogram of response latencies for the peer
+    pub fn get_response_latency_histogram(&self) -> &ResponseLatencyHistogram {
+        &self.response_latency_histogram
+    }
+
+    /// Returns true iff the peer state was restored from storage
+    pub fn is_restored_from_storage(&self) -> bool {
+        self.restored_from_storage
+    }
+
+    /// Returns a human-readable explanation of how the peer state impacts
+    /// peer selection (e.g., whether or not the peer is currently ignored).
+    pub fn get_peer_selection_rationale(&self) -> String {
+        let selection_status = if self.is_ignored() {
+            format!(
+                "ignored (score {:.2} is at or below the ignore threshold of {:.2})",
+                self.score, IGNORE_PEER_THRESHOLD
+            )
+        } else if self.storage_summary.is_none() {
+            "not selectable (no storage summary has been received)".into()
+        } else {
+            format!(
+                "selectable (score {:.2} is above the ignore threshold of {:.2})",
+                self.score, IGNORE_PEER_THRESHOLD
+            )
+        };
+        let state_origin = if self.restored_from_storage {
+            "restored from storage"
+        } else {
+            "learned since startup"
+        };
+        format!("{}, peer state {}", selection_status, state_origin)
+    }
+
+    /// Returns a snapshot of the peer state that can be persisted
+    pub fn get_persisted_state(&self) -> PersistedPeerState {
+        PersistedPeerState {
+            bad_responses_by_type: self.get_bad_responses_by_type(),
+            response_latency_histogram: self.response_latency_histogram.clone(),
+            scaled_score: (self.score * PERSISTED_SCORE_SCALING_FACTOR) as u64,
+        }
+    }
+
     /// Returns the storage summary iff the peer is not below the ignore threshold
     pub fn get_storage_summary_if_not_ignored(&self) -> Option<&StorageServerSummary> {
         if self.is_ignored() {
@@ -149,7 +368,7 @@ impl PeerState {
     }
 
     /// Returns true iff the peer is currently ignored
-    fn is_ignored(&self) -> bool {
+    pub fn is_ignored(&self) -> bool {
         // Only ignore peers if the config allows it
         if !self.data_client_config.ignore_low_score_peers {
             return false;
@@ -164,8 +383,14 @@ impl PeerState {
         self.score = f64::min(self.score + SUCCESSFUL_RESPONSE_DELTA, MAX_SCORE);
     }
 
+    /// Records the latency of a response received from the peer
+    fn record_response_latency(&mut self, latency: Duration) {
+        self.response_latency_histogram.record_latency(latency);
+    }
+
     /// Updates the score of the peer according to an error
     fn update_score_error(&mut self, error: ErrorType) {
+        self.increment_bad_response_counter(error.get_label().into());
         let multiplier = match error {
             ErrorType::NotUseful => NOT_USEFUL_MULTIPLIER,
             ErrorType::Malicious => MALICIOUS_MULTIPLIER,
@@ -185,6 +410,12 @@ impl PeerState {
 pub struct PeerStates {
     data_client_config: Arc<CreditChainDataClientConfig>,
     peer_to_state: Arc<DashMap<PeerNetworkId, PeerState>>,
+
+    // The states of peers that are not currently tracked (e.g., states restored
+    // from storage for peers that haven't reconnected, or disconnected peers).
+    // These are used to initialize the peer states when the peers reconnect.
+    // Each state is stored with the time (in microseconds) it became untracked.
+    untracked_peer_states: Arc<DashMap<PeerNetworkId, (PersistedPeerState, u64)>>,
 }
 
 impl PeerStates {
@@ -192,6 +423,19 @@ impl PeerStates {
         Self {
             data_client_config,
             peer_to_state: Arc::new(DashMap::new()),
+            untracked_peer_states: Arc::new(DashMap::new()),
+        }
+    }
+
+    /// Creates a new peer state for the given peer. If the peer has a
+    /// previously persisted state, that state is used for initialization.
+    fn create_peer_state(&self, peer: &PeerNetworkId) -> PeerState {
+        match self.untracked_peer_states.remove(peer) {
+            Some((_, (persisted_peer_state, _))) => PeerState::new_from_persisted_state(
+                self.data_client_config.clone(),
+                persisted_peer_state,
+            ),
+            None => PeerState::new(self.data_client_config.clone()),
         }
     }
 
@@ -256,6 +500,13 @@ impl PeerStates {
         }
     }
 
+    /// Records the latency of a response received from the given peer
+    pub fn record_response_latency(&self, peer: PeerNetworkId, latency: Duration) {
+        if let Some(mut entry) = self.peer_to_state.get_mut(&peer) {
+            entry.record_response_latency(latency);
+        }
+    }
+
     /// Updates the logs and metrics for the peer request distributions
     pub fn update_peer_request_logs_and_metrics(&self) {
         // Periodically update the metrics
@@ -325,14 +576,130 @@ impl PeerStates {
     pub fn update_summary(&self, peer: PeerNetworkId, storage_summary: StorageServerSummary) {
         self.peer_to_state
             .entry(peer)
-            .or_insert(PeerState::new(self.data_client_config.clone()))
+            .or_insert_with(|| self.create_peer_state(&peer))
             .update_storage_summary(storage_summary);
     }
 
-    /// Garbage collects the peer states to remove data for disconnected peers
-    pub fn garbage_collect_peer_states(&self, connected_peers: HashSet<PeerNetworkId>) {
-        self.peer_to_state
-            .retain(|peer_network_id, _| connected_peers.contains(peer_network_id));
+    /// Garbage collects the peer states to remove data for disconnected peers.
+    /// The states of disconnected peers are kept (as untracked states), so
+    /// that they can be persisted and reused if the peers reconnect. Untracked
+    /// states are evicted once they expire, or if there are too many of them.
+    pub fn garbage_collect_peer_states(
+        &self,
+        connected_peers: HashSet<PeerNetworkId>,
+        current_time_usecs: u64,
+    ) {
+        self.peer_to_state.retain(|peer_network_id, peer_state| {
+            if connected_peers.contains(peer_network_id) {
+                true
+            } else {
+                self.untracked_peer_states.insert(
+                    *peer_network_id,
+                    (peer_state.get_persisted_state(), current_time_usecs),
+                );
+                false
+            }
+        });
+
+        // Evict the untracked states that have expired
+        let peer_score_persistence_config = self.data_client_config.peer_score_persistence_config;
+        let untracked_peer_state_ttl_usecs = peer_score_persistence_config
+            .untracked_peer_state_ttl_secs
+            .saturating_mul(1_000_000);
+        self.untracked_peer_states
+            .retain(|_, (_, untracked_at_usecs)| {
+                current_time_usecs.saturating_sub(*untracked_at_usecs)
+                    <= untracked_peer_state_ttl_usecs
+            });
+
+        // Evict the oldest untracked states if there are too many
+        let max_untracked_peers = peer_score_persistence_config.max_persisted_peers as usize;
+        let num_untracked_peers = self.untracked_peer_states.len();
+        if num_untracked_peers > max_untracked_peers {
+            let mut untracked_peers: Vec<_> = self
+                .untracked_peer_states
+                .iter()
+                .map(|entry| (entry.value().1, *entry.key()))
+                .collect();
+            untracked_peers.sort();
+            for (_, peer) in untracked_peers
+                .into_iter()
+                .take(num_untracked_peers - max_untracked_peers)
+            {
+                self.untracked_peer_states.remove(&peer);
+            }
+        }
+    }
+
+    /// Returns a snapshot of all peer states (both tracked and untracked)
+    /// that can be persisted. Tracked peers are always included first.
+    pub fn get_persisted_peer_states(&self, persisted_at_usecs: u64) -> PersistedPeerStates {
+        let max_persisted_peers = self
+            .data_client_config
+            .peer_score_persistence_config
+            .max_persisted_peers as usize;
+
+        // Gather the tracked peer states
+        let mut peer_states = BTreeMap::new();
+        for peer_state_entry in self.peer_to_state.iter().take(max_persisted_peers) {
+            peer_states.insert(
+                *peer_state_entry.key(),
+                peer_state_entry.value().get_persisted_state(),
+            );
+        }
+
+        // Gather the untracked peer states (if there's space)
+        for peer_state_entry in self.untracked_peer_states.iter() {
+            if peer_states.len() >= max_persisted_peers {
+                break;
+            }
+            peer_states
+                .entry(*peer_state_entry.key())
+                .or_insert_with(|| peer_state_entry.value().0.clone());
+        }
+
+        PersistedPeerStates {
+            persisted_at_usecs,
+            peer_states,
+        }
+    }
+
+    /// Restores the given persisted peer states. Each state is decayed based
+    /// on the time elapsed since it was persisted (using the configured half-life).
+    /// States for peers that are already tracked are ignored.
+    pub fn restore_persisted_peer_states(
+        &self,
+        persisted_peer_states: PersistedPeerStates,
+        current_time_usecs: u64,
+    ) {
+        // Calculate the decay factor for the persisted states
+        let elapsed_usecs =
+            current_time_usecs.saturating_sub(persisted_peer_states.persisted_at_usecs);
+        let elapsed_secs = (elapsed_usecs as f64) / 1_000_000.0;
+        let decay_half_life_secs = self
+            .data_client_config
+            .peer_score_persistence_config
+            .decay_half_life_secs;
+        let decay_factor = if decay_half_life_secs == 0 {
+            0.0 // Decay everything immediately
+        } else {
+            0.5_f64.powf(elapsed_secs / (decay_half_life_secs as f64))
+        };
+
+        // Decay and restore the persisted states
+        for (peer, mut persisted_peer_state) in persisted_peer_states.peer_states {
+            if self.peer_to_state.contains_key(&peer) {
+                continue; // The peer state is already tracked
+            }
+            persisted_peer_state.decay(decay_factor);
+            self.untracked_peer_states.insert(
+                peer,
+                (
+                    persisted_peer_state,
+                    persisted_peer_states.persisted_at_usecs,
+                ),
+            );
+        }
     }
 
     /// Calculates a global data summary using all known storage summaries
@@ -279,8 +279,17 @@ pub async fn start_poller(poller: DataSummaryPoller) {
     let poll_loop_ticker = poller.time_service.interval(data_polling_interval);
     futures::pin_mut!(poll_loop_ticker);
 
+    // Get the peer states persistence interval
+    let peer_states_persistence_interval = Duration::from_millis(
+        poller
+            .data_client_config
+            .peer_score_persistence_config
+            .persistence_interval_ms,
+    );
+
     // Start the poller
     let mut polling_round: u64 = 0;
+    let mut last_peer_states_persistence = poller.time_service.now();
     info!((LogSchema::new(LogEntry::DataSummaryPoller).message("Starting the CreditChain data poller!")));
     loop {
         // Wait for the next round before polling
@@ -305,6 +314,14 @@ pub async fn start_poller(poller: DataSummaryPoller) {
         // Update the metrics and logs for the peer states
         poller.data_client.update_peer_metrics_and_logs();
 
+        // Persist the peer states (if enough time has elapsed)
+        let time_now = poller.time_service.now();
+        if time_now.duration_since(last_peer_states_persistence) >= peer_states_persistence_interval
+        {
+            poller.data_client.persist_peer_states();
+            last_peer_states_persistence = time_now;
+        }
+
         // Determine the peers to poll this round. If the round is even, poll
         // the priority peers. Otherwise, poll the regular peers. This allows
         // us to alternate between peer types and load balance requests.
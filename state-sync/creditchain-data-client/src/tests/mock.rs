@@ -111,6 +111,7 @@ impl MockNetwork {
             create_mock_db_reader(),
             storage_service_client,
             None,
+            None,
         );
 
         // Create the mock network
@@ -5,6 +5,7 @@ mod advertise;
 mod compression;
 pub mod mock;
 mod multi_fetch;
+mod peer_states;
 mod peers;
 mod poller;
 mod priority;
@@ -0,0 +1,178 @@
+// Copyright © CreditChain Research Team
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    peer_states::{ErrorType, PeerStates},
+    tests::utils,
+};
+use creditchain_config::{
+    config::{CreditChainDataClientConfig, CreditChainPeerScorePersistenceConfig},
+    network_id::PeerNetworkId,
+};
+use maplit::hashset;
+use std::{sync::Arc, time::Duration};
+
+// Useful test constants
+const DECAY_HALF_LIFE_SECS: u64 = 100;
+const NUM_MICROSECONDS_IN_SECOND: u64 = 1_000_000;
+
+#[test]
+fn test_persisted_peer_states_decay() {
+    // Create a peer state with a low score, bad responses and latencies
+    let peer_states = create_peer_states();
+    let peer = PeerNetworkId::random();
+    peer_states.update_summary(peer, utils::create_storage_summary(100));
+    for _ in 0..8 {
+        peer_states.update_score_error(peer, ErrorType::Malicious);
+    }
+    for _ in 0..4 {
+        peer_states.record_response_latency(peer, Duration::from_millis(200));
+    }
+    let peer_score = get_peer_score(&peer_states, &peer);
+    assert!(peer_score < 25.0);
+
+    // Persist the peer states and restore them after one half-life
+    let persisted_peer_states = peer_states.get_persisted_peer_states(0);
+    let restored_peer_states = create_peer_states();
+    restored_peer_states.restore_persisted_peer_states(
+        persisted_peer_states,
+        DECAY_HALF_LIFE_SECS * NUM_MICROSECONDS_IN_SECOND,
+    );
+
+    // Reconnect the peer and verify the state was decayed towards the starting state
+    restored_peer_states.update_summary(peer, utils::create_storage_summary(100));
+    let peer_to_state = restored_peer_states.get_peer_to_states();
+    let peer_state = peer_to_state.get(&peer).unwrap();
+    assert!(peer_state.is_restored_from_storage());
+    let expected_score = 50.0 + (peer_score - 50.0) / 2.0;
+    assert!((peer_state.get_score() - expected_score).abs() < 0.01);
+    assert_eq!(
+        peer_state.get_bad_responses_by_type().get("malicious"),
+        Some(&4)
+    );
+    assert_eq!(
+        peer_state
+            .get_response_latency_histogram()
+            .get_num_latencies(),
+        2
+    );
+}
+
+#[test]
+fn test_persisted_peer_states_disconnected_peers() {
+    // Create several connected peers
+    let peer_states = create_peer_states();
+    let peer_1 = PeerNetworkId::random();
+    let peer_2 = PeerNetworkId::random();
+    for peer in [peer_1, peer_2] {
+        peer_states.update_summary(peer, utils::create_storage_summary(100));
+    }
+    peer_states.update_score_error(peer_2, ErrorType::NotUseful);
+
+    // Disconnect the second peer and verify its state is still persisted
+    peer_states.garbage_collect_peer_states(hashset![peer_1], 0);
+    let persisted_peer_states = peer_states.get_persisted_peer_states(10);
+    assert_eq!(persisted_peer_states.persisted_at_usecs, 10);
+    assert_eq!(persisted_peer_states.peer_states.len(), 2);
+    assert!(persisted_peer_states.peer_states[&peer_2].get_score() < 50.0);
+
+    // Reconnect the second peer and verify its state is reused
+    peer_states.update_summary(peer_2, utils::create_storage_summary(100));
+    let peer_score = get_peer_score(&peer_states, &peer_2);
+    assert!(peer_score < 50.0);
+}
+
+#[test]
+fn test_persisted_peer_states_max_peers() {
+    // Create a data client config that only persists a few peers
+    let max_persisted_peers = 5;
+    let data_client_config = CreditChainDataClientConfig {
+        peer_score_persistence_config: CreditChainPeerScorePersistenceConfig {
+            max_persisted_peers,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let peer_states = PeerStates::new(Arc::new(data_client_config));
+
+    // Create many peers and disconnect all but one
+    let peers: Vec<_> = (0..10).map(|_| PeerNetworkId::random()).collect();
+    for peer in &peers {
+        peer_states.update_summary(*peer, utils::create_storage_summary(100));
+    }
+    let connected_peer = peers[0];
+    peer_states.garbage_collect_peer_states(hashset![connected_peer], 0);
+
+    // Verify that only the max number of peers are persisted (tracked peers first)
+    let persisted_peer_states = peer_states.get_persisted_peer_states(0);
+    assert_eq!(
+        persisted_peer_states.peer_states.len() as u64,
+        max_persisted_peers
+    );
+    assert!(persisted_peer_states
+        .peer_states
+        .contains_key(&connected_peer));
+}
+
+#[test]
+fn test_untracked_peer_states_eviction() {
+    // Create a data client config that only keeps a few untracked peers (for a short time)
+    let max_persisted_peers = 3;
+    let untracked_peer_state_ttl_secs = 10;
+    let data_client_config = CreditChainDataClientConfig {
+        peer_score_persistence_config: CreditChainPeerScorePersistenceConfig {
+            max_persisted_peers,
+            untracked_peer_state_ttl_secs,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let peer_states = PeerStates::new(Arc::new(data_client_config));
+
+    // Create several peers and disconnect them one at a time
+    let peers: Vec<_> = (0..5).map(|_| PeerNetworkId::random()).collect();
+    for peer in &peers {
+        peer_states.update_summary(*peer, utils::create_storage_summary(100));
+    }
+    for (index, peer) in peers.iter().enumerate() {
+        let connected_peers = peers.iter().skip(index + 1).cloned().collect();
+        peer_states.garbage_collect_peer_states(connected_peers, index as u64);
+    }
+
+    // Verify that only the most recently disconnected peers are kept
+    let persisted_peer_states = peer_states.get_persisted_peer_states(0);
+    assert_eq!(
+        persisted_peer_states.peer_states.len() as u64,
+        max_persisted_peers
+    );
+    for peer in &peers[2..] {
+        assert!(persisted_peer_states.peer_states.contains_key(peer));
+    }
+
+    // Garbage collect the peers after the TTL and verify all untracked states are evicted
+    let current_time_usecs = (untracked_peer_state_ttl_secs + 5) * NUM_MICROSECONDS_IN_SECOND;
+    peer_states.garbage_collect_peer_states(hashset![], current_time_usecs);
+    let persisted_peer_states = peer_states.get_persisted_peer_states(current_time_usecs);
+    assert!(persisted_peer_states.peer_states.is_empty());
+}
+
+/// Creates a new set of peer states with a known decay half-life
+fn create_peer_states() -> PeerStates {
+    let data_client_config = CreditChainDataClientConfig {
+        peer_score_persistence_config: CreditChainPeerScorePersistenceConfig {
+            decay_half_life_secs: DECAY_HALF_LIFE_SECS,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    PeerStates::new(Arc::new(data_client_config))
+}
+
+/// Returns the score of the given (tracked) peer
+fn get_peer_score(peer_states: &PeerStates, peer: &PeerNetworkId) -> f64 {
+    peer_states
+        .get_peer_to_states()
+        .get(peer)
+        .unwrap()
+        .get_score()
+}
@@ -0,0 +1,830 @@
+// Copyright © CreditChain Research Team
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    error::Error,
+    logging::{LogEntry, LogSchema},
+};
+use anyhow::{anyhow, ensure};
+use creditchain_backup_cli::{
+    backup_types::{
+        epoch_ending::manifest::EpochEndingBackup,
+        state_snapshot::manifest::StateSnapshotBackup,
+        transaction::manifest::{TransactionBackup, TransactionChunkFormat},
+    },
+    metadata::{
+        cache::{sync_and_load, MetadataCacheOpt},
+        EpochEndingBackupMeta, TransactionBackupMeta,
+    },
+    storage::{
+        command_adapter::{config::CommandAdapterConfig, CommandAdapter},
+        local_fs::LocalFs,
+        BackupStorage,
+    },
+    utils::{read_record_bytes::ReadRecordBytes, storage_ext::BackupStorageExt},
+};
+use creditchain_config::config::BackupBootstrappingConfig;
+use creditchain_crypto::hash::CryptoHash;
+use creditchain_data_client::global_summary::{AdvertisedData, GlobalDataSummary, OptimalChunkSizes};
+use creditchain_data_streaming_service::{
+    data_notification::{DataNotification, DataPayload, NotificationId},
+    data_stream::{DataStreamId, DataStreamListener},
+    error::Error as StreamingError,
+    streaming_client::{
+        DataStreamingClient, Epoch, NotificationAndFeedback, NotificationFeedback,
+    },
+};
+use creditchain_infallible::Mutex;
+use creditchain_logger::prelude::*;
+use creditchain_storage_service_types::responses::CompleteDataRange;
+use creditchain_types::{
+    contract_event::ContractEvent,
+    ledger_info::LedgerInfoWithSignatures,
+    proof::{
+        SparseMerkleRangeProof, TransactionAccumulatorProof, TransactionAccumulatorRangeProof,
+        TransactionInfoListWithProof, TransactionInfoWithProof,
+    },
+    state_store::{
+        state_key::StateKey,
+        state_value::{StateValue, StateValueChunkWithProof},
+    },
+    transaction::{
+        PersistedAuxiliaryInfo, Transaction, TransactionAuxiliaryData, TransactionInfo,
+        TransactionOutput, TransactionOutputListWithAuxiliaryInfos, TransactionOutputListWithProof,
+        TransactionOutputListWithProofV2, TransactionStatus, Version,
+    },
+    write_set::WriteSet,
+};
+use async_trait::async_trait;
+use futures::{channel::mpsc, SinkExt};
+use std::{
+    collections::HashMap,
+    sync::{
+        atomic::{AtomicBool, AtomicU64, Ordering},
+        Arc,
+    },
+};
+use tokio::{io::BufReader, sync::OnceCell};
+
+// The maximum number of pending notifications per backup data stream
+const MAX_PENDING_BACKUP_NOTIFICATIONS: usize = 10;
+
+// The first stream and notification ID used by backup data streams. The
+// streaming service allocates its IDs from a generator that starts at zero,
+// so backup IDs start at the upper half of the ID space to keep them disjoint.
+pub(crate) const FIRST_BACKUP_STREAM_ID: u64 = 1 << 63;
+
+/// A backup archive (i.e., a backup storage location created by the backup-cli)
+/// that can be used to bootstrap the node, instead of fetching data from peers.
+/// The archive metadata is loaded lazily (by a background task), on first use.
+#[derive(Clone)]
+pub struct BackupArchive {
+    config: BackupBootstrappingConfig,
+    loaded_archive: Arc<OnceCell<Arc<LoadedBackupArchive>>>,
+    loading_archive: Arc<AtomicBool>,
+}
+
+impl BackupArchive {
+    pub fn new(config: BackupBootstrappingConfig) -> Self {
+        Self {
+            config,
+            loaded_archive: Arc::new(OnceCell::new()),
+            loading_archive: Arc::new(AtomicBool::new(false)),
+        }
+    }
+
+    /// Returns the loaded backup archive (loading it if required)
+    async fn get_loaded_archive(&self) -> Result<Arc<LoadedBackupArchive>, Error> {
+        self.loaded_archive
+            .get_or_try_init(|| async {
+                LoadedBackupArchive::load(&self.config)
+                    .await
+                    .map(Arc::new)
+                    .map_err(|error| {
+                        Error::UnexpectedError(format!(
+                            "Failed to load the backup archive! Error: {:?}",
+                            error
+                        ))
+                    })
+            })
+            .await
+            .cloned()
+    }
+
+    /// Returns the loaded backup archive if it has already been loaded.
+    /// Otherwise, spawns a task to load the archive and returns None.
+    fn try_get_loaded_archive(&self) -> Option<Arc<LoadedBackupArchive>> {
+        if let Some(loaded_archive) = self.loaded_archive.get() {
+            return Some(loaded_archive.clone());
+        }
+
+        // Spawn the loading task (if one isn't already running)
+        if !self.loading_archive.swap(true, Ordering::SeqCst) {
+            let backup_archive = self.clone();
+            tokio::spawn(async move {
+                if let Err(error) = backup_archive.get_loaded_archive().await {
+                    warn!(LogSchema::new(LogEntry::BackupArchive)
+                        .error(&error)
+                        .message("Failed to load the backup archive! Retrying on next use."));
+                }
+                backup_archive
+                    .loading_archive
+                    .store(false, Ordering::SeqCst);
+            });
+        }
+
+        None
+    }
+
+    /// Returns a global data summary that advertises the data held by the
+    /// backup archive (i.e., everything required to fast sync to the latest
+    /// state snapshot in the archive). This never blocks on loading the
+    /// archive: if the archive hasn't been loaded yet, None is returned.
+    pub fn get_global_data_summary(&self) -> Result<Option<GlobalDataSummary>, Error> {
+        let Some(loaded_archive) = self.try_get_loaded_archive() else {
+            return Ok(None);
+        };
+        loaded_archive
+            .create_global_data_summary()
+            .map(Some)
+            .map_err(|error| Error::UnexpectedError(error.to_string()))
+    }
+}
+
+/// The metadata of a backup archive, and the target of the state snapshot to sync
+struct LoadedBackupArchive {
+    storage: Arc<dyn BackupStorage>,
+    epoch_ending_backups: Vec<EpochEndingBackupMeta>,
+    state_snapshot_manifest: StateSnapshotBackup,
+    target_ledger_info: LedgerInfoWithSignatures,
+    target_transaction_info_with_proof: TransactionInfoWithProof,
+    target_transaction_backup: TransactionBackupMeta,
+}
+
+impl LoadedBackupArchive {
+    /// Loads the backup archive metadata and identifies the latest state snapshot
+    async fn load(config: &BackupBootstrappingConfig) -> anyhow::Result<Self> {
+        // Initialize the backup storage
+        let storage: Arc<dyn BackupStorage> =
+            if let Some(command_adapter_config) = &config.command_adapter_config {
+                let command_adapter_config =
+                    CommandAdapterConfig::load_from_file(command_adapter_config).await?;
+                Arc::new(CommandAdapter::new(command_adapter_config))
+            } else if let Some(local_fs_dir) = &config.local_fs_dir {
+                Arc::new(LocalFs::new(local_fs_dir.clone()))
+            } else {
+                return Err(anyhow!("No backup storage has been specified!"));
+            };
+
+        // Sync and load the backup metadata
+        let metadata_cache_opt = MetadataCacheOpt::new(config.metadata_cache_dir.as_ref());
+        let metadata_view = sync_and_load(
+            &metadata_cache_opt,
+            storage.clone(),
+            config.concurrent_downloads,
+        )
+        .await?;
+
+        // Identify the latest state snapshot (these are always taken at epoch ending versions)
+        let state_snapshot = metadata_view
+            .select_state_snapshot(Version::MAX)?
+            .ok_or_else(|| anyhow!("No state snapshot was found in the backup archive!"))?;
+        let target_version = state_snapshot.version;
+        let state_snapshot_manifest: StateSnapshotBackup =
+            storage.load_json_file(&state_snapshot.manifest).await?;
+        let (target_transaction_info_with_proof, target_ledger_info): (
+            TransactionInfoWithProof,
+            LedgerInfoWithSignatures,
+        ) = storage
+            .load_bcs_file(&state_snapshot_manifest.proof)
+            .await?;
+        ensure!(
+            target_ledger_info.ledger_info().version() == target_version
+                && target_ledger_info.ledger_info().ends_epoch(),
+            "The state snapshot proof must be at an epoch ending version! Snapshot version: {}, ledger info: {:?}",
+            target_version,
+            target_ledger_info,
+        );
+
+        // Identify the epoch ending and transaction backups required for the snapshot
+        let epoch_ending_backups = metadata_view.select_epoch_ending_backups(target_version)?;
+        let target_transaction_backup = metadata_view
+            .select_transaction_backups(target_version, target_version)?
+            .into_iter()
+            .find(|backup| {
+                backup.first_version <= target_version && target_version <= backup.last_version
+            })
+            .ok_or_else(|| {
+                anyhow!(
+                    "No transaction backup was found for the state snapshot version: {}",
+                    target_version
+                )
+            })?;
+
+        info!(LogSchema::new(LogEntry::BackupArchive).message(&format!(
+            "Loaded the backup archive! Target state snapshot version: {}, epoch: {}",
+            target_version, state_snapshot.epoch
+        )));
+
+        Ok(Self {
+            storage,
+            epoch_ending_backups,
+            state_snapshot_manifest,
+            target_ledger_info,
+            target_transaction_info_with_proof,
+            target_transaction_backup,
+        })
+    }
+
+    /// Returns the epoch of the target ledger info (i.e., the highest epoch ending in the archive)
+    fn get_target_epoch(&self) -> Epoch {
+        self.target_ledger_info.ledger_info().epoch()
+    }
+
+    /// Returns the version of the target ledger info (i.e., the state snapshot version)
+    fn get_target_version(&self) -> Version {
+        self.target_ledger_info.ledger_info().version()
+    }
+
+    /// Verifies that the states at the given version can be served by the archive
+    fn verify_state_values_request(&self, version: Version) -> Result<(), StreamingError> {
+        let target_version = self.get_target_version();
+        if version != target_version {
+            return Err(StreamingError::UnsupportedRequestEncountered(format!(
+                "The backup archive only holds states at version: {}, but got: {}",
+                target_version, version
+            )));
+        }
+        Ok(())
+    }
+
+    /// Verifies that the transaction outputs in the given range can be served
+    /// by the archive. Only the output at the target version can be proven.
+    fn verify_transaction_outputs_request(
+        &self,
+        start_version: Version,
+        end_version: Version,
+        proof_version: Version,
+    ) -> Result<(), StreamingError> {
+        let target_version = self.get_target_version();
+        if start_version != target_version
+            || end_version != target_version
+            || proof_version != target_version
+        {
+            return Err(StreamingError::UnsupportedRequestEncountered(format!(
+                "The backup archive only holds the output at version: {}, but got: ({}, {}, {})",
+                target_version, start_version, end_version, proof_version
+            )));
+        }
+        Ok(())
+    }
+
+    /// Creates a global data summary for the data in the archive
+    fn create_global_data_summary(&self) -> anyhow::Result<GlobalDataSummary> {
+        let target_version = self.get_target_version();
+        let target_version_range = CompleteDataRange::new(target_version, target_version)?;
+        let advertised_data = AdvertisedData {
+            epoch_ending_ledger_infos: vec![CompleteDataRange::new(0, self.get_target_epoch())?],
+            states: vec![target_version_range],
+            synced_ledger_infos: vec![self.target_ledger_info.clone()],
+            transactions: vec![],
+            transaction_outputs: vec![target_version_range],
+        };
+
+        Ok(GlobalDataSummary {
+            advertised_data,
+            optimal_chunk_sizes: OptimalChunkSizes::empty(),
+        })
+    }
+
+    /// Streams all epoch ending ledger infos from the start epoch to the target epoch
+    async fn stream_epoch_ending_ledger_infos(
+        &self,
+        start_epoch: Epoch,
+        notification_sender: &mut BackupNotificationSender,
+    ) -> anyhow::Result<()> {
+        let target_epoch = self.get_target_epoch();
+        for backup in &self.epoch_ending_backups {
+            if backup.last_epoch < start_epoch {
+                continue; // We don't need anything in this backup
+            }
+
+            let manifest: EpochEndingBackup = self.storage.load_json_file(&backup.manifest).await?;
+            manifest.verify()?;
+            for chunk in manifest.chunks {
+                if chunk.last_epoch < start_epoch {
+                    continue; // We don't need anything in this chunk
+                }
+                if chunk.first_epoch > target_epoch {
+                    return Ok(()); // We've reached the target epoch
+                }
+
+                // Read the ledger infos in the chunk
+                let mut ledger_infos = vec![];
+                let mut file = self.storage.open_for_read(&chunk.ledger_infos).await?;
+                while let Some(record_bytes) = file.read_record_bytes().await? {
+                    let ledger_info: LedgerInfoWithSignatures = bcs::from_bytes(&record_bytes)?;
+                    let epoch = ledger_info.ledger_info().epoch();
+                    if epoch >= start_epoch && epoch <= target_epoch {
+                        ledger_infos.push(ledger_info);
+                    }
+                }
+
+                // Send the ledger infos (they will be verified by the bootstrapper)
+                if !ledger_infos.is_empty() {
+                    notification_sender
+                        .send(DataPayload::EpochEndingLedgerInfos(ledger_infos))
+                        .await?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Streams the transaction output (with proof) at the target version
+    async fn stream_target_transaction_output(
+        &self,
+        notification_sender: &mut BackupNotificationSender,
+    ) -> anyhow::Result<()> {
+        // Identify the transaction chunk that holds the target version
+        let target_version = self.get_target_version();
+        let manifest: TransactionBackup = self
+            .storage
+            .load_json_file(&self.target_transaction_backup.manifest)
+            .await?;
+        let chunk = manifest
+            .chunks
+            .into_iter()
+            .find(|chunk| {
+                chunk.first_version <= target_version && target_version <= chunk.last_version
+            })
+            .ok_or_else(|| {
+                anyhow!("No transaction chunk was found for version: {}", target_version)
+            })?;
+
+        // Read the transaction records until we reach the target version
+        let mut file = BufReader::new(self.storage.open_for_read(&chunk.transactions).await?);
+        let mut next_version = chunk.first_version;
+        let mut target_record = None;
+        while let Some(record_bytes) = file.read_record_bytes().await? {
+            if next_version == target_version {
+                let record: (Transaction, PersistedAuxiliaryInfo, WriteSet, Vec<ContractEvent>) =
+                    match chunk.format {
+                        TransactionChunkFormat::V0 => {
+                            let (transaction, _, events, write_set): (
+                                Transaction,
+                                TransactionInfo,
+                                Vec<ContractEvent>,
+                                WriteSet,
+                            ) = bcs::from_bytes(&record_bytes)?;
+                            (transaction, PersistedAuxiliaryInfo::None, write_set, events)
+                        },
+                        TransactionChunkFormat::V1 => {
+                            let (transaction, auxiliary_info, _, events, write_set): (
+                                Transaction,
+                                PersistedAuxiliaryInfo,
+                                TransactionInfo,
+                                Vec<ContractEvent>,
+                                WriteSet,
+                            ) = bcs::from_bytes(&record_bytes)?;
+                            (transaction, auxiliary_info, write_set, events)
+                        },
+                    };
+                target_record = Some(record);
+                break;
+            }
+            next_version += 1;
+        }
+        let (transaction, auxiliary_info, write_set, events) = target_record.ok_or_else(|| {
+            anyhow!("The transaction at version {} was not found!", target_version)
+        })?;
+
+        // Create the output list with proof (it will be verified by the bootstrapper)
+        let transaction_info = self
+            .target_transaction_info_with_proof
+            .transaction_info()
+            .clone();
+        let transaction_output = TransactionOutput::new(
+            write_set,
+            events,
+            transaction_info.gas_used(),
+            TransactionStatus::Keep(transaction_info.status().clone()),
+            TransactionAuxiliaryData::None,
+        );
+        let range_proof = create_single_leaf_range_proof(
+            target_version,
+            self.target_transaction_info_with_proof
+                .ledger_info_to_transaction_info_proof(),
+        );
+        let output_list_with_proof = TransactionOutputListWithProof::new(
+            vec![(transaction, transaction_output)],
+            Some(target_version),
+            TransactionInfoListWithProof::new(range_proof, vec![transaction_info]),
+        );
+        let output_list_with_proof =
+            TransactionOutputListWithProofV2::new(TransactionOutputListWithAuxiliaryInfos::new(
+                output_list_with_proof,
+                vec![auxiliary_info],
+            ));
+
+        // Send the output list with proof
+        notification_sender
+            .send(DataPayload::TransactionOutputsWithProof(
+                output_list_with_proof,
+            ))
+            .await
+    }
+
+    /// Streams all state values in the state snapshot, starting at the given index
+    async fn stream_state_values(
+        &self,
+        start_index: u64,
+        notification_sender: &mut BackupNotificationSender,
+    ) -> anyhow::Result<()> {
+        for chunk in &self.state_snapshot_manifest.chunks {
+            let (chunk_first_index, chunk_last_index) =
+                (chunk.first_idx as u64, chunk.last_idx as u64);
+            if chunk_last_index < start_index {
+                continue; // We've already processed this chunk
+            }
+
+            // Read the state values in the chunk
+            let mut raw_values: Vec<(StateKey, StateValue)> = vec![];
+            let mut file = self.storage.open_for_read(&chunk.blobs).await?;
+            while let Some(record_bytes) = file.read_record_bytes().await? {
+                raw_values.push(bcs::from_bytes(&record_bytes)?);
+            }
+            ensure!(
+                raw_values.len() as u64 == chunk_last_index - chunk_first_index + 1,
+                "Number of state values in the chunk doesn't match the manifest! Expected: {}, found: {}",
+                chunk_last_index - chunk_first_index + 1,
+                raw_values.len(),
+            );
+
+            // Skip any values before the start index. The proof only holds the
+            // right siblings of the last key, so it also proves the remaining values.
+            let first_index = chunk_first_index.max(start_index);
+            raw_values.drain(..(first_index - chunk_first_index) as usize);
+            let first_key = raw_values
+                .first()
+                .map(|(state_key, _)| CryptoHash::hash(state_key))
+                .ok_or_else(|| anyhow!("The state value chunk is empty!"))?;
+
+            // Send the state value chunk (it will be verified by the bootstrapper)
+            let proof: SparseMerkleRangeProof = self.storage.load_bcs_file(&chunk.proof).await?;
+            let state_value_chunk_with_proof = StateValueChunkWithProof {
+                first_index,
+                last_index: chunk_last_index,
+                first_key,
+                last_key: chunk.last_key,
+                raw_values,
+                proof,
+                root_hash: self.state_snapshot_manifest.root_hash,
+            };
+            notification_sender
+                .send(DataPayload::StateValuesWithProof(
+                    state_value_chunk_with_proof,
+                ))
+                .await?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Converts the accumulator proof of a single transaction info into a range
+/// proof (for the same leaf). Both proofs hold the siblings from the leaf to
+/// the root, so this simply partitions the siblings into left and right siblings.
+pub(crate) fn create_single_leaf_range_proof(
+    version: Version,
+    accumulator_proof: &TransactionAccumulatorProof,
+) -> TransactionAccumulatorRangeProof {
+    let mut left_siblings = vec![];
+    let mut right_siblings = vec![];
+    let mut index = version;
+    for sibling in accumulator_proof.siblings() {
+        if index % 2 == 0 {
+            right_siblings.push(*sibling); // The current node is a left child
+        } else {
+            left_siblings.push(*sibling); // The current node is a right child
+        }
+        index /= 2;
+    }
+    TransactionAccumulatorRangeProof::new(left_siblings, right_siblings)
+}
+
+/// A simple wrapper for sending the notifications of a single backup data stream
+struct BackupNotificationSender {
+    next_notification_id: Arc<AtomicU64>,
+    notification_sender: mpsc::Sender<DataNotification>,
+}
+
+impl BackupNotificationSender {
+    async fn send(&mut self, data_payload: DataPayload) -> anyhow::Result<()> {
+        let notification_id: NotificationId =
+            self.next_notification_id.fetch_add(1, Ordering::Relaxed);
+        self.notification_sender
+            .send(DataNotification::new(notification_id, data_payload))
+            .await
+            .map_err(|error| anyhow!("Failed to send the backup data notification: {:?}", error))
+    }
+}
+
+/// A data streaming client that serves the data required for bootstrapping
+/// from a backup archive (if one is configured), and all other requests (e.g.,
+/// continuous syncing) from the given streaming client (i.e., from peers).
+/// The bootstrapper verifies the backup data exactly as it does peer data.
+#[derive(Clone)]
+pub struct BackupStreamingClient<StreamingClient> {
+    backup_archive: Option<BackupArchive>,
+    streaming_client: StreamingClient,
+
+    // The senders for the active backup data streams. These are held until
+    // each stream is terminated, so that the streams are never closed early.
+    active_backup_streams: Arc<Mutex<HashMap<DataStreamId, mpsc::Sender<DataNotification>>>>,
+    next_notification_id: Arc<AtomicU64>,
+    next_stream_id: Arc<AtomicU64>,
+}
+
+impl<StreamingClient> BackupStreamingClient<StreamingClient> {
+    pub fn new(backup_archive: Option<BackupArchive>, streaming_client: StreamingClient) -> Self {
+        Self {
+            backup_archive,
+            streaming_client,
+            active_backup_streams: Arc::new(Mutex::new(HashMap::new())),
+            next_notification_id: Arc::new(AtomicU64::new(FIRST_BACKUP_STREAM_ID)),
+            next_stream_id: Arc::new(AtomicU64::new(FIRST_BACKUP_STREAM_ID)),
+        }
+    }
+
+    /// Returns the backup archive (if one is configured)
+    fn get_backup_archive(&self) -> Option<&BackupArchive> {
+        self.backup_archive.as_ref()
+    }
+
+    /// Returns the loaded backup archive, or an error if it hasn't been loaded yet
+    fn get_loaded_backup_archive(
+        backup_archive: &BackupArchive,
+    ) -> Result<Arc<LoadedBackupArchive>, StreamingError> {
+        backup_archive.try_get_loaded_archive().ok_or_else(|| {
+            StreamingError::DataIsUnavailable("The backup archive hasn't been loaded yet!".into())
+        })
+    }
+
+    /// Logs and returns an error for a request that can't be served by the
+    /// backup archive. These are rejected before any stream is created, so
+    /// that the bootstrapper doesn't wait on a stream that would never progress.
+    fn reject_unsupported_request(
+        &self,
+        error: StreamingError,
+    ) -> Result<DataStreamListener, StreamingError> {
+        warn!(LogSchema::new(LogEntry::BackupArchive).message(&format!(
+            "Rejected a backup archive request! Error: {:?}",
+            error
+        )));
+        Err(error)
+    }
+
+    /// Creates a new backup data stream, and spawns a task that uses the
+    /// given function to stream the data from the loaded backup archive.
+    fn create_backup_stream<StreamData>(
+        &self,
+        loaded_archive: Arc<LoadedBackupArchive>,
+        stream_data: StreamData,
+    ) -> Result<DataStreamListener, StreamingError>
+    where
+        StreamData: for<'a> FnOnce(
+                &'a LoadedBackupArchive,
+                &'a mut BackupNotificationSender,
+            ) -> futures::future::BoxFuture<'a, anyhow::Result<()>>
+            + Send
+            + 'static,
+    {
+        // Create the data stream
+        let data_stream_id = self.next_stream_id.fetch_add(1, Ordering::Relaxed);
+        let (notification_sender, notification_receiver) =
+            mpsc::channel(MAX_PENDING_BACKUP_NOTIFICATIONS);
+        self.active_backup_streams
+            .lock()
+            .insert(data_stream_id, notification_sender.clone());
+
+        // Spawn the task that streams the data
+        let mut notification_sender = BackupNotificationSender {
+            next_notification_id: self.next_notification_id.clone(),
+            notification_sender,
+        };
+        tokio::spawn(async move {
+            let result = match stream_data(&loaded_archive, &mut notification_sender).await {
+                Ok(()) => notification_sender.send(DataPayload::EndOfStream).await,
+                Err(error) => Err(error),
+            };
+            if let Err(error) = result {
+                // The stream will time out, and be terminated by the bootstrapper
+                warn!(LogSchema::new(LogEntry::BackupArchive).message(&format!(
+                    "Failed to stream data from the backup archive! Stream ID: {:?}, error: {:?}",
+                    data_stream_id, error
+                )));
+            }
+        });
+
+        Ok(DataStreamListener::new(
+            data_stream_id,
+            notification_receiver,
+        ))
+    }
+}
+
+#[async_trait]
+impl<StreamingClient: DataStreamingClient + Send + Sync> DataStreamingClient
+    for BackupStreamingClient<StreamingClient>
+{
+    async fn get_all_state_values(
+        &self,
+        version: Version,
+        start_index: Option<u64>,
+    ) -> Result<DataStreamListener, StreamingError> {
+        let Some(backup_archive) = self.get_backup_archive() else {
+            return self
+                .streaming_client
+                .get_all_state_values(version, start_index)
+                .await;
+        };
+
+        let loaded_archive = Self::get_loaded_backup_archive(backup_archive)?;
+        if let Err(error) = loaded_archive.verify_state_values_request(version) {
+            return self.reject_unsupported_request(error);
+        }
+
+        self.create_backup_stream(loaded_archive, move |loaded_archive, sender| {
+            Box::pin(loaded_archive.stream_state_values(start_index.unwrap_or(0), sender))
+        })
+    }
+
+    async fn get_all_epoch_ending_ledger_infos(
+        &self,
+        start_epoch: Epoch,
+    ) -> Result<DataStreamListener, StreamingError> {
+        let Some(backup_archive) = self.get_backup_archive() else {
+            return self
+                .streaming_client
+                .get_all_epoch_ending_ledger_infos(start_epoch)
+                .await;
+        };
+
+        let loaded_archive = Self::get_loaded_backup_archive(backup_archive)?;
+        self.create_backup_stream(loaded_archive, move |loaded_archive, sender| {
+            Box::pin(loaded_archive.stream_epoch_ending_ledger_infos(start_epoch, sender))
+        })
+    }
+
+    async fn get_all_transaction_outputs(
+        &self,
+        start_version: Version,
+        end_version: Version,
+        proof_version: Version,
+    ) -> Result<DataStreamListener, StreamingError> {
+        let Some(backup_archive) = self.get_backup_archive() else {
+            return self
+                .streaming_client
+                .get_all_transaction_outputs(start_version, end_version, proof_version)
+                .await;
+        };
+
+        let loaded_archive = Self::get_loaded_backup_archive(backup_archive)?;
+        if let Err(error) = loaded_archive.verify_transaction_outputs_request(
+            start_version,
+            end_version,
+            proof_version,
+        ) {
+            return self.reject_unsupported_request(error);
+        }
+
+        self.create_backup_stream(loaded_archive, move |loaded_archive, sender| {
+            Box::pin(loaded_archive.stream_target_transaction_output(sender))
+        })
+    }
+
+    async fn get_all_transactions(
+        &self,
+        start_version: Version,
+        end_version: Version,
+        proof_version: Version,
+        include_events: bool,
+    ) -> Result<DataStreamListener, StreamingError> {
+        if self.get_backup_archive().is_some() {
+            return self.reject_unsupported_request(StreamingError::UnsupportedRequestEncountered(
+                "Transactions cannot be streamed from a backup archive! Use fast sync instead."
+                    .into(),
+            ));
+        }
+
+        self.streaming_client
+            .get_all_transactions(start_version, end_version, proof_version, include_events)
+            .await
+    }
+
+    async fn get_all_transactions_or_outputs(
+        &self,
+        start_version: Version,
+        end_version: Version,
+        proof_version: Version,
+        include_events: bool,
+    ) -> Result<DataStreamListener, StreamingError> {
+        if self.get_backup_archive().is_some() {
+            return self.reject_unsupported_request(StreamingError::UnsupportedRequestEncountered(
+                "Transactions or outputs cannot be streamed from a backup archive! Use fast sync instead."
+                    .into(),
+            ));
+        }
+
+        self.streaming_client
+            .get_all_transactions_or_outputs(
+                start_version,
+                end_version,
+                proof_version,
+                include_events,
+            )
+            .await
+    }
+
+    async fn continuously_stream_transaction_outputs(
+        &self,
+        start_version: Version,
+        start_epoch: Epoch,
+        target: Option<LedgerInfoWithSignatures>,
+    ) -> Result<DataStreamListener, StreamingError> {
+        self.streaming_client
+            .continuously_stream_transaction_outputs(start_version, start_epoch, target)
+            .await
+    }
+
+    async fn continuously_stream_transactions(
+        &self,
+        start_version: Version,
+        start_epoch: Epoch,
+        include_events: bool,
+        target: Option<LedgerInfoWithSignatures>,
+    ) -> Result<DataStreamListener, StreamingError> {
+        self.streaming_client
+            .continuously_stream_transactions(start_version, start_epoch, include_events, target)
+            .await
+    }
+
+    async fn continuously_stream_transactions_or_outputs(
+        &self,
+        start_version: Version,
+        start_epoch: Epoch,
+        include_events: bool,
+        target: Option<LedgerInfoWithSignatures>,
+    ) -> Result<DataStreamListener, StreamingError> {
+        self.streaming_client
+            .continuously_stream_transactions_or_outputs(
+                start_version,
+                start_epoch,
+                include_events,
+                target,
+            )
+            .await
+    }
+
+    async fn terminate_stream_with_feedback(
+        &self,
+        data_stream_id: DataStreamId,
+        notification_and_feedback: Option<NotificationAndFeedback>,
+    ) -> Result<(), StreamingError> {
+        // If this isn't a backup data stream, forward the request to the streaming client
+        if data_stream_id < FIRST_BACKUP_STREAM_ID {
+            return self
+                .streaming_client
+                .terminate_stream_with_feedback(data_stream_id, notification_and_feedback)
+                .await;
+        }
+
+        // Otherwise, close the backup data stream
+        if self
+            .active_backup_streams
+            .lock()
+            .remove(&data_stream_id)
+            .is_none()
+        {
+            return Err(StreamingError::UnexpectedErrorEncountered(format!(
+                "The backup data stream was not found! Stream ID: {:?}",
+                data_stream_id
+            )));
+        }
+
+        // Otherwise, the stream is now closed. Log any invalid data feedback.
+        if let Some(notification_and_feedback) = notification_and_feedback {
+            if notification_and_feedback.notification_feedback != NotificationFeedback::EndOfStream
+            {
+                warn!(LogSchema::new(LogEntry::BackupArchive).message(&format!(
+                    "The backup archive data was rejected! Stream ID: {:?}, feedback: {:?}",
+                    data_stream_id, notification_and_feedback
+                )));
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -13,7 +13,7 @@ use crate::{
     utils,
     utils::{OutputFallbackHandler, SpeculativeStreamState, PENDING_DATA_LOG_FREQ_SECS},
 };
-use creditchain_config::config::BootstrappingMode;
+use creditchain_config::config::{BootstrappingMode, SnapshotSyncTarget};
 use creditchain_data_client::global_summary::GlobalDataSummary;
 use creditchain_data_streaming_service::{
     data_notification::{DataNotification, DataPayload, NotificationId},
@@ -228,6 +228,27 @@ impl VerifiedEpochStates {
         Ok(highest_known_ledger_info)
     }
 
+    /// Returns the epoch ending ledger info for the given snapshot sync target
+    /// (if it has been fetched). For version targets, this is the highest epoch
+    /// ending ledger info at (or below) the target version.
+    pub fn get_snapshot_sync_target_ledger_info(
+        &self,
+        snapshot_sync_target: SnapshotSyncTarget,
+    ) -> Option<LedgerInfoWithSignatures> {
+        match snapshot_sync_target {
+            SnapshotSyncTarget::Epoch(epoch) => self
+                .new_epoch_ending_ledger_infos
+                .values()
+                .find(|ledger_info| ledger_info.ledger_info().epoch() == epoch)
+                .cloned(),
+            SnapshotSyncTarget::Version(version) => self
+                .new_epoch_ending_ledger_infos
+                .range(..=version)
+                .next_back()
+                .map(|(_, ledger_info)| ledger_info.clone()),
+        }
+    }
+
     /// Returns the next epoch ending version after the given version (if one
     /// exists).
     pub fn next_epoch_ending_version(&self, version: Version) -> Option<Version> {
@@ -467,7 +488,7 @@ impl<
 
         // Get the highest synced and known ledger info versions
         let highest_synced_version = utils::fetch_pre_committed_version(self.storage.clone())?;
-        let highest_known_ledger_info = self.get_highest_known_ledger_info()?;
+        let highest_known_ledger_info = self.get_target_ledger_info()?;
         let highest_known_ledger_version = highest_known_ledger_info.ledger_info().version();
 
         // Check if we need to sync more data
@@ -1462,6 +1483,59 @@ impl<
         Ok(highest_known_ledger_info)
     }
 
+    /// Returns the ledger info to bootstrap to. If a snapshot sync target is
+    /// configured, this is the verified epoch ending ledger info for the target
+    /// (or the highest synced ledger info, if the node has already synced to or
+    /// beyond the target). Otherwise, this is the highest known ledger info.
+    fn get_target_ledger_info(&self) -> Result<LedgerInfoWithSignatures, Error> {
+        // If there's no snapshot sync target, use the highest known ledger info
+        let snapshot_sync_target = match self.driver_configuration.config.snapshot_sync_target {
+            Some(snapshot_sync_target) => snapshot_sync_target,
+            None => return self.get_highest_known_ledger_info(),
+        };
+
+        // Fetch the highest synced ledger info from storage
+        let highest_synced_ledger_info =
+            utils::fetch_latest_synced_ledger_info(self.storage.clone())?;
+        let highest_synced_version = highest_synced_ledger_info.ledger_info().version();
+
+        // Identify the verified ledger info for the target (fetched from the network)
+        if let Some(target_ledger_info) = self
+            .verified_epoch_states
+            .get_snapshot_sync_target_ledger_info(snapshot_sync_target)
+        {
+            return if target_ledger_info.ledger_info().version() > highest_synced_version {
+                Ok(target_ledger_info)
+            } else {
+                Ok(highest_synced_ledger_info) // We've already synced beyond the target
+            };
+        }
+
+        // Otherwise, the target was not fetched from the network. This is only
+        // valid if the node has already synced to (or beyond) the target.
+        let synced_beyond_target = match snapshot_sync_target {
+            SnapshotSyncTarget::Epoch(epoch) => {
+                highest_synced_ledger_info.ledger_info().next_block_epoch() > epoch
+            },
+            SnapshotSyncTarget::Version(_) => true, // All fetched epochs end after the target
+        };
+        if synced_beyond_target {
+            Ok(highest_synced_ledger_info)
+        } else {
+            Err(Error::AdvertisedDataError(format!(
+                "The snapshot sync target is not (yet) available! Target: {:?}, highest synced ledger info: {:?}",
+                snapshot_sync_target, highest_synced_ledger_info
+            )))
+        }
+    }
+
+    /// Returns true iff the node should stop syncing once bootstrapped
+    /// (i.e., because it has reached the snapshot sync target).
+    pub fn halt_at_snapshot_sync_target(&self) -> bool {
+        let config = &self.driver_configuration.config;
+        config.snapshot_sync_target.is_some() && config.halt_at_snapshot_sync_target
+    }
+
     /// Handles the end of stream notification or an invalid payload by
     /// terminating the stream appropriately.
     async fn handle_end_of_stream_or_invalid_payload(
@@ -3,6 +3,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
+    backup_streaming_client::BackupArchive,
     bootstrapper::Bootstrapper,
     continuous_syncer::ContinuousSyncer,
     driver_client::{ClientNotificationListener, DriverNotification},
@@ -25,7 +26,9 @@ use creditchain_consensus_notifications::{
     ConsensusCommitNotification, ConsensusNotification, ConsensusSyncDurationNotification,
     ConsensusSyncTargetNotification,
 };
-use creditchain_data_client::interface::CreditChainDataClientInterface;
+use creditchain_data_client::{
+    global_summary::GlobalDataSummary, interface::CreditChainDataClientInterface,
+};
 use creditchain_data_streaming_service::streaming_client::{
     DataStreamingClient, NotificationAndFeedback, NotificationFeedback,
 };
@@ -90,6 +93,9 @@ pub struct StateSyncDriver<
     StorageSyncer,
     StreamingClient,
 > {
+    // The backup archive to bootstrap from (if one is configured)
+    backup_archive: Option<BackupArchive>,
+
     // The component that manages the initial bootstrapping of the node
     bootstrapper: Bootstrapper<MetadataStorage, StorageSyncer, StreamingClient>,
 
@@ -155,6 +161,7 @@ impl<
 {
     #[allow(clippy::too_many_arguments)]
     pub fn new(
+        backup_archive: Option<BackupArchive>,
         client_notification_listener: ClientNotificationListener,
         commit_notification_listener: CommitNotificationListener,
         consensus_notification_handler: ConsensusNotificationHandler,
@@ -191,6 +198,7 @@ impl<
         );
 
         Self {
+            backup_archive,
             bootstrapper,
             client_notification_listener,
             commit_notification_listener,
@@ -658,8 +666,8 @@ impl<
         // Update the executing component metrics
         self.update_executing_component_metrics();
 
-        // Fetch the global data summary and verify we have active peers
-        let global_data_summary = self.creditchain_data_client.get_global_data_summary();
+        // Fetch the global data summary and verify we have active peers (or a backup archive)
+        let global_data_summary = self.get_global_data_summary();
         if global_data_summary.is_empty() {
             trace!(LogSchema::new(LogEntry::Driver).message(
                 "The global data summary is empty! It's likely that we have no active peers."
@@ -681,6 +689,17 @@ impl<
 
         // Drive progress depending on if we're bootstrapping or continuously syncing
         if self.bootstrapper.is_bootstrapped() {
+            // If the node should halt at the snapshot sync target, there's nothing to do
+            if self.bootstrapper.halt_at_snapshot_sync_target() {
+                sample!(
+                    SampleRate::Duration(Duration::from_secs(DRIVER_INFO_LOG_FREQ_SECS)),
+                    info!(LogSchema::new(LogEntry::Driver).message(
+                        "The node has reached the snapshot sync target! Halting all syncing."
+                    ));
+                );
+                return;
+            }
+
             // Fetch any consensus sync requests
             let consensus_sync_request = self.consensus_notification_handler.get_sync_request();
 
@@ -709,6 +728,43 @@ impl<
         };
     }
 
+    /// Returns the global data summary to drive progress. If the node is still
+    /// bootstrapping and a backup archive is configured, the summary of the
+    /// archive is returned (or an empty summary, while the archive is loaded
+    /// in the background). Otherwise, the summary of our peers is returned.
+    fn get_global_data_summary(&self) -> GlobalDataSummary {
+        if let Some(backup_archive) = &self.backup_archive {
+            if !self.bootstrapper.is_bootstrapped() {
+                return match backup_archive.get_global_data_summary() {
+                    Ok(Some(global_data_summary)) => global_data_summary,
+                    Ok(None) => {
+                        sample!(
+                            SampleRate::Duration(Duration::from_secs(DRIVER_INFO_LOG_FREQ_SECS)),
+                            info!(LogSchema::new(LogEntry::BackupArchive)
+                                .message("Waiting for the backup archive to load..."));
+                        );
+                        GlobalDataSummary::empty()
+                    },
+                    Err(error) => {
+                        sample!(
+                            SampleRate::Duration(Duration::from_secs(DRIVER_ERROR_LOG_FREQ_SECS)),
+                            warn!(LogSchema::new(LogEntry::BackupArchive)
+                                .error(&error)
+                                .message("Failed to fetch the backup archive data summary!"));
+                        );
+                        metrics::increment_counter(
+                            &metrics::BOOTSTRAPPER_ERRORS,
+                            error.get_label(),
+                        );
+                        GlobalDataSummary::empty()
+                    },
+                };
+            }
+        }
+
+        self.creditchain_data_client.get_global_data_summary()
+    }
+
     /// Updates the executing component metrics for the driver
     fn update_executing_component_metrics(&self) {
         // Determine the executing component
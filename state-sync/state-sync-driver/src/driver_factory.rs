@@ -3,6 +3,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
+    backup_streaming_client::{BackupArchive, BackupStreamingClient},
     driver::{DriverConfiguration, StateSyncDriver},
     driver_client::{ClientNotificationListener, DriverClient, DriverNotification},
     metadata_storage::MetadataStorageInterface,
@@ -164,8 +165,17 @@ impl DriverFactory {
             waypoint,
         );
 
+        // Create the backup archive and streaming client (if bootstrapping from a backup)
+        let backup_bootstrapping_config = node_config.backup_bootstrapping.clone();
+        let backup_archive = backup_bootstrapping_config
+            .is_enabled()
+            .then(|| BackupArchive::new(backup_bootstrapping_config));
+        let streaming_client =
+            BackupStreamingClient::new(backup_archive.clone(), streaming_service_client);
+
         // Create the state sync driver
         let state_sync_driver = StateSyncDriver::new(
+            backup_archive,
             client_notification_listener,
             commit_notification_listener,
             consensus_notification_handler,
@@ -177,7 +187,7 @@ impl DriverFactory {
             storage_service_notification_handler,
             storage_synchronizer,
             creditchain_data_client,
-            streaming_service_client,
+            streaming_client,
             storage.reader,
             time_service,
         );
@@ -4,6 +4,7 @@
 
 #![forbid(unsafe_code)]
 
+mod backup_streaming_client;
 mod bootstrapper;
 mod continuous_syncer;
 mod driver;
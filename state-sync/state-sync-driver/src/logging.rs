@@ -28,6 +28,7 @@ impl LogSchema<'_> {
 #[serde(rename_all = "snake_case")]
 pub enum LogEntry {
     AutoBootstrapping,
+    BackupArchive,
     Bootstrapper,
     ClientNotification,
     ConsensusNotification,
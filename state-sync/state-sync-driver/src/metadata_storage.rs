@@ -6,6 +6,10 @@ use crate::{
     metadata_storage::database_schema::{MetadataKey, MetadataSchema, MetadataValue},
 };
 use anyhow::{anyhow, Result};
+use creditchain_data_client::{
+    error::Error as DataClientError,
+    peer_states::{PeerStatesStorageInterface, PersistedPeerStates},
+};
 use creditchain_logger::prelude::*;
 use creditchain_schemadb::{
     batch::SchemaBatch,
@@ -107,10 +111,37 @@ impl PersistentMetadataStorage {
                     ))
                 })?;
         match maybe_metadata_value {
-            Some(metadata_value) => {
-                let MetadataValue::StateSnapshotSync(snapshot_progress) = metadata_value;
+            Some(MetadataValue::StateSnapshotSync(snapshot_progress)) => {
                 Ok(Some(snapshot_progress))
             },
+            Some(metadata_value) => Err(Error::StorageError(format!(
+                "Unexpected metadata value found for key: {:?}. Value: {:?}",
+                metadata_key, metadata_value
+            ))),
+            None => Ok(None),
+        }
+    }
+
+    /// Returns the persisted data client peer states. Returns None if no states are found.
+    fn get_peer_states(&self) -> Result<Option<PersistedPeerStates>, Error> {
+        let metadata_key = MetadataKey::PeerStates;
+        let maybe_metadata_value =
+            self.database
+                .get::<MetadataSchema>(&metadata_key)
+                .map_err(|error| {
+                    Error::StorageError(format!(
+                        "Failed to read metadata value for key: {:?}. Error: {:?}",
+                        metadata_key, error
+                    ))
+                })?;
+        match maybe_metadata_value {
+            Some(MetadataValue::PeerStates(persisted_peer_states)) => {
+                Ok(Some(persisted_peer_states))
+            },
+            Some(metadata_value) => Err(Error::StorageError(format!(
+                "Unexpected metadata value found for key: {:?}. Value: {:?}",
+                metadata_key, metadata_value
+            ))),
             None => Ok(None),
         }
     }
@@ -227,6 +258,24 @@ impl MetadataStorageInterface for PersistentMetadataStorage {
     }
 }
 
+impl PeerStatesStorageInterface for PersistentMetadataStorage {
+    fn get_persisted_peer_states(&self) -> Result<Option<PersistedPeerStates>, DataClientError> {
+        self.get_peer_states()
+            .map_err(|error| DataClientError::UnexpectedErrorEncountered(error.to_string()))
+    }
+
+    fn persist_peer_states(
+        &self,
+        persisted_peer_states: PersistedPeerStates,
+    ) -> Result<(), DataClientError> {
+        self.commit_key_value(
+            MetadataKey::PeerStates,
+            MetadataValue::PeerStates(persisted_peer_states),
+        )
+        .map_err(|error| DataClientError::UnexpectedErrorEncountered(error.to_string()))
+    }
+}
+
 /// A simple struct for recording the progress of a state snapshot sync
 #[derive(Debug, Deserialize, Eq, PartialEq, Serialize)]
 pub struct StateSnapshotProgress {
@@ -253,6 +302,7 @@ pub mod database_schema {
     #[repr(u8)]
     pub enum MetadataKey {
         StateSnapshotSync, // A state snapshot sync that was started
+        PeerStates,        // The data client peer states (e.g., scores and latencies)
     }
 
     /// A metadata value that can be inserted into the database
@@ -260,6 +310,7 @@ pub mod database_schema {
     #[repr(u8)]
     pub enum MetadataValue {
         StateSnapshotSync(StateSnapshotProgress), // A state snapshot sync progress marker
+        PeerStates(PersistedPeerStates),          // A snapshot of the data client peer states
     }
 
     impl KeyCodec<MetadataSchema> for MetadataKey {
@@ -0,0 +1,370 @@
+// Copyright © CreditChain Research Team
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    backup_streaming_client::{
+        create_single_leaf_range_proof, BackupArchive, BackupStreamingClient,
+        FIRST_BACKUP_STREAM_ID,
+    },
+    tests::{
+        mocks::create_mock_streaming_client,
+        utils::{create_epoch_ending_ledger_info_for_epoch, create_transaction_info},
+    },
+};
+use creditchain_backup_cli::{
+    backup_types::{
+        epoch_ending::manifest::{EpochEndingBackup, EpochEndingChunk},
+        state_snapshot::manifest::{StateSnapshotBackup, StateSnapshotChunk},
+    },
+    metadata::Metadata,
+    storage::{local_fs::LocalFs, BackupHandleRef, BackupStorage, FileHandle},
+};
+use creditchain_config::config::BackupBootstrappingConfig;
+use creditchain_crypto::{
+    hash::{CryptoHash, TransactionAccumulatorHasher, ACCUMULATOR_PLACEHOLDER_HASH},
+    HashValue,
+};
+use creditchain_data_client::global_summary::GlobalDataSummary;
+use creditchain_data_streaming_service::{
+    data_notification::DataPayload, error::Error as StreamingError,
+    streaming_client::DataStreamingClient,
+};
+use creditchain_storage_service_types::responses::CompleteDataRange;
+use creditchain_temppath::TempPath;
+use creditchain_types::{
+    ledger_info::LedgerInfoWithSignatures,
+    proof::{
+        accumulator::InMemoryTransactionAccumulator, MerkleTreeInternalNode,
+        SparseMerkleRangeProof, TransactionAccumulatorProof, TransactionInfoWithProof,
+    },
+    state_store::{state_key::StateKey, state_value::StateValue},
+    waypoint::Waypoint,
+};
+use claims::{assert_matches, assert_ok};
+use futures::StreamExt;
+use mockall::predicate::eq;
+use serde::Serialize;
+use std::{path::Path, time::Duration};
+use tokio::io::AsyncWriteExt;
+
+#[test]
+fn test_create_single_leaf_range_proof() {
+    // Create an accumulator with several leaves
+    let leaf_hashes: Vec<_> = (0..11).map(|_| HashValue::random()).collect();
+    let accumulator = InMemoryTransactionAccumulator::default().append(&leaf_hashes);
+
+    // Verify the range proof for each leaf (the last leaf is the one used for fast sync)
+    for (index, leaf_hash) in leaf_hashes.iter().enumerate() {
+        let index = index as u64;
+        let accumulator_proof = create_accumulator_proof(&leaf_hashes, index);
+        accumulator_proof
+            .verify(accumulator.root_hash(), *leaf_hash, index)
+            .unwrap();
+
+        let range_proof = create_single_leaf_range_proof(index, &accumulator_proof);
+        range_proof
+            .verify(accumulator.root_hash(), Some(index), &[*leaf_hash])
+            .unwrap();
+    }
+}
+
+#[tokio::test]
+async fn test_stream_from_backup_archive() {
+    // Create a backup archive with a state snapshot at the end of epoch 0
+    let backup_dir = TempPath::new();
+    backup_dir.create_as_dir().unwrap();
+    let ledger_info = create_epoch_ending_ledger_info_for_epoch(0, 0);
+    let state_values: Vec<_> = (0..3u8)
+        .map(|index| {
+            let state_key = StateKey::raw(format!("key_{}", index).as_bytes());
+            let state_value = StateValue::new_legacy(vec![index].into());
+            (state_key, state_value)
+        })
+        .collect();
+    create_backup_archive(backup_dir.path(), &ledger_info, &state_values).await;
+
+    // Create a backup streaming client (the streaming service should only see stream 0)
+    let mut mock_streaming_client = create_mock_streaming_client();
+    mock_streaming_client
+        .expect_terminate_stream_with_feedback()
+        .with(eq(0), eq(None))
+        .times(1)
+        .return_const(Ok(()));
+    let metadata_cache_dir = TempPath::new();
+    let backup_archive = BackupArchive::new(BackupBootstrappingConfig {
+        local_fs_dir: Some(backup_dir.path().to_path_buf()),
+        metadata_cache_dir: Some(metadata_cache_dir.path().to_path_buf()),
+        ..Default::default()
+    });
+    let backup_streaming_client =
+        BackupStreamingClient::new(Some(backup_archive.clone()), mock_streaming_client);
+
+    // Verify that streams can't be created until the archive is loaded
+    assert_matches!(
+        backup_streaming_client
+            .get_all_epoch_ending_ledger_infos(0)
+            .await,
+        Err(StreamingError::DataIsUnavailable(_))
+    );
+
+    // Wait for the archive to load, and verify the advertised data
+    let global_data_summary = wait_for_global_data_summary(&backup_archive).await;
+    let advertised_data = global_data_summary.advertised_data;
+    assert_eq!(advertised_data.states, vec![CompleteDataRange::new(0, 0).unwrap()]);
+    assert_eq!(advertised_data.synced_ledger_infos, vec![ledger_info.clone()]);
+
+    // Stream the epoch ending ledger infos and verify the notifications
+    let mut stream_listener = backup_streaming_client
+        .get_all_epoch_ending_ledger_infos(0)
+        .await
+        .unwrap();
+    let data_stream_id = stream_listener.data_stream_id;
+    assert!(data_stream_id >= FIRST_BACKUP_STREAM_ID);
+    let notification = stream_listener.select_next_some().await;
+    assert!(notification.notification_id >= FIRST_BACKUP_STREAM_ID);
+    match notification.data_payload {
+        DataPayload::EpochEndingLedgerInfos(ledger_infos) => {
+            assert_eq!(ledger_infos, vec![ledger_info.clone()])
+        },
+        data_payload => panic!("Unexpected data payload: {:?}", data_payload),
+    }
+    let notification = stream_listener.select_next_some().await;
+    assert_matches!(notification.data_payload, DataPayload::EndOfStream);
+    assert_ok!(
+        backup_streaming_client
+            .terminate_stream_with_feedback(data_stream_id, None)
+            .await
+    );
+
+    // Stream the state values (from the second index) and verify the notifications
+    let mut stream_listener = backup_streaming_client
+        .get_all_state_values(0, Some(1))
+        .await
+        .unwrap();
+    assert!(stream_listener.data_stream_id > data_stream_id);
+    let notification = stream_listener.select_next_some().await;
+    match notification.data_payload {
+        DataPayload::StateValuesWithProof(state_value_chunk_with_proof) => {
+            assert_eq!(state_value_chunk_with_proof.first_index, 1);
+            assert_eq!(state_value_chunk_with_proof.last_index, 2);
+            assert_eq!(state_value_chunk_with_proof.raw_values, state_values[1..]);
+        },
+        data_payload => panic!("Unexpected data payload: {:?}", data_payload),
+    }
+    let notification = stream_listener.select_next_some().await;
+    assert_matches!(notification.data_payload, DataPayload::EndOfStream);
+
+    // Verify that a backup stream can only be terminated once
+    let data_stream_id = stream_listener.data_stream_id;
+    assert_ok!(
+        backup_streaming_client
+            .terminate_stream_with_feedback(data_stream_id, None)
+            .await
+    );
+    assert_matches!(
+        backup_streaming_client
+            .terminate_stream_with_feedback(data_stream_id, None)
+            .await,
+        Err(StreamingError::UnexpectedErrorEncountered(_))
+    );
+
+    // Verify that requests the archive can't serve are rejected explicitly
+    assert_matches!(
+        backup_streaming_client.get_all_state_values(1, None).await,
+        Err(StreamingError::UnsupportedRequestEncountered(_))
+    );
+    assert_matches!(
+        backup_streaming_client
+            .get_all_transaction_outputs(0, 1, 1)
+            .await,
+        Err(StreamingError::UnsupportedRequestEncountered(_))
+    );
+    assert_matches!(
+        backup_streaming_client
+            .get_all_transactions(0, 0, 0, false)
+            .await,
+        Err(StreamingError::UnsupportedRequestEncountered(_))
+    );
+
+    // Verify that streams of the streaming service are still terminated by it
+    assert_ok!(
+        backup_streaming_client
+            .terminate_stream_with_feedback(0, None)
+            .await
+    );
+}
+
+/// Creates an accumulator proof for the given leaf (by building the tree in memory)
+fn create_accumulator_proof(
+    leaf_hashes: &[HashValue],
+    leaf_index: u64,
+) -> TransactionAccumulatorProof {
+    let mut siblings = vec![];
+    let mut level_hashes = leaf_hashes.to_vec();
+    let mut index = leaf_index as usize;
+    while level_hashes.len() > 1 {
+        if level_hashes.len() % 2 == 1 {
+            level_hashes.push(*ACCUMULATOR_PLACEHOLDER_HASH);
+        }
+        siblings.push(level_hashes[index ^ 1]);
+        level_hashes = level_hashes
+            .chunks(2)
+            .map(|pair| {
+                MerkleTreeInternalNode::<TransactionAccumulatorHasher>::new(pair[0], pair[1])
+                    .hash()
+            })
+            .collect();
+        index /= 2;
+    }
+    TransactionAccumulatorProof::new(siblings)
+}
+
+/// Creates a backup archive (in the given directory) that holds a single
+/// epoch ending ledger info, and a state snapshot with the given values.
+async fn create_backup_archive(
+    backup_dir: &Path,
+    ledger_info: &LedgerInfoWithSignatures,
+    state_values: &[(StateKey, StateValue)],
+) {
+    let storage = LocalFs::new(backup_dir.to_path_buf());
+    let version = ledger_info.ledger_info().version();
+    let epoch = ledger_info.ledger_info().epoch();
+
+    // Create the epoch ending backup
+    let backup_handle = storage
+        .create_backup(&"epoch_ending".parse().unwrap())
+        .await
+        .unwrap();
+    let ledger_infos =
+        write_records(&storage, &backup_handle, "ledger_infos", &[ledger_info]).await;
+    let manifest = EpochEndingBackup {
+        first_epoch: epoch,
+        last_epoch: epoch,
+        waypoints: vec![Waypoint::new_epoch_boundary(ledger_info.ledger_info()).unwrap()],
+        chunks: vec![EpochEndingChunk {
+            first_epoch: epoch,
+            last_epoch: epoch,
+            ledger_infos,
+        }],
+    };
+    let manifest = write_file(
+        &storage,
+        &backup_handle,
+        "epoch_ending.manifest",
+        serde_json::to_vec(&manifest).unwrap(),
+    )
+    .await;
+    save_metadata(
+        &storage,
+        Metadata::new_epoch_ending_backup(epoch, epoch, version, version, manifest),
+    )
+    .await;
+
+    // Create the state snapshot backup
+    let backup_handle = storage
+        .create_backup(&"state_snapshot".parse().unwrap())
+        .await
+        .unwrap();
+    let blobs = write_records(&storage, &backup_handle, "blobs", state_values).await;
+    let chunk_proof = write_file(
+        &storage,
+        &backup_handle,
+        "chunk.proof",
+        bcs::to_bytes(&SparseMerkleRangeProof::new(vec![])).unwrap(),
+    )
+    .await;
+    let snapshot_proof = TransactionInfoWithProof::new(
+        TransactionAccumulatorProof::new(vec![]),
+        create_transaction_info(),
+    );
+    let proof = write_file(
+        &storage,
+        &backup_handle,
+        "state.proof",
+        bcs::to_bytes(&(snapshot_proof, ledger_info)).unwrap(),
+    )
+    .await;
+    let manifest = StateSnapshotBackup {
+        version,
+        epoch,
+        root_hash: HashValue::random(),
+        chunks: vec![StateSnapshotChunk {
+            first_idx: 0,
+            last_idx: state_values.len() - 1,
+            first_key: CryptoHash::hash(&state_values.first().unwrap().0),
+            last_key: CryptoHash::hash(&state_values.last().unwrap().0),
+            blobs,
+            proof: chunk_proof,
+        }],
+        proof,
+    };
+    let manifest = write_file(
+        &storage,
+        &backup_handle,
+        "state.manifest",
+        serde_json::to_vec(&manifest).unwrap(),
+    )
+    .await;
+    save_metadata(
+        &storage,
+        Metadata::new_state_snapshot_backup(epoch, version, manifest),
+    )
+    .await;
+
+    // Create the transaction backup metadata (the manifest is only read for outputs)
+    save_metadata(
+        &storage,
+        Metadata::new_transaction_backup(0, version, "unused.manifest".into()),
+    )
+    .await;
+}
+
+/// Saves the given metadata entry to the backup storage
+async fn save_metadata(storage: &LocalFs, metadata: Metadata) {
+    storage
+        .save_metadata_line(&metadata.name(), &metadata.to_text_line().unwrap())
+        .await
+        .unwrap();
+}
+
+/// Waits for the backup archive to load, and returns the global data summary
+async fn wait_for_global_data_summary(backup_archive: &BackupArchive) -> GlobalDataSummary {
+    loop {
+        if let Some(global_data_summary) = backup_archive.get_global_data_summary().unwrap() {
+            return global_data_summary;
+        }
+        tokio::time::sleep(Duration::from_millis(10)).await;
+    }
+}
+
+/// Writes the given bytes to a new file in the backup storage
+async fn write_file(
+    storage: &LocalFs,
+    backup_handle: &BackupHandleRef,
+    name: &str,
+    bytes: Vec<u8>,
+) -> FileHandle {
+    let (file_handle, mut file) = storage
+        .create_for_write(backup_handle, &name.parse().unwrap())
+        .await
+        .unwrap();
+    file.write_all(&bytes).await.unwrap();
+    file.shutdown().await.unwrap();
+    file_handle
+}
+
+/// Writes the given records (each prefixed by its length) to a new file in the backup storage
+async fn write_records<T: Serialize>(
+    storage: &LocalFs,
+    backup_handle: &BackupHandleRef,
+    name: &str,
+    records: &[T],
+) -> FileHandle {
+    let mut bytes = vec![];
+    for record in records {
+        let record_bytes = bcs::to_bytes(record).unwrap();
+        bytes.extend((record_bytes.len() as u32).to_be_bytes());
+        bytes.extend(record_bytes);
+    }
+    write_file(storage, backup_handle, name, bytes).await
+}
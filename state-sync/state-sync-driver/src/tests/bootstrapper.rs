@@ -20,7 +20,7 @@ use crate::{
     },
     utils::OutputFallbackHandler,
 };
-use creditchain_config::config::BootstrappingMode;
+use creditchain_config::config::{BootstrappingMode, SnapshotSyncTarget};
 use creditchain_data_client::global_summary::GlobalDataSummary;
 use creditchain_data_streaming_service::{
     data_notification::{DataNotification, DataPayload, NotificationId},
@@ -1450,6 +1450,104 @@ async fn test_snapshot_sync_lag_panic() {
         .unwrap();
 }
 
+#[tokio::test]
+async fn test_snapshot_sync_target() {
+    // Create test data
+    let synced_version = GENESIS_TRANSACTION_VERSION; // Genesis is the highest synced
+    let epoch_ending_versions = vec![100, 200, 300];
+
+    // Verify that the bootstrapper fast syncs to the epoch ending version of each target
+    for (snapshot_sync_target, expected_target_version) in [
+        (SnapshotSyncTarget::Epoch(1), 200),
+        (SnapshotSyncTarget::Version(250), 200),
+        (SnapshotSyncTarget::Version(300), 300),
+    ] {
+        // Create a driver configuration with a genesis waypoint and a snapshot sync target
+        let mut driver_configuration = create_full_node_driver_configuration();
+        driver_configuration.config.bootstrapping_mode = BootstrappingMode::DownloadLatestStates;
+        driver_configuration.config.snapshot_sync_target = Some(snapshot_sync_target);
+
+        // Create the mock streaming client
+        let mut mock_streaming_client = create_mock_streaming_client();
+        let (_notification_sender_1, data_stream_listener_1) = create_data_stream_listener();
+        mock_streaming_client
+            .expect_get_all_state_values()
+            .times(1)
+            .with(eq(expected_target_version), eq(Some(0)))
+            .return_once(move |_, _| Ok(data_stream_listener_1));
+
+        // Create the mock metadata storage
+        let mut metadata_storage = MockMetadataStorage::new();
+        metadata_storage
+            .expect_previous_snapshot_sync_target()
+            .returning(move || Ok(None));
+
+        // Create the bootstrapper
+        let mut bootstrapper = create_bootstrapper_with_storage(
+            driver_configuration,
+            mock_streaming_client,
+            metadata_storage,
+            None,
+            synced_version,
+            true,
+        );
+
+        // Insert the epoch ending ledger infos into the verified states of the bootstrapper
+        insert_verified_epoch_ending_ledger_infos(&mut bootstrapper, &epoch_ending_versions);
+
+        // Manually insert a transaction output to sync
+        bootstrapper
+            .get_state_value_syncer()
+            .set_transaction_output_to_sync(create_output_list_with_proof());
+
+        // Create a global data summary
+        let mut global_data_summary = create_global_summary(epoch_ending_versions.len() as u64);
+        global_data_summary.advertised_data.synced_ledger_infos =
+            vec![create_random_epoch_ending_ledger_info(1000, 5)];
+
+        // Drive progress to start the state value stream
+        drive_progress(&mut bootstrapper, &global_data_summary, false)
+            .await
+            .unwrap();
+    }
+}
+
+#[tokio::test]
+async fn test_snapshot_sync_target_unavailable() {
+    // Create a driver configuration with a snapshot sync target that is unavailable
+    let mut driver_configuration = create_full_node_driver_configuration();
+    driver_configuration.config.bootstrapping_mode = BootstrappingMode::DownloadLatestStates;
+    driver_configuration.config.snapshot_sync_target = Some(SnapshotSyncTarget::Epoch(10));
+
+    // Create the bootstrapper
+    let mut metadata_storage = MockMetadataStorage::new();
+    metadata_storage
+        .expect_previous_snapshot_sync_target()
+        .returning(move || Ok(None));
+    let mut bootstrapper = create_bootstrapper_with_storage(
+        driver_configuration,
+        create_mock_streaming_client(),
+        metadata_storage,
+        None,
+        GENESIS_TRANSACTION_VERSION,
+        true,
+    );
+
+    // Insert the epoch ending ledger infos into the verified states of the bootstrapper
+    insert_verified_epoch_ending_ledger_infos(&mut bootstrapper, &[100, 200]);
+
+    // Create a global data summary
+    let mut global_data_summary = create_global_summary(2);
+    global_data_summary.advertised_data.synced_ledger_infos =
+        vec![create_random_epoch_ending_ledger_info(1000, 5)];
+
+    // Drive progress and verify an error is returned
+    let error = drive_progress(&mut bootstrapper, &global_data_summary, false)
+        .await
+        .unwrap_err();
+    assert_matches!(error, Error::AdvertisedDataError(_));
+}
+
 #[tokio::test]
 async fn test_waypoint_mismatch() {
     // Create a waypoint
@@ -1749,6 +1847,35 @@ fn manipulate_verified_epoch_states(
     }
 }
 
+/// Inserts a verified epoch ending ledger info (starting at epoch 0) for each
+/// of the given versions into the verified epoch states of the bootstrapper.
+fn insert_verified_epoch_ending_ledger_infos(
+    bootstrapper: &mut Bootstrapper<
+        MockMetadataStorage,
+        MockStorageSynchronizer,
+        MockStreamingClient,
+    >,
+    epoch_ending_versions: &[Version],
+) {
+    // Mark the epochs as fetched and the waypoint as verified
+    let verified_epoch_states = bootstrapper.get_verified_epoch_states();
+    verified_epoch_states.set_fetched_epoch_ending_ledger_infos();
+    verified_epoch_states.set_verified_waypoint(0);
+
+    // Insert the epoch ending ledger infos
+    let waypoint_ledger_info = create_random_epoch_ending_ledger_info(0, 1);
+    for (epoch, version) in epoch_ending_versions.iter().enumerate() {
+        let epoch_ending_ledger_info =
+            create_random_epoch_ending_ledger_info(*version, epoch as u64);
+        verified_epoch_states
+            .update_verified_epoch_states(
+                &epoch_ending_ledger_info,
+                &Waypoint::new_any(waypoint_ledger_info.ledger_info()),
+            )
+            .unwrap();
+    }
+}
+
 /// Verifies that the receiver gets a successful notification
 fn verify_bootstrap_notification(notification_receiver: oneshot::Receiver<Result<(), Error>>) {
     assert_ok!(notification_receiver.now_or_never().unwrap().unwrap());
@@ -8,9 +8,15 @@ use crate::{
     },
     tests::utils::{create_epoch_ending_ledger_info, create_ledger_info_at_version},
 };
+use creditchain_config::network_id::{NetworkId, PeerNetworkId};
+use creditchain_data_client::peer_states::{
+    PeerStatesStorageInterface, PersistedPeerState, PersistedPeerStates, ResponseLatencyHistogram,
+};
 use creditchain_schemadb::schema::fuzzing::assert_encode_decode;
 use creditchain_temppath::TempPath;
+use creditchain_types::PeerId;
 use claims::{assert_err, assert_none};
+use std::collections::BTreeMap;
 
 #[test]
 fn test_create_then_open() {
@@ -101,6 +107,39 @@ fn test_metadata_schema_encode_decode() {
     );
 }
 
+#[test]
+fn test_peer_states_reads_and_writes() {
+    // Create a new metadata storage
+    let tmp_dir = TempPath::new();
+    let metadata_storage = PersistentMetadataStorage::new(tmp_dir.path());
+
+    // Verify no peer states are persisted
+    assert_none!(metadata_storage.get_persisted_peer_states().unwrap());
+
+    // Persist several peer states snapshots and verify they're returned
+    for persisted_at_usecs in 0..10 {
+        let persisted_peer_states = create_persisted_peer_states(persisted_at_usecs);
+        metadata_storage
+            .persist_peer_states(persisted_peer_states.clone())
+            .unwrap();
+        assert_eq!(
+            Some(persisted_peer_states),
+            metadata_storage.get_persisted_peer_states().unwrap()
+        );
+    }
+
+    // Verify the peer states don't interfere with the snapshot progress
+    assert_none!(metadata_storage.previous_snapshot_sync_target().unwrap());
+    let target_ledger_info = create_ledger_info_at_version(100);
+    metadata_storage
+        .update_last_persisted_state_value_index(&target_ledger_info, 10, false)
+        .unwrap();
+    assert_eq!(
+        Some(create_persisted_peer_states(9)),
+        metadata_storage.get_persisted_peer_states().unwrap()
+    );
+}
+
 #[test]
 fn test_multiple_reads_and_writes() {
     // Create a new metadata storage
@@ -167,3 +206,23 @@ fn test_writes_to_different_targets() {
         .update_last_persisted_state_value_index(&target_ledger_info, 10101, false)
         .unwrap_err();
 }
+
+/// Creates a set of persisted peer states for testing
+fn create_persisted_peer_states(persisted_at_usecs: u64) -> PersistedPeerStates {
+    let mut peer_states = BTreeMap::new();
+    for score in 0..5 {
+        let peer_id = PeerId::new([score as u8; PeerId::LENGTH]);
+        let peer = PeerNetworkId::new(NetworkId::Public, peer_id);
+        let persisted_peer_state = PersistedPeerState {
+            bad_responses_by_type: BTreeMap::from([("malicious".into(), score)]),
+            response_latency_histogram: ResponseLatencyHistogram::default(),
+            scaled_score: score * 10_000,
+        };
+        peer_states.insert(peer, persisted_peer_state);
+    }
+
+    PersistedPeerStates {
+        persisted_at_usecs,
+        peer_states,
+    }
+}
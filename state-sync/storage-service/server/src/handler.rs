@@ -35,7 +35,7 @@ use creditchain_types::transaction::Version;
 use arc_swap::ArcSwap;
 use dashmap::{mapref::entry::Entry, DashMap};
 use mini_moka::sync::Cache;
-use std::{sync::Arc, time::Duration};
+use std::{hash::Hash, sync::Arc, time::Duration};
 
 /// Storage server constants
 const ERROR_LOG_FREQUENCY_SECS: u64 = 5; // The frequency to log errors
@@ -135,7 +135,7 @@ impl<T: StorageReaderInterface> Handler<T> {
 
         // Process the request and return the response to the client
         let response = self.process_request(&peer_network_id, request.clone(), false);
-        self.send_response(request, response, response_sender);
+        self.send_response(&peer_network_id, request, response, response_sender);
     }
 
     /// Processes the given request and returns the response
@@ -231,12 +231,18 @@ impl<T: StorageReaderInterface> Handler<T> {
     /// Sends a response via the provided sender
     pub(crate) fn send_response(
         &self,
+        peer_network_id: &PeerNetworkId,
         request: StorageServiceRequest,
         response: creditchain_storage_service_types::Result<StorageServiceResponse>,
         response_sender: ResponseSender,
     ) {
         log_storage_response(request, &response);
-        response_sender.send(response);
+        let num_response_bytes = response_sender.send(response);
+
+        // Charge the response to the peer's serving budget
+        self.request_moderator
+            .get_peer_serving_budgets()
+            .record_served_bytes(peer_network_id, num_response_bytes);
     }
 
     /// Handles the given optimistic fetch request
@@ -373,6 +379,7 @@ impl<T: StorageReaderInterface> Handler<T> {
 
         // Notify the client of the failure
         self.send_response(
+            &peer_network_id,
             request,
             Err(StorageServiceError::InvalidRequest(error.to_string())),
             subscription_request.take_response_sender(),
@@ -385,6 +392,45 @@ impl<T: StorageReaderInterface> Handler<T> {
         &self,
         peer_network_id: &PeerNetworkId,
         request: &StorageServiceRequest,
+    ) -> creditchain_storage_service_types::Result<StorageServiceResponse, Error> {
+        // If the peer is over its serving budget, serve a smaller response. Note:
+        // these are cached separately (by chunk size) to avoid mixing them with
+        // full responses in the LRU cache.
+        let peer_serving_budgets = self.request_moderator.get_peer_serving_budgets();
+        if let Some(max_network_chunk_bytes) =
+            peer_serving_budgets.get_over_budget_max_network_chunk_bytes(peer_network_id)
+        {
+            let handler = Handler {
+                storage: self
+                    .storage
+                    .with_max_network_chunk_bytes(max_network_chunk_bytes),
+                ..self.clone()
+            };
+            return handler.process_request_with_cache(
+                peer_network_id,
+                request,
+                &peer_serving_budgets.get_over_budget_response_cache(),
+                (max_network_chunk_bytes, request.clone()),
+            );
+        }
+
+        // Otherwise, use the LRU response cache
+        self.process_request_with_cache(
+            peer_network_id,
+            request,
+            &self.lru_response_cache,
+            request.clone(),
+        )
+    }
+
+    /// Processes a storage service request by checking the given cache first,
+    /// and fetching (and caching) the response from storage on a cache miss.
+    fn process_request_with_cache<K: Eq + Hash + Send + Sync + 'static>(
+        &self,
+        peer_network_id: &PeerNetworkId,
+        request: &StorageServiceRequest,
+        response_cache: &Cache<K, StorageServiceResponse>,
+        cache_key: K,
     ) -> creditchain_storage_service_types::Result<StorageServiceResponse, Error> {
         // Increment the LRU cache probe counter
         increment_counter(
@@ -394,7 +440,7 @@ impl<T: StorageReaderInterface> Handler<T> {
         );
 
         // Check if the response is already in the cache
-        if let Some(response) = self.lru_response_cache.get(request) {
+        if let Some(response) = response_cache.get(&cache_key) {
             increment_counter(
                 &metrics::LRU_CACHE_EVENT,
                 peer_network_id.network_id(),
@@ -403,7 +449,24 @@ impl<T: StorageReaderInterface> Handler<T> {
             return Ok(response.clone());
         }
 
-        // Otherwise, fetch the data from storage and time the operation
+        // Otherwise, fetch the data from storage
+        let storage_response = self.fetch_storage_response(peer_network_id, request)?;
+
+        // Cache the storage response
+        response_cache.insert(cache_key, storage_response.clone());
+
+        // Return the storage response
+        Ok(storage_response)
+    }
+
+    /// Fetches the data for the given request from storage
+    /// and creates the storage response (without caching it).
+    fn fetch_storage_response(
+        &self,
+        peer_network_id: &PeerNetworkId,
+        request: &StorageServiceRequest,
+    ) -> creditchain_storage_service_types::Result<StorageServiceResponse, Error> {
+        // Fetch the data from storage and time the operation
         let fetch_data_response = || match &request.data_request {
             DataRequest::GetStateValuesWithProof(request) => {
                 self.get_state_value_chunk_with_proof(request)
@@ -444,20 +507,13 @@ impl<T: StorageReaderInterface> Handler<T> {
             StorageServiceResponse::new(data_response, request.use_compression)
                 .map_err(|error| error.into())
         };
-        let storage_response = utils::execute_and_time_duration(
+        utils::execute_and_time_duration(
             &metrics::STORAGE_RESPONSE_CREATION_LATENCY,
             Some((peer_network_id, request)),
             None,
             create_storage_response,
             None,
-        )?;
-
-        // Create and cache the storage response
-        self.lru_response_cache
-            .insert(request.clone(), storage_response.clone());
-
-        // Return the storage response
-        Ok(storage_response)
+        )
     }
 
     fn get_state_value_chunk_with_proof(
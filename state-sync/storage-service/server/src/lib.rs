@@ -42,6 +42,7 @@ pub mod metrics;
 mod moderator;
 pub mod network;
 mod optimistic_fetch;
+mod serving_budgets;
 pub mod storage;
 mod subscription;
 mod utils;
@@ -388,6 +389,16 @@ impl<T: StorageReaderInterface + Send + Sync> StorageServiceServer<T> {
 
         // Handle the storage requests as they arrive
         while let Some(network_request) = self.network_requests.next().await {
+            // Enforce the per-peer concurrency budgets. If the peer already has
+            // too many requests in flight, the request will be served (in order)
+            // once one of the peer's active requests completes.
+            let peer_serving_budgets = self.request_moderator.get_peer_serving_budgets();
+            let network_request =
+                match peer_serving_budgets.start_or_queue_request(network_request) {
+                    Some(network_request) => network_request,
+                    None => continue,
+                };
+
             // All handler methods are currently CPU-bound and synchronous
             // I/O-bound, so we want to spawn on the blocking thread pool to
             // avoid starving other async tasks on the same runtime.
@@ -400,7 +411,7 @@ impl<T: StorageReaderInterface + Send + Sync> StorageServiceServer<T> {
             let request_moderator = self.request_moderator.clone();
             let time_service = self.time_service.clone();
             self.runtime.spawn_blocking(move || {
-                Handler::new(
+                let handler = Handler::new(
                     cached_storage_server_summary,
                     optimistic_fetches,
                     lru_response_cache,
@@ -408,14 +419,22 @@ impl<T: StorageReaderInterface + Send + Sync> StorageServiceServer<T> {
                     storage,
                     subscriptions,
                     time_service,
-                )
-                .process_request_and_respond(
-                    config,
-                    network_request.peer_network_id,
-                    network_request.protocol_id,
-                    network_request.storage_service_request,
-                    network_request.response_sender,
                 );
+
+                // Process the request, and any requests from the same
+                // peer that were queued while this request was in flight.
+                let mut next_network_request = Some(network_request);
+                while let Some(network_request) = next_network_request {
+                    let peer_network_id = network_request.peer_network_id;
+                    handler.process_request_and_respond(
+                        config,
+                        peer_network_id,
+                        network_request.protocol_id,
+                        network_request.storage_service_request,
+                        network_request.response_sender,
+                    );
+                    next_network_request = peer_serving_budgets.complete_request(&peer_network_id);
+                }
             });
         }
     }
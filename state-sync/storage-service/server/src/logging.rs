@@ -39,6 +39,7 @@ pub enum LogEntry {
     OptimisticFetchRefresh,
     OptimisticFetchRequest,
     OptimisticFetchResponse,
+    PeerServingBudget,
     ReceivedCacheUpdateNotification,
     ReceivedCommitNotification,
     ReceivedStorageRequest,
@@ -14,7 +14,11 @@ use std::time::Instant;
 pub const LRU_CACHE_HIT: &str = "lru_cache_hit";
 pub const LRU_CACHE_PROBE: &str = "lru_cache_probe";
 pub const OPTIMISTIC_FETCH_ADD: &str = "optimistic_fetch_add";
+pub const OPTIMISTIC_FETCH_DEFER: &str = "optimistic_fetch_defer";
 pub const OPTIMISTIC_FETCH_EXPIRE: &str = "optimistic_fetch_expire";
+pub const PEER_BUDGET_OVER_BUDGET_RESPONSE: &str = "over_budget_response";
+pub const PEER_BUDGET_REQUEST_DROPPED: &str = "request_dropped";
+pub const PEER_BUDGET_REQUEST_QUEUED: &str = "request_queued";
 pub const RESULT_SUCCESS: &str = "success";
 pub const RESULT_FAILURE: &str = "failure";
 pub const SUBSCRIPTION_ADD: &str = "subscription_add";
@@ -90,6 +94,36 @@ pub static OPTIMISTIC_FETCH_LATENCIES: Lazy<HistogramVec> = Lazy::new(|| {
     .unwrap()
 });
 
+/// Gauge for tracking the number of peers that are over their serving budget
+pub static OVER_BUDGET_PEER_COUNT: Lazy<IntGaugeVec> = Lazy::new(|| {
+    register_int_gauge_vec!(
+        "creditchain_storage_service_server_over_budget_peer_count",
+        "Gauge for tracking the number of peers that are over their serving budget",
+        &["network_id"]
+    )
+    .unwrap()
+});
+
+/// Counter for peer serving budget events (e.g., queued and dropped requests)
+pub static PEER_BUDGET_EVENTS: Lazy<IntCounterVec> = Lazy::new(|| {
+    register_int_counter_vec!(
+        "creditchain_storage_service_server_peer_budget_event",
+        "Counters related to peer serving budget events",
+        &["network_id", "event"]
+    )
+    .unwrap()
+});
+
+/// Gauge for tracking the number of requests waiting for a peer's concurrency budget
+pub static PENDING_PEER_REQUEST_COUNT: Lazy<IntGaugeVec> = Lazy::new(|| {
+    register_int_gauge_vec!(
+        "creditchain_storage_service_server_pending_peer_request_count",
+        "Gauge for tracking the number of requests waiting for a peer's concurrency budget",
+        &["network_id"]
+    )
+    .unwrap()
+});
+
 /// Counter for pending network events to the storage service (server-side)
 pub static PENDING_STORAGE_SERVER_NETWORK_EVENTS: Lazy<IntCounterVec> = Lazy::new(|| {
     register_int_counter_vec!(
@@ -100,6 +134,16 @@ pub static PENDING_STORAGE_SERVER_NETWORK_EVENTS: Lazy<IntCounterVec> = Lazy::ne
     .unwrap()
 });
 
+/// Counter for the number of bytes served to peers
+pub static SERVED_BYTES: Lazy<IntCounterVec> = Lazy::new(|| {
+    register_int_counter_vec!(
+        "creditchain_storage_service_server_served_bytes",
+        "Counters for the number of bytes served to peers by the storage server",
+        &["network_id"]
+    )
+    .unwrap()
+});
+
 /// Counter for storage service errors encountered
 pub static STORAGE_ERRORS_ENCOUNTERED: Lazy<IntCounterVec> = Lazy::new(|| {
     register_int_counter_vec!(
@@ -227,6 +271,13 @@ pub fn increment_counter(counter: &Lazy<IntCounterVec>, network_id: NetworkId, l
         .inc();
 }
 
+/// Increments the served bytes counter for the given network
+pub fn increment_served_bytes(network_id: NetworkId, num_bytes: u64) {
+    SERVED_BYTES
+        .with_label_values(&[network_id.as_str()])
+        .inc_by(num_bytes);
+}
+
 /// Sets the gauge with the specific label and value
 pub fn set_gauge(counter: &Lazy<IntGaugeVec>, label: &str, value: u64) {
     counter.with_label_values(&[label]).set(value as i64);
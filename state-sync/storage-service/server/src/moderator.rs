@@ -1,7 +1,9 @@
 // Copyright © CreditChain Research Team
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::{error::Error, logging::LogEntry, metrics, utils, LogSchema};
+use crate::{
+    error::Error, logging::LogEntry, metrics, serving_budgets::PeerServingBudgets, utils, LogSchema,
+};
 use creditchain_config::{
     config::{CreditChainDataClientConfig, StorageServiceConfig},
     network_id::{NetworkId, PeerNetworkId},
@@ -101,10 +103,12 @@ impl UnhealthyPeerState {
 /// The request moderator is responsible for validating inbound storage
 /// requests and ensuring that only valid (and satisfiable) requests are processed.
 /// If a peer sends too many invalid requests, the moderator will mark the peer as
-/// "unhealthy" and will ignore requests from that peer for some time.
+/// "unhealthy" and will ignore requests from that peer for some time. The
+/// moderator also tracks the serving budgets of each peer (see `PeerServingBudgets`).
 pub struct RequestModerator {
     creditchain_data_client_config: CreditChainDataClientConfig,
     cached_storage_server_summary: Arc<ArcSwap<StorageServerSummary>>,
+    peer_serving_budgets: Arc<PeerServingBudgets>,
     peers_and_metadata: Arc<PeersAndMetadata>,
     storage_service_config: StorageServiceConfig,
     time_service: TimeService,
@@ -119,9 +123,15 @@ impl RequestModerator {
         storage_service_config: StorageServiceConfig,
         time_service: TimeService,
     ) -> Self {
+        let peer_serving_budgets = Arc::new(PeerServingBudgets::new(
+            storage_service_config.peer_serving_budgets,
+            time_service.clone(),
+        ));
+
         Self {
             creditchain_data_client_config,
             cached_storage_server_summary,
+            peer_serving_budgets,
             unhealthy_peer_states: Arc::new(DashMap::new()),
             peers_and_metadata,
             storage_service_config,
@@ -195,7 +205,13 @@ impl RequestModerator {
         )
     }
 
-    /// Refresh the unhealthy peer states and garbage collect disconnected peers
+    /// Returns the serving budgets of all peers
+    pub fn get_peer_serving_budgets(&self) -> Arc<PeerServingBudgets> {
+        self.peer_serving_budgets.clone()
+    }
+
+    /// Refresh the unhealthy peer states (and peer serving
+    /// budgets) and garbage collect disconnected peers.
     pub fn refresh_unhealthy_peer_states(&self) -> Result<(), Error> {
         // Get the currently connected peers
         let connected_peers_and_metadata = self
@@ -234,6 +250,10 @@ impl RequestModerator {
             num_ignored_peers,
         );
 
+        // Refresh the peer serving budgets
+        self.peer_serving_budgets
+            .refresh_peer_budgets(&connected_peers_and_metadata);
+
         Ok(())
     }
 
@@ -104,11 +104,15 @@ impl ResponseSender {
         Self { response_tx }
     }
 
-    pub fn send(self, response: Result<StorageServiceResponse>) {
+    /// Sends the response to the peer and returns the number of
+    /// bytes sent (i.e., the size of the serialized response).
+    pub fn send(self, response: Result<StorageServiceResponse>) -> u64 {
         let msg = StorageServiceMessage::Response(response);
         let result = bcs::to_bytes(&msg)
             .map(Bytes::from)
             .map_err(RpcError::BcsError);
+        let num_bytes = result.as_ref().map_or(0, |bytes| bytes.len() as u64);
         let _ = self.response_tx.send(result);
+        num_bytes
     }
 }
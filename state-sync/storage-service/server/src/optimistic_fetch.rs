@@ -4,7 +4,7 @@
 use crate::{
     error::Error,
     metrics,
-    metrics::{increment_counter, OPTIMISTIC_FETCH_EXPIRE},
+    metrics::{increment_counter, OPTIMISTIC_FETCH_DEFER, OPTIMISTIC_FETCH_EXPIRE},
     moderator::RequestModerator,
     network::ResponseSender,
     storage::StorageReaderInterface,
@@ -405,6 +405,7 @@ async fn identify_expired_invalid_and_ready_fetches<T: StorageReaderInterface>(
     Vec<(PeerNetworkId, LedgerInfoWithSignatures)>,
 ) {
     // Gather the highest synced version and epoch for each peer
+    let peer_serving_budgets = request_moderator.get_peer_serving_budgets();
     let mut peers_and_highest_synced_data = HashMap::new();
     let mut peers_with_expired_optimistic_fetches = vec![];
     for optimistic_fetch in optimistic_fetches.iter() {
@@ -412,8 +413,20 @@ async fn identify_expired_invalid_and_ready_fetches<T: StorageReaderInterface>(
         let peer_network_id = *optimistic_fetch.key();
         let optimistic_fetch = optimistic_fetch.value();
 
-        // Gather the peer's highest synced version and epoch
+        // Check if the optimistic fetch has expired
         if !optimistic_fetch.is_expired(config.max_optimistic_fetch_period_ms) {
+            // If the peer is over its serving budget, defer the optimistic fetch
+            // (i.e., it will be handled once the budget refills, or it will expire).
+            if peer_serving_budgets.is_over_budget(&peer_network_id) {
+                increment_counter(
+                    &metrics::OPTIMISTIC_FETCH_EVENTS,
+                    peer_network_id.network_id(),
+                    OPTIMISTIC_FETCH_DEFER.into(),
+                );
+                continue;
+            }
+
+            // Gather the peer's highest synced version and epoch
             let highest_known_version = optimistic_fetch.highest_known_version();
             let highest_known_epoch = optimistic_fetch.highest_known_epoch();
 
@@ -0,0 +1,328 @@
+// Copyright © CreditChain Research Team
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    logging::{LogEntry, LogSchema},
+    metrics,
+    metrics::{
+        increment_counter, PEER_BUDGET_OVER_BUDGET_RESPONSE, PEER_BUDGET_REQUEST_DROPPED,
+        PEER_BUDGET_REQUEST_QUEUED,
+    },
+    network::NetworkRequest,
+};
+use creditchain_config::{
+    config::PeerServingBudgetConfig,
+    network_id::{NetworkId, PeerNetworkId},
+};
+use creditchain_logger::{sample, sample::SampleRate, warn};
+use creditchain_network::application::metadata::PeerMetadata;
+use creditchain_storage_service_types::{
+    requests::StorageServiceRequest, responses::StorageServiceResponse, StorageServiceError,
+};
+use creditchain_time_service::{TimeService, TimeServiceTrait};
+use dashmap::{mapref::one::RefMut, DashMap};
+use mini_moka::sync::Cache;
+use std::{
+    cmp::min,
+    collections::{HashMap, VecDeque},
+    time::{Duration, Instant},
+};
+
+// The frequency to log dropped requests (secs)
+const DROPPED_REQUEST_LOG_FREQUENCY_SECS: u64 = 5;
+
+/// The serving budget of a single peer. This tracks the number of bytes
+/// the peer can still be served (using a token bucket that refills at the
+/// peer's bytes per second budget), as well as the requests that are
+/// currently being served and those waiting to be served.
+pub struct PeerServingBudget {
+    available_bytes: i64, // The bytes left to serve (negative if the peer is over budget)
+    last_refill_time: Instant, // The last time the available bytes were refilled
+    max_burst_bytes: u64, // The max bytes that can be served in a single burst
+    max_bytes_per_sec: u64, // The number of bytes (per second) that refill the budget
+    num_active_requests: u64, // The number of requests currently being served
+    pending_requests: VecDeque<NetworkRequest>, // The requests waiting to be served
+}
+
+impl PeerServingBudget {
+    pub fn new(max_bytes_per_sec: u64, max_burst_secs: u64, time_service: &TimeService) -> Self {
+        let max_burst_bytes = max_bytes_per_sec.saturating_mul(max_burst_secs);
+        Self {
+            available_bytes: i64::try_from(max_burst_bytes).unwrap_or(i64::MAX),
+            last_refill_time: time_service.now(),
+            max_burst_bytes,
+            max_bytes_per_sec,
+            num_active_requests: 0,
+            pending_requests: VecDeque::new(),
+        }
+    }
+
+    /// Returns true iff the peer has exhausted its serving budget
+    pub fn is_over_budget(&self) -> bool {
+        self.available_bytes <= 0
+    }
+
+    /// Refills the available bytes according to the time elapsed since the last refill
+    fn refill_available_bytes(&mut self, time_now: Instant) {
+        // Calculate the number of bytes to refill
+        let elapsed_time = time_now.saturating_duration_since(self.last_refill_time);
+        let refill_bytes = elapsed_time.as_micros() * self.max_bytes_per_sec as u128
+            / Duration::from_secs(1).as_micros();
+        if refill_bytes == 0 {
+            return; // Not enough time has elapsed (we avoid losing partial refills)
+        }
+
+        // Refill the available bytes (up to the max burst)
+        let refill_bytes = i64::try_from(refill_bytes).unwrap_or(i64::MAX);
+        let max_burst_bytes = i64::try_from(self.max_burst_bytes).unwrap_or(i64::MAX);
+        self.available_bytes = min(
+            self.available_bytes.saturating_add(refill_bytes),
+            max_burst_bytes,
+        );
+        self.last_refill_time = time_now;
+    }
+
+    /// Consumes the given number of bytes from the available bytes
+    fn consume_bytes(&mut self, num_bytes: u64) {
+        let num_bytes = i64::try_from(num_bytes).unwrap_or(i64::MAX);
+        self.available_bytes = self.available_bytes.saturating_sub(num_bytes);
+    }
+}
+
+/// The serving budgets for all peers. These ensure that the storage service
+/// shares its bandwidth (and request handling threads) fairly between peers,
+/// and that a few syncing peers cannot saturate the node.
+pub struct PeerServingBudgets {
+    config: PeerServingBudgetConfig,
+    // The responses served to peers over budget (keyed by the max network chunk bytes)
+    over_budget_response_cache: Cache<(u64, StorageServiceRequest), StorageServiceResponse>,
+    peer_budgets: DashMap<PeerNetworkId, PeerServingBudget>,
+    time_service: TimeService,
+}
+
+impl PeerServingBudgets {
+    pub fn new(config: PeerServingBudgetConfig, time_service: TimeService) -> Self {
+        Self {
+            config,
+            over_budget_response_cache: Cache::new(config.max_over_budget_lru_cache_size),
+            peer_budgets: DashMap::new(),
+            time_service,
+        }
+    }
+
+    /// Starts serving the given request if the peer has not reached its max
+    /// number of concurrent requests, and returns the request to the caller.
+    /// Otherwise, the request is queued until one of the peer's active requests
+    /// completes (or dropped if the peer already has too many pending requests).
+    pub fn start_or_queue_request(
+        &self,
+        network_request: NetworkRequest,
+    ) -> Option<NetworkRequest> {
+        // If peer serving budgets are disabled, serve the request immediately
+        if !self.config.enable_peer_serving_budgets {
+            return Some(network_request);
+        }
+
+        // Get the serving budget for the peer
+        let peer_network_id = network_request.peer_network_id;
+        let mut peer_budget = self.get_or_create_peer_budget(&peer_network_id);
+
+        // If the peer can have more requests in flight, start serving the request
+        if peer_budget.num_active_requests < self.config.max_concurrent_requests_per_peer {
+            peer_budget.num_active_requests += 1;
+            return Some(network_request);
+        }
+
+        // Otherwise, queue the request (if the peer has room for more pending requests)
+        let num_pending_requests = peer_budget.pending_requests.len() as u64;
+        if num_pending_requests < self.config.max_pending_requests_per_peer {
+            peer_budget.pending_requests.push_back(network_request);
+            increment_counter(
+                &metrics::PEER_BUDGET_EVENTS,
+                peer_network_id.network_id(),
+                PEER_BUDGET_REQUEST_QUEUED.into(),
+            );
+            return None;
+        }
+        drop(peer_budget);
+
+        // The peer has too many pending requests, so we drop the request
+        increment_counter(
+            &metrics::PEER_BUDGET_EVENTS,
+            peer_network_id.network_id(),
+            PEER_BUDGET_REQUEST_DROPPED.into(),
+        );
+        sample!(
+            SampleRate::Duration(Duration::from_secs(DROPPED_REQUEST_LOG_FREQUENCY_SECS)),
+            warn!(LogSchema::new(LogEntry::PeerServingBudget)
+                .peer_network_id(&peer_network_id)
+                .request(&network_request.storage_service_request)
+                .message("Dropping request! The peer has too many pending requests.")
+            );
+        );
+        network_request
+            .response_sender
+            .send(Err(StorageServiceError::InternalError(
+                "The peer has too many pending requests! Request dropped.".into(),
+            )));
+        None
+    }
+
+    /// Marks one of the peer's active requests as complete. If the peer has
+    /// pending requests, the next request is started and returned to the caller.
+    pub fn complete_request(&self, peer_network_id: &PeerNetworkId) -> Option<NetworkRequest> {
+        // If peer serving budgets are disabled, there's nothing to do
+        if !self.config.enable_peer_serving_budgets {
+            return None;
+        }
+
+        // Get the serving budget for the peer (it may have been garbage collected)
+        let mut peer_budget = self.peer_budgets.get_mut(peer_network_id)?;
+
+        // Start serving the next pending request (if any)
+        match peer_budget.pending_requests.pop_front() {
+            Some(network_request) => Some(network_request),
+            None => {
+                peer_budget.num_active_requests = peer_budget.num_active_requests.saturating_sub(1);
+                None
+            },
+        }
+    }
+
+    /// Records the given number of bytes as served to the peer
+    pub fn record_served_bytes(&self, peer_network_id: &PeerNetworkId, num_bytes: u64) {
+        // Update the served bytes metrics for the peer class
+        metrics::increment_served_bytes(peer_network_id.network_id(), num_bytes);
+
+        // Consume the bytes from the peer's serving budget
+        if self.config.enable_peer_serving_budgets {
+            self.get_or_create_peer_budget(peer_network_id)
+                .consume_bytes(num_bytes);
+        }
+    }
+
+    /// Returns true iff the peer has exhausted its serving budget
+    pub fn is_over_budget(&self, peer_network_id: &PeerNetworkId) -> bool {
+        // If peer serving budgets are disabled, no peer is ever over budget
+        if !self.config.enable_peer_serving_budgets {
+            return false;
+        }
+
+        // Refill the peer's budget and check if the peer is over budget
+        match self.peer_budgets.get_mut(peer_network_id) {
+            Some(mut peer_budget) => {
+                peer_budget.refill_available_bytes(self.time_service.now());
+                peer_budget.is_over_budget()
+            },
+            None => false, // The peer hasn't been served anything yet
+        }
+    }
+
+    /// Returns the max network chunk bytes to use when serving the peer, if the
+    /// peer is over budget. Otherwise, None is returned (i.e., no limit applies).
+    pub fn get_over_budget_max_network_chunk_bytes(
+        &self,
+        peer_network_id: &PeerNetworkId,
+    ) -> Option<u64> {
+        if self.is_over_budget(peer_network_id) {
+            increment_counter(
+                &metrics::PEER_BUDGET_EVENTS,
+                peer_network_id.network_id(),
+                PEER_BUDGET_OVER_BUDGET_RESPONSE.into(),
+            );
+            Some(self.config.over_budget_max_network_chunk_bytes)
+        } else {
+            None
+        }
+    }
+
+    /// Returns the LRU cache of responses served to peers over budget. These
+    /// are cached separately, as they are smaller than regular responses.
+    pub fn get_over_budget_response_cache(
+        &self,
+    ) -> Cache<(u64, StorageServiceRequest), StorageServiceResponse> {
+        self.over_budget_response_cache.clone()
+    }
+
+    /// Garbage collects the budgets of disconnected peers, refreshes
+    /// the budgets of connected peers and updates the budget metrics.
+    pub fn refresh_peer_budgets(
+        &self,
+        connected_peers_and_metadata: &HashMap<PeerNetworkId, PeerMetadata>,
+    ) {
+        // Remove disconnected peers and refresh the budgets of connected peers
+        let time_now = self.time_service.now();
+        let mut num_over_budget_peers: HashMap<NetworkId, u64> = HashMap::new();
+        let mut num_pending_requests: HashMap<NetworkId, u64> = HashMap::new();
+        self.peer_budgets.retain(|peer_network_id, peer_budget| {
+            if connected_peers_and_metadata.contains_key(peer_network_id) {
+                // Refresh the peer's budget
+                peer_budget.refill_available_bytes(time_now);
+
+                // Update the over budget peers and pending requests
+                let network_id = peer_network_id.network_id();
+                if peer_budget.is_over_budget() {
+                    *num_over_budget_peers.entry(network_id).or_default() += 1;
+                }
+                *num_pending_requests.entry(network_id).or_default() +=
+                    peer_budget.pending_requests.len() as u64;
+
+                true // The peer is still connected, so we should keep it
+            } else {
+                false // The peer is no longer connected, so we should remove it
+            }
+        });
+
+        // Update the budget metrics for each peer class
+        for network_id in [NetworkId::Validator, NetworkId::Vfn, NetworkId::Public] {
+            metrics::set_gauge(
+                &metrics::OVER_BUDGET_PEER_COUNT,
+                network_id.as_str(),
+                num_over_budget_peers.get(&network_id).copied().unwrap_or(0),
+            );
+            metrics::set_gauge(
+                &metrics::PENDING_PEER_REQUEST_COUNT,
+                network_id.as_str(),
+                num_pending_requests.get(&network_id).copied().unwrap_or(0),
+            );
+        }
+    }
+
+    /// Returns the serving budget for the peer (creating a new one if none exists)
+    fn get_or_create_peer_budget(
+        &self,
+        peer_network_id: &PeerNetworkId,
+    ) -> RefMut<'_, PeerNetworkId, PeerServingBudget> {
+        self.peer_budgets
+            .entry(*peer_network_id)
+            .or_insert_with(|| {
+                let max_bytes_per_sec = match peer_network_id.network_id() {
+                    NetworkId::Validator => self.config.max_validator_peer_bytes_per_sec,
+                    NetworkId::Vfn => self.config.max_vfn_peer_bytes_per_sec,
+                    NetworkId::Public => self.config.max_public_peer_bytes_per_sec,
+                };
+                PeerServingBudget::new(
+                    max_bytes_per_sec,
+                    self.config.max_burst_secs,
+                    &self.time_service,
+                )
+            })
+    }
+
+    #[cfg(test)]
+    /// Returns the number of active and pending requests for the peer
+    pub(crate) fn get_num_active_and_pending_requests(
+        &self,
+        peer_network_id: &PeerNetworkId,
+    ) -> (u64, u64) {
+        self.peer_budgets
+            .get(peer_network_id)
+            .map(|peer_budget| {
+                (
+                    peer_budget.num_active_requests,
+                    peer_budget.pending_requests.len() as u64,
+                )
+            })
+            .unwrap_or_default()
+    }
+}
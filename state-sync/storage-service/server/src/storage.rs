@@ -99,6 +99,11 @@ pub trait StorageReaderInterface: Clone + Send + 'static {
         start_index: u64,
         end_index: u64,
     ) -> creditchain_storage_service_types::Result<StateValueChunkWithProof, Error>;
+
+    /// Returns a copy of the storage reader that limits each response to
+    /// `max_network_chunk_bytes` (or the configured limit, if it is smaller).
+    /// This is used to serve smaller chunks to peers over their serving budget.
+    fn with_max_network_chunk_bytes(&self, max_network_chunk_bytes: u64) -> Self;
 }
 
 /// The underlying implementation of the StorageReaderInterface, used by the
@@ -558,6 +563,17 @@ impl StorageReaderInterface for StorageReader {
             version, start_index, end_index
         )))
     }
+
+    fn with_max_network_chunk_bytes(&self, max_network_chunk_bytes: u64) -> Self {
+        let mut config = self.config;
+        config.max_network_chunk_bytes =
+            min(config.max_network_chunk_bytes, max_network_chunk_bytes);
+
+        Self {
+            config,
+            storage: self.storage.clone(),
+        }
+    }
 }
 
 // A simple macro that wraps each storage read call with a timer
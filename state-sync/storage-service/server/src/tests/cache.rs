@@ -2,7 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::tests::{mock, mock::MockClient, utils};
-use creditchain_config::config::StorageServiceConfig;
+use creditchain_config::config::{PeerServingBudgetConfig, StorageServiceConfig};
 use creditchain_crypto::hash::HashValue;
 use creditchain_types::{
     proof::definition::SparseMerkleRangeProof, state_store::state_value::StateValueChunkWithProof,
@@ -155,6 +155,72 @@ async fn test_cachable_requests_data_versions() {
     }
 }
 
+#[tokio::test]
+async fn test_cachable_requests_over_budget() {
+    // Create test data
+    let start_version = 0;
+    let end_version = 454;
+    let proof_version = end_version;
+    let include_events = false;
+    let use_request_v2 = false;
+
+    // Create the mock db reader
+    let mut db_reader = mock::create_mock_db_reader();
+    let transaction_list_with_proof = utils::create_transaction_list_with_proof(
+        start_version,
+        end_version,
+        proof_version,
+        include_events,
+        use_request_v2,
+    );
+
+    // Expect the data to be fetched from storage exactly once
+    utils::expect_get_transactions(
+        &mut db_reader,
+        start_version,
+        end_version - start_version + 1,
+        proof_version,
+        include_events,
+        transaction_list_with_proof.clone(),
+    );
+
+    // Create a storage service config where all peers are always over budget
+    let storage_config = StorageServiceConfig {
+        peer_serving_budgets: PeerServingBudgetConfig {
+            enable_peer_serving_budgets: true,
+            max_burst_secs: 0,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    // Create the storage client and server
+    let (mut mock_client, mut service, _, _, _) =
+        MockClient::new(Some(db_reader), Some(storage_config));
+    utils::update_storage_server_summary(&mut service, end_version, 10);
+    tokio::spawn(service.start());
+
+    // Repeatedly fetch the data and verify the responses are served from the cache
+    for _ in 0..10 {
+        let response = utils::get_transactions_with_proof(
+            &mut mock_client,
+            start_version,
+            end_version,
+            proof_version,
+            include_events,
+            true,
+            use_request_v2,
+        )
+        .await
+        .unwrap();
+        utils::verify_transaction_with_proof_response(
+            use_request_v2,
+            transaction_list_with_proof.clone(),
+            response,
+        );
+    }
+}
+
 #[tokio::test]
 async fn test_cachable_requests_eviction() {
     // Create test data
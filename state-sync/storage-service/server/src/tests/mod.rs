@@ -11,6 +11,7 @@ mod number_of_states;
 mod optimistic_fetch;
 mod protocol_version;
 mod request_moderator;
+mod serving_budgets;
 mod state_values;
 mod storage_summary;
 mod subscribe_transaction_outputs;
@@ -0,0 +1,213 @@
+// Copyright © CreditChain Research Team
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    network::{NetworkRequest, ResponseSender},
+    serving_budgets::PeerServingBudgets,
+};
+use creditchain_config::{
+    config::PeerServingBudgetConfig,
+    network_id::{NetworkId, PeerNetworkId},
+};
+use creditchain_network::protocols::{network::RpcError, wire::handshake::v1::ProtocolId};
+use creditchain_storage_service_types::{
+    requests::{DataRequest, StorageServiceRequest},
+    StorageServiceError, StorageServiceMessage,
+};
+use creditchain_time_service::TimeService;
+use creditchain_types::PeerId;
+use bytes::Bytes;
+use claims::assert_matches;
+use futures::channel::oneshot;
+use std::time::Duration;
+
+#[test]
+fn test_concurrent_requests_budget() {
+    // Create the peer serving budgets
+    let max_concurrent_requests_per_peer = 3;
+    let max_pending_requests_per_peer = 2;
+    let peer_budget_config = PeerServingBudgetConfig {
+        enable_peer_serving_budgets: true,
+        max_concurrent_requests_per_peer,
+        max_pending_requests_per_peer,
+        ..Default::default()
+    };
+    let peer_serving_budgets = PeerServingBudgets::new(peer_budget_config, TimeService::mock());
+
+    // Start the max number of concurrent requests and verify they are all served
+    let peer_network_id = PeerNetworkId::new(NetworkId::Public, PeerId::random());
+    for _ in 0..max_concurrent_requests_per_peer {
+        let (network_request, _) = create_network_request(peer_network_id);
+        assert!(peer_serving_budgets
+            .start_or_queue_request(network_request)
+            .is_some());
+    }
+
+    // Send more requests and verify they are queued
+    for _ in 0..max_pending_requests_per_peer {
+        let (network_request, _) = create_network_request(peer_network_id);
+        assert!(peer_serving_budgets
+            .start_or_queue_request(network_request)
+            .is_none());
+    }
+    verify_active_and_pending_requests(
+        &peer_serving_budgets,
+        &peer_network_id,
+        max_concurrent_requests_per_peer,
+        max_pending_requests_per_peer,
+    );
+
+    // Send another request and verify it is dropped (with an error response)
+    let (network_request, response_receiver) = create_network_request(peer_network_id);
+    assert!(peer_serving_budgets
+        .start_or_queue_request(network_request)
+        .is_none());
+    verify_dropped_request_response(response_receiver);
+
+    // Verify that requests from other peers are still served
+    let other_peer_network_id = PeerNetworkId::new(NetworkId::Public, PeerId::random());
+    let (network_request, _) = create_network_request(other_peer_network_id);
+    assert!(peer_serving_budgets
+        .start_or_queue_request(network_request)
+        .is_some());
+
+    // Complete the active requests and verify the pending requests are served (in order)
+    for _ in 0..max_pending_requests_per_peer {
+        assert!(peer_serving_budgets
+            .complete_request(&peer_network_id)
+            .is_some());
+    }
+    verify_active_and_pending_requests(
+        &peer_serving_budgets,
+        &peer_network_id,
+        max_concurrent_requests_per_peer,
+        0,
+    );
+
+    // Complete all remaining requests and verify the peer has no active requests
+    for _ in 0..max_concurrent_requests_per_peer {
+        assert!(peer_serving_budgets
+            .complete_request(&peer_network_id)
+            .is_none());
+    }
+    verify_active_and_pending_requests(&peer_serving_budgets, &peer_network_id, 0, 0);
+}
+
+#[test]
+fn test_bytes_per_second_budget() {
+    // Create the peer serving budgets
+    let max_public_peer_bytes_per_sec = 1000;
+    let max_burst_secs = 2;
+    let over_budget_max_network_chunk_bytes = 100;
+    let peer_budget_config = PeerServingBudgetConfig {
+        enable_peer_serving_budgets: true,
+        max_burst_secs,
+        max_public_peer_bytes_per_sec,
+        over_budget_max_network_chunk_bytes,
+        ..Default::default()
+    };
+    let time_service = TimeService::mock();
+    let peer_serving_budgets = PeerServingBudgets::new(peer_budget_config, time_service.clone());
+
+    // Serve the peer its entire burst and verify it is not yet over budget
+    let peer_network_id = PeerNetworkId::new(NetworkId::Public, PeerId::random());
+    peer_serving_budgets
+        .record_served_bytes(&peer_network_id, max_public_peer_bytes_per_sec * max_burst_secs - 1);
+    assert!(!peer_serving_budgets.is_over_budget(&peer_network_id));
+    assert!(peer_serving_budgets
+        .get_over_budget_max_network_chunk_bytes(&peer_network_id)
+        .is_none());
+
+    // Serve the peer more data and verify it is now over budget
+    peer_serving_budgets.record_served_bytes(&peer_network_id, 1500);
+    assert!(peer_serving_budgets.is_over_budget(&peer_network_id));
+    assert_eq!(
+        peer_serving_budgets.get_over_budget_max_network_chunk_bytes(&peer_network_id),
+        Some(over_budget_max_network_chunk_bytes)
+    );
+
+    // Verify that peers on other networks are unaffected
+    let vfn_peer_network_id = PeerNetworkId::new(NetworkId::Vfn, PeerId::random());
+    peer_serving_budgets.record_served_bytes(&vfn_peer_network_id, 1500);
+    assert!(!peer_serving_budgets.is_over_budget(&vfn_peer_network_id));
+
+    // Elapse a second and verify the peer is still over budget
+    let time_service = time_service.into_mock();
+    time_service.advance(Duration::from_secs(1));
+    assert!(peer_serving_budgets.is_over_budget(&peer_network_id));
+
+    // Elapse another second and verify the peer is no longer over budget
+    time_service.advance(Duration::from_secs(1));
+    assert!(!peer_serving_budgets.is_over_budget(&peer_network_id));
+}
+
+#[test]
+fn test_disabled_budgets() {
+    // Create the peer serving budgets (with budgets disabled)
+    let peer_budget_config = PeerServingBudgetConfig {
+        enable_peer_serving_budgets: false,
+        max_concurrent_requests_per_peer: 1,
+        max_public_peer_bytes_per_sec: 1,
+        ..Default::default()
+    };
+    let peer_serving_budgets = PeerServingBudgets::new(peer_budget_config, TimeService::mock());
+
+    // Send many requests and verify they are all served immediately
+    let peer_network_id = PeerNetworkId::new(NetworkId::Public, PeerId::random());
+    for _ in 0..10 {
+        let (network_request, _) = create_network_request(peer_network_id);
+        assert!(peer_serving_budgets
+            .start_or_queue_request(network_request)
+            .is_some());
+    }
+
+    // Serve the peer lots of data and verify it is never over budget
+    peer_serving_budgets.record_served_bytes(&peer_network_id, 1_000_000);
+    assert!(!peer_serving_budgets.is_over_budget(&peer_network_id));
+    assert!(peer_serving_budgets
+        .get_over_budget_max_network_chunk_bytes(&peer_network_id)
+        .is_none());
+}
+
+/// Creates a network request for the given peer and returns
+/// the request alongside the receiver for the response.
+fn create_network_request(
+    peer_network_id: PeerNetworkId,
+) -> (NetworkRequest, oneshot::Receiver<Result<Bytes, RpcError>>) {
+    let (response_sender, response_receiver) = oneshot::channel();
+    let network_request = NetworkRequest {
+        peer_network_id,
+        protocol_id: ProtocolId::StorageServiceRpc,
+        storage_service_request: StorageServiceRequest::new(
+            DataRequest::GetStorageServerSummary,
+            false,
+        ),
+        response_sender: ResponseSender::new(response_sender),
+    };
+    (network_request, response_receiver)
+}
+
+/// Verifies the number of active and pending requests for the peer
+fn verify_active_and_pending_requests(
+    peer_serving_budgets: &PeerServingBudgets,
+    peer_network_id: &PeerNetworkId,
+    expected_num_active_requests: u64,
+    expected_num_pending_requests: u64,
+) {
+    assert_eq!(
+        peer_serving_budgets.get_num_active_and_pending_requests(peer_network_id),
+        (expected_num_active_requests, expected_num_pending_requests)
+    );
+}
+
+/// Verifies that the response for a dropped request is an internal error
+fn verify_dropped_request_response(
+    mut response_receiver: oneshot::Receiver<Result<Bytes, RpcError>>,
+) {
+    let response_bytes = response_receiver.try_recv().unwrap().unwrap().unwrap();
+    let response_message = bcs::from_bytes::<StorageServiceMessage>(&response_bytes).unwrap();
+    assert_matches!(
+        response_message,
+        StorageServiceMessage::Response(Err(StorageServiceError::InternalError(_)))
+    );
+}
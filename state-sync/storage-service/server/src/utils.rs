@@ -187,7 +187,12 @@ pub fn notify_peer_of_new_data<T: StorageReaderInterface>(
         };
 
     // Send the response to the peer
-    handler.send_response(missing_data_request, Ok(storage_response), response_sender);
+    handler.send_response(
+        peer_network_id,
+        missing_data_request,
+        Ok(storage_response),
+        response_sender,
+    );
 
     Ok(transformed_data_response)
 }
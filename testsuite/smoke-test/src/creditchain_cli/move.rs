@@ -159,3 +159,68 @@ async fn test_move_publish_flow() {
         ),
     };
 }
+
+#[tokio::test]
+async fn test_move_check_upgrade_flow() {
+    let (_swarm, mut cli, _faucet) = SwarmBuilder::new_local(1)
+        .with_libra2()
+        .build_with_cli(1)
+        .await;
+
+    let account = cli.account_id(0).to_hex_literal();
+    cli.init_move_dir();
+    let mut package_addresses = BTreeMap::new();
+    package_addresses.insert(HELLO_BLOCKCHAIN, "_");
+    cli.init_package(
+        PACKAGE_NAME.to_string(),
+        package_addresses,
+        Some(creditchain_framework_dir()),
+    )
+    .await
+    .expect("Should succeed");
+    cli.add_move_files();
+    let upgrade_module = cli.move_dir().join("sources").join("upgrade.move");
+    std::fs::write(
+        &upgrade_module,
+        "module hello_blockchain::upgrade { public fun value(): u64 { 1 } }",
+    )
+    .unwrap();
+
+    cli.wait_for_account(0)
+        .await
+        .expect("Should create account");
+    let mut named_addresses = BTreeMap::new();
+    named_addresses.insert(HELLO_BLOCKCHAIN, account.as_str());
+    cli.publish_package(0, None, named_addresses.clone(), None)
+        .await
+        .expect("Should publish package");
+
+    // Changing a function body and adding a function is compatible
+    std::fs::write(
+        &upgrade_module,
+        "module hello_blockchain::upgrade {
+            public fun value(): u64 { 2 }
+            public fun other_value(): u64 { 3 }
+        }",
+    )
+    .unwrap();
+    let result = cli
+        .check_upgrade(0, named_addresses.clone())
+        .await
+        .expect("Upgrade should be compatible");
+    assert!(result.contains("is compatible"), "{}", result);
+
+    // Changing the signature of a public function is not
+    std::fs::write(
+        &upgrade_module,
+        "module hello_blockchain::upgrade { public fun value(x: u64): u64 { x } }",
+    )
+    .unwrap();
+    let err = cli
+        .check_upgrade(0, named_addresses)
+        .await
+        .expect_err("Upgrade should be incompatible")
+        .to_string();
+    assert!(err.contains("found 1 breaking change(s)"), "{}", err);
+    assert!(err.contains("::upgrade`"), "{}", err);
+}
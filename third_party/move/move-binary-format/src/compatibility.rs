@@ -90,12 +90,37 @@ impl Compatibility {
     }
 
     /// Check compatibility for `new_module` relative to old module `old_module`.
-    #[allow(clippy::nonminimal_bool)] // simplification is more unreadable
     pub fn check(
         &self,
         old_module: &CompiledModule,
         new_module: &CompiledModule,
     ) -> PartialVMResult<()> {
+        let errors = self.incompatibilities(old_module, new_module);
+        if !errors.is_empty() {
+            Err(
+                PartialVMError::new(StatusCode::BACKWARD_INCOMPATIBLE_MODULE_UPDATE).with_message(
+                    format!(
+                        "Module update failure: new module not compatible with \
+                        existing module in `{}`: {}",
+                        old_module.self_id(),
+                        errors.join(", ")
+                    ),
+                ),
+            )
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Returns a description of every incompatibility of `new_module` relative to old module
+    /// `old_module`, e.g. "removed function `foo`". The modules are compatible if the result is
+    /// empty.
+    #[allow(clippy::nonminimal_bool)] // simplification is more unreadable
+    pub fn incompatibilities(
+        &self,
+        old_module: &CompiledModule,
+        new_module: &CompiledModule,
+    ) -> Vec<String> {
         let mut errors = vec![];
 
         // module's name and address are unchanged
@@ -121,7 +146,7 @@ impl Compatibility {
                     // Also, struct layout cannot be guaranteed transitively, because after
                     // removing the struct, it could be re-added later with a different layout.
                     errors.push(format!("removed struct `{}`", old_struct.name()));
-                    break;
+                    continue;
                 },
             };
 
@@ -284,20 +309,7 @@ impl Compatibility {
             }
         }
 
-        if !errors.is_empty() {
-            Err(
-                PartialVMError::new(StatusCode::BACKWARD_INCOMPATIBLE_MODULE_UPDATE).with_message(
-                    format!(
-                        "Module update failure: new module not compatible with \
-                        existing module in `{}`: {}",
-                        old_view.id(),
-                        errors.join(", ")
-                    ),
-                ),
-            )
-        } else {
-            Ok(())
-        }
+        errors
     }
 
     // When upgrading, the new abilities must be a superset of the old abilities.
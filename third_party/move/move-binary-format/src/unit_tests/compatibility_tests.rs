@@ -120,3 +120,22 @@ fn deprecated_add_script_visibility() {
         .check(&friend_module, &script_module)
         .is_err());
 }
+
+#[test]
+fn report_all_incompatibilities() {
+    let public_module = mk_module(Visibility::Public as u8);
+    assert!(Compatibility::full_check()
+        .incompatibilities(&public_module, &public_module)
+        .is_empty());
+
+    // public -> private, and module renamed
+    let mut module = mk_module(Visibility::Private as u8);
+    module.identifiers[0] = Identifier::new("N").unwrap();
+    assert_eq!(
+        Compatibility::full_check().incompatibilities(&public_module, &module),
+        vec![
+            "module name changed to `N`".to_string(),
+            "changed visibility of function `fn`".to_string(),
+        ]
+    );
+}
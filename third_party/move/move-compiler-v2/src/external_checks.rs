@@ -4,6 +4,8 @@
 //! This module contains the interface for externally specified checks
 //! that can be run by the Move compiler.
 
+use crate::Options;
+use codespan_reporting::diagnostic::Severity;
 use legacy_move_compiler::shared::known_attributes::LintAttribute;
 use move_model::{
     ast::ExpData,
@@ -22,6 +24,25 @@ pub trait ExternalChecks {
 
     /// Get all the stackless bytecode checkers.
     fn get_stackless_bytecode_checkers(&self) -> Vec<Box<dyn StacklessBytecodeChecker>>;
+
+    /// Get the names of all the checkers known to this collection, including the ones that
+    /// are not run under the current configuration, so that they can still be skipped.
+    fn get_known_checker_names(&self) -> BTreeSet<String> {
+        let mut names = BTreeSet::new();
+        for checker in self.get_exp_checkers() {
+            names.insert(checker.get_name());
+        }
+        for checker in self.get_stackless_bytecode_checkers() {
+            names.insert(checker.get_name());
+        }
+        names
+    }
+
+    /// Returns `true` if the reports of the checker `checker_name` should be errors
+    /// instead of warnings.
+    fn is_denied(&self, _checker_name: &str) -> bool {
+        false
+    }
 }
 
 impl fmt::Debug for dyn ExternalChecks {
@@ -83,26 +104,33 @@ pub trait StacklessBytecodeChecker {
 
 /// Get the set of known checker names from the given external checkers.
 pub fn known_checker_names(external_checkers: &Vec<Arc<dyn ExternalChecks>>) -> BTreeSet<String> {
-    let mut names = BTreeSet::new();
-    for checkers in external_checkers {
-        for checker in checkers.get_exp_checkers() {
-            names.insert(checker.get_name());
-        }
-        for checker in checkers.get_stackless_bytecode_checkers() {
-            names.insert(checker.get_name());
-        }
-    }
-    names
+    external_checkers
+        .iter()
+        .flat_map(|checkers| checkers.get_known_checker_names())
+        .collect()
 }
 
 /// Report the `msg` highlighting the `loc` for the `checker_name`.
+/// The report is an error if any of the external checks deny the checker.
 fn report(env: &GlobalEnv, loc: &Loc, msg: &str, checker_name: &str) {
-    env.lint_diag_with_notes(loc, msg, vec![
+    let denied = env.get_extension::<Options>().is_some_and(|options| {
+        options
+            .external_checks
+            .iter()
+            .any(|checks| checks.is_denied(checker_name))
+    });
+    let (severity, kind) = if denied {
+        (Severity::Error, "error")
+    } else {
+        (Severity::Warning, "warning")
+    };
+    env.lint_diag_with_severity_and_notes(severity, loc, msg, vec![
         format!(
-        "To suppress this warning, annotate the function/module with the attribute `#[{}({})]`.",
-        LintAttribute::SKIP,
-        checker_name
-    ),
+            "To suppress this {}, annotate the function/module with the attribute `#[{}({})]`.",
+            kind,
+            LintAttribute::SKIP,
+            checker_name
+        ),
         format!(
             "For more information, see {}#{}.",
             LINTER_URL_BASE, checker_name
@@ -1008,8 +1008,19 @@ impl GlobalEnv {
 
     /// Add a lint warning to this environment, with the `msg` and `notes`.
     pub fn lint_diag_with_notes(&self, loc: &Loc, msg: &str, notes: Vec<String>) {
+        self.lint_diag_with_severity_and_notes(Severity::Warning, loc, msg, notes)
+    }
+
+    /// Add a lint diagnostic of given severity to this environment, with the `msg` and `notes`.
+    pub fn lint_diag_with_severity_and_notes(
+        &self,
+        severity: Severity,
+        loc: &Loc,
+        msg: &str,
+        notes: Vec<String>,
+    ) {
         let lint_msg = format!("[lint] {}", msg);
-        self.diag_with_notes(Severity::Warning, loc, &lint_msg, notes)
+        self.diag_with_notes(severity, loc, &lint_msg, notes)
     }
 
     /// Adds a diagnostic of given severity to this environment, with notes.
@@ -0,0 +1,622 @@
+// Copyright (c) The Move Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Exports source-level line and branch coverage in formats understood by coverage dashboards:
+//! LCOV tracefiles and Cobertura XML reports.
+//!
+//! Bytecode coverage is mapped back to source lines via the source map of each module. A line is
+//! hit as many times as the most executed instruction starting on it. Every conditional branch
+//! instruction contributes two outcomes. Only instructions are counted, so the outcomes are
+//! derived from a destination which can only be reached through the branch: its executions are
+//! exactly the ones of its outcome, and the other outcome makes up the remaining executions of
+//! the branch. If both destinations are join points (e.g., loop headers), each outcome is
+//! approximated by the executions of its destination, capped at the executions of the branch,
+//! and the branch is marked as approximate.
+
+#![forbid(unsafe_code)]
+
+use crate::coverage_map::ExecCoverageMap;
+use anyhow::{bail, Result};
+use clap::ValueEnum;
+use move_binary_format::{
+    access::ModuleAccess,
+    file_format::{Bytecode, CodeOffset, FunctionDefinitionIndex},
+    CompiledModule,
+};
+use move_bytecode_source_map::source_map::SourceMap;
+use serde::Serialize;
+use std::{
+    collections::BTreeMap,
+    fmt::{Display, Formatter},
+    fs,
+    io::{self, Write},
+    path::{Path, PathBuf},
+    str::FromStr,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+/// Format of an exported coverage report.
+#[derive(ValueEnum, Clone, Copy, Debug, Serialize, PartialEq, Eq)]
+pub enum ExportFormat {
+    /// LCOV tracefile
+    Lcov,
+    /// Cobertura XML report
+    Cobertura,
+}
+
+impl Display for ExportFormat {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        use ExportFormat::*;
+        match self {
+            Lcov => f.write_str("lcov"),
+            Cobertura => f.write_str("cobertura"),
+        }
+    }
+}
+
+impl FromStr for ExportFormat {
+    type Err = &'static str;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        use ExportFormat::*;
+        match s {
+            "lcov" => Ok(Lcov),
+            "cobertura" => Ok(Cobertura),
+            _ => Err("unknown variant"),
+        }
+    }
+}
+
+/// Coverage of a single function.
+#[derive(Clone, Debug, Serialize)]
+pub struct FunctionLineCoverage {
+    pub name: String,
+    /// Line of the function definition, starting at 1.
+    pub line: usize,
+    /// Number of times the function was entered.
+    pub hits: u64,
+}
+
+/// Coverage of the two outcomes of a conditional branch instruction.
+#[derive(Clone, Debug, Serialize)]
+pub struct BranchCoverage {
+    /// Line of the branch instruction, starting at 1.
+    pub line: usize,
+    /// Number of times the branch instruction was executed.
+    pub executions: u64,
+    /// Number of times the branch jumped to its destination.
+    pub taken: u64,
+    /// Number of times the branch fell through to the next instruction.
+    pub not_taken: u64,
+    /// Whether `taken` and `not_taken` are upper bounds rather than exact counts, because both
+    /// destinations of the branch can also be reached from elsewhere.
+    pub approximate: bool,
+}
+
+impl BranchCoverage {
+    /// Computes the coverage of the branch at `offset` jumping to `target`, from the number of
+    /// predecessors and executions of every instruction of the function.
+    fn new(
+        line: usize,
+        offset: CodeOffset,
+        target: CodeOffset,
+        num_predecessors: &[usize],
+        exec_count: impl Fn(CodeOffset) -> u64,
+    ) -> Self {
+        let executions = exec_count(offset);
+        let fall_through = offset + 1;
+        let is_exclusive = |destination: CodeOffset| {
+            target != fall_through && num_predecessors[destination as usize] == 1
+        };
+        let (taken, not_taken, approximate) = if is_exclusive(fall_through) {
+            let not_taken = exec_count(fall_through).min(executions);
+            (executions - not_taken, not_taken, false)
+        } else if is_exclusive(target) {
+            let taken = exec_count(target).min(executions);
+            (taken, executions - taken, false)
+        } else {
+            (
+                exec_count(target).min(executions),
+                exec_count(fall_through).min(executions),
+                true,
+            )
+        };
+        Self {
+            line,
+            executions,
+            taken,
+            not_taken,
+            approximate,
+        }
+    }
+}
+
+/// Line and branch coverage of a module, mapped back to its source file.
+#[derive(Clone, Debug, Serialize)]
+pub struct ModuleLineCoverage {
+    /// Name of the package the module belongs to.
+    pub package_name: String,
+    pub module_name: String,
+    pub source_path: PathBuf,
+    pub functions: Vec<FunctionLineCoverage>,
+    /// Hit counts of all executable lines, keyed by line number starting at 1.
+    pub lines: BTreeMap<usize, u64>,
+    pub branches: Vec<BranchCoverage>,
+}
+
+/// Byte offsets at which the lines of a file start, used to map source locations to lines.
+struct LineIndex(Vec<u32>);
+
+impl LineIndex {
+    fn new(contents: &str) -> Self {
+        let starts = std::iter::once(0)
+            .chain(contents.match_indices('\n').map(|(idx, _)| idx as u32 + 1))
+            .collect();
+        Self(starts)
+    }
+
+    fn line_of(&self, offset: u32) -> usize {
+        match self.0.binary_search(&offset) {
+            Ok(idx) => idx + 1,
+            Err(idx) => idx,
+        }
+    }
+}
+
+impl ModuleLineCoverage {
+    /// Computes line and branch coverage of a module from the unified execution map.
+    ///
+    /// Fails if the source file no longer matches the source map of the module.
+    pub fn new(
+        package_name: impl Into<String>,
+        module: &CompiledModule,
+        source_map: &SourceMap,
+        source_path: &Path,
+        coverage_map: &ExecCoverageMap,
+    ) -> Result<Self> {
+        let contents = fs::read_to_string(source_path)?;
+        if !source_map.check(&contents) {
+            bail!(
+                "File contents {} out of sync with source map",
+                source_path.display()
+            );
+        }
+        let line_index = LineIndex::new(&contents);
+        let file_hash = source_map.definition_location.file_hash();
+
+        let module_id = module.self_id();
+        let module_map = coverage_map
+            .module_maps
+            .get(&(*module_id.address(), module_id.name().to_owned()));
+
+        let mut functions = vec![];
+        let mut lines = BTreeMap::new();
+        let mut branches = vec![];
+        for (idx, function_def) in module.function_defs().iter().enumerate() {
+            let code_unit = match &function_def.code {
+                Some(code_unit) => code_unit,
+                None => continue,
+            };
+            let function_def_idx = FunctionDefinitionIndex(idx as u16);
+            let fn_handle = module.function_handle_at(function_def.function);
+            let fn_name = module.identifier_at(fn_handle.name);
+            let function_coverage =
+                module_map.and_then(|module_map| module_map.function_maps.get(fn_name));
+            let exec_count = |offset: CodeOffset| {
+                function_coverage
+                    .and_then(|coverage| coverage.get(&(offset as u64)))
+                    .copied()
+                    .unwrap_or(0)
+            };
+            // The entry of the function counts as a predecessor of the first instruction
+            let mut num_predecessors = vec![0; code_unit.code.len()];
+            num_predecessors[0] = 1;
+            for offset in 0..code_unit.code.len() {
+                for successor in Bytecode::get_successors(offset as CodeOffset, &code_unit.code) {
+                    num_predecessors[successor as usize] += 1;
+                }
+            }
+
+            if let Ok(function_source_map) = source_map.get_function_source_map(function_def_idx) {
+                let loc = function_source_map.definition_location;
+                if loc.file_hash() == file_hash {
+                    functions.push(FunctionLineCoverage {
+                        name: format!("{}::{}", module_id.name(), fn_name),
+                        line: line_index.line_of(loc.start()),
+                        hits: exec_count(0),
+                    });
+                }
+            }
+
+            for (offset, instr) in code_unit.code.iter().enumerate() {
+                let offset = offset as CodeOffset;
+                let loc = match source_map.get_code_location(function_def_idx, offset) {
+                    Ok(loc) if loc.file_hash() == file_hash => loc,
+                    _ => continue,
+                };
+                let line = line_index.line_of(loc.start());
+                let hits = lines.entry(line).or_insert(0);
+                *hits = (*hits).max(exec_count(offset));
+
+                if let Bytecode::BrTrue(target) | Bytecode::BrFalse(target) = instr {
+                    branches.push(BranchCoverage::new(
+                        line,
+                        offset,
+                        *target,
+                        &num_predecessors,
+                        exec_count,
+                    ));
+                }
+            }
+        }
+
+        Ok(Self {
+            package_name: package_name.into(),
+            module_name: module_id.short_str_lossless(),
+            source_path: source_path.to_path_buf(),
+            functions,
+            lines,
+            branches,
+        })
+    }
+
+    fn lines_covered(&self) -> usize {
+        self.lines.values().filter(|hits| **hits > 0).count()
+    }
+
+    fn branches_covered(&self) -> usize {
+        self.branches
+            .iter()
+            .map(|branch| (branch.taken > 0) as usize + (branch.not_taken > 0) as usize)
+            .sum()
+    }
+}
+
+/// Writes the coverage of the given modules as an LCOV tracefile. Modules defined in the same
+/// source file are merged into a single record.
+pub fn write_lcov<W: Write>(modules: &[ModuleLineCoverage], writer: &mut W) -> io::Result<()> {
+    let mut files: BTreeMap<&Path, Vec<&ModuleLineCoverage>> = BTreeMap::new();
+    for module in modules {
+        files
+            .entry(module.source_path.as_path())
+            .or_default()
+            .push(module);
+    }
+
+    for (path, modules) in files {
+        writeln!(writer, "TN:")?;
+        writeln!(writer, "SF:{}", path.display())?;
+
+        let functions = modules.iter().flat_map(|module| &module.functions);
+        for function in functions.clone() {
+            writeln!(writer, "FN:{},{}", function.line, function.name)?;
+        }
+        for function in functions.clone() {
+            writeln!(writer, "FNDA:{},{}", function.hits, function.name)?;
+        }
+        writeln!(writer, "FNF:{}", functions.clone().count())?;
+        writeln!(writer, "FNH:{}", functions.filter(|f| f.hits > 0).count())?;
+
+        let mut branches: BTreeMap<usize, Vec<&BranchCoverage>> = BTreeMap::new();
+        for branch in modules.iter().flat_map(|module| &module.branches) {
+            branches.entry(branch.line).or_default().push(branch);
+        }
+        let (mut branches_found, mut branches_hit) = (0, 0);
+        for (line, branches) in branches {
+            for (block, branch) in branches.into_iter().enumerate() {
+                for (outcome, count) in [branch.taken, branch.not_taken].into_iter().enumerate() {
+                    if branch.executions == 0 {
+                        writeln!(writer, "BRDA:{},{},{},-", line, block, outcome)?;
+                    } else {
+                        writeln!(writer, "BRDA:{},{},{},{}", line, block, outcome, count)?;
+                    }
+                    branches_found += 1;
+                    branches_hit += (count > 0) as usize;
+                }
+            }
+        }
+        writeln!(writer, "BRF:{}", branches_found)?;
+        writeln!(writer, "BRH:{}", branches_hit)?;
+
+        let mut lines = BTreeMap::new();
+        for (line, hits) in modules.iter().flat_map(|module| &module.lines) {
+            let entry = lines.entry(*line).or_insert(0);
+            *entry = (*entry).max(*hits);
+        }
+        for (line, hits) in &lines {
+            writeln!(writer, "DA:{},{}", line, hits)?;
+        }
+        writeln!(writer, "LF:{}", lines.len())?;
+        writeln!(
+            writer,
+            "LH:{}",
+            lines.values().filter(|hits| **hits > 0).count()
+        )?;
+        writeln!(writer, "end_of_record")?;
+    }
+    Ok(())
+}
+
+fn rate(covered: usize, valid: usize) -> String {
+    if valid == 0 {
+        "1".to_string()
+    } else {
+        format!("{:.4}", covered as f64 / valid as f64)
+    }
+}
+
+fn escape_xml(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// Writes the coverage of the given modules as a Cobertura XML report. Every Move package becomes
+/// a Cobertura package and every module a class. Source paths are reported relative to
+/// `source_root` where possible.
+pub fn write_cobertura<W: Write>(
+    modules: &[ModuleLineCoverage],
+    source_root: &Path,
+    writer: &mut W,
+) -> io::Result<()> {
+    let lines_valid: usize = modules.iter().map(|module| module.lines.len()).sum();
+    let lines_covered: usize = modules.iter().map(|module| module.lines_covered()).sum();
+    let branches_valid: usize = modules.iter().map(|module| 2 * module.branches.len()).sum();
+    let branches_covered: usize = modules.iter().map(|module| module.branches_covered()).sum();
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or(0);
+
+    let mut packages: BTreeMap<&str, Vec<&ModuleLineCoverage>> = BTreeMap::new();
+    for module in modules {
+        packages
+            .entry(module.package_name.as_str())
+            .or_default()
+            .push(module);
+    }
+
+    writeln!(writer, r#"<?xml version="1.0" ?>"#)?;
+    writeln!(
+        writer,
+        r#"<!DOCTYPE coverage SYSTEM "http://cobertura.sourceforge.net/xml/coverage-04.dtd">"#
+    )?;
+    writeln!(
+        writer,
+        r#"<coverage line-rate="{}" branch-rate="{}" lines-covered="{}" lines-valid="{}" branches-covered="{}" branches-valid="{}" complexity="0" version="0.1" timestamp="{}">"#,
+        rate(lines_covered, lines_valid),
+        rate(branches_covered, branches_valid),
+        lines_covered,
+        lines_valid,
+        branches_covered,
+        branches_valid,
+        timestamp
+    )?;
+    writeln!(writer, "  <sources>")?;
+    writeln!(
+        writer,
+        "    <source>{}</source>",
+        escape_xml(&source_root.display().to_string())
+    )?;
+    writeln!(writer, "  </sources>")?;
+    writeln!(writer, "  <packages>")?;
+    for (package_name, modules) in packages {
+        let lines_valid: usize = modules.iter().map(|module| module.lines.len()).sum();
+        let lines_covered: usize = modules.iter().map(|module| module.lines_covered()).sum();
+        let branches_valid: usize = modules.iter().map(|module| 2 * module.branches.len()).sum();
+        let branches_covered: usize = modules.iter().map(|module| module.branches_covered()).sum();
+        writeln!(
+            writer,
+            r#"    <package name="{}" line-rate="{}" branch-rate="{}" complexity="0">"#,
+            escape_xml(package_name),
+            rate(lines_covered, lines_valid),
+            rate(branches_covered, branches_valid)
+        )?;
+        writeln!(writer, "      <classes>")?;
+        for module in modules {
+            write_cobertura_class(module, source_root, writer)?;
+        }
+        writeln!(writer, "      </classes>")?;
+        writeln!(writer, "    </package>")?;
+    }
+    writeln!(writer, "  </packages>")?;
+    writeln!(writer, "</coverage>")
+}
+
+fn write_cobertura_class<W: Write>(
+    module: &ModuleLineCoverage,
+    source_root: &Path,
+    writer: &mut W,
+) -> io::Result<()> {
+    let filename = module
+        .source_path
+        .strip_prefix(source_root)
+        .unwrap_or(&module.source_path);
+    writeln!(
+        writer,
+        r#"        <class name="{}" filename="{}" line-rate="{}" branch-rate="{}" complexity="0">"#,
+        escape_xml(&module.module_name),
+        escape_xml(&filename.display().to_string()),
+        rate(module.lines_covered(), module.lines.len()),
+        rate(module.branches_covered(), 2 * module.branches.len())
+    )?;
+
+    writeln!(writer, "          <methods>")?;
+    for function in &module.functions {
+        let name = function.name.rsplit("::").next().unwrap_or(&function.name);
+        writeln!(
+            writer,
+            r#"            <method name="{}" signature="" line-rate="{}" branch-rate="1" complexity="0">"#,
+            escape_xml(name),
+            rate((function.hits > 0) as usize, 1)
+        )?;
+        writeln!(
+            writer,
+            r#"              <lines><line number="{}" hits="{}"/></lines>"#,
+            function.line, function.hits
+        )?;
+        writeln!(writer, "            </method>")?;
+    }
+    writeln!(writer, "          </methods>")?;
+
+    let mut branches: BTreeMap<usize, (usize, usize)> = BTreeMap::new();
+    for branch in &module.branches {
+        let (covered, valid) = branches.entry(branch.line).or_default();
+        *covered += (branch.taken > 0) as usize + (branch.not_taken > 0) as usize;
+        *valid += 2;
+    }
+    writeln!(writer, "          <lines>")?;
+    for (line, hits) in &module.lines {
+        match branches.get(line) {
+            Some((covered, valid)) => writeln!(
+                writer,
+                r#"            <line number="{}" hits="{}" branch="true" condition-coverage="{}% ({}/{})"/>"#,
+                line,
+                hits,
+                covered * 100 / valid,
+                covered,
+                valid
+            )?,
+            None => writeln!(
+                writer,
+                r#"            <line number="{}" hits="{}" branch="false"/>"#,
+                line, hits
+            )?,
+        }
+    }
+    writeln!(writer, "          </lines>")?;
+    writeln!(writer, "        </class>")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn module_coverage(module_name: &str, source_path: &str) -> ModuleLineCoverage {
+        ModuleLineCoverage {
+            package_name: "Example".to_string(),
+            module_name: module_name.to_string(),
+            source_path: PathBuf::from(source_path),
+            functions: vec![
+                FunctionLineCoverage {
+                    name: format!("{}::covered", module_name),
+                    line: 2,
+                    hits: 3,
+                },
+                FunctionLineCoverage {
+                    name: format!("{}::uncovered", module_name),
+                    line: 8,
+                    hits: 0,
+                },
+            ],
+            lines: BTreeMap::from([(3, 3), (4, 1), (5, 2), (9, 0)]),
+            branches: vec![BranchCoverage {
+                line: 3,
+                executions: 3,
+                taken: 1,
+                not_taken: 0,
+                approximate: false,
+            }],
+        }
+    }
+
+    #[test]
+    fn test_line_index() {
+        let index = LineIndex::new("module 0x1::m {\n  fun f() {}\n}\n");
+        assert_eq!(index.line_of(0), 1);
+        assert_eq!(index.line_of(15), 1);
+        assert_eq!(index.line_of(16), 2);
+        assert_eq!(index.line_of(20), 2);
+        assert_eq!(index.line_of(29), 3);
+    }
+
+    #[test]
+    fn test_branch_coverage() {
+        // 0: BrTrue(3), 1: ..., 2: Branch(3), 3: ...
+        // The fall-through (1) is only reached through the branch, the target (3) is a join point
+        let exec_count = |offset: CodeOffset| [5, 2, 2, 5][offset as usize];
+        let branch = BranchCoverage::new(1, 0, 3, &[1, 1, 1, 2], exec_count);
+        assert_eq!((branch.taken, branch.not_taken), (3, 2));
+        assert!(!branch.approximate);
+
+        // 1: BrTrue(3), 2: ..., 3: ...
+        // The target (3) is only reached through the branch, the fall-through (2) is a join point
+        let exec_count = |offset: CodeOffset| [1, 4, 2, 3][offset as usize];
+        let branch = BranchCoverage::new(1, 1, 3, &[1, 2, 2, 1], exec_count);
+        assert_eq!(
+            (branch.executions, branch.taken, branch.not_taken),
+            (4, 3, 1)
+        );
+        assert!(!branch.approximate);
+
+        // Both destinations are join points, so the outcomes are overestimated
+        let branch = BranchCoverage::new(1, 1, 3, &[1, 2, 2, 2], exec_count);
+        assert_eq!((branch.taken, branch.not_taken), (3, 2));
+        assert!(branch.approximate);
+    }
+
+    #[test]
+    fn test_write_lcov() {
+        let mut output = vec![];
+        write_lcov(&[module_coverage("0x1::m", "sources/m.move")], &mut output).unwrap();
+        let expected = "\
+TN:
+SF:sources/m.move
+FN:2,0x1::m::covered
+FN:8,0x1::m::uncovered
+FNDA:3,0x1::m::covered
+FNDA:0,0x1::m::uncovered
+FNF:2
+FNH:1
+BRDA:3,0,0,1
+BRDA:3,0,1,0
+BRF:2
+BRH:1
+DA:3,3
+DA:4,1
+DA:5,2
+DA:9,0
+LF:4
+LH:3
+end_of_record
+";
+        assert_eq!(String::from_utf8(output).unwrap(), expected);
+    }
+
+    #[test]
+    fn test_write_lcov_merges_modules_in_same_file() {
+        let mut output = vec![];
+        let modules = [
+            module_coverage("0x1::m", "sources/m.move"),
+            module_coverage("0x1::n", "sources/m.move"),
+        ];
+        write_lcov(&modules, &mut output).unwrap();
+        let output = String::from_utf8(output).unwrap();
+        assert_eq!(output.matches("SF:").count(), 1);
+        assert!(output.contains("FNF:4\n"));
+        assert!(output.contains("BRDA:3,1,0,1\n"));
+        assert!(output.contains("LF:4\n"));
+    }
+
+    #[test]
+    fn test_write_cobertura() {
+        let mut output = vec![];
+        write_cobertura(
+            &[module_coverage("0x1::m", "/pkg/sources/m.move")],
+            Path::new("/pkg"),
+            &mut output,
+        )
+        .unwrap();
+        let output = String::from_utf8(output).unwrap();
+        assert!(output.contains(
+            r#"line-rate="0.7500" branch-rate="0.5000" lines-covered="3" lines-valid="4" branches-covered="1" branches-valid="2""#
+        ));
+        assert!(output.contains(r#"<package name="Example""#));
+        assert!(output.contains(r#"<class name="0x1::m" filename="sources/m.move""#));
+        assert!(output.contains(
+            r#"<line number="3" hits="3" branch="true" condition-coverage="50% (1/2)"/>"#
+        ));
+        assert!(output.contains(r#"<line number="9" hits="0" branch="false"/>"#));
+    }
+}
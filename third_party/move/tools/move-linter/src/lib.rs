@@ -5,21 +5,64 @@ mod model_ast_lints;
 mod stackless_bytecode_lints;
 mod utils;
 
+use anyhow::{bail, Result};
 use move_compiler_v2::external_checks::{ExpChecker, ExternalChecks, StacklessBytecodeChecker};
-use std::{collections::BTreeMap, sync::Arc};
+use move_package_manifest::{LintCategory, LintLevel};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    sync::Arc,
+};
+
+/// Key of the config selecting the category of lint checks to run.
+pub const CHECKS_KEY: &str = "checks";
+
+/// Key of the config that turns all lint warnings into errors, when set to `true`.
+pub const WARNINGS_AS_ERRORS_KEY: &str = "warnings_as_errors";
+
+/// Prefix of the config keys setting the level of a lint check or a category of lint checks,
+/// e.g., `level.needless_bool` or `level.security`.
+pub const LEVEL_KEY_PREFIX: &str = "level.";
 
 /// Holds collection of lint checks for Move.
 pub struct MoveLintChecks {
-    config: BTreeMap<String, String>,
+    /// The category of lint checks selected via the `checks` key.
+    checks: LintCategory,
+    /// Explicitly configured levels, keyed by the name of a lint check or a category.
+    levels: BTreeMap<String, LintLevel>,
+    /// Whether all lint warnings are reported as errors.
+    warnings_as_errors: bool,
+    /// The category of each known lint check, keyed by its name.
+    categories: BTreeMap<String, LintCategory>,
 }
 
 impl ExternalChecks for MoveLintChecks {
     fn get_exp_checkers(&self) -> Vec<Box<dyn ExpChecker>> {
-        model_ast_lints::get_default_linter_pipeline(&self.config)
+        model_ast_lints::get_linter_pipeline()
+            .into_iter()
+            .filter(|(category, lint)| self.is_enabled(*category, &lint.get_name()))
+            .map(|(_, lint)| lint)
+            .collect()
     }
 
     fn get_stackless_bytecode_checkers(&self) -> Vec<Box<dyn StacklessBytecodeChecker>> {
-        stackless_bytecode_lints::get_default_linter_pipeline(&self.config)
+        stackless_bytecode_lints::get_linter_pipeline()
+            .into_iter()
+            .filter(|(category, lint)| self.is_enabled(*category, &lint.get_name()))
+            .map(|(_, lint)| lint)
+            .collect()
+    }
+
+    fn get_known_checker_names(&self) -> BTreeSet<String> {
+        self.categories.keys().cloned().collect()
+    }
+
+    fn is_denied(&self, checker_name: &str) -> bool {
+        match self.categories.get(checker_name) {
+            Some(category) => {
+                self.warnings_as_errors || self.level(*category, checker_name) == LintLevel::Deny
+            },
+            None => false,
+        }
     }
 }
 
@@ -27,18 +70,152 @@ impl MoveLintChecks {
     /// Make an instance of lint checks for Move, provided as `ExternalChecks`.
     /// Will panic if the configuration is not valid.
     pub fn make(config: BTreeMap<String, String>) -> Arc<dyn ExternalChecks> {
-        // Check whether the config map is valid.
-        // Currently, we expect it to contain a single key "checks" that can map to
-        // one of the three string values: "default", "strict", or "experimental".
-        if config.len() != 1 {
-            panic!("config should have a single key `checks`");
-        }
-        let checks_value = config
-            .get("checks")
-            .expect("config is missing the `checks` key");
-        if !matches!(checks_value.as_str(), "default" | "strict" | "experimental") {
-            panic!("Invalid value for `checks` key in the config, expected one of: `default`, `strict`, or `experimental`");
+        Self::try_make(config).unwrap_or_else(|err| panic!("{:#}", err))
+    }
+
+    /// Make an instance of lint checks for Move, provided as `ExternalChecks`.
+    /// Returns an error if the configuration is not valid.
+    ///
+    /// The config is expected to contain the key `checks`, which maps to one of the
+    /// categories: "default", "strict", "experimental", or "security". Optionally, it may
+    /// contain `warnings_as_errors` ("true" or "false"), and `level.<name>` keys, which set
+    /// the level ("allow", "warn", or "deny") of the lint check or category `<name>`.
+    pub fn try_make(config: BTreeMap<String, String>) -> Result<Arc<dyn ExternalChecks>> {
+        let Some(checks) = config.get(CHECKS_KEY) else {
+            bail!("config is missing the `checks` key");
+        };
+        let checks = checks.parse::<LintCategory>()?;
+        let categories = model_ast_lints::get_linter_pipeline()
+            .into_iter()
+            .map(|(category, lint)| (lint.get_name(), category))
+            .chain(
+                stackless_bytecode_lints::get_linter_pipeline()
+                    .into_iter()
+                    .map(|(category, lint)| (lint.get_name(), category)),
+            )
+            .collect::<BTreeMap<_, _>>();
+        let mut levels = BTreeMap::new();
+        let mut warnings_as_errors = false;
+        for (key, value) in &config {
+            if key == CHECKS_KEY {
+                continue;
+            } else if key == WARNINGS_AS_ERRORS_KEY {
+                warnings_as_errors = match value.as_str() {
+                    "true" => true,
+                    "false" => false,
+                    _ => bail!(
+                        "Invalid value for `{}` key in the config, expected `true` or `false`",
+                        WARNINGS_AS_ERRORS_KEY
+                    ),
+                };
+            } else if let Some(name) = key.strip_prefix(LEVEL_KEY_PREFIX) {
+                if !categories.contains_key(name) && name.parse::<LintCategory>().is_err() {
+                    bail!("unknown lint check or category: `{}`", name);
+                }
+                levels.insert(name.to_string(), value.parse::<LintLevel>()?);
+            } else {
+                bail!("unexpected key `{}` in the config", key);
+            }
         }
-        Arc::new(MoveLintChecks { config })
+        Ok(Arc::new(MoveLintChecks {
+            checks,
+            levels,
+            warnings_as_errors,
+            categories,
+        }))
+    }
+
+    /// Returns the level of the lint check `name` in the `category`. A level set for the
+    /// lint check takes precedence over the level set for its category.
+    fn level(&self, category: LintCategory, name: &str) -> LintLevel {
+        self.levels
+            .get(name)
+            .or_else(|| self.levels.get(&category.to_string()))
+            .copied()
+            .unwrap_or(if includes(self.checks, category) {
+                LintLevel::Warn
+            } else {
+                LintLevel::Allow
+            })
+    }
+
+    /// Returns `true` if the lint check `name` in the `category` should be run.
+    fn is_enabled(&self, category: LintCategory, name: &str) -> bool {
+        self.level(category, name) != LintLevel::Allow
+    }
+}
+
+/// Returns `true` if selecting the `checks` category also runs the checks of `category`.
+fn includes(checks: LintCategory, category: LintCategory) -> bool {
+    use LintCategory::*;
+    match category {
+        Default => true,
+        Strict => matches!(checks, Strict | Experimental),
+        Experimental => checks == Experimental,
+        Security => checks == Security,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn make(entries: &[(&str, &str)]) -> Result<Arc<dyn ExternalChecks>> {
+        MoveLintChecks::try_make(
+            entries
+                .iter()
+                .map(|(key, value)| (key.to_string(), value.to_string()))
+                .collect(),
+        )
+    }
+
+    fn names(checks: &Arc<dyn ExternalChecks>) -> BTreeSet<String> {
+        checks
+            .get_exp_checkers()
+            .into_iter()
+            .map(|lint| lint.get_name())
+            .chain(
+                checks
+                    .get_stackless_bytecode_checkers()
+                    .into_iter()
+                    .map(|lint| lint.get_name()),
+            )
+            .collect()
+    }
+
+    #[test]
+    fn levels_select_lints() {
+        let checks = make(&[
+            ("checks", "default"),
+            ("level.security", "deny"),
+            ("level.unchecked_signer", "allow"),
+            ("level.while_true", "allow"),
+        ])
+        .unwrap();
+        let names = names(&checks);
+        assert!(names.contains("needless_bool"));
+        assert!(names.contains("caller_controlled_global_borrow"));
+        assert!(!names.contains("unchecked_signer"));
+        assert!(!names.contains("while_true"));
+        // Lints which are not run can still be skipped.
+        assert!(checks.get_known_checker_names().contains("while_true"));
+        assert!(checks.is_denied("caller_controlled_global_borrow"));
+        assert!(!checks.is_denied("needless_bool"));
+    }
+
+    #[test]
+    fn warnings_as_errors_denies_all_lints() {
+        let checks = make(&[("checks", "default"), ("warnings_as_errors", "true")]).unwrap();
+        assert!(checks.is_denied("needless_bool"));
+        assert!(!names(&checks).contains("unchecked_signer"));
+    }
+
+    #[test]
+    fn invalid_config() {
+        assert!(make(&[]).is_err());
+        assert!(make(&[("checks", "pedantic")]).is_err());
+        assert!(make(&[("checks", "default"), ("level.no_such_lint", "deny")]).is_err());
+        assert!(make(&[("checks", "default"), ("level.needless_bool", "forbid")]).is_err());
+        assert!(make(&[("checks", "default"), ("unknown", "true")]).is_err());
     }
 }
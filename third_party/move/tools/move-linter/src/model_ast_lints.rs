@@ -11,19 +11,20 @@ mod needless_deref_ref;
 mod needless_ref_deref;
 mod needless_ref_in_field_access;
 mod nonminimal_bool;
+mod randomness_in_public_function;
 mod self_assignment;
 mod simpler_numeric_expression;
 mod unnecessary_boolean_identity_comparison;
 mod unnecessary_numerical_extreme_comparison;
 mod while_true;
 
+use crate::LintCategory;
 use move_compiler_v2::external_checks::ExpChecker;
-use std::collections::BTreeMap;
 
-/// Returns a default pipeline of "expression linters" to run.
-pub fn get_default_linter_pipeline(config: &BTreeMap<String, String>) -> Vec<Box<dyn ExpChecker>> {
-    // Start with the default set of checks.
-    let checks: Vec<Box<dyn ExpChecker>> = vec![
+/// Returns the pipeline of all the "expression linters", along with their categories.
+/// Which of them are run is decided by the configuration of `MoveLintChecks`.
+pub fn get_linter_pipeline() -> Vec<(LintCategory, Box<dyn ExpChecker>)> {
+    let default_checks: Vec<Box<dyn ExpChecker>> = vec![
         Box::<almost_swapped::AlmostSwapped>::default(),
         Box::<assert_const::AssertConst>::default(),
         Box::<blocks_in_conditions::BlocksInConditions>::default(),
@@ -38,12 +39,18 @@ pub fn get_default_linter_pipeline(config: &BTreeMap<String, String>) -> Vec<Box
         Box::<unnecessary_numerical_extreme_comparison::UnnecessaryNumericalExtremeComparison>::default(),
         Box::<while_true::WhileTrue>::default(),
     ];
-    let checks_category = config.get("checks").map_or("default", |s| s.as_str());
-    if checks_category == "strict" || checks_category == "experimental" {
-        // Push strict checks to `checks`.
-    }
-    if checks_category == "experimental" {
-        // Push experimental checks to `checks`.
-    }
-    checks
+    let strict_checks: Vec<Box<dyn ExpChecker>> = vec![];
+    let experimental_checks: Vec<Box<dyn ExpChecker>> = vec![];
+    let security_checks: Vec<Box<dyn ExpChecker>> = vec![
+        Box::<randomness_in_public_function::RandomnessInPublicFunction>::default(),
+    ];
+    [
+        (LintCategory::Default, default_checks),
+        (LintCategory::Strict, strict_checks),
+        (LintCategory::Experimental, experimental_checks),
+        (LintCategory::Security, security_checks),
+    ]
+    .into_iter()
+    .flat_map(|(category, checks)| checks.into_iter().map(move |check| (category, check)))
+    .collect()
 }
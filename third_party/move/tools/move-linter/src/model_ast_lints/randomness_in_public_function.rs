@@ -0,0 +1,47 @@
+// Copyright (c) A-p-t-o-s Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! This module implements an expression linter that checks for calls to the `randomness`
+//! module from public non-entry functions. Such a function can be called from another
+//! module, which can observe the random outcome and abort the transaction when it does
+//! not like it (a test-and-abort attack). Randomness should only be consumed in private
+//! (or friend) functions, reached from private entry functions.
+
+use crate::utils::{is_framework_function, is_framework_module};
+use move_compiler_v2::external_checks::ExpChecker;
+use move_model::{
+    ast::{ExpData, Operation},
+    model::{FunctionEnv, Visibility},
+};
+
+#[derive(Default)]
+pub struct RandomnessInPublicFunction;
+
+impl ExpChecker for RandomnessInPublicFunction {
+    fn get_name(&self) -> String {
+        "randomness_in_public_function".to_string()
+    }
+
+    fn visit_expr_pre(&mut self, function: &FunctionEnv, expr: &ExpData) {
+        let ExpData::Call(id, Operation::MoveFunction(mid, fid), _) = expr else {
+            return;
+        };
+        if function.visibility() != Visibility::Public
+            || function.is_entry()
+            || is_framework_module(&function.module_env, "randomness")
+        {
+            return;
+        }
+        let env = function.env();
+        if !is_framework_function(&env.get_function(mid.qualified(*fid)), "randomness", &[]) {
+            return;
+        }
+        self.report(
+            env,
+            &env.get_node_loc(*id),
+            "Randomness API called from a public non-entry function: other modules can call \
+             this function and abort if they do not like the outcome, consider making it \
+             private and calling it from a private entry function",
+        );
+    }
+}
@@ -6,26 +6,39 @@
 //! The lint checks also assume that all the correctness checks have already been performed.
 
 mod avoid_copy_on_identity_comparison;
+mod caller_controlled_global_borrow;
+mod missing_balance_change_event;
 mod needless_mutable_reference;
+mod object_without_ref_policy;
+mod unchecked_signer;
+mod unchecked_storage_arithmetic;
 
+use crate::LintCategory;
 use move_compiler_v2::external_checks::StacklessBytecodeChecker;
-use std::collections::BTreeMap;
 
-/// Get default pipeline of "stackless bytecode linters" to run.
-pub fn get_default_linter_pipeline(
-    config: &BTreeMap<String, String>,
-) -> Vec<Box<dyn StacklessBytecodeChecker>> {
-    // Start with the default set of checks.
-    let checks: Vec<Box<dyn StacklessBytecodeChecker>> = vec![
+/// Returns the pipeline of all the "stackless bytecode linters", along with their categories.
+/// Which of them are run is decided by the configuration of `MoveLintChecks`.
+pub fn get_linter_pipeline() -> Vec<(LintCategory, Box<dyn StacklessBytecodeChecker>)> {
+    let default_checks: Vec<Box<dyn StacklessBytecodeChecker>> = vec![
         Box::new(avoid_copy_on_identity_comparison::AvoidCopyOnIdentityComparison {}),
         Box::new(needless_mutable_reference::NeedlessMutableReference {}),
     ];
-    let checks_category = config.get("checks").map_or("default", |s| s.as_str());
-    if checks_category == "strict" || checks_category == "experimental" {
-        // Push strict checks to `checks`.
-    }
-    if checks_category == "experimental" {
-        // Push experimental checks to `checks`.
-    }
-    checks
+    let strict_checks: Vec<Box<dyn StacklessBytecodeChecker>> = vec![];
+    let experimental_checks: Vec<Box<dyn StacklessBytecodeChecker>> = vec![];
+    let security_checks: Vec<Box<dyn StacklessBytecodeChecker>> = vec![
+        Box::new(unchecked_signer::UncheckedSigner {}),
+        Box::new(unchecked_storage_arithmetic::UncheckedStorageArithmetic {}),
+        Box::new(caller_controlled_global_borrow::CallerControlledGlobalBorrow {}),
+        Box::new(object_without_ref_policy::ObjectWithoutRefPolicy {}),
+        Box::new(missing_balance_change_event::MissingBalanceChangeEvent {}),
+    ];
+    [
+        (LintCategory::Default, default_checks),
+        (LintCategory::Strict, strict_checks),
+        (LintCategory::Experimental, experimental_checks),
+        (LintCategory::Security, security_checks),
+    ]
+    .into_iter()
+    .flat_map(|(category, checks)| checks.into_iter().map(move |check| (category, check)))
+    .collect()
 }
@@ -0,0 +1,98 @@
+// Copyright (c) A-p-t-o-s Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! This module implements a stackless-bytecode linter that checks for `borrow_global_mut`
+//! on an address passed in by the caller of a public or entry function. Since anyone can
+//! call such a function with an arbitrary address, the resource stored under someone
+//! else's account can be modified, unless the address is validated first.
+//!
+//! An address is considered validated if it is compared for (in)equality anywhere in the
+//! function, e.g., `assert!(addr == signer::address_of(account), E_NOT_OWNER)`.
+
+use move_compiler_v2::external_checks::StacklessBytecodeChecker;
+use move_model::{
+    ast::TempIndex,
+    model::{Parameter, Visibility},
+};
+use move_stackless_bytecode::{
+    function_target::FunctionTarget,
+    stackless_bytecode::{Bytecode, Operation},
+};
+use std::collections::BTreeSet;
+
+/// Linter for detecting mutable global borrows at caller-controlled addresses.
+pub struct CallerControlledGlobalBorrow {}
+
+impl StacklessBytecodeChecker for CallerControlledGlobalBorrow {
+    fn get_name(&self) -> String {
+        "caller_controlled_global_borrow".to_string()
+    }
+
+    fn check(&self, target: &FunctionTarget) {
+        use Bytecode::*;
+        use Operation::*;
+        let func_env = target.func_env;
+        if func_env.visibility() != Visibility::Public && !func_env.is_entry() {
+            return;
+        }
+        let controlled = caller_controlled_addresses(target);
+        if controlled.is_empty() {
+            return;
+        }
+        let validated = target.get_bytecode().iter().any(|instr| {
+            matches!(instr, Call(_, _, Eq | Neq, srcs, _)
+                if srcs.iter().any(|src| controlled.contains(src)))
+        });
+        if validated {
+            return;
+        }
+        for instr in target.get_bytecode() {
+            if let Call(id, dsts, BorrowGlobal(..), srcs, _) = instr {
+                if !target.get_local_type(dsts[0]).is_mutable_reference()
+                    || !controlled.contains(&srcs[0])
+                {
+                    continue;
+                }
+                let loc = target.get_bytecode_loc(*id);
+                if loc.is_inlined() {
+                    continue;
+                }
+                self.report(
+                    target.global_env(),
+                    &loc,
+                    "Mutable borrow of global storage at an address controlled by the caller: \
+                     anyone can modify the resource stored under an arbitrary address, consider \
+                     deriving the address from a signer or validating it first",
+                );
+            }
+        }
+    }
+}
+
+/// Returns the address parameters of the function, along with the temps they are copied to.
+fn caller_controlled_addresses(target: &FunctionTarget) -> BTreeSet<TempIndex> {
+    // Note: we assume that parameters are laid out as the initial temps.
+    let mut controlled: BTreeSet<TempIndex> = target
+        .func_env
+        .get_parameters_ref()
+        .iter()
+        .enumerate()
+        .filter_map(|(i, Parameter(_, ty, _))| ty.is_address().then_some(i))
+        .collect();
+    if controlled.is_empty() {
+        return controlled;
+    }
+    loop {
+        let mut changed = false;
+        for instr in target.get_bytecode() {
+            if let Bytecode::Assign(_, dst, src, _) = instr {
+                if controlled.contains(src) {
+                    changed |= controlled.insert(*dst);
+                }
+            }
+        }
+        if !changed {
+            return controlled;
+        }
+    }
+}
@@ -0,0 +1,120 @@
+// Copyright (c) A-p-t-o-s Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! This module implements a stackless-bytecode linter that checks for functions which
+//! modify a balance held in global storage without emitting an event. Indexers and
+//! wallets rely on events to track balances, so a silent balance change is invisible
+//! to them.
+//!
+//! A balance change is a write through a mutable reference to a field whose name contains
+//! `balance`, of a struct borrowed via `borrow_global_mut`. The function is considered to
+//! emit an event if it calls `event::emit` or `event::emit_event` anywhere.
+
+use crate::utils::{is_framework_function, is_framework_module};
+use move_compiler_v2::external_checks::StacklessBytecodeChecker;
+use move_model::ast::TempIndex;
+use move_stackless_bytecode::{
+    function_target::FunctionTarget,
+    stackless_bytecode::{Bytecode, Operation},
+};
+use std::collections::BTreeSet;
+
+/// Linter for detecting balance changes in global storage without an accompanying event.
+pub struct MissingBalanceChangeEvent {}
+
+impl StacklessBytecodeChecker for MissingBalanceChangeEvent {
+    fn get_name(&self) -> String {
+        "missing_balance_change_event".to_string()
+    }
+
+    fn check(&self, target: &FunctionTarget) {
+        use Bytecode::*;
+        use Operation::*;
+        if is_framework_module(&target.func_env.module_env, "event") {
+            return;
+        }
+        let env = target.global_env();
+        let emits_event = target.get_bytecode().iter().any(|instr| match instr {
+            Call(_, _, Function(mid, fid, _), _, _) => is_framework_function(
+                &env.get_function(mid.qualified(*fid)),
+                "event",
+                &["emit", "emit_event"],
+            ),
+            _ => false,
+        });
+        if emits_event {
+            return;
+        }
+        let balance_refs = balance_field_references(target);
+        // Only the first balance change in the function is reported.
+        let first_change = target.get_bytecode().iter().find_map(|instr| match instr {
+            Call(id, _, WriteRef, srcs, _) if balance_refs.contains(&srcs[0]) => {
+                Some(target.get_bytecode_loc(*id))
+            },
+            _ => None,
+        });
+        if let Some(loc) = first_change {
+            if loc.is_inlined() {
+                return;
+            }
+            self.report(
+                env,
+                &loc,
+                "Balance in global storage changed without emitting an event: consider \
+                 emitting an event with `event::emit`, so that the change can be tracked \
+                 off-chain",
+            );
+        }
+    }
+}
+
+/// Returns the temps holding mutable references to balance fields of structs borrowed
+/// from global storage.
+fn balance_field_references(target: &FunctionTarget) -> BTreeSet<TempIndex> {
+    use Bytecode::*;
+    use Operation::*;
+    let env = target.global_env();
+    let mut global_refs = BTreeSet::new();
+    let mut balance_refs = BTreeSet::new();
+    loop {
+        let mut changed = false;
+        for instr in target.get_bytecode() {
+            match instr {
+                Call(_, dsts, BorrowGlobal(..), _, _)
+                    if target.get_local_type(dsts[0]).is_mutable_reference() =>
+                {
+                    changed |= global_refs.insert(dsts[0]);
+                },
+                Call(_, dsts, BorrowField(mid, sid, _, offset), srcs, _)
+                    if global_refs.contains(&srcs[0])
+                        && target.get_local_type(dsts[0]).is_mutable_reference() =>
+                {
+                    let field_name = env
+                        .get_struct(mid.qualified(*sid))
+                        .get_field_by_offset(*offset)
+                        .get_name()
+                        .display(env.symbol_pool())
+                        .to_string();
+                    if field_name.contains("balance") {
+                        changed |= balance_refs.insert(dsts[0]);
+                    } else {
+                        // Balances may be nested in other fields.
+                        changed |= global_refs.insert(dsts[0]);
+                    }
+                },
+                Assign(_, dst, src, _) => {
+                    if global_refs.contains(src) {
+                        changed |= global_refs.insert(*dst);
+                    }
+                    if balance_refs.contains(src) {
+                        changed |= balance_refs.insert(*dst);
+                    }
+                },
+                _ => {},
+            }
+        }
+        if !changed {
+            return balance_refs;
+        }
+    }
+}
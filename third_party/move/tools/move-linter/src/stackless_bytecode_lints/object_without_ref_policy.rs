@@ -0,0 +1,117 @@
+// Copyright (c) A-p-t-o-s Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! This module implements a stackless-bytecode linter that checks for objects which are
+//! created without deciding on their deletion or transfer policy. The `ConstructorRef`
+//! returned by `object::create_*` is the only opportunity to generate a `DeleteRef` or a
+//! `TransferRef` (or to make the object untransferable); once it is dropped, the object
+//! can never be deleted and its transferability can never be restricted.
+//!
+//! A created object is not reported if, anywhere in the function, its `ConstructorRef`
+//! is used to generate one of these refs, or escapes the function (it is returned or
+//! passed to a function outside of the `object` module), since the policy may then be
+//! decided elsewhere.
+
+use crate::utils::{is_framework_function, is_framework_module};
+use move_compiler_v2::external_checks::StacklessBytecodeChecker;
+use move_model::{ast::TempIndex, model::Loc};
+use move_stackless_bytecode::{
+    function_target::FunctionTarget,
+    stackless_bytecode::{Bytecode, Operation},
+};
+use std::collections::{BTreeMap, BTreeSet};
+
+/// Functions of the `object` module which return a `ConstructorRef`.
+const OBJECT_CONSTRUCTORS: &[&str] = &[
+    "create_named_object",
+    "create_object",
+    "create_object_from_account",
+    "create_object_from_object",
+    "create_sticky_object",
+    "create_sticky_object_at_address",
+    "create_user_derived_object",
+];
+
+/// Functions of the `object` module which decide on the deletion or transfer policy.
+const REF_POLICY_FUNCTIONS: &[&str] = &[
+    "generate_delete_ref",
+    "generate_transfer_ref",
+    "set_untransferable",
+];
+
+/// Linter for detecting objects created without a deletion or transfer policy.
+pub struct ObjectWithoutRefPolicy {}
+
+impl StacklessBytecodeChecker for ObjectWithoutRefPolicy {
+    fn get_name(&self) -> String {
+        "object_without_ref_policy".to_string()
+    }
+
+    fn check(&self, target: &FunctionTarget) {
+        use Bytecode::*;
+        use Operation::*;
+        if is_framework_module(&target.func_env.module_env, "object") {
+            return;
+        }
+        let env = target.global_env();
+        // Maps each constructor ref created in this function to its creation location.
+        let mut created: BTreeMap<TempIndex, Loc> = BTreeMap::new();
+        for instr in target.get_bytecode() {
+            if let Call(id, dsts, Function(mid, fid, _), _, _) = instr {
+                let callee = env.get_function(mid.qualified(*fid));
+                if is_framework_function(&callee, "object", OBJECT_CONSTRUCTORS) {
+                    created.insert(dsts[0], target.get_bytecode_loc(*id));
+                }
+            }
+        }
+        for (constructor_ref, loc) in created {
+            if loc.is_inlined() || has_ref_policy_or_escapes(target, constructor_ref) {
+                continue;
+            }
+            self.report(
+                env,
+                &loc,
+                "Object created without a deletion or transfer policy: consider generating a \
+                 `DeleteRef` or `TransferRef` (or calling `object::set_untransferable`) from \
+                 its `ConstructorRef`, which cannot be done once it is dropped",
+            );
+        }
+    }
+}
+
+/// Returns `true` if the `constructor_ref` (or a reference to it) is used to decide on a
+/// deletion or transfer policy, or escapes the function.
+fn has_ref_policy_or_escapes(target: &FunctionTarget, constructor_ref: TempIndex) -> bool {
+    use Bytecode::*;
+    use Operation::*;
+    let env = target.global_env();
+    let mut aliases = BTreeSet::from([constructor_ref]);
+    loop {
+        let mut changed = false;
+        for instr in target.get_bytecode() {
+            match instr {
+                Assign(_, dst, src, _) if aliases.contains(src) => {
+                    changed |= aliases.insert(*dst);
+                },
+                Call(_, dsts, BorrowLoc, srcs, _) if aliases.contains(&srcs[0]) => {
+                    changed |= aliases.insert(dsts[0]);
+                },
+                _ => {},
+            }
+        }
+        if !changed {
+            break;
+        }
+    }
+    target.get_bytecode().iter().any(|instr| match instr {
+        Ret(_, srcs) => srcs.iter().any(|src| aliases.contains(src)),
+        Call(_, _, Function(mid, fid, _), srcs, _)
+            if srcs.iter().any(|src| aliases.contains(src)) =>
+        {
+            let callee = env.get_function(mid.qualified(*fid));
+            !is_framework_module(&callee.module_env, "object")
+                || is_framework_function(&callee, "object", REF_POLICY_FUNCTIONS)
+        },
+        _ => false,
+    })
+}
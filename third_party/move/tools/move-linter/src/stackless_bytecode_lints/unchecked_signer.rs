@@ -0,0 +1,57 @@
+// Copyright (c) A-p-t-o-s Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! This module implements a stackless-bytecode linter that checks for entry functions
+//! which take a `signer` or `&signer` parameter but never use it. Such a function can be
+//! called by anyone, so requiring a signer that is never checked (e.g., via
+//! `signer::address_of` compared against an expected address) usually means that an
+//! intended authorization check is missing.
+//!
+//! Parameters prefixed with `_` are flagged as well: the prefix silences the compiler's
+//! unused variable warning, but not the missing authorization.
+
+use move_compiler_v2::external_checks::StacklessBytecodeChecker;
+use move_model::{ast::TempIndex, model::Parameter};
+use move_stackless_bytecode::{
+    function_target::FunctionTarget,
+    stackless_bytecode::{Bytecode, Operation},
+};
+use std::collections::BTreeSet;
+
+/// Linter for detecting entry functions with unchecked signer parameters.
+pub struct UncheckedSigner {}
+
+impl StacklessBytecodeChecker for UncheckedSigner {
+    fn get_name(&self) -> String {
+        "unchecked_signer".to_string()
+    }
+
+    fn check(&self, target: &FunctionTarget) {
+        if !target.func_env.is_entry() {
+            return;
+        }
+        // Dropping or releasing a signer does not count as using it.
+        let used: BTreeSet<TempIndex> = target
+            .get_bytecode()
+            .iter()
+            .filter(|instr| {
+                !matches!(
+                    instr,
+                    Bytecode::Call(_, _, Operation::Drop | Operation::Release, _, _)
+                )
+            })
+            .flat_map(|instr| instr.sources())
+            .collect();
+        for (i, Parameter(_, ty, loc)) in target.func_env.get_parameters_ref().iter().enumerate() {
+            // Note: we assume that parameters are laid out as the initial temps.
+            if ty.skip_reference().is_signer() && !used.contains(&i) && !loc.is_inlined() {
+                self.report(
+                    target.global_env(),
+                    loc,
+                    "Entry function takes a signer but never checks it: anyone can call this \
+                     function, consider verifying the signer's address or removing the parameter",
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,103 @@
+// Copyright (c) A-p-t-o-s Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! This module implements a stackless-bytecode linter that checks for `u64` arithmetic
+//! (`+`, `-`, `*`) on values read from global storage, in functions which never compare
+//! such values. For example, `r.balance - amount` without a preceding
+//! `assert!(r.balance >= amount, E_INSUFFICIENT_BALANCE)` aborts with an arithmetic error
+//! instead of a meaningful abort code when the balance is too low.
+//!
+//! The tracking is flow-insensitive: any comparison (`<`, `<=`, `>`, `>=`) of a value read
+//! from storage anywhere in the function is considered a bounds check for all arithmetic
+//! in that function, to minimize perceived false positives.
+
+use move_compiler_v2::external_checks::StacklessBytecodeChecker;
+use move_model::{
+    ast::TempIndex,
+    model::Loc,
+    ty::{PrimitiveType, Type},
+};
+use move_stackless_bytecode::{
+    function_target::FunctionTarget,
+    stackless_bytecode::{Bytecode, Operation},
+};
+use std::collections::BTreeSet;
+
+/// Linter for detecting unchecked arithmetic on values read from global storage.
+pub struct UncheckedStorageArithmetic {}
+
+impl StacklessBytecodeChecker for UncheckedStorageArithmetic {
+    fn get_name(&self) -> String {
+        "unchecked_storage_arithmetic".to_string()
+    }
+
+    fn check(&self, target: &FunctionTarget) {
+        let from_storage = storage_derived_temps(target);
+        if from_storage.is_empty() {
+            return;
+        }
+        let mut unchecked: Vec<Loc> = vec![];
+        for instr in target.get_bytecode() {
+            use Bytecode::*;
+            use Operation::*;
+            match instr {
+                Call(_, _, Lt | Le | Gt | Ge, srcs, _)
+                    if srcs.iter().any(|src| from_storage.contains(src)) =>
+                {
+                    // The function performs a bounds check on a value read from storage.
+                    return;
+                },
+                Call(id, dsts, Add | Sub | Mul, srcs, _)
+                    if target.get_local_type(dsts[0]) == &Type::Primitive(PrimitiveType::U64)
+                        && srcs.iter().any(|src| from_storage.contains(src)) =>
+                {
+                    unchecked.push(target.get_bytecode_loc(*id));
+                },
+                _ => {},
+            }
+        }
+        for loc in unchecked {
+            if loc.is_inlined() {
+                continue;
+            }
+            self.report(
+                target.global_env(),
+                &loc,
+                "Unchecked `u64` arithmetic on a value read from global storage: an overflow or \
+                 underflow aborts without a meaningful error, consider checking the bounds with \
+                 an `assert!` first",
+            );
+        }
+    }
+}
+
+/// Returns the temps holding references into global storage, or values read or computed
+/// from them. The result is computed as a fixpoint, so that it does not depend on the
+/// order of instructions.
+fn storage_derived_temps(target: &FunctionTarget) -> BTreeSet<TempIndex> {
+    use Bytecode::*;
+    use Operation::*;
+    let mut derived = BTreeSet::new();
+    loop {
+        let mut changed = false;
+        for instr in target.get_bytecode() {
+            match instr {
+                Call(_, dsts, BorrowGlobal(..), _, _) => {
+                    changed |= derived.insert(dsts[0]);
+                },
+                Call(_, dsts, BorrowField(..) | ReadRef | Add | Sub | Mul, srcs, _)
+                    if srcs.iter().any(|src| derived.contains(src)) =>
+                {
+                    changed |= derived.insert(dsts[0]);
+                },
+                Assign(_, dst, src, _) if derived.contains(src) => {
+                    changed |= derived.insert(*dst);
+                },
+                _ => {},
+            }
+        }
+        if !changed {
+            return derived;
+        }
+    }
+}
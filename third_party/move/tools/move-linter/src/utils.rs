@@ -3,7 +3,10 @@
 
 //! This module holds utility functions for the Move linter.
 
-use move_model::ast::{ExpData, Operation};
+use move_model::{
+    ast::{ExpData, Operation},
+    model::{FunctionEnv, ModuleEnv},
+};
 
 /// Returns `true` if two expressions represent the same simple access pattern.
 /// This compares nested `Select`, `Borrow`, and local variable references for structural equality.
@@ -33,3 +36,24 @@ pub(crate) fn is_simple_access_equal(expr1: &ExpData, expr2: &ExpData) -> bool {
         _ => false,
     }
 }
+
+/// Returns `true` if `module` is the module `module_name` published at the standard library
+/// address (where the framework modules also live).
+pub(crate) fn is_framework_module(module: &ModuleEnv, module_name: &str) -> bool {
+    let env = module.env;
+    let name = module.get_name();
+    name.addr() == &env.get_stdlib_address()
+        && name.name().display(env.symbol_pool()).to_string() == module_name
+}
+
+/// Returns `true` if `function` is one of `function_names` declared in the framework module
+/// `module_name`. If `function_names` is empty, any function of that module matches.
+pub(crate) fn is_framework_function(
+    function: &FunctionEnv,
+    module_name: &str,
+    function_names: &[&str],
+) -> bool {
+    is_framework_module(&function.module_env, module_name)
+        && (function_names.is_empty()
+            || function_names.contains(&function.get_name_str().as_str()))
+}
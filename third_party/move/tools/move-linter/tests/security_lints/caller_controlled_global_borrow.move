@@ -0,0 +1,34 @@
+module 0xc0ffee::m {
+    use std::signer;
+
+    struct Counter has key {
+        count: u64,
+    }
+
+    public fun reset_warn(addr: address) {
+        borrow_global_mut<Counter>(addr).count = 0;
+    }
+
+    public entry fun reset_entry_warn(target: address) {
+        let counter = borrow_global_mut<Counter>(target);
+        counter.count = 0;
+    }
+
+    public fun reset_checked_no_warn(account: &signer, addr: address) {
+        assert!(signer::address_of(account) == addr, 1);
+        borrow_global_mut<Counter>(addr).count = 0;
+    }
+
+    public fun read_no_warn(addr: address): u64 {
+        borrow_global<Counter>(addr).count
+    }
+
+    fun private_no_warn(addr: address) {
+        borrow_global_mut<Counter>(addr).count = 0;
+    }
+
+    #[lint::skip(caller_controlled_global_borrow)]
+    public fun suppressed_no_warn(addr: address) {
+        borrow_global_mut<Counter>(addr).count = 0;
+    }
+}
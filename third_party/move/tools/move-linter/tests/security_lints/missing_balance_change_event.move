@@ -0,0 +1,35 @@
+module creditchain_framework::event {
+    public fun emit<T: store + drop>(_msg: T) {}
+}
+
+module 0xc0ffee::m {
+    use creditchain_framework::event;
+    use std::signer;
+
+    struct Account has key {
+        balance: u64,
+        frozen: bool,
+    }
+
+    struct BalanceChanged has drop, store {
+        balance: u64,
+    }
+
+    fun set_balance_warn(account: &signer, balance: u64) {
+        borrow_global_mut<Account>(signer::address_of(account)).balance = balance;
+    }
+
+    fun set_balance_no_warn(account: &signer, balance: u64) {
+        borrow_global_mut<Account>(signer::address_of(account)).balance = balance;
+        event::emit(BalanceChanged { balance });
+    }
+
+    fun freeze_no_warn(account: &signer) {
+        borrow_global_mut<Account>(signer::address_of(account)).frozen = true;
+    }
+
+    #[lint::skip(missing_balance_change_event)]
+    fun suppressed_no_warn(account: &signer, balance: u64) {
+        borrow_global_mut<Account>(signer::address_of(account)).balance = balance;
+    }
+}
@@ -0,0 +1,58 @@
+module creditchain_framework::object {
+    struct ConstructorRef has drop {
+        addr: address,
+    }
+
+    struct DeleteRef has drop, store {
+        addr: address,
+    }
+
+    public fun create_object(owner_address: address): ConstructorRef {
+        ConstructorRef { addr: owner_address }
+    }
+
+    public fun generate_delete_ref(constructor_ref: &ConstructorRef): DeleteRef {
+        DeleteRef { addr: constructor_ref.addr }
+    }
+
+    public fun address_from_constructor_ref(constructor_ref: &ConstructorRef): address {
+        constructor_ref.addr
+    }
+}
+
+module 0xc0ffee::m {
+    use creditchain_framework::object::{Self, ConstructorRef, DeleteRef};
+
+    struct Holder has key {
+        delete_ref: DeleteRef,
+    }
+
+    fun create_warn(): address {
+        let constructor_ref = object::create_object(@0xc0ffee);
+        object::address_from_constructor_ref(&constructor_ref)
+    }
+
+    fun create_deletable_no_warn(account: &signer) {
+        let constructor_ref = object::create_object(@0xc0ffee);
+        let delete_ref = object::generate_delete_ref(&constructor_ref);
+        move_to(account, Holder { delete_ref });
+    }
+
+    fun create_returned_no_warn(): ConstructorRef {
+        object::create_object(@0xc0ffee)
+    }
+
+    fun keep_delete_ref(account: &signer, constructor_ref: &ConstructorRef) {
+        move_to(account, Holder { delete_ref: object::generate_delete_ref(constructor_ref) });
+    }
+
+    fun create_passed_no_warn(account: &signer) {
+        let constructor_ref = object::create_object(@0xc0ffee);
+        keep_delete_ref(account, &constructor_ref);
+    }
+
+    #[lint::skip(object_without_ref_policy)]
+    fun suppressed_no_warn() {
+        object::create_object(@0xc0ffee);
+    }
+}
@@ -0,0 +1,30 @@
+module creditchain_framework::randomness {
+    public fun u64_integer(): u64 {
+        4
+    }
+}
+
+module 0xc0ffee::m {
+    use creditchain_framework::randomness;
+
+    public fun roll_warn(): u64 {
+        randomness::u64_integer() % 6
+    }
+
+    fun roll_no_warn(): u64 {
+        randomness::u64_integer() % 6
+    }
+
+    entry fun roll_entry_no_warn() {
+        roll_no_warn();
+    }
+
+    public entry fun roll_public_entry_no_warn() {
+        randomness::u64_integer();
+    }
+
+    #[lint::skip(randomness_in_public_function)]
+    public fun suppressed_no_warn(): u64 {
+        randomness::u64_integer()
+    }
+}
@@ -0,0 +1,25 @@
+module 0xc0ffee::m {
+    use std::signer;
+
+    struct Config has key {
+        admin: address,
+        value: u64,
+    }
+
+    public entry fun set_value_warn(_admin: &signer, value: u64) {
+        borrow_global_mut<Config>(@0xc0ffee).value = value;
+    }
+
+    entry fun private_entry_warn(_account: signer) {}
+
+    public entry fun set_value_no_warn(admin: &signer, value: u64) {
+        let config = borrow_global_mut<Config>(@0xc0ffee);
+        assert!(signer::address_of(admin) == config.admin, 1);
+        config.value = value;
+    }
+
+    public fun not_entry_no_warn(_account: &signer) {}
+
+    #[lint::skip(unchecked_signer)]
+    public entry fun suppressed_no_warn(_admin: &signer) {}
+}
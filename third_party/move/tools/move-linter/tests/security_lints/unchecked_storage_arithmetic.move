@@ -0,0 +1,33 @@
+module 0xc0ffee::m {
+    use std::signer;
+
+    struct Vault has key {
+        amount: u64,
+    }
+
+    fun withdraw_warn(account: &signer, value: u64) {
+        let vault = borrow_global_mut<Vault>(signer::address_of(account));
+        vault.amount = vault.amount - value;
+    }
+
+    fun deposit_warn(account: &signer, value: u64): u64 {
+        let amount = borrow_global<Vault>(signer::address_of(account)).amount;
+        amount + value
+    }
+
+    fun withdraw_no_warn(account: &signer, value: u64) {
+        let vault = borrow_global_mut<Vault>(signer::address_of(account));
+        assert!(vault.amount >= value, 1);
+        vault.amount = vault.amount - value;
+    }
+
+    fun total_no_warn(a: u64, b: u64): u64 {
+        a + b
+    }
+
+    #[lint::skip(unchecked_storage_arithmetic)]
+    fun suppressed_no_warn(account: &signer, value: u64) {
+        let vault = borrow_global_mut<Vault>(signer::address_of(account));
+        vault.amount = vault.amount + value;
+    }
+}
@@ -17,6 +17,12 @@ pub const EXP_EXT: &str = "exp";
 datatest_stable::harness!(test_runner, "tests", r".*\.move$");
 
 fn test_runner(path: &Path) -> datatest_stable::Result<()> {
+    // Security checks are a separate category, which is not included in "experimental".
+    let checks = if path.components().any(|c| c.as_os_str() == "security_lints") {
+        "security"
+    } else {
+        "experimental"
+    };
     let compiler_options = move_compiler_v2::Options {
         sources: vec![path.display().to_string()],
         dependencies: vec![path_from_crate_root("../../move-stdlib/sources")],
@@ -30,7 +36,7 @@ fn test_runner(path: &Path) -> datatest_stable::Result<()> {
         experiments: vec![Experiment::LINT_CHECKS.to_string()],
         external_checks: vec![MoveLintChecks::make(BTreeMap::from([(
             "checks".to_string(),
-            "experimental".to_string(),
+            checks.to_string(),
         )]))],
         ..Default::default()
     };
@@ -1,7 +1,7 @@
 // Copyright (c) A-p-t-o-s Foundation
 // SPDX-License-Identifier: Apache-2.0
 
-use anyhow::{anyhow, Result};
+use anyhow::{anyhow, bail, Result};
 use std::ops::Deref;
 use url::Url;
 
@@ -15,10 +15,12 @@ pub struct CanonicalGitIdentity(String);
 
 impl CanonicalGitIdentity {
     pub fn new(git_url: &Url) -> Result<Self> {
-        let host = git_url
-            .host_str()
-            .ok_or_else(|| anyhow!("invalid git URL, unable to extract host: {}", git_url))?
-            .to_ascii_lowercase();
+        let host = match git_url.host_str() {
+            Some(host) => host.to_ascii_lowercase(),
+            // Local repositories, e.g. `file:///path/to/repo`, are identified by their path only.
+            None if git_url.scheme() == "file" => "".to_string(),
+            None => bail!("invalid git URL, unable to extract host: {}", git_url),
+        };
 
         let port = match git_url.port() {
             Some(port) => match (git_url.scheme(), port) {
@@ -61,6 +63,11 @@ fn test_canonical_git_identity() {
         );
     }
 
+    assert_eq!(
+        &*CanonicalGitIdentity::new(&Url::parse("file:///foo/bar.git").unwrap()).unwrap(),
+        "/foo/bar"
+    );
+
     #[allow(clippy::single_element_loop)]
     for url in ["https://github.com/foo/bar.git/abc"] {
         assert_ne!(
@@ -24,6 +24,15 @@
 //! └── on-chain/          # Fetched bytecode packages
 //! ```
 //!
+//! ## Lock Files, Vendoring & Offline Mode
+//!
+//! To make builds reproducible, the resolved Git commits and on-chain network versions can be
+//! pinned in a `Move.lock` file, along with a content hash of each dependency. The lock file is
+//! also honored by the package resolver of `move-package`, so the pins apply to regular builds.
+//! Pinned dependencies can be copied into a vendor directory with the layout of the Move home
+//! directory, so that builds with `MOVE_HOME` set to it work in offline mode, where any attempt
+//! to access the network fails instead.
+//!
 //! ## Concurrency & Safety
 //!
 //! The package cache is designed to allow safe concurrent access:
@@ -45,4 +54,8 @@ mod listener;
 mod package_cache;
 
 pub use listener::{DebugPackageCacheListener, EmptyPackageCacheListener, PackageCacheListener};
-pub use package_cache::PackageCache;
+pub use move_package::lock_file::{
+    hash_directory, hash_package_sources, normalize_git_url, LockedGitDependency,
+    LockedOnChainPackage, PackageLock, LOCK_FILE_NAME,
+};
+pub use package_cache::{resolve_node_url, PackageCache};
@@ -2,35 +2,86 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use anyhow::Result;
-use move_core_types::account_address::AccountAddress;
-use move_package_cache::{DebugPackageCacheListener, PackageCache};
-use std::str::FromStr;
-use url::Url;
+use clap::{Parser, Subcommand};
+use move_package_cache::{PackageCache, PackageLock, LOCK_FILE_NAME};
+use std::path::PathBuf;
 
-// Note: this is just sample workflow demonstrating how the package cache can be used as a library.
-// It will likely be removed later as the package cache is intended to be integrated into
-// other tools rather than used as a standalone executable.
+// Note: this is a thin command line wrapper around the package cache, mainly meant for release
+// pipelines which need to pin and vendor dependencies ahead of time. The package cache is
+// intended to be integrated into other tools rather than used as a standalone executable.
+
+#[derive(Parser)]
+#[clap(
+    name = "move-package-cache",
+    about = "Fetch, pin and vendor Move package dependencies"
+)]
+struct Args {
+    /// Root directory of the package cache.
+    #[clap(long, global = true, default_value = "./data")]
+    cache_dir: PathBuf,
+
+    /// Fail instead of fetching anything from the network.
+    #[clap(long, global = true)]
+    offline: bool,
+
+    #[clap(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Fetch the remote dependencies of a package, including transitive ones, and pin them in
+    /// its `Move.lock`.
+    Fetch {
+        /// Directory of the package, containing `Move.toml`.
+        #[clap(long, default_value = ".")]
+        package_dir: PathBuf,
+
+        /// Fail if `Move.lock` is missing or out of date, instead of updating it.
+        #[clap(long)]
+        locked: bool,
+    },
+    /// Copy all the dependencies pinned in `Move.lock` into a vendor directory, replacing its
+    /// previous contents.
+    ///
+    /// The package can then be built from the vendored dependencies alone, by setting
+    /// `MOVE_HOME=<VENDOR_DIR>` and building in offline mode, e.g. with
+    /// `creditchain move compile --offline`.
+    Vendor {
+        /// Directory of the package, containing `Move.toml` and `Move.lock`.
+        #[clap(long, default_value = ".")]
+        package_dir: PathBuf,
+
+        /// Directory to copy the dependencies into. Defaults to `<PACKAGE_DIR>/vendor`.
+        #[clap(long)]
+        vendor_dir: Option<PathBuf>,
+    },
+}
 
 #[tokio::main]
 async fn main() -> Result<()> {
-    let cache = PackageCache::new_with_listener("./data", DebugPackageCacheListener).unwrap();
-
-    let creditchain_framework_url =
-        Url::from_str("https://github.com/libra2org/libra2-framework").unwrap();
-
-    let oid = cache
-        .resolve_git_revision(&creditchain_framework_url, "main")
-        .await?;
-    cache.checkout_git_repo(&creditchain_framework_url, oid).await?;
-
-    cache
-        .fetch_on_chain_package(
-            Url::from_str("https://fullnode.mainnet.creditchain.io").unwrap(),
-            3022354983,
-            AccountAddress::ONE,
-            "MoveStdlib",
-        )
-        .await?;
+    let args = Args::parse();
+    let mut cache = PackageCache::new(&args.cache_dir)?;
+    cache.set_offline(args.offline);
+
+    match args.command {
+        Command::Fetch {
+            package_dir,
+            locked,
+        } => {
+            cache
+                .fetch_package_dependencies(&package_dir, locked)
+                .await?;
+        },
+        Command::Vendor {
+            package_dir,
+            vendor_dir,
+        } => {
+            let lock = PackageLock::load(package_dir.join(LOCK_FILE_NAME))?;
+            let vendor_dir = vendor_dir.unwrap_or_else(|| package_dir.join("vendor"));
+            cache.vendor(&lock, &vendor_dir).await?;
+        },
+    }
 
     Ok(())
 }
@@ -6,21 +6,32 @@ use crate::{
     file_lock::FileLock,
     listener::{EmptyPackageCacheListener, PackageCacheListener},
 };
-use anyhow::{bail, Result};
-use creditchain_framework::natives::code::PackageRegistry;
+use anyhow::{bail, Context, Result};
+use creditchain_framework::{natives::code::PackageRegistry, unzip_metadata_str};
 use futures::future;
 use git2::{
     build::RepoBuilder, FetchOptions, ObjectType, Oid, RemoteCallbacks, Repository, TreeWalkResult,
 };
 use move_core_types::account_address::AccountAddress;
+use move_package::{
+    lock_file::{
+        hash_directory, hash_package_sources, LockedGitDependency, LockedOnChainPackage,
+        PackageLock, LOCK_FILE_NAME,
+    },
+    source_package::manifest_parser::{git_locked_checkout_dir_name, on_chain_package_dir_name},
+};
+use move_package_manifest::{parse_package_manifest, PackageLocation};
 use percent_encoding::{AsciiSet, CONTROLS};
 use std::{
+    collections::BTreeSet,
     fs::{self, File},
     io::Write,
     path::{Path, PathBuf},
+    str::FromStr,
     time::Duration,
 };
 use url::Url;
+use walkdir::WalkDir;
 
 /// Removes a directory if it exists, ignoring "directory not found" errors.
 fn remove_dir_if_exists(path: &Path) -> std::io::Result<()> {
@@ -31,13 +42,62 @@ fn remove_dir_if_exists(path: &Path) -> std::io::Result<()> {
     }
 }
 
+/// Recursively copies the contents of a directory into another, creating it if needed.
+fn copy_dir(from: &Path, to: &Path) -> Result<()> {
+    for entry in WalkDir::new(from) {
+        let entry = entry?;
+        let target = to.join(entry.path().strip_prefix(from)?);
+        if entry.file_type().is_dir() {
+            fs::create_dir_all(&target)?;
+        } else {
+            fs::copy(entry.path(), &target)?;
+        }
+    }
+    Ok(())
+}
+
+/// Resolves the node URL of an on-chain dependency, which is either the URL of a fullnode or the
+/// name of a known network (e.g. "mainnet").
+pub fn resolve_node_url(node_url: &str) -> Result<Url> {
+    let url = match node_url {
+        "mainnet" => "https://fullnode.mainnet.creditchain.io",
+        "testnet" => "https://fullnode.testnet.creditchain.io",
+        "devnet" => "https://fullnode.devnet.creditchain.io",
+        "local" => "http://localhost:8080",
+        url => url,
+    };
+    Url::parse(url).map_err(|err| anyhow::anyhow!("invalid node URL {:?}: {}", node_url, err))
+}
+
+/// Reads the dependencies and dev dependencies declared in the manifest of a package, along
+/// with their names.
+fn read_dependencies(package_dir: &Path) -> Result<Vec<(String, PackageLocation)>> {
+    let manifest_path = package_dir.join("Move.toml");
+    let manifest = parse_package_manifest(
+        &fs::read_to_string(&manifest_path)
+            .with_context(|| format!("failed to read {}", manifest_path.display()))?,
+    )
+    .with_context(|| format!("failed to parse {}", manifest_path.display()))?;
+
+    Ok(manifest
+        .dependencies
+        .iter()
+        .chain(manifest.dev_dependencies.iter())
+        .map(|(name, dep)| (name.to_string(), dep.location().clone()))
+        .collect())
+}
+
 /// A Move package cache that manages Git repositories and on-chain packages.
 ///
 /// The cache provides async APIs for fetching and storing remote dependencies.
 /// Safe concurrent access is provided through file-based locking.
+///
+/// In offline mode, the cache never touches the network: requests which cannot be served from
+/// the local data fail instead.
 pub struct PackageCache<L> {
     root: PathBuf,
     listener: L,
+    offline: bool,
 }
 
 /// An opened Git repository with an active file lock.
@@ -83,7 +143,16 @@ impl<L> PackageCache<L> {
 
         fs::create_dir_all(&root)?;
 
-        Ok(PackageCache { root, listener })
+        Ok(PackageCache {
+            root,
+            listener,
+            offline: false,
+        })
+    }
+
+    /// Enables or disables offline mode.
+    pub fn set_offline(&mut self, offline: bool) {
+        self.offline = offline;
     }
 
     /// Clones or updates a Git repository, ensuring it is available locally with up-to-date data.
@@ -134,7 +203,13 @@ impl<L> PackageCache<L> {
         let mut fetch_options = FetchOptions::new();
         fetch_options.remote_callbacks(cbs);
 
-        let repo = if repo_path.exists() {
+        let repo = if self.offline {
+            // In offline mode, use the repo as it is, without updating it.
+            if !repo_path.exists() {
+                bail!("cannot clone {} in offline mode", git_url);
+            }
+            Repository::open_bare(&repo_path)?
+        } else if repo_path.exists() {
             // If the repo already exists, update it.
             self.listener.on_repo_update_start(git_url.as_str());
 
@@ -259,8 +334,9 @@ impl<L> PackageCache<L> {
     /// Fetches an on-chain package from the specified network and version and stores it locally.
     /// Returns the path to the cached package.
     ///
-    /// The cached package currently only contains the bytecode modules, but may be extended with
-    /// additional metadata in the future.
+    /// The cached package contains the bytecode modules, as well as the manifest and the sources
+    /// (of the modules published with their source) laid out as a source package, which is what
+    /// builds of dependent packages use.
     pub async fn fetch_on_chain_package(
         &self,
         fullnode_url: Url,
@@ -289,7 +365,16 @@ impl<L> PackageCache<L> {
         }
 
         // Package directory does not exist -- need to download the package and cache it.
-        //
+        if self.offline {
+            bail!(
+                "cannot download {}//{}::{} at version {} in offline mode",
+                fullnode_url,
+                address,
+                package_name,
+                network_version
+            );
+        }
+
         // First, acquire a lock to ensure exclusive write access to this package.
         let lock_path = cached_package_path.with_extension("lock");
 
@@ -382,12 +467,258 @@ impl<L> PackageCache<L> {
 
         future::try_join_all(fetch_futures).await?;
 
+        // Lay out the manifest and sources the same way as the Move home directory does.
+        fs::write(
+            temp.path().join("Move.toml"),
+            unzip_metadata_str(&package.manifest)?,
+        )?;
+        for module in package
+            .modules
+            .iter()
+            .filter(|module| !module.source.is_empty())
+        {
+            let sources_path = temp.path().join("sources");
+            fs::create_dir_all(&sources_path)?;
+            fs::write(
+                sources_path.join(format!("{}.move", module.name)),
+                unzip_metadata_str(&module.source)?,
+            )?;
+        }
+
         remove_dir_if_exists(&cached_package_path)?;
-        fs::rename(temp.into_path(), cached_package_path)?;
+        fs::rename(temp.into_path(), &cached_package_path)?;
 
         self.listener
             .on_bytecode_package_download_complete(address, package_name);
 
-        Ok(PathBuf::new())
+        Ok(cached_package_path)
+    }
+
+    /// Checks out a Git dependency and returns the path to the checkout.
+    ///
+    /// If the lock has an entry for the URL and revision, the pinned commit is checked out and
+    /// its contents are verified against the recorded hash. Otherwise, the revision is resolved
+    /// and the resulting commit is added to the lock.
+    pub async fn resolve_git_dependency(
+        &self,
+        git_url: &Url,
+        rev: &str,
+        lock: &mut PackageLock,
+    ) -> Result<PathBuf>
+    where
+        L: PackageCacheListener,
+    {
+        if let Some(locked) = lock.git_dependency(git_url.as_str(), rev) {
+            let oid = Oid::from_str(&locked.commit)?;
+            let checkout_path = self.checkout_git_repo(git_url, oid).await?;
+            locked.verify(&checkout_path)?;
+            return Ok(checkout_path);
+        }
+
+        let oid = self.resolve_git_revision(git_url, rev).await?;
+        let checkout_path = self.checkout_git_repo(git_url, oid).await?;
+        lock.insert_git_dependency(LockedGitDependency {
+            url: git_url.to_string(),
+            rev: rev.to_string(),
+            commit: oid.to_string(),
+            hash: hash_directory(&checkout_path)?,
+        });
+        Ok(checkout_path)
+    }
+
+    /// Fetches an on-chain package and returns the path to the cached package.
+    ///
+    /// `node_url` is the node URL as written in the manifest, i.e. either the URL of a fullnode or
+    /// the name of a known network, see [`resolve_node_url`]. The lock entry is keyed by it as is.
+    ///
+    /// If the lock has an entry for the package, it is fetched at the pinned network version
+    /// and its contents are verified against the recorded hash. Otherwise, the package is fetched
+    /// at the latest version of the network, which is then added to the lock.
+    pub async fn resolve_on_chain_package(
+        &self,
+        node_url: &str,
+        address: AccountAddress,
+        package_name: &str,
+        lock: &mut PackageLock,
+    ) -> Result<PathBuf>
+    where
+        L: PackageCacheListener,
+    {
+        let fullnode_url = resolve_node_url(node_url)?;
+        if let Some(locked) = lock.on_chain_package(node_url, address, package_name) {
+            let package_path = self
+                .fetch_on_chain_package(fullnode_url, locked.network_version, address, package_name)
+                .await?;
+            locked.verify(&package_path)?;
+            return Ok(package_path);
+        }
+
+        if self.offline {
+            bail!(
+                "cannot determine the network version of {}//{}::{} in offline mode, \
+                 as it is not pinned in the lock file",
+                fullnode_url,
+                address,
+                package_name
+            );
+        }
+        let network_version = creditchain_rest_client::Client::new(fullnode_url.clone())
+            .get_ledger_information()
+            .await?
+            .into_inner()
+            .version;
+        let package_path = self
+            .fetch_on_chain_package(fullnode_url, network_version, address, package_name)
+            .await?;
+        lock.insert_on_chain_package(LockedOnChainPackage {
+            node_url: node_url.to_string(),
+            address,
+            package_name: package_name.to_string(),
+            network_version,
+            hash: hash_package_sources(&package_path)?,
+        });
+        Ok(package_path)
+    }
+
+    /// Fetches the remote dependencies of a package, including transitive ones, and returns a
+    /// lock pinning all of them.
+    ///
+    /// The entries of the package's `Move.lock` are reused where they apply. With `locked`, the
+    /// lock file must exist and be up to date, otherwise it is updated.
+    pub async fn fetch_package_dependencies(
+        &self,
+        package_dir: &Path,
+        locked: bool,
+    ) -> Result<PackageLock>
+    where
+        L: PackageCacheListener,
+    {
+        let lock_path = package_dir.join(LOCK_FILE_NAME);
+        let mut original = if locked {
+            PackageLock::load(&lock_path)?
+        } else {
+            PackageLock::load_or_default(&lock_path)?
+        };
+
+        let lock = self
+            .resolve_package_dependencies(package_dir, &original)
+            .await?;
+        if locked {
+            original.sort();
+            if lock != original {
+                bail!("{} is out of date", lock_path.display());
+            }
+        } else {
+            lock.save(&lock_path)?;
+        }
+        Ok(lock)
+    }
+
+    /// Fetches the remote dependencies of a package, and recursively those of its dependencies.
+    /// Returns a lock pinning all of them, reusing the entries of `original` where they apply.
+    async fn resolve_package_dependencies(
+        &self,
+        package_dir: &Path,
+        original: &PackageLock,
+    ) -> Result<PackageLock>
+    where
+        L: PackageCacheListener,
+    {
+        // Only keep the entries which are still in use, so that removed dependencies disappear
+        // from the lock file.
+        let mut lock = PackageLock::default();
+        let mut visited = BTreeSet::new();
+        let mut worklist = vec![package_dir.to_owned()];
+        while let Some(dir) = worklist.pop() {
+            if !visited.insert(fs::canonicalize(&dir)?) {
+                continue;
+            }
+            for (name, location) in read_dependencies(&dir)? {
+                match location {
+                    PackageLocation::Local { path } => worklist.push(dir.join(path)),
+                    PackageLocation::Git { url, rev, subdir } => {
+                        let rev = rev.with_context(|| {
+                            format!("git revision not supplied for dependency {}", name)
+                        })?;
+                        if let Some(locked) = original.git_dependency(url.as_str(), &rev) {
+                            lock.insert_git_dependency(locked.clone());
+                        }
+                        let checkout_path =
+                            self.resolve_git_dependency(&url, &rev, &mut lock).await?;
+                        worklist.push(checkout_path.join(subdir.unwrap_or_default()));
+                    },
+                    PackageLocation::CreditChain {
+                        node_url,
+                        package_addr,
+                    } => {
+                        let address =
+                            AccountAddress::from_str(&package_addr).with_context(|| {
+                                format!("invalid address {} for dependency {}", package_addr, name)
+                            })?;
+                        if let Some(locked) = original.on_chain_package(&node_url, address, &name) {
+                            lock.insert_on_chain_package(locked.clone());
+                        }
+                        self.resolve_on_chain_package(&node_url, address, &name, &mut lock)
+                            .await?;
+                    },
+                }
+            }
+        }
+        lock.sort();
+        Ok(lock)
+    }
+
+    /// Copies all the dependencies pinned in the lock into `vendor_dir`, after verifying their
+    /// contents. The previous contents of `vendor_dir` are removed.
+    ///
+    /// The vendor directory has the same layout as the Move home directory after a build with the
+    /// lock file, so that packages can be built from the vendored dependencies alone, e.g. in an
+    /// air-gapped environment, with `MOVE_HOME=<vendor_dir>` and in offline mode.
+    pub async fn vendor(&self, lock: &PackageLock, vendor_dir: &Path) -> Result<()>
+    where
+        L: PackageCacheListener,
+    {
+        if vendor_dir.exists() && fs::canonicalize(vendor_dir)? == fs::canonicalize(&self.root)? {
+            bail!("cannot vendor dependencies into the package cache itself");
+        }
+
+        let mut lock = lock.clone();
+        let mut copies = vec![];
+        for dep in lock.git.clone() {
+            let git_url = Url::parse(&dep.url)?;
+            let checkout_path = self
+                .resolve_git_dependency(&git_url, &dep.rev, &mut lock)
+                .await?;
+            copies.push((
+                checkout_path,
+                git_locked_checkout_dir_name(&dep.url, &dep.commit),
+            ));
+        }
+        for package in lock.on_chain.clone() {
+            let package_path = self
+                .resolve_on_chain_package(
+                    &package.node_url,
+                    package.address,
+                    &package.package_name,
+                    &mut lock,
+                )
+                .await?;
+            copies.push((
+                package_path,
+                on_chain_package_dir_name(
+                    &package.node_url,
+                    &package.address.to_hex_literal(),
+                    &package.package_name,
+                ),
+            ));
+        }
+
+        // Start from a clean slate, so that no stale dependencies are left behind.
+        remove_dir_if_exists(vendor_dir)?;
+        for (path, dir_name) in copies {
+            copy_dir(&path, &vendor_dir.join(dir_name))?;
+        }
+
+        Ok(())
     }
 }
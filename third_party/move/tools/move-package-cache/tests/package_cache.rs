@@ -0,0 +1,240 @@
+// Copyright (c) A-p-t-o-s Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use move_package::BuildConfig;
+use move_package_cache::{EmptyPackageCacheListener, PackageCache, PackageLock, LOCK_FILE_NAME};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    process::Command,
+};
+use tempfile::{tempdir, TempDir};
+use url::Url;
+
+/// Runs a git command in the given directory and returns its trimmed output.
+fn git(dir: &Path, args: &[&str]) -> String {
+    let output = Command::new("git")
+        .args(["-c", "user.name=test", "-c", "user.email=test@example.com"])
+        .arg("-C")
+        .arg(dir)
+        .args(args)
+        .output()
+        .unwrap();
+    assert!(output.status.success(), "git {:?} failed", args);
+    String::from_utf8(output.stdout).unwrap().trim().to_string()
+}
+
+/// Writes a package with a single (empty) module and commits it.
+fn commit_package(repo: &Path, module_name: &str) -> String {
+    fs::create_dir_all(repo.join("sources")).unwrap();
+    fs::write(
+        repo.join("Move.toml"),
+        "[package]\nname = \"Dep\"\nversion = \"0.0.0\"\n",
+    )
+    .unwrap();
+    fs::write(
+        repo.join("sources").join(format!("{}.move", module_name)),
+        format!("module 0x42::{} {{}}\n", module_name),
+    )
+    .unwrap();
+    git(repo, &["add", "-A"]);
+    git(repo, &["commit", "-m", module_name]);
+    git(repo, &["rev-parse", "HEAD"])
+}
+
+/// A local Git repository containing a package, and a package depending on its `main` branch.
+struct Fixture {
+    dir: TempDir,
+    repo: PathBuf,
+    git_url: Url,
+    package: PathBuf,
+    first_commit: String,
+}
+
+impl Fixture {
+    fn new() -> Self {
+        let dir = tempdir().unwrap();
+
+        let repo = dir.path().join("dep");
+        fs::create_dir_all(&repo).unwrap();
+        git(&repo, &["init", "-q"]);
+        git(&repo, &["checkout", "-q", "-b", "main"]);
+        let first_commit = commit_package(&repo, "first");
+        let git_url = Url::from_directory_path(&repo).unwrap();
+
+        let package = dir.path().join("package");
+        fs::create_dir_all(package.join("sources")).unwrap();
+        fs::write(
+            package.join("Move.toml"),
+            format!(
+                "[package]\nname = \"Root\"\nversion = \"0.0.0\"\n\n\
+                 [dependencies]\nDep = {{ git = \"{}\", rev = \"main\" }}\n",
+                git_url
+            ),
+        )
+        .unwrap();
+        fs::write(
+            package.join("sources").join("root.move"),
+            "module 0x42::root {}\n",
+        )
+        .unwrap();
+
+        Self {
+            dir,
+            repo,
+            git_url,
+            package,
+            first_commit,
+        }
+    }
+
+    fn cache(&self, offline: bool) -> PackageCache<EmptyPackageCacheListener> {
+        let mut cache = PackageCache::new(self.dir.path().join("cache")).unwrap();
+        cache.set_offline(offline);
+        cache
+    }
+}
+
+#[tokio::test]
+async fn test_fetch_locked() {
+    let fixture = Fixture::new();
+    let cache = fixture.cache(false);
+
+    // Without a lock file, a locked fetch fails
+    let err = cache
+        .fetch_package_dependencies(&fixture.package, true)
+        .await
+        .unwrap_err();
+    assert!(format!("{:#}", err).contains(LOCK_FILE_NAME), "{:#}", err);
+
+    // Otherwise, the dependencies are pinned in the lock file
+    let lock = cache
+        .fetch_package_dependencies(&fixture.package, false)
+        .await
+        .unwrap();
+    assert_eq!(lock.git.len(), 1);
+    assert_eq!(lock.git[0].commit, fixture.first_commit);
+    assert_eq!(
+        PackageLock::load(fixture.package.join(LOCK_FILE_NAME)).unwrap(),
+        lock
+    );
+
+    // New commits do not affect a locked fetch
+    commit_package(&fixture.repo, "second");
+    assert_eq!(
+        cache
+            .fetch_package_dependencies(&fixture.package, true)
+            .await
+            .unwrap(),
+        lock
+    );
+
+    // A lock file which does not match the manifest is rejected
+    fs::write(
+        fixture.package.join("Move.toml"),
+        "[package]\nname = \"Root\"\nversion = \"0.0.0\"\n",
+    )
+    .unwrap();
+    let err = cache
+        .fetch_package_dependencies(&fixture.package, true)
+        .await
+        .unwrap_err();
+    assert!(format!("{:#}", err).contains("is out of date"), "{:#}", err);
+}
+
+#[tokio::test]
+async fn test_hash_mismatch() {
+    let fixture = Fixture::new();
+    let cache = fixture.cache(false);
+    let mut lock = cache
+        .fetch_package_dependencies(&fixture.package, false)
+        .await
+        .unwrap();
+
+    // Tamper with the cached checkout of the pinned commit
+    let checkout = cache
+        .resolve_git_dependency(&fixture.git_url, "main", &mut lock)
+        .await
+        .unwrap();
+    lock.git[0].verify(&checkout).unwrap();
+    fs::write(checkout.join("sources").join("first.move"), "").unwrap();
+
+    let err = lock.git[0].verify(&checkout).unwrap_err();
+    assert!(
+        format!("{:#}", err).contains("content hash mismatch"),
+        "{:#}",
+        err
+    );
+    let err = cache
+        .fetch_package_dependencies(&fixture.package, true)
+        .await
+        .unwrap_err();
+    assert!(
+        format!("{:#}", err).contains("content hash mismatch"),
+        "{:#}",
+        err
+    );
+}
+
+#[tokio::test]
+async fn test_offline() {
+    let fixture = Fixture::new();
+
+    // Nothing is cached yet, so nothing can be resolved in offline mode
+    let err = fixture
+        .cache(true)
+        .fetch_package_dependencies(&fixture.package, false)
+        .await
+        .unwrap_err();
+    assert!(
+        format!("{:#}", err).contains("in offline mode"),
+        "{:#}",
+        err
+    );
+
+    fixture
+        .cache(false)
+        .fetch_package_dependencies(&fixture.package, false)
+        .await
+        .unwrap();
+
+    // Once cached, the dependencies are resolved from the cache, without picking up new commits
+    commit_package(&fixture.repo, "second");
+    fs::remove_file(fixture.package.join(LOCK_FILE_NAME)).unwrap();
+    let lock = fixture
+        .cache(true)
+        .fetch_package_dependencies(&fixture.package, false)
+        .await
+        .unwrap();
+    assert_eq!(lock.git[0].commit, fixture.first_commit);
+}
+
+#[tokio::test]
+async fn test_vendor_and_offline_build() {
+    let fixture = Fixture::new();
+    let cache = fixture.cache(false);
+    let lock = cache
+        .fetch_package_dependencies(&fixture.package, false)
+        .await
+        .unwrap();
+    commit_package(&fixture.repo, "second");
+
+    let vendor_dir = fixture.dir.path().join("vendor");
+    cache.vendor(&lock, &vendor_dir).await.unwrap();
+
+    // Neither the repository nor the cache are needed anymore
+    fs::remove_dir_all(&fixture.repo).unwrap();
+    fs::remove_dir_all(fixture.dir.path().join("cache")).unwrap();
+
+    std::env::set_var("MOVE_HOME", &vendor_dir);
+    let package = BuildConfig {
+        install_dir: Some(fixture.dir.path().join("build")),
+        offline: true,
+        ..Default::default()
+    }
+    .compile_package(&fixture.package, &mut Vec::new())
+    .unwrap();
+    assert_eq!(package.root_compiled_units.len(), 1);
+    assert_eq!(package.deps_compiled_units.len(), 1);
+    assert_eq!(package.deps_compiled_units[0].0.as_str(), "Dep");
+}
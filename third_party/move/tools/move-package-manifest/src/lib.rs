@@ -7,8 +7,8 @@ mod package_name;
 mod util;
 
 pub use manifest::{
-    AddressAssignment, BuildOptions, Dependency, PackageLocation, PackageManifest, PackageMetadata,
-    Version,
+    AddressAssignment, BuildOptions, Dependency, LintCategory, LintLevel, LintOptions,
+    PackageLocation, PackageManifest, PackageMetadata, Version,
 };
 pub use named_address::NamedAddress;
 pub use package_name::PackageName;
@@ -17,3 +17,14 @@ pub use util::render_error;
 pub fn parse_package_manifest(s: &str) -> Result<PackageManifest, toml::de::Error> {
     toml::from_str(s)
 }
+
+/// Parses only the `[lint]` section of a `Move.toml` manifest, ignoring all the other sections.
+/// Returns `None` if there is no `[lint]` section.
+pub fn parse_lint_options(s: &str) -> Result<Option<LintOptions>, toml::de::Error> {
+    #[derive(serde::Deserialize)]
+    struct LintSection {
+        lint: Option<LintOptions>,
+    }
+
+    toml::from_str::<LintSection>(s).map(|section| section.lint)
+}
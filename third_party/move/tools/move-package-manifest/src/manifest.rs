@@ -36,6 +36,9 @@ pub struct PackageManifest {
     /// Build options.
     pub build: Option<BuildOptions>,
 
+    /// Lint options.
+    pub lint: Option<LintOptions>,
+
     /// Regular (non-dev) package dependencies.
     #[serde(default)]
     pub dependencies: BTreeMap<PackageName, Dependency>,
@@ -107,6 +110,65 @@ pub struct BuildOptions {
     pub language_version: Option<LanguageVersion>,
 }
 
+/// Lint options defined in the `[lint]` section of `Move.toml`.
+///
+/// Example:
+/// ```toml
+/// [lint]
+/// checks = "strict"
+/// warnings_as_errors = false
+///
+/// [lint.levels]
+/// security = "deny"
+/// needless_bool = "allow"
+/// ```
+#[derive(Default, Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct LintOptions {
+    /// Category of lint checks to run. Defaults to the default checks.
+    pub checks: Option<LintCategory>,
+
+    /// Whether all lint warnings should be reported as errors.
+    #[serde(default)]
+    pub warnings_as_errors: bool,
+
+    /// Levels of individual lint checks or whole categories, keyed by their names.
+    /// The level of a lint check takes precedence over the level of its category.
+    #[serde(default)]
+    pub levels: BTreeMap<String, LintLevel>,
+}
+
+/// Category of lint checks.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LintCategory {
+    /// Lint checks curated to minimize false positives.
+    Default,
+
+    /// Lint checks that are more strict and may produce more false positives.
+    Strict,
+
+    /// Lint checks that are not yet stable.
+    Experimental,
+
+    /// Security-focused lint checks.
+    Security,
+}
+
+/// Level of a lint check or category of lint checks.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LintLevel {
+    /// The lint check is not run.
+    Allow,
+
+    /// The lint check is reported as a warning.
+    Warn,
+
+    /// The lint check is reported as an error, failing the build.
+    Deny,
+}
+
 /// Represents a dependency entry in `[dependencies]` or `[dev-dependencies]`.
 #[derive(Debug, Clone, Eq, PartialEq)]
 pub struct Dependency {
@@ -343,6 +405,11 @@ impl Serialize for AddressAssignment {
 }
 
 impl Dependency {
+    /// Returns the location of the dependency.
+    pub fn location(&self) -> &PackageLocation {
+        &self.location
+    }
+
     fn into_raw(self) -> RawDependency {
         let mut raw = RawDependency {
             version: self.version,
@@ -423,3 +490,59 @@ impl Debug for Version {
         write!(f, "\"{}.{}.{}\"", self.major, self.minor, self.patch)
     }
 }
+
+impl Display for LintCategory {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            LintCategory::Default => "default",
+            LintCategory::Strict => "strict",
+            LintCategory::Experimental => "experimental",
+            LintCategory::Security => "security",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+impl Display for LintLevel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            LintLevel::Allow => "allow",
+            LintLevel::Warn => "warn",
+            LintLevel::Deny => "deny",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+impl FromStr for LintCategory {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> anyhow::Result<Self> {
+        match s {
+            "default" => Ok(LintCategory::Default),
+            "strict" => Ok(LintCategory::Strict),
+            "experimental" => Ok(LintCategory::Experimental),
+            "security" => Ok(LintCategory::Security),
+            _ => anyhow::bail!(
+                "Invalid lint category `{}`, expected one of: `default`, `strict`, `experimental`, or `security`",
+                s
+            ),
+        }
+    }
+}
+
+impl FromStr for LintLevel {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> anyhow::Result<Self> {
+        match s {
+            "allow" => Ok(LintLevel::Allow),
+            "warn" => Ok(LintLevel::Warn),
+            "deny" => Ok(LintLevel::Deny),
+            _ => anyhow::bail!(
+                "Invalid lint level `{}`, expected one of: `allow`, `warn`, or `deny`",
+                s
+            ),
+        }
+    }
+}
@@ -5,6 +5,7 @@
 mod package_lock;
 
 pub mod compilation;
+pub mod lock_file;
 pub mod package_hooks;
 pub mod resolution;
 pub mod source_package;
@@ -13,6 +14,7 @@ use crate::{
     compilation::{
         build_plan::BuildPlan, compiled_package::CompiledPackage, model_builder::ModelBuilder,
     },
+    lock_file::LOCK_FILE_NAME,
     package_lock::PackageLock,
     resolution::resolution_graph::{ResolutionGraph, ResolvedGraph},
     source_package::manifest_parser,
@@ -91,6 +93,12 @@ pub struct BuildConfig {
     #[clap(long = "skip-fetch-latest-git-deps", global = true)]
     pub skip_fetch_latest_git_deps: bool,
 
+    /// Fail instead of accessing the network to fetch dependencies. Remote dependencies must
+    /// already be in MOVE_HOME, and Git dependencies are not updated.
+    #[clap(long = "offline", global = true)]
+    #[serde(default)]
+    pub offline: bool,
+
     #[clap(flatten)]
     pub compiler_config: CompilerConfig,
 }
@@ -187,11 +195,12 @@ impl BuildConfig {
         let path = SourcePackageLayout::try_find_root(path)?;
         let toml_manifest =
             self.parse_toml_manifest(path.join(SourcePackageLayout::Manifest.path()))?;
+        let package_lock = lock_file::PackageLock::load_or_default(path.join(LOCK_FILE_NAME))?;
         let mutx = PackageLock::strict_lock();
         // This should be locked as it inspects the environment for `MOVE_HOME` which could
         // possibly be set by a different process in parallel.
         let manifest = manifest_parser::parse_source_manifest(toml_manifest)?;
-        ResolutionGraph::download_dependency_repos(&manifest, self, &path, writer)?;
+        ResolutionGraph::download_dependency_repos(&manifest, self, &package_lock, &path, writer)?;
         mutx.unlock();
         Ok(())
     }
@@ -0,0 +1,258 @@
+// Copyright (c) A-p-t-o-s Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::{bail, Context, Result};
+use move_core_types::account_address::AccountAddress;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::{fmt::Display, fs, path::Path};
+use url::Url;
+use walkdir::WalkDir;
+
+/// Name of the lock file, stored next to the package manifest.
+pub const LOCK_FILE_NAME: &str = "Move.lock";
+
+/// Current version of the lock file format.
+const LOCK_FILE_VERSION: u64 = 1;
+
+const LOCK_FILE_HEADER: &str = concat!(
+    "# This file is generated by the Move package cache. ",
+    "It is not intended for manual editing.\n\n"
+);
+
+/// The contents of a `Move.lock` file, pinning the remote dependencies of a package.
+///
+/// Git dependencies are pinned to the commit their revision resolved to, and on-chain packages
+/// to the network version they were fetched at. Each entry also records a content hash, so that
+/// a dependency which changed in the cache (or upstream, for a force-pushed commit) is detected.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PackageLock {
+    pub version: u64,
+    #[serde(default)]
+    pub git: Vec<LockedGitDependency>,
+    #[serde(default, rename = "on-chain")]
+    pub on_chain: Vec<LockedOnChainPackage>,
+}
+
+/// A Git dependency, pinned to a specific commit.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct LockedGitDependency {
+    /// URL of the repository, normalized with [`normalize_git_url`].
+    pub url: String,
+    /// Revision requested by the manifest, e.g. a branch, a tag or a commit.
+    pub rev: String,
+    /// Commit the revision resolved to.
+    pub commit: String,
+    /// Hash of the contents of the checkout, see [`hash_directory`].
+    pub hash: String,
+}
+
+/// An on-chain package, pinned to a specific network version.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct LockedOnChainPackage {
+    /// URL of the fullnode, as written in the manifest.
+    pub node_url: String,
+    pub address: AccountAddress,
+    pub package_name: String,
+    /// Ledger version the package was fetched at.
+    pub network_version: u64,
+    /// Hash of the manifest and sources of the package, see [`hash_package_sources`].
+    pub hash: String,
+}
+
+impl Default for PackageLock {
+    fn default() -> Self {
+        Self {
+            version: LOCK_FILE_VERSION,
+            git: vec![],
+            on_chain: vec![],
+        }
+    }
+}
+
+impl PackageLock {
+    /// Reads a lock file.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let contents = fs::read_to_string(path)
+            .with_context(|| format!("failed to read lock file {}", path.display()))?;
+        let lock: Self = toml::from_str(&contents)
+            .with_context(|| format!("failed to parse lock file {}", path.display()))?;
+        if lock.version != LOCK_FILE_VERSION {
+            bail!(
+                "unsupported lock file version {} in {} (expected {})",
+                lock.version,
+                path.display(),
+                LOCK_FILE_VERSION
+            );
+        }
+        Ok(lock)
+    }
+
+    /// Reads a lock file, or returns an empty lock if the file does not exist.
+    pub fn load_or_default(path: impl AsRef<Path>) -> Result<Self> {
+        if path.as_ref().exists() {
+            Self::load(path)
+        } else {
+            Ok(Self::default())
+        }
+    }
+
+    /// Sorts the entries, so that equal locks compare equal and are written identically.
+    pub fn sort(&mut self) {
+        self.git
+            .sort_by(|a, b| (&a.url, &a.rev).cmp(&(&b.url, &b.rev)));
+        self.on_chain.sort_by(|a, b| {
+            (&a.node_url, a.address, &a.package_name).cmp(&(
+                &b.node_url,
+                b.address,
+                &b.package_name,
+            ))
+        });
+    }
+
+    /// Writes the lock file, with the entries sorted so that the output is deterministic.
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<()> {
+        let mut lock = self.clone();
+        lock.sort();
+        let contents = format!("{}{}", LOCK_FILE_HEADER, toml::to_string(&lock)?);
+        fs::write(path, contents)?;
+        Ok(())
+    }
+
+    /// Returns the pinned commit of a Git dependency, if any.
+    pub fn git_dependency(&self, url: &str, rev: &str) -> Option<&LockedGitDependency> {
+        let url = normalize_git_url(url);
+        self.git
+            .iter()
+            .find(|dep| normalize_git_url(&dep.url) == url && dep.rev == rev)
+    }
+
+    /// Returns the pinned version of an on-chain package, if any.
+    pub fn on_chain_package(
+        &self,
+        node_url: &str,
+        address: AccountAddress,
+        package_name: &str,
+    ) -> Option<&LockedOnChainPackage> {
+        self.on_chain.iter().find(|package| {
+            package.node_url == node_url
+                && package.address == address
+                && package.package_name == package_name
+        })
+    }
+
+    /// Adds a Git dependency, replacing the existing entry for the same URL and revision.
+    pub fn insert_git_dependency(&mut self, dep: LockedGitDependency) {
+        let dep = LockedGitDependency {
+            url: normalize_git_url(&dep.url),
+            ..dep
+        };
+        self.git.retain(|existing| {
+            normalize_git_url(&existing.url) != dep.url || existing.rev != dep.rev
+        });
+        self.git.push(dep);
+    }
+
+    /// Adds an on-chain package, replacing the existing entry for the same package.
+    pub fn insert_on_chain_package(&mut self, package: LockedOnChainPackage) {
+        self.on_chain.retain(|existing| {
+            existing.node_url != package.node_url
+                || existing.address != package.address
+                || existing.package_name != package.package_name
+        });
+        self.on_chain.push(package);
+    }
+}
+
+impl LockedGitDependency {
+    /// Checks that a checkout of the dependency matches the hash recorded in the lock file.
+    pub fn verify(&self, checkout_path: &Path) -> Result<()> {
+        verify_hash(
+            &hash_directory(checkout_path)?,
+            &self.hash,
+            checkout_path,
+            format_args!("{}@{}", self.url, self.commit),
+        )
+    }
+}
+
+impl LockedOnChainPackage {
+    /// Checks that a download of the package matches the hash recorded in the lock file.
+    pub fn verify(&self, package_path: &Path) -> Result<()> {
+        verify_hash(
+            &hash_package_sources(package_path)?,
+            &self.hash,
+            package_path,
+            format_args!("{}//{}::{}", self.node_url, self.address, self.package_name),
+        )
+    }
+}
+
+fn verify_hash(actual: &str, expected: &str, path: &Path, dependency: impl Display) -> Result<()> {
+    if actual != expected {
+        bail!(
+            "content hash mismatch for {} at {}: expected {}, found {}",
+            dependency,
+            path.display(),
+            expected,
+            actual
+        );
+    }
+    Ok(())
+}
+
+/// Normalizes the URL of a Git repository, so that different spellings of the same URL, e.g.
+/// with and without a trailing slash, map to the same entry of the lock file.
+pub fn normalize_git_url(url: &str) -> String {
+    let url = match Url::parse(url) {
+        Ok(url) => url.to_string(),
+        // e.g. `git@github.com:foo/bar.git`, which is not a URL as far as the parser is concerned
+        Err(_) => url.to_string(),
+    };
+    url.trim_end_matches('/').to_string()
+}
+
+/// Computes a hash of all the files in a directory, including their paths relative to the
+/// directory. The result does not depend on the location of the directory, nor on the order in
+/// which the file system lists entries.
+pub fn hash_directory(path: &Path) -> Result<String> {
+    hash_files(path, |_| true)
+}
+
+/// Computes a hash of the manifest and sources of a package, in the same way as
+/// [`hash_directory`]. Other files, e.g. the bytecode which the package cache stores along with
+/// on-chain packages, are not included, as they are not used by builds of dependent packages.
+pub fn hash_package_sources(path: &Path) -> Result<String> {
+    hash_files(path, |relative_path| {
+        relative_path == "Move.toml" || relative_path.starts_with("sources/")
+    })
+}
+
+fn hash_files(path: &Path, include: impl Fn(&str) -> bool) -> Result<String> {
+    let mut hasher = Sha256::new();
+    for entry in WalkDir::new(path).sort_by_file_name() {
+        let entry = entry?;
+        if !entry.file_type().is_file() {
+            continue;
+        }
+        let relative_path = entry.path().strip_prefix(path)?;
+        let relative_path = relative_path
+            .components()
+            .map(|component| component.as_os_str().to_string_lossy())
+            .collect::<Vec<_>>()
+            .join("/");
+        if !include(&relative_path) {
+            continue;
+        }
+        let contents = fs::read(entry.path())?;
+
+        hasher.update((relative_path.len() as u64).to_le_bytes());
+        hasher.update(relative_path.as_bytes());
+        hasher.update((contents.len() as u64).to_le_bytes());
+        hasher.update(&contents);
+    }
+    Ok(format!("sha256:{:x}", hasher.finalize()))
+}
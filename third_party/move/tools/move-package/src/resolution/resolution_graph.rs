@@ -3,14 +3,17 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
+    lock_file::{LockedGitDependency, PackageLock, LOCK_FILE_NAME},
     package_hooks,
     resolution::digest::compute_digest,
     source_package::{
         layout::SourcePackageLayout,
-        manifest_parser::{parse_move_manifest_string, parse_source_manifest},
+        manifest_parser::{
+            git_locked_checkout_dir_name, parse_move_manifest_string, parse_source_manifest,
+        },
         parsed_manifest::{
-            Dependencies, Dependency, FileName, NamedAddress, PackageDigest, PackageName,
-            SourceManifest, SubstOrRename,
+            CustomDepInfo, Dependencies, Dependency, FileName, GitInfo, NamedAddress,
+            PackageDigest, PackageName, SourceManifest, SubstOrRename,
         },
         std_lib::{StdLib, StdVersion},
     },
@@ -19,8 +22,11 @@ use crate::{
 use anyhow::{bail, Context, Result};
 use colored::Colorize;
 use legacy_move_compiler::command_line::DEFAULT_OUTPUT_DIR;
-use move_command_line_common::files::{
-    extension_equals, find_filenames, find_move_filenames, FileHash, MOVE_COMPILED_EXTENSION,
+use move_command_line_common::{
+    env::MOVE_HOME,
+    files::{
+        extension_equals, find_filenames, find_move_filenames, FileHash, MOVE_COMPILED_EXTENSION,
+    },
 };
 use move_core_types::account_address::AccountAddress;
 use move_symbol_pool::Symbol;
@@ -33,6 +39,7 @@ use std::{
     path::{Path, PathBuf},
     process::{Command, Stdio},
     rc::Rc,
+    str::FromStr,
 };
 
 pub type ResolvedTable = ResolutionTable<AccountAddress>;
@@ -70,6 +77,8 @@ pub struct ResolutionGraph<T> {
     pub root_package_path: PathBuf,
     /// Build options
     pub build_options: BuildConfig,
+    /// Remote dependencies pinned in the `Move.lock` of the root package, if any
+    pub package_lock: PackageLock,
     /// Root package
     pub root_package: SourceManifest,
     /// Dependency graph
@@ -101,9 +110,11 @@ impl ResolvingGraph {
         build_options: BuildConfig,
         writer: &mut W,
     ) -> Result<ResolvingGraph> {
+        let package_lock = PackageLock::load_or_default(root_package_path.join(LOCK_FILE_NAME))?;
         let mut resolution_graph = Self {
             root_package_path: root_package_path.clone(),
             build_options: build_options.clone(),
+            package_lock,
             root_package: root_package.clone(),
             graph: DiGraphMap::new(),
             package_table: BTreeMap::new(),
@@ -132,6 +143,7 @@ impl ResolvingGraph {
         let ResolvingGraph {
             root_package_path,
             build_options,
+            package_lock,
             root_package,
             graph,
             package_table,
@@ -192,6 +204,7 @@ impl ResolvingGraph {
         Ok(ResolvedGraph {
             root_package_path,
             build_options,
+            package_lock,
             root_package,
             graph,
             package_table: resolved_package_table,
@@ -406,10 +419,11 @@ impl ResolvingGraph {
         override_std: &Option<StdVersion>,
         writer: &mut W,
     ) -> Result<(Renaming, ResolvingTable)> {
-        Self::download_and_update_if_remote(
+        let dep = Self::download_and_update_if_remote(
             dep_name_in_pkg,
             &dep,
-            self.build_options.skip_fetch_latest_git_deps,
+            &self.build_options,
+            &self.package_lock,
             writer,
         )?;
         let (dep_package, dep_package_dir) =
@@ -541,6 +555,7 @@ impl ResolvingGraph {
     pub fn download_dependency_repos<W: Write>(
         manifest: &SourceManifest,
         build_options: &BuildConfig,
+        package_lock: &PackageLock,
         root_path: &Path,
         writer: &mut W,
     ) -> Result<()> {
@@ -554,35 +569,67 @@ impl ResolvingGraph {
         };
 
         for (dep_name, dep) in manifest.dependencies.iter().chain(additional_deps.iter()) {
-            Self::download_and_update_if_remote(
+            let dep = Self::download_and_update_if_remote(
                 *dep_name,
                 dep,
-                build_options.skip_fetch_latest_git_deps,
+                build_options,
+                package_lock,
                 writer,
             )?;
 
             let (dep_manifest, _) =
-                Self::parse_package_manifest(dep, dep_name, root_path.to_path_buf())
+                Self::parse_package_manifest(&dep, dep_name, root_path.to_path_buf())
                     .with_context(|| format!("While processing dependency '{}'", *dep_name))?;
             // download dependencies of dependencies
-            Self::download_dependency_repos(&dep_manifest, build_options, root_path, writer)?;
+            Self::download_dependency_repos(
+                &dep_manifest,
+                build_options,
+                package_lock,
+                root_path,
+                writer,
+            )?;
         }
         Ok(())
     }
 
+    /// Downloads a remote dependency, or updates it, and returns the dependency as it is to be
+    /// used, i.e. pointing at the checkout of the commit pinned in the lock file, if any.
     fn download_and_update_if_remote<W: Write>(
         dep_name: PackageName,
         dep: &Dependency,
-        skip_fetch_latest_git_deps: bool,
+        build_options: &BuildConfig,
+        package_lock: &PackageLock,
         writer: &mut W,
-    ) -> Result<()> {
+    ) -> Result<Dependency> {
+        let offline = build_options.offline;
         if let Some(git_info) = &dep.git_info {
             let git_url = git_info.git_url.as_str();
             let git_rev = git_info.git_rev.as_str();
             let git_path = &git_info.download_to.display().to_string();
 
+            // A commit pinned in the lock file takes precedence over the state of the revision
+            if let Some(locked) = package_lock.git_dependency(git_url, git_rev) {
+                let checkout_path =
+                    Self::checkout_pinned_commit(dep_name, git_info, locked, offline, writer)?;
+                return Ok(Dependency {
+                    local: checkout_path.join(&git_info.subdir),
+                    git_info: Some(GitInfo {
+                        download_to: checkout_path,
+                        ..git_info.clone()
+                    }),
+                    ..dep.clone()
+                });
+            }
+
             // If there is no cached dependency, download it
             if !git_info.download_to.exists() {
+                if offline {
+                    bail!(
+                        "Cannot fetch Git dependency '{}' from {} in offline mode",
+                        dep_name,
+                        git_url
+                    );
+                }
                 writeln!(
                     writer,
                     "{} {}",
@@ -610,7 +657,7 @@ impl ResolvingGraph {
                             dep_name
                         )
                     })?;
-            } else if !skip_fetch_latest_git_deps {
+            } else if !offline && !build_options.skip_fetch_latest_git_deps {
                 // Confirm git is available.
                 confirm_git_available()?;
 
@@ -623,7 +670,7 @@ impl ResolvingGraph {
                     if let Ok(parsable_version) = String::from_utf8(rev.stdout) {
                         // If it's exactly the same, then it's a git rev
                         if parsable_version.trim().starts_with(git_rev) {
-                            return Ok(());
+                            return Ok(dep.clone());
                         }
                     }
                 }
@@ -638,7 +685,7 @@ impl ResolvingGraph {
                         // Tags don't easily update locally and you can't use reset --hard to cleanup
                         // any extra files
                         if parsable_version.trim().starts_with(git_rev) {
-                            return Ok(());
+                            return Ok(dep.clone());
                         }
                     }
                 }
@@ -705,9 +752,145 @@ impl ResolvingGraph {
             }
         }
         if let Some(node_info) = &dep.node_info {
-            package_hooks::resolve_custom_dependency(dep_name, node_info)?
+            let locked = AccountAddress::from_str(node_info.package_address.as_str())
+                .ok()
+                .and_then(|address| {
+                    package_lock.on_chain_package(
+                        node_info.node_url.as_str(),
+                        address,
+                        node_info.package_name.as_str(),
+                    )
+                });
+            if offline {
+                if !node_info.download_to.exists() {
+                    bail!(
+                        "Cannot download on-chain dependency '{}' from {} in offline mode",
+                        dep_name,
+                        node_info.node_url
+                    );
+                }
+            } else {
+                let node_info = CustomDepInfo {
+                    network_version: locked.map(|locked| locked.network_version),
+                    ..node_info.clone()
+                };
+                package_hooks::resolve_custom_dependency(dep_name, &node_info)?
+            }
+            if let Some(locked) = locked {
+                locked
+                    .verify(&node_info.download_to)
+                    .with_context(|| format!("While downloading dependency '{}'", dep_name))?;
+            }
         }
-        Ok(())
+        Ok(dep.clone())
+    }
+
+    /// Checks out the commit a Git dependency is pinned to in the lock file, and returns the path
+    /// to the checkout.
+    ///
+    /// Each pinned commit is checked out into its own directory, next to the clone of the
+    /// repository, rather than in the clone itself: the clone is shared by all the packages
+    /// depending on the same revision, which may pin different commits of it. The checkout does
+    /// not contain the Git metadata, so that its contents can be verified against the hash in the
+    /// lock file.
+    fn checkout_pinned_commit<W: Write>(
+        dep_name: PackageName,
+        git_info: &GitInfo,
+        locked: &LockedGitDependency,
+        offline: bool,
+        writer: &mut W,
+    ) -> Result<PathBuf> {
+        let git_url = git_info.git_url.as_str();
+        let commit = locked.commit.as_str();
+        let checkout_path =
+            PathBuf::from(MOVE_HOME.clone()).join(git_locked_checkout_dir_name(git_url, commit));
+
+        if !checkout_path.exists() {
+            // The commit is taken from the clone of the repository, which is created if needed
+            let git_path = &git_info.download_to.display().to_string();
+            if !git_info.download_to.exists() {
+                if offline {
+                    bail!(
+                        "Cannot fetch Git dependency '{}' from {} in offline mode",
+                        dep_name,
+                        git_url
+                    );
+                }
+                writeln!(
+                    writer,
+                    "{} {}",
+                    "FETCHING GIT DEPENDENCY".bold().green(),
+                    git_url,
+                )?;
+                confirm_git_available()?;
+                run_git(&["clone", "--quiet", git_url, git_path]).with_context(|| {
+                    format!("Failed to clone Git repository for package '{}'", dep_name)
+                })?;
+            }
+
+            let has_commit = Command::new("git")
+                .args([
+                    "-C",
+                    git_path,
+                    "cat-file",
+                    "-e",
+                    &format!("{}^{{commit}}", commit),
+                ])
+                .stderr(Stdio::null())
+                .status()
+                .is_ok_and(|status| status.success());
+            if !has_commit {
+                if offline {
+                    bail!(
+                        "Cannot fetch pinned commit '{}' for package '{}' in offline mode",
+                        commit,
+                        dep_name
+                    );
+                }
+                run_git(&["-C", git_path, "fetch", "--quiet", "origin"]).with_context(|| {
+                    format!("Failed to fetch Git state for package '{}'", dep_name)
+                })?;
+            }
+
+            // Check out into a temporary directory, which is then renamed, so that an interrupted
+            // checkout is never used
+            let parent = checkout_path.parent().expect("checkout path has a parent");
+            let temp = tempfile::tempdir_in(parent)?;
+            let temp_path = &temp.path().display().to_string();
+            run_git(&[
+                "clone",
+                "--quiet",
+                "--shared",
+                "--no-checkout",
+                git_path,
+                temp_path,
+            ])
+            .and_then(|()| {
+                run_git(&[
+                    "-c",
+                    "core.autocrlf=false",
+                    "-C",
+                    temp_path,
+                    "checkout",
+                    "--quiet",
+                    "--detach",
+                    commit,
+                ])
+            })
+            .with_context(|| {
+                format!(
+                    "Failed to checkout pinned commit '{}' for package '{}'",
+                    commit, dep_name
+                )
+            })?;
+            fs::remove_dir_all(temp.path().join(".git"))?;
+            fs::rename(temp.into_path(), &checkout_path)?;
+        }
+
+        locked
+            .verify(&checkout_path)
+            .with_context(|| format!("While checking out dependency '{}'", dep_name))?;
+        Ok(checkout_path)
     }
 }
 
@@ -961,6 +1144,20 @@ impl ResolvedPackage {
     }
 }
 
+/// Runs a Git command, failing if it does not succeed.
+fn run_git(args: &[&str]) -> Result<()> {
+    let output = Command::new("git").args(args).output()?;
+    if !output.status.success() {
+        bail!(
+            "git {} failed | Exit status: {}\n{}",
+            args.join(" "),
+            output.status,
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Ok(())
+}
+
 fn confirm_git_available() -> Result<()> {
     match Command::new("git").arg("--version").output() {
         Ok(_) => Ok(()),
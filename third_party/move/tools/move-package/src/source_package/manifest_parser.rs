@@ -3,7 +3,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use super::layout::SourcePackageLayout;
-use crate::{package_hooks, source_package::parsed_manifest as PM};
+use crate::{lock_file::normalize_git_url, package_hooks, source_package::parsed_manifest as PM};
 use anyhow::{bail, format_err, Context, Result};
 use move_command_line_common::env::MOVE_HOME;
 use move_core_types::account_address::{AccountAddress, AccountAddressParseError};
@@ -22,6 +22,8 @@ const ADDRESSES_NAME: &str = "addresses";
 const DEV_ADDRESSES_NAME: &str = "dev-addresses";
 const DEPENDENCY_NAME: &str = "dependencies";
 const DEV_DEPENDENCY_NAME: &str = "dev-dependencies";
+// The `[lint]` section is parsed by `move-package-manifest`, but known here to avoid warnings.
+const LINT_NAME: &str = "lint";
 
 const KNOWN_NAMES: &[&str] = &[
     PACKAGE_NAME,
@@ -30,6 +32,7 @@ const KNOWN_NAMES: &[&str] = &[
     DEV_ADDRESSES_NAME,
     DEPENDENCY_NAME,
     DEV_DEPENDENCY_NAME,
+    LINT_NAME,
 ];
 
 const REQUIRED_FIELDS: &[&str] = &[PACKAGE_NAME];
@@ -402,17 +405,18 @@ fn parse_dependency(dep_name: &str, tval: TV) -> Result<PM::Dependency> {
                     let node_url = custom_key
                         .as_str()
                         .ok_or_else(|| anyhow::anyhow!("Git URL not a string"))?;
-                    let local_path = PathBuf::from(MOVE_HOME.clone()).join(format!(
-                        "{}_{}_{}",
-                        url_to_file_name(node_url),
-                        address,
-                        package_name
-                    ));
+                    let local_path =
+                        PathBuf::from(MOVE_HOME.clone()).join(on_chain_package_dir_name(
+                            node_url,
+                            address.as_str(),
+                            package_name.as_str(),
+                        ));
                     node_info = Some(PM::CustomDepInfo {
                         node_url: Symbol::from(node_url),
                         package_address: address,
                         package_name,
                         download_to: local_path.clone(),
+                        network_version: None,
                     });
                     Ok(PM::Dependency {
                         subst,
@@ -562,6 +566,32 @@ fn check_for_required_field_names(
     Ok(())
 }
 
+/// Gets the name of the directory a commit of a git repo pinned in the lock file is checked out
+/// to, relative to the Move home directory
+pub fn git_locked_checkout_dir_name(git_url: &str, commit: &str) -> String {
+    format!(
+        "{}_locked_{}",
+        url_to_file_name(&normalize_git_url(git_url)),
+        commit
+    )
+}
+
+/// Gets the name of the directory an on-chain package is downloaded to, relative to the Move home
+/// directory
+pub fn on_chain_package_dir_name(node_url: &str, address: &str, package_name: &str) -> String {
+    // Spell the address the same way as the lock file does
+    let address = match address.parse::<AccountAddress>() {
+        Ok(address) => address.to_hex_literal(),
+        Err(_) => address.to_string(),
+    };
+    format!(
+        "{}_{}_{}",
+        url_to_file_name(node_url),
+        address,
+        package_name
+    )
+}
+
 /// Gets the local path to download the package from a git repo
 pub fn git_repo_cache_path(git_url: &str, rev_name: &str) -> PathBuf {
     let move_home = MOVE_HOME.clone();
@@ -111,6 +111,9 @@ pub struct CustomDepInfo {
     pub package_name: Symbol,
     /// Where the package is downloaded to.
     pub download_to: PathBuf,
+    /// The network version to download the package at, if pinned in the lock file.
+    /// Otherwise, the latest version is downloaded.
+    pub network_version: Option<u64>,
 }
 
 #[derive(Default, Debug, Clone, Eq, PartialEq)]
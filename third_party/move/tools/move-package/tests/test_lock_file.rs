@@ -0,0 +1,244 @@
+// Copyright (c) The Move Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use move_core_types::account_address::AccountAddress;
+use move_package::{
+    lock_file::{
+        hash_directory, hash_package_sources, normalize_git_url, LockedGitDependency,
+        LockedOnChainPackage, PackageLock, LOCK_FILE_NAME,
+    },
+    source_package::manifest_parser::{git_locked_checkout_dir_name, git_repo_cache_path},
+    BuildConfig,
+};
+use move_symbol_pool::Symbol;
+use std::{fs, path::Path, process::Command};
+use tempfile::tempdir;
+
+/// Runs a git command in the given directory and returns its trimmed output.
+fn git(dir: &Path, args: &[&str]) -> String {
+    let output = Command::new("git")
+        .args(["-c", "user.name=test", "-c", "user.email=test@example.com"])
+        .arg("-C")
+        .arg(dir)
+        .args(args)
+        .output()
+        .unwrap();
+    assert!(output.status.success(), "git {:?} failed", args);
+    String::from_utf8(output.stdout).unwrap().trim().to_string()
+}
+
+/// Writes a package with a single (empty) module and commits it.
+fn commit_package(repo: &Path, module_name: &str) -> String {
+    fs::create_dir_all(repo.join("sources")).unwrap();
+    fs::write(
+        repo.join("Move.toml"),
+        "[package]\nname = \"Dep\"\nversion = \"0.0.0\"\n",
+    )
+    .unwrap();
+    fs::write(
+        repo.join("sources").join(format!("{}.move", module_name)),
+        format!("module 0x42::{} {{}}\n", module_name),
+    )
+    .unwrap();
+    git(repo, &["add", "-A"]);
+    git(repo, &["commit", "-m", module_name]);
+    git(repo, &["rev-parse", "HEAD"])
+}
+
+/// Computes the hash of the contents of a commit, as recorded in lock files.
+fn commit_hash(repo: &Path, commit: &str) -> String {
+    let dir = tempdir().unwrap();
+    let checkout = dir.path().join("checkout");
+    git(
+        dir.path(),
+        &["clone", "-q", &repo.display().to_string(), "checkout"],
+    );
+    git(&checkout, &["checkout", "-q", "--detach", commit]);
+    fs::remove_dir_all(checkout.join(".git")).unwrap();
+    hash_directory(&checkout).unwrap()
+}
+
+#[test]
+fn test_lock_file_round_trip() {
+    let dir = tempdir().unwrap();
+    let path = dir.path().join(LOCK_FILE_NAME);
+
+    let mut lock = PackageLock::default();
+    lock.insert_on_chain_package(LockedOnChainPackage {
+        node_url: "mainnet".to_string(),
+        address: AccountAddress::ONE,
+        package_name: "MoveStdlib".to_string(),
+        network_version: 3022354983,
+        hash: "sha256:00".to_string(),
+    });
+    for rev in ["main", "devnet", "main"] {
+        lock.insert_git_dependency(LockedGitDependency {
+            url: "https://github.com/foo/bar".to_string(),
+            rev: rev.to_string(),
+            commit: "0123456789abcdef0123456789abcdef01234567".to_string(),
+            hash: "sha256:01".to_string(),
+        });
+    }
+    assert_eq!(lock.git.len(), 2);
+
+    // Different spellings of the same URL refer to the same entry
+    assert_eq!(
+        normalize_git_url("https://github.com/foo/bar/"),
+        "https://github.com/foo/bar"
+    );
+    lock.insert_git_dependency(LockedGitDependency {
+        url: "https://github.com/foo/bar/".to_string(),
+        rev: "main".to_string(),
+        commit: "0123456789abcdef0123456789abcdef01234567".to_string(),
+        hash: "sha256:02".to_string(),
+    });
+    assert_eq!(lock.git.len(), 2);
+    assert_eq!(
+        lock.git_dependency("https://github.com/foo/bar/", "main")
+            .unwrap()
+            .hash,
+        "sha256:02"
+    );
+
+    lock.save(&path).unwrap();
+    let loaded = PackageLock::load(&path).unwrap();
+    assert_eq!(loaded.git[0].rev, "devnet");
+    assert_eq!(loaded.git[1].rev, "main");
+    assert_eq!(
+        loaded
+            .on_chain_package("mainnet", AccountAddress::ONE, "MoveStdlib")
+            .unwrap()
+            .network_version,
+        3022354983
+    );
+}
+
+#[test]
+fn test_pinned_git_dependency_and_offline_mode() {
+    let dir = tempdir().unwrap();
+    std::env::set_var("MOVE_HOME", dir.path().join("move_home"));
+
+    // Create a Git dependency with two commits, and a package depending on it
+    let repo = dir.path().join("dep");
+    fs::create_dir_all(&repo).unwrap();
+    git(&repo, &["init", "-q"]);
+    git(&repo, &["checkout", "-q", "-b", "main"]);
+    let pinned_commit = commit_package(&repo, "first");
+    commit_package(&repo, "second");
+
+    let git_url = format!("file://{}", repo.display());
+    let package = dir.path().join("package");
+    fs::create_dir_all(&package).unwrap();
+    fs::write(
+        package.join("Move.toml"),
+        format!(
+            "[package]\nname = \"Root\"\nversion = \"0.0.0\"\n\n\
+             [dependencies]\nDep = {{ git = \"{}\", rev = \"main\" }}\n",
+            git_url
+        ),
+    )
+    .unwrap();
+    let mut lock = PackageLock::default();
+    lock.insert_git_dependency(LockedGitDependency {
+        url: git_url.clone(),
+        rev: "main".to_string(),
+        commit: pinned_commit.clone(),
+        hash: "sha256:00".to_string(),
+    });
+    // The lock file may spell the URL differently than the manifest
+    lock.git[0].url = format!("{}/", git_url);
+    lock.save(package.join(LOCK_FILE_NAME)).unwrap();
+
+    let resolve = |offline: bool| {
+        BuildConfig {
+            install_dir: Some(dir.path().join("build")),
+            offline,
+            ..Default::default()
+        }
+        .resolution_graph_for_package(&package, &mut Vec::new())
+    };
+
+    // In offline mode, the dependency cannot be fetched
+    let err = resolve(true).unwrap_err();
+    assert!(
+        format!("{:#}", err).contains("in offline mode"),
+        "{:#}",
+        err
+    );
+
+    // The contents of the pinned commit are verified
+    let err = resolve(false).unwrap_err();
+    assert!(
+        format!("{:#}", err).contains("content hash mismatch"),
+        "{:#}",
+        err
+    );
+    lock.git[0].hash = commit_hash(&repo, &pinned_commit);
+    lock.save(package.join(LOCK_FILE_NAME)).unwrap();
+
+    // Otherwise, the pinned commit is checked out into its own directory, leaving the clone of
+    // the revision as it is
+    let graph = resolve(false).unwrap();
+    let checkout = dir
+        .path()
+        .join("move_home")
+        .join(git_locked_checkout_dir_name(&git_url, &pinned_commit));
+    assert!(checkout.join("sources/first.move").exists());
+    assert!(!checkout.join("sources/second.move").exists());
+    assert!(git_repo_cache_path(&git_url, "main")
+        .join("sources/second.move")
+        .exists());
+    assert_eq!(
+        graph.package_table[&Symbol::from("Dep")].package_path,
+        checkout
+    );
+
+    // Once fetched, the dependency can be used in offline mode
+    resolve(true).unwrap();
+}
+
+#[test]
+fn test_hash_directory() {
+    let create = |files: &[(&str, &str)]| {
+        let dir = tempdir().unwrap();
+        for (path, contents) in files {
+            let path = dir.path().join(path);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, contents).unwrap();
+        }
+        dir
+    };
+
+    let a = create(&[
+        ("Move.toml", "[package]"),
+        ("sources/a.move", "module 0x1::a {}"),
+    ]);
+    let b = create(&[
+        ("sources/a.move", "module 0x1::a {}"),
+        ("Move.toml", "[package]"),
+    ]);
+    let c = create(&[
+        ("Move.toml", "[package]"),
+        ("sources/b.move", "module 0x1::a {}"),
+    ]);
+    let d = create(&[
+        ("Move.toml", "[package]"),
+        ("sources/a.move", "module 0x1::b {}"),
+    ]);
+    let e = create(&[
+        ("Move.toml", "[package]"),
+        ("sources/a.move", "module 0x1::a {}"),
+        ("a.mv", "bytecode"),
+    ]);
+
+    let hash = hash_directory(a.path()).unwrap();
+    assert_eq!(hash, hash_directory(b.path()).unwrap());
+    assert_ne!(hash, hash_directory(c.path()).unwrap());
+    assert_ne!(hash, hash_directory(d.path()).unwrap());
+    assert_ne!(hash, hash_directory(e.path()).unwrap());
+
+    // Only the manifest and sources of on-chain packages are hashed
+    let hash = hash_package_sources(a.path()).unwrap();
+    assert_eq!(hash, hash_package_sources(e.path()).unwrap());
+    assert_ne!(hash, hash_package_sources(d.path()).unwrap());
+}
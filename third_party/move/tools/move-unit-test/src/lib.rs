@@ -21,8 +21,10 @@ use move_package::compilation::compiled_package::build_and_report_v2_driver;
 use move_vm_runtime::native_functions::NativeFunctionTable;
 use std::{
     collections::BTreeMap,
-    io::{Result, Write},
+    fs::File,
+    io::{BufWriter, Result, Write},
     marker::Send,
+    path::PathBuf,
     sync::Mutex,
 };
 use test_reporter::UnitTestFactory;
@@ -102,6 +104,14 @@ pub struct UnitTestingConfig {
     /// Verbose mode
     #[clap(short = 'v', long = "verbose")]
     pub verbose: bool,
+
+    /// Write a JUnit XML report of the test results to this file
+    #[clap(name = "junit_report", long = "junit_report")]
+    pub junit_report: Option<PathBuf>,
+
+    /// Write the test results as JSON events, one per line, to this file once all tests have run
+    #[clap(name = "json_report", long = "json_report")]
+    pub json_report: Option<PathBuf>,
 }
 
 fn format_module_id(module_id: &ModuleId) -> String {
@@ -127,6 +137,8 @@ impl Default for UnitTestingConfig {
             verbose: false,
             list: false,
             named_address_values: vec![],
+            junit_report: None,
+            json_report: None,
         }
     }
 }
@@ -235,6 +247,18 @@ impl UnitTestingConfig {
             test_results.report_goldens(&shared_writer)?;
         }
 
+        if let Some(junit_report) = &self.junit_report {
+            let mut report_writer = BufWriter::new(File::create(junit_report)?);
+            test_results.report_junit(&mut report_writer)?;
+            report_writer.flush()?;
+        }
+
+        if let Some(json_report) = &self.json_report {
+            let mut report_writer = BufWriter::new(File::create(json_report)?);
+            test_results.report_json_events(&mut report_writer)?;
+            report_writer.flush()?;
+        }
+
         let ok = test_results.summarize(&shared_writer)?;
 
         let writer = shared_writer.into_inner().unwrap();
@@ -15,13 +15,18 @@ use move_binary_format::{
     errors::{ExecutionState, Location, VMError, VMResult},
 };
 use move_command_line_common::{env::read_bool_env_var, files::FileHash};
-use move_core_types::{effects::ChangeSet, language_storage::ModuleId, vm_status::StatusType};
+use move_core_types::{
+    effects::ChangeSet,
+    language_storage::ModuleId,
+    vm_status::{StatusCode, StatusType},
+};
 use move_ir_types::location::Loc;
 use move_symbol_pool::Symbol;
 use move_vm_runtime::native_extensions::NativeContextExtensions;
 use move_vm_test_utils::gas_schedule::{zero_cost_schedule, CostTable, GasCost, GasStatus};
 use move_vm_types::gas::GasMeter;
 use once_cell::sync::Lazy;
+use serde_json::{json, Value};
 use std::{
     collections::{BTreeMap, BTreeSet, HashMap},
     io::{Result, Write},
@@ -193,6 +198,34 @@ impl FailureReason {
     pub fn property(details: String) -> Self {
         FailureReason::Property(details)
     }
+
+    /// A short, machine-readable name of the kind of failure.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            FailureReason::NoError(_) => "no_error",
+            FailureReason::WrongError(..) => "wrong_error",
+            FailureReason::WrongAbortDEPRECATED(..) => "wrong_abort",
+            FailureReason::UnexpectedError(..) => "unexpected_error",
+            FailureReason::Timeout(_) => "timeout",
+            FailureReason::Mismatch { .. } => "mismatch",
+            FailureReason::Property(_) => "property",
+        }
+    }
+
+    /// A one-line description of the failure, without source locations.
+    pub fn message(&self) -> &str {
+        match self {
+            FailureReason::NoError(message)
+            | FailureReason::WrongError(message, ..)
+            | FailureReason::WrongAbortDEPRECATED(message, ..)
+            | FailureReason::UnexpectedError(message, _)
+            | FailureReason::Timeout(message)
+            | FailureReason::Property(message) => message,
+            FailureReason::Mismatch { .. } => {
+                "Executions via Move VM and stackless VM yield different results"
+            },
+        }
+    }
 }
 
 impl TestFailure {
@@ -322,57 +355,128 @@ impl TestFailure {
         }
     }
 
-    fn report_exec_state(test_plan: &TestPlan, exec_state: &ExecutionState) -> String {
+    fn get_start_line(
+        loc: &Loc,
+        files: &SimpleFiles<Symbol, &str>,
+        file_mapping: &HashMap<FileHash, usize>,
+    ) -> Option<usize> {
+        let id = file_mapping.get(&loc.file_hash())?;
+        let line_index = files.line_index(*id, loc.start() as usize).ok()?;
+        files.line_number(*id, line_index).ok()
+    }
+
+    fn source_files(test_plan: &TestPlan) -> (SimpleFiles<Symbol, &str>, HashMap<FileHash, usize>) {
+        let mut files = SimpleFiles::new();
+        let mut file_mapping = HashMap::new();
+        for (fhash, (fname, source)) in &test_plan.files {
+            let id = files.add(*fname, source.as_str());
+            file_mapping.insert(*fhash, id);
+        }
+        (files, file_mapping)
+    }
+
+    /// Renders the frames of the stack trace as `module::function(file:line)`, or returns an
+    /// error message if the stack trace cannot be mapped back to source.
+    fn stack_trace_frames(
+        test_plan: &TestPlan,
+        exec_state: &ExecutionState,
+    ) -> std::result::Result<Vec<String>, String> {
         let stack_trace = exec_state.stack_trace();
-        let mut buf = String::new();
-        if !stack_trace.is_empty() {
-            buf.push_str("stack trace\n");
-            let mut files = SimpleFiles::new();
-            let mut file_mapping = HashMap::new();
-            for (fhash, (fname, source)) in &test_plan.files {
-                let id = files.add(*fname, source.as_str());
-                file_mapping.insert(*fhash, id);
-            }
+        if stack_trace.is_empty() {
+            return Ok(vec![]);
+        }
 
-            for frame in stack_trace {
-                let module_id = match &frame.0 {
-                    Some(v) => v,
-                    None => return "\tmalformed stack trace (no module ID)".to_string(),
-                };
-                let named_module = match test_plan.module_info.get(module_id) {
-                    Some(NamedOrBytecodeModule::Named(v)) => v,
-                    Some(NamedOrBytecodeModule::Bytecode(_)) => {
-                        return "\tno source map for bytecode module".to_string()
-                    },
-                    None => return "\tmalformed stack trace (no module)".to_string(),
+        let (files, file_mapping) = Self::source_files(test_plan);
+        let mut frames = vec![];
+        for frame in stack_trace {
+            let module_id = match &frame.0 {
+                Some(v) => v,
+                None => return Err("malformed stack trace (no module ID)".to_string()),
+            };
+            let named_module = match test_plan.module_info.get(module_id) {
+                Some(NamedOrBytecodeModule::Named(v)) => v,
+                Some(NamedOrBytecodeModule::Bytecode(_)) => {
+                    return Err("no source map for bytecode module".to_string())
+                },
+                None => return Err("malformed stack trace (no module)".to_string()),
+            };
+            let function_source_map =
+                match named_module.source_map.get_function_source_map(frame.1) {
+                    Ok(v) => v,
+                    Err(_) => return Err("malformed stack trace (no source map)".to_string()),
                 };
-                let function_source_map =
-                    match named_module.source_map.get_function_source_map(frame.1) {
-                        Ok(v) => v,
-                        Err(_) => return "\tmalformed stack trace (no source map)".to_string(),
-                    };
-                // unwrap here is a mirror of the same unwrap in report_error_with_location
-                let loc = function_source_map.get_code_location(frame.2).unwrap();
-                let fn_handle_idx = named_module.module.function_def_at(frame.1).function;
-                let fn_id_idx = named_module.module.function_handle_at(fn_handle_idx).name;
-                let fn_name = named_module.module.identifier_at(fn_id_idx).as_str();
-                let file_name = match test_plan.files.get(&loc.file_hash()) {
-                    Some(v) => format!("{}", v.0),
-                    None => "unknown_source".to_string(),
-                };
-                buf.push_str(
-                    &format!(
-                        "\t{}::{}({}:{})\n",
-                        module_id.name(),
-                        fn_name,
-                        file_name,
-                        Self::get_line_number(&loc, &files, &file_mapping)
-                    )
-                    .to_string(),
-                );
-            }
+            // unwrap here is a mirror of the same unwrap in report_error_with_location
+            let loc = function_source_map.get_code_location(frame.2).unwrap();
+            let fn_handle_idx = named_module.module.function_def_at(frame.1).function;
+            let fn_id_idx = named_module.module.function_handle_at(fn_handle_idx).name;
+            let fn_name = named_module.module.identifier_at(fn_id_idx).as_str();
+            let file_name = match test_plan.files.get(&loc.file_hash()) {
+                Some(v) => format!("{}", v.0),
+                None => "unknown_source".to_string(),
+            };
+            frames.push(format!(
+                "{}::{}({}:{})",
+                module_id.name(),
+                fn_name,
+                file_name,
+                Self::get_line_number(&loc, &files, &file_mapping)
+            ));
+        }
+        Ok(frames)
+    }
+
+    fn report_exec_state(test_plan: &TestPlan, exec_state: &ExecutionState) -> String {
+        match Self::stack_trace_frames(test_plan, exec_state) {
+            Ok(frames) if frames.is_empty() => String::new(),
+            Ok(frames) => {
+                let mut buf = "stack trace\n".to_string();
+                for frame in frames {
+                    buf.push_str(&format!("\t{}\n", frame));
+                }
+                buf
+            },
+            Err(message) => format!("\t{}", message),
+        }
+    }
+
+    /// The abort code of the test, if it aborted.
+    pub fn abort_code(&self) -> Option<u64> {
+        self.vm_error
+            .as_ref()
+            .filter(|vm_error| vm_error.major_status() == StatusCode::ABORTED)
+            .and_then(|vm_error| vm_error.sub_status())
+    }
+
+    /// The source file and line at which the test failed, if known.
+    pub fn source_location(&self, test_plan: &TestPlan) -> Option<(String, Option<usize>)> {
+        let vm_error = self.vm_error.as_ref()?;
+        let module_id = match vm_error.location() {
+            Location::Module(module_id) => module_id,
+            _ => return None,
+        };
+        let (fdef_idx, offset) = vm_error.offsets().first()?;
+        let named_module = match test_plan.module_info.get(module_id)? {
+            NamedOrBytecodeModule::Named(named_module) => named_module,
+            NamedOrBytecodeModule::Bytecode(_) => return None,
+        };
+        let loc = named_module
+            .source_map
+            .get_function_source_map(*fdef_idx)
+            .ok()?
+            .get_code_location(*offset)?;
+        let file_name = test_plan.files.get(&loc.file_hash())?.0.to_string();
+        let (files, file_mapping) = Self::source_files(test_plan);
+        Some((file_name, Self::get_start_line(&loc, &files, &file_mapping)))
+    }
+
+    /// The stack trace of the test at the point of failure, if it was recorded, i.e., if
+    /// `report_stacktrace_on_abort` is set.
+    pub fn stack_trace(&self, test_plan: &TestPlan) -> Vec<String> {
+        match self.vm_error.as_ref().and_then(|vm_error| vm_error.exec_state()) {
+            None => vec![],
+            Some(exec_state) => Self::stack_trace_frames(test_plan, exec_state)
+                .unwrap_or_else(|message| vec![message]),
         }
-        buf
     }
 
     fn report_error_with_location(
@@ -606,6 +710,167 @@ impl TestResults {
         writeln!(writer.lock().unwrap())
     }
 
+    /// All test results, grouped by module and sorted by test name. Failed tests come with
+    /// their failure.
+    fn test_records(&self) -> BTreeMap<&ModuleId, Vec<(&TestRunInfo, Option<&TestFailure>)>> {
+        let mut records: BTreeMap<_, Vec<_>> = BTreeMap::new();
+        for (module_id, test_results) in &self.final_statistics.passed {
+            let entry = records.entry(module_id).or_default();
+            entry.extend(test_results.iter().map(|test_result| (test_result, None)));
+        }
+        for (module_id, test_failures) in &self.final_statistics.failed {
+            let entry = records.entry(module_id).or_default();
+            entry.extend(
+                test_failures
+                    .iter()
+                    .map(|test_failure| (&test_failure.test_run_info, Some(test_failure))),
+            );
+        }
+        for tests in records.values_mut() {
+            tests.sort_by(|(info1, _), (info2, _)| info1.function_ident.cmp(&info2.function_ident));
+        }
+        records
+    }
+
+    /// Renders a test failure without color escape codes, for structured reports.
+    fn render_plain_error(&self, test_failure: &TestFailure) -> String {
+        control::set_override(false);
+        let rendered = test_failure.render_error(&self.test_plan);
+        control::unset_override();
+        rendered
+    }
+
+    /// Writes the test results as a JUnit XML report. Every module becomes a test suite.
+    pub fn report_junit<W: Write>(&self, writer: &mut W) -> Result<()> {
+        let records = self.test_records();
+        let num_tests: usize = records.values().map(|tests| tests.len()).sum();
+        let num_failures = self.final_statistics.failed.values().map(|f| f.len()).sum::<usize>();
+        let total_time: f64 = records
+            .values()
+            .flatten()
+            .map(|(info, _)| info.elapsed_time.as_secs_f64())
+            .sum();
+
+        writeln!(writer, r#"<?xml version="1.0" encoding="UTF-8"?>"#)?;
+        writeln!(
+            writer,
+            r#"<testsuites name="move-unit-tests" tests="{}" failures="{}" errors="0" time="{:.3}">"#,
+            num_tests, num_failures, total_time
+        )?;
+        for (module_id, tests) in records {
+            let module_name = format_module_id(module_id);
+            let suite_failures = tests.iter().filter(|(_, failure)| failure.is_some()).count();
+            let suite_time: f64 = tests
+                .iter()
+                .map(|(info, _)| info.elapsed_time.as_secs_f64())
+                .sum();
+            writeln!(
+                writer,
+                r#"  <testsuite name="{}" tests="{}" failures="{}" errors="0" time="{:.3}">"#,
+                escape_xml(&module_name),
+                tests.len(),
+                suite_failures,
+                suite_time
+            )?;
+            for (info, failure) in tests {
+                let location = failure
+                    .and_then(|failure| failure.source_location(&self.test_plan))
+                    .map(|(file, line)| match line {
+                        Some(line) => format!(r#" file="{}" line="{}""#, escape_xml(&file), line),
+                        None => format!(r#" file="{}""#, escape_xml(&file)),
+                    })
+                    .unwrap_or_default();
+                writeln!(
+                    writer,
+                    r#"    <testcase name="{}" classname="{}" time="{:.3}"{}>"#,
+                    escape_xml(&info.function_ident),
+                    escape_xml(&module_name),
+                    info.elapsed_time.as_secs_f64(),
+                    location
+                )?;
+                writeln!(writer, "      <properties>")?;
+                writeln!(
+                    writer,
+                    r#"        <property name="gas_used" value="{}"/>"#,
+                    info.gas_used
+                )?;
+                if let Some(abort_code) = failure.and_then(|failure| failure.abort_code()) {
+                    writeln!(
+                        writer,
+                        r#"        <property name="abort_code" value="{}"/>"#,
+                        abort_code
+                    )?;
+                }
+                writeln!(writer, "      </properties>")?;
+                if let Some(failure) = failure {
+                    writeln!(
+                        writer,
+                        r#"      <failure message="{}" type="{}">{}</failure>"#,
+                        escape_xml(failure.failure_reason.message()),
+                        failure.failure_reason.kind(),
+                        escape_xml(&self.render_plain_error(failure))
+                    )?;
+                }
+                writeln!(writer, "    </testcase>")?;
+            }
+            writeln!(writer, "  </testsuite>")?;
+        }
+        writeln!(writer, "</testsuites>")
+    }
+
+    /// Writes the test results as JSON events, one per line, in the spirit of the libtest JSON
+    /// format: a `suite` event announcing the number of tests, one `test` event per test and a
+    /// final `suite` event with the overall result. The events are written once all tests have
+    /// run, with the tests ordered by module and name rather than by completion.
+    pub fn report_json_events<W: Write>(&self, writer: &mut W) -> Result<()> {
+        let records = self.test_records();
+        let num_tests: usize = records.values().map(|tests| tests.len()).sum();
+        let num_failures = self.final_statistics.failed.values().map(|f| f.len()).sum::<usize>();
+
+        let mut events = vec![json!({
+            "type": "suite",
+            "event": "started",
+            "test_count": num_tests,
+        })];
+        let mut total_time = 0.0;
+        for (module_id, tests) in records {
+            for (info, failure) in tests {
+                total_time += info.elapsed_time.as_secs_f64();
+                let mut event = json!({
+                    "type": "test",
+                    "event": if failure.is_some() { "failed" } else { "ok" },
+                    "name": format!("{}::{}", format_module_id(module_id), info.function_ident),
+                    "exec_time": info.elapsed_time.as_secs_f64(),
+                    "gas_used": info.gas_used,
+                });
+                if let Some(failure) = failure {
+                    let location = failure
+                        .source_location(&self.test_plan)
+                        .map(|(file, line)| json!({ "file": file, "line": line }));
+                    event["reason"] = json!(failure.failure_reason.kind());
+                    event["message"] = json!(failure.failure_reason.message());
+                    event["abort_code"] = json!(failure.abort_code());
+                    event["location"] = location.unwrap_or(Value::Null);
+                    event["stack_trace"] = json!(failure.stack_trace(&self.test_plan));
+                    event["details"] = json!(self.render_plain_error(failure));
+                }
+                events.push(event);
+            }
+        }
+        events.push(json!({
+            "type": "suite",
+            "event": if num_failures == 0 { "ok" } else { "failed" },
+            "passed": num_tests - num_failures,
+            "failed": num_failures,
+            "exec_time": total_time,
+        }));
+
+        for event in events {
+            writeln!(writer, "{}", event)?;
+        }
+        Ok(())
+    }
+
     /// Returns `true` if all tests passed, `false` if there was a test failure/timeout
     pub fn summarize<W: Write>(self, writer: &Mutex<W>) -> Result<bool> {
         let num_failed_tests = self
@@ -659,3 +924,91 @@ impl TestResults {
         Ok(num_failed_tests == 0)
     }
 }
+
+fn escape_xml(s: &str) -> String {
+    s.chars()
+        .filter(|c| !c.is_control() || matches!(c, '\n' | '\r' | '\t'))
+        .collect::<String>()
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use move_core_types::{account_address::AccountAddress, identifier::Identifier};
+
+    fn test_results() -> TestResults {
+        let module_plan = ModuleTestPlan {
+            module_id: ModuleId::new(AccountAddress::ONE, Identifier::new("m").unwrap()),
+            tests: BTreeMap::new(),
+        };
+        let mut stats = TestStatistics::new();
+        stats.test_success(
+            TestRunInfo {
+                function_ident: "passes".to_string(),
+                elapsed_time: Duration::from_millis(2),
+                gas_used: 10,
+            },
+            &module_plan,
+        );
+        stats.test_failure(
+            TestFailure::new(
+                FailureReason::timeout(),
+                TestRunInfo {
+                    function_ident: "loops".to_string(),
+                    elapsed_time: Duration::from_millis(5),
+                    gas_used: 1000,
+                },
+                None,
+                None,
+            ),
+            &module_plan,
+        );
+        let test_plan = TestPlan {
+            files: Default::default(),
+            module_tests: BTreeMap::new(),
+            module_info: BTreeMap::new(),
+        };
+        TestResults::new(stats, test_plan)
+    }
+
+    #[test]
+    fn test_report_junit() {
+        let mut output = vec![];
+        test_results().report_junit(&mut output).unwrap();
+        let output = String::from_utf8(output).unwrap();
+        assert!(output.contains(r#"<testsuites name="move-unit-tests" tests="2" failures="1""#));
+        assert!(output.contains(r#"<testsuite name="0x1::m" tests="2" failures="1""#));
+        assert!(output.contains(r#"<testcase name="loops" classname="0x1::m" time="0.005">"#));
+        assert!(output.contains(r#"<property name="gas_used" value="1000"/>"#));
+        assert!(output.contains(r#"<failure message="Test timed out" type="timeout">"#));
+        // Tests are sorted by name within a suite.
+        assert!(output.find("loops").unwrap() < output.find("passes").unwrap());
+    }
+
+    #[test]
+    fn test_report_json_events() {
+        let mut output = vec![];
+        test_results().report_json_events(&mut output).unwrap();
+        let events = String::from_utf8(output)
+            .unwrap()
+            .lines()
+            .map(|line| serde_json::from_str::<Value>(line).unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(events.len(), 4);
+        assert_eq!(events[0]["event"], "started");
+        assert_eq!(events[0]["test_count"], 2);
+        assert_eq!(events[1]["name"], "0x1::m::loops");
+        assert_eq!(events[1]["event"], "failed");
+        assert_eq!(events[1]["reason"], "timeout");
+        assert_eq!(events[1]["abort_code"], Value::Null);
+        assert_eq!(events[2]["name"], "0x1::m::passes");
+        assert_eq!(events[2]["event"], "ok");
+        assert_eq!(events[2]["gas_used"], 10);
+        assert_eq!(events[3]["event"], "failed");
+        assert_eq!(events[3]["passed"], 1);
+    }
+}
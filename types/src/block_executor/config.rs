@@ -44,6 +44,9 @@ pub struct BlockExecutorLocalConfig {
     // If true, we will discard the failed blocks and continue with the next block.
     // (allow_fallback needs to be set)
     pub discard_failed_blocks: bool,
+    // If true, parallel execution records per-block conflict diagnostics (hot keys, dependency
+    // chains, re-executions), see creditchain_block_executor::conflict_diagnostics.
+    pub enable_conflict_diagnostics: bool,
     pub module_cache_config: BlockExecutorModuleCacheLocalConfig,
 }
 
@@ -51,6 +54,7 @@ impl BlockExecutorLocalConfig {
     /// Returns a new config with specified concurrency level and:
     ///   - Allowed fallback to sequential execution from parallel.
     ///   - Not allowed discards of failed blocks.
+    ///   - Disabled conflict diagnostics.
     ///   - Default module cache configs.
     pub fn default_with_concurrency_level(concurrency_level: usize) -> Self {
         Self {
@@ -58,6 +62,7 @@ impl BlockExecutorLocalConfig {
             concurrency_level,
             allow_fallback: true,
             discard_failed_blocks: false,
+            enable_conflict_diagnostics: false,
             module_cache_config: BlockExecutorModuleCacheLocalConfig::default(),
         }
     }